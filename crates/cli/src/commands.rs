@@ -67,6 +67,14 @@ pub fn build_cli() -> Command {
                 .action(clap::ArgAction::SetTrue)
                 .global(true),
         )
+        .arg(
+            Arg::new("key-file")
+                .long("key-file")
+                .value_name("PATH")
+                .help("Encryption key file (32 raw bytes or 64 hex characters)")
+                .conflicts_with("cache")
+                .global(true),
+        )
         .subcommand(build_kv())
         .subcommand(build_json())
         .subcommand(build_event())
@@ -82,8 +90,14 @@ pub fn build_cli() -> Command {
         .subcommand(build_info())
         .subcommand(build_flush())
         .subcommand(build_compact())
+        .subcommand(build_backup())
         .subcommand(build_search())
         .subcommand(build_setup())
+        .subcommand(build_rekey())
+        .subcommand(build_restore())
+        .subcommand(build_fsck())
+        .subcommand(build_wal())
+        .subcommand(build_wal_archive())
         .subcommand(build_configure_model())
         .subcommand(build_embed())
         .subcommand(build_models())
@@ -112,6 +126,7 @@ pub fn build_repl_cmd() -> Command {
         .subcommand(build_info())
         .subcommand(build_flush())
         .subcommand(build_compact())
+        .subcommand(build_backup())
         .subcommand(build_search())
         .subcommand(build_configure_model())
         .subcommand(build_embed())
@@ -562,7 +577,12 @@ fn build_branch() -> Command {
         .subcommand(
             Command::new("list")
                 .about("List all branches")
-                .arg(Arg::new("limit").long("limit").help("Maximum branches")),
+                .arg(Arg::new("limit").long("limit").help("Maximum branches"))
+                .arg(
+                    Arg::new("status")
+                        .long("status")
+                        .help("Only list branches with this status: active, protected or frozen"),
+                ),
         )
         .subcommand(
             Command::new("exists")
@@ -574,6 +594,69 @@ fn build_branch() -> Command {
                 .about("Delete a branch")
                 .arg(Arg::new("name").required(true).help("Branch name")),
         )
+        .subcommand(
+            Command::new("protect")
+                .about("Protect a branch so it only changes through merges")
+                .arg(Arg::new("name").required(true).help("Branch name")),
+        )
+        .subcommand(
+            Command::new("freeze")
+                .about("Freeze a branch so it rejects all writes")
+                .arg(Arg::new("name").required(true).help("Branch name")),
+        )
+        .subcommand(
+            Command::new("unprotect")
+                .about("Make a protected or frozen branch writable again")
+                .arg(Arg::new("name").required(true).help("Branch name")),
+        )
+        .subcommand(
+            Command::new("retention")
+                .about("Branch retention policies")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("get")
+                        .about("Show a branch's retention policy and horizon")
+                        .arg(Arg::new("name").required(true).help("Branch name")),
+                )
+                .subcommand(
+                    Command::new("set")
+                        .about("Set how much version history a branch keeps")
+                        .arg(Arg::new("name").required(true).help("Branch name"))
+                        .arg(
+                            Arg::new("keep-last")
+                                .long("keep-last")
+                                .conflicts_with_all(["keep-for", "keep-all"])
+                                .help("Keep the last N versions of each key"),
+                        )
+                        .arg(
+                            Arg::new("keep-for")
+                                .long("keep-for")
+                                .conflicts_with("keep-all")
+                                .help("Keep versions newer than this many seconds"),
+                        )
+                        .arg(
+                            Arg::new("keep-all")
+                                .long("keep-all")
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Keep all versions (removes the policy)"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("stats")
+                        .about("Show the versions and bytes a branch holds")
+                        .arg(Arg::new("name").required(true).help("Branch name")),
+                )
+                .subcommand(
+                    Command::new("preview")
+                        .about("List the versions applying the policy would prune")
+                        .arg(Arg::new("name").required(true).help("Branch name")),
+                )
+                .subcommand(
+                    Command::new("apply")
+                        .about("Prune the versions the policy no longer keeps")
+                        .arg(Arg::new("name").required(true).help("Branch name")),
+                ),
+        )
         .subcommand(
             Command::new("fork")
                 .about("Fork current branch to a new branch")
@@ -581,13 +664,66 @@ fn build_branch() -> Command {
                     Arg::new("dest")
                         .required(true)
                         .help("Destination branch name"),
+                )
+                .arg(
+                    Arg::new("as-of")
+                        .long("as-of")
+                        .help("Fork the branch as of this timestamp (microseconds)"),
+                ),
+        )
+        .subcommand(
+            Command::new("revert")
+                .about("Restore current branch to its state at a past timestamp")
+                .arg(
+                    Arg::new("as_of")
+                        .required(true)
+                        .help("Timestamp to revert to (microseconds)"),
                 ),
         )
         .subcommand(
             Command::new("diff")
                 .about("Compare two branches")
                 .arg(Arg::new("a").required(true).help("Branch A"))
-                .arg(Arg::new("b").required(true).help("Branch B")),
+                .arg(Arg::new("b").required(true).help("Branch B"))
+                .arg(
+                    Arg::new("primitive")
+                        .long("primitive")
+                        .action(clap::ArgAction::Append)
+                        .help("Only compare this primitive: kv, json, event, state or vector (repeatable)"),
+                )
+                .arg(
+                    Arg::new("space")
+                        .long("space")
+                        .action(clap::ArgAction::Append)
+                        .help("Only compare this space (repeatable)"),
+                )
+                .arg(
+                    Arg::new("prefix")
+                        .long("prefix")
+                        .help("Only compare keys with this prefix"),
+                )
+                .arg(Arg::new("limit").long("limit").help("Maximum entries"))
+                .arg(
+                    Arg::new("cursor")
+                        .long("cursor")
+                        .help("Resume after a previous page"),
+                )
+                .arg(
+                    Arg::new("summary")
+                        .long("summary")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Only show counts"),
+                )
+                .arg(
+                    Arg::new("as-of-a")
+                        .long("as-of-a")
+                        .help("Compare branch A as of this timestamp (microseconds)"),
+                )
+                .arg(
+                    Arg::new("as-of-b")
+                        .long("as-of-b")
+                        .help("Compare branch B as of this timestamp (microseconds)"),
+                ),
         )
         .subcommand(
             Command::new("merge")
@@ -597,9 +733,71 @@ fn build_branch() -> Command {
                     Arg::new("strategy")
                         .long("strategy")
                         .default_value("lww")
-                        .help("Merge strategy: lww or strict"),
+                        .help("Merge strategy: lww, strict or manual"),
+                )
+                .args(primitive_merge_args()),
+        )
+        .subcommand(
+            Command::new("log")
+                .about("List commits on the current branch, newest first")
+                .arg(
+                    Arg::new("limit")
+                        .long("limit")
+                        .help("Maximum number of commits to show"),
+                )
+                .arg(
+                    Arg::new("before")
+                        .long("before")
+                        .help("Only show commits older than this version"),
                 ),
         )
+        .subcommand(
+            Command::new("cherry-pick")
+                .about("Apply commits from source branch onto current branch")
+                .arg(Arg::new("source").required(true).help("Source branch"))
+                .arg(
+                    Arg::new("commits")
+                        .required(true)
+                        .num_args(1..)
+                        .help("Commit versions to apply (from branch log)"),
+                )
+                .arg(
+                    Arg::new("strategy")
+                        .long("strategy")
+                        .default_value("lww")
+                        .help("Conflict strategy: lww, strict or manual"),
+                )
+                .args(primitive_merge_args()),
+        )
+        .subcommand(
+            Command::new("resolve")
+                .about("Merge source branch into current branch, resolving conflicts")
+                .arg(Arg::new("source").required(true).help("Source branch"))
+                .arg(
+                    Arg::new("take")
+                        .long("take")
+                        .help("Resolve remaining conflicts: source or target"),
+                )
+                .arg(
+                    Arg::new("pick-source")
+                        .long("pick-source")
+                        .action(clap::ArgAction::Append)
+                        .help("Keep the source value for KEY (repeatable)"),
+                )
+                .arg(
+                    Arg::new("pick-target")
+                        .long("pick-target")
+                        .action(clap::ArgAction::Append)
+                        .help("Keep the target value for KEY (repeatable)"),
+                )
+                .arg(
+                    Arg::new("set")
+                        .long("set")
+                        .action(clap::ArgAction::Append)
+                        .help("Resolve KEY with a custom value, as KEY=VALUE (repeatable)"),
+                )
+                .args(primitive_merge_args()),
+        )
         .subcommand(
             Command::new("export")
                 .about("Export a branch to a bundle file")
@@ -618,6 +816,24 @@ fn build_branch() -> Command {
         )
 }
 
+/// Per-primitive merge flags shared by `branch merge` and `branch resolve`.
+fn primitive_merge_args() -> [Arg; 3] {
+    [
+        Arg::new("json-merge")
+            .long("json-merge")
+            .default_value("document")
+            .help("JSON merge: document or path"),
+        Arg::new("event-merge")
+            .long("event-merge")
+            .default_value("key")
+            .help("Event merge: key or append"),
+        Arg::new("state-merge")
+            .long("state-merge")
+            .default_value("value")
+            .help("State merge: value or version"),
+    ]
+}
+
 // =========================================================================
 // Space
 // =========================================================================
@@ -655,12 +871,32 @@ fn build_space() -> Command {
 // =========================================================================
 
 fn build_txn_begin() -> Command {
-    Command::new("begin").about("Begin a new transaction").arg(
-        Arg::new("txn-read-only")
-            .long("read-only")
-            .action(clap::ArgAction::SetTrue)
-            .help("Start a read-only transaction"),
-    )
+    Command::new("begin")
+        .about("Begin a new transaction")
+        .arg(
+            Arg::new("txn-read-only")
+                .long("read-only")
+                .action(clap::ArgAction::SetTrue)
+                .help("Start a read-only transaction"),
+        )
+        .arg(
+            Arg::new("serializable")
+                .long("serializable")
+                .action(clap::ArgAction::SetTrue)
+                .help("Abort on commit if a concurrent write inserted into a listed prefix"),
+        )
+        .arg(
+            Arg::new("message")
+                .long("message")
+                .short('m')
+                .help("Commit message shown by branch log"),
+        )
+        .arg(
+            Arg::new("meta")
+                .long("meta")
+                .action(clap::ArgAction::Append)
+                .help("Commit metadata as KEY=VALUE (repeatable)"),
+        )
 }
 
 fn build_txn_commit() -> Command {
@@ -673,10 +909,25 @@ fn build_txn_rollback() -> Command {
 
 fn build_txn() -> Command {
     Command::new("txn")
-        .about("Transaction info")
+        .about("Transaction info and savepoints")
         .subcommand_required(true)
         .subcommand(Command::new("info").about("Get current transaction info"))
         .subcommand(Command::new("active").about("Check if a transaction is active"))
+        .subcommand(
+            Command::new("savepoint")
+                .about("Mark a savepoint in the current transaction")
+                .arg(Arg::new("name").required(true).help("Savepoint name")),
+        )
+        .subcommand(
+            Command::new("rollback-to")
+                .about("Undo everything since a savepoint, keeping the savepoint")
+                .arg(Arg::new("name").required(true).help("Savepoint name")),
+        )
+        .subcommand(
+            Command::new("release")
+                .about("Release a savepoint, keeping its writes")
+                .arg(Arg::new("name").required(true).help("Savepoint name")),
+        )
 }
 
 // =========================================================================
@@ -699,6 +950,16 @@ fn build_compact() -> Command {
     Command::new("compact").about("Trigger compaction")
 }
 
+fn build_backup() -> Command {
+    Command::new("backup")
+        .about("Copy the database to a new directory while writes continue")
+        .arg(
+            Arg::new("path")
+                .required(true)
+                .help("Destination directory (must not exist or be empty)"),
+        )
+}
+
 // =========================================================================
 // Search
 // =========================================================================
@@ -748,6 +1009,129 @@ fn build_setup() -> Command {
     Command::new("setup").about("Download model files for auto-embedding")
 }
 
+// =========================================================================
+// Rekey
+// =========================================================================
+
+fn build_rekey() -> Command {
+    Command::new("rekey")
+        .about("Rewrite a closed database under a new encryption key")
+        .arg(
+            Arg::new("new-key-file")
+                .long("new-key-file")
+                .value_name("PATH")
+                .help("Key file to encrypt with")
+                .required_unless_present("decrypt"),
+        )
+        .arg(
+            Arg::new("decrypt")
+                .long("decrypt")
+                .help("Remove encryption instead of setting a new key")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("new-key-file"),
+        )
+}
+
+// =========================================================================
+// Restore
+// =========================================================================
+
+fn build_restore() -> Command {
+    Command::new("restore")
+        .about("Restore a backup into a new database directory (--db)")
+        .arg(
+            Arg::new("backup")
+                .required(true)
+                .help("Backup directory created by `backup`"),
+        )
+        .arg(
+            Arg::new("to")
+                .long("to")
+                .value_name("TIMESTAMP")
+                .help("Restore the state as of this timestamp (microseconds)"),
+        )
+        .arg(
+            Arg::new("wal-archive")
+                .long("wal-archive")
+                .value_name("DIR")
+                .help("Also replay WAL segments archived after the backup"),
+        )
+}
+
+// =========================================================================
+// Fsck
+// =========================================================================
+
+fn build_fsck() -> Command {
+    Command::new("fsck")
+        .about("Check the integrity of a closed database")
+        .arg(
+            Arg::new("repair")
+                .long("repair")
+                .help("Truncate torn WAL tails and remove orphaned or broken caches")
+                .action(clap::ArgAction::SetTrue),
+        )
+}
+
+// =========================================================================
+// WAL
+// =========================================================================
+
+fn build_wal() -> Command {
+    Command::new("wal")
+        .about("Inspect the write-ahead log without opening the database")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("dump")
+                .about("Print each WAL record and its decoded writes (--branch filters by branch)")
+                .arg(
+                    Arg::new("min-txn")
+                        .long("min-txn")
+                        .value_name("TXN_ID")
+                        .help("Only records with a transaction id at or above this"),
+                )
+                .arg(
+                    Arg::new("max-txn")
+                        .long("max-txn")
+                        .value_name("TXN_ID")
+                        .help("Only records with a transaction id at or below this"),
+                )
+                .arg(Arg::new("since").long("since").value_name("MICROS").help(
+                    "Only records committed at or after this time (microseconds since epoch)",
+                ))
+                .arg(Arg::new("until").long("until").value_name("MICROS").help(
+                    "Only records committed at or before this time (microseconds since epoch)",
+                )),
+        )
+}
+
+// =========================================================================
+// WAL Archive
+// =========================================================================
+
+fn build_wal_archive() -> Command {
+    Command::new("wal-archive")
+        .about("Inspect or prune a WAL archive directory")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("verify")
+                .about("Check every archived segment against its CRC")
+                .arg(Arg::new("dir").required(true).help("WAL archive directory")),
+        )
+        .subcommand(
+            Command::new("prune")
+                .about("Remove archived segments older than a given age")
+                .arg(Arg::new("dir").required(true).help("WAL archive directory"))
+                .arg(
+                    Arg::new("older-than")
+                        .long("older-than")
+                        .value_name("SECONDS")
+                        .required(true)
+                        .help("Remove segments whose newest commit is older than this"),
+                ),
+        )
+}
+
 // =========================================================================
 // Configure Model
 // =========================================================================
@@ -6,7 +6,10 @@
 //! - **Raw** (`--raw`): Bare values, no quotes, no type prefixes
 
 use strata_executor::{
-    BranchDiffResult, Error, ForkInfo, MergeInfo, Output, Value, VersionedValue,
+    ArchivePruneInfo, ArchiveVerifyReport, BranchDiffResult, BranchRetention, BranchStatus,
+    CherryPickInfo, CommitInfo, ConflictEntry, Error, ForkInfo, FsckReport, MergeInfo, Output,
+    PrimitiveRetention, RekeyInfo, RestoreInfo, RetentionApplyInfo, RetentionHorizon,
+    RetentionPreview, RetentionStats, RevertInfo, SpaceDiff, Value, VersionedValue, WalDumpRecord,
 };
 
 /// Output formatting mode.
@@ -102,16 +105,413 @@ pub fn format_fork_info(info: &ForkInfo, mode: OutputMode) -> String {
             "destination": info.destination,
             "keys_copied": info.keys_copied,
             "spaces_copied": info.spaces_copied,
+            "fork_version": info.fork_version,
         }))
         .unwrap(),
         OutputMode::Raw => format!("{}", info.keys_copied),
         OutputMode::Human => format!(
-            "Forked \"{}\" -> \"{}\" ({} keys, {} spaces)",
-            info.source, info.destination, info.keys_copied, info.spaces_copied
+            "Forked \"{}\" -> \"{}\" at v{} ({} spaces)",
+            info.source, info.destination, info.fork_version, info.spaces_copied
         ),
     }
 }
 
+/// Format branch revert result.
+pub fn format_revert_info(info: &RevertInfo, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "branch": info.branch,
+            "as_of": info.as_of,
+            "keys_restored": info.keys_restored,
+            "keys_deleted": info.keys_deleted,
+        }))
+        .unwrap(),
+        OutputMode::Raw => format!("{}\t{}", info.keys_restored, info.keys_deleted),
+        OutputMode::Human => format!(
+            "Reverted \"{}\" to {} ({} keys restored, {} deleted)",
+            info.branch, info.as_of, info.keys_restored, info.keys_deleted
+        ),
+    }
+}
+
+/// Format the result of an offline rekey.
+pub fn format_rekey_info(info: &RekeyInfo, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "old_codec": info.old_codec,
+            "new_codec": info.new_codec,
+            "wal_segments": info.wal_segments,
+            "wal_records": info.wal_records,
+            "snapshots": info.snapshots,
+        }))
+        .unwrap(),
+        OutputMode::Raw => info.new_codec.clone(),
+        OutputMode::Human => format!(
+            "Rekeyed {} -> {} ({} WAL segments, {} records, {} snapshots)",
+            info.old_codec, info.new_codec, info.wal_segments, info.wal_records, info.snapshots
+        ),
+    }
+}
+
+/// Format the result of an offline restore.
+pub fn format_restore_info(info: &RestoreInfo, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "path": info.path,
+            "target_timestamp": info.target_timestamp,
+            "version": info.version,
+            "txns_replayed": info.txns_replayed,
+            "txns_dropped": info.txns_dropped,
+        }))
+        .unwrap(),
+        OutputMode::Raw => info.version.to_string(),
+        OutputMode::Human => format!(
+            "Restored to {} (v{}, {} transactions replayed, {} dropped)",
+            info.path.display(),
+            info.version,
+            info.txns_replayed,
+            info.txns_dropped
+        ),
+    }
+}
+
+/// Format the result of an offline integrity check.
+pub fn format_fsck_report(report: &FsckReport, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "path": report.path,
+            "wal_segments": report.wal_segments,
+            "wal_records": report.wal_records,
+            "snapshots": report.snapshots,
+            "branches": report.branches,
+            "event_logs": report.event_logs,
+            "events": report.events,
+            "findings": report
+                .findings
+                .iter()
+                .map(|f| serde_json::json!({
+                    "severity": f.severity.as_str(),
+                    "component": f.component.as_str(),
+                    "path": f.path,
+                    "message": f.message,
+                    "repaired": f.repaired,
+                }))
+                .collect::<Vec<_>>(),
+        }))
+        .unwrap(),
+        OutputMode::Raw => report
+            .findings
+            .iter()
+            .map(|f| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}",
+                    f.severity.as_str(),
+                    f.component.as_str(),
+                    f.path
+                        .as_ref()
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_default(),
+                    f.message,
+                    f.repaired
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputMode::Human => {
+            let mut lines = vec![format!(
+                "Checked {} WAL segments ({} records), {} snapshots, {} events in {} logs",
+                report.wal_segments,
+                report.wal_records,
+                report.snapshots,
+                report.events,
+                report.event_logs
+            )];
+            for f in &report.findings {
+                let mut line = format!("{} [{}]", f.severity.as_str(), f.component.as_str());
+                if let Some(path) = &f.path {
+                    line.push_str(&format!(" {}", path.display()));
+                }
+                line.push_str(&format!(": {}", f.message));
+                if f.repaired {
+                    line.push_str(" (repaired)");
+                }
+                lines.push(line);
+            }
+            if report.is_clean() {
+                lines.push("No problems found".to_string());
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+/// Format the result of verifying a WAL archive.
+pub fn format_wal_archive_verify(report: &ArchiveVerifyReport, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "verified": report.verified,
+            "corrupt": report
+                .corrupt
+                .iter()
+                .map(|(segment, detail)| serde_json::json!({
+                    "segment": segment,
+                    "detail": detail,
+                }))
+                .collect::<Vec<_>>(),
+            "gaps": report.gaps,
+        }))
+        .unwrap(),
+        OutputMode::Raw => format!(
+            "{}\t{}\t{}",
+            report.verified.len(),
+            report.corrupt.len(),
+            report.gaps.len()
+        ),
+        OutputMode::Human => {
+            let mut lines = vec![format!("{} segments verified", report.verified.len())];
+            for (segment, detail) in &report.corrupt {
+                lines.push(format!("segment {}: {}", segment, detail));
+            }
+            if !report.gaps.is_empty() {
+                let gaps: Vec<String> = report.gaps.iter().map(|s| s.to_string()).collect();
+                lines.push(format!("missing segments: {}", gaps.join(", ")));
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+/// Format the result of pruning a WAL archive.
+pub fn format_wal_archive_prune(info: &ArchivePruneInfo, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "segments_removed": info.segments_removed,
+            "bytes_reclaimed": info.bytes_reclaimed,
+        }))
+        .unwrap(),
+        OutputMode::Raw => info.segments_removed.to_string(),
+        OutputMode::Human => format!(
+            "Pruned {} archived segments ({} bytes)",
+            info.segments_removed, info.bytes_reclaimed
+        ),
+    }
+}
+
+/// Format the records of a WAL dump, one transaction at a time.
+pub fn format_wal_dump(records: &[WalDumpRecord], mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(
+            &records
+                .iter()
+                .map(|r| {
+                    serde_json::json!({
+                        "segment": r.segment,
+                        "txn_id": r.txn_id,
+                        "branch_id": r.branch_id.to_string(),
+                        "timestamp": r.timestamp,
+                        "version": r.version,
+                        "message": r.message,
+                        "writes": r
+                            .writes
+                            .iter()
+                            .map(|w| serde_json::json!({
+                                "op": if w.is_delete() { "delete" } else { "put" },
+                                "primitive": w.primitive,
+                                "space": w.space,
+                                "key": w.key,
+                                "value": w.value.clone().map(serde_json::Value::from),
+                            }))
+                            .collect::<Vec<_>>(),
+                        "error": r.error,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )
+        .unwrap(),
+        OutputMode::Raw => records
+            .iter()
+            .flat_map(|r| {
+                r.writes.iter().map(move |w| {
+                    format!(
+                        "{}\t{}\t{}\t{}\t{}/{}/{}\t{}",
+                        r.txn_id,
+                        r.branch_id,
+                        r.timestamp,
+                        if w.is_delete() { "delete" } else { "put" },
+                        w.primitive,
+                        w.space,
+                        w.key,
+                        format_value_inline(&w.value)
+                    )
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputMode::Human => {
+            if records.is_empty() {
+                return "(empty list)".to_string();
+            }
+            let mut lines = Vec::new();
+            for r in records {
+                let mut header = format!(
+                    "txn {}  branch {}  {}  segment {}",
+                    r.txn_id, r.branch_id, r.timestamp, r.segment
+                );
+                if let Some(version) = r.version {
+                    header.push_str(&format!("  v{}", version));
+                }
+                if let Some(message) = &r.message {
+                    header.push_str(&format!("  {}", message));
+                }
+                lines.push(header);
+                if let Some(error) = &r.error {
+                    lines.push(format!("    error: {}", error));
+                }
+                for w in &r.writes {
+                    let key = format!("{}/{}/{}", w.primitive, w.space, w.key);
+                    match &w.value {
+                        Some(_) => lines.push(format!(
+                            "    put {} = {}",
+                            key,
+                            format_value_inline(&w.value)
+                        )),
+                        None => lines.push(format!("    delete {}", key)),
+                    }
+                }
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+/// Format the result of changing a branch's status.
+pub fn format_branch_status(name: &str, status: BranchStatus, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "branch": name,
+            "status": status,
+        }))
+        .unwrap(),
+        OutputMode::Raw => format!("{:?}", status).to_lowercase(),
+        OutputMode::Human => match status {
+            BranchStatus::Active => format!("Branch \"{}\" is writable", name),
+            BranchStatus::Protected => {
+                format!("Branch \"{}\" is protected (merges only)", name)
+            }
+            BranchStatus::Frozen => format!("Branch \"{}\" is frozen", name),
+        },
+    }
+}
+
+/// Format a branch's retention policy and horizon.
+pub fn format_branch_retention(info: &BranchRetention, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(info).unwrap(),
+        OutputMode::Raw => info.policy.summary(),
+        OutputMode::Human => format!(
+            "Branch \"{}\": {}{}",
+            info.branch,
+            info.policy.summary(),
+            format_horizon(info.horizon.as_ref())
+        ),
+    }
+}
+
+/// Format the version history a branch holds.
+pub fn format_retention_stats(stats: &RetentionStats, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(stats).unwrap(),
+        OutputMode::Raw => format!("{}\t{}", stats.versions(), stats.bytes()),
+        OutputMode::Human => {
+            let mut lines = vec![format!(
+                "Branch \"{}\": {} versions, {} bytes ({}{})",
+                stats.branch,
+                stats.versions(),
+                stats.bytes(),
+                stats.policy.summary(),
+                format_horizon(stats.horizon.as_ref())
+            )];
+            lines.extend(format_primitive_retention(&stats.primitives));
+            lines.join("\n")
+        }
+    }
+}
+
+/// Format the versions applying a retention policy would prune.
+pub fn format_retention_preview(preview: &RetentionPreview, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(preview).unwrap(),
+        OutputMode::Raw => preview
+            .versions
+            .iter()
+            .map(|v| format!("{}\t{}\t{}\t{}", v.primitive, v.space, v.key, v.version))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputMode::Human => {
+            if preview.versions.is_empty() {
+                return format!(
+                    "Branch \"{}\": nothing to prune ({})",
+                    preview.branch,
+                    preview.policy.summary()
+                );
+            }
+            let mut lines = vec![format!(
+                "Branch \"{}\": {} versions to prune ({}{})",
+                preview.branch,
+                preview.versions.len(),
+                preview.policy.summary(),
+                format_horizon(preview.horizon.as_ref())
+            )];
+            lines.extend(format_primitive_retention(&preview.primitives));
+            for v in &preview.versions {
+                lines.push(format!(
+                    "  {} {}/{} v{}{}",
+                    v.primitive,
+                    v.space,
+                    v.key,
+                    v.version,
+                    if v.tombstone { " (deleted)" } else { "" }
+                ));
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+/// Format the result of applying a retention policy.
+pub fn format_retention_apply(info: &RetentionApplyInfo, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(info).unwrap(),
+        OutputMode::Raw => format!("{}\t{}", info.versions_pruned, info.bytes_freed),
+        OutputMode::Human => format!(
+            "Pruned {} versions from \"{}\" ({} bytes){}",
+            info.versions_pruned,
+            info.branch,
+            info.bytes_freed,
+            format_horizon(info.horizon.as_ref())
+        ),
+    }
+}
+
+fn format_horizon(horizon: Option<&RetentionHorizon>) -> String {
+    horizon.map_or(String::new(), |h| {
+        format!(", history from v{} at {}", h.version, h.timestamp)
+    })
+}
+
+fn format_primitive_retention(primitives: &[PrimitiveRetention]) -> Vec<String> {
+    primitives
+        .iter()
+        .filter(|p| p.versions > 0)
+        .map(|p| {
+            format!(
+                "  {}: {} keys, {} versions, {} bytes",
+                p.primitive, p.keys, p.versions, p.bytes
+            )
+        })
+        .collect()
+}
+
 /// Format branch diff result.
 pub fn format_diff(diff: &BranchDiffResult, mode: OutputMode) -> String {
     match mode {
@@ -128,7 +528,9 @@ pub fn format_diff(diff: &BranchDiffResult, mode: OutputMode) -> String {
                 "added": sd.added.len(),
                 "removed": sd.removed.len(),
                 "modified": sd.modified.len(),
+                "entries": diff_entries_json(sd),
             })).collect::<Vec<_>>(),
+            "next_cursor": diff.next_cursor,
         }))
         .unwrap(),
         OutputMode::Raw => format!(
@@ -149,21 +551,239 @@ pub fn format_diff(diff: &BranchDiffResult, mode: OutputMode) -> String {
                 if !sd.added.is_empty() || !sd.removed.is_empty() || !sd.modified.is_empty() {
                     lines.push(format!("  Space \"{}\":", sd.space));
                     for entry in &sd.added {
-                        lines.push(format!("    + {} ({})", entry.key, entry.primitive));
+                        lines.push(format!(
+                            "    + {} ({}): {}",
+                            entry.key,
+                            entry.primitive,
+                            format_value_inline(&entry.value_b)
+                        ));
                     }
                     for entry in &sd.removed {
-                        lines.push(format!("    - {} ({})", entry.key, entry.primitive));
+                        lines.push(format!(
+                            "    - {} ({}): {}",
+                            entry.key,
+                            entry.primitive,
+                            format_value_inline(&entry.value_a)
+                        ));
                     }
                     for entry in &sd.modified {
-                        lines.push(format!("    ~ {} ({})", entry.key, entry.primitive));
+                        lines.push(format!(
+                            "    ~ {} ({}): {} -> {}",
+                            entry.key,
+                            entry.primitive,
+                            format_value_inline(&entry.value_a),
+                            format_value_inline(&entry.value_b)
+                        ));
                     }
                 }
             }
+            if let Some(cursor) = &diff.next_cursor {
+                lines.push(format!("  (more: --cursor {})", cursor));
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+/// JSON entries of a space diff, tagged with their change kind.
+fn diff_entries_json(sd: &SpaceDiff) -> Vec<serde_json::Value> {
+    [
+        ("added", &sd.added),
+        ("removed", &sd.removed),
+        ("modified", &sd.modified),
+    ]
+    .into_iter()
+    .flat_map(|(change, entries)| entries.iter().map(move |e| (change, e)))
+    .map(|(change, e)| {
+        let mut entry = serde_json::json!({
+            "change": change,
+            "key": e.key,
+            "primitive": e.primitive.to_string(),
+            "value_a": e.value_a.clone().map(serde_json::Value::from),
+            "value_b": e.value_b.clone().map(serde_json::Value::from),
+        });
+        if !e.patches.is_empty() {
+            entry["patches"] = serde_json::to_value(&e.patches).unwrap_or_default();
+        }
+        entry
+    })
+    .collect()
+}
+
+/// Format an optional value on one line, as JSON.
+fn format_value_inline(v: &Option<Value>) -> String {
+    match v {
+        Some(v) => serde_json::Value::from(v.clone()).to_string(),
+        None => "(deleted)".to_string(),
+    }
+}
+
+/// Format the conflicts reported by a manual merge that was not applied.
+pub fn format_merge_conflicts(info: &MergeInfo, mode: OutputMode) -> String {
+    format_conflicts(
+        &format!("Merge \"{}\" -> \"{}\"", info.source, info.target),
+        &info.source,
+        &info.target,
+        &info.conflicts,
+        &format!(
+            "Resolve with: branch resolve {} --take source|target, --pick-source KEY, --pick-target KEY or --set KEY=VALUE",
+            info.source
+        ),
+        mode,
+    )
+}
+
+/// Format the conflicts reported by a manual cherry-pick that was not applied.
+pub fn format_cherry_pick_conflicts(info: &CherryPickInfo, mode: OutputMode) -> String {
+    format_conflicts(
+        &format!("Cherry-pick \"{}\" -> \"{}\"", info.source, info.target),
+        &info.source,
+        &info.target,
+        &info.conflicts,
+        "Apply the commits anyway with --strategy lww",
+        mode,
+    )
+}
+
+fn format_conflicts(
+    operation: &str,
+    source: &str,
+    target: &str,
+    conflicts: &[ConflictEntry],
+    hint: &str,
+    mode: OutputMode,
+) -> String {
+    let value_json = |v: &Option<Value>| v.clone().map(serde_json::Value::from);
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "source": source,
+            "target": target,
+            "conflicts": conflicts.iter().map(|c| serde_json::json!({
+                "space": c.space,
+                "primitive": c.primitive.to_string(),
+                "key": c.key,
+                "source_value": value_json(&c.source_value),
+                "target_value": value_json(&c.target_value),
+            })).collect::<Vec<_>>(),
+        }))
+        .unwrap(),
+        OutputMode::Raw => conflicts
+            .iter()
+            .map(|c| c.key.clone())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputMode::Human => {
+            let mut lines = vec![format!(
+                "{} not applied: {} conflicts",
+                operation,
+                conflicts.len()
+            )];
+            for c in conflicts {
+                lines.push(format!(
+                    "  {} ({}, space \"{}\"): source {}, target {}",
+                    c.key,
+                    c.primitive,
+                    c.space,
+                    format_value_inline(&c.source_value),
+                    format_value_inline(&c.target_value)
+                ));
+            }
+            lines.push(hint.to_string());
             lines.join("\n")
         }
     }
 }
 
+/// Format the commits of a branch, newest first.
+pub fn format_commit_log(commits: &[CommitInfo], mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&commits).unwrap(),
+        OutputMode::Raw => commits
+            .iter()
+            .map(|c| format!("{}\t{}", c.version, c.timestamp))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputMode::Human => {
+            if commits.is_empty() {
+                return "(empty list)".to_string();
+            }
+            let mut lines = Vec::new();
+            for c in commits {
+                lines.push(format!(
+                    "v{}  {}  {}",
+                    c.version,
+                    c.timestamp,
+                    c.message.as_deref().unwrap_or("(no message)")
+                ));
+                for (key, value) in &c.metadata {
+                    lines.push(format!("    {}: {}", key, value));
+                }
+                for t in &c.touched {
+                    let more = (t.keys_written + t.keys_deleted) as usize - t.keys.len();
+                    let more_note = if more == 0 {
+                        String::new()
+                    } else {
+                        format!(" (+{} more)", more)
+                    };
+                    lines.push(format!(
+                        "    {}: {} written, {} deleted: {}{}",
+                        t.primitive,
+                        t.keys_written,
+                        t.keys_deleted,
+                        t.keys.join(", "),
+                        more_note
+                    ));
+                }
+            }
+            lines.join("\n")
+        }
+    }
+}
+
+/// Format cherry-pick info.
+pub fn format_cherry_pick_info(info: &CherryPickInfo, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "source": info.source,
+            "target": info.target,
+            "commits": info.commits,
+            "keys_applied": info.keys_applied,
+            "keys_deleted": info.keys_deleted,
+            "events_appended": info.events_appended,
+            "conflicts": info.conflicts.len(),
+        }))
+        .unwrap(),
+        OutputMode::Raw => format!("{}", info.keys_applied),
+        OutputMode::Human => {
+            let delete_note = if info.keys_deleted == 0 {
+                String::new()
+            } else {
+                format!(", {} deleted", info.keys_deleted)
+            };
+            let event_note = if info.events_appended == 0 {
+                String::new()
+            } else {
+                format!(", {} events appended", info.events_appended)
+            };
+            let conflict_note = if info.conflicts.is_empty() {
+                String::new()
+            } else {
+                format!(", {} conflicts resolved", info.conflicts.len())
+            };
+            format!(
+                "Cherry-picked {} commits \"{}\" -> \"{}\" ({} keys{}{}{})",
+                info.commits.len(),
+                info.source,
+                info.target,
+                info.keys_applied,
+                delete_note,
+                event_note,
+                conflict_note
+            )
+        }
+    }
+}
+
 /// Format merge info.
 pub fn format_merge_info(info: &MergeInfo, mode: OutputMode) -> String {
     match mode {
@@ -171,20 +791,38 @@ pub fn format_merge_info(info: &MergeInfo, mode: OutputMode) -> String {
             "source": info.source,
             "target": info.target,
             "keys_applied": info.keys_applied,
+            "keys_deleted": info.keys_deleted,
+            "events_appended": info.events_appended,
             "conflicts": info.conflicts.len(),
             "spaces_merged": info.spaces_merged,
         }))
         .unwrap(),
         OutputMode::Raw => format!("{}", info.keys_applied),
         OutputMode::Human => {
+            let delete_note = if info.keys_deleted == 0 {
+                String::new()
+            } else {
+                format!(", {} deleted", info.keys_deleted)
+            };
+            let event_note = if info.events_appended == 0 {
+                String::new()
+            } else {
+                format!(", {} events appended", info.events_appended)
+            };
             let conflict_note = if info.conflicts.is_empty() {
                 String::new()
             } else {
                 format!(", {} conflicts resolved", info.conflicts.len())
             };
             format!(
-                "Merged \"{}\" -> \"{}\" ({} keys, {} spaces{})",
-                info.source, info.target, info.keys_applied, info.spaces_merged, conflict_note
+                "Merged \"{}\" -> \"{}\" ({} keys{}{}, {} spaces{})",
+                info.source,
+                info.target,
+                info.keys_applied,
+                delete_note,
+                event_note,
+                info.spaces_merged,
+                conflict_note
             )
         }
     }
@@ -279,6 +917,17 @@ fn format_raw(output: &Output) -> String {
         Output::BranchMerged(info) => {
             format!("{}\t{}\t{}", info.source, info.target, info.keys_applied)
         }
+        Output::BranchLog(commits) => format_commit_log(commits, OutputMode::Raw),
+        Output::BranchCherryPicked(info) => {
+            format!("{}\t{}\t{}", info.source, info.target, info.keys_applied)
+        }
+        Output::BranchReverted(info) => {
+            format!(
+                "{}\t{}\t{}",
+                info.branch, info.keys_restored, info.keys_deleted
+            )
+        }
+        Output::BranchRetention(info) => format_branch_retention(info, OutputMode::Raw),
         Output::Config(_) | Output::DurabilityCounters(_) => {
             serde_json::to_string(output).unwrap_or_default()
         }
@@ -287,6 +936,9 @@ fn format_raw(output: &Output) -> String {
         Output::TxnBegun => "OK".to_string(),
         Output::TxnCommitted { version } => version.to_string(),
         Output::TxnAborted => "OK".to_string(),
+        Output::RetentionResult(info) => format_retention_apply(info, OutputMode::Raw),
+        Output::RetentionStats(stats) => format_retention_stats(stats, OutputMode::Raw),
+        Output::RetentionPreview(preview) => format_retention_preview(preview, OutputMode::Raw),
         Output::DatabaseInfo(info) => {
             format!(
                 "{}\t{}\t{}\t{}",
@@ -294,6 +946,7 @@ fn format_raw(output: &Output) -> String {
             )
         }
         Output::Pong { version } => version.clone(),
+        Output::BackupCreated(r) => format!("{}\t{}", r.path, r.version),
         Output::SearchResults(hits) => hits
             .iter()
             .map(|h| format!("{}\t{}\t{}", h.entity, h.primitive, h.score))
@@ -553,8 +1206,8 @@ fn format_human(output: &Output) -> String {
         }
         Output::BranchForked(info) => {
             format!(
-                "Forked \"{}\" → \"{}\" ({} keys, {} spaces)",
-                info.source, info.destination, info.keys_copied, info.spaces_copied
+                "Forked \"{}\" → \"{}\" at v{} ({} spaces)",
+                info.source, info.destination, info.fork_version, info.spaces_copied
             )
         }
         Output::BranchDiff(result) => {
@@ -580,26 +1233,53 @@ fn format_human(output: &Output) -> String {
             lines.join("\n")
         }
         Output::BranchMerged(info) => {
+            let delete_msg = if info.keys_deleted == 0 {
+                String::new()
+            } else {
+                format!(", {} deleted", info.keys_deleted)
+            };
+            let event_msg = if info.events_appended == 0 {
+                String::new()
+            } else {
+                format!(", {} events appended", info.events_appended)
+            };
             let conflict_msg = if info.conflicts.is_empty() {
                 String::new()
             } else {
                 format!(", {} conflicts", info.conflicts.len())
             };
             format!(
-                "Merged \"{}\" → \"{}\" ({} keys applied, {} spaces{})",
-                info.source, info.target, info.keys_applied, info.spaces_merged, conflict_msg
+                "Merged \"{}\" → \"{}\" ({} keys applied{}{}, {} spaces{})",
+                info.source,
+                info.target,
+                info.keys_applied,
+                delete_msg,
+                event_msg,
+                info.spaces_merged,
+                conflict_msg
             )
         }
+        Output::BranchReverted(info) => format_revert_info(info, OutputMode::Human),
+        Output::BranchLog(commits) => format_commit_log(commits, OutputMode::Human),
+        Output::BranchCherryPicked(info) => format_cherry_pick_info(info, OutputMode::Human),
+        Output::BranchRetention(info) => format_branch_retention(info, OutputMode::Human),
         Output::Config(cfg) => {
             serde_json::to_string_pretty(cfg).unwrap_or_else(|_| format!("{:?}", cfg))
         }
         Output::DurabilityCounters(counters) => {
             format!(
-                "wal_appends: {}\nsync_calls: {}\nbytes_written: {}\nsync_nanos: {}",
+                "wal_appends: {}\nsync_calls: {}\nbytes_written: {}\nsync_nanos: {}\n\
+                 checkpoints: {}\ncompactions: {}\nwal_segments_removed: {}\n\
+                 wal_bytes_reclaimed: {}\nwal_segments_archived: {}",
                 counters.wal_appends,
                 counters.sync_calls,
                 counters.bytes_written,
-                counters.sync_nanos
+                counters.sync_nanos,
+                counters.checkpoints,
+                counters.compactions,
+                counters.wal_segments_removed,
+                counters.wal_bytes_reclaimed,
+                counters.wal_segments_archived
             )
         }
         Output::TxnInfo(None) => "(nil)".to_string(),
@@ -612,6 +1292,9 @@ fn format_human(output: &Output) -> String {
         Output::TxnBegun => "OK".to_string(),
         Output::TxnCommitted { version } => format!("Committed (v{})", version),
         Output::TxnAborted => "OK".to_string(),
+        Output::RetentionResult(info) => format_retention_apply(info, OutputMode::Human),
+        Output::RetentionStats(stats) => format_retention_stats(stats, OutputMode::Human),
+        Output::RetentionPreview(preview) => format_retention_preview(preview, OutputMode::Human),
         Output::DatabaseInfo(info) => {
             format!(
                 "version: {}\nuptime_secs: {}\nbranches: {}\ntotal_keys: {}",
@@ -619,6 +1302,12 @@ fn format_human(output: &Output) -> String {
             )
         }
         Output::Pong { version } => format!("PONG {}", version),
+        Output::BackupCreated(r) => {
+            format!(
+                "Backed up to {} (v{}, {} snapshots, {} WAL segments, {} bytes)",
+                r.path, r.version, r.snapshots, r.wal_segments, r.bytes
+            )
+        }
         Output::SearchResults(hits) => {
             if hits.is_empty() {
                 "(empty list)".to_string()
@@ -13,14 +13,20 @@ mod state;
 mod value;
 
 use std::io::IsTerminal;
+use std::path::Path;
 use std::process;
 
-use strata_executor::{AccessMode, Command, OpenOptions, Output, Strata};
+use strata_executor::{
+    AccessMode, Command, LocalWalArchive, MergeStrategy, OpenOptions, Output, Strata, WalDumpFilter,
+};
 
 use commands::build_cli;
 use format::{
-    format_diff, format_error, format_fork_info, format_merge_info, format_multi_output,
-    format_multi_versioned_output, format_output, format_versioned_output, OutputMode,
+    format_branch_status, format_cherry_pick_conflicts, format_cherry_pick_info, format_commit_log,
+    format_diff, format_error, format_fork_info, format_fsck_report, format_merge_conflicts,
+    format_merge_info, format_multi_output, format_multi_versioned_output, format_output,
+    format_rekey_info, format_restore_info, format_revert_info, format_versioned_output,
+    format_wal_archive_prune, format_wal_archive_verify, format_wal_dump, OutputMode,
 };
 use parse::{matches_to_action, BranchOp, CliAction, Primitive};
 use state::SessionState;
@@ -44,6 +50,31 @@ fn main() {
         OutputMode::Human
     };
 
+    // Handle `rekey` subcommand offline: the database must not be open.
+    if let Some(("rekey", sub)) = matches.subcommand() {
+        process::exit(run_rekey(&matches, sub, output_mode));
+    }
+
+    // Handle `restore` offline: it creates the database directory.
+    if let Some(("restore", sub)) = matches.subcommand() {
+        process::exit(run_restore(&matches, sub, output_mode));
+    }
+
+    // Handle `fsck` offline: the database must not be open.
+    if let Some(("fsck", sub)) = matches.subcommand() {
+        process::exit(run_fsck(&matches, sub, output_mode));
+    }
+
+    // Handle `wal` offline: reading the WAL must not trigger recovery.
+    if let Some(("wal", sub)) = matches.subcommand() {
+        process::exit(run_wal(&matches, sub, output_mode));
+    }
+
+    // Handle `wal-archive` offline: it works on the archive directory only.
+    if let Some(("wal-archive", sub)) = matches.subcommand() {
+        process::exit(run_wal_archive(sub, output_mode));
+    }
+
     // Auto-download model files when --auto-embed is set (best-effort).
     #[cfg(feature = "embed")]
     if matches.get_flag("auto-embed") {
@@ -114,6 +145,9 @@ fn open_database(matches: &clap::ArgMatches) -> Result<Strata, String> {
         if auto_embed {
             opts = opts.auto_embed(true);
         }
+        if let Some(key_file) = matches.get_one::<String>("key-file") {
+            opts = opts.encryption_key_file(&absolute_path(key_file)?);
+        }
 
         Strata::open_with(path, opts).map_err(|e| format!("Failed to open database: {}", e))
     }
@@ -135,7 +169,7 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
             }
         },
         Ok(CliAction::BranchOp(op)) => match op {
-            BranchOp::Fork { destination } => match state.fork_branch(&destination) {
+            BranchOp::Fork { destination, as_of } => match state.fork_branch(&destination, as_of) {
                 Ok(info) => {
                     println!("{}", format_fork_info(&info, mode));
                     0
@@ -145,19 +179,49 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                     1
                 }
             },
-            BranchOp::Diff { branch_a, branch_b } => {
-                match state.diff_branches(&branch_a, &branch_b) {
-                    Ok(diff) => {
-                        println!("{}", format_diff(&diff, mode));
-                        0
-                    }
-                    Err(e) => {
-                        eprintln!("{}", format_error(&e, mode));
-                        1
-                    }
+            BranchOp::SetStatus { name, status } => match state.set_branch_status(&name, status) {
+                Ok(()) => {
+                    println!("{}", format_branch_status(&name, status, mode));
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    1
+                }
+            },
+            BranchOp::Revert { as_of } => match state.revert_branch(as_of) {
+                Ok(info) => {
+                    println!("{}", format_revert_info(&info, mode));
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    1
+                }
+            },
+            BranchOp::Diff {
+                branch_a,
+                branch_b,
+                options,
+            } => match state.diff_branches(&branch_a, &branch_b, options) {
+                Ok(diff) => {
+                    println!("{}", format_diff(&diff, mode));
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    1
+                }
+            },
+            BranchOp::Merge {
+                source,
+                strategy,
+                primitives,
+            } => match state.merge_branch(&source, strategy, primitives) {
+                Ok(info) if strategy == MergeStrategy::Manual && !info.conflicts.is_empty() => {
+                    println!("{}", format_merge_conflicts(&info, mode));
+                    1
                 }
-            }
-            BranchOp::Merge { source, strategy } => match state.merge_branch(&source, strategy) {
                 Ok(info) => {
                     println!("{}", format_merge_info(&info, mode));
                     0
@@ -167,6 +231,49 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                     1
                 }
             },
+            BranchOp::Resolve {
+                source,
+                primitives,
+                picks,
+            } => match state.resolve_merge(&source, primitives, &picks) {
+                Ok(info) => {
+                    println!("{}", format_merge_info(&info, mode));
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    1
+                }
+            },
+            BranchOp::Log { limit, before } => match state.branch_log(limit, before) {
+                Ok(commits) => {
+                    println!("{}", format_commit_log(&commits, mode));
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    1
+                }
+            },
+            BranchOp::CherryPick {
+                source,
+                commits,
+                strategy,
+                primitives,
+            } => match state.cherry_pick(&source, commits, strategy, primitives) {
+                Ok(info) if strategy == MergeStrategy::Manual && !info.conflicts.is_empty() => {
+                    println!("{}", format_cherry_pick_conflicts(&info, mode));
+                    1
+                }
+                Ok(info) => {
+                    println!("{}", format_cherry_pick_info(&info, mode));
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    1
+                }
+            },
         },
         Ok(CliAction::Meta(_)) => {
             eprintln!("(error) Meta-commands are only available in REPL mode");
@@ -184,6 +291,8 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                     space: space.clone(),
                     key,
                     value,
+                    ttl_ms: None,
+                    expires_at: None,
                 }) {
                     Ok(output) => outputs.push(output),
                     Err(e) => {
@@ -349,6 +458,181 @@ fn run_shell_mode(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
     }
 }
 
+fn run_rekey(matches: &clap::ArgMatches, sub: &clap::ArgMatches, mode: OutputMode) -> i32 {
+    let path = matches
+        .get_one::<String>("db")
+        .map(|s| s.as_str())
+        .unwrap_or(".strata");
+    let old_key_file = matches.get_one::<String>("key-file").map(Path::new);
+    let new_key_file = if sub.get_flag("decrypt") {
+        None
+    } else {
+        sub.get_one::<String>("new-key-file").map(Path::new)
+    };
+
+    match Strata::rekey(path, old_key_file, new_key_file) {
+        Ok(info) => {
+            println!("{}", format_rekey_info(&info, mode));
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", format_error(&e, mode));
+            1
+        }
+    }
+}
+
+fn run_restore(matches: &clap::ArgMatches, sub: &clap::ArgMatches, mode: OutputMode) -> i32 {
+    let path = matches
+        .get_one::<String>("db")
+        .map(|s| s.as_str())
+        .unwrap_or(".strata");
+    let backup = sub.get_one::<String>("backup").unwrap();
+    let key_file = matches.get_one::<String>("key-file").map(Path::new);
+    let wal_archive = sub.get_one::<String>("wal-archive").map(Path::new);
+    let to = match sub.get_one::<String>("to").map(|s| s.parse::<u64>()) {
+        Some(Ok(ts)) => Some(ts),
+        Some(Err(e)) => {
+            eprintln!("Invalid --to: {}", e);
+            return 1;
+        }
+        None => None,
+    };
+
+    match Strata::restore(path, backup, to, wal_archive, key_file) {
+        Ok(info) => {
+            println!("{}", format_restore_info(&info, mode));
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", format_error(&e, mode));
+            1
+        }
+    }
+}
+
+fn run_fsck(matches: &clap::ArgMatches, sub: &clap::ArgMatches, mode: OutputMode) -> i32 {
+    let path = matches
+        .get_one::<String>("db")
+        .map(|s| s.as_str())
+        .unwrap_or(".strata");
+    let key_file = matches.get_one::<String>("key-file").map(Path::new);
+
+    match Strata::fsck(path, sub.get_flag("repair"), key_file) {
+        Ok(report) => {
+            println!("{}", format_fsck_report(&report, mode));
+            // Non-zero while unrepaired errors remain, like fsck(8)
+            if report.has_errors() {
+                1
+            } else {
+                0
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", format_error(&e, mode));
+            1
+        }
+    }
+}
+
+fn run_wal(matches: &clap::ArgMatches, sub: &clap::ArgMatches, mode: OutputMode) -> i32 {
+    let Some(("dump", args)) = sub.subcommand() else {
+        return 1;
+    };
+    let path = matches
+        .get_one::<String>("db")
+        .map(|s| s.as_str())
+        .unwrap_or(".strata");
+    let key_file = matches.get_one::<String>("key-file").map(Path::new);
+    // Only filter by branch when one was asked for
+    let branch = matches.get_one::<String>("branch").map(|s| s.as_str());
+
+    let mut filter = WalDumpFilter::default();
+    for (name, bound) in [
+        ("min-txn", &mut filter.min_txn_id),
+        ("max-txn", &mut filter.max_txn_id),
+        ("since", &mut filter.since),
+        ("until", &mut filter.until),
+    ] {
+        if let Some(value) = args.get_one::<String>(name) {
+            match value.parse::<u64>() {
+                Ok(value) => *bound = Some(value),
+                Err(e) => {
+                    eprintln!("Invalid --{}: {}", name, e);
+                    return 1;
+                }
+            }
+        }
+    }
+
+    match Strata::wal_dump(path, branch, filter, key_file) {
+        Ok(records) => {
+            println!("{}", format_wal_dump(&records, mode));
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", format_error(&e, mode));
+            1
+        }
+    }
+}
+
+fn run_wal_archive(sub: &clap::ArgMatches, mode: OutputMode) -> i32 {
+    let Some((action, args)) = sub.subcommand() else {
+        return 1;
+    };
+    let dir = args.get_one::<String>("dir").unwrap();
+    let archive = match LocalWalArchive::open(dir) {
+        Ok(archive) => archive,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+
+    let result = match action {
+        "verify" => archive.verify().map(|report| {
+            println!("{}", format_wal_archive_verify(&report, mode));
+            if report.is_ok() {
+                0
+            } else {
+                1
+            }
+        }),
+        "prune" => {
+            let secs = match args.get_one::<String>("older-than").unwrap().parse::<u64>() {
+                Ok(secs) => secs,
+                Err(e) => {
+                    eprintln!("Invalid --older-than: {}", e);
+                    return 1;
+                }
+            };
+            archive
+                .prune_older_than(std::time::Duration::from_secs(secs))
+                .map(|info| {
+                    println!("{}", format_wal_archive_prune(&info, mode));
+                    0
+                })
+        }
+        _ => return 1,
+    };
+    result.unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        1
+    })
+}
+
+/// Resolve a key file path against the current directory.
+///
+/// Relative paths in `strata.toml` resolve against the database directory,
+/// so paths given on the command line are made absolute first.
+fn absolute_path(path: &str) -> Result<String, String> {
+    Path::new(path)
+        .canonicalize()
+        .map(|p| p.to_string_lossy().into_owned())
+        .map_err(|e| format!("Failed to read key file '{}': {}", path, e))
+}
+
 fn run_setup() {
     #[cfg(feature = "embed")]
     {
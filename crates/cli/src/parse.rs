@@ -8,11 +8,14 @@
 //! - Pagination → `CliAction::ListAll`
 
 use std::io::Read;
+use std::time::Duration;
 
 use clap::ArgMatches;
 use strata_executor::{
-    BatchVectorEntry, BranchId, Command, DistanceMetric, MergeStrategy, MetadataFilter,
-    SearchQuery, TimeRangeInput, TxnOptions, Value,
+    BatchVectorEntry, BranchId, BranchStatus, Command, ConflictResolution, DiffOptions,
+    DistanceMetric, EventMergeStrategy, JsonMergeStrategy, MergeStrategy, MetadataFilter,
+    PrimitiveMergeStrategies, PrimitiveType, RetentionPolicy, SearchQuery, StateMergeStrategy,
+    TimeRangeInput, TxnIsolation, TxnOptions, Value,
 };
 
 use crate::state::SessionState;
@@ -70,19 +73,52 @@ pub enum Primitive {
 
 /// Branch operations that bypass the Command enum.
 pub enum BranchOp {
+    SetStatus {
+        name: String,
+        status: BranchStatus,
+    },
     Fork {
         destination: String,
+        as_of: Option<u64>,
+    },
+    Revert {
+        as_of: u64,
     },
     Diff {
         branch_a: String,
         branch_b: String,
+        options: DiffOptions,
     },
     Merge {
         source: String,
         strategy: MergeStrategy,
+        primitives: PrimitiveMergeStrategies,
+    },
+    Resolve {
+        source: String,
+        primitives: PrimitiveMergeStrategies,
+        picks: ConflictPicks,
+    },
+    Log {
+        limit: Option<u64>,
+        before: Option<u64>,
+    },
+    CherryPick {
+        source: String,
+        commits: Vec<u64>,
+        strategy: MergeStrategy,
+        primitives: PrimitiveMergeStrategies,
     },
 }
 
+/// Conflict choices for `branch resolve`, matched by conflicting key name.
+pub struct ConflictPicks {
+    /// Resolution for conflicts without a per-key pick (`--take`).
+    pub default: Option<ConflictResolution>,
+    /// Per-key resolutions (`--pick-source`, `--pick-target`, `--set`).
+    pub keys: Vec<(String, ConflictResolution)>,
+}
+
 /// REPL meta-commands.
 pub enum MetaCommand {
     Use {
@@ -142,6 +178,10 @@ pub fn matches_to_action(matches: &ArgMatches, state: &SessionState) -> Result<C
         "info" => Ok(CliAction::Execute(Command::Info)),
         "flush" => Ok(CliAction::Execute(Command::Flush)),
         "compact" => Ok(CliAction::Execute(Command::Compact)),
+        "backup" => {
+            let path = sub_matches.get_one::<String>("path").unwrap().clone();
+            Ok(CliAction::Execute(Command::Backup { path }))
+        }
         "search" => parse_search(sub_matches, state),
         "configure-model" => parse_configure_model(sub_matches),
         "embed" => parse_embed(sub_matches),
@@ -240,6 +280,8 @@ fn parse_kv(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, Str
                     space: space(state),
                     key: pairs[0].clone(),
                     value,
+                    ttl_ms: None,
+                    expires_at: None,
                 }))
             } else {
                 // Normal mode: key-value pairs from args
@@ -266,6 +308,8 @@ fn parse_kv(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, Str
                         space: space(state),
                         key,
                         value,
+                        ttl_ms: None,
+                        expires_at: None,
                     }))
                 } else {
                     // Multiple pairs
@@ -555,6 +599,8 @@ fn parse_state(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
                 space: space(state),
                 cell,
                 value,
+                ttl_ms: None,
+                expires_at: None,
             }))
         }
         "get" => {
@@ -594,6 +640,8 @@ fn parse_state(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
                 space: space(state),
                 cell,
                 value,
+                ttl_ms: None,
+                expires_at: None,
             }))
         }
         "cas" => {
@@ -667,6 +715,134 @@ fn parse_metric(s: &str) -> Result<DistanceMetric, String> {
     }
 }
 
+fn parse_branch_status(s: &str) -> Result<BranchStatus, String> {
+    match s.to_lowercase().as_str() {
+        "active" => Ok(BranchStatus::Active),
+        "protected" => Ok(BranchStatus::Protected),
+        "frozen" => Ok(BranchStatus::Frozen),
+        other => Err(format!(
+            "Invalid status '{}': expected active, protected or frozen",
+            other
+        )),
+    }
+}
+
+fn parse_merge_strategy(m: &ArgMatches) -> MergeStrategy {
+    match m.get_one::<String>("strategy").map(|s| s.as_str()) {
+        Some("strict") => MergeStrategy::Strict,
+        Some("manual") => MergeStrategy::Manual,
+        _ => MergeStrategy::LastWriterWins,
+    }
+}
+
+fn parse_primitive_merge(m: &ArgMatches) -> Result<PrimitiveMergeStrategies, String> {
+    let arg = |name: &str| {
+        m.get_one::<String>(name)
+            .map(|s| s.to_lowercase())
+            .unwrap_or_default()
+    };
+    let json = match arg("json-merge").as_str() {
+        "" | "document" => JsonMergeStrategy::Document,
+        "path" => JsonMergeStrategy::Path,
+        other => {
+            return Err(format!(
+                "Unknown JSON merge: {}. Use document or path",
+                other
+            ))
+        }
+    };
+    let event = match arg("event-merge").as_str() {
+        "" | "key" => EventMergeStrategy::ByKey,
+        "append" => EventMergeStrategy::Append,
+        other => return Err(format!("Unknown event merge: {}. Use key or append", other)),
+    };
+    let state = match arg("state-merge").as_str() {
+        "" | "value" => StateMergeStrategy::Value,
+        "version" => StateMergeStrategy::CounterVersion,
+        other => {
+            return Err(format!(
+                "Unknown state merge: {}. Use value or version",
+                other
+            ))
+        }
+    };
+    Ok(PrimitiveMergeStrategies { json, event, state })
+}
+
+fn parse_diff_options(m: &ArgMatches) -> Result<DiffOptions, String> {
+    let many = |name: &str| {
+        m.get_many::<String>(name)
+            .map(|vals| vals.cloned().collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+    let primitives = many("primitive")
+        .iter()
+        .map(|p| match p.to_lowercase().as_str() {
+            "kv" => Ok(PrimitiveType::Kv),
+            "json" => Ok(PrimitiveType::Json),
+            "event" => Ok(PrimitiveType::Event),
+            "state" => Ok(PrimitiveType::State),
+            "vector" => Ok(PrimitiveType::Vector),
+            other => Err(format!(
+                "Unknown primitive: {}. Use kv, json, event, state or vector",
+                other
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let limit = m
+        .get_one::<String>("limit")
+        .map(|s| s.parse::<usize>())
+        .transpose()
+        .map_err(|e| format!("Invalid limit: {}", e))?;
+    let as_of = |name: &str| {
+        m.get_one::<String>(name)
+            .map(|s| s.parse::<u64>())
+            .transpose()
+            .map_err(|e| format!("Invalid --{}: {}", name, e))
+    };
+    Ok(DiffOptions {
+        primitives,
+        spaces: many("space"),
+        key_prefix: m.get_one::<String>("prefix").cloned(),
+        limit,
+        cursor: m.get_one::<String>("cursor").cloned(),
+        summary_only: m.get_flag("summary"),
+        as_of_a: as_of("as-of-a")?,
+        as_of_b: as_of("as-of-b")?,
+    })
+}
+
+fn parse_conflict_picks(m: &ArgMatches) -> Result<ConflictPicks, String> {
+    let default = match m.get_one::<String>("take").map(|s| s.to_lowercase()) {
+        None => None,
+        Some(side) if side == "source" => Some(ConflictResolution::TakeSource),
+        Some(side) if side == "target" => Some(ConflictResolution::TakeTarget),
+        Some(other) => return Err(format!("Unknown side: {}. Use source or target", other)),
+    };
+    let many = |name: &str| {
+        m.get_many::<String>(name)
+            .map(|vals| vals.cloned().collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+    let mut keys = Vec::new();
+    for key in many("pick-source") {
+        keys.push((key, ConflictResolution::TakeSource));
+    }
+    for key in many("pick-target") {
+        keys.push((key, ConflictResolution::TakeTarget));
+    }
+    for assignment in many("set") {
+        let (key, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid --set: {}. Use KEY=VALUE", assignment))?;
+        keys.push((
+            key.to_string(),
+            ConflictResolution::Custom(parse_value(value)),
+        ));
+    }
+    Ok(ConflictPicks { default, keys })
+}
+
 fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, String> {
     let (sub, m) = matches.subcommand().ok_or("No vector subcommand")?;
     match sub {
@@ -815,8 +991,12 @@ fn parse_branch(matches: &ArgMatches, _state: &SessionState) -> Result<CliAction
                 .map(|s| s.parse::<u64>())
                 .transpose()
                 .map_err(|e| format!("Invalid limit: {}", e))?;
+            let state = m
+                .get_one::<String>("status")
+                .map(|s| parse_branch_status(s))
+                .transpose()?;
             Ok(CliAction::Execute(Command::BranchList {
-                state: None,
+                state,
                 limit,
                 offset: None,
             }))
@@ -833,22 +1013,95 @@ fn parse_branch(matches: &ArgMatches, _state: &SessionState) -> Result<CliAction
                 branch: BranchId::from(name),
             }))
         }
+        "protect" | "freeze" | "unprotect" => {
+            let name = m.get_one::<String>("name").unwrap().clone();
+            let status = match sub {
+                "protect" => BranchStatus::Protected,
+                "freeze" => BranchStatus::Frozen,
+                _ => BranchStatus::Active,
+            };
+            Ok(CliAction::BranchOp(BranchOp::SetStatus { name, status }))
+        }
+        "retention" => parse_branch_retention(m),
         "fork" => {
             let destination = m.get_one::<String>("dest").unwrap().clone();
-            Ok(CliAction::BranchOp(BranchOp::Fork { destination }))
+            let as_of = m
+                .get_one::<String>("as-of")
+                .map(|s| s.parse::<u64>())
+                .transpose()
+                .map_err(|e| format!("Invalid --as-of: {}", e))?;
+            Ok(CliAction::BranchOp(BranchOp::Fork { destination, as_of }))
+        }
+        "revert" => {
+            let as_of = m
+                .get_one::<String>("as_of")
+                .unwrap()
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid timestamp: {}", e))?;
+            Ok(CliAction::BranchOp(BranchOp::Revert { as_of }))
         }
         "diff" => {
             let branch_a = m.get_one::<String>("a").unwrap().clone();
             let branch_b = m.get_one::<String>("b").unwrap().clone();
-            Ok(CliAction::BranchOp(BranchOp::Diff { branch_a, branch_b }))
+            let options = parse_diff_options(m)?;
+            Ok(CliAction::BranchOp(BranchOp::Diff {
+                branch_a,
+                branch_b,
+                options,
+            }))
         }
         "merge" => {
             let source = m.get_one::<String>("source").unwrap().clone();
-            let strategy = match m.get_one::<String>("strategy").map(|s| s.as_str()) {
-                Some("strict") => MergeStrategy::Strict,
-                _ => MergeStrategy::LastWriterWins,
-            };
-            Ok(CliAction::BranchOp(BranchOp::Merge { source, strategy }))
+            let strategy = parse_merge_strategy(m);
+            let primitives = parse_primitive_merge(m)?;
+            Ok(CliAction::BranchOp(BranchOp::Merge {
+                source,
+                strategy,
+                primitives,
+            }))
+        }
+        "log" => {
+            let limit = m
+                .get_one::<String>("limit")
+                .map(|s| s.parse::<u64>())
+                .transpose()
+                .map_err(|e| format!("Invalid limit: {}", e))?;
+            let before = m
+                .get_one::<String>("before")
+                .map(|s| s.trim_start_matches('v').parse::<u64>())
+                .transpose()
+                .map_err(|e| format!("Invalid --before: {}", e))?;
+            Ok(CliAction::BranchOp(BranchOp::Log { limit, before }))
+        }
+        "cherry-pick" => {
+            let source = m.get_one::<String>("source").unwrap().clone();
+            let commits = m
+                .get_many::<String>("commits")
+                .unwrap()
+                .map(|s| {
+                    s.trim_start_matches('v')
+                        .parse::<u64>()
+                        .map_err(|e| format!("Invalid commit version '{}': {}", s, e))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let strategy = parse_merge_strategy(m);
+            let primitives = parse_primitive_merge(m)?;
+            Ok(CliAction::BranchOp(BranchOp::CherryPick {
+                source,
+                commits,
+                strategy,
+                primitives,
+            }))
+        }
+        "resolve" => {
+            let source = m.get_one::<String>("source").unwrap().clone();
+            let primitives = parse_primitive_merge(m)?;
+            let picks = parse_conflict_picks(m)?;
+            Ok(CliAction::BranchOp(BranchOp::Resolve {
+                source,
+                primitives,
+                picks,
+            }))
         }
         "export" => {
             let branch_id = m.get_one::<String>("branch").unwrap().clone();
@@ -870,6 +1123,47 @@ fn parse_branch(matches: &ArgMatches, _state: &SessionState) -> Result<CliAction
     }
 }
 
+fn parse_branch_retention(matches: &ArgMatches) -> Result<CliAction, String> {
+    let (sub, m) = matches.subcommand().ok_or("No retention subcommand")?;
+    let name = m.get_one::<String>("name").unwrap().clone();
+    match sub {
+        "get" => Ok(CliAction::Execute(Command::BranchGetRetention {
+            branch: BranchId::from(name),
+        })),
+        "set" => {
+            let policy = if let Some(n) = m.get_one::<String>("keep-last") {
+                let n = n
+                    .parse::<usize>()
+                    .map_err(|e| format!("Invalid --keep-last: {}", e))?;
+                RetentionPolicy::KeepLast(n)
+            } else if let Some(secs) = m.get_one::<String>("keep-for") {
+                let secs = secs
+                    .parse::<u64>()
+                    .map_err(|e| format!("Invalid --keep-for: {}", e))?;
+                RetentionPolicy::KeepFor(Duration::from_secs(secs))
+            } else if m.get_flag("keep-all") {
+                RetentionPolicy::KeepAll
+            } else {
+                return Err("Specify --keep-last, --keep-for or --keep-all".to_string());
+            };
+            Ok(CliAction::Execute(Command::BranchSetRetention {
+                branch: BranchId::from(name),
+                policy,
+            }))
+        }
+        "stats" => Ok(CliAction::Execute(Command::RetentionStats {
+            branch: Some(BranchId::from(name)),
+        })),
+        "preview" => Ok(CliAction::Execute(Command::RetentionPreview {
+            branch: Some(BranchId::from(name)),
+        })),
+        "apply" => Ok(CliAction::Execute(Command::RetentionApply {
+            branch: Some(BranchId::from(name)),
+        })),
+        other => Err(format!("Unknown retention subcommand: {}", other)),
+    }
+}
+
 // =========================================================================
 // Space
 // =========================================================================
@@ -913,17 +1207,42 @@ fn parse_space(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
 
 fn parse_begin(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, String> {
     let read_only = matches.get_flag("txn-read-only");
+    let isolation = if matches.get_flag("serializable") {
+        TxnIsolation::Serializable
+    } else {
+        TxnIsolation::Snapshot
+    };
+    let metadata = matches
+        .get_many::<String>("meta")
+        .into_iter()
+        .flatten()
+        .map(|entry| {
+            entry
+                .split_once('=')
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .ok_or_else(|| format!("Invalid --meta '{}': expected KEY=VALUE", entry))
+        })
+        .collect::<Result<_, _>>()?;
     Ok(CliAction::Execute(Command::TxnBegin {
         branch: branch(state),
-        options: Some(TxnOptions { read_only }),
+        options: Some(TxnOptions {
+            read_only,
+            isolation,
+            message: matches.get_one::<String>("message").cloned(),
+            metadata,
+        }),
     }))
 }
 
 fn parse_txn(matches: &ArgMatches) -> Result<CliAction, String> {
-    let (sub, _) = matches.subcommand().ok_or("No txn subcommand")?;
+    let (sub, sub_matches) = matches.subcommand().ok_or("No txn subcommand")?;
+    let name = || sub_matches.get_one::<String>("name").unwrap().clone();
     match sub {
         "info" => Ok(CliAction::Execute(Command::TxnInfo)),
         "active" => Ok(CliAction::Execute(Command::TxnIsActive)),
+        "savepoint" => Ok(CliAction::Execute(Command::TxnSavepoint { name: name() })),
+        "rollback-to" => Ok(CliAction::Execute(Command::TxnRollbackTo { name: name() })),
+        "release" => Ok(CliAction::Execute(Command::TxnRelease { name: name() })),
         other => Err(format!("Unknown txn subcommand: {}", other)),
     }
 }
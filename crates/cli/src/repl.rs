@@ -12,12 +12,14 @@ use rustyline::hint::Hinter;
 use rustyline::validate::Validator;
 use rustyline::{CompletionType, Config, Context, Editor, Helper};
 
-use strata_executor::{Command, Output};
+use strata_executor::{Command, MergeStrategy, Output};
 
 use crate::commands::build_repl_cmd;
 use crate::format::{
-    format_diff, format_error, format_fork_info, format_merge_info, format_multi_output,
-    format_multi_versioned_output, format_output, format_versioned_output, OutputMode,
+    format_branch_status, format_cherry_pick_conflicts, format_cherry_pick_info, format_commit_log,
+    format_diff, format_error, format_fork_info, format_merge_conflicts, format_merge_info,
+    format_multi_output, format_multi_versioned_output, format_output, format_revert_info,
+    format_versioned_output, OutputMode,
 };
 use crate::parse::{
     check_meta_command, matches_to_action, BranchOp, CliAction, MetaCommand, Primitive,
@@ -189,7 +191,7 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
             }
         },
         Ok(CliAction::BranchOp(op)) => match op {
-            BranchOp::Fork { destination } => match state.fork_branch(&destination) {
+            BranchOp::Fork { destination, as_of } => match state.fork_branch(&destination, as_of) {
                 Ok(info) => {
                     println!("{}", format_fork_info(&info, mode));
                     true
@@ -199,19 +201,49 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                     false
                 }
             },
-            BranchOp::Diff { branch_a, branch_b } => {
-                match state.diff_branches(&branch_a, &branch_b) {
-                    Ok(diff) => {
-                        println!("{}", format_diff(&diff, mode));
-                        true
-                    }
-                    Err(e) => {
-                        eprintln!("{}", format_error(&e, mode));
-                        false
-                    }
+            BranchOp::SetStatus { name, status } => match state.set_branch_status(&name, status) {
+                Ok(()) => {
+                    println!("{}", format_branch_status(&name, status, mode));
+                    true
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    false
+                }
+            },
+            BranchOp::Revert { as_of } => match state.revert_branch(as_of) {
+                Ok(info) => {
+                    println!("{}", format_revert_info(&info, mode));
+                    true
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    false
+                }
+            },
+            BranchOp::Diff {
+                branch_a,
+                branch_b,
+                options,
+            } => match state.diff_branches(&branch_a, &branch_b, options) {
+                Ok(diff) => {
+                    println!("{}", format_diff(&diff, mode));
+                    true
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    false
+                }
+            },
+            BranchOp::Merge {
+                source,
+                strategy,
+                primitives,
+            } => match state.merge_branch(&source, strategy, primitives) {
+                Ok(info) if strategy == MergeStrategy::Manual && !info.conflicts.is_empty() => {
+                    println!("{}", format_merge_conflicts(&info, mode));
+                    false
                 }
-            }
-            BranchOp::Merge { source, strategy } => match state.merge_branch(&source, strategy) {
                 Ok(info) => {
                     println!("{}", format_merge_info(&info, mode));
                     true
@@ -221,6 +253,49 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                     false
                 }
             },
+            BranchOp::Resolve {
+                source,
+                primitives,
+                picks,
+            } => match state.resolve_merge(&source, primitives, &picks) {
+                Ok(info) => {
+                    println!("{}", format_merge_info(&info, mode));
+                    true
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    false
+                }
+            },
+            BranchOp::Log { limit, before } => match state.branch_log(limit, before) {
+                Ok(commits) => {
+                    println!("{}", format_commit_log(&commits, mode));
+                    true
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    false
+                }
+            },
+            BranchOp::CherryPick {
+                source,
+                commits,
+                strategy,
+                primitives,
+            } => match state.cherry_pick(&source, commits, strategy, primitives) {
+                Ok(info) if strategy == MergeStrategy::Manual && !info.conflicts.is_empty() => {
+                    println!("{}", format_cherry_pick_conflicts(&info, mode));
+                    false
+                }
+                Ok(info) => {
+                    println!("{}", format_cherry_pick_info(&info, mode));
+                    true
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    false
+                }
+            },
         },
         Ok(CliAction::Meta(_)) => {
             // Meta-commands should have been handled before reaching here
@@ -238,6 +313,8 @@ fn execute_action(matches: &clap::ArgMatches, state: &mut SessionState, mode: Ou
                     space: space.clone(),
                     key,
                     value,
+                    ttl_ms: None,
+                    expires_at: None,
                 }) {
                     Ok(output) => outputs.push(output),
                     Err(e) => {
@@ -424,16 +501,19 @@ fn print_help(command: Option<&str>) {
         println!("  event       Event log operations (append, get, list, len)");
         println!("  state       State cell operations (set, get, del, init, cas, list, history)");
         println!("  vector      Vector store operations (upsert, get, del, search, create, ...)");
-        println!("  branch      Branch operations (create, info, list, fork, diff, merge, ...)");
+        println!(
+            "  branch      Branch operations (create, info, list, fork, revert, diff, merge, log, ...)"
+        );
         println!("  space       Space operations (list, create, del, exists)");
         println!("  begin       Begin a transaction");
         println!("  commit      Commit a transaction");
         println!("  rollback    Rollback a transaction");
-        println!("  txn         Transaction info (info, active)");
+        println!("  txn         Transaction info and savepoints (info, active, savepoint, ...)");
         println!("  ping        Ping the database");
         println!("  info        Database information");
         println!("  flush       Flush writes to disk");
         println!("  compact     Trigger compaction");
+        println!("  backup      Copy the database to a directory");
         println!("  search      Search across primitives");
         println!();
         println!("Meta-commands:");
@@ -451,7 +531,8 @@ fn print_help(command: Option<&str>) {
 /// Known top-level commands for TAB completion.
 const TOP_LEVEL_COMMANDS: &[&str] = &[
     "kv", "json", "event", "state", "vector", "branch", "space", "begin", "commit", "rollback",
-    "txn", "ping", "info", "flush", "compact", "search", "use", "help", "quit", "exit", "clear",
+    "txn", "ping", "info", "flush", "compact", "backup", "search", "use", "help", "quit", "exit",
+    "clear",
 ];
 
 /// Known subcommands for each top-level command.
@@ -474,11 +555,29 @@ fn subcommands_for(cmd: &str) -> &'static [&'static str] {
             "batch-upsert",
         ],
         "branch" => &[
-            "create", "info", "get", "list", "exists", "del", "fork", "diff", "merge", "export",
-            "import", "validate",
+            "create",
+            "info",
+            "get",
+            "list",
+            "exists",
+            "del",
+            "protect",
+            "freeze",
+            "unprotect",
+            "retention",
+            "fork",
+            "revert",
+            "diff",
+            "merge",
+            "resolve",
+            "log",
+            "cherry-pick",
+            "export",
+            "import",
+            "validate",
         ],
         "space" => &["list", "create", "del", "exists"],
-        "txn" => &["info", "active"],
+        "txn" => &["info", "active", "savepoint", "rollback-to", "release"],
         _ => &[],
     }
 }
@@ -5,10 +5,13 @@
 //! `Arc<Database>`.
 
 use strata_executor::{
-    BranchDiffResult, Branches, Command, Error, ForkInfo, MergeInfo, MergeStrategy, Output, Result,
-    Session, Strata,
+    BranchDiffResult, BranchStatus, Branches, CherryPickInfo, Command, CommitInfo, DiffOptions,
+    Error, ForkInfo, MergeInfo, MergeResolution, MergeStrategy, Output, PrimitiveMergeStrategies,
+    Result, RevertInfo, Session, Strata,
 };
 
+use crate::parse::ConflictPicks;
+
 /// Wraps the database handles and tracks current context.
 pub struct SessionState {
     db: Strata,
@@ -49,19 +52,123 @@ impl SessionState {
         self.db.branches()
     }
 
-    /// Fork the current branch.
-    pub fn fork_branch(&self, destination: &str) -> Result<ForkInfo> {
-        self.db.branches().fork(&self.branch, destination)
+    /// Fork the current branch, optionally as of a past timestamp.
+    pub fn fork_branch(&self, destination: &str, as_of: Option<u64>) -> Result<ForkInfo> {
+        match as_of {
+            Some(ts) => self.db.branches().fork_at(&self.branch, destination, ts),
+            None => self.db.branches().fork(&self.branch, destination),
+        }
+    }
+
+    /// Protect, freeze or reactivate a branch.
+    pub fn set_branch_status(&self, name: &str, status: BranchStatus) -> Result<()> {
+        self.db.branches().set_status(name, status)
+    }
+
+    /// Revert the current branch to its state at a past timestamp.
+    pub fn revert_branch(&self, as_of: u64) -> Result<RevertInfo> {
+        self.db.branches().revert(&self.branch, as_of)
     }
 
     /// Diff two branches.
-    pub fn diff_branches(&self, branch_a: &str, branch_b: &str) -> Result<BranchDiffResult> {
-        self.db.branches().diff(branch_a, branch_b)
+    pub fn diff_branches(
+        &self,
+        branch_a: &str,
+        branch_b: &str,
+        options: DiffOptions,
+    ) -> Result<BranchDiffResult> {
+        self.db
+            .branches()
+            .diff_with_options(branch_a, branch_b, options)
     }
 
     /// Merge a source branch into the current branch.
-    pub fn merge_branch(&self, source: &str, strategy: MergeStrategy) -> Result<MergeInfo> {
-        self.db.branches().merge(source, &self.branch, strategy)
+    pub fn merge_branch(
+        &self,
+        source: &str,
+        strategy: MergeStrategy,
+        primitives: PrimitiveMergeStrategies,
+    ) -> Result<MergeInfo> {
+        self.db
+            .branches()
+            .merge_with_strategies(source, &self.branch, strategy, primitives)
+    }
+
+    /// List commits on the current branch, newest first.
+    pub fn branch_log(&self, limit: Option<u64>, before: Option<u64>) -> Result<Vec<CommitInfo>> {
+        self.db.branches().log(&self.branch, limit, before)
+    }
+
+    /// Apply commits from a source branch onto the current branch.
+    pub fn cherry_pick(
+        &self,
+        source: &str,
+        commits: Vec<u64>,
+        strategy: MergeStrategy,
+        primitives: PrimitiveMergeStrategies,
+    ) -> Result<CherryPickInfo> {
+        self.db
+            .branches()
+            .cherry_pick(source, &self.branch, commits, strategy, primitives)
+    }
+
+    /// Merge a source branch into the current branch, resolving each
+    /// conflict from `picks`. Per-key picks win over the `--take` default.
+    pub fn resolve_merge(
+        &self,
+        source: &str,
+        primitives: PrimitiveMergeStrategies,
+        picks: &ConflictPicks,
+    ) -> Result<MergeInfo> {
+        let branches = self.db.branches();
+        let info = branches.merge_with_strategies(
+            source,
+            &self.branch,
+            MergeStrategy::Manual,
+            primitives,
+        )?;
+        if info.conflicts.is_empty() {
+            // Nothing to resolve: the manual merge already applied
+            return Ok(info);
+        }
+
+        if let Some((key, _)) = picks
+            .keys
+            .iter()
+            .find(|(key, _)| !info.conflicts.iter().any(|c| &c.key == key))
+        {
+            return Err(Error::InvalidInput {
+                reason: format!("Key '{}' is not in conflict", key),
+            });
+        }
+
+        let mut resolutions = Vec::new();
+        let mut unresolved = Vec::new();
+        for conflict in &info.conflicts {
+            let pick = picks
+                .keys
+                .iter()
+                .rev()
+                .find(|(key, _)| *key == conflict.key)
+                .map(|(_, resolution)| resolution)
+                .or(picks.default.as_ref());
+            match pick {
+                Some(resolution) => {
+                    resolutions.push(MergeResolution::new(conflict, resolution.clone()))
+                }
+                None => unresolved.push(conflict.key.as_str()),
+            }
+        }
+        if !unresolved.is_empty() {
+            return Err(Error::InvalidInput {
+                reason: format!(
+                    "No resolution for conflicting keys: {}. Use --take, --pick-source, --pick-target or --set",
+                    unresolved.join(", ")
+                ),
+            });
+        }
+
+        branches.resolve_merge(source, &self.branch, primitives, resolutions)
     }
 
     /// Current branch name.
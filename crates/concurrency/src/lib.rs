@@ -9,6 +9,7 @@
 //! - Compare-and-swap (CAS) operations
 //! - WAL integration for durability
 //! - JSON region-based conflict detection
+//! - Opt-in serializable isolation with phantom detection on prefix scans
 
 #![warn(missing_docs)]
 #![warn(clippy::all)]
@@ -22,10 +23,12 @@ pub mod transaction;
 pub mod validation;
 
 pub use manager::TransactionManager;
-pub use payload::TransactionPayload;
+pub use payload::{CheckpointBatch, CheckpointPayload, TransactionPayload};
 pub use recovery::{RecoveryCoordinator, RecoveryResult, RecoveryStats};
 pub use snapshot::ClonedSnapshotView;
-pub use transaction::{CommitError, JsonStoreExt, TransactionContext, TransactionStatus};
+pub use transaction::{
+    CommitError, IsolationLevel, JsonStoreExt, TransactionContext, TransactionStatus,
+};
 
 // Re-export the SnapshotView trait from core for convenience
 pub use strata_core::traits::SnapshotView;
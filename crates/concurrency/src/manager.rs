@@ -114,6 +114,11 @@ impl TransactionManager {
         self.version.load(Ordering::SeqCst)
     }
 
+    /// Highest transaction ID allocated so far (0 if none)
+    pub fn last_txn_id(&self) -> u64 {
+        self.next_txn_id.load(Ordering::SeqCst) - 1
+    }
+
     /// Allocate next transaction ID
     ///
     /// # Panics
@@ -219,8 +224,9 @@ impl TransactionManager {
         // Step 1: Validate and mark committed (in-memory)
         // This performs: Active → Validating → Committed
         // Or: Active → Validating → Aborted (if conflicts detected)
-        // Skip validation for blind writes (no reads, no CAS, no JSON snapshots/writes)
+        // Skip validation for blind writes (no reads, no scans, no CAS, no JSON snapshots/writes)
         let can_skip_validation = txn.read_set.is_empty()
+            && txn.scan_predicates().is_empty()
             && txn.cas_set.is_empty()
             && txn.json_snapshot_versions().map_or(true, |v| v.is_empty())
             && txn.json_writes().is_empty();
@@ -378,7 +384,9 @@ mod tests {
     fn test_next_txn_id_increments() {
         // TransactionManager::new(0) calls with_txn_id(0, 0), which sets next_txn_id = 0 + 1 = 1
         let manager = TransactionManager::new(0);
+        assert_eq!(manager.last_txn_id(), 0);
         assert_eq!(manager.next_txn_id(), 1);
+        assert_eq!(manager.last_txn_id(), 1);
         assert_eq!(manager.next_txn_id(), 2);
         assert_eq!(manager.next_txn_id(), 3);
     }
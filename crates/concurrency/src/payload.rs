@@ -9,10 +9,20 @@
 //!
 //! The payload is serialized using MessagePack (`rmp-serde`) for compact
 //! binary encoding with schema evolution support.
+//!
+//! Checkpoints use the same encoding: a `CheckpointPayload` holds the storage
+//! state up to a commit version and is stored in a snapshot file, so recovery
+//! only replays the WAL records committed after it. A full checkpoint holds
+//! every version; an incremental one holds only the versions committed since
+//! the previous checkpoint and names the snapshots it builds on.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
 use strata_core::types::Key;
 use strata_core::value::Value;
+use strata_core::{StrataResult, Timestamp};
+use strata_storage::ShardedStore;
 
 use crate::TransactionContext;
 
@@ -29,6 +39,15 @@ pub struct TransactionPayload {
     pub puts: Vec<(Key, Value)>,
     /// Keys to delete (from delete_set)
     pub deletes: Vec<Key>,
+    /// Commit message (absent in payloads written before messages existed)
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Commit metadata
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    /// Time-to-live of the puts written with one, relative to the commit time
+    #[serde(default)]
+    pub ttls: Vec<(Key, Duration)>,
 }
 
 impl TransactionPayload {
@@ -64,16 +83,145 @@ impl TransactionPayload {
             version,
             puts,
             deletes,
+            message: txn.commit_message.clone(),
+            metadata: txn.commit_metadata.clone(),
+            ttls: txn.ttls().map(|(k, ttl)| (k.clone(), ttl)).collect(),
         }
     }
 }
 
+/// Serializable storage state for a checkpoint.
+///
+/// Holds the stored versions in `(base_version, version]` (shadowed versions
+/// and tombstones included) grouped into the commits that wrote them, so
+/// recovery rebuilds the same version chains and time-travel reads keep
+/// working. Stored in the `STORAGE` section of a snapshot file.
+///
+/// A full checkpoint has `base_version == 0` and no parents. An incremental
+/// checkpoint starts where the last of its `parents` ended; recovery applies
+/// the parents (full snapshot first) and then the checkpoint itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckpointPayload {
+    /// Highest commit version covered by the checkpoint
+    pub version: u64,
+    /// Highest transaction ID allocated when the checkpoint was taken
+    pub max_txn_id: u64,
+    /// Commits in ascending version order
+    pub batches: Vec<CheckpointBatch>,
+    /// Version covered by the previous checkpoint in the chain (0 if full)
+    #[serde(default)]
+    pub base_version: u64,
+    /// Snapshot IDs this checkpoint builds on, full snapshot first
+    #[serde(default)]
+    pub parents: Vec<u64>,
+}
+
+/// Writes and deletes of one commit captured in a checkpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckpointBatch {
+    /// Commit version
+    pub version: u64,
+    /// Commit timestamp (microseconds since epoch)
+    pub timestamp: u64,
+    /// Key-value pairs written
+    pub puts: Vec<(Key, Value)>,
+    /// Keys deleted
+    pub deletes: Vec<Key>,
+    /// Time-to-live of the puts written with one
+    #[serde(default)]
+    pub ttls: Vec<(Key, Duration)>,
+}
+
+impl CheckpointPayload {
+    /// Capture every version at or below `version` from `storage`.
+    ///
+    /// The caller must ensure no commit at or below `version` is still being
+    /// applied (the engine reads `version` under the WAL lock, which commits
+    /// hold while applying).
+    pub fn capture(storage: &ShardedStore, version: u64, max_txn_id: u64) -> StrataResult<Self> {
+        Self::capture_since(storage, 0, version, max_txn_id)
+    }
+
+    /// Capture the versions in `(base_version, version]` from `storage`.
+    ///
+    /// With a non-zero `base_version` this is an incremental checkpoint;
+    /// the caller records the snapshots it builds on in `parents`.
+    pub fn capture_since(
+        storage: &ShardedStore,
+        base_version: u64,
+        version: u64,
+        max_txn_id: u64,
+    ) -> StrataResult<Self> {
+        let mut batches: BTreeMap<(u64, u64), CheckpointBatch> = BTreeMap::new();
+        for (key, sv) in storage.versions_between(base_version, version)? {
+            let commit_version = sv.version().as_u64();
+            let timestamp = u64::from(sv.timestamp());
+            let batch = batches
+                .entry((commit_version, timestamp))
+                .or_insert_with(|| CheckpointBatch {
+                    version: commit_version,
+                    timestamp,
+                    ..Default::default()
+                });
+            if sv.is_tombstone() {
+                batch.deletes.push(key);
+            } else {
+                if let Some(ttl) = sv.ttl() {
+                    batch.ttls.push((key.clone(), ttl));
+                }
+                batch.puts.push((key, sv.value().clone()));
+            }
+        }
+
+        Ok(CheckpointPayload {
+            version,
+            max_txn_id,
+            batches: batches.into_values().collect(),
+            base_version,
+            parents: Vec::new(),
+        })
+    }
+
+    /// Whether this checkpoint builds on earlier ones.
+    pub fn is_incremental(&self) -> bool {
+        !self.parents.is_empty()
+    }
+
+    /// Apply the captured commits to `storage`, oldest first.
+    pub fn apply(&self, storage: &ShardedStore) -> StrataResult<()> {
+        for batch in &self.batches {
+            storage.apply_batch_with_ttls(
+                &batch.puts,
+                &batch.deletes,
+                &batch.ttls,
+                batch.version,
+                Timestamp::from(batch.timestamp),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Serialize to MessagePack bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        rmp_serde::to_vec(self).expect("CheckpointPayload serialization should not fail")
+    }
+
+    /// Deserialize from MessagePack bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PayloadError> {
+        rmp_serde::from_slice(bytes)
+            .map_err(|e| PayloadError::CheckpointDeserializeFailed(e.to_string()))
+    }
+}
+
 /// Errors from payload serialization/deserialization.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum PayloadError {
     /// Failed to deserialize payload bytes
     #[error("Failed to deserialize transaction payload: {0}")]
     DeserializeFailed(String),
+    /// Failed to deserialize checkpoint bytes
+    #[error("Failed to deserialize checkpoint payload: {0}")]
+    CheckpointDeserializeFailed(String),
 }
 
 #[cfg(test)]
@@ -99,6 +247,9 @@ mod tests {
             version: 42,
             puts: vec![],
             deletes: vec![],
+            message: None,
+            metadata: BTreeMap::new(),
+            ttls: Vec::new(),
         };
         let bytes = payload.to_bytes();
         let decoded = TransactionPayload::from_bytes(&bytes).unwrap();
@@ -121,6 +272,9 @@ mod tests {
                 (key2.clone(), Value::String("hello".to_string())),
             ],
             deletes: vec![key3.clone()],
+            message: None,
+            metadata: BTreeMap::new(),
+            ttls: Vec::new(),
         };
 
         let bytes = payload.to_bytes();
@@ -134,6 +288,117 @@ mod tests {
         assert_eq!(decoded.deletes[0], key3);
     }
 
+    #[test]
+    fn test_roundtrip_commit_message() {
+        let payload = TransactionPayload {
+            version: 7,
+            puts: vec![],
+            deletes: vec![],
+            message: Some("apply tool call".to_string()),
+            metadata: BTreeMap::from([("agent".to_string(), "planner".to_string())]),
+            ttls: Vec::new(),
+        };
+
+        let decoded = TransactionPayload::from_bytes(&payload.to_bytes()).unwrap();
+        assert_eq!(decoded.message.as_deref(), Some("apply tool call"));
+        assert_eq!(decoded.metadata["agent"], "planner");
+    }
+
+    #[test]
+    fn test_decode_payload_without_commit_message() {
+        // Payload layout written before commit messages were added
+        #[derive(Serialize)]
+        struct LegacyPayload {
+            version: u64,
+            puts: Vec<(Key, Value)>,
+            deletes: Vec<Key>,
+        }
+        let key = Key::new_kv(test_ns(), "k");
+        let bytes = rmp_serde::to_vec(&LegacyPayload {
+            version: 3,
+            puts: vec![(key.clone(), Value::Int(1))],
+            deletes: vec![],
+        })
+        .unwrap();
+
+        let decoded = TransactionPayload::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.version, 3);
+        assert_eq!(decoded.puts[0].0, key);
+        assert!(decoded.message.is_none());
+        assert!(decoded.metadata.is_empty());
+        assert!(decoded.ttls.is_empty());
+    }
+
+    #[test]
+    fn test_roundtrip_ttls() {
+        let key = Key::new_kv(test_ns(), "lease");
+        let mut txn = TransactionContext::new(1, BranchId::new(), 0);
+        txn.put_with_ttl(key.clone(), Value::Int(1), Some(Duration::from_secs(30)))
+            .unwrap();
+
+        let payload = TransactionPayload::from_transaction(&txn, 5);
+        let decoded = TransactionPayload::from_bytes(&payload.to_bytes()).unwrap();
+        assert_eq!(decoded.ttls, vec![(key, Duration::from_secs(30))]);
+    }
+
+    #[test]
+    fn test_checkpoint_capture_and_apply() {
+        let ns = test_ns();
+        let k1 = Key::new_kv(ns.clone(), "a");
+        let k2 = Key::new_kv(ns, "b");
+
+        let source = ShardedStore::new();
+        source
+            .apply_batch_at(&[(k1.clone(), Value::Int(1))], &[], 1, Timestamp::from(10))
+            .unwrap();
+        source
+            .apply_batch_at(
+                &[(k2.clone(), Value::Int(2))],
+                std::slice::from_ref(&k1),
+                2,
+                Timestamp::from(20),
+            )
+            .unwrap();
+        source
+            .apply_batch_at(&[(k1.clone(), Value::Int(3))], &[], 3, Timestamp::from(30))
+            .unwrap();
+
+        let payload = CheckpointPayload::capture(&source, 2, 9).unwrap();
+        assert_eq!(payload.batches.len(), 2);
+
+        let decoded = CheckpointPayload::from_bytes(&payload.to_bytes()).unwrap();
+        assert_eq!(decoded.version, 2);
+        assert_eq!(decoded.max_txn_id, 9);
+
+        let restored = ShardedStore::new();
+        decoded.apply(&restored).unwrap();
+        assert!(restored.get_at_timestamp(&k1, 25).unwrap().is_none());
+        assert_eq!(
+            restored.get_at_timestamp(&k1, 15).unwrap().unwrap().value,
+            Value::Int(1)
+        );
+        assert_eq!(
+            restored.get_at_timestamp(&k2, 25).unwrap().unwrap().value,
+            Value::Int(2)
+        );
+        // Versions above the checkpoint are left to WAL replay
+        assert!(restored.get_at_timestamp(&k1, 35).unwrap().is_none());
+
+        // An incremental checkpoint picks up only what changed since
+        let mut delta = CheckpointPayload::capture_since(&source, 2, 3, 10).unwrap();
+        delta.parents = vec![1];
+        assert!(delta.is_incremental());
+        assert_eq!(delta.batches.len(), 1);
+        let decoded = CheckpointPayload::from_bytes(&delta.to_bytes()).unwrap();
+        assert_eq!(decoded.base_version, 2);
+        assert_eq!(decoded.parents, vec![1]);
+        decoded.apply(&restored).unwrap();
+        assert_eq!(
+            restored.get_at_timestamp(&k1, 35).unwrap().unwrap().value,
+            Value::Int(3)
+        );
+    }
+
     #[test]
     fn test_invalid_bytes() {
         let result = TransactionPayload::from_bytes(&[0xFF, 0x00, 0x01]);
@@ -8,35 +8,46 @@
 //!
 //! ## Recovery Procedure
 //!
-//! 1. Load snapshot (if exists) - not implemented in M2
+//! 1. Load the checkpoint snapshot (if one is configured), together with the
+//!    snapshots an incremental checkpoint builds on
 //! 2. Scan segmented WAL directory for records
 //! 3. Each WalRecord = one committed transaction (TransactionPayload)
-//! 4. Apply all records in order
+//! 4. Apply the records committed after the checkpoint, in order
 //! 5. Initialize TransactionManager with final version
 
-use crate::payload::TransactionPayload;
+use crate::payload::{CheckpointPayload, TransactionPayload};
 use crate::TransactionManager;
-use std::path::PathBuf;
-use strata_core::traits::Storage;
+use std::path::{Path, PathBuf};
 use strata_core::StrataResult;
-use strata_durability::codec::IdentityCodec;
-use strata_durability::wal::WalReader;
-use strata_storage::ShardedStore;
+use strata_core::Timestamp;
+use strata_durability::codec::{IdentityCodec, StorageCodec};
+use strata_durability::disk_snapshot::SnapshotReader;
+use strata_durability::format::snapshot::primitive_tags;
+use strata_durability::format::snapshot_path;
+use strata_durability::wal::{WalReader, WalReaderError};
+use strata_storage::{ShardedStore, SpillConfig};
+use tracing::warn;
 
 /// Coordinates database recovery after crash or restart
 ///
 /// Per spec Section 5.4:
-/// 1. Loads checkpoint (if exists) - not implemented in M2
+/// 1. Loads the checkpoint snapshot (if one is configured)
 /// 2. Reads all WAL records from the segmented WAL directory
 /// 3. Each record is a committed transaction (one WalRecord per txn)
-/// 4. Applies all writes/deletes with version preservation
+/// 4. Applies writes/deletes not covered by the checkpoint, with version
+///    preservation
 /// 5. Initializes TransactionManager with final version
 pub struct RecoveryCoordinator {
     /// Path to WAL directory (contains wal-NNNNNN.seg files)
     wal_dir: PathBuf,
-    /// Path to snapshot directory (optional, not used in M2)
-    #[allow(dead_code)]
+    /// Path to the checkpoint snapshot file (snap-NNNNNN.chk), if any
     snapshot_path: Option<PathBuf>,
+    /// Codec the WAL was written with
+    codec: Box<dyn StorageCodec>,
+    /// Stop replay before the first commit after this timestamp, if set
+    target_timestamp: Option<u64>,
+    /// Memory budget and spill directory for the recovered store, if set
+    spill: Option<SpillConfig>,
 }
 
 impl RecoveryCoordinator {
@@ -48,19 +59,143 @@ impl RecoveryCoordinator {
         RecoveryCoordinator {
             wal_dir,
             snapshot_path: None,
+            codec: Box::new(IdentityCodec),
+            target_timestamp: None,
+            spill: None,
         }
     }
 
-    /// Set snapshot path for checkpoint-based recovery (M3+ feature)
+    /// Decode WAL records with `codec` (defaults to the identity codec)
+    pub fn with_codec(mut self, codec: Box<dyn StorageCodec>) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Start recovery from the checkpoint snapshot at `path`
     ///
-    /// Note: Snapshot-based recovery is not implemented in M2.
-    /// This method is provided for future extensibility.
-    #[allow(dead_code)]
-    pub(crate) fn with_snapshot_path(mut self, path: PathBuf) -> Self {
+    /// The snapshot's storage state is loaded first and only WAL records
+    /// committed after it are replayed. An incremental snapshot also loads
+    /// the snapshots it builds on from the same directory. Snapshots written
+    /// before checkpoints carried the storage state are ignored.
+    pub fn with_snapshot_path(mut self, path: PathBuf) -> Self {
         self.snapshot_path = Some(path);
         self
     }
 
+    /// Recover the state as of `timestamp` (microseconds since epoch)
+    ///
+    /// WAL replay stops before the first commit made after `timestamp`.
+    /// Recovery fails with `InvalidInput` if the checkpoint already holds
+    /// commits made after it, since those cannot be undone.
+    pub fn with_target_timestamp(mut self, timestamp: u64) -> Self {
+        self.target_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Recover into a store that spills to disk beyond a memory budget
+    ///
+    /// The budget applies during replay too, so a WAL larger than memory
+    /// can be recovered.
+    pub fn with_spill(mut self, config: SpillConfig) -> Self {
+        self.spill = Some(config);
+        self
+    }
+
+    /// Load the checkpoint chain from the configured snapshot, if any
+    ///
+    /// Returns the checkpoints to apply in order: the full checkpoint first,
+    /// then each incremental one up to the configured snapshot.
+    ///
+    /// An unreadable or missing snapshot (or a broken chain) is only
+    /// tolerated while the WAL still holds the full history (its first
+    /// segment was never compacted away); otherwise recovery would silently
+    /// lose data, so it fails with `Corruption`.
+    fn load_checkpoint(&self) -> StrataResult<Option<Vec<CheckpointPayload>>> {
+        let path = match &self.snapshot_path {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        match self.load_chain(path) {
+            Ok(chain) => Ok(chain),
+            Err(e) => {
+                if self.wal_has_full_history() {
+                    warn!(
+                        target: "strata::recovery",
+                        path = %path.display(),
+                        error = %e,
+                        "Checkpoint snapshot unreadable, replaying the full WAL"
+                    );
+                    return Ok(None);
+                }
+                Err(strata_core::StrataError::corruption(format!(
+                    "checkpoint snapshot {} is unreadable and the WAL it covers was compacted: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        }
+    }
+
+    /// Read the snapshot at `path` and the snapshots it builds on
+    fn load_chain(&self, path: &Path) -> Result<Option<Vec<CheckpointPayload>>, String> {
+        let latest = match self.read_checkpoint(path)? {
+            Some(latest) => latest,
+            None => return Ok(None),
+        };
+
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let mut chain = Vec::with_capacity(latest.parents.len() + 1);
+        for &id in &latest.parents {
+            let parent_path = snapshot_path(dir, id);
+            match self.read_checkpoint(&parent_path)? {
+                Some(parent) => chain.push(parent),
+                None => {
+                    return Err(format!(
+                        "snapshot {} has no storage section",
+                        parent_path.display()
+                    ))
+                }
+            }
+        }
+        chain.push(latest);
+
+        // Each link must start exactly where the previous one ended
+        let mut previous = 0;
+        for checkpoint in &chain {
+            if checkpoint.base_version != previous || checkpoint.version < previous {
+                return Err(format!(
+                    "checkpoint chain is broken: expected base version {}, found {}",
+                    previous, checkpoint.base_version
+                ));
+            }
+            previous = checkpoint.version;
+        }
+        Ok(Some(chain))
+    }
+
+    /// Decode the storage section of the snapshot at `path`
+    fn read_checkpoint(&self, path: &Path) -> Result<Option<CheckpointPayload>, String> {
+        let snapshot = SnapshotReader::new(self.codec.clone_box())
+            .load(path)
+            .map_err(|e| e.to_string())?;
+        let section = match snapshot.find_section(primitive_tags::STORAGE) {
+            Some(section) => section,
+            None => return Ok(None),
+        };
+        CheckpointPayload::from_bytes(&section.data)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Whether the WAL still starts at its first segment
+    fn wal_has_full_history(&self) -> bool {
+        match WalReader::new(self.codec.clone_box()).list_segments(&self.wal_dir) {
+            Ok(segments) => segments.first().map_or(true, |&first| first == 1),
+            Err(_) => !self.wal_dir.exists(),
+        }
+    }
+
     /// Perform recovery and return initialized components
     ///
     /// Each WalRecord in the segmented WAL represents a single committed
@@ -73,26 +208,67 @@ impl RecoveryCoordinator {
     /// # Errors
     /// - If WAL directory cannot be read
     /// - If record deserialization fails
+    /// - `Corruption` if a record cannot be decoded by the codec (wrong key)
+    /// - `Corruption` if the checkpoint snapshot is needed but unreadable
+    /// - If the spill directory cannot be created or cleared
     pub fn recover(&self) -> StrataResult<RecoveryResult> {
-        let storage = ShardedStore::new();
+        let storage = match &self.spill {
+            Some(config) => ShardedStore::with_spill(config.clone())?,
+            None => ShardedStore::new(),
+        };
         let mut max_version = 0u64;
         let mut max_txn_id = 0u64;
         let mut stats = RecoveryStats::default();
 
-        // If WAL dir doesn't exist, return empty result
+        // Load the checkpoint first; WAL records it covers are skipped below
+        let checkpoint_version = match self.load_checkpoint()? {
+            Some(chain) => {
+                if let Some(target) = self.target_timestamp {
+                    let newest = chain
+                        .iter()
+                        .flat_map(|c| c.batches.iter().map(|b| b.timestamp))
+                        .max()
+                        .unwrap_or(0);
+                    if newest > target {
+                        return Err(strata_core::StrataError::invalid_input(format!(
+                            "recovery target {} precedes the checkpoint (its newest commit is at {})",
+                            target, newest
+                        )));
+                    }
+                }
+                for checkpoint in &chain {
+                    checkpoint.apply(&storage)?;
+                    max_txn_id = max_txn_id.max(checkpoint.max_txn_id);
+                }
+                let latest = &chain[chain.len() - 1];
+                max_version = latest.version;
+                stats.from_checkpoint = true;
+                stats.checkpoint_version = latest.version;
+                stats.checkpoint_parents = latest.parents.clone();
+                Some(latest.version)
+            }
+            None => None,
+        };
+
+        // If WAL dir doesn't exist, return what the checkpoint held
         if !self.wal_dir.exists() {
+            stats.final_version = max_version;
+            stats.max_txn_id = max_txn_id;
             return Ok(RecoveryResult {
                 storage,
-                txn_manager: TransactionManager::new(0),
+                txn_manager: TransactionManager::with_txn_id(max_version, max_txn_id),
                 stats,
             });
         }
 
         // Read all records from segmented WAL
-        let reader = WalReader::new(Box::new(IdentityCodec));
-        let read_result = reader
-            .read_all(&self.wal_dir)
-            .map_err(|e| strata_core::StrataError::storage(format!("WAL read failed: {}", e)))?;
+        let reader = WalReader::new(self.codec.clone_box());
+        let read_result = reader.read_all(&self.wal_dir).map_err(|e| match e {
+            WalReaderError::Codec(e) => {
+                strata_core::StrataError::corruption(format!("WAL decode failed: {}", e))
+            }
+            e => strata_core::StrataError::storage(format!("WAL read failed: {}", e)),
+        })?;
 
         for record in &read_result.records {
             max_txn_id = max_txn_id.max(record.txn_id);
@@ -104,20 +280,27 @@ impl RecoveryCoordinator {
                 ))
             })?;
 
-            max_version = max_version.max(payload.version);
-
-            // Apply puts
-            for (key, value) in &payload.puts {
-                storage.put_with_version(key.clone(), value.clone(), payload.version, None)?;
-                stats.writes_applied += 1;
+            if checkpoint_version.is_some_and(|v| payload.version <= v) {
+                continue;
             }
-
-            // Apply deletes — use trait method explicitly so Storage::version is updated
-            for key in &payload.deletes {
-                Storage::delete_with_version(&storage, key, payload.version)?;
-                stats.deletes_applied += 1;
+            if self.target_timestamp.is_some_and(|t| record.timestamp > t) {
+                break;
             }
 
+            max_version = max_version.max(payload.version);
+
+            // Apply puts and deletes with the original commit timestamp so
+            // time-travel reads (as_of) survive a restart
+            storage.apply_batch_with_ttls(
+                &payload.puts,
+                &payload.deletes,
+                &payload.ttls,
+                payload.version,
+                Timestamp::from(record.timestamp),
+            )?;
+            stats.writes_applied += payload.puts.len();
+            stats.deletes_applied += payload.deletes.len();
+
             stats.txns_replayed += 1;
         }
 
@@ -206,10 +389,19 @@ pub struct RecoveryStats {
     /// transactions already in the WAL.
     pub max_txn_id: u64,
 
-    /// Whether recovery was from checkpoint
+    /// Whether recovery started from a checkpoint snapshot
     ///
-    /// In M2, this is always false as checkpoint-based recovery is not implemented.
+    /// When true, `txns_replayed` only counts WAL records committed after
+    /// the checkpoint.
     pub from_checkpoint: bool,
+
+    /// Highest commit version covered by the checkpoint (0 without one)
+    pub checkpoint_version: u64,
+
+    /// Snapshot IDs the loaded checkpoint builds on, full snapshot first
+    ///
+    /// Empty when the checkpoint was a full one (or there was none).
+    pub checkpoint_parents: Vec<u64>,
 }
 
 impl RecoveryStats {
@@ -228,6 +420,7 @@ impl RecoveryStats {
 mod tests {
     use super::*;
     use crate::payload::TransactionPayload;
+    use strata_core::traits::Storage;
     use strata_core::types::{BranchId, Key, Namespace};
     use strata_core::value::Value;
     use strata_durability::codec::IdentityCodec;
@@ -270,6 +463,9 @@ mod tests {
             version,
             puts,
             deletes,
+            message: None,
+            metadata: Default::default(),
+            ttls: Vec::new(),
         };
         let record = WalRecord::new(
             txn_id,
@@ -344,6 +540,51 @@ mod tests {
         assert_eq!(stored.version.as_u64(), 100);
     }
 
+    #[test]
+    fn test_recovery_preserves_commit_timestamps() {
+        let temp_dir = TempDir::new().unwrap();
+        let wal_dir = temp_dir.path().join("wal");
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = Key::new_kv(ns, "ts_key");
+
+        {
+            let mut wal = create_test_wal(&wal_dir);
+            for (txn_id, ts, value) in [(1u64, 1_000u64, 1i64), (2, 2_000, 2)] {
+                let payload = TransactionPayload {
+                    version: txn_id,
+                    puts: vec![(key.clone(), Value::Int(value))],
+                    deletes: vec![],
+                    message: None,
+                    metadata: Default::default(),
+                    ttls: Vec::new(),
+                };
+                let record = WalRecord::new(txn_id, *branch_id.as_bytes(), ts, payload.to_bytes());
+                wal.append(&record).unwrap();
+            }
+            let payload = TransactionPayload {
+                version: 3,
+                puts: vec![],
+                deletes: vec![key.clone()],
+                message: None,
+                metadata: Default::default(),
+                ttls: Vec::new(),
+            };
+            let record = WalRecord::new(3, *branch_id.as_bytes(), 3_000, payload.to_bytes());
+            wal.append(&record).unwrap();
+            wal.flush().unwrap();
+        }
+
+        let result = RecoveryCoordinator::new(wal_dir).recover().unwrap();
+        let at = |ts| result.storage.get_at_timestamp(&key, ts).unwrap();
+
+        assert!(at(500).is_none());
+        assert_eq!(at(1_500).unwrap().value, Value::Int(1));
+        assert_eq!(at(2_500).unwrap().value, Value::Int(2));
+        assert!(at(3_500).is_none());
+    }
+
     #[test]
     fn test_recovery_version_preservation() {
         let temp_dir = TempDir::new().unwrap();
@@ -493,6 +734,8 @@ mod tests {
             final_version: 100,
             max_txn_id: 8,
             from_checkpoint: false,
+            checkpoint_version: 0,
+            checkpoint_parents: Vec::new(),
         };
 
         assert_eq!(stats.total_operations(), 13);
@@ -514,6 +757,233 @@ mod tests {
         assert!(!result.stats.from_checkpoint);
     }
 
+    /// Helper: write a checkpoint snapshot of `storage` up to `version`
+    fn write_checkpoint(
+        dir: &std::path::Path,
+        storage: &ShardedStore,
+        version: u64,
+        max_txn_id: u64,
+    ) -> PathBuf {
+        use strata_durability::disk_snapshot::{CheckpointCoordinator, CheckpointData};
+
+        let mut coordinator =
+            CheckpointCoordinator::new(dir.to_path_buf(), Box::new(IdentityCodec), [0u8; 16])
+                .unwrap();
+        let payload = CheckpointPayload::capture(storage, version, max_txn_id).unwrap();
+        let info = coordinator
+            .checkpoint(
+                max_txn_id,
+                CheckpointData::new().with_storage(payload.to_bytes()),
+            )
+            .unwrap();
+        strata_durability::format::snapshot_path(dir, info.snapshot_id)
+    }
+
+    #[test]
+    fn test_recovery_from_checkpoint_skips_covered_records() {
+        let temp_dir = TempDir::new().unwrap();
+        let wal_dir = temp_dir.path().join("wal");
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = Key::new_kv(ns, "k");
+
+        {
+            let mut wal = create_test_wal(&wal_dir);
+            for version in 1..=3 {
+                write_txn(
+                    &mut wal,
+                    version,
+                    branch_id,
+                    vec![(key.clone(), Value::Int(version as i64))],
+                    vec![],
+                    version,
+                );
+            }
+        }
+
+        // Checkpoint the state after the first two commits
+        let live = RecoveryCoordinator::new(wal_dir.clone()).recover().unwrap();
+        let snapshot = write_checkpoint(temp_dir.path(), &live.storage, 2, 2);
+
+        let result = RecoveryCoordinator::new(wal_dir)
+            .with_snapshot_path(snapshot)
+            .recover()
+            .unwrap();
+
+        assert!(result.stats.from_checkpoint);
+        assert_eq!(result.stats.txns_replayed, 1);
+        assert_eq!(result.stats.final_version, 3);
+        assert_eq!(result.stats.max_txn_id, 3);
+        assert_eq!(
+            result.storage.get(&key).unwrap().unwrap().value,
+            Value::Int(3)
+        );
+        // History from the checkpoint is kept
+        assert_eq!(
+            result
+                .storage
+                .get_versioned(&key, 1)
+                .unwrap()
+                .unwrap()
+                .value,
+            Value::Int(1)
+        );
+    }
+
+    #[test]
+    fn test_recovery_from_incremental_checkpoint_chain() {
+        use strata_durability::disk_snapshot::{CheckpointCoordinator, CheckpointData};
+
+        let temp_dir = TempDir::new().unwrap();
+        let wal_dir = temp_dir.path().join("wal");
+        let snapshots_dir = temp_dir.path().join("snapshots");
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = Key::new_kv(ns.clone(), "k");
+        let other = Key::new_kv(ns, "other");
+
+        {
+            let mut wal = create_test_wal(&wal_dir);
+            write_txn(
+                &mut wal,
+                1,
+                branch_id,
+                vec![(other.clone(), Value::Int(0))],
+                vec![],
+                1,
+            );
+            for version in 2..=4 {
+                write_txn(
+                    &mut wal,
+                    version,
+                    branch_id,
+                    vec![(key.clone(), Value::Int(version as i64))],
+                    vec![],
+                    version,
+                );
+            }
+        }
+        let live = RecoveryCoordinator::new(wal_dir.clone()).recover().unwrap();
+
+        // Full checkpoint at version 2, then a delta covering version 3
+        let mut coordinator =
+            CheckpointCoordinator::new(snapshots_dir.clone(), Box::new(IdentityCodec), [0u8; 16])
+                .unwrap();
+        let full = CheckpointPayload::capture(&live.storage, 2, 2).unwrap();
+        let base = coordinator
+            .checkpoint(2, CheckpointData::new().with_storage(full.to_bytes()))
+            .unwrap();
+        let mut delta = CheckpointPayload::capture_since(&live.storage, 2, 3, 3).unwrap();
+        delta.parents = vec![base.snapshot_id];
+        assert_eq!(delta.batches.len(), 1);
+        let latest = coordinator
+            .checkpoint(3, CheckpointData::new().with_storage(delta.to_bytes()))
+            .unwrap();
+        let latest_path = snapshot_path(&snapshots_dir, latest.snapshot_id);
+
+        let result = RecoveryCoordinator::new(wal_dir.clone())
+            .with_snapshot_path(latest_path.clone())
+            .recover()
+            .unwrap();
+        assert!(result.stats.from_checkpoint);
+        assert_eq!(result.stats.checkpoint_version, 3);
+        assert_eq!(result.stats.checkpoint_parents, vec![base.snapshot_id]);
+        assert_eq!(result.stats.txns_replayed, 1);
+        assert_eq!(
+            result.storage.get(&other).unwrap().unwrap().value,
+            Value::Int(0)
+        );
+        assert_eq!(
+            result.storage.get(&key).unwrap().unwrap().value,
+            Value::Int(4)
+        );
+
+        // A missing base breaks the chain; with the full WAL still present
+        // recovery falls back to replaying it
+        std::fs::remove_file(snapshot_path(&snapshots_dir, base.snapshot_id)).unwrap();
+        let result = RecoveryCoordinator::new(wal_dir)
+            .with_snapshot_path(latest_path)
+            .recover()
+            .unwrap();
+        assert!(!result.stats.from_checkpoint);
+        assert_eq!(result.stats.txns_replayed, 4);
+    }
+
+    #[test]
+    fn test_recovery_stops_at_target_timestamp() {
+        let temp_dir = TempDir::new().unwrap();
+        let wal_dir = temp_dir.path().join("wal");
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = Key::new_kv(ns, "k");
+
+        {
+            let mut wal = create_test_wal(&wal_dir);
+            for version in 1..=3u64 {
+                let payload = TransactionPayload {
+                    version,
+                    puts: vec![(key.clone(), Value::Int(version as i64))],
+                    deletes: vec![],
+                    message: None,
+                    metadata: Default::default(),
+                    ttls: Vec::new(),
+                };
+                let record = WalRecord::new(
+                    version,
+                    *branch_id.as_bytes(),
+                    version * 100,
+                    payload.to_bytes(),
+                );
+                wal.append(&record).unwrap();
+            }
+            wal.flush().unwrap();
+        }
+
+        let result = RecoveryCoordinator::new(wal_dir.clone())
+            .with_target_timestamp(250)
+            .recover()
+            .unwrap();
+        assert_eq!(result.stats.txns_replayed, 2);
+        assert_eq!(result.stats.final_version, 2);
+        assert_eq!(
+            result.storage.get(&key).unwrap().unwrap().value,
+            Value::Int(2)
+        );
+
+        // A checkpoint holding commits after the target cannot be rewound
+        let live = RecoveryCoordinator::new(wal_dir.clone()).recover().unwrap();
+        let snapshot = write_checkpoint(temp_dir.path(), &live.storage, 3, 3);
+        let result = RecoveryCoordinator::new(wal_dir)
+            .with_snapshot_path(snapshot)
+            .with_target_timestamp(250)
+            .recover();
+        assert!(matches!(
+            result,
+            Err(strata_core::StrataError::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn test_recovery_missing_checkpoint_after_compaction_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let wal_dir = temp_dir.path().join("wal");
+        std::fs::create_dir_all(&wal_dir).unwrap();
+
+        // The first segment is gone, as it would be after compaction
+        strata_durability::format::WalSegment::create(&wal_dir, 2, [0u8; 16]).unwrap();
+
+        let result = RecoveryCoordinator::new(wal_dir)
+            .with_snapshot_path(temp_dir.path().join("snap-000001.chk"))
+            .recover();
+        assert!(matches!(
+            result,
+            Err(strata_core::StrataError::Corruption { .. })
+        ));
+    }
+
     // ========================================
     // Crash Scenario Tests
     // ========================================
@@ -51,7 +51,7 @@ impl std::fmt::Display for CommitError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CommitError::ValidationFailed(result) => {
-                write!(f, "Commit failed: {} conflict(s)", result.conflict_count())
+                write!(f, "Commit failed: {}", result)
             }
             CommitError::InvalidState(msg) => write!(f, "Invalid state: {}", msg),
             CommitError::WALError(msg) => write!(f, "WAL error: {}", msg),
@@ -67,7 +67,7 @@ impl From<CommitError> for StrataError {
     fn from(e: CommitError) -> Self {
         match e {
             CommitError::ValidationFailed(result) => StrataError::TransactionAborted {
-                reason: format!("Validation failed: {} conflict(s)", result.conflict_count()),
+                reason: format!("Validation failed: {}", result),
             },
             CommitError::InvalidState(msg) => StrataError::TransactionNotActive { state: msg },
             CommitError::WALError(msg) => StrataError::Storage {
@@ -131,6 +131,29 @@ impl PendingOperations {
     }
 }
 
+/// Isolation level of a transaction
+///
+/// Both levels read from a snapshot taken at transaction start and validate
+/// the read-set at commit (first-committer-wins). They differ in how range
+/// reads are protected:
+///
+/// - `Snapshot`: keys returned by `scan_prefix` are validated, but keys
+///   inserted under the prefix by a concurrent commit are not. Two
+///   transactions can each scan a range, see no conflict, and insert into it
+///   (write skew through phantoms).
+/// - `Serializable`: every prefix scanned is recorded as a predicate and
+///   re-scanned at commit. A key that appeared under the prefix since the
+///   snapshot aborts the transaction with a phantom conflict, so constraints
+///   spanning several keys cannot be jointly violated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IsolationLevel {
+    /// Snapshot isolation (default)
+    #[default]
+    Snapshot,
+    /// Serializable: snapshot isolation plus phantom detection on scans
+    Serializable,
+}
+
 /// Status of a transaction in its lifecycle
 ///
 /// State transitions:
@@ -327,6 +350,24 @@ pub trait JsonStoreExt {
     fn json_exists(&mut self, key: &Key) -> StrataResult<bool>;
 }
 
+/// Buffered state captured by a savepoint
+///
+/// Only what a rollback undoes is captured. The read-set, scan predicates and
+/// JSON snapshot versions are never rolled back: a value read after the
+/// savepoint may still have influenced what the transaction goes on to write.
+#[derive(Debug, Clone)]
+struct Savepoint {
+    /// Name given to `savepoint()`, or None for a nested `transaction()`
+    name: Option<String>,
+    write_set: HashMap<Key, Value>,
+    delete_set: HashSet<Key>,
+    cas_set: Vec<CASOperation>,
+    json_writes: Option<Vec<JsonPatchEntry>>,
+    ttls: Option<HashMap<Key, Duration>>,
+    event_sequence_count: Option<u64>,
+    event_last_hash: Option<[u8; 32]>,
+}
+
 /// Transaction context for OCC with snapshot isolation
 ///
 /// Tracks all reads, writes, deletes, and CAS operations for a transaction.
@@ -351,6 +392,10 @@ pub trait JsonStoreExt {
 /// 2. **READ/WRITE**: Use `get()`, `put()`, `delete()`, `cas()`
 /// 3. **VALIDATE**: Call `mark_validating()`, check for conflicts
 /// 4. **COMMIT/ABORT**: Call `mark_committed()` or `mark_aborted()`
+///
+/// While active, `savepoint()` / `rollback_to_savepoint()` undo part of the
+/// buffered work, and `transaction()` runs a nested closure that is undone on
+/// error.
 pub struct TransactionContext {
     // Identity
     /// Unique transaction ID
@@ -422,6 +467,33 @@ pub struct TransactionContext {
     /// Only allocated when JSON operations are performed.
     json_snapshot_versions: Option<HashMap<Key, u64>>,
 
+    // TTLs (lazy allocation, most writes never expire)
+    /// Time-to-live of buffered writes, relative to the commit time
+    ///
+    /// Only allocated when a write is buffered with a TTL.
+    ttls: Option<HashMap<Key, Duration>>,
+
+    // Isolation
+    /// Isolation level, `Snapshot` unless set with `set_isolation()`
+    isolation: IsolationLevel,
+
+    /// Prefixes scanned by a serializable transaction
+    ///
+    /// Re-scanned at commit to detect phantoms. Only allocated when a
+    /// serializable transaction scans.
+    scan_predicates: Option<Vec<Key>>,
+
+    // Savepoints
+    /// Active savepoints, oldest first
+    savepoints: Vec<Savepoint>,
+
+    // Commit annotations
+    /// Message recorded with the commit in the WAL
+    pub commit_message: Option<String>,
+
+    /// Metadata recorded with the commit in the WAL
+    pub commit_metadata: BTreeMap<String, String>,
+
     // State
     /// Current transaction status
     pub status: TransactionStatus,
@@ -469,6 +541,12 @@ impl TransactionContext {
             json_reads: None,
             json_writes: None,
             json_snapshot_versions: None,
+            ttls: None,
+            isolation: IsolationLevel::Snapshot,
+            scan_predicates: None,
+            savepoints: Vec::new(),
+            commit_message: None,
+            commit_metadata: BTreeMap::new(),
             status: TransactionStatus::Active,
             start_time: Instant::now(),
         }
@@ -517,6 +595,12 @@ impl TransactionContext {
             json_reads: None,
             json_writes: None,
             json_snapshot_versions: None,
+            ttls: None,
+            isolation: IsolationLevel::Snapshot,
+            scan_predicates: None,
+            savepoints: Vec::new(),
+            commit_message: None,
+            commit_metadata: BTreeMap::new(),
             status: TransactionStatus::Active,
             start_time: Instant::now(),
         }
@@ -663,6 +747,8 @@ impl TransactionContext {
     /// - Includes uncommitted writes from this transaction matching prefix
     /// - Excludes uncommitted deletes from this transaction
     /// - Tracks all scanned keys from snapshot in read_set
+    /// - Under `IsolationLevel::Serializable`, also records the prefix so that
+    ///   keys inserted under it by concurrent commits are detected as phantoms
     ///
     /// Results are sorted by key order.
     ///
@@ -694,6 +780,14 @@ impl TransactionContext {
         // Get all matching keys from snapshot
         let snapshot_results = snapshot.scan_prefix(prefix)?;
 
+        // Record the predicate so commit can detect inserts into the range
+        if self.isolation == IsolationLevel::Serializable {
+            let predicates = self.scan_predicates.get_or_insert_with(Vec::new);
+            if !predicates.contains(prefix) {
+                predicates.push(prefix.clone());
+            }
+        }
+
         // Build result set with read-your-writes using BTreeMap for sorted output
         let mut results: BTreeMap<Key, Value> = BTreeMap::new();
 
@@ -765,6 +859,7 @@ impl TransactionContext {
 
         // Remove from delete_set if previously deleted in this txn
         self.delete_set.remove(&key);
+        self.clear_ttl(&key);
 
         // Add to write_set (overwrites any previous write to same key)
         self.write_set.insert(key, value);
@@ -803,12 +898,73 @@ impl TransactionContext {
 
         // Remove from write_set if previously written in this txn
         self.write_set.remove(&key);
+        self.clear_ttl(&key);
 
         // Add to delete_set
         self.delete_set.insert(key);
         Ok(())
     }
 
+    /// Buffer a write that expires `ttl` after the transaction commits
+    ///
+    /// Same as [`put`](Self::put) with `ttl: None`. Once expired, reads at
+    /// or after the expiry time no longer see the value, and the engine's
+    /// background reaper deletes it.
+    ///
+    /// # Errors
+    /// Returns `StrataError::invalid_input` if transaction is not active.
+    pub fn put_with_ttl(
+        &mut self,
+        key: Key,
+        value: Value,
+        ttl: Option<Duration>,
+    ) -> StrataResult<()> {
+        self.put(key.clone(), value)?;
+        if let Some(ttl) = ttl {
+            self.ttls.get_or_insert_with(HashMap::new).insert(key, ttl);
+        }
+        Ok(())
+    }
+
+    /// Give the buffered write to `key` a TTL
+    ///
+    /// For writes buffered through helpers that only call [`put`](Self::put).
+    ///
+    /// # Errors
+    /// Returns `StrataError::invalid_input` if transaction is not active or
+    /// `key` has no buffered write.
+    pub fn set_ttl(&mut self, key: &Key, ttl: Duration) -> StrataResult<()> {
+        self.ensure_active()?;
+        if !self.write_set.contains_key(key) {
+            return Err(StrataError::invalid_input(format!(
+                "Cannot set a TTL: no write to {:?} in transaction {}",
+                key, self.txn_id
+            )));
+        }
+        self.ttls
+            .get_or_insert_with(HashMap::new)
+            .insert(key.clone(), ttl);
+        Ok(())
+    }
+
+    /// TTL of the buffered write to `key`, if it was written with one
+    pub fn ttl_of(&self, key: &Key) -> Option<Duration> {
+        self.ttls.as_ref().and_then(|ttls| ttls.get(key).copied())
+    }
+
+    /// Buffered writes that carry a TTL
+    pub fn ttls(&self) -> impl Iterator<Item = (&Key, Duration)> {
+        self.ttls
+            .iter()
+            .flat_map(|ttls| ttls.iter().map(|(k, ttl)| (k, *ttl)))
+    }
+
+    fn clear_ttl(&mut self, key: &Key) {
+        if let Some(ttls) = self.ttls.as_mut() {
+            ttls.remove(key);
+        }
+    }
+
     /// Buffer a compare-and-swap operation
     ///
     /// CAS operations are validated at COMMIT time, not call time.
@@ -853,6 +1009,143 @@ impl TransactionContext {
         Ok(())
     }
 
+    // === Savepoints ===
+
+    /// Mark a savepoint that `rollback_to_savepoint()` can return to
+    ///
+    /// Captures the buffered writes, deletes, CAS operations, JSON patches,
+    /// TTLs and event sequence state. Reads are not rolled back: keys read
+    /// after the savepoint stay in the read-set and are validated at commit.
+    ///
+    /// Reusing a name shadows the earlier savepoint of that name until the
+    /// newer one is released.
+    ///
+    /// # Errors
+    /// Returns `StrataError::invalid_input` if transaction is not active.
+    pub fn savepoint(&mut self, name: impl Into<String>) -> StrataResult<()> {
+        self.ensure_active()?;
+        self.push_savepoint(Some(name.into()));
+        Ok(())
+    }
+
+    /// Undo everything buffered since the savepoint `name`
+    ///
+    /// The savepoint itself is kept and can be rolled back to again.
+    /// Savepoints created after it are discarded.
+    ///
+    /// # Errors
+    /// Returns `StrataError::invalid_input` if transaction is not active or
+    /// has no savepoint named `name`.
+    pub fn rollback_to_savepoint(&mut self, name: &str) -> StrataResult<()> {
+        self.ensure_active()?;
+        let index = self.find_savepoint(name)?;
+        self.savepoints.truncate(index + 1);
+        self.restore_savepoint(index);
+        Ok(())
+    }
+
+    /// Release the savepoint `name`, keeping everything buffered since
+    ///
+    /// Savepoints created after it are released too.
+    ///
+    /// # Errors
+    /// Returns `StrataError::invalid_input` if transaction is not active or
+    /// has no savepoint named `name`.
+    pub fn release_savepoint(&mut self, name: &str) -> StrataResult<()> {
+        self.ensure_active()?;
+        let index = self.find_savepoint(name)?;
+        self.savepoints.truncate(index);
+        Ok(())
+    }
+
+    /// Names of the active savepoints, oldest first
+    pub fn savepoint_names(&self) -> impl Iterator<Item = &str> {
+        self.savepoints.iter().filter_map(|sp| sp.name.as_deref())
+    }
+
+    /// Run `f` as a nested transaction
+    ///
+    /// The closure counterpart of a savepoint: if `f` returns an error,
+    /// everything it buffered is undone and the error is returned; otherwise
+    /// its work stays buffered and commits with this transaction.
+    ///
+    /// # Errors
+    /// Returns `StrataError::invalid_input` if transaction is not active, or
+    /// the error returned by `f`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use strata_concurrency::TransactionContext;
+    /// # use strata_core::types::{BranchId, Key, Namespace};
+    /// # use strata_core::value::Value;
+    /// # fn example(txn: &mut TransactionContext) -> strata_core::StrataResult<()> {
+    /// # let ns = Namespace::for_branch(BranchId::default());
+    /// # let step_key = Key::new_kv(ns, "step");
+    /// let attempt = txn.transaction(|txn| {
+    ///     txn.put(step_key.clone(), Value::Int(1))?;
+    ///     Err::<(), _>(strata_core::StrataError::invalid_input("tool failed"))
+    /// });
+    /// assert!(attempt.is_err());
+    /// assert_eq!(txn.get(&step_key)?, None); // the step was undone
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transaction<F, T>(&mut self, f: F) -> StrataResult<T>
+    where
+        F: FnOnce(&mut TransactionContext) -> StrataResult<T>,
+    {
+        self.ensure_active()?;
+        let depth = self.savepoints.len();
+        self.push_savepoint(None);
+
+        let result = f(self);
+
+        // `f` may have released or rolled back past our savepoint; only
+        // restore it if it is still there.
+        if result.is_err() && self.is_active() && self.savepoints.len() > depth {
+            self.restore_savepoint(depth);
+        }
+        self.savepoints.truncate(depth);
+        result
+    }
+
+    fn push_savepoint(&mut self, name: Option<String>) {
+        self.savepoints.push(Savepoint {
+            name,
+            write_set: self.write_set.clone(),
+            delete_set: self.delete_set.clone(),
+            cas_set: self.cas_set.clone(),
+            json_writes: self.json_writes.clone(),
+            ttls: self.ttls.clone(),
+            event_sequence_count: self.event_sequence_count,
+            event_last_hash: self.event_last_hash,
+        });
+    }
+
+    fn find_savepoint(&self, name: &str) -> StrataResult<usize> {
+        self.savepoints
+            .iter()
+            .rposition(|sp| sp.name.as_deref() == Some(name))
+            .ok_or_else(|| {
+                StrataError::invalid_input(format!(
+                    "No savepoint named '{}' in transaction {}",
+                    name, self.txn_id
+                ))
+            })
+    }
+
+    fn restore_savepoint(&mut self, index: usize) {
+        let sp = &self.savepoints[index];
+        self.write_set = sp.write_set.clone();
+        self.delete_set = sp.delete_set.clone();
+        self.cas_set = sp.cas_set.clone();
+        self.json_writes = sp.json_writes.clone();
+        self.ttls = sp.ttls.clone();
+        self.event_sequence_count = sp.event_sequence_count;
+        self.event_last_hash = sp.event_last_hash;
+    }
+
     // === Event State Tracking ===
 
     /// Get the current event sequence count tracked across Transaction instances.
@@ -963,7 +1256,8 @@ impl TransactionContext {
     /// This is useful for retry scenarios where you want to restart
     /// a transaction's operations without creating a new transaction.
     ///
-    /// Clears: read_set, write_set, delete_set, cas_set, and all JSON operation sets
+    /// Clears: read_set, write_set, delete_set, cas_set, TTLs, scan predicates,
+    /// savepoints, and all JSON operation sets
     ///
     /// Note: Does not change transaction state or snapshot.
     ///
@@ -980,6 +1274,9 @@ impl TransactionContext {
         self.json_reads = None;
         self.json_writes = None;
         self.json_snapshot_versions = None;
+        self.ttls = None;
+        self.scan_predicates = None;
+        self.savepoints.clear();
         Ok(())
     }
 
@@ -1149,6 +1446,7 @@ impl TransactionContext {
                 self.write_set.clear();
                 self.delete_set.clear();
                 self.cas_set.clear();
+                self.ttls = None;
 
                 // Note: read_set is kept for debugging/diagnostics
 
@@ -1213,9 +1511,8 @@ impl TransactionContext {
 
         if !validation_result.is_valid() {
             // Step 3a: Validation failed - abort
-            let conflict_count = validation_result.conflict_count();
             self.status = TransactionStatus::Aborted {
-                reason: format!("Commit failed: {} conflict(s) detected", conflict_count),
+                reason: format!("Commit failed: {}", validation_result),
             };
             return Err(CommitError::ValidationFailed(validation_result));
         }
@@ -1269,7 +1566,7 @@ impl TransactionContext {
 
         // Apply puts from write_set
         for (key, value) in &self.write_set {
-            store.put_with_version(key.clone(), value.clone(), commit_version, None)?;
+            store.put_with_version(key.clone(), value.clone(), commit_version, self.ttl_of(key))?;
             result.puts_applied += 1;
         }
 
@@ -1333,6 +1630,40 @@ impl TransactionContext {
         self.write_set.is_empty() && self.delete_set.is_empty() && self.cas_set.is_empty()
     }
 
+    /// Set the message recorded with this transaction's commit
+    ///
+    /// The message is stored in the WAL alongside the writeset and shows up
+    /// in the branch commit log. Read-only transactions write no commit, so
+    /// their message is dropped.
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.commit_message = Some(message.into());
+    }
+
+    /// Add a metadata entry recorded with this transaction's commit
+    pub fn set_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.commit_metadata.insert(key.into(), value.into());
+    }
+
+    /// Set the isolation level of this transaction
+    ///
+    /// Call before the first read: scans made earlier are not recorded as
+    /// predicates and so are not checked for phantoms.
+    pub fn set_isolation(&mut self, isolation: IsolationLevel) {
+        self.isolation = isolation;
+    }
+
+    /// Get the isolation level of this transaction
+    pub fn isolation(&self) -> IsolationLevel {
+        self.isolation
+    }
+
+    /// Prefixes scanned by this transaction that are checked for phantoms
+    ///
+    /// Always empty for `IsolationLevel::Snapshot` transactions.
+    pub fn scan_predicates(&self) -> &[Key] {
+        self.scan_predicates.as_deref().unwrap_or(&[])
+    }
+
     /// Get the abort reason if transaction is aborted
     pub fn abort_reason(&self) -> Option<&str> {
         match &self.status {
@@ -1408,6 +1739,16 @@ impl TransactionContext {
         self.json_reads = None;
         self.json_writes = None;
         self.json_snapshot_versions = None;
+        self.ttls = None;
+
+        // Back to the default isolation level
+        self.isolation = IsolationLevel::Snapshot;
+        self.scan_predicates = None;
+        self.savepoints.clear();
+
+        // Clear commit annotations
+        self.commit_message = None;
+        self.commit_metadata.clear();
 
         // Reset state
         self.status = TransactionStatus::Active;
@@ -1707,4 +2048,163 @@ mod tests {
         // Verify version tracked for conflict detection
         assert_eq!(txn.read_set.get(&key), Some(&15));
     }
+
+    #[test]
+    fn test_scan_predicates_recorded_only_when_serializable() {
+        let ns = test_namespace();
+        let key = test_key(&ns, "alloc:a");
+        let prefix = test_key(&ns, "alloc:");
+        let branch_id = BranchId::new();
+
+        let snap = snapshot_with_key(&key, Value::Int(1), 5);
+        let mut txn = TransactionContext::with_snapshot(1, branch_id, snap);
+        txn.scan_prefix(&prefix).unwrap();
+        assert!(txn.scan_predicates().is_empty());
+
+        let snap = snapshot_with_key(&key, Value::Int(1), 5);
+        let mut txn = TransactionContext::with_snapshot(2, branch_id, snap);
+        txn.set_isolation(IsolationLevel::Serializable);
+        txn.scan_prefix(&prefix).unwrap();
+        txn.scan_prefix(&prefix).unwrap();
+        assert_eq!(txn.scan_predicates(), std::slice::from_ref(&prefix));
+    }
+
+    #[test]
+    fn test_reset_restores_snapshot_isolation() {
+        let ns = test_namespace();
+        let key = test_key(&ns, "alloc:a");
+        let branch_id = BranchId::new();
+
+        let mut txn = TransactionContext::with_snapshot(
+            1,
+            branch_id,
+            snapshot_with_key(&key, Value::Int(1), 5),
+        );
+        txn.set_isolation(IsolationLevel::Serializable);
+        txn.scan_prefix(&test_key(&ns, "alloc:")).unwrap();
+
+        txn.reset(
+            2,
+            branch_id,
+            Some(snapshot_with_key(&key, Value::Int(1), 6)),
+        );
+        assert_eq!(txn.isolation(), IsolationLevel::Snapshot);
+        assert!(txn.scan_predicates().is_empty());
+    }
+
+    fn txn_with_key(ns: &Namespace, name: &str) -> TransactionContext {
+        let key = test_key(ns, name);
+        TransactionContext::with_snapshot(
+            1,
+            BranchId::new(),
+            snapshot_with_key(&key, Value::Int(0), 5),
+        )
+    }
+
+    #[test]
+    fn test_rollback_to_savepoint_restores_buffered_state() {
+        let ns = test_namespace();
+        let base = test_key(&ns, "base");
+        let mut txn = txn_with_key(&ns, "base");
+        let a = test_key(&ns, "a");
+        let b = test_key(&ns, "b");
+
+        txn.put(a.clone(), Value::Int(1)).unwrap();
+        txn.set_event_state(1, [1u8; 32]);
+        txn.savepoint("step").unwrap();
+
+        txn.put(a.clone(), Value::Int(2)).unwrap();
+        txn.put_with_ttl(b.clone(), Value::Int(3), Some(Duration::from_secs(1)))
+            .unwrap();
+        txn.delete(base.clone()).unwrap();
+        txn.cas(test_key(&ns, "c"), 0, Value::Int(4)).unwrap();
+        txn.set_event_state(2, [2u8; 32]);
+
+        txn.rollback_to_savepoint("step").unwrap();
+        assert_eq!(txn.get(&a).unwrap(), Some(Value::Int(1)));
+        assert_eq!(txn.get(&b).unwrap(), None);
+        assert_eq!(txn.ttl_of(&b), None);
+        assert_eq!(txn.get(&base).unwrap(), Some(Value::Int(0)));
+        assert_eq!(txn.cas_count(), 0);
+        assert_eq!(txn.event_sequence_count(), 1);
+        assert_eq!(txn.event_last_hash(), [1u8; 32]);
+
+        // The savepoint survives a rollback to it
+        txn.put(a.clone(), Value::Int(5)).unwrap();
+        txn.rollback_to_savepoint("step").unwrap();
+        assert_eq!(txn.get(&a).unwrap(), Some(Value::Int(1)));
+    }
+
+    #[test]
+    fn test_release_savepoint_keeps_writes_and_drops_later_savepoints() {
+        let ns = test_namespace();
+        let mut txn = txn_with_key(&ns, "base");
+        let a = test_key(&ns, "a");
+
+        txn.savepoint("outer").unwrap();
+        txn.savepoint("inner").unwrap();
+        txn.put(a.clone(), Value::Int(1)).unwrap();
+
+        txn.release_savepoint("outer").unwrap();
+        assert_eq!(txn.savepoint_names().count(), 0);
+        assert_eq!(txn.get(&a).unwrap(), Some(Value::Int(1)));
+        assert!(txn.rollback_to_savepoint("inner").is_err());
+    }
+
+    #[test]
+    fn test_rollback_to_outer_savepoint_discards_inner() {
+        let ns = test_namespace();
+        let mut txn = txn_with_key(&ns, "base");
+
+        txn.savepoint("outer").unwrap();
+        txn.savepoint("inner").unwrap();
+        txn.savepoint("outer").unwrap();
+        txn.rollback_to_savepoint("outer").unwrap();
+        assert_eq!(
+            txn.savepoint_names().collect::<Vec<_>>(),
+            vec!["outer", "inner", "outer"]
+        );
+
+        txn.release_savepoint("outer").unwrap();
+        txn.rollback_to_savepoint("outer").unwrap();
+        assert_eq!(txn.savepoint_names().collect::<Vec<_>>(), vec!["outer"]);
+    }
+
+    #[test]
+    fn test_nested_transaction_undone_on_error_only() {
+        let ns = test_namespace();
+        let mut txn = txn_with_key(&ns, "base");
+        let a = test_key(&ns, "a");
+        let b = test_key(&ns, "b");
+
+        let kept = txn.transaction(|txn| {
+            txn.put(a.clone(), Value::Int(1))?;
+            Ok(7)
+        });
+        assert_eq!(kept.unwrap(), 7);
+
+        let undone: StrataResult<()> = txn.transaction(|txn| {
+            txn.put(b.clone(), Value::Int(2))?;
+            txn.transaction(|txn| txn.delete(a.clone()))?;
+            Err(StrataError::invalid_input("step failed".to_string()))
+        });
+        assert!(undone.is_err());
+
+        assert_eq!(txn.get(&a).unwrap(), Some(Value::Int(1)));
+        assert_eq!(txn.get(&b).unwrap(), None);
+        assert_eq!(txn.savepoint_names().count(), 0);
+        assert!(txn.is_active());
+    }
+
+    #[test]
+    fn test_reads_after_savepoint_survive_rollback() {
+        let ns = test_namespace();
+        let base = test_key(&ns, "base");
+        let mut txn = txn_with_key(&ns, "base");
+
+        txn.savepoint("sp").unwrap();
+        txn.get(&base).unwrap();
+        txn.rollback_to_savepoint("sp").unwrap();
+        assert_eq!(txn.get_read_version(&base), Some(5));
+    }
 }
@@ -7,10 +7,14 @@
 //! - First-committer-wins based on READ-SET, not write-set
 //! - Blind writes (write without read) do NOT conflict
 //! - CAS is validated separately from read-set
-//! - Write skew is ALLOWED (do not try to prevent it)
+//! - Under snapshot isolation (the default), write skew through range reads
+//!   is ALLOWED: keys inserted under a scanned prefix are not checked
+//! - Under `IsolationLevel::Serializable`, scanned prefixes are re-scanned at
+//!   commit and any phantom aborts the transaction
 
-use crate::transaction::{CASOperation, TransactionContext};
+use crate::transaction::{CASOperation, IsolationLevel, TransactionContext};
 use std::collections::HashMap;
+use std::fmt;
 use strata_core::traits::Storage;
 use strata_core::types::Key;
 
@@ -88,6 +92,114 @@ pub enum ConflictType {
         /// The second write path (overlaps with path1)
         path2: strata_core::primitives::json::JsonPath,
     },
+
+    /// Phantom conflict: a key appeared under a prefix scanned by a
+    /// serializable transaction
+    ///
+    /// The transaction's scan did not return `key`, but a transaction that
+    /// committed since the snapshot inserted it. Only reported for
+    /// `IsolationLevel::Serializable` transactions.
+    PhantomConflict {
+        /// The key that appeared under the scanned prefix
+        key: Key,
+        /// The prefix that was scanned
+        prefix: Key,
+        /// Version of the key at validation time
+        current_version: u64,
+    },
+}
+
+impl ConflictType {
+    /// The key the conflict was detected on
+    pub fn key(&self) -> &Key {
+        match self {
+            ConflictType::ReadWriteConflict { key, .. }
+            | ConflictType::CASConflict { key, .. }
+            | ConflictType::JsonDocConflict { key, .. }
+            | ConflictType::JsonPathReadWriteConflict { key, .. }
+            | ConflictType::JsonPathWriteWriteConflict { key, .. }
+            | ConflictType::PhantomConflict { key, .. } => key,
+        }
+    }
+}
+
+/// Render a key for conflict messages, e.g. `kv "budget:a" in space "default"`
+fn describe_key(key: &Key) -> String {
+    format!(
+        "{} {:?} in space {:?}",
+        format!("{:?}", key.type_tag).to_lowercase(),
+        String::from_utf8_lossy(&key.user_key),
+        key.namespace.space
+    )
+}
+
+impl fmt::Display for ConflictType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConflictType::ReadWriteConflict {
+                key,
+                read_version,
+                current_version,
+            } => write!(
+                f,
+                "{} changed since read (read version {}, now {})",
+                describe_key(key),
+                read_version,
+                current_version
+            ),
+            ConflictType::CASConflict {
+                key,
+                expected_version,
+                current_version,
+            } => write!(
+                f,
+                "{} failed CAS (expected version {}, found {})",
+                describe_key(key),
+                expected_version,
+                current_version
+            ),
+            ConflictType::JsonDocConflict {
+                key,
+                snapshot_version,
+                current_version,
+            } => write!(
+                f,
+                "{} document changed since read (read version {}, now {})",
+                describe_key(key),
+                snapshot_version,
+                current_version
+            ),
+            ConflictType::JsonPathReadWriteConflict {
+                key,
+                read_path,
+                write_path,
+            } => write!(
+                f,
+                "{} read at {} overlaps write at {}",
+                describe_key(key),
+                read_path,
+                write_path
+            ),
+            ConflictType::JsonPathWriteWriteConflict { key, path1, path2 } => write!(
+                f,
+                "{} has overlapping writes at {} and {}",
+                describe_key(key),
+                path1,
+                path2
+            ),
+            ConflictType::PhantomConflict {
+                key,
+                prefix,
+                current_version,
+            } => write!(
+                f,
+                "{} appeared under scanned prefix {:?} (version {})",
+                describe_key(key),
+                String::from_utf8_lossy(&prefix.user_key),
+                current_version
+            ),
+        }
+    }
 }
 
 /// Result of transaction validation
@@ -131,6 +243,36 @@ impl ValidationResult {
     pub fn conflict_count(&self) -> usize {
         self.conflicts.len()
     }
+
+    /// Keys involved in the detected conflicts, in detection order
+    pub fn conflicting_keys(&self) -> Vec<&Key> {
+        self.conflicts.iter().map(ConflictType::key).collect()
+    }
+}
+
+/// Maximum number of conflicts spelled out in a validation message
+const MAX_DESCRIBED_CONFLICTS: usize = 5;
+
+impl fmt::Display for ValidationResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} conflict(s)", self.conflict_count())?;
+        for (i, conflict) in self
+            .conflicts
+            .iter()
+            .take(MAX_DESCRIBED_CONFLICTS)
+            .enumerate()
+        {
+            write!(f, "{} {}", if i == 0 { ":" } else { ";" }, conflict)?;
+        }
+        if self.conflict_count() > MAX_DESCRIBED_CONFLICTS {
+            write!(
+                f,
+                "; and {} more",
+                self.conflict_count() - MAX_DESCRIBED_CONFLICTS
+            )?;
+        }
+        Ok(())
+    }
 }
 
 /// Validate the read-set against current storage state
@@ -319,6 +461,50 @@ pub fn validate_json_paths(
     result
 }
 
+/// Validate the prefixes scanned by a serializable transaction
+///
+/// Re-scans each prefix in current storage. Every key found there was either
+/// returned by the transaction's own scan (and is therefore in the read-set,
+/// where `validate_read_set` checks its version) or was inserted by a
+/// transaction that committed after the snapshot. The latter is a phantom:
+/// the scan's result would differ if it ran now.
+///
+/// # Arguments
+/// * `predicates` - Prefixes scanned by the transaction
+/// * `read_set` - Keys read with their versions at read time
+/// * `store` - Storage to re-scan
+///
+/// # Returns
+/// ValidationResult with any PhantomConflicts found
+pub fn validate_scan_predicates<S: Storage>(
+    predicates: &[Key],
+    read_set: &HashMap<Key, u64>,
+    store: &S,
+) -> strata_core::StrataResult<ValidationResult> {
+    let mut result = ValidationResult::ok();
+
+    for prefix in predicates {
+        let current = store.scan_prefix(prefix, u64::MAX).map_err(|e| {
+            strata_core::StrataError::internal(format!(
+                "Storage error during predicate validation for prefix {:?}: {}",
+                prefix, e
+            ))
+        })?;
+
+        for (key, vv) in current {
+            if !read_set.contains_key(&key) {
+                result.conflicts.push(ConflictType::PhantomConflict {
+                    key,
+                    prefix: prefix.clone(),
+                    current_version: vv.version.as_u64(),
+                });
+            }
+        }
+    }
+
+    Ok(result)
+}
+
 /// Validate a complete transaction against current storage state
 ///
 /// Per spec Section 3 (Conflict Detection):
@@ -326,13 +512,16 @@ pub fn validate_json_paths(
 /// 2. Validates CAS-set: ensures expected versions still match
 /// 3. Validates JSON-set: ensures JSON document versions haven't changed
 /// 4. Validates JSON paths: ensures no overlapping writes within transaction (M5 Epic 31)
+/// 5. For serializable transactions, validates scanned prefixes: detects phantoms
 ///
 /// Note: Write-set validation is intentionally omitted. Per spec Section 3.2,
 /// blind writes do not conflict — first-committer-wins is based on the read-set.
 ///
 /// **Per spec Section 3.2 Scenario 3**: Read-only transactions ALWAYS succeed.
 /// If a transaction has no writes (empty write_set, delete_set, cas_set, and json_writes),
-/// validation is skipped entirely and the transaction succeeds.
+/// validation is skipped entirely and the transaction succeeds. This holds for
+/// serializable transactions too: a read-only transaction serializes at its
+/// snapshot, since every writer it could have missed validated at commit.
 ///
 /// # Arguments
 /// * `txn` - Transaction to validate (should be in Validating state for correctness,
@@ -373,5 +562,14 @@ pub fn validate_transaction<S: Storage>(
     // 4. Validate JSON paths (detects overlapping writes within transaction)
     result.merge(validate_json_paths(txn.json_reads(), txn.json_writes()));
 
+    // 5. Validate scan predicates (detects phantoms under serializable isolation)
+    if txn.isolation() == IsolationLevel::Serializable {
+        result.merge(validate_scan_predicates(
+            txn.scan_predicates(),
+            &txn.read_set,
+            store,
+        )?);
+    }
+
     Ok(result)
 }
@@ -53,6 +53,7 @@ pub use primitives::{
     // JSON types
     apply_patches,
     delete_at_path,
+    diff_patches,
     get_at_path,
     get_at_path_mut,
     merge_patch,
@@ -1387,6 +1387,77 @@ pub fn apply_patches(root: &mut JsonValue, patches: &[JsonPatch]) -> Result<(),
     Ok(())
 }
 
+/// Compute the patches that turn `from` into `to`
+///
+/// Objects are compared key by key, so each patch touches the narrowest
+/// path that changed: removed keys become `Delete` patches and added or
+/// modified keys become `Set` patches. Arrays and scalars are replaced
+/// as a whole. Applying the result to `from` with [`apply_patches`]
+/// yields `to`.
+///
+/// # Examples
+///
+/// ```
+/// use strata_core::primitives::json::{diff_patches, apply_patches, JsonPatch, JsonValue};
+///
+/// let from: JsonValue = serde_json::json!({"name": "Alice", "age": 30}).into();
+/// let to: JsonValue = serde_json::json!({"name": "Alice", "age": 31, "city": "Paris"}).into();
+///
+/// let patches = diff_patches(&from, &to);
+/// assert_eq!(patches.len(), 2);
+/// assert!(patches.contains(&JsonPatch::set("age", JsonValue::from(31i64))));
+///
+/// let mut patched = from.clone();
+/// apply_patches(&mut patched, &patches).unwrap();
+/// assert_eq!(patched, to);
+/// ```
+pub fn diff_patches(from: &JsonValue, to: &JsonValue) -> Vec<JsonPatch> {
+    let mut patches = Vec::new();
+    diff_patches_inner(
+        &mut JsonPath::root(),
+        from.as_inner(),
+        to.as_inner(),
+        &mut patches,
+    );
+    patches
+}
+
+/// Internal implementation operating on serde_json::Value
+fn diff_patches_inner(
+    path: &mut JsonPath,
+    from: &serde_json::Value,
+    to: &serde_json::Value,
+    patches: &mut Vec<JsonPatch>,
+) {
+    if from == to {
+        return;
+    }
+    match (from, to) {
+        (serde_json::Value::Object(from_obj), serde_json::Value::Object(to_obj)) => {
+            for key in from_obj.keys() {
+                if !to_obj.contains_key(key) {
+                    patches.push(JsonPatch::delete_at(path.clone().key(key.clone())));
+                }
+            }
+            for (key, to_value) in to_obj {
+                path.push_key(key.clone());
+                match from_obj.get(key) {
+                    Some(from_value) => diff_patches_inner(path, from_value, to_value, patches),
+                    None => patches.push(JsonPatch::set_at(
+                        path.clone(),
+                        JsonValue::from_value(to_value.clone()),
+                    )),
+                }
+                path.segments.pop();
+            }
+        }
+        _ => patches.push(JsonPatch::set_at(
+            path.clone(),
+            JsonValue::from_value(to.clone()),
+        )),
+    }
+}
+
 // =============================================================================
 // RFC 7396 JSON Merge Patch
 // =============================================================================
@@ -3203,4 +3274,48 @@ mod tests {
         assert_eq!(items[0].as_str(), Some("first"));
         assert_eq!(items[1].as_str(), Some("second"));
     }
+
+    #[test]
+    fn test_diff_patches_equal_values() {
+        let json: JsonValue = serde_json::json!({"a": {"b": [1, 2]}}).into();
+        assert!(diff_patches(&json, &json.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_patches_nested_changes() {
+        let from: JsonValue =
+            serde_json::json!({"user": {"name": "Alice", "email": "a@x.io"}, "n": 1}).into();
+        let to: JsonValue = serde_json::json!({"user": {"name": "Bob"}, "n": 1, "tags": []}).into();
+
+        let patches = diff_patches(&from, &to);
+        assert_eq!(patches.len(), 3);
+        assert!(patches.contains(&JsonPatch::delete("user.email")));
+        assert!(patches.contains(&JsonPatch::set("user.name", JsonValue::from("Bob"))));
+        assert!(patches.contains(&JsonPatch::set("tags", JsonValue::array())));
+
+        let mut patched = from.clone();
+        apply_patches(&mut patched, &patches).unwrap();
+        assert_eq!(patched, to);
+    }
+
+    #[test]
+    fn test_diff_patches_replaces_arrays_and_type_changes() {
+        let from: JsonValue = serde_json::json!({"items": [1, 2], "v": {"x": 1}}).into();
+        let to: JsonValue = serde_json::json!({"items": [1, 2, 3], "v": 5}).into();
+
+        let patches = diff_patches(&from, &to);
+        assert_eq!(
+            patches,
+            vec![
+                JsonPatch::set("items", serde_json::json!([1, 2, 3]).into()),
+                JsonPatch::set("v", JsonValue::from(5i64)),
+            ]
+        );
+
+        let root = diff_patches(&JsonValue::from(1i64), &JsonValue::from("x"));
+        assert_eq!(
+            root,
+            vec![JsonPatch::set_at(JsonPath::root(), JsonValue::from("x"))]
+        );
+    }
 }
@@ -19,9 +19,9 @@ pub mod vector;
 // Re-export all types at module level
 pub use event::{ChainVerification, Event};
 pub use json::{
-    apply_patches, delete_at_path, get_at_path, get_at_path_mut, merge_patch, set_at_path,
-    JsonLimitError, JsonPatch, JsonPath, JsonPathError, JsonValue, PathParseError, PathSegment,
-    MAX_ARRAY_SIZE, MAX_DOCUMENT_SIZE, MAX_NESTING_DEPTH, MAX_PATH_LENGTH,
+    apply_patches, delete_at_path, diff_patches, get_at_path, get_at_path_mut, merge_patch,
+    set_at_path, JsonLimitError, JsonPatch, JsonPath, JsonPathError, JsonValue, PathParseError,
+    PathSegment, MAX_ARRAY_SIZE, MAX_DOCUMENT_SIZE, MAX_NESTING_DEPTH, MAX_PATH_LENGTH,
 };
 pub use state::State;
 pub use vector::{
@@ -0,0 +1,556 @@
+//! WAL segment archiving
+//!
+//! WAL compaction deletes segments once a snapshot covers them. With an
+//! archiver configured, each segment is handed to the archiver first and is
+//! only deleted once it has been archived, so the archive holds every
+//! segment the live WAL no longer does. A base backup plus the archived
+//! segments can then be restored to any point after the backup.
+//!
+//! [`WalArchiver`] is the extension point; [`LocalWalArchive`] copies
+//! segments into a local directory.
+//!
+//! # Archive layout
+//!
+//! ```text
+//! <archive>/
+//! ├── wal-000001.seg   # copy of the segment, byte for byte
+//! ├── wal-000001.arc   # index entry: size, CRC32, timestamps
+//! ├── wal-000002.seg
+//! └── wal-000002.arc
+//! ```
+//!
+//! # Index entry format (80 bytes)
+//!
+//! ```text
+//! magic("STAR", 4) + version(4) + segment_number(8) + size(8) + segment_crc(4)
+//! + archived_at(8) + min_ts(8) + max_ts(8) + min_txn_id(8) + max_txn_id(8)
+//! + record_count(8) + crc32(4) = 80 bytes
+//! ```
+//!
+//! The `.seg` file is written and synced before its `.arc` entry, so a
+//! segment without an entry is an interrupted copy and is ignored.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::format::segment_meta::SegmentMeta;
+use crate::format::{SegmentHeader, WalSegment};
+use crate::snapshot_types::now_micros;
+
+/// Magic bytes for archive index entries.
+pub const ARCHIVE_ENTRY_MAGIC: &[u8; 4] = b"STAR";
+
+/// Current format version for archive index entries.
+pub const ARCHIVE_ENTRY_VERSION: u32 = 1;
+
+/// Total size of a serialized `ArchiveEntry` in bytes.
+pub const ARCHIVE_ENTRY_SIZE: usize = 80;
+
+/// Receives WAL segments before compaction removes them.
+///
+/// Implementations must not return until the segment is durably stored:
+/// compaction deletes the segment as soon as `archive_segment` succeeds.
+/// On error the segment is kept and offered again at the next compaction.
+pub trait WalArchiver: Send + Sync {
+    /// Archive the closed segment `segment_number` found at `segment_path`.
+    ///
+    /// `meta` describes the records in the segment (timestamps, txn ids).
+    fn archive_segment(
+        &self,
+        segment_number: u64,
+        segment_path: &Path,
+        meta: &SegmentMeta,
+    ) -> Result<(), ArchiveError>;
+}
+
+/// Index entry describing one archived segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    /// Segment number (`wal-NNNNNN.seg`).
+    pub segment_number: u64,
+    /// Size of the segment file in bytes.
+    pub size: u64,
+    /// CRC32 of the whole segment file.
+    pub segment_crc: u32,
+    /// When the segment was archived (microseconds since epoch).
+    pub archived_at: u64,
+    /// Records in the segment, as tracked by its metadata.
+    pub meta: SegmentMeta,
+}
+
+impl ArchiveEntry {
+    /// Timestamp of the newest commit in the segment, or the archive time
+    /// for an empty segment.
+    pub fn newest_timestamp(&self) -> u64 {
+        if self.meta.is_empty() {
+            self.archived_at
+        } else {
+            self.meta.max_timestamp
+        }
+    }
+
+    /// Path of the index entry for `segment_number` in `dir`.
+    pub fn entry_path(dir: &Path, segment_number: u64) -> PathBuf {
+        dir.join(format!("wal-{:06}.arc", segment_number))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(ARCHIVE_ENTRY_SIZE);
+        buf.extend_from_slice(ARCHIVE_ENTRY_MAGIC);
+        buf.extend_from_slice(&ARCHIVE_ENTRY_VERSION.to_le_bytes());
+        buf.extend_from_slice(&self.segment_number.to_le_bytes());
+        buf.extend_from_slice(&self.size.to_le_bytes());
+        buf.extend_from_slice(&self.segment_crc.to_le_bytes());
+        buf.extend_from_slice(&self.archived_at.to_le_bytes());
+        buf.extend_from_slice(&self.meta.min_timestamp.to_le_bytes());
+        buf.extend_from_slice(&self.meta.max_timestamp.to_le_bytes());
+        buf.extend_from_slice(&self.meta.min_txn_id.to_le_bytes());
+        buf.extend_from_slice(&self.meta.max_txn_id.to_le_bytes());
+        buf.extend_from_slice(&self.meta.record_count.to_le_bytes());
+
+        let crc = crc32fast::hash(&buf);
+        buf.extend_from_slice(&crc.to_le_bytes());
+        buf
+    }
+
+    /// Deserialize from bytes, validating magic, version, and CRC.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ArchiveError> {
+        if data.len() < ARCHIVE_ENTRY_SIZE {
+            return Err(ArchiveError::InvalidEntry(format!(
+                "expected {} bytes, got {}",
+                ARCHIVE_ENTRY_SIZE,
+                data.len()
+            )));
+        }
+        if &data[0..4] != ARCHIVE_ENTRY_MAGIC {
+            return Err(ArchiveError::InvalidEntry("invalid magic bytes".into()));
+        }
+        let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
+        if version != ARCHIVE_ENTRY_VERSION {
+            return Err(ArchiveError::InvalidEntry(format!(
+                "unsupported version {}",
+                version
+            )));
+        }
+        let crc_offset = ARCHIVE_ENTRY_SIZE - 4;
+        let stored = u32::from_le_bytes(data[crc_offset..ARCHIVE_ENTRY_SIZE].try_into().unwrap());
+        let computed = crc32fast::hash(&data[..crc_offset]);
+        if stored != computed {
+            return Err(ArchiveError::InvalidEntry(format!(
+                "checksum mismatch: stored {:#010x}, computed {:#010x}",
+                stored, computed
+            )));
+        }
+
+        let u64_at =
+            |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        let segment_number = u64_at(8);
+        Ok(ArchiveEntry {
+            segment_number,
+            size: u64_at(16),
+            segment_crc: u32::from_le_bytes(data[24..28].try_into().unwrap()),
+            archived_at: u64_at(28),
+            meta: SegmentMeta {
+                segment_number,
+                min_timestamp: u64_at(36),
+                max_timestamp: u64_at(44),
+                min_txn_id: u64_at(52),
+                max_txn_id: u64_at(60),
+                record_count: u64_at(68),
+            },
+        })
+    }
+}
+
+/// Result of [`LocalWalArchive::verify`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveVerifyReport {
+    /// Segments whose size and CRC match their index entry.
+    pub verified: Vec<u64>,
+    /// Segments that failed verification, with the reason.
+    pub corrupt: Vec<(u64, String)>,
+    /// Segment numbers missing between the oldest and newest archived segment.
+    pub gaps: Vec<u64>,
+}
+
+impl ArchiveVerifyReport {
+    /// Whether every archived segment verified and none are missing.
+    pub fn is_ok(&self) -> bool {
+        self.corrupt.is_empty() && self.gaps.is_empty()
+    }
+}
+
+/// Result of [`LocalWalArchive::prune_older_than`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchivePruneInfo {
+    /// Number of segments removed from the archive.
+    pub segments_removed: usize,
+    /// Bytes reclaimed.
+    pub bytes_reclaimed: u64,
+}
+
+/// Archive of WAL segments in a local directory.
+#[derive(Debug, Clone)]
+pub struct LocalWalArchive {
+    dir: PathBuf,
+}
+
+impl LocalWalArchive {
+    /// Open (creating if needed) the archive directory `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, ArchiveError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(LocalWalArchive { dir })
+    }
+
+    /// Open an existing archive directory without creating it.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, ArchiveError> {
+        let dir = dir.into();
+        if !dir.is_dir() {
+            return Err(ArchiveError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("WAL archive '{}' does not exist", dir.display()),
+            )));
+        }
+        Ok(LocalWalArchive { dir })
+    }
+
+    /// The archive directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the archived copy of `segment_number`.
+    pub fn segment_path(&self, segment_number: u64) -> PathBuf {
+        WalSegment::segment_path(&self.dir, segment_number)
+    }
+
+    /// Index entries of all archived segments, oldest first.
+    ///
+    /// Segments without an index entry (interrupted copies) are skipped.
+    pub fn entries(&self) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+        let mut numbers = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let name = entry?.file_name().to_string_lossy().to_string();
+            if name.starts_with("wal-") && name.ends_with(".arc") && name.len() == 14 {
+                if let Ok(num) = name[4..10].parse::<u64>() {
+                    numbers.push(num);
+                }
+            }
+        }
+        numbers.sort_unstable();
+        numbers.into_iter().map(|num| self.entry(num)).collect()
+    }
+
+    /// Read the index entry for `segment_number`.
+    pub fn entry(&self, segment_number: u64) -> Result<ArchiveEntry, ArchiveError> {
+        let data = std::fs::read(ArchiveEntry::entry_path(&self.dir, segment_number))?;
+        let entry = ArchiveEntry::from_bytes(&data)?;
+        if entry.segment_number != segment_number {
+            return Err(ArchiveError::InvalidEntry(format!(
+                "entry for segment {} names segment {}",
+                segment_number, entry.segment_number
+            )));
+        }
+        Ok(entry)
+    }
+
+    /// Check an archived segment against its index entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Corrupt` if the file is missing, has a different size or
+    /// CRC, or does not start with a valid segment header.
+    pub fn verify_segment(&self, entry: &ArchiveEntry) -> Result<(), ArchiveError> {
+        let corrupt = |detail: String| ArchiveError::Corrupt {
+            segment_number: entry.segment_number,
+            detail,
+        };
+        let data = std::fs::read(self.segment_path(entry.segment_number))
+            .map_err(|e| corrupt(format!("cannot read segment: {}", e)))?;
+        if data.len() as u64 != entry.size {
+            return Err(corrupt(format!(
+                "size {} does not match archived size {}",
+                data.len(),
+                entry.size
+            )));
+        }
+        let crc = crc32fast::hash(&data);
+        if crc != entry.segment_crc {
+            return Err(corrupt(format!(
+                "CRC {:#010x} does not match archived CRC {:#010x}",
+                crc, entry.segment_crc
+            )));
+        }
+        match SegmentHeader::from_bytes_slice(&data) {
+            Some(header) if header.is_valid() => Ok(()),
+            _ => Err(corrupt("invalid segment header".into())),
+        }
+    }
+
+    /// Verify every archived segment and report missing segment numbers.
+    pub fn verify(&self) -> Result<ArchiveVerifyReport, ArchiveError> {
+        let mut report = ArchiveVerifyReport::default();
+        let entries = self.entries()?;
+        for pair in entries.windows(2) {
+            report
+                .gaps
+                .extend(pair[0].segment_number + 1..pair[1].segment_number);
+        }
+        for entry in &entries {
+            match self.verify_segment(entry) {
+                Ok(()) => report.verified.push(entry.segment_number),
+                Err(e) => report.corrupt.push((entry.segment_number, e.to_string())),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Remove archived segments whose newest commit is older than `max_age`.
+    ///
+    /// Restores can no longer reach points before the oldest remaining
+    /// segment.
+    pub fn prune_older_than(&self, max_age: Duration) -> Result<ArchivePruneInfo, ArchiveError> {
+        let cutoff = now_micros().saturating_sub(max_age.as_micros() as u64);
+        self.prune_before(cutoff)
+    }
+
+    /// Remove archived segments whose newest commit is before `cutoff`
+    /// (microseconds since epoch).
+    ///
+    /// Only a prefix of the archive is removed: pruning stops at the first
+    /// segment that is kept, so the remaining segments stay contiguous.
+    pub fn prune_before(&self, cutoff: u64) -> Result<ArchivePruneInfo, ArchiveError> {
+        let mut info = ArchivePruneInfo::default();
+        for entry in self.entries()? {
+            if entry.newest_timestamp() >= cutoff {
+                break;
+            }
+            // Entry first: a segment without an entry is ignored
+            std::fs::remove_file(ArchiveEntry::entry_path(&self.dir, entry.segment_number))?;
+            match std::fs::remove_file(self.segment_path(entry.segment_number)) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+            info.segments_removed += 1;
+            info.bytes_reclaimed += entry.size;
+        }
+        sync_dir(&self.dir)?;
+        Ok(info)
+    }
+}
+
+impl WalArchiver for LocalWalArchive {
+    fn archive_segment(
+        &self,
+        segment_number: u64,
+        segment_path: &Path,
+        meta: &SegmentMeta,
+    ) -> Result<(), ArchiveError> {
+        let data = std::fs::read(segment_path)?;
+        let entry = ArchiveEntry {
+            segment_number,
+            size: data.len() as u64,
+            segment_crc: crc32fast::hash(&data),
+            archived_at: now_micros(),
+            meta: SegmentMeta {
+                segment_number,
+                ..meta.clone()
+            },
+        };
+
+        let target = self.segment_path(segment_number);
+        write_synced(&target.with_extension("seg.tmp"), &data)?;
+        std::fs::rename(target.with_extension("seg.tmp"), &target)?;
+
+        let entry_path = ArchiveEntry::entry_path(&self.dir, segment_number);
+        write_synced(&entry_path.with_extension("arc.tmp"), &entry.to_bytes())?;
+        std::fs::rename(entry_path.with_extension("arc.tmp"), &entry_path)?;
+
+        sync_dir(&self.dir)?;
+        Ok(())
+    }
+}
+
+fn write_synced(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+fn sync_dir(dir: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+/// Errors from WAL archiving.
+#[derive(Debug, thiserror::Error)]
+pub enum ArchiveError {
+    /// I/O error while reading or writing the archive.
+    #[error("WAL archive I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// An index entry is unreadable.
+    #[error("invalid WAL archive entry: {0}")]
+    InvalidEntry(String),
+
+    /// An archived segment does not match its index entry.
+    #[error("archived WAL segment {segment_number} is corrupt: {detail}")]
+    Corrupt {
+        /// Segment number.
+        segment_number: u64,
+        /// What did not match.
+        detail: String,
+    },
+
+    /// Error reported by a custom archiver.
+    #[error("WAL archiver failed: {0}")]
+    Archiver(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::WalRecord;
+    use tempfile::tempdir;
+
+    fn write_segment(wal_dir: &Path, segment_number: u64, timestamps: &[u64]) -> SegmentMeta {
+        let mut segment = WalSegment::create(wal_dir, segment_number, [0u8; 16]).unwrap();
+        let mut meta = SegmentMeta::new_empty(segment_number);
+        for (i, &ts) in timestamps.iter().enumerate() {
+            let txn_id = segment_number * 100 + i as u64;
+            segment
+                .write(&WalRecord::new(txn_id, [0u8; 16], ts, vec![1, 2, 3]).to_bytes())
+                .unwrap();
+            meta.track_record(txn_id, ts);
+        }
+        segment.close().unwrap();
+        meta
+    }
+
+    fn add_segment(wal_dir: &Path, archive: &LocalWalArchive, segment_number: u64, ts: &[u64]) {
+        let meta = write_segment(wal_dir, segment_number, ts);
+        archive
+            .archive_segment(
+                segment_number,
+                &WalSegment::segment_path(wal_dir, segment_number),
+                &meta,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_entry_roundtrip() {
+        let entry = ArchiveEntry {
+            segment_number: 7,
+            size: 1234,
+            segment_crc: 0xdead_beef,
+            archived_at: 99,
+            meta: SegmentMeta {
+                segment_number: 7,
+                min_timestamp: 10,
+                max_timestamp: 20,
+                min_txn_id: 3,
+                max_txn_id: 5,
+                record_count: 3,
+            },
+        };
+        let bytes = entry.to_bytes();
+        assert_eq!(bytes.len(), ARCHIVE_ENTRY_SIZE);
+        assert_eq!(ArchiveEntry::from_bytes(&bytes).unwrap(), entry);
+
+        let mut corrupted = bytes.clone();
+        corrupted[20] ^= 0xff;
+        assert!(matches!(
+            ArchiveEntry::from_bytes(&corrupted),
+            Err(ArchiveError::InvalidEntry(_))
+        ));
+    }
+
+    #[test]
+    fn test_archive_and_verify() {
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+        std::fs::create_dir_all(&wal_dir).unwrap();
+        let archive = LocalWalArchive::new(dir.path().join("archive")).unwrap();
+
+        add_segment(&wal_dir, &archive, 1, &[100, 200]);
+        add_segment(&wal_dir, &archive, 2, &[300]);
+
+        let entries = archive.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].meta.max_timestamp, 200);
+        assert_eq!(entries[1].meta.record_count, 1);
+
+        let report = archive.verify().unwrap();
+        assert!(report.is_ok());
+        assert_eq!(report.verified, vec![1, 2]);
+
+        // Flip a byte in the archived copy of segment 2
+        let path = archive.segment_path(2);
+        let mut data = std::fs::read(&path).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 0xff;
+        std::fs::write(&path, data).unwrap();
+
+        let report = archive.verify().unwrap();
+        assert_eq!(report.verified, vec![1]);
+        assert_eq!(report.corrupt.len(), 1);
+        assert_eq!(report.corrupt[0].0, 2);
+    }
+
+    #[test]
+    fn test_verify_reports_gaps() {
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+        std::fs::create_dir_all(&wal_dir).unwrap();
+        let archive = LocalWalArchive::new(dir.path().join("archive")).unwrap();
+
+        add_segment(&wal_dir, &archive, 1, &[100]);
+        add_segment(&wal_dir, &archive, 4, &[400]);
+
+        let report = archive.verify().unwrap();
+        assert_eq!(report.gaps, vec![2, 3]);
+        assert!(!report.is_ok());
+    }
+
+    #[test]
+    fn test_prune_before() {
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+        std::fs::create_dir_all(&wal_dir).unwrap();
+        let archive = LocalWalArchive::new(dir.path().join("archive")).unwrap();
+
+        add_segment(&wal_dir, &archive, 1, &[100, 200]);
+        add_segment(&wal_dir, &archive, 2, &[300]);
+        add_segment(&wal_dir, &archive, 3, &[400]);
+
+        let info = archive.prune_before(350).unwrap();
+        assert_eq!(info.segments_removed, 2);
+        assert!(info.bytes_reclaimed > 0);
+        assert!(!archive.segment_path(1).exists());
+
+        let remaining: Vec<u64> = archive
+            .entries()
+            .unwrap()
+            .iter()
+            .map(|e| e.segment_number)
+            .collect();
+        assert_eq!(remaining, vec![3]);
+    }
+
+    #[test]
+    fn test_segment_without_entry_is_ignored() {
+        let dir = tempdir().unwrap();
+        let archive = LocalWalArchive::new(dir.path().join("archive")).unwrap();
+        write_segment(archive.dir(), 5, &[100]);
+
+        assert!(archive.entries().unwrap().is_empty());
+    }
+}
@@ -70,6 +70,10 @@ pub enum BranchBundleError {
     #[error("WAL replay error: {0}")]
     WalReplay(String),
 
+    /// WAL.branchlog could not be decoded (wrong codec or key)
+    #[error("Codec error: {0}")]
+    Codec(#[from] crate::codec::CodecError),
+
     /// WAL entry is malformed
     #[error("Invalid WAL entry at index {index}: {reason}")]
     InvalidWalEntry {
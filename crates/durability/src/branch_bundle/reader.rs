@@ -8,6 +8,7 @@ use crate::branch_bundle::types::{
     BRANCHBUNDLE_FORMAT_VERSION,
 };
 use crate::branch_bundle::wal_log::{BranchlogPayload, WalLogReader};
+use crate::codec::{CodecError, IdentityCodec, StorageCodec};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufReader, Read};
@@ -28,6 +29,17 @@ impl BranchBundleReader {
     /// - Checksums match manifest
     /// - WAL.branchlog header is valid
     pub fn validate(path: &Path) -> BranchBundleResult<BundleVerifyInfo> {
+        Self::validate_with_codec(path, &IdentityCodec)
+    }
+
+    /// Validate a bundle whose WAL.branchlog was written with `codec`
+    ///
+    /// Fails with `BranchBundleError::Codec` if the bundle was written with
+    /// a different codec or cannot be decoded (e.g. wrong key).
+    pub fn validate_with_codec(
+        path: &Path,
+        codec: &dyn StorageCodec,
+    ) -> BranchBundleResult<BundleVerifyInfo> {
         let files = Self::extract_all_files(path)?;
 
         // Check required files
@@ -69,7 +81,8 @@ impl BranchBundleReader {
         }
 
         // Validate WAL header (without parsing entries)
-        WalLogReader::validate(std::io::Cursor::new(wal_data))?;
+        let wal_data = Self::decode_wal(&manifest, wal_data, codec)?;
+        WalLogReader::validate(std::io::Cursor::new(&wal_data))?;
 
         // Parse branch info for branch_id
         let branch_info: BundleBranchInfo = serde_json::from_slice(branch_data)?;
@@ -134,11 +147,21 @@ impl BranchBundleReader {
             }
         }
 
-        WalLogReader::read_from_slice(wal_data)
+        let wal_data = Self::decode_wal(&manifest, wal_data, &IdentityCodec)?;
+        WalLogReader::read_from_slice(&wal_data)
     }
 
     /// Read all components from the bundle
     pub fn read_all(path: &Path) -> BranchBundleResult<BundleContents> {
+        Self::read_all_with_codec(path, &IdentityCodec)
+    }
+
+    /// Read all components from a bundle whose WAL.branchlog was written
+    /// with `codec`
+    pub fn read_all_with_codec(
+        path: &Path,
+        codec: &dyn StorageCodec,
+    ) -> BranchBundleResult<BundleContents> {
         let files = Self::extract_all_files(path)?;
 
         let manifest_data = files
@@ -153,7 +176,8 @@ impl BranchBundleReader {
 
         let manifest: BundleManifest = serde_json::from_slice(manifest_data)?;
         let branch_info: BundleBranchInfo = serde_json::from_slice(branch_data)?;
-        let payloads = WalLogReader::read_from_slice(wal_data)?;
+        let wal_data = Self::decode_wal(&manifest, wal_data, codec)?;
+        let payloads = WalLogReader::read_from_slice(&wal_data)?;
 
         Ok(BundleContents {
             manifest,
@@ -162,6 +186,22 @@ impl BranchBundleReader {
         })
     }
 
+    /// Decode WAL.branchlog with `codec`, checking it matches the manifest
+    fn decode_wal(
+        manifest: &BundleManifest,
+        wal_data: &[u8],
+        codec: &dyn StorageCodec,
+    ) -> BranchBundleResult<Vec<u8>> {
+        if manifest.codec != codec.codec_id() {
+            return Err(CodecError::CodecMismatch {
+                expected: manifest.codec.clone(),
+                actual: codec.codec_id().to_string(),
+            }
+            .into());
+        }
+        Ok(codec.decode(wal_data)?)
+    }
+
     /// Extract a single file from the archive
     fn extract_file(path: &Path, file_name: &str) -> BranchBundleResult<Vec<u8>> {
         let file = File::open(path)?;
@@ -582,4 +622,38 @@ mod tests {
             assert_eq!(original, read);
         }
     }
+
+    #[test]
+    fn test_encrypted_bundle_roundtrip() {
+        use crate::codec::AesGcmCodec;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("secret.branchbundle.tar.zst");
+
+        let writer = BranchBundleWriter::new(&ExportOptions::default())
+            .with_codec(Box::new(AesGcmCodec::new(&[9u8; 32])));
+        let payloads = make_test_payloads();
+        writer
+            .write(&make_test_branch_info(), &payloads, &path)
+            .unwrap();
+
+        let codec = AesGcmCodec::new(&[9u8; 32]);
+        let verify = BranchBundleReader::validate_with_codec(&path, &codec).unwrap();
+        assert!(verify.checksums_valid);
+
+        let contents = BranchBundleReader::read_all_with_codec(&path, &codec).unwrap();
+        assert_eq!(contents.manifest.codec, "aes-gcm-256");
+        assert_eq!(contents.payloads, payloads);
+
+        // Without the codec: mismatch; with the wrong key: decode error
+        assert!(matches!(
+            BranchBundleReader::read_all(&path),
+            Err(BranchBundleError::Codec(CodecError::CodecMismatch { .. }))
+        ));
+        let wrong = AesGcmCodec::new(&[8u8; 32]);
+        assert!(matches!(
+            BranchBundleReader::read_all_with_codec(&path, &wrong),
+            Err(BranchBundleError::Codec(CodecError::DecodeError { .. }))
+        ));
+    }
 }
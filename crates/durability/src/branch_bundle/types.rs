@@ -68,6 +68,16 @@ pub struct BundleManifest {
 
     /// Summary of bundle contents
     pub contents: BundleContents,
+
+    /// Storage codec applied to WAL.branchlog (e.g. "aes-gcm-256")
+    ///
+    /// Bundles written before codecs were recorded use "identity".
+    #[serde(default = "default_codec_id")]
+    pub codec: String,
+}
+
+fn default_codec_id() -> String {
+    "identity".to_string()
 }
 
 impl BundleManifest {
@@ -80,6 +90,7 @@ impl BundleManifest {
             checksum_algorithm: "xxh3".to_string(),
             checksums: HashMap::new(),
             contents,
+            codec: default_codec_id(),
         }
     }
 
@@ -4,6 +4,10 @@
 //! - MANIFEST.json - Format metadata and checksums
 //! - BRANCH.json - Branch metadata
 //! - WAL.branchlog - Branch-scoped transaction payloads (msgpack v2 format)
+//!
+//! WAL.branchlog is passed through the writer's storage codec, so bundles
+//! exported from an encrypted database stay encrypted. The codec id is
+//! recorded in MANIFEST.json.
 
 use crate::branch_bundle::error::{BranchBundleError, BranchBundleResult};
 use crate::branch_bundle::types::{
@@ -11,6 +15,7 @@ use crate::branch_bundle::types::{
     ExportOptions,
 };
 use crate::branch_bundle::wal_log::{BranchlogPayload, WalLogWriter};
+use crate::codec::{IdentityCodec, StorageCodec};
 use std::fs::{self, File};
 use std::io::{BufWriter, Write};
 use std::path::Path;
@@ -21,6 +26,7 @@ use tar::{Builder, Header};
 /// Creates .branchbundle.tar.zst files with atomic write semantics.
 pub struct BranchBundleWriter {
     compression_level: i32,
+    codec: Box<dyn StorageCodec>,
 }
 
 impl BranchBundleWriter {
@@ -28,9 +34,16 @@ impl BranchBundleWriter {
     pub fn new(options: &ExportOptions) -> Self {
         Self {
             compression_level: options.compression_level,
+            codec: Box::new(IdentityCodec),
         }
     }
 
+    /// Encode WAL.branchlog with `codec` (defaults to the identity codec)
+    pub fn with_codec(mut self, codec: Box<dyn StorageCodec>) -> Self {
+        self.codec = codec;
+        self
+    }
+
     /// Create a new writer with default options
     pub fn with_defaults() -> Self {
         Self::new(&ExportOptions::default())
@@ -84,8 +97,9 @@ impl BranchBundleWriter {
         // Prepare file contents
         let branch_json = serde_json::to_vec_pretty(branch_info)?;
         let (wal_data, wal_info) = WalLogWriter::write_to_vec(payloads)?;
+        let wal_data = self.codec.encode(&wal_data);
 
-        // Build manifest with checksums
+        // Build manifest with checksums (over the stored, encoded bytes)
         let mut manifest = BundleManifest::new(
             env!("CARGO_PKG_VERSION"),
             BundleContents {
@@ -93,8 +107,9 @@ impl BranchBundleWriter {
                 wal_size_bytes: wal_info.bytes_written,
             },
         );
+        manifest.codec = self.codec.codec_id().to_string();
         manifest.add_checksum("BRANCH.json", xxh3_hex(&branch_json));
-        manifest.add_checksum("WAL.branchlog", &wal_info.checksum);
+        manifest.add_checksum("WAL.branchlog", xxh3_hex(&wal_data));
 
         let manifest_json = serde_json::to_vec_pretty(&manifest)?;
         manifest.add_checksum("MANIFEST.json", xxh3_hex(&manifest_json));
@@ -172,8 +187,9 @@ impl BranchBundleWriter {
         // Prepare file contents
         let branch_json = serde_json::to_vec_pretty(branch_info)?;
         let (wal_data, wal_info) = WalLogWriter::write_to_vec(payloads)?;
+        let wal_data = self.codec.encode(&wal_data);
 
-        // Build manifest with checksums
+        // Build manifest with checksums (over the stored, encoded bytes)
         let mut manifest = BundleManifest::new(
             env!("CARGO_PKG_VERSION"),
             BundleContents {
@@ -181,8 +197,9 @@ impl BranchBundleWriter {
                 wal_size_bytes: wal_info.bytes_written,
             },
         );
+        manifest.codec = self.codec.codec_id().to_string();
         manifest.add_checksum("BRANCH.json", xxh3_hex(&branch_json));
-        manifest.add_checksum("WAL.branchlog", &wal_info.checksum);
+        manifest.add_checksum("WAL.branchlog", xxh3_hex(&wal_data));
 
         let manifest_json = serde_json::to_vec_pretty(&manifest)?;
         manifest.add_checksum("MANIFEST.json", xxh3_hex(&manifest_json));
@@ -0,0 +1,270 @@
+//! AES-256-GCM codec (authenticated encryption at rest).
+//!
+//! Every encoded buffer carries its own random nonce, so the same plaintext
+//! never produces the same ciphertext twice:
+//!
+//! ```text
+//! +------------------+
+//! | Nonce            | 12 bytes
+//! | Ciphertext       | len(plaintext) bytes
+//! | Tag              | 16 bytes
+//! +------------------+
+//! ```
+//!
+//! The tag authenticates the ciphertext, so decoding with the wrong key (or
+//! decoding tampered bytes) fails with `CodecError::DecodeError` instead of
+//! returning garbage.
+
+use std::path::Path;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+
+use super::traits::{CodecError, StorageCodec};
+
+/// Codec identifier recorded in the MANIFEST.
+pub const AES_GCM_CODEC_ID: &str = "aes-gcm-256";
+
+/// Key length in bytes (AES-256).
+pub const AES_GCM_KEY_LEN: usize = 32;
+
+/// Nonce length in bytes (96-bit GCM nonce).
+const NONCE_LEN: usize = 12;
+
+/// Authentication tag length in bytes.
+const TAG_LEN: usize = 16;
+
+/// AES-256-GCM codec.
+///
+/// # Example
+///
+/// ```
+/// use strata_durability::codec::{AesGcmCodec, StorageCodec};
+///
+/// let codec = AesGcmCodec::new(&[7u8; 32]);
+/// let encoded = codec.encode(b"hello world");
+/// assert_ne!(encoded.as_slice(), b"hello world");
+///
+/// let decoded = codec.decode(&encoded).unwrap();
+/// assert_eq!(decoded.as_slice(), b"hello world");
+/// ```
+#[derive(Clone)]
+pub struct AesGcmCodec {
+    cipher: Aes256Gcm,
+}
+
+impl AesGcmCodec {
+    /// Create a codec from a raw 32-byte key.
+    pub fn new(key: &[u8; AES_GCM_KEY_LEN]) -> Self {
+        AesGcmCodec {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        }
+    }
+
+    /// Create a codec from key material.
+    ///
+    /// Accepts either exactly 32 raw bytes or 64 hexadecimal characters
+    /// (surrounding whitespace is ignored, so key files may end in a newline).
+    pub fn from_key_material(material: &[u8]) -> Result<Self, CodecError> {
+        if material.len() == AES_GCM_KEY_LEN {
+            let key: [u8; AES_GCM_KEY_LEN] = material.try_into().unwrap();
+            return Ok(Self::new(&key));
+        }
+
+        let text = std::str::from_utf8(material)
+            .map_err(|_| CodecError::InvalidKey(invalid_key_detail(material.len())))?
+            .trim();
+        let key = parse_hex_key(text)
+            .ok_or_else(|| CodecError::InvalidKey(invalid_key_detail(text.len())))?;
+        Ok(Self::new(&key))
+    }
+
+    /// Create a codec from a key file (raw or hex-encoded key).
+    pub fn from_key_file(path: &Path) -> Result<Self, CodecError> {
+        let material = std::fs::read(path).map_err(|e| {
+            CodecError::InvalidKey(format!("cannot read key file '{}': {}", path.display(), e))
+        })?;
+        Self::from_key_material(&material)
+    }
+}
+
+impl std::fmt::Debug for AesGcmCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print key material
+        f.debug_struct("AesGcmCodec").finish_non_exhaustive()
+    }
+}
+
+impl StorageCodec for AesGcmCodec {
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, data)
+            .expect("AES-GCM encryption cannot fail for in-memory buffers");
+
+        let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        out
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>, CodecError> {
+        if data.len() < NONCE_LEN + TAG_LEN {
+            return Err(CodecError::decode(
+                "ciphertext shorter than nonce and tag",
+                AES_GCM_CODEC_ID,
+                data.len(),
+            ));
+        }
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                CodecError::decode(
+                    "authentication failed (wrong key or corrupted data)",
+                    AES_GCM_CODEC_ID,
+                    data.len(),
+                )
+            })
+    }
+
+    fn codec_id(&self) -> &str {
+        AES_GCM_CODEC_ID
+    }
+
+    fn clone_box(&self) -> Box<dyn StorageCodec> {
+        Box::new(self.clone())
+    }
+}
+
+/// Parse a 64-character hex string into a 32-byte key.
+fn parse_hex_key(text: &str) -> Option<[u8; AES_GCM_KEY_LEN]> {
+    if text.len() != AES_GCM_KEY_LEN * 2 || !text.is_ascii() {
+        return None;
+    }
+    let mut key = [0u8; AES_GCM_KEY_LEN];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(key)
+}
+
+fn invalid_key_detail(len: usize) -> String {
+    format!(
+        "expected {} raw bytes or {} hex characters, got {} bytes",
+        AES_GCM_KEY_LEN,
+        AES_GCM_KEY_LEN * 2,
+        len
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEX_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_aes_gcm_roundtrip() {
+        let codec = AesGcmCodec::new(&[1u8; 32]);
+        let data = b"the quick brown fox";
+
+        let encoded = codec.encode(data);
+        assert_eq!(encoded.len(), data.len() + NONCE_LEN + TAG_LEN);
+        assert_ne!(&encoded[NONCE_LEN..NONCE_LEN + data.len()], data);
+
+        let decoded = codec.decode(&encoded).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_aes_gcm_empty() {
+        let codec = AesGcmCodec::new(&[1u8; 32]);
+        let encoded = codec.encode(b"");
+        assert!(codec.decode(&encoded).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_aes_gcm_random_nonce() {
+        let codec = AesGcmCodec::new(&[1u8; 32]);
+        assert_ne!(codec.encode(b"same"), codec.encode(b"same"));
+    }
+
+    #[test]
+    fn test_aes_gcm_wrong_key_fails() {
+        let encoded = AesGcmCodec::new(&[1u8; 32]).encode(b"secret");
+        let result = AesGcmCodec::new(&[2u8; 32]).decode(&encoded);
+
+        match result {
+            Err(CodecError::DecodeError {
+                detail, codec_id, ..
+            }) => {
+                assert!(detail.contains("wrong key"));
+                assert_eq!(codec_id, AES_GCM_CODEC_ID);
+            }
+            other => panic!("expected DecodeError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_aes_gcm_tampered_data_fails() {
+        let codec = AesGcmCodec::new(&[1u8; 32]);
+        let mut encoded = codec.encode(b"secret");
+        encoded[NONCE_LEN] ^= 0x01;
+        assert!(codec.decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_aes_gcm_truncated_data_fails() {
+        let codec = AesGcmCodec::new(&[1u8; 32]);
+        assert!(matches!(
+            codec.decode(&[0u8; 8]),
+            Err(CodecError::DecodeError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_key_material_hex_and_raw_agree() {
+        let raw: Vec<u8> = (0u8..32).collect();
+        let from_raw = AesGcmCodec::from_key_material(&raw).unwrap();
+        let from_hex = AesGcmCodec::from_key_material(format!("{}\n", HEX_KEY).as_bytes()).unwrap();
+
+        let encoded = from_raw.encode(b"data");
+        assert_eq!(from_hex.decode(&encoded).unwrap(), b"data");
+    }
+
+    #[test]
+    fn test_key_material_invalid() {
+        assert!(matches!(
+            AesGcmCodec::from_key_material(b"too short"),
+            Err(CodecError::InvalidKey(_))
+        ));
+        let bad_hex = "zz".repeat(32);
+        assert!(matches!(
+            AesGcmCodec::from_key_material(bad_hex.as_bytes()),
+            Err(CodecError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_key_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("strata.key");
+        std::fs::write(&path, HEX_KEY).unwrap();
+
+        let codec = AesGcmCodec::from_key_file(&path).unwrap();
+        assert_eq!(codec.codec_id(), AES_GCM_CODEC_ID);
+
+        let missing = AesGcmCodec::from_key_file(&dir.path().join("missing.key"));
+        assert!(matches!(missing, Err(CodecError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn test_debug_hides_key() {
+        let codec = AesGcmCodec::new(&[0xAB; 32]);
+        let debug = format!("{:?}", codec);
+        assert!(!debug.to_lowercase().contains("ab"));
+    }
+}
@@ -0,0 +1,152 @@
+//! Chained codec (codec pipeline).
+//!
+//! Applies a sequence of codecs: `encode` runs them first to last and
+//! `decode` runs them last to first. The usual chain is compression then
+//! encryption (`lz4+aes-gcm-256`), since ciphertext does not compress.
+//!
+//! The chain's identifier joins the stage identifiers with `+`, so the
+//! MANIFEST records the full pipeline and a database cannot be reopened
+//! with the stages in a different order.
+
+use super::traits::{CodecError, StorageCodec};
+
+/// Separator between stage identifiers in a chained codec ID.
+pub const CODEC_CHAIN_SEPARATOR: char = '+';
+
+/// Codec pipeline.
+///
+/// # Example
+///
+/// ```
+/// use strata_durability::codec::{AesGcmCodec, ChainedCodec, Lz4Codec, StorageCodec};
+///
+/// let codec = ChainedCodec::new(vec![
+///     Box::new(Lz4Codec),
+///     Box::new(AesGcmCodec::new(&[7u8; 32])),
+/// ]);
+/// assert_eq!(codec.codec_id(), "lz4+aes-gcm-256");
+///
+/// let data = b"hello hello hello hello hello hello";
+/// let decoded = codec.decode(&codec.encode(data)).unwrap();
+/// assert_eq!(data.as_slice(), decoded.as_slice());
+/// ```
+pub struct ChainedCodec {
+    stages: Vec<Box<dyn StorageCodec>>,
+    codec_id: String,
+}
+
+impl ChainedCodec {
+    /// Create a chain from its stages, in encode order.
+    pub fn new(stages: Vec<Box<dyn StorageCodec>>) -> Self {
+        let codec_id = stages
+            .iter()
+            .map(|s| s.codec_id())
+            .collect::<Vec<_>>()
+            .join(&CODEC_CHAIN_SEPARATOR.to_string());
+        ChainedCodec { stages, codec_id }
+    }
+
+    /// The stages of this chain, in encode order.
+    pub fn stages(&self) -> &[Box<dyn StorageCodec>] {
+        &self.stages
+    }
+}
+
+impl Clone for ChainedCodec {
+    fn clone(&self) -> Self {
+        ChainedCodec {
+            stages: self.stages.iter().map(|s| s.clone_box()).collect(),
+            codec_id: self.codec_id.clone(),
+        }
+    }
+}
+
+impl std::fmt::Debug for ChainedCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Stages may hold key material, so only print the identifier
+        f.debug_struct("ChainedCodec")
+            .field("codec_id", &self.codec_id)
+            .finish()
+    }
+}
+
+impl StorageCodec for ChainedCodec {
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let mut buf = data.to_vec();
+        for stage in &self.stages {
+            buf = stage.encode(&buf);
+        }
+        buf
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>, CodecError> {
+        let mut buf = data.to_vec();
+        for stage in self.stages.iter().rev() {
+            buf = stage.decode(&buf)?;
+        }
+        Ok(buf)
+    }
+
+    fn codec_id(&self) -> &str {
+        &self.codec_id
+    }
+
+    fn clone_box(&self) -> Box<dyn StorageCodec> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::{AesGcmCodec, IdentityCodec, Lz4Codec};
+
+    fn lz4_aes(key: u8) -> ChainedCodec {
+        ChainedCodec::new(vec![
+            Box::new(Lz4Codec),
+            Box::new(AesGcmCodec::new(&[key; 32])),
+        ])
+    }
+
+    #[test]
+    fn test_chained_roundtrip() {
+        let codec = lz4_aes(1);
+        let data = b"the same text over and over ".repeat(40);
+
+        let encoded = codec.encode(&data);
+        // Compressed before encryption, so still much smaller
+        assert!(encoded.len() < data.len() / 4);
+        assert_eq!(codec.decode(&encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn test_chained_codec_id() {
+        assert_eq!(lz4_aes(1).codec_id(), "lz4+aes-gcm-256");
+        let single = ChainedCodec::new(vec![Box::new(IdentityCodec)]);
+        assert_eq!(single.codec_id(), "identity");
+    }
+
+    #[test]
+    fn test_chained_wrong_key_fails() {
+        let encoded = lz4_aes(1).encode(b"secret");
+        assert!(matches!(
+            lz4_aes(2).decode(&encoded),
+            Err(CodecError::DecodeError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_chained_clone_box() {
+        let codec = lz4_aes(1);
+        let cloned = codec.clone_box();
+        assert_eq!(cloned.codec_id(), codec.codec_id());
+        assert_eq!(cloned.decode(&codec.encode(b"x")).unwrap(), b"x");
+    }
+
+    #[test]
+    fn test_chained_debug_hides_key() {
+        let debug = format!("{:?}", lz4_aes(0xAB));
+        assert!(debug.contains("lz4+aes-gcm-256"));
+        assert!(!debug.contains("171"));
+    }
+}
@@ -0,0 +1,112 @@
+//! LZ4 compression codec.
+//!
+//! Encoded buffers carry the uncompressed length so decoding can allocate
+//! the output up front:
+//!
+//! ```text
+//! +------------------+
+//! | Length           | 4 bytes (u32 LE, uncompressed size)
+//! | Block            | LZ4 block
+//! +------------------+
+//! ```
+
+use super::traits::{CodecError, StorageCodec};
+
+/// Codec identifier recorded in the MANIFEST.
+pub const LZ4_CODEC_ID: &str = "lz4";
+
+/// LZ4 block compression codec.
+///
+/// # Example
+///
+/// ```
+/// use strata_durability::codec::{Lz4Codec, StorageCodec};
+///
+/// let codec = Lz4Codec;
+/// let data = b"abcabcabcabcabcabcabcabcabcabcabcabc";
+///
+/// let encoded = codec.encode(data);
+/// assert!(encoded.len() < data.len());
+///
+/// let decoded = codec.decode(&encoded).unwrap();
+/// assert_eq!(data.as_slice(), decoded.as_slice());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lz4Codec;
+
+impl StorageCodec for Lz4Codec {
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        lz4_flex::compress_prepend_size(data)
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>, CodecError> {
+        if data.len() < 4 {
+            return Err(CodecError::decode(
+                "missing length prefix",
+                LZ4_CODEC_ID,
+                data.len(),
+            ));
+        }
+        let expected = u32::from_le_bytes(data[..4].try_into().unwrap()) as usize;
+        let decoded = lz4_flex::decompress(&data[4..], expected)
+            .map_err(|e| CodecError::decode(e.to_string(), LZ4_CODEC_ID, data.len()))?;
+        if decoded.len() != expected {
+            return Err(CodecError::decode(
+                format!("decoded {} bytes, expected {}", decoded.len(), expected),
+                LZ4_CODEC_ID,
+                data.len(),
+            ));
+        }
+        Ok(decoded)
+    }
+
+    fn codec_id(&self) -> &str {
+        LZ4_CODEC_ID
+    }
+
+    fn clone_box(&self) -> Box<dyn StorageCodec> {
+        Box::new(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lz4_roundtrip() {
+        let codec = Lz4Codec;
+        let data = br#"{"name":"alice","tags":["a","b"]}"#.repeat(50);
+
+        let encoded = codec.encode(&data);
+        assert!(encoded.len() < data.len() / 4);
+        assert_eq!(codec.decode(&encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn test_lz4_empty() {
+        let codec = Lz4Codec;
+        let encoded = codec.encode(b"");
+        assert!(codec.decode(&encoded).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_lz4_incompressible() {
+        let codec = Lz4Codec;
+        let data: Vec<u8> = (0..=255u8).collect();
+        assert_eq!(codec.decode(&codec.encode(&data)).unwrap(), data);
+    }
+
+    #[test]
+    fn test_lz4_corrupted_fails() {
+        let codec = Lz4Codec;
+        let mut encoded = codec.encode(&b"hello hello hello hello".repeat(10));
+        // Claim a larger uncompressed size than the block produces
+        encoded[0] = encoded[0].wrapping_add(1);
+        assert!(matches!(
+            codec.decode(&encoded),
+            Err(CodecError::DecodeError { .. })
+        ));
+        assert!(codec.decode(&[1, 2]).is_err());
+    }
+}
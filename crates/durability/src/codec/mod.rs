@@ -1,17 +1,15 @@
 //! Storage codec abstraction.
 //!
-//! The codec seam provides a hook point for future encryption-at-rest and
+//! The codec seam provides a hook point for encryption-at-rest and
 //! compression. All bytes passing through the storage layer go through the
 //! codec for encode/decode operations.
 //!
+//! Available codecs:
 //!
-//!
-//! Uses `IdentityCodec` which performs no transformation. This establishes
-//! the codec seam without adding complexity. Future milestones can add:
-//!
-//! - `AesGcmCodec`: AES-256-GCM encryption at rest
+//! - `IdentityCodec`: no transformation (the default)
+//! - `AesGcmCodec`: AES-256-GCM authenticated encryption at rest
 //! - `Lz4Codec`: LZ4 compression
-//! - `ChainedCodec`: Compression + encryption pipeline
+//! - `ChainedCodec`: Compression + encryption pipeline (`lz4+aes-gcm-256`)
 //!
 //! # Usage
 //!
@@ -27,12 +25,21 @@
 //! assert_eq!(data.as_slice(), decoded.as_slice());
 //! ```
 
+mod aes_gcm;
+mod chained;
 mod identity;
+mod lz4;
 mod traits;
 
+pub use aes_gcm::{AesGcmCodec, AES_GCM_CODEC_ID, AES_GCM_KEY_LEN};
+pub use chained::{ChainedCodec, CODEC_CHAIN_SEPARATOR};
 pub use identity::IdentityCodec;
+pub use lz4::{Lz4Codec, LZ4_CODEC_ID};
 pub use traits::{CodecError, StorageCodec};
 
+/// Plaintext sealed into the MANIFEST key check.
+const KEY_CHECK_PLAINTEXT: &[u8] = b"strata-key-check";
+
 /// Get a codec by its identifier.
 ///
 /// Returns the codec if recognized, or an error for unknown codec IDs.
@@ -40,18 +47,62 @@ pub use traits::{CodecError, StorageCodec};
 /// # Known Codecs
 ///
 /// - `"identity"`: No-op codec (pass-through)
-///
-/// # Future Codecs
-///
-/// - `"aes-gcm-256"`: AES-256-GCM encryption
 /// - `"lz4"`: LZ4 compression
+/// - `"a+b"`: Chain of the codecs `a` then `b`
+///
+/// Keyed codecs such as `"aes-gcm-256"` cannot be built from an identifier
+/// alone and return `CodecError::InvalidKey`; construct them directly.
 pub fn get_codec(codec_id: &str) -> Result<Box<dyn StorageCodec>, CodecError> {
+    if codec_id.contains(CODEC_CHAIN_SEPARATOR) {
+        let stages = codec_id
+            .split(CODEC_CHAIN_SEPARATOR)
+            .map(get_codec)
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(Box::new(ChainedCodec::new(stages)));
+    }
+
     match codec_id {
         "identity" => Ok(Box::new(IdentityCodec)),
+        LZ4_CODEC_ID => Ok(Box::new(Lz4Codec)),
+        AES_GCM_CODEC_ID => Err(CodecError::InvalidKey(format!(
+            "codec '{}' requires an encryption key",
+            codec_id
+        ))),
         _ => Err(CodecError::UnknownCodec(codec_id.to_string())),
     }
 }
 
+/// Build the key check stored in the MANIFEST for `codec`.
+///
+/// The key check is a fixed plaintext encoded with the codec. Reopening
+/// with a different key fails to decode it, so a wrong key is reported
+/// before any WAL record is read. Returns an empty check for the identity
+/// codec.
+pub fn make_key_check(codec: &dyn StorageCodec) -> Vec<u8> {
+    if codec.codec_id() == "identity" {
+        return Vec::new();
+    }
+    codec.encode(KEY_CHECK_PLAINTEXT)
+}
+
+/// Verify a MANIFEST key check against `codec`.
+///
+/// An empty key check (older MANIFESTs, identity codec) always passes.
+pub fn verify_key_check(codec: &dyn StorageCodec, key_check: &[u8]) -> Result<(), CodecError> {
+    if key_check.is_empty() {
+        return Ok(());
+    }
+    let decoded = codec.decode(key_check)?;
+    if decoded != KEY_CHECK_PLAINTEXT {
+        return Err(CodecError::decode(
+            "key check mismatch",
+            codec.codec_id(),
+            key_check.len(),
+        ));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,4 +118,52 @@ mod tests {
         let result = get_codec("unknown");
         assert!(matches!(result, Err(CodecError::UnknownCodec(_))));
     }
+
+    #[test]
+    fn test_get_lz4_codec() {
+        let codec = get_codec("lz4").unwrap();
+        assert_eq!(codec.codec_id(), LZ4_CODEC_ID);
+    }
+
+    #[test]
+    fn test_get_chained_codec() {
+        let codec = get_codec("lz4+identity").unwrap();
+        assert_eq!(codec.codec_id(), "lz4+identity");
+        assert_eq!(codec.decode(&codec.encode(b"data")).unwrap(), b"data");
+
+        assert!(matches!(
+            get_codec("lz4+aes-gcm-256"),
+            Err(CodecError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            get_codec("lz4+zip"),
+            Err(CodecError::UnknownCodec(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_keyed_codec_requires_key() {
+        let result = get_codec(AES_GCM_CODEC_ID);
+        assert!(matches!(result, Err(CodecError::InvalidKey(_))));
+    }
+
+    #[test]
+    fn test_key_check_roundtrip() {
+        let codec = AesGcmCodec::new(&[3u8; 32]);
+        let check = make_key_check(&codec);
+        assert!(!check.is_empty());
+        assert!(verify_key_check(&codec, &check).is_ok());
+
+        let wrong = AesGcmCodec::new(&[4u8; 32]);
+        assert!(matches!(
+            verify_key_check(&wrong, &check),
+            Err(CodecError::DecodeError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_identity_key_check_is_empty() {
+        assert!(make_key_check(&IdentityCodec).is_empty());
+        assert!(verify_key_check(&IdentityCodec, &[]).is_ok());
+    }
 }
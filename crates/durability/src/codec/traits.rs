@@ -33,6 +33,12 @@ pub trait StorageCodec: Send + Sync {
     /// This is stored in the MANIFEST to ensure the correct codec
     /// is used when reopening a database.
     fn codec_id(&self) -> &str;
+
+    /// Clone this codec into a new boxed trait object.
+    ///
+    /// Keyed codecs cannot be reconstructed from their identifier alone,
+    /// so components that need their own codec instance clone it instead.
+    fn clone_box(&self) -> Box<dyn StorageCodec>;
 }
 
 /// Codec errors.
@@ -56,6 +62,10 @@ pub enum CodecError {
     #[error("Unknown codec: {0}")]
     UnknownCodec(String),
 
+    /// Key material is malformed or could not be loaded.
+    #[error("Invalid encryption key: {0}")]
+    InvalidKey(String),
+
     /// Codec mismatch (database was created with different codec).
     #[error("Codec mismatch: expected {expected}, got {actual}")]
     CodecMismatch {
@@ -1,7 +1,8 @@
 //! Database compaction
 //!
 //! Compaction reclaims disk space by removing WAL segments and old versions.
-//! Compaction is user-triggered and deterministic.
+//! Compaction is deterministic. It runs when the user calls it, or after each
+//! automatic checkpoint when the database has a `[checkpoint]` policy.
 //!
 //! # Compaction Modes
 //!
@@ -13,7 +14,8 @@
 //!
 //! # Key Invariants
 //!
-//! - Compaction is **user-triggered**: No background compaction
+//! - Compaction is **explicit or policy-driven**: The engine only compacts on
+//!   request or after an automatic checkpoint, never on its own schedule
 //! - Compaction is **deterministic**: Same input → same output
 //! - Compaction is **logically invisible**: Read results unchanged for retained data
 //! - **Version IDs never change**: Critical semantic invariant
@@ -89,6 +91,9 @@ pub struct CompactInfo {
     /// Number of WAL segments removed
     pub wal_segments_removed: usize,
 
+    /// Number of WAL segments archived before removal
+    pub wal_segments_archived: usize,
+
     /// Number of versions removed (Full mode only)
     pub versions_removed: usize,
 
@@ -109,6 +114,7 @@ impl CompactInfo {
             mode,
             reclaimed_bytes: 0,
             wal_segments_removed: 0,
+            wal_segments_archived: 0,
             versions_removed: 0,
             snapshot_watermark: None,
             duration_ms: 0,
@@ -219,6 +225,7 @@ mod tests {
         assert_eq!(info.mode, CompactMode::WALOnly);
         assert_eq!(info.reclaimed_bytes, 0);
         assert_eq!(info.wal_segments_removed, 0);
+        assert_eq!(info.wal_segments_archived, 0);
         assert_eq!(info.versions_removed, 0);
         assert_eq!(info.snapshot_watermark, None);
         assert_eq!(info.duration_ms, 0);
@@ -14,12 +14,17 @@
 //!    - Delete covered segments
 //! 4. Track reclaimed bytes and segment count
 //!
+//! With a [`WalArchiver`] attached, each covered segment is archived before
+//! it is deleted. A segment that fails to archive is kept.
+//!
 //! # Safety
 //!
 //! - Never removes the active segment
 //! - Only removes segments fully covered by snapshot
 //! - Requires a valid snapshot to exist
 
+use crate::archive::WalArchiver;
+use crate::codec::{IdentityCodec, StorageCodec};
 use crate::format::segment_meta::SegmentMeta;
 use crate::format::{
     ManifestManager, SegmentHeader, WalRecord, WalRecordError, SEGMENT_HEADER_SIZE,
@@ -37,12 +42,33 @@ use super::{CompactInfo, CompactMode, CompactionError};
 pub struct WalOnlyCompactor {
     wal_dir: PathBuf,
     manifest: Arc<Mutex<ManifestManager>>,
+    codec: Box<dyn StorageCodec>,
+    archiver: Option<Arc<dyn WalArchiver>>,
 }
 
 impl WalOnlyCompactor {
     /// Create a new WAL-only compactor
     pub fn new(wal_dir: PathBuf, manifest: Arc<Mutex<ManifestManager>>) -> Self {
-        WalOnlyCompactor { wal_dir, manifest }
+        WalOnlyCompactor {
+            wal_dir,
+            manifest,
+            codec: Box::new(IdentityCodec),
+            archiver: None,
+        }
+    }
+
+    /// Use `codec` to decode records during full-scan coverage checks.
+    ///
+    /// Must match the codec the WAL was written with.
+    pub fn with_codec(mut self, codec: Box<dyn StorageCodec>) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Archive covered segments with `archiver` before removing them.
+    pub fn with_archiver(mut self, archiver: Arc<dyn WalArchiver>) -> Self {
+        self.archiver = Some(archiver);
+        self
     }
 
     /// Perform WAL-only compaction
@@ -85,10 +111,26 @@ impl WalOnlyCompactor {
             }
 
             // Check if segment is fully covered by snapshot
-            match self.segment_covered_by_watermark(segment_number, watermark) {
-                Ok(true) => {
+            match self.segment_meta(segment_number) {
+                Ok(meta) if meta.is_empty() || meta.max_txn_id <= watermark => {
                     let segment_path = segment_path(&self.wal_dir, segment_number);
 
+                    if let Some(archiver) = &self.archiver {
+                        if let Err(e) =
+                            archiver.archive_segment(segment_number, &segment_path, &meta)
+                        {
+                            // Keep the segment; the next compaction retries
+                            warn!(
+                                target: "strata::compaction",
+                                segment = segment_number,
+                                error = %e,
+                                "Failed to archive WAL segment, keeping it"
+                            );
+                            continue;
+                        }
+                        info.wal_segments_archived += 1;
+                    }
+
                     match std::fs::metadata(&segment_path) {
                         Ok(metadata) => {
                             let segment_size = metadata.len();
@@ -131,7 +173,7 @@ impl WalOnlyCompactor {
                         }
                     }
                 }
-                Ok(false) => {
+                Ok(_) => {
                     // Segment has records beyond watermark, keep it
                 }
                 Err(e) => {
@@ -183,22 +225,25 @@ impl WalOnlyCompactor {
     /// Check if a segment is fully covered by the snapshot watermark.
     ///
     /// A segment is covered if its highest txn_id <= watermark.
-    /// Tries `.meta` sidecar first for O(1) check; falls back to full scan.
+    #[cfg(test)]
     fn segment_covered_by_watermark(
         &self,
         segment_number: u64,
         watermark: u64,
     ) -> Result<bool, CompactionError> {
+        let meta = self.segment_meta(segment_number)?;
+        Ok(meta.is_empty() || meta.max_txn_id <= watermark)
+    }
+
+    /// Describe the records in a segment.
+    ///
+    /// Tries `.meta` sidecar first for O(1) check; falls back to full scan.
+    fn segment_meta(&self, segment_number: u64) -> Result<SegmentMeta, CompactionError> {
         // Try .meta sidecar first (O(1) check)
         match SegmentMeta::read_from_file(&self.wal_dir, segment_number) {
             Ok(Some(meta)) if meta.segment_number == segment_number => {
-                if meta.is_empty() {
-                    debug!(target: "strata::compaction", segment = segment_number, "Empty segment (via .meta), considered covered");
-                    return Ok(true);
-                }
-                let covered = meta.max_txn_id <= watermark;
-                debug!(target: "strata::compaction", segment = segment_number, max_txn_id = meta.max_txn_id, watermark, covered, "Coverage check via .meta");
-                return Ok(covered);
+                debug!(target: "strata::compaction", segment = segment_number, max_txn_id = meta.max_txn_id, "Coverage check via .meta");
+                return Ok(meta);
             }
             Ok(Some(_)) => {
                 warn!(target: "strata::compaction", segment = segment_number, "Segment meta has mismatched segment number, falling back to full scan");
@@ -211,15 +256,11 @@ impl WalOnlyCompactor {
             }
         }
 
-        self.segment_covered_by_watermark_full_scan(segment_number, watermark)
+        self.segment_meta_full_scan(segment_number)
     }
 
-    /// Full-scan fallback: read all records to determine max txn_id.
-    fn segment_covered_by_watermark_full_scan(
-        &self,
-        segment_number: u64,
-        watermark: u64,
-    ) -> Result<bool, CompactionError> {
+    /// Full-scan fallback: read all records to rebuild the segment metadata.
+    fn segment_meta_full_scan(&self, segment_number: u64) -> Result<SegmentMeta, CompactionError> {
         let segment_path = segment_path(&self.wal_dir, segment_number);
         let file_data = std::fs::read(&segment_path)?;
 
@@ -249,19 +290,13 @@ impl WalOnlyCompactor {
             SEGMENT_HEADER_SIZE
         };
 
-        // Empty segment (just header) is considered covered
-        if file_data.len() <= actual_header_size {
-            return Ok(true);
-        }
-
-        // Find highest txn_id in segment
+        let mut meta = SegmentMeta::new_empty(segment_number);
         let mut cursor = actual_header_size;
-        let mut max_txn_id = 0u64;
 
         while cursor < file_data.len() {
-            match WalRecord::from_bytes(&file_data[cursor..]) {
+            match WalRecord::from_encoded_bytes(&file_data[cursor..], self.codec.as_ref()) {
                 Ok((record, consumed)) => {
-                    max_txn_id = max_txn_id.max(record.txn_id);
+                    meta.track_record(record.txn_id, record.timestamp);
                     cursor += consumed;
                 }
                 Err(WalRecordError::InsufficientData) => {
@@ -270,14 +305,20 @@ impl WalOnlyCompactor {
                 Err(WalRecordError::ChecksumMismatch { .. }) => {
                     break;
                 }
+                Err(WalRecordError::Codec(e)) => {
+                    // Never delete a segment we cannot read
+                    return Err(CompactionError::internal(format!(
+                        "Segment {} cannot be decoded: {}",
+                        segment_number, e
+                    )));
+                }
                 Err(_) => {
                     break;
                 }
             }
         }
 
-        // Segment is covered if all records are at or below watermark
-        Ok(max_txn_id <= watermark)
+        Ok(meta)
     }
 
     /// Get the WAL directory path
@@ -592,4 +633,69 @@ mod tests {
         assert_eq!(info.wal_segments_removed, 1);
         assert!(!segment_path(&wal_dir, 1).exists());
     }
+
+    struct FailingArchiver;
+
+    impl WalArchiver for FailingArchiver {
+        fn archive_segment(
+            &self,
+            _segment_number: u64,
+            _segment_path: &Path,
+            _meta: &SegmentMeta,
+        ) -> Result<(), crate::archive::ArchiveError> {
+            Err(crate::archive::ArchiveError::Archiver("unavailable".into()))
+        }
+    }
+
+    #[test]
+    fn test_compact_archives_before_removing() {
+        let (dir, wal_dir, manifest) = setup_test_env();
+
+        create_segment_with_records(&wal_dir, 1, &[1, 2, 3]).unwrap();
+        create_segment_with_records(&wal_dir, 2, &[4, 5, 6]).unwrap();
+        {
+            let mut m = manifest.lock();
+            m.set_snapshot_watermark(1, 3).unwrap();
+            m.manifest_mut().active_wal_segment = 3;
+            m.persist().unwrap();
+        }
+
+        let archive =
+            Arc::new(crate::archive::LocalWalArchive::new(dir.path().join("archive")).unwrap());
+        let compactor =
+            WalOnlyCompactor::new(wal_dir.clone(), manifest).with_archiver(archive.clone());
+        let info = compactor.compact().unwrap();
+
+        assert_eq!(info.wal_segments_removed, 1);
+        assert_eq!(info.wal_segments_archived, 1);
+        assert!(!segment_path(&wal_dir, 1).exists());
+
+        // Archived without a .meta sidecar: metadata comes from the full scan
+        let entries = archive.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].meta.max_txn_id, 3);
+        assert_eq!(entries[0].meta.max_timestamp, 3000);
+        assert!(archive.verify().unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_compact_keeps_segment_when_archive_fails() {
+        let (_dir, wal_dir, manifest) = setup_test_env();
+
+        create_segment_with_records(&wal_dir, 1, &[1, 2, 3]).unwrap();
+        {
+            let mut m = manifest.lock();
+            m.set_snapshot_watermark(1, 3).unwrap();
+            m.manifest_mut().active_wal_segment = 2;
+            m.persist().unwrap();
+        }
+
+        let compactor = WalOnlyCompactor::new(wal_dir.clone(), manifest)
+            .with_archiver(Arc::new(FailingArchiver));
+        let info = compactor.compact().unwrap();
+
+        assert_eq!(info.wal_segments_removed, 0);
+        assert_eq!(info.wal_segments_archived, 0);
+        assert!(segment_path(&wal_dir, 1).exists());
+    }
 }
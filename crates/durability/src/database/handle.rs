@@ -377,7 +377,7 @@ fn generate_uuid() -> [u8; 16] {
 fn clone_codec(
     codec: &dyn StorageCodec,
 ) -> Result<Box<dyn StorageCodec>, crate::codec::CodecError> {
-    get_codec(codec.codec_id())
+    Ok(codec.clone_box())
 }
 
 #[cfg(test)]
@@ -131,6 +131,10 @@ impl CheckpointCoordinator {
             ));
         }
 
+        if let Some(storage) = data.storage {
+            sections.push(SnapshotSection::new(primitive_tags::STORAGE, storage));
+        }
+
         // Create the snapshot
         let snapshot_info = self
             .snapshot_writer
@@ -176,6 +180,8 @@ pub struct CheckpointData {
     pub json: Option<Vec<crate::format::primitives::JsonSnapshotEntry>>,
     /// Vector primitive entries
     pub vectors: Option<Vec<crate::format::primitives::VectorCollectionSnapshotEntry>>,
+    /// Serialized full storage state, written as-is (see `primitive_tags::STORAGE`)
+    pub storage: Option<Vec<u8>>,
 }
 
 impl CheckpointData {
@@ -231,6 +237,12 @@ impl CheckpointData {
         self.vectors = Some(entries);
         self
     }
+
+    /// Add the serialized storage state
+    pub fn with_storage(mut self, bytes: Vec<u8>) -> Self {
+        self.storage = Some(bytes);
+        self
+    }
 }
 
 /// Errors that can occur during checkpoint creation
@@ -363,6 +375,28 @@ mod tests {
         assert_eq!(info.snapshot_id, 1);
     }
 
+    #[test]
+    fn test_checkpoint_with_storage_section() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let mut coordinator = CheckpointCoordinator::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap();
+
+        let data = CheckpointData::new().with_storage(b"opaque state".to_vec());
+        let info = coordinator.checkpoint(7, data).unwrap();
+
+        let path = crate::format::snapshot_path(temp_dir.path(), info.snapshot_id);
+        let loaded = crate::disk_snapshot::SnapshotReader::new(Box::new(IdentityCodec))
+            .load(&path)
+            .unwrap();
+        let section = loaded.find_section(primitive_tags::STORAGE).unwrap();
+        assert_eq!(section.data, b"opaque state");
+    }
+
     #[test]
     fn test_watermark_coverage() {
         let temp_dir = tempfile::tempdir().unwrap();
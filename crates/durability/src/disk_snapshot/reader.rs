@@ -149,7 +149,8 @@ impl SnapshotReader {
                 });
             }
 
-            let section_data = data[cursor..cursor + data_len].to_vec();
+            // Sections are stored codec-encoded (see SnapshotWriter)
+            let section_data = self.codec.decode(&data[cursor..cursor + data_len])?;
             cursor += data_len;
 
             sections.push(LoadedSection {
@@ -580,4 +581,36 @@ mod tests {
         assert_eq!(loaded.sections[0].primitive_name(), "KV");
         assert_eq!(loaded.sections[1].primitive_name(), "Event");
     }
+
+    #[test]
+    fn test_encrypted_snapshot_roundtrip() {
+        use crate::codec::AesGcmCodec;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let writer = SnapshotWriter::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(AesGcmCodec::new(&[5u8; 32])),
+            test_uuid(),
+        )
+        .unwrap();
+
+        let sections = vec![SnapshotSection::new(
+            primitive_tags::KV,
+            b"secret section".to_vec(),
+        )];
+        let info = writer.create_snapshot(1, 100, sections).unwrap();
+
+        let raw = std::fs::read(&info.path).unwrap();
+        assert!(!raw.windows(14).any(|w| w == b"secret section"));
+
+        let reader = SnapshotReader::new(Box::new(AesGcmCodec::new(&[5u8; 32])));
+        let loaded = reader.load(&info.path).unwrap();
+        assert_eq!(loaded.sections[0].data, b"secret section");
+
+        let wrong = SnapshotReader::new(Box::new(AesGcmCodec::new(&[6u8; 32])));
+        assert!(matches!(
+            wrong.load(&info.path),
+            Err(SnapshotReadError::Codec(CodecError::DecodeError { .. }))
+        ));
+    }
 }
@@ -102,16 +102,17 @@ impl SnapshotWriter {
         let mut all_bytes = header.to_bytes().to_vec();
         all_bytes.extend_from_slice(codec_id.as_bytes());
 
-        // Write sections
+        // Write sections (data passes through the codec; the header
+        // records the encoded length)
         for section in &sections {
-            let section_header =
-                SectionHeader::new(section.primitive_type, section.data.len() as u64);
+            let data = self.codec.encode(&section.data);
+            let section_header = SectionHeader::new(section.primitive_type, data.len() as u64);
             let section_header_bytes = section_header.to_bytes();
             file.write_all(&section_header_bytes)?;
-            file.write_all(&section.data)?;
+            file.write_all(&data)?;
 
             all_bytes.extend_from_slice(&section_header_bytes);
-            all_bytes.extend_from_slice(&section.data);
+            all_bytes.extend_from_slice(&data);
         }
 
         // Write footer CRC32
@@ -15,9 +15,15 @@
 //! | Active WAL Seg   | 8 bytes (u64 LE)
 //! | Snapshot Watermark | 8 bytes (u64 LE, 0 = none)
 //! | Snapshot ID      | 8 bytes (u64 LE, 0 = none)
+//! | Key Check Length | 4 bytes (u32 LE, version >= 2)
+//! | Key Check        | variable (version >= 2)
 //! | CRC32            | 4 bytes
 //! +------------------+
 //! ```
+//!
+//! The key check is a fixed plaintext encoded with the database codec (see
+//! `codec::make_key_check`). It is empty for the identity codec. Version 1
+//! MANIFESTs have no key check and are read with an empty one.
 
 use std::fs::{File, OpenOptions};
 use std::io::Write;
@@ -27,7 +33,7 @@ use std::path::{Path, PathBuf};
 pub const MANIFEST_MAGIC: [u8; 4] = *b"STRM";
 
 /// Current MANIFEST format version
-pub const MANIFEST_FORMAT_VERSION: u32 = 1;
+pub const MANIFEST_FORMAT_VERSION: u32 = 2;
 
 /// MANIFEST file structure
 ///
@@ -50,6 +56,8 @@ pub struct Manifest {
     pub snapshot_watermark: Option<u64>,
     /// Latest snapshot identifier (if any)
     pub snapshot_id: Option<u64>,
+    /// Codec-encoded key check (empty for the identity codec)
+    pub key_check: Vec<u8>,
 }
 
 impl Manifest {
@@ -62,6 +70,7 @@ impl Manifest {
             active_wal_segment: 1,
             snapshot_watermark: None,
             snapshot_id: None,
+            key_check: Vec::new(),
         }
     }
 
@@ -93,6 +102,12 @@ impl Manifest {
         let snapshot_id = self.snapshot_id.unwrap_or(0);
         bytes.extend_from_slice(&snapshot_id.to_le_bytes());
 
+        // Key check (length-prefixed, version >= 2)
+        if self.format_version >= 2 {
+            bytes.extend_from_slice(&(self.key_check.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&self.key_check);
+        }
+
         // CRC32 of all preceding bytes
         let crc = crc32fast::hash(&bytes);
         bytes.extend_from_slice(&crc.to_le_bytes());
@@ -157,12 +172,29 @@ impl Manifest {
 
         // Snapshot ID
         let snapshot_id_val = u64::from_le_bytes(bytes[cursor..cursor + 8].try_into().unwrap());
+        cursor += 8;
         let snapshot_id = if snapshot_id_val > 0 {
             Some(snapshot_id_val)
         } else {
             None
         };
 
+        // Key check (version >= 2)
+        let key_check = if format_version >= 2 {
+            if cursor + 4 > bytes.len() - 4 {
+                return Err(ManifestError::TooShort);
+            }
+            let key_check_len =
+                u32::from_le_bytes(bytes[cursor..cursor + 4].try_into().unwrap()) as usize;
+            cursor += 4;
+            if cursor + key_check_len > bytes.len() - 4 {
+                return Err(ManifestError::TooShort);
+            }
+            bytes[cursor..cursor + key_check_len].to_vec()
+        } else {
+            Vec::new()
+        };
+
         Ok(Manifest {
             format_version,
             database_uuid,
@@ -170,6 +202,7 @@ impl Manifest {
             active_wal_segment,
             snapshot_watermark,
             snapshot_id,
+            key_check,
         })
     }
 }
@@ -268,6 +301,14 @@ impl ManifestManager {
         self.persist()
     }
 
+    /// Update codec identity and key check and persist (used by rekey)
+    pub fn set_codec(&mut self, codec_id: String, key_check: Vec<u8>) -> Result<(), ManifestError> {
+        self.manifest.codec_id = codec_id;
+        self.manifest.key_check = key_check;
+        self.manifest.format_version = MANIFEST_FORMAT_VERSION;
+        self.persist()
+    }
+
     /// Clear snapshot info (for testing/reset)
     pub fn clear_snapshot(&mut self) -> Result<(), ManifestError> {
         self.manifest.snapshot_id = None;
@@ -344,6 +385,7 @@ mod tests {
             active_wal_segment: 42,
             snapshot_watermark: Some(1000),
             snapshot_id: Some(5),
+            key_check: vec![0xAA; 44],
         };
 
         let bytes = manifest.to_bytes();
@@ -427,6 +469,36 @@ mod tests {
         assert_eq!(parsed.codec_id, long_codec);
     }
 
+    #[test]
+    fn test_manifest_v1_has_no_key_check() {
+        let mut manifest = Manifest::new(test_uuid(), "identity".to_string());
+        manifest.format_version = 1;
+
+        let bytes = manifest.to_bytes();
+        assert_eq!(bytes.len(), 56 + "identity".len());
+
+        let parsed = Manifest::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.format_version, 1);
+        assert!(parsed.key_check.is_empty());
+    }
+
+    #[test]
+    fn test_manifest_manager_set_codec() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manifest_path = temp_dir.path().join("MANIFEST");
+
+        let mut manager =
+            ManifestManager::create(manifest_path.clone(), test_uuid(), "identity".to_string())
+                .unwrap();
+        manager
+            .set_codec("aes-gcm-256".to_string(), vec![1, 2, 3])
+            .unwrap();
+
+        let loaded = ManifestManager::load(manifest_path).unwrap();
+        assert_eq!(loaded.manifest().codec_id, "aes-gcm-256");
+        assert_eq!(loaded.manifest().key_check, vec![1, 2, 3]);
+    }
+
     #[test]
     fn test_manifest_manager_create_and_load() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -197,6 +197,8 @@ pub mod primitive_tags {
     pub const JSON: u8 = 0x06;
     /// Vector embedding
     pub const VECTOR: u8 = 0x07;
+    /// Full storage state (every key version, used for restore)
+    pub const STORAGE: u8 = 0x08;
 
     /// Get the tag name for display
     pub fn tag_name(tag: u8) -> &'static str {
@@ -207,12 +209,13 @@ pub mod primitive_tags {
             BRANCH => "Branch",
             JSON => "Json",
             VECTOR => "Vector",
+            STORAGE => "Storage",
             _ => "Unknown",
         }
     }
 
     /// All valid primitive tags in order
-    pub const ALL_TAGS: [u8; 6] = [KV, EVENT, STATE, BRANCH, JSON, VECTOR];
+    pub const ALL_TAGS: [u8; 7] = [KV, EVENT, STATE, BRANCH, JSON, VECTOR, STORAGE];
 }
 
 /// Generate snapshot file path
@@ -349,12 +352,13 @@ mod tests {
         assert_eq!(primitive_tags::tag_name(primitive_tags::BRANCH), "Branch");
         assert_eq!(primitive_tags::tag_name(primitive_tags::JSON), "Json");
         assert_eq!(primitive_tags::tag_name(primitive_tags::VECTOR), "Vector");
+        assert_eq!(primitive_tags::tag_name(primitive_tags::STORAGE), "Storage");
         assert_eq!(primitive_tags::tag_name(0xFF), "Unknown");
     }
 
     #[test]
     fn test_all_tags() {
-        assert_eq!(primitive_tags::ALL_TAGS.len(), 6);
+        assert_eq!(primitive_tags::ALL_TAGS.len(), 7);
         assert_eq!(
             primitive_tags::ALL_TAGS,
             [
@@ -364,6 +368,7 @@ mod tests {
                 primitive_tags::BRANCH,
                 primitive_tags::JSON,
                 primitive_tags::VECTOR,
+                primitive_tags::STORAGE,
             ]
         );
     }
@@ -28,7 +28,18 @@
 //! │ TxnId (8)    │ BranchId (16)   │ Timestamp (8)│ Writeset (variable)         │
 //! └──────────────┴──────────────┴──────────────┴─────────────────────────────┘
 //! ```
+//!
+//! With a non-identity codec (e.g. encryption) the whole record above is
+//! passed through the codec and stored in the same length + CRC32 envelope,
+//! with the CRC computed over the encoded bytes:
+//!
+//! ```text
+//! ┌─────────────────┬─────────────────────────────────┬──────────┐
+//! │ Length (4 bytes)│ Codec-encoded record (variable) │ CRC32 (4)│
+//! └─────────────────┴─────────────────────────────────┴──────────┘
+//! ```
 
+use crate::codec::{CodecError, StorageCodec};
 use crc32fast::Hasher;
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
@@ -419,6 +430,14 @@ impl WalSegment {
         self.file.sync_all()
     }
 
+    /// Open a second handle to the segment file.
+    ///
+    /// Syncing the handle syncs the segment, so a sync can run while the
+    /// segment keeps taking writes.
+    pub fn try_clone_file(&self) -> std::io::Result<File> {
+        self.file.try_clone()
+    }
+
     /// Mark segment as closed (immutable).
     ///
     /// Syncs data to disk before closing.
@@ -585,6 +604,48 @@ impl WalRecord {
         Ok((record, 4 + length))
     }
 
+    /// Serialize record to bytes, passing it through `codec`.
+    ///
+    /// The identity codec produces exactly `to_bytes()`, so plaintext WALs
+    /// keep their original layout. Other codecs wrap the encoded record in a
+    /// length + CRC32 envelope so torn writes and corruption are detected
+    /// without decoding.
+    pub fn to_encoded_bytes(&self, codec: &dyn StorageCodec) -> Vec<u8> {
+        let record = self.to_bytes();
+        if codec.codec_id() == "identity" {
+            return record;
+        }
+
+        let encoded = codec.encode(&record);
+        let crc = Self::compute_crc(&encoded);
+        let mut frame = Vec::with_capacity(8 + encoded.len());
+        frame.extend_from_slice(&((encoded.len() + 4) as u32).to_le_bytes());
+        frame.extend_from_slice(&encoded);
+        frame.extend_from_slice(&crc.to_le_bytes());
+        frame
+    }
+
+    /// Deserialize a record written by `to_encoded_bytes`.
+    ///
+    /// Returns (record, bytes_consumed) on success. A frame whose checksum
+    /// is valid but which the codec cannot decode yields
+    /// `WalRecordError::Codec` (typically a wrong encryption key).
+    pub fn from_encoded_bytes(
+        bytes: &[u8],
+        codec: &dyn StorageCodec,
+    ) -> Result<(Self, usize), WalRecordError> {
+        if codec.codec_id() == "identity" {
+            return Self::from_bytes(bytes);
+        }
+
+        Self::verify_checksum(bytes)?;
+        let length = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+        let decoded = codec.decode(&bytes[4..4 + length - 4])?;
+        let (record, _) = Self::from_bytes(&decoded)?;
+
+        Ok((record, 4 + length))
+    }
+
     /// Compute CRC32 checksum of data.
     fn compute_crc(data: &[u8]) -> u32 {
         let mut hasher = Hasher::new();
@@ -642,6 +703,10 @@ pub enum WalRecordError {
     /// Unsupported format version
     #[error("Unsupported format version: {0}")]
     UnsupportedVersion(u8),
+
+    /// Checksum was valid but the codec could not decode the record
+    #[error("Codec error: {0}")]
+    Codec(#[from] CodecError),
 }
 
 #[cfg(test)]
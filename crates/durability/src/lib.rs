@@ -18,6 +18,7 @@
 #![warn(clippy::all)]
 
 // === Existing modules ===
+pub mod archive; // WAL segment archiving ahead of compaction (WalArchiver, LocalWalArchive)
 pub mod branch_bundle; // Portable execution artifacts (BranchBundle)
 pub mod recovery; // WAL replay logic
 pub mod snapshot; // Snapshot writer and serialization
@@ -25,7 +26,7 @@ pub mod snapshot_types; // Snapshot envelope and header types
 pub mod wal; // WAL segment types, durability modes
 
 // === Modules moved from storage crate (Phase 1 consolidation) ===
-pub mod codec; // Storage codec abstraction (identity, future encryption/compression)
+pub mod codec; // Storage codec abstraction (identity, LZ4, AES-GCM, chains)
 pub mod compaction; // WAL segment cleanup and tombstone tracking
 pub mod disk_snapshot; // Crash-safe snapshot I/O and checkpoint coordination
 pub mod format; // Binary on-disk formats (WAL segments, snapshots, manifest, writesets)
@@ -58,7 +59,9 @@ pub use branch_bundle::{
 // === Re-exports from moved modules ===
 
 // Codec
-pub use codec::{get_codec, CodecError, IdentityCodec, StorageCodec};
+pub use codec::{
+    get_codec, AesGcmCodec, ChainedCodec, CodecError, IdentityCodec, Lz4Codec, StorageCodec,
+};
 
 // Disk snapshot
 pub use disk_snapshot::{
@@ -128,6 +131,11 @@ pub use compaction::{
     TombstoneReason, WalOnlyCompactor,
 };
 
+// WAL archiving
+pub use archive::{
+    ArchiveEntry, ArchiveError, ArchivePruneInfo, ArchiveVerifyReport, LocalWalArchive, WalArchiver,
+};
+
 // Testing utilities
 pub use testing::{
     CrashConfig, CrashPoint, CrashTestError, CrashTestResult, CrashType, DataState, Operation,
@@ -142,7 +150,8 @@ pub use database::{
 
 // WAL segmented types (new in Phase 2)
 pub use wal::{
-    TruncateInfo, WalConfig, WalConfigError, WalCounters, WalReader, WalReaderError, WalWriter,
+    GroupCommit, TruncateInfo, WalConfig, WalConfigError, WalCounters, WalReader, WalReaderError,
+    WalWriter,
 };
 
 // Recovery coordinator types (new in Phase 2)
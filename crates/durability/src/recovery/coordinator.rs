@@ -365,7 +365,7 @@ impl RecoveryError {
 
 /// Helper to clone a boxed codec
 fn clone_codec(codec: &dyn StorageCodec) -> Result<Box<dyn StorageCodec>, CodecError> {
-    crate::codec::get_codec(codec.codec_id())
+    Ok(codec.clone_box())
 }
 
 #[cfg(test)]
@@ -29,13 +29,15 @@
 use std::collections::HashMap;
 use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
 use strata_core::PrimitiveType;
 
 /// Retention policy for a branch
 ///
 /// Controls how much version history is retained.
 /// Policies are stored as database entries and are themselves versioned.
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RetentionPolicy {
     /// Keep all versions forever (default)
     ///
@@ -102,6 +104,28 @@ impl RetentionPolicy {
         }
     }
 
+    /// Check that the policy's parameters are valid
+    ///
+    /// Policies built with the constructors are always valid; this catches
+    /// `KeepLast(0)` and `KeepFor(0)` in policies that were deserialized.
+    pub fn validate(&self) -> Result<(), RetentionPolicyError> {
+        match self {
+            RetentionPolicy::KeepAll => Ok(()),
+            RetentionPolicy::KeepLast(0) => Err(RetentionPolicyError::InvalidValue(
+                "KeepLast(0) is invalid".to_string(),
+            )),
+            RetentionPolicy::KeepLast(_) => Ok(()),
+            RetentionPolicy::KeepFor(duration) if duration.is_zero() => Err(
+                RetentionPolicyError::InvalidValue("KeepFor(0) is invalid".to_string()),
+            ),
+            RetentionPolicy::KeepFor(_) => Ok(()),
+            RetentionPolicy::Composite { default, overrides } => {
+                default.validate()?;
+                overrides.values().try_for_each(|policy| policy.validate())
+            }
+        }
+    }
+
     /// Check if a version should be retained
     ///
     /// # Arguments
@@ -536,6 +560,30 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_serde_roundtrip_and_validate() {
+        let composite = RetentionPolicy::composite(RetentionPolicy::keep_last(5))
+            .with_override(
+                PrimitiveType::Event,
+                RetentionPolicy::keep_for(Duration::from_secs(3600)),
+            )
+            .build();
+        let json = serde_json::to_string(&composite).unwrap();
+        let restored: RetentionPolicy = serde_json::from_str(&json).unwrap();
+        assert_eq!(composite, restored);
+        assert!(restored.validate().is_ok());
+
+        let invalid: RetentionPolicy = serde_json::from_str(r#"{"keep_last":0}"#).unwrap();
+        assert!(matches!(
+            invalid.validate(),
+            Err(RetentionPolicyError::InvalidValue(_))
+        ));
+        let nested = RetentionPolicy::composite(RetentionPolicy::keep_all())
+            .with_override(PrimitiveType::Kv, invalid)
+            .build();
+        assert!(nested.validate().is_err());
+    }
+
     #[test]
     fn test_summary() {
         assert_eq!(RetentionPolicy::keep_all().summary(), "KeepAll");
@@ -15,6 +15,21 @@ pub struct WalConfig {
     /// For Standard durability mode, fsync is triggered when this many
     /// bytes have been written since the last fsync.
     pub buffered_sync_bytes: u64,
+
+    /// Leave `Always` mode's per-commit fsync to a [`GroupCommit`]
+    /// (default: false).
+    ///
+    /// Appends are written but not synced; each committer then calls
+    /// [`GroupCommit::wait_durable`], which batches the fsyncs of concurrent
+    /// committers. Has no effect in other durability modes.
+    ///
+    /// The caller decides what readers see meanwhile: the engine applies a
+    /// commit before its sync, so a failed sync leaves visible writes that
+    /// may not be on disk, and it stops accepting transactions after one.
+    ///
+    /// [`GroupCommit`]: super::GroupCommit
+    /// [`GroupCommit::wait_durable`]: super::GroupCommit::wait_durable
+    pub group_commit: bool,
 }
 
 impl Default for WalConfig {
@@ -22,6 +37,7 @@ impl Default for WalConfig {
         WalConfig {
             segment_size: 64 * 1024 * 1024,       // 64MB
             buffered_sync_bytes: 4 * 1024 * 1024, // 4MB
+            group_commit: false,
         }
     }
 }
@@ -44,6 +60,12 @@ impl WalConfig {
         self
     }
 
+    /// Enable or disable group commit (builder pattern).
+    pub fn with_group_commit(mut self, enabled: bool) -> Self {
+        self.group_commit = enabled;
+        self
+    }
+
     /// Validate configuration.
     pub fn validate(&self) -> Result<(), WalConfigError> {
         if self.segment_size < 1024 {
@@ -60,6 +82,7 @@ impl WalConfig {
         WalConfig {
             segment_size: 64 * 1024,        // 64KB for faster rotation in tests
             buffered_sync_bytes: 16 * 1024, // 16KB
+            group_commit: false,
         }
     }
 }
@@ -85,16 +108,19 @@ mod tests {
         let config = WalConfig::default();
         assert_eq!(config.segment_size, 64 * 1024 * 1024);
         assert_eq!(config.buffered_sync_bytes, 4 * 1024 * 1024);
+        assert!(!config.group_commit);
     }
 
     #[test]
     fn test_builder_pattern() {
         let config = WalConfig::new()
             .with_segment_size(128 * 1024 * 1024)
-            .with_buffered_sync_bytes(8 * 1024 * 1024);
+            .with_buffered_sync_bytes(8 * 1024 * 1024)
+            .with_group_commit(true);
 
         assert_eq!(config.segment_size, 128 * 1024 * 1024);
         assert_eq!(config.buffered_sync_bytes, 8 * 1024 * 1024);
+        assert!(config.group_commit);
     }
 
     #[test]
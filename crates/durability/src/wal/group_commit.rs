@@ -0,0 +1,232 @@
+//! Group commit for `Always` durability.
+//!
+//! With `WalConfig::group_commit`, an `Always`-mode append writes the record
+//! but does not fsync it. The committer then calls
+//! [`GroupCommit::wait_durable`] with the record's sequence number, and
+//! returns only once a sync covering that record has completed.
+//!
+//! One waiting committer at a time leads a sync: it captures how many
+//! records have been appended, fsyncs the segment without holding the
+//! writer lock, and wakes every committer the sync covered. Committers that
+//! append while a sync is running wait for it and then share the next one,
+//! so N concurrent commits cost about one fsync instead of N.
+//!
+//! A failed sync poisons the group: after an fsync error the kernel may have
+//! dropped the dirty pages, so a later sync could report success for data
+//! that never reached disk. Every later wait fails instead of retrying.
+
+use std::time::Instant;
+
+use parking_lot::{Condvar, Mutex};
+
+use super::WalWriter;
+
+/// Batches the fsyncs of concurrent `Always`-mode committers.
+///
+/// Share one `GroupCommit` between all committers of a `WalWriter` opened
+/// with `WalConfig::group_commit`.
+#[derive(Debug, Default)]
+pub struct GroupCommit {
+    state: Mutex<GroupState>,
+    synced: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct GroupState {
+    /// Appends known to be on disk
+    durable: u64,
+    /// Whether a committer is running a sync
+    syncing: bool,
+    /// Error of the failed sync that poisoned the group
+    failure: Option<String>,
+    /// Error the next sync reports instead of syncing (tests only)
+    injected: Option<String>,
+}
+
+impl GroupCommit {
+    /// Create a group commit coordinator with nothing synced yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait until the record with sequence number `seq` (from
+    /// `WalWriter::append_seq`) and everything appended before it is on
+    /// disk, leading a sync if none in progress covers it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the fsync error if the sync this caller led failed, and an
+    /// error for every wait not already covered once the group is poisoned.
+    pub fn wait_durable(&self, wal: &Mutex<WalWriter>, seq: u64) -> std::io::Result<()> {
+        let mut state = self.state.lock();
+        while state.syncing && state.durable < seq {
+            self.synced.wait(&mut state);
+        }
+        if state.durable >= seq {
+            return Ok(());
+        }
+        if let Some(failure) = &state.failure {
+            return Err(Self::poisoned_error(failure));
+        }
+        state.syncing = true;
+        let injected = state.injected.take();
+        drop(state);
+
+        let result = match injected {
+            Some(reason) => Err(std::io::Error::new(std::io::ErrorKind::Other, reason)),
+            None => Self::sync(wal),
+        };
+
+        let mut state = self.state.lock();
+        state.syncing = false;
+        match &result {
+            Ok(through) => state.durable = state.durable.max(*through),
+            Err(e) => state.failure = Some(e.to_string()),
+        }
+        drop(state);
+        self.synced.notify_all();
+        result.map(|_| ())
+    }
+
+    /// Make the next sync fail with `reason` instead of syncing.
+    ///
+    /// Lets tests exercise the failed-sync path, since a real fsync error
+    /// cannot be provoked on demand.
+    #[doc(hidden)]
+    pub fn fail_next_sync(&self, reason: &str) {
+        self.state.lock().injected = Some(reason.to_string());
+    }
+
+    /// Whether a failed sync has poisoned the group.
+    pub fn is_poisoned(&self) -> bool {
+        self.state.lock().failure.is_some()
+    }
+
+    fn poisoned_error(failure: &str) -> std::io::Error {
+        std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("WAL poisoned by an earlier failed sync: {}", failure),
+        )
+    }
+
+    /// Sync everything appended so far. Returns how many appends are durable.
+    fn sync(wal: &Mutex<WalWriter>) -> std::io::Result<u64> {
+        let (through, file) = wal.lock().begin_group_sync()?;
+        // No file: already synced by a flush, rotation or earlier group sync
+        if let Some(file) = file {
+            let start = Instant::now();
+            file.sync_all()?;
+            wal.lock().finish_group_sync(through, start.elapsed());
+        }
+        Ok(through)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::IdentityCodec;
+    use crate::format::WalRecord;
+    use crate::wal::{DurabilityMode, WalConfig, WalReader};
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    fn make_writer(dir: &std::path::Path) -> WalWriter {
+        WalWriter::new(
+            dir.to_path_buf(),
+            [1u8; 16],
+            DurabilityMode::Always,
+            WalConfig::for_testing().with_group_commit(true),
+            Box::new(IdentityCodec),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_append_defers_sync_to_group_commit() {
+        let dir = tempdir().unwrap();
+        let wal = Mutex::new(make_writer(dir.path()));
+        let group = GroupCommit::new();
+
+        for txn_id in 1..=3 {
+            wal.lock()
+                .append(&WalRecord::new(txn_id, [1u8; 16], 0, vec![1, 2, 3]))
+                .unwrap();
+        }
+        assert_eq!(wal.lock().counters().sync_calls, 0);
+
+        let seq = wal.lock().append_seq();
+        assert_eq!(seq, 3);
+        group.wait_durable(&wal, seq).unwrap();
+        assert_eq!(wal.lock().counters().sync_calls, 1);
+
+        // Already durable: no further sync
+        group.wait_durable(&wal, 2).unwrap();
+        assert_eq!(wal.lock().counters().sync_calls, 1);
+    }
+
+    #[test]
+    fn test_failed_sync_poisons_group() {
+        let dir = tempdir().unwrap();
+        let wal = Mutex::new(make_writer(dir.path()));
+        let group = GroupCommit::new();
+
+        wal.lock()
+            .append(&WalRecord::new(1, [1u8; 16], 0, vec![1, 2, 3]))
+            .unwrap();
+        group.wait_durable(&wal, 1).unwrap();
+        group.state.lock().failure = Some("injected".to_string());
+        assert!(group.is_poisoned());
+
+        // Already durable records stay durable; later ones never sync again
+        group.wait_durable(&wal, 1).unwrap();
+        wal.lock()
+            .append(&WalRecord::new(2, [1u8; 16], 0, vec![1, 2, 3]))
+            .unwrap();
+        let err = group.wait_durable(&wal, 2).unwrap_err();
+        assert!(err.to_string().contains("injected"));
+        assert_eq!(wal.lock().counters().sync_calls, 1);
+    }
+
+    #[test]
+    fn test_concurrent_commits_share_syncs() {
+        let dir = tempdir().unwrap();
+        let wal = Arc::new(Mutex::new(make_writer(dir.path())));
+        let group = Arc::new(GroupCommit::new());
+        let threads = 8;
+        let per_thread = 50;
+
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                let wal = Arc::clone(&wal);
+                let group = Arc::clone(&group);
+                std::thread::spawn(move || {
+                    for i in 0..per_thread {
+                        let seq = {
+                            let mut wal = wal.lock();
+                            let txn_id = (t * per_thread + i + 1) as u64;
+                            wal.append(&WalRecord::new(txn_id, [1u8; 16], 0, vec![0; 32]))
+                                .unwrap();
+                            wal.append_seq()
+                        };
+                        group.wait_durable(&wal, seq).unwrap();
+                        assert!(group.state.lock().durable >= seq);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let counters = wal.lock().counters();
+        assert_eq!(counters.wal_appends, (threads * per_thread) as u64);
+        assert!(counters.sync_calls <= counters.wal_appends);
+
+        let records = WalReader::new(Box::new(IdentityCodec))
+            .read_all(dir.path())
+            .unwrap()
+            .records;
+        assert_eq!(records.len(), threads * per_thread);
+    }
+}
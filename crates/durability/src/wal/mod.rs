@@ -2,10 +2,12 @@
 //!
 //! - `mode`: DurabilityMode (Cache, Always, Standard)
 //! - `config`: WAL configuration (WalConfig, WalConfigError)
+//! - `group_commit`: Batched fsyncs for `Always` mode (GroupCommit)
 //! - `writer`: Segmented WAL writer (WalWriter)
 //! - `reader`: Segmented WAL reader (WalReader)
 
 pub mod config;
+pub mod group_commit;
 pub mod mode;
 pub mod reader;
 pub mod writer;
@@ -15,5 +17,6 @@ pub use mode::DurabilityMode;
 
 // Segmented WAL types (primary API)
 pub use config::{WalConfig, WalConfigError};
+pub use group_commit::GroupCommit;
 pub use reader::{ReadStopReason, TruncateInfo, WalReader, WalReaderError};
 pub use writer::{WalCounters, WalWriter};
@@ -2,7 +2,7 @@
 //!
 //! The reader handles reading WAL records from segments for recovery.
 
-use crate::codec::StorageCodec;
+use crate::codec::{CodecError, StorageCodec};
 use crate::format::segment_meta::SegmentMeta;
 use crate::format::{WalRecord, WalRecordError, WalSegment};
 use std::io::Read;
@@ -19,9 +19,8 @@ const MAX_RECOVERY_SCAN_WINDOW: usize = 1_024 * 1_024; // 1 MB
 pub struct WalReader {
     /// Storage codec for decoding.
     ///
-    /// Currently the codec is stored for future use when codec-aware
-    /// decoding is implemented. The identity codec passes through unchanged.
-    #[allow(dead_code)]
+    /// Must match the codec the segments were written with. The identity
+    /// codec passes through unchanged.
     codec: Box<dyn StorageCodec>,
 }
 
@@ -72,12 +71,12 @@ impl WalReader {
         let mut skipped_corrupted = 0usize;
 
         while offset < buffer.len() {
-            // Try to decode through codec first
-            // For identity codec, this is just the raw bytes
+            // Decode through the codec; for the identity codec this
+            // parses the raw record bytes directly
             let remaining = &buffer[offset..];
 
             // Try to parse a record
-            match WalRecord::from_bytes(remaining) {
+            match WalRecord::from_encoded_bytes(remaining, self.codec.as_ref()) {
                 Ok((record, consumed)) => {
                     records.push(record);
                     offset += consumed;
@@ -97,7 +96,12 @@ impl WalReader {
                     let mut found = false;
 
                     for scan_offset in scan_start..scan_end {
-                        if WalRecord::from_bytes(&buffer[scan_offset..]).is_ok() {
+                        if WalRecord::from_encoded_bytes(
+                            &buffer[scan_offset..],
+                            self.codec.as_ref(),
+                        )
+                        .is_ok()
+                        {
                             tracing::warn!(
                                 target: "strata::recovery",
                                 corrupted_offset = offset,
@@ -120,6 +124,12 @@ impl WalReader {
                     stop_reason = ReadStopReason::ChecksumMismatch { offset };
                     break;
                 }
+                Err(WalRecordError::Codec(e)) => {
+                    // The frame is intact but cannot be decoded: the WAL was
+                    // written with a different codec or key. Truncating here
+                    // would destroy valid data, so fail instead.
+                    return Err(WalReaderError::Codec(e));
+                }
                 Err(e) => {
                     // CRC was valid but payload couldn't be parsed.
                     // This indicates codec mismatch or format version incompatibility,
@@ -433,6 +443,10 @@ pub enum WalReaderError {
     /// Record parsing error
     #[error("Record parsing error: {0}")]
     ParseError(String),
+
+    /// Record could not be decoded by the configured codec
+    #[error("Codec error: {0}")]
+    Codec(CodecError),
 }
 
 #[cfg(test)]
@@ -928,4 +942,48 @@ mod tests {
         assert_eq!(meta.max_txn_id, 2);
         assert_eq!(meta.record_count, 2);
     }
+
+    #[test]
+    fn test_encrypted_wal_roundtrip_and_wrong_key() {
+        use crate::codec::AesGcmCodec;
+
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+
+        let mut writer = WalWriter::new(
+            wal_dir.clone(),
+            [1u8; 16],
+            DurabilityMode::Always,
+            WalConfig::for_testing(),
+            Box::new(AesGcmCodec::new(&[1u8; 32])),
+        )
+        .unwrap();
+        for i in 1..=3 {
+            writer
+                .append(&WalRecord::new(
+                    i,
+                    [1u8; 16],
+                    i * 1000,
+                    b"plaintext".to_vec(),
+                ))
+                .unwrap();
+        }
+        writer.flush().unwrap();
+
+        // Payload bytes never hit the disk in the clear
+        let raw = std::fs::read(WalSegment::segment_path(&wal_dir, 1)).unwrap();
+        assert!(!raw.windows(9).any(|w| w == b"plaintext"));
+
+        let reader = WalReader::new(Box::new(AesGcmCodec::new(&[1u8; 32])));
+        let result = reader.read_all(&wal_dir).unwrap();
+        assert_eq!(result.records.len(), 3);
+        assert_eq!(result.records[2].writeset, b"plaintext");
+        assert!(result.truncate_info.is_none());
+
+        let wrong = WalReader::new(Box::new(AesGcmCodec::new(&[2u8; 32])));
+        assert!(matches!(
+            wrong.read_all(&wal_dir),
+            Err(WalReaderError::Codec(CodecError::DecodeError { .. }))
+        ));
+    }
 }
@@ -11,15 +11,18 @@ use crate::format::segment_meta::SegmentMeta;
 use crate::format::{WalRecord, WalSegment, SEGMENT_HEADER_SIZE_V2};
 use crate::wal::config::WalConfig;
 use crate::wal::reader::WalReader;
+use std::fs::File;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
 /// Cumulative WAL operation counters.
 ///
 /// These counters accumulate over the lifetime of the WalWriter
 /// and are never reset. Use them to observe how many WAL operations
-/// a workload triggers.
+/// a workload triggers. The checkpoint and compaction counters are
+/// filled in by the database that owns the writer (zero from the
+/// writer itself).
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct WalCounters {
     /// Total WAL record appends (calls to append() that did work)
@@ -30,6 +33,21 @@ pub struct WalCounters {
     pub bytes_written: u64,
     /// Total nanoseconds spent in sync/fsync calls
     pub sync_nanos: u64,
+    /// Checkpoints written
+    #[serde(default)]
+    pub checkpoints: u64,
+    /// WAL compactions run
+    #[serde(default)]
+    pub compactions: u64,
+    /// WAL segments removed by compaction
+    #[serde(default)]
+    pub wal_segments_removed: u64,
+    /// WAL bytes reclaimed by compaction
+    #[serde(default)]
+    pub wal_bytes_reclaimed: u64,
+    /// WAL segments archived before compaction removed them
+    #[serde(default)]
+    pub wal_segments_archived: u64,
 }
 
 /// WAL writer with configurable durability modes.
@@ -81,6 +99,9 @@ pub struct WalWriter {
     /// Whether there is data written but not yet fsynced
     has_unsynced_data: bool,
 
+    /// Appends known to be on disk (see `append_seq`)
+    synced_appends: u64,
+
     /// In-memory metadata for the current active segment.
     /// `None` in Cache mode (no WAL persistence).
     current_segment_meta: Option<SegmentMeta>,
@@ -122,6 +143,7 @@ impl WalWriter {
                 current_segment_number: 0,
                 current_segment_meta: None,
                 has_unsynced_data: false,
+                synced_appends: 0,
                 total_wal_appends: 0,
                 total_sync_calls: 0,
                 total_bytes_written: 0,
@@ -158,7 +180,7 @@ impl WalWriter {
         // Build initial segment metadata
         let current_segment_meta = if is_reopened {
             // Reopening an existing segment — rebuild metadata from its records
-            Self::rebuild_meta_for_segment(&wal_dir, segment_number)
+            Self::rebuild_meta_for_segment(&wal_dir, segment_number, codec.as_ref())
         } else {
             Some(SegmentMeta::new_empty(segment_number))
         };
@@ -176,6 +198,7 @@ impl WalWriter {
             current_segment_number: segment_number,
             current_segment_meta,
             has_unsynced_data: false,
+            synced_appends: 0,
             total_wal_appends: 0,
             total_sync_calls: 0,
             total_bytes_written: 0,
@@ -187,7 +210,9 @@ impl WalWriter {
     ///
     /// Respects the configured durability mode:
     /// - `Cache`: No-op, returns immediately
-    /// - `Always`: Writes and fsyncs before returning
+    /// - `Always`: Writes and fsyncs before returning, or with
+    ///   `WalConfig::group_commit` writes only and leaves the fsync to
+    ///   `GroupCommit::wait_durable`
     /// - `Standard`: Writes, fsyncs periodically
     pub fn append(&mut self, record: &WalRecord) -> std::io::Result<()> {
         // Cache mode: no persistence
@@ -200,11 +225,8 @@ impl WalWriter {
             .as_mut()
             .expect("Segment should exist for non-Cache mode");
 
-        // Serialize record
-        let record_bytes = record.to_bytes();
-
-        // Encode through codec
-        let encoded = self.codec.encode(&record_bytes);
+        // Serialize record and encode through codec
+        let encoded = record.to_encoded_bytes(self.codec.as_ref());
 
         // Check if we need to rotate before writing
         if segment.size() + encoded.len() as u64 > self.config.segment_size {
@@ -238,6 +260,10 @@ impl WalWriter {
     /// Handle fsync based on durability mode.
     fn maybe_sync(&mut self) -> std::io::Result<()> {
         match self.durability {
+            DurabilityMode::Always if self.config.group_commit => {
+                // Group commit: the committer batches its fsync with
+                // concurrent committers in GroupCommit::wait_durable.
+            }
             DurabilityMode::Always => {
                 // Always sync immediately
                 if let Some(ref mut segment) = self.segment {
@@ -268,6 +294,7 @@ impl WalWriter {
         self.writes_since_sync = 0;
         self.last_sync_time = Instant::now();
         self.has_unsynced_data = false;
+        self.synced_appends = self.total_wal_appends;
     }
 
     /// Rotate to a new segment.
@@ -352,6 +379,42 @@ impl WalWriter {
         Ok(false)
     }
 
+    /// Sequence number of the last appended record.
+    ///
+    /// Counts this writer's appends. Pass it to `GroupCommit::wait_durable`
+    /// to wait until the record and everything appended before it is on
+    /// disk.
+    pub fn append_seq(&self) -> u64 {
+        self.total_wal_appends
+    }
+
+    /// Start a group sync: returns how many appends it covers and a handle
+    /// to the active segment, so the fsync can run without holding the
+    /// writer. Segments rotated out were synced when they were closed.
+    ///
+    /// The handle is `None` when everything appended is already on disk.
+    pub(crate) fn begin_group_sync(&self) -> std::io::Result<(u64, Option<File>)> {
+        let file = match self.segment {
+            Some(ref segment) if self.synced_appends < self.total_wal_appends => {
+                Some(segment.try_clone_file()?)
+            }
+            _ => None,
+        };
+        Ok((self.total_wal_appends, file))
+    }
+
+    /// Record a group sync started by `begin_group_sync` that made the first
+    /// `through` appends durable.
+    pub(crate) fn finish_group_sync(&mut self, through: u64, elapsed: Duration) {
+        self.total_sync_calls += 1;
+        self.total_sync_nanos += elapsed.as_nanos() as u64;
+        self.synced_appends = self.synced_appends.max(through);
+        if self.synced_appends == self.total_wal_appends {
+            self.reset_sync_counters();
+        }
+        debug!(target: "strata::wal", through, segment = self.current_segment_number, "WAL group sync");
+    }
+
     /// Get the current segment number.
     pub fn current_segment(&self) -> u64 {
         self.current_segment_number
@@ -372,6 +435,7 @@ impl WalWriter {
             sync_calls: self.total_sync_calls,
             bytes_written: self.total_bytes_written,
             sync_nanos: self.total_sync_nanos,
+            ..WalCounters::default()
         }
     }
 
@@ -391,8 +455,12 @@ impl WalWriter {
     ///
     /// Returns `Some(meta)` on success, or `Some(empty_meta)` if the segment
     /// cannot be read (best-effort).
-    fn rebuild_meta_for_segment(wal_dir: &Path, segment_number: u64) -> Option<SegmentMeta> {
-        let reader = WalReader::new(Box::new(crate::codec::IdentityCodec));
+    fn rebuild_meta_for_segment(
+        wal_dir: &Path,
+        segment_number: u64,
+        codec: &dyn StorageCodec,
+    ) -> Option<SegmentMeta> {
+        let reader = WalReader::new(codec.clone_box());
         match reader.read_segment(wal_dir, segment_number) {
             Ok((records, _, _, _)) => {
                 let mut meta = SegmentMeta::new_empty(segment_number);
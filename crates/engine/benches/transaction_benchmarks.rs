@@ -5,13 +5,14 @@
 //! - Single-threaded: >5K txns/sec
 //! - Multi-threaded (no conflict): >10K txns/sec
 //! - Multi-threaded (with conflict): >2K txns/sec
+//! - Multi-threaded `Always` durability: scales with threads (group commit)
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use std::sync::Arc;
 use std::thread;
 use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
-use strata_engine::Database;
+use strata_engine::{Database, StrataConfig};
 use tempfile::TempDir;
 
 fn create_ns(branch_id: BranchId) -> Namespace {
@@ -123,6 +124,65 @@ fn bench_multi_threaded_no_conflict(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark: Multi-threaded transactions under `Always` durability
+///
+/// Concurrent committers share fsyncs through group commit, so throughput
+/// should grow with the thread count instead of staying at one fsync per
+/// commit.
+fn bench_multi_threaded_always(c: &mut Criterion) {
+    let mut group = c.benchmark_group("multi_threaded_always");
+    group.sample_size(10);
+
+    for num_threads in [1, 4, 8] {
+        group.throughput(Throughput::Elements(num_threads as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("threads", num_threads),
+            &num_threads,
+            |b, &num_threads| {
+                b.iter_custom(|iters| {
+                    let temp_dir = TempDir::new().unwrap();
+                    let cfg = StrataConfig {
+                        durability: "always".to_string(),
+                        ..StrataConfig::default()
+                    };
+                    let db = Database::open_with_config(temp_dir.path().join("db"), cfg).unwrap();
+                    let branch_id = BranchId::new();
+
+                    let start = std::time::Instant::now();
+
+                    let handles: Vec<_> = (0..num_threads)
+                        .map(|thread_id| {
+                            let db = Arc::clone(&db);
+                            let ns = create_ns(branch_id);
+
+                            thread::spawn(move || {
+                                for i in 0..iters {
+                                    let key =
+                                        Key::new_kv(ns.clone(), format!("t{}_{}", thread_id, i));
+                                    db.transaction(branch_id, |txn| {
+                                        txn.put(key.clone(), Value::Int(i as i64))?;
+                                        Ok(())
+                                    })
+                                    .unwrap();
+                                }
+                            })
+                        })
+                        .collect();
+
+                    for h in handles {
+                        h.join().unwrap();
+                    }
+
+                    start.elapsed()
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
 /// Benchmark: Multi-threaded transactions (with conflicts - same keys)
 fn bench_multi_threaded_with_conflict(c: &mut Criterion) {
     let mut group = c.benchmark_group("multi_threaded_with_conflict");
@@ -277,6 +337,7 @@ criterion_group!(
     benches,
     bench_single_threaded_transactions,
     bench_multi_threaded_no_conflict,
+    bench_multi_threaded_always,
     bench_multi_threaded_with_conflict,
     bench_read_only_transactions,
     bench_direct_operations,
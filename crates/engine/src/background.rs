@@ -181,7 +181,8 @@ impl BackgroundScheduler {
 
     /// Shut down the scheduler: signal workers to exit and join all threads.
     ///
-    /// Workers drain remaining tasks before exiting.
+    /// Workers drain remaining tasks before exiting. When called from a
+    /// worker thread, that worker is not joined.
     pub fn shutdown(&self) {
         self.inner.shutdown.store(true, AtomicOrdering::Release);
 
@@ -195,9 +196,15 @@ impl BackgroundScheduler {
             self.inner.work_ready.notify_all();
         }
 
+        // A task can drop the last reference to the owner of this scheduler
+        // and run its shutdown on a worker; that worker exits by itself once
+        // the task returns, so only the other workers are joined.
+        let current = std::thread::current().id();
         let mut workers = self.workers.lock();
         for handle in workers.drain(..) {
-            let _ = handle.join();
+            if handle.thread().id() != current {
+                let _ = handle.join();
+            }
         }
     }
 
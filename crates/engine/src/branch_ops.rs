@@ -5,23 +5,54 @@
 //!
 //! ## Operations
 //!
-//! - `fork_branch` — Create a copy of a branch with all its data
+//! - `fork_branch` — Create a copy-on-write fork of a branch
+//! - `fork_branch_at` — Fork a branch as it was at a past timestamp
+//! - `revert_branch` — Restore a branch's state as of a past timestamp
 //! - `diff_branches` — Compare two branches and return structured differences
-//! - `merge_branches` — Merge data from one branch into another
+//! - `diff_branches_with_options` — Filtered, paginated or summary-only diff,
+//!   optionally between past points in time
+//! - `merge_branches` — Three-way merge of one branch into another
+//! - `merge_branches_with_strategies` — Three-way merge with per-primitive
+//!   semantics (JSON path merge, event append, counter-aware state cells)
+//! - `resolve_merge` — Complete a `Manual` merge with per-key conflict resolutions
+//! - `list_commits` — List the transactions committed on a branch
+//! - `cherry_pick` — Replay chosen commits from one branch onto another
+//!
+//! ## Lineage
+//!
+//! Forking records the parent branch and the storage version at which the
+//! source was forked (see [`BranchMetadata::fork_version`]). The fork shares
+//! the parent's data up to that version instead of copying it; reads of keys
+//! the fork hasn't written fall back to the parent. Merges walk this
+//! lineage to find a common ancestor and compare both branches against the
+//! ancestor's state at the fork point, read from the MVCC version chains.
+//! A completed merge is recorded on the target (see
+//! [`BranchMetadata::merged_from`]), and later merges between the same two
+//! branches use the merged state as their base instead.
+//!
+//! [`BranchMetadata::fork_version`]: crate::BranchMetadata::fork_version
+//! [`BranchMetadata::merged_from`]: crate::BranchMetadata::merged_from
 
 use crate::database::Database;
 use crate::primitives::branch::resolve_branch_name;
+use crate::primitives::{event, json, state};
+use crate::retention::check_retained;
 use crate::BranchIndex;
-use crate::SpaceIndex;
-use std::collections::{HashMap, HashSet};
+use crate::{Event, JsonStore, SpaceIndex, State};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use strata_concurrency::{IsolationLevel, TransactionPayload};
+use strata_core::contract::Version;
+use strata_core::primitives::json::{apply_patches, diff_patches, JsonPatch};
 use strata_core::types::{BranchId, Key, Namespace, TypeTag};
 use strata_core::value::Value;
 use strata_core::PrimitiveType;
 use strata_core::StrataError;
 use strata_core::StrataResult;
+use strata_durability::format::WalRecord;
+use strata_storage::ShardedStore;
 use tracing::info;
 
 // =============================================================================
@@ -48,10 +79,15 @@ pub struct ForkInfo {
     pub source: String,
     /// Destination branch name
     pub destination: String,
-    /// Number of keys copied
+    /// Number of keys copied into the destination.
+    ///
+    /// Forks are copy-on-write, so this is 0: the destination shares the
+    /// source's data as of `fork_version` and only stores what it writes.
     pub keys_copied: u64,
     /// Number of spaces copied
     pub spaces_copied: u64,
+    /// Storage version of the source at which the fork was taken
+    pub fork_version: u64,
 }
 
 /// A single entry in a branch diff.
@@ -68,10 +104,18 @@ pub struct BranchDiffEntry {
     pub primitive: PrimitiveType,
     /// Space this entry belongs to
     pub space: String,
-    /// Debug-formatted value in branch A (None if not present)
-    pub value_a: Option<String>,
-    /// Debug-formatted value in branch B (None if not present)
-    pub value_b: Option<String>,
+    /// Value in branch A as its primitive reads it (None if not present)
+    ///
+    /// JSON documents are their document value, state cells their current
+    /// value and events an object with `sequence`, `event_type`, `payload`
+    /// and `timestamp`.
+    pub value_a: Option<Value>,
+    /// Value in branch B as its primitive reads it (None if not present)
+    pub value_b: Option<Value>,
+    /// For modified JSON documents, the patches that turn A's document
+    /// into B's (empty otherwise)
+    #[serde(default)]
+    pub patches: Vec<JsonPatch>,
 }
 
 /// Per-space diff between two branches.
@@ -88,6 +132,9 @@ pub struct SpaceDiff {
 }
 
 /// Summary statistics for a branch diff.
+///
+/// Counts the entries of the returned page; a `summary_only` diff counts
+/// every entry.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DiffSummary {
     /// Total entries added (in B but not A)
@@ -113,6 +160,45 @@ pub struct BranchDiffResult {
     pub spaces: Vec<SpaceDiff>,
     /// Aggregate summary
     pub summary: DiffSummary,
+    /// Cursor for the next page, if entries remain after this one
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// Filters and pagination for [`diff_branches_with_options`].
+///
+/// The default compares everything and returns every entry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiffOptions {
+    /// Only compare these primitives (all if empty)
+    pub primitives: Vec<PrimitiveType>,
+    /// Only compare these spaces (all if empty)
+    pub spaces: Vec<String>,
+    /// Only compare keys starting with this prefix
+    pub key_prefix: Option<String>,
+    /// Maximum number of entries to return
+    pub limit: Option<usize>,
+    /// Resume after the last entry of a previous page (its `next_cursor`)
+    pub cursor: Option<String>,
+    /// Only compute the summary counts, without returning entries
+    pub summary_only: bool,
+    /// Compare branch A as of this timestamp (microseconds since epoch)
+    /// instead of its current state
+    pub as_of_a: Option<u64>,
+    /// Compare branch B as of this timestamp (microseconds since epoch)
+    /// instead of its current state
+    pub as_of_b: Option<u64>,
+}
+
+impl DiffOptions {
+    fn includes_primitive(&self, primitive: PrimitiveType) -> bool {
+        self.primitives.is_empty() || self.primitives.contains(&primitive)
+    }
+
+    fn includes_space(&self, space: &str) -> bool {
+        self.spaces.is_empty() || self.spaces.iter().any(|s| s == space)
+    }
 }
 
 /// Strategy for resolving conflicts during merge.
@@ -122,21 +208,123 @@ pub enum MergeStrategy {
     LastWriterWins,
     /// Merge fails if any conflicts exist
     Strict,
+    /// Nothing is applied if any conflicts exist; the conflicts are returned
+    /// in [`MergeInfo`] so they can be resolved with [`resolve_merge`]
+    Manual,
+}
+
+/// How JSON documents changed on both sides of a merge are reconciled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum JsonMergeStrategy {
+    /// Compare whole documents: any change on both sides conflicts
+    #[default]
+    Document,
+    /// Three-way merge at path level: only overlapping path changes conflict
+    Path,
+}
+
+/// How event streams of both branches are reconciled during a merge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EventMergeStrategy {
+    /// Compare events by sequence key like any other entry
+    #[default]
+    ByKey,
+    /// Append source events written after the merge base to the target log,
+    /// assigning new sequences and extending the target's hash chain
+    Append,
+}
+
+/// How state cells changed on both sides of a merge are reconciled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StateMergeStrategy {
+    /// Compare stored cells (value, counter and timestamp)
+    #[default]
+    Value,
+    /// Conflict when both sides advanced the counter version to different
+    /// values; merged cells get a counter past both sides so stale CAS
+    /// attempts on the target fail
+    CounterVersion,
+}
+
+/// Per-primitive merge semantics, applied on top of [`MergeStrategy`].
+///
+/// The defaults compare every entry as an opaque value. The
+/// [`MergeStrategy`] still decides what happens to conflicts the
+/// per-primitive strategies cannot reconcile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PrimitiveMergeStrategies {
+    /// Strategy for JSON documents
+    #[serde(default)]
+    pub json: JsonMergeStrategy,
+    /// Strategy for event streams
+    #[serde(default)]
+    pub event: EventMergeStrategy,
+    /// Strategy for state cells
+    #[serde(default)]
+    pub state: StateMergeStrategy,
 }
 
 /// A conflict detected during merge.
+///
+/// Values are shown as their primitive reads them: JSON documents as their
+/// document value, state cells as their current value and events as an
+/// object with `sequence`, `event_type`, `payload` and `timestamp`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ConflictEntry {
-    /// User key
+    /// User key (UTF-8 or hex-encoded for binary keys)
     pub key: String,
+    /// Raw user key bytes (identifies the key when resolving the conflict)
+    pub raw_key: Vec<u8>,
     /// Primitive type
     pub primitive: PrimitiveType,
     /// Space
     pub space: String,
-    /// Value in the source branch
-    pub source_value: String,
-    /// Value in the target branch
-    pub target_value: String,
+    /// Value in the source branch (None if deleted there)
+    pub source_value: Option<Value>,
+    /// Value in the target branch (None if deleted there)
+    pub target_value: Option<Value>,
+}
+
+/// How a single merge conflict is resolved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConflictResolution {
+    /// Apply the source side, exactly as `LastWriterWins` would
+    TakeSource,
+    /// Keep the target's current value
+    TakeTarget,
+    /// Write a custom value (KV, state and JSON conflicts only)
+    Custom(Value),
+}
+
+/// Resolution for one conflicting key, submitted to [`resolve_merge`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeResolution {
+    /// Space of the conflicting key
+    pub space: String,
+    /// Primitive type of the conflicting key
+    pub primitive: PrimitiveType,
+    /// Raw user key bytes of the conflicting key
+    pub raw_key: Vec<u8>,
+    /// How to resolve it
+    pub resolution: ConflictResolution,
+}
+
+impl MergeResolution {
+    /// Create a resolution for a reported conflict.
+    pub fn new(conflict: &ConflictEntry, resolution: ConflictResolution) -> Self {
+        Self {
+            space: conflict.space.clone(),
+            primitive: conflict.primitive,
+            raw_key: conflict.raw_key.clone(),
+            resolution,
+        }
+    }
+
+    fn matches(&self, conflict: &ConflictEntry) -> bool {
+        self.space == conflict.space
+            && self.primitive == conflict.primitive
+            && self.raw_key == conflict.raw_key
+    }
 }
 
 /// Information returned after merging branches.
@@ -148,10 +336,101 @@ pub struct MergeInfo {
     pub target: String,
     /// Number of keys written to target
     pub keys_applied: u64,
-    /// Conflicts encountered (empty for LWW, populated for Strict failures)
+    /// Number of keys deleted from target (deleted on source since the merge base)
+    pub keys_deleted: u64,
+    /// Number of source events appended to target logs ([`EventMergeStrategy::Append`])
+    #[serde(default)]
+    pub events_appended: u64,
+    /// Conflicts encountered: overwritten by `LastWriterWins`, resolved by
+    /// [`resolve_merge`], or awaiting resolution for `Manual` (nothing applied)
     pub conflicts: Vec<ConflictEntry>,
     /// Number of spaces merged
     pub spaces_merged: u64,
+    /// Common ancestor used as the merge base, if the branches share lineage
+    pub merge_base: Option<MergeBase>,
+}
+
+/// Information returned after reverting a branch to a past timestamp.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevertInfo {
+    /// Branch name
+    pub branch: String,
+    /// Timestamp the branch was reverted to (microseconds since epoch)
+    pub as_of: u64,
+    /// Keys written back with their value as of `as_of`
+    pub keys_restored: u64,
+    /// Keys deleted because they did not exist at `as_of`
+    pub keys_deleted: u64,
+}
+
+/// A transaction committed on a branch, as recorded in the WAL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitInfo {
+    /// Commit version (identifies the commit for [`cherry_pick`])
+    pub version: u64,
+    /// Transaction ID
+    pub txn_id: u64,
+    /// Commit timestamp (microseconds since epoch)
+    pub timestamp: u64,
+    /// Commit message, if the transaction set one
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Commit metadata set by the transaction
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    /// Number of keys written
+    pub keys_written: u64,
+    /// Number of keys deleted
+    pub keys_deleted: u64,
+    /// Touched keys per primitive, in primitive order
+    #[serde(default)]
+    pub touched: Vec<TouchedKeys>,
+}
+
+/// Keys of one primitive touched by a commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TouchedKeys {
+    /// Primitive type
+    pub primitive: PrimitiveType,
+    /// Number of keys written
+    pub keys_written: u64,
+    /// Number of keys deleted
+    pub keys_deleted: u64,
+    /// The first touched user keys (UTF-8 or hex-encoded), at most
+    /// [`COMMIT_KEY_SAMPLE`] of them
+    pub keys: Vec<String>,
+}
+
+/// Maximum number of keys listed per primitive in [`TouchedKeys::keys`].
+pub const COMMIT_KEY_SAMPLE: usize = 10;
+
+/// Information returned after cherry-picking commits onto a branch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CherryPickInfo {
+    /// Source branch name
+    pub source: String,
+    /// Target branch name
+    pub target: String,
+    /// Versions of the picked commits, oldest first
+    pub commits: Vec<u64>,
+    /// Number of keys written to target
+    pub keys_applied: u64,
+    /// Number of keys deleted from target
+    pub keys_deleted: u64,
+    /// Number of events appended to target logs ([`EventMergeStrategy::Append`])
+    pub events_appended: u64,
+    /// Conflicts encountered: overwritten by `LastWriterWins`, or returned
+    /// without applying anything for `Manual`
+    pub conflicts: Vec<ConflictEntry>,
+}
+
+/// Common ancestor of two branches used for a three-way merge.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeBase {
+    /// Ancestor branch name
+    pub branch: String,
+    /// Storage version of the ancestor that both branches descend from
+    pub version: u64,
 }
 
 // =============================================================================
@@ -171,7 +450,7 @@ fn type_tag_to_primitive(tag: TypeTag) -> PrimitiveType {
 }
 
 /// Format a user key as a readable string (UTF-8 or hex for binary).
-fn format_user_key(user_key: &[u8]) -> String {
+pub(crate) fn format_user_key(user_key: &[u8]) -> String {
     match std::str::from_utf8(user_key) {
         Ok(s) => s.to_string(),
         Err(_) => {
@@ -184,9 +463,15 @@ fn format_user_key(user_key: &[u8]) -> String {
     }
 }
 
-/// Format a value for display in diffs.
-fn format_value(value: &Value) -> String {
-    format!("{:?}", value)
+/// Event entries are keyed by their big-endian sequence number; everything
+/// else (including the event log's `__meta__` and index keys) is text.
+pub(crate) fn render_user_key(key: &Key) -> String {
+    if key.type_tag == TypeTag::Event && key.user_key != b"__meta__" {
+        if let Ok(sequence) = <[u8; 8]>::try_from(key.user_key.as_slice()) {
+            return u64::from_be_bytes(sequence).to_string();
+        }
+    }
+    format_user_key(&key.user_key)
 }
 
 /// Verify a branch exists and return its resolved BranchId.
@@ -198,21 +483,415 @@ fn resolve_and_verify(db: &Arc<Database>, name: &str) -> StrataResult<BranchId>
     Ok(resolve_branch_name(name))
 }
 
+/// Walk a branch's fork lineage, returning `(ancestor, visible_version)` pairs.
+///
+/// The first entry is the branch itself at `u64::MAX` (its head). Each
+/// subsequent entry is an ancestor together with the newest ancestor version
+/// whose state is inherited by the starting branch. Lineage stops at a
+/// missing parent, or at a parent created after its child (the name was
+/// deleted and reused, so the recorded fork point no longer applies).
+fn lineage(branch_index: &BranchIndex, name: &str) -> StrataResult<Vec<(String, u64)>> {
+    let mut chain = vec![(name.to_string(), u64::MAX)];
+    let mut visible = u64::MAX;
+    let mut current = match branch_index.get_branch(name)? {
+        Some(meta) => meta.value,
+        None => return Ok(chain),
+    };
+
+    while let (Some(parent), Some(fork_version)) =
+        (current.parent_branch.clone(), current.fork_version)
+    {
+        // Guard against cycles from corrupted metadata
+        if chain.iter().any(|(n, _)| *n == parent) {
+            break;
+        }
+        let parent_meta = match branch_index.get_branch(&parent)? {
+            Some(meta) => meta.value,
+            None => break,
+        };
+        if parent_meta.created_at > current.created_at {
+            break;
+        }
+        visible = visible.min(fork_version);
+        chain.push((parent, visible));
+        current = parent_meta;
+    }
+
+    Ok(chain)
+}
+
+/// Find the nearest common ancestor of two branches.
+///
+/// Returns the ancestor branch and the storage version at which both
+/// branches last shared its state, or `None` if the branches are unrelated.
+/// Once the branches have been merged (in either direction), the base is
+/// the merged branch's state at the latest merge rather than the fork point.
+fn find_merge_base(
+    db: &Arc<Database>,
+    source: &str,
+    target: &str,
+) -> StrataResult<Option<MergeBase>> {
+    let branch_index = BranchIndex::new(db.clone());
+    let source_chain = lineage(&branch_index, source)?;
+    let target_chain = lineage(&branch_index, target)?;
+
+    let mut base = None;
+    for (ancestor, source_visible) in &source_chain {
+        if let Some((_, target_visible)) = target_chain.iter().find(|(n, _)| n == ancestor) {
+            let version = (*source_visible).min(*target_visible);
+            if version == u64::MAX {
+                // Same branch on both sides: nothing to merge against
+                return Ok(None);
+            }
+            base = Some(MergeBase {
+                branch: ancestor.clone(),
+                version,
+            });
+            break;
+        }
+    }
+
+    // After a merge both branches share the merged branch's state as of
+    // that merge, which is newer than any fork point. A record left by a
+    // deleted branch of the same name no longer applies.
+    for (into, from) in [(target, source), (source, target)] {
+        let from_id = match branch_index.get_branch(from)? {
+            Some(meta) => meta.value.branch_id,
+            None => continue,
+        };
+        let merged = branch_index.get_branch(into)?.and_then(|meta| {
+            meta.value
+                .merged_from
+                .get(from)
+                .filter(|record| record.source_id == from_id)
+                .map(|record| record.version)
+        });
+        if let Some(version) = merged {
+            if base.as_ref().map_or(true, |b| version > b.version) {
+                base = Some(MergeBase {
+                    branch: from.to_string(),
+                    version,
+                });
+            }
+        }
+    }
+
+    Ok(base)
+}
+
+/// All user data in a branch keyed by `(space, user_key, type_tag)`.
+type BranchState = HashMap<(String, Vec<u8>, TypeTag), Value>;
+
+/// Collect every live user-data entry of a branch, optionally as of a past version.
+fn collect_branch_state(
+    db: &Arc<Database>,
+    branch_id: BranchId,
+    version: Option<u64>,
+) -> StrataResult<BranchState> {
+    let storage = db.storage();
+    let mut state = BranchState::new();
+    for type_tag in DATA_TYPE_TAGS {
+        let entries = match version {
+            Some(v) => storage.list_by_type_at_version(&branch_id, type_tag, v)?,
+            None => storage.list_by_type(&branch_id, type_tag)?,
+        };
+        for (key, vv) in entries {
+            state.insert(
+                (key.namespace.space.clone(), key.user_key.clone(), type_tag),
+                vv.value,
+            );
+        }
+    }
+    Ok(state)
+}
+
+// =============================================================================
+// Per-primitive Merge
+// =============================================================================
+
+/// Outcome of reconciling a key that changed on both sides of a merge.
+enum Reconciled {
+    /// Both sides already agree; nothing to write
+    Unchanged,
+    /// Both changes combined into a single value
+    Merged(Value),
+    /// The changes conflict; holds what last-writer-wins writes (`None` deletes)
+    Conflict(Option<Value>),
+}
+
+/// Reconcile a key changed on both sides using its primitive's strategy.
+fn reconcile(
+    type_tag: TypeTag,
+    strategies: &PrimitiveMergeStrategies,
+    base: Option<&Value>,
+    source: Option<&Value>,
+    target: Option<&Value>,
+) -> StrataResult<Reconciled> {
+    match type_tag {
+        TypeTag::Json if strategies.json == JsonMergeStrategy::Path => {
+            reconcile_json(base, source, target)
+        }
+        TypeTag::State if strategies.state == StateMergeStrategy::CounterVersion => {
+            reconcile_state(base, source, target)
+        }
+        _ => Ok(Reconciled::Conflict(source.cloned())),
+    }
+}
+
+/// Three-way merge of a JSON document at path level.
+///
+/// Both sides are diffed against the base into path patches. Source
+/// patches are applied on top of the target document; the merge conflicts
+/// only when a source patch overlaps a different target patch, in which
+/// case the source wins at the overlapping paths. Creations and deletions
+/// of the whole document fall back to document-level conflicts.
+fn reconcile_json(
+    base: Option<&Value>,
+    source: Option<&Value>,
+    target: Option<&Value>,
+) -> StrataResult<Reconciled> {
+    let (Some(base), Some(source), Some(target)) = (base, source, target) else {
+        return Ok(Reconciled::Conflict(source.cloned()));
+    };
+    let base_doc = JsonStore::deserialize_doc(base)?;
+    let source_doc = JsonStore::deserialize_doc(source)?;
+    let target_doc = JsonStore::deserialize_doc(target)?;
+
+    let source_patches = diff_patches(&base_doc.value, &source_doc.value);
+    let target_patches = diff_patches(&base_doc.value, &target_doc.value);
+    let conflicting = source_patches
+        .iter()
+        .any(|s| target_patches.iter().any(|t| s != t && s.conflicts_with(t)));
+
+    let mut merged = target_doc.clone();
+    if apply_patches(&mut merged.value, &source_patches).is_err() {
+        return Ok(Reconciled::Conflict(Some(source.clone())));
+    }
+    if !conflicting && merged.value == target_doc.value {
+        return Ok(Reconciled::Unchanged);
+    }
+    merged.version = target_doc.version.max(source_doc.version);
+    merged.touch();
+
+    let value = JsonStore::serialize_doc(&merged)?;
+    Ok(if conflicting {
+        Reconciled::Conflict(Some(value))
+    } else {
+        Reconciled::Merged(value)
+    })
+}
+
+/// Merge a state cell using its counter version.
+///
+/// Cells whose values agree are left alone. Otherwise the merge conflicts
+/// when both sides advanced the counter past the base. Either way the
+/// written cell takes the source value with a counter past both sides, so
+/// a CAS against a version read before the merge fails on the target.
+fn reconcile_state(
+    base: Option<&Value>,
+    source: Option<&Value>,
+    target: Option<&Value>,
+) -> StrataResult<Reconciled> {
+    let (Some(source), Some(target)) = (source, target) else {
+        return Ok(Reconciled::Conflict(source.cloned()));
+    };
+    let decode = |value: &Value| {
+        state::from_stored_value::<State>(value)
+            .map_err(|e| StrataError::serialization(e.to_string()))
+    };
+    let source_cell = decode(source)?;
+    let target_cell = decode(target)?;
+    if source_cell.value == target_cell.value {
+        return Ok(Reconciled::Unchanged);
+    }
+
+    let base_version = base.map(decode).transpose()?.map(|cell| cell.version);
+    let advanced = |version: Version| base_version.map_or(true, |base| version > base);
+    let both_advanced = advanced(source_cell.version) && advanced(target_cell.version);
+
+    let value = state::to_stored_value(&State {
+        value: source_cell.value,
+        version: source_cell.version.max(target_cell.version).increment(),
+        updated_at: State::now(),
+    })?;
+    Ok(if both_advanced {
+        Reconciled::Conflict(Some(value))
+    } else {
+        Reconciled::Merged(value)
+    })
+}
+
+/// Source events written since the merge base, grouped by space.
+///
+/// Events already copied into the target by an earlier merge (same type,
+/// payload and timestamp among the target's own post-base events) are
+/// skipped so repeated merges don't duplicate them. Each list is in
+/// source sequence order.
+fn events_to_append(
+    base_state: &BranchState,
+    source_state: &BranchState,
+    target_state: &BranchState,
+) -> BTreeMap<String, Vec<Event>> {
+    let new_events = |state: &BranchState| {
+        state
+            .iter()
+            .filter(|((_, _, type_tag), _)| *type_tag == TypeTag::Event)
+            .filter(|(entry_key, _)| !base_state.contains_key(*entry_key))
+            .filter_map(|((space, user_key, _), value)| {
+                event::decode_event(user_key, value).map(|event| (space.clone(), event))
+            })
+            .collect::<Vec<_>>()
+    };
+    let identity = |space: &str, event: &Event| {
+        (
+            space.to_string(),
+            event.timestamp,
+            event.event_type.clone(),
+            serde_json::to_string(&event.payload).unwrap_or_default(),
+        )
+    };
+
+    let present: HashSet<_> = new_events(target_state)
+        .iter()
+        .map(|(space, event)| identity(space, event))
+        .collect();
+
+    let mut appends: BTreeMap<String, Vec<Event>> = BTreeMap::new();
+    for (space, event) in new_events(source_state) {
+        if !present.contains(&identity(&space, &event)) {
+            appends.entry(space).or_default().push(event);
+        }
+    }
+    for events in appends.values_mut() {
+        events.sort_by_key(|event| event.sequence);
+    }
+    appends
+}
+
+/// Decode a stored entry into the value its primitive reads.
+///
+/// JSON documents become their document value, state cells their current
+/// value and events an object with their sequence, type, payload and
+/// timestamp. Everything else (KV values, vectors, event log metadata) is
+/// returned as stored.
+fn user_value(type_tag: TypeTag, user_key: &[u8], stored: &Value) -> Value {
+    let decoded = match type_tag {
+        TypeTag::Json => JsonStore::deserialize_doc(stored)
+            .ok()
+            .map(|doc| json::json_to_value(doc.value.as_inner())),
+        TypeTag::State => state::from_stored_value::<State>(stored)
+            .ok()
+            .map(|cell| cell.value),
+        TypeTag::Event => event::decode_event(user_key, stored).map(|event| {
+            Value::Object(HashMap::from([
+                ("sequence".to_string(), Value::Int(event.sequence as i64)),
+                ("event_type".to_string(), Value::String(event.event_type)),
+                ("payload".to_string(), event.payload),
+                ("timestamp".to_string(), Value::Int(event.timestamp as i64)),
+            ]))
+        }),
+        _ => None,
+    };
+    decoded.unwrap_or_else(|| stored.clone())
+}
+
+/// Encode a custom conflict resolution in the primitive's storage format.
+///
+/// State cells and JSON documents get a version past both sides, so the
+/// resolution reads as a new write on the target.
+fn encode_custom_value(
+    type_tag: TypeTag,
+    value: &Value,
+    source: Option<&Value>,
+    target: Option<&Value>,
+) -> StrataResult<Value> {
+    let sides = [target, source];
+    match type_tag {
+        TypeTag::KV => Ok(value.clone()),
+        TypeTag::State => {
+            let version = sides
+                .iter()
+                .flatten()
+                .filter_map(|v| state::from_stored_value::<State>(v).ok())
+                .map(|cell| cell.version)
+                .max()
+                .map_or(Version::counter(1), |v| v.increment());
+            state::to_stored_value(&State {
+                value: value.clone(),
+                version,
+                updated_at: State::now(),
+            })
+        }
+        TypeTag::Json => {
+            let docs: Vec<_> = sides
+                .iter()
+                .flatten()
+                .filter_map(|v| JsonStore::deserialize_doc(v).ok())
+                .collect();
+            let mut doc = docs.first().cloned().ok_or_else(|| {
+                StrataError::invalid_input("conflicting JSON document could not be decoded")
+            })?;
+            doc.value = json::value_to_json(value)?;
+            doc.version = docs.iter().map(|d| d.version).max().unwrap_or(doc.version);
+            doc.touch();
+            JsonStore::serialize_doc(&doc)
+        }
+        other => Err(StrataError::invalid_input(format!(
+            "Custom values are not supported for {} conflicts",
+            type_tag_to_primitive(other)
+        ))),
+    }
+}
+
 // =============================================================================
 // Fork
 // =============================================================================
 
-/// Fork a branch, creating a complete copy of all its data.
+/// Fork a branch as a copy-on-write view of its data.
 ///
-/// Creates a new branch with `destination` name and copies all data
-/// (KV, Event, State, JSON, Vector, VectorConfig) from `source` to it,
-/// preserving space organization.
+/// Creates a new branch with `destination` name that sees all data (KV,
+/// Event, State, JSON, Vector, VectorConfig) of `source` as of a single
+/// storage version, preserving space organization. That version is
+/// recorded in the destination's metadata as its fork point. No data is
+/// copied: the destination shares the source's version chains and only
+/// stores keys it writes itself, so forking costs the same regardless of
+/// branch size.
 ///
 /// # Errors
 ///
 /// - Source branch does not exist
 /// - Destination branch already exists
 pub fn fork_branch(db: &Arc<Database>, source: &str, destination: &str) -> StrataResult<ForkInfo> {
+    fork_branch_inner(db, source, destination, None)
+}
+
+/// Fork a branch as it was at a past timestamp.
+///
+/// Like [`fork_branch`], but the destination sees `source` as of `as_of`
+/// (microseconds since epoch) instead of its head. The fork point is the
+/// newest storage version visible on `source` at that time, so later
+/// merges use the historical point as their merge base.
+///
+/// # Errors
+///
+/// - Source branch does not exist
+/// - Destination branch already exists
+/// - `as_of` is before the source's retention horizon (`HistoryTrimmed`)
+pub fn fork_branch_at(
+    db: &Arc<Database>,
+    source: &str,
+    destination: &str,
+    as_of: u64,
+) -> StrataResult<ForkInfo> {
+    fork_branch_inner(db, source, destination, Some(as_of))
+}
+
+fn fork_branch_inner(
+    db: &Arc<Database>,
+    source: &str,
+    destination: &str,
+    as_of: Option<u64>,
+) -> StrataResult<ForkInfo> {
     let branch_index = BranchIndex::new(db.clone());
     let space_index = SpaceIndex::new(db.clone());
 
@@ -229,11 +908,20 @@ pub fn fork_branch(db: &Arc<Database>, source: &str, destination: &str) -> Strat
         )));
     }
 
-    // 3. Create destination branch
-    branch_index.create_branch(destination)?;
-
-    // 4. Resolve BranchIds
+    // 3. Pin the fork point and create the destination as a copy-on-write
+    //    fork: it shares the source's data as of the fork version, so
+    //    nothing is copied and later source writes stay invisible to it.
     let source_id = resolve_branch_name(source);
+    let fork_version = match as_of {
+        Some(ts) => {
+            check_retained(db, &source_id, ts)?;
+            db.storage().version_at_timestamp(&source_id, ts)?
+        }
+        None => db.storage().version(),
+    };
+    branch_index.create_forked_branch(destination, source, fork_version)?;
+
+    // 4. Resolve destination BranchId
     let dest_id = resolve_branch_name(destination);
 
     // 5. List source spaces and register them in destination
@@ -247,40 +935,6 @@ pub fn fork_branch(db: &Arc<Database>, source: &str, destination: &str) -> Strat
         spaces_copied += 1;
     }
 
-    // 6. Scan all source data and copy to destination
-    let storage = db.storage();
-    let mut keys_copied = 0u64;
-
-    for type_tag in DATA_TYPE_TAGS {
-        let entries = storage.list_by_type(&source_id, type_tag);
-
-        if entries.is_empty() {
-            continue;
-        }
-
-        // Batch write all entries for this type tag
-        let batch: Vec<(Key, Value)> = entries
-            .into_iter()
-            .map(|(key, vv)| {
-                // Rewrite key with destination namespace (preserving space and user_key)
-                let new_ns = Namespace::for_branch_space(dest_id, &key.namespace.space);
-                let new_key = Key::new(new_ns, key.type_tag, key.user_key.clone());
-                (new_key, vv.value)
-            })
-            .collect();
-
-        let batch_len = batch.len() as u64;
-
-        db.transaction(dest_id, |txn| {
-            for (key, value) in &batch {
-                txn.put(key.clone(), value.clone())?;
-            }
-            Ok(())
-        })?;
-
-        keys_copied += batch_len;
-    }
-
     // Reload vector backends for the destination branch so that forked
     // vectors are immediately searchable without requiring a database restart.
     {
@@ -299,16 +953,149 @@ pub fn fork_branch(db: &Arc<Database>, source: &str, destination: &str) -> Strat
         target: "strata::branch_ops",
         source,
         destination,
-        keys_copied,
         spaces_copied,
+        fork_version,
         "Branch forked"
     );
 
     Ok(ForkInfo {
         source: source.to_string(),
         destination: destination.to_string(),
-        keys_copied,
+        keys_copied: 0,
         spaces_copied,
+        fork_version,
+    })
+}
+
+// =============================================================================
+// Revert
+// =============================================================================
+
+/// Revert a branch to its state as of a past timestamp.
+///
+/// Every key whose current value differs from its value at `as_of`
+/// (microseconds since epoch) is written back, and keys created since are
+/// deleted, across all primitives — KV (including graphs, which are stored
+/// as KV), events, state cells, JSON documents and vector collections — in
+/// a single transaction. The revert is recorded as new versions, so the
+/// history it reverts stays readable through time-travel reads.
+///
+/// The current state is read through that transaction at serializable
+/// isolation, so a concurrent write to the branch aborts the revert rather
+/// than being overwritten or missed.
+///
+/// State cells and JSON documents get a version past their current one, so
+/// version-based compare-and-swap keeps working. Vector backends are
+/// reloaded afterwards. Vector records do not store their embeddings, so
+/// these are read from the in-memory backend, which also keeps the
+/// embeddings of vectors deleted since it was built; vectors whose
+/// embeddings it no longer holds (deleted before the database was last
+/// opened) are skipped.
+///
+/// # Errors
+///
+/// - Branch does not exist
+/// - `as_of` is before the branch's retention horizon (`HistoryTrimmed`)
+/// - A concurrent write to the branch conflicted with the revert
+pub fn revert_branch(db: &Arc<Database>, branch: &str, as_of: u64) -> StrataResult<RevertInfo> {
+    let branch_id = resolve_and_verify(db, branch)?;
+    check_retained(db, &branch_id, as_of)?;
+    let storage = db.storage();
+
+    // Past values are settled history, read straight from the version chains
+    let mut past: HashMap<Key, Value> = HashMap::new();
+    for type_tag in DATA_TYPE_TAGS {
+        let entries = storage.list_by_type_at_timestamp(&branch_id, type_tag, as_of)?;
+        past.extend(entries.into_iter().map(|(key, vv)| (key, vv.value)));
+    }
+
+    // Spaces deleted since `as_of` come back with their data
+    let past_spaces: BTreeSet<String> =
+        past.keys().map(|key| key.namespace.space.clone()).collect();
+    let space_index = SpaceIndex::new(db.clone());
+    for space in &past_spaces {
+        if space != "default" {
+            space_index.register(branch_id, space)?;
+        }
+    }
+
+    let (keys_restored, keys_deleted) =
+        db.transaction_with_isolation(branch_id, IsolationLevel::Serializable, |txn| {
+            let mut spaces = past_spaces.clone();
+            spaces.insert("default".to_string());
+            for (key, _) in txn.scan_prefix(&Key::new_space_prefix(branch_id))? {
+                if let Some(space) = key.user_key_string() {
+                    spaces.insert(space);
+                }
+            }
+
+            let mut current: HashMap<Key, Value> = HashMap::new();
+            for space in &spaces {
+                let ns = Namespace::for_branch_space(branch_id, space);
+                for type_tag in DATA_TYPE_TAGS {
+                    current.extend(txn.scan_prefix(&Key::new(ns.clone(), type_tag, Vec::new()))?);
+                }
+            }
+
+            let mut puts: Vec<(Key, Value)> = Vec::new();
+            for (key, value) in &past {
+                let current_value = current.get(key);
+                if current_value != Some(value) {
+                    let type_tag = key.type_tag;
+                    let restored = match type_tag {
+                        TypeTag::State | TypeTag::Json => encode_custom_value(
+                            type_tag,
+                            &user_value(type_tag, &key.user_key, value),
+                            Some(value),
+                            current_value,
+                        )?,
+                        _ => value.clone(),
+                    };
+                    puts.push((key.clone(), restored));
+                }
+            }
+            let deletes: Vec<Key> = current
+                .into_keys()
+                .filter(|key| !past.contains_key(key))
+                .collect();
+
+            for (key, value) in &puts {
+                txn.put(key.clone(), value.clone())?;
+            }
+            for key in &deletes {
+                txn.delete(key.clone())?;
+            }
+            Ok((puts.len() as u64, deletes.len() as u64))
+        })?;
+
+    // Reload vector backends so restored vectors are searchable and
+    // removed ones drop out of search immediately.
+    {
+        use crate::primitives::vector::store::VectorStore;
+        let vector_store = VectorStore::new(db.clone());
+        if let Err(e) = vector_store.post_merge_reload_vectors(branch_id) {
+            tracing::warn!(
+                target: "strata::branch_ops",
+                error = %e,
+                "Failed to reload vector backends after revert"
+            );
+        }
+    }
+
+    info!(
+        target: "strata::branch_ops",
+        branch,
+        as_of,
+        keys_restored,
+        keys_deleted,
+        "Branch reverted"
+    );
+
+    Ok(RevertInfo {
+        branch: branch.to_string(),
+        as_of,
+        keys_restored,
+        keys_deleted,
     })
 }
 
@@ -328,112 +1115,189 @@ pub fn diff_branches(
     db: &Arc<Database>,
     branch_a: &str,
     branch_b: &str,
+) -> StrataResult<BranchDiffResult> {
+    diff_branches_with_options(db, branch_a, branch_b, &DiffOptions::default())
+}
+
+/// Compare two branches, filtered and paginated by `options`.
+///
+/// Entries are ordered by (space, primitive, key). Each side is read with
+/// prefix scans that start at the cursor, so a page only reads the keys up
+/// to its last entry. The summary counts the entries of the page; with
+/// `summary_only` it counts every entry that passes the filters and no
+/// entries are materialized at all. When more entries remain after
+/// `limit`, `next_cursor` resumes the diff on the next call.
+///
+/// `as_of_a` and `as_of_b` compare either side at a past timestamp, read
+/// from the MVCC version chains. Passing the same branch twice with two
+/// timestamps shows what changed on it between those points. Space lists
+/// (`spaces_only_in_a`/`spaces_only_in_b`) reflect current registrations.
+///
+/// # Errors
+///
+/// - Either branch does not exist
+/// - `options.cursor` is not a cursor returned by a previous diff
+/// - A timestamp is before its branch's retention horizon (`HistoryTrimmed`)
+pub fn diff_branches_with_options(
+    db: &Arc<Database>,
+    branch_a: &str,
+    branch_b: &str,
+    options: &DiffOptions,
 ) -> StrataResult<BranchDiffResult> {
     let space_index = SpaceIndex::new(db.clone());
 
     // 1. Verify both branches exist and resolve IDs
     let id_a = resolve_and_verify(db, branch_a)?;
     let id_b = resolve_and_verify(db, branch_b)?;
+    for (id, as_of) in [(&id_a, options.as_of_a), (&id_b, options.as_of_b)] {
+        if let Some(ts) = as_of {
+            check_retained(db, id, ts)?;
+        }
+    }
+    let cursor = options
+        .cursor
+        .as_deref()
+        .map(decode_diff_cursor)
+        .transpose()?;
 
     // 2. List spaces in both branches
-    let spaces_a: HashSet<String> = space_index.list(id_a)?.into_iter().collect();
-    let spaces_b: HashSet<String> = space_index.list(id_b)?.into_iter().collect();
+    let spaces_a: BTreeSet<String> = space_index
+        .list(id_a)?
+        .into_iter()
+        .filter(|s| options.includes_space(s))
+        .collect();
+    let spaces_b: BTreeSet<String> = space_index
+        .list(id_b)?
+        .into_iter()
+        .filter(|s| options.includes_space(s))
+        .collect();
 
     let spaces_only_in_a: Vec<String> = spaces_a.difference(&spaces_b).cloned().collect();
     let spaces_only_in_b: Vec<String> = spaces_b.difference(&spaces_a).cloned().collect();
-    let all_spaces: HashSet<String> = spaces_a.union(&spaces_b).cloned().collect();
 
+    // 3. Walk each (space, primitive) in diff order, merging the two
+    //    branches' key-ordered scans and stopping once the page is full
     let storage = db.storage();
-    let mut space_diffs = Vec::new();
+    let mut type_tags: Vec<TypeTag> = DATA_TYPE_TAGS
+        .into_iter()
+        .filter(|tag| options.includes_primitive(type_tag_to_primitive(*tag)))
+        .collect();
+    type_tags.sort();
+    let key_prefix = options
+        .key_prefix
+        .as_deref()
+        .unwrap_or_default()
+        .as_bytes()
+        .to_vec();
+    let page_limit = options.limit.filter(|_| !options.summary_only);
+
     let mut total_added = 0usize;
     let mut total_removed = 0usize;
     let mut total_modified = 0usize;
+    let mut space_diffs: Vec<SpaceDiff> = Vec::new();
+    let mut emitted = 0usize;
+    let mut last_emitted: Option<DiffKey> = None;
+    let mut next_cursor = None;
+
+    'scan: for space in spaces_a.union(&spaces_b) {
+        for type_tag in &type_tags {
+            let resume_after = match &cursor {
+                Some((c_space, c_tag, c_key)) => match (c_space, c_tag).cmp(&(space, type_tag)) {
+                    std::cmp::Ordering::Greater => continue,
+                    std::cmp::Ordering::Equal => Some(c_key),
+                    std::cmp::Ordering::Less => None,
+                },
+                None => None,
+            };
+            let scan = |id: &BranchId, as_of: Option<u64>| {
+                let ns = Namespace::for_branch_space(*id, space);
+                DiffScan {
+                    storage,
+                    prefix: Key::new(ns.clone(), *type_tag, key_prefix.clone()),
+                    after: resume_after.map(|k| Key::new(ns, *type_tag, k.clone())),
+                    as_of,
+                    batch: VecDeque::new(),
+                    exhausted: false,
+                }
+            };
+            let mut scan_a = scan(&id_a, options.as_of_a);
+            let mut scan_b = scan(&id_b, options.as_of_b);
+
+            loop {
+                let order = match (scan_a.peek()?, scan_b.peek()?) {
+                    (None, None) => break,
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (Some((a, _)), Some((b, _))) => a.user_key.cmp(&b.user_key),
+                };
+                let (entry_a, entry_b) = match order {
+                    std::cmp::Ordering::Less => (scan_a.next()?, None),
+                    std::cmp::Ordering::Greater => (None, scan_b.next()?),
+                    std::cmp::Ordering::Equal => (scan_a.next()?, scan_b.next()?),
+                };
+                let user_key = match (&entry_a, &entry_b) {
+                    (Some((key, _)), _) | (None, Some((key, _))) => key.user_key.clone(),
+                    (None, None) => break,
+                };
+                let val_a = entry_a.map(|(_, value)| value);
+                let val_b = entry_b.map(|(_, value)| value);
+                if let (Some(a), Some(b)) = (&val_a, &val_b) {
+                    if same_user_value(*type_tag, &user_key, a, b) {
+                        continue;
+                    }
+                }
 
-    // 3. Scan all data once per type tag, grouped by space
-    let mut maps_a: HashMap<String, HashMap<(Vec<u8>, TypeTag), Value>> = HashMap::new();
-    let mut maps_b: HashMap<String, HashMap<(Vec<u8>, TypeTag), Value>> = HashMap::new();
-
-    for type_tag in DATA_TYPE_TAGS {
-        for (key, vv) in storage.list_by_type(&id_a, type_tag) {
-            maps_a
-                .entry(key.namespace.space.clone())
-                .or_default()
-                .insert((key.user_key.clone(), type_tag), vv.value);
-        }
-        for (key, vv) in storage.list_by_type(&id_b, type_tag) {
-            maps_b
-                .entry(key.namespace.space.clone())
-                .or_default()
-                .insert((key.user_key.clone(), type_tag), vv.value);
-        }
-    }
-
-    // 4. For each space, compare data
-    for space in &all_spaces {
-        let map_a = maps_a.remove(space).unwrap_or_default();
-        let map_b = maps_b.remove(space).unwrap_or_default();
-
-        let mut added = Vec::new();
-        let mut removed = Vec::new();
-        let mut modified = Vec::new();
+                // One entry past a full page only tells us there is more
+                if page_limit.is_some_and(|limit| emitted >= limit) {
+                    next_cursor = last_emitted.as_ref().map(encode_diff_cursor);
+                    break 'scan;
+                }
+                match (&val_a, &val_b) {
+                    (None, _) => total_added += 1,
+                    (_, None) => total_removed += 1,
+                    _ => total_modified += 1,
+                }
+                if options.summary_only {
+                    continue;
+                }
 
-        // Keys in A (check for removed and modified)
-        for ((user_key, tag), val_a) in &map_a {
-            let key_str = format_user_key(user_key);
-            let primitive = type_tag_to_primitive(*tag);
+                let patches = match (*type_tag, &val_a, &val_b) {
+                    (TypeTag::Json, Some(a), Some(b)) => {
+                        match (JsonStore::deserialize_doc(a), JsonStore::deserialize_doc(b)) {
+                            (Ok(doc_a), Ok(doc_b)) => diff_patches(&doc_a.value, &doc_b.value),
+                            _ => Vec::new(),
+                        }
+                    }
+                    _ => Vec::new(),
+                };
+                let entry = BranchDiffEntry {
+                    key: format_user_key(&user_key),
+                    raw_key: user_key.clone(),
+                    primitive: type_tag_to_primitive(*type_tag),
+                    space: space.clone(),
+                    value_a: val_a.map(|v| user_value(*type_tag, &user_key, &v)),
+                    value_b: val_b.map(|v| user_value(*type_tag, &user_key, &v)),
+                    patches,
+                };
 
-            match map_b.get(&(user_key.clone(), *tag)) {
-                None => {
-                    removed.push(BranchDiffEntry {
-                        key: key_str,
-                        raw_key: user_key.clone(),
-                        primitive,
+                if space_diffs.last().map_or(true, |sd| &sd.space != space) {
+                    space_diffs.push(SpaceDiff {
                         space: space.clone(),
-                        value_a: Some(format_value(val_a)),
-                        value_b: None,
+                        added: Vec::new(),
+                        removed: Vec::new(),
+                        modified: Vec::new(),
                     });
                 }
-                Some(val_b) => {
-                    if val_a != val_b {
-                        modified.push(BranchDiffEntry {
-                            key: key_str,
-                            raw_key: user_key.clone(),
-                            primitive,
-                            space: space.clone(),
-                            value_a: Some(format_value(val_a)),
-                            value_b: Some(format_value(val_b)),
-                        });
-                    }
+                let space_diff = space_diffs.last_mut().expect("space diff just pushed");
+                match (&entry.value_a, &entry.value_b) {
+                    (None, _) => space_diff.added.push(entry),
+                    (_, None) => space_diff.removed.push(entry),
+                    _ => space_diff.modified.push(entry),
                 }
+                emitted += 1;
+                last_emitted = Some((space.clone(), *type_tag, user_key));
             }
         }
-
-        // Keys only in B (added)
-        for ((user_key, tag), val_b) in &map_b {
-            if !map_a.contains_key(&(user_key.clone(), *tag)) {
-                added.push(BranchDiffEntry {
-                    key: format_user_key(user_key),
-                    raw_key: user_key.clone(),
-                    primitive: type_tag_to_primitive(*tag),
-                    space: space.clone(),
-                    value_a: None,
-                    value_b: Some(format_value(val_b)),
-                });
-            }
-        }
-
-        total_added += added.len();
-        total_removed += removed.len();
-        total_modified += modified.len();
-
-        if !added.is_empty() || !removed.is_empty() || !modified.is_empty() {
-            space_diffs.push(SpaceDiff {
-                space: space.clone(),
-                added,
-                removed,
-                modified,
-            });
-        }
     }
 
     Ok(BranchDiffResult {
@@ -447,157 +1311,518 @@ pub fn diff_branches(
             spaces_only_in_a,
             spaces_only_in_b,
         },
+        next_cursor,
     })
 }
 
+/// Position of an entry in diff order: (space, type tag, user key).
+type DiffKey = (String, TypeTag, Vec<u8>);
+
+/// Entries read from storage at a time while diffing
+const DIFF_SCAN_BATCH: usize = 256;
+
+/// One branch's entries under a prefix, read from storage in key order
+/// one batch at a time.
+struct DiffScan<'a> {
+    storage: &'a ShardedStore,
+    prefix: Key,
+    after: Option<Key>,
+    as_of: Option<u64>,
+    batch: VecDeque<(Key, Value)>,
+    exhausted: bool,
+}
+
+impl DiffScan<'_> {
+    fn peek(&mut self) -> StrataResult<Option<&(Key, Value)>> {
+        if self.batch.is_empty() && !self.exhausted {
+            let entries = self.storage.scan_prefix_after(
+                &self.prefix,
+                self.after.as_ref(),
+                self.as_of,
+                DIFF_SCAN_BATCH,
+            )?;
+            self.exhausted = entries.len() < DIFF_SCAN_BATCH;
+            if let Some((key, _)) = entries.last() {
+                self.after = Some(key.clone());
+            }
+            self.batch
+                .extend(entries.into_iter().map(|(key, vv)| (key, vv.value)));
+        }
+        Ok(self.batch.front())
+    }
+
+    fn next(&mut self) -> StrataResult<Option<(Key, Value)>> {
+        self.peek()?;
+        Ok(self.batch.pop_front())
+    }
+}
+
+/// Whether two stored values hold the same user-visible value.
+///
+/// Equal stored values need no decoding. State cells, JSON documents and
+/// events wrap the user value with bookkeeping (versions, timestamps), so
+/// differing stored values of those are decoded before comparing.
+fn same_user_value(type_tag: TypeTag, user_key: &[u8], a: &Value, b: &Value) -> bool {
+    a == b
+        || (matches!(type_tag, TypeTag::State | TypeTag::Json | TypeTag::Event)
+            && user_value(type_tag, user_key, a) == user_value(type_tag, user_key, b))
+}
+
+/// Encode a diff position as an opaque cursor: hex of
+/// `space \0 type_tag user_key`.
+fn encode_diff_cursor((space, type_tag, user_key): &DiffKey) -> String {
+    space
+        .as_bytes()
+        .iter()
+        .chain([0u8, type_tag.as_byte()].iter())
+        .chain(user_key.iter())
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Decode a cursor produced by [`encode_diff_cursor`].
+fn decode_diff_cursor(cursor: &str) -> StrataResult<DiffKey> {
+    let invalid = || StrataError::invalid_input(format!("Invalid diff cursor '{}'", cursor));
+    if cursor.len() % 2 != 0 || !cursor.is_ascii() {
+        return Err(invalid());
+    }
+    let bytes = (0..cursor.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&cursor[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| invalid())?;
+    let split = bytes.iter().position(|b| *b == 0).ok_or_else(invalid)?;
+    let space = String::from_utf8(bytes[..split].to_vec()).map_err(|_| invalid())?;
+    let type_tag = bytes
+        .get(split + 1)
+        .and_then(|b| TypeTag::from_byte(*b))
+        .ok_or_else(invalid)?;
+    Ok((space, type_tag, bytes[split + 2..].to_vec()))
+}
+
 // =============================================================================
 // Merge
 // =============================================================================
 
 /// Merge data from source branch into target branch.
 ///
-/// Uses `diff_branches(target, source)` to identify changes, then applies
-/// them to the target. Target is branch A (base), source is branch B (incoming).
+/// Performs a three-way merge. The merge base is the nearest common ancestor
+/// of both branches in their fork lineage, read at the fork version both
+/// branches inherited from it. Each key is classified by comparing source
+/// and target against the base:
 ///
-/// - **Added entries** (in source but not target): written to target
-/// - **Modified entries** (in both, different values):
-///   - `LastWriterWins`: source value overwrites target (appends new version)
+/// - **Unchanged in source**: target is left as is
+/// - **Changed only in source**: source value is written to target, or the
+///   key is deleted from target if source deleted it
+/// - **Changed in both, same result**: nothing to do
+/// - **Changed in both, differently**: a conflict
+///   - `LastWriterWins`: source wins (write or delete)
 ///   - `Strict`: merge fails with conflict list (no writes)
-/// - **Removed entries** (in target but not source): left unchanged
 ///
-/// Version history in the target is preserved — merged values are appended
-/// as new versions via `db.transaction()`.
+/// Unrelated branches have no merge base; every key is then compared
+/// against an empty base, so source additions are applied, target-only keys
+/// are kept and keys present on both sides with different values conflict.
+///
+/// All changes are applied to the target in a single transaction, appending
+/// new versions (and tombstones) so target history is preserved.
+///
+/// Every entry is compared as an opaque value; see
+/// [`merge_branches_with_strategies`] for primitive-aware merging.
 ///
 /// # Errors
 ///
 /// - Either branch does not exist
 /// - `Strict` strategy with conflicts
+/// - The target changed while the merge was being applied (`Conflict`)
 pub fn merge_branches(
     db: &Arc<Database>,
     source: &str,
     target: &str,
     strategy: MergeStrategy,
 ) -> StrataResult<MergeInfo> {
-    let space_index = SpaceIndex::new(db.clone());
+    merge_branches_with_strategies(
+        db,
+        source,
+        target,
+        strategy,
+        PrimitiveMergeStrategies::default(),
+    )
+}
+
+/// Merge data from source branch into target branch with per-primitive semantics.
+///
+/// Works like [`merge_branches`], except that keys changed on both sides
+/// are first reconciled by their primitive's strategy:
+///
+/// - [`JsonMergeStrategy::Path`]: documents are merged path by path and
+///   only overlapping path changes conflict
+/// - [`StateMergeStrategy::CounterVersion`]: cells conflict when both sides
+///   advanced the counter version, and merged cells get a counter past both
+/// - [`EventMergeStrategy::Append`]: event logs never conflict; source
+///   events written since the merge base are appended to the target log
+///
+/// Conflicts the strategies cannot reconcile are handled by `strategy`.
+///
+/// # Errors
+///
+/// - Either branch does not exist
+/// - `Strict` strategy with conflicts
+/// - The target changed while the merge was being applied (`Conflict`)
+pub fn merge_branches_with_strategies(
+    db: &Arc<Database>,
+    source: &str,
+    target: &str,
+    strategy: MergeStrategy,
+    strategies: PrimitiveMergeStrategies,
+) -> StrataResult<MergeInfo> {
+    let mut plan = plan_merge(db, source, target, strategies)?;
+
+    if !plan.conflicts.is_empty() {
+        match strategy {
+            MergeStrategy::Strict => {
+                return Err(StrataError::invalid_input(format!(
+                    "Merge conflict: {} keys differ between '{}' and '{}'. Use LastWriterWins strategy or resolve conflicts with the Manual strategy.",
+                    plan.conflicts.len(),
+                    source,
+                    target
+                )));
+            }
+            MergeStrategy::Manual => {
+                info!(
+                    target: "strata::branch_ops",
+                    source,
+                    target,
+                    conflicts = plan.conflicts.len(),
+                    "Merge awaiting conflict resolution"
+                );
+                return Ok(MergeInfo {
+                    source: source.to_string(),
+                    target: target.to_string(),
+                    keys_applied: 0,
+                    keys_deleted: 0,
+                    events_appended: 0,
+                    conflicts: plan.conflicts.into_iter().map(|c| c.entry).collect(),
+                    spaces_merged: 0,
+                    merge_base: plan.merge_base,
+                });
+            }
+            MergeStrategy::LastWriterWins => {}
+        }
+    }
 
-    // 1. Diff: target is A (base), source is B (incoming)
-    let diff = diff_branches(db, target, source)?;
+    // Source wins every remaining conflict
+    let mut conflicts = Vec::with_capacity(plan.conflicts.len());
+    for conflict in std::mem::take(&mut plan.conflicts) {
+        plan.write(conflict.key, conflict.resolved, conflict.target.as_ref());
+        conflicts.push(conflict.entry);
+    }
 
-    // 2. Check for conflicts in Strict mode
-    if strategy == MergeStrategy::Strict && diff.summary.total_modified > 0 {
-        let conflicts: Vec<ConflictEntry> = diff
-            .spaces
-            .iter()
-            .flat_map(|sd| {
-                sd.modified.iter().map(|entry| ConflictEntry {
-                    key: entry.key.clone(),
-                    primitive: entry.primitive,
-                    space: entry.space.clone(),
-                    source_value: entry.value_b.clone().unwrap_or_default(),
-                    target_value: entry.value_a.clone().unwrap_or_default(),
-                })
-            })
-            .collect();
+    apply_merge(
+        db,
+        source,
+        target,
+        plan,
+        conflicts,
+        &format!("{:?}", strategy),
+    )
+}
+
+/// Complete a merge by applying per-key resolutions for its conflicts.
+///
+/// Recomputes the merge of `source` into `target` (with the same
+/// per-primitive `strategies` used to list the conflicts) and applies the
+/// non-conflicting changes together with the resolutions in a single
+/// transaction. Every current conflict must have a resolution, and every
+/// resolution must name a current conflict, so changes made to either
+/// branch after the conflicts were listed are never applied blindly.
+///
+/// # Errors
+///
+/// - Either branch does not exist
+/// - A conflict has no resolution, or a resolution matches no conflict
+/// - A custom value cannot be stored as the conflicting primitive
+/// - The target changed while the merge was being applied (`Conflict`)
+pub fn resolve_merge(
+    db: &Arc<Database>,
+    source: &str,
+    target: &str,
+    strategies: PrimitiveMergeStrategies,
+    resolutions: &[MergeResolution],
+) -> StrataResult<MergeInfo> {
+    let mut plan = plan_merge(db, source, target, strategies)?;
+    let planned = std::mem::take(&mut plan.conflicts);
+
+    if let Some(stale) = resolutions
+        .iter()
+        .find(|r| !planned.iter().any(|c| r.matches(&c.entry)))
+    {
+        return Err(StrataError::invalid_input(format!(
+            "No merge conflict on {} key '{}' in space '{}' between '{}' and '{}'",
+            stale.primitive,
+            format_user_key(&stale.raw_key),
+            stale.space,
+            source,
+            target
+        )));
+    }
 
+    let unresolved = planned
+        .iter()
+        .filter(|c| !resolutions.iter().any(|r| r.matches(&c.entry)))
+        .count();
+    if unresolved > 0 {
         return Err(StrataError::invalid_input(format!(
-            "Merge conflict: {} keys differ between '{}' and '{}'. Use LastWriterWins strategy or resolve conflicts manually.",
-            conflicts.len(),
+            "Merge conflict: {} of {} conflicts between '{}' and '{}' have no resolution",
+            unresolved,
+            planned.len(),
             source,
             target
         )));
     }
 
-    // Collect conflicts for reporting (LWW resolves them, Strict already returned error)
-    let conflicts: Vec<ConflictEntry> = if strategy == MergeStrategy::LastWriterWins {
-        diff.spaces
+    let mut conflicts = Vec::with_capacity(planned.len());
+    for conflict in planned {
+        let resolution = resolutions
             .iter()
-            .flat_map(|sd| {
-                sd.modified.iter().map(|entry| ConflictEntry {
-                    key: entry.key.clone(),
-                    primitive: entry.primitive,
-                    space: entry.space.clone(),
-                    source_value: entry.value_b.clone().unwrap_or_default(),
-                    target_value: entry.value_a.clone().unwrap_or_default(),
-                })
-            })
-            .collect()
-    } else {
-        vec![]
-    };
+            .find(|r| r.matches(&conflict.entry))
+            .map(|r| &r.resolution);
+        match resolution {
+            Some(ConflictResolution::TakeSource) => {
+                plan.write(conflict.key, conflict.resolved, conflict.target.as_ref())
+            }
+            Some(ConflictResolution::Custom(value)) => {
+                let encoded = encode_custom_value(
+                    conflict.key.type_tag,
+                    value,
+                    conflict.source.as_ref(),
+                    conflict.target.as_ref(),
+                )?;
+                plan.write(conflict.key, Some(encoded), conflict.target.as_ref());
+            }
+            Some(ConflictResolution::TakeTarget) | None => {}
+        }
+        conflicts.push(conflict.entry);
+    }
 
-    // 3. Resolve IDs
-    let source_id = resolve_branch_name(source);
-    let target_id = resolve_branch_name(target);
-    let storage = db.storage();
+    apply_merge(db, source, target, plan, conflicts, "Resolved")
+}
 
-    let mut keys_applied = 0u64;
-    let mut spaces_merged = 0u64;
+/// Changes computed by a three-way merge, before any are applied.
+struct MergePlan {
+    source_id: BranchId,
+    target_id: BranchId,
+    merge_base: Option<MergeBase>,
+    /// Storage version the source state was read at; `None` for
+    /// cherry-picks, which don't move the merge base
+    source_version: Option<u64>,
+    puts: Vec<(Key, Value)>,
+    deletes: Vec<Key>,
+    appends: BTreeMap<String, Vec<Event>>,
+    touched_spaces: BTreeSet<String>,
+    conflicts: Vec<PlannedConflict>,
+    /// Target's stored value for each written key when the plan was made
+    expected: HashMap<Key, Option<Value>>,
+}
 
-    // 4. Apply changes
-    for space_diff in &diff.spaces {
-        let space = &space_diff.space;
+/// A conflicting key with both sides' stored values.
+struct PlannedConflict {
+    /// Conflict as reported to the caller
+    entry: ConflictEntry,
+    /// Key in the target branch
+    key: Key,
+    /// Stored value in the source branch
+    source: Option<Value>,
+    /// Stored value in the target branch
+    target: Option<Value>,
+    /// What `LastWriterWins` writes (`None` deletes)
+    resolved: Option<Value>,
+}
 
-        // Ensure target has this space
-        if space != "default" {
-            space_index.register(target_id, space)?;
+impl MergePlan {
+    /// Record a write (or a delete, for `None`) to the target, planned
+    /// against the target holding `current`.
+    fn write(&mut self, key: Key, value: Option<Value>, current: Option<&Value>) {
+        self.touched_spaces.insert(key.namespace.space.clone());
+        self.expected.insert(key.clone(), current.cloned());
+        match value {
+            Some(value) => self.puts.push((key, value)),
+            None => self.deletes.push(key),
         }
-        spaces_merged += 1;
+    }
+}
 
-        // Collect entries to write from source: added + modified (for LWW)
-        let entries_to_apply: Vec<&BranchDiffEntry> = space_diff
-            .added
-            .iter()
-            .chain(if strategy == MergeStrategy::LastWriterWins {
-                space_diff.modified.iter()
-            } else {
-                [].iter()
-            })
-            .collect();
+/// Classify every key touched by either branch since their merge base.
+///
+/// Keys changed only on the source become writes; keys changed on both
+/// sides are reconciled by their primitive's strategy and otherwise
+/// recorded as conflicts. Nothing is written.
+fn plan_merge(
+    db: &Arc<Database>,
+    source: &str,
+    target: &str,
+    strategies: PrimitiveMergeStrategies,
+) -> StrataResult<MergePlan> {
+    // 1. Verify both branches exist and resolve IDs
+    let source_id = resolve_and_verify(db, source)?;
+    let target_id = resolve_and_verify(db, target)?;
+
+    // 2. Locate the merge base and load all three states
+    let merge_base = find_merge_base(db, source, target)?;
+    let base_state = match &merge_base {
+        Some(base) => {
+            collect_branch_state(db, resolve_branch_name(&base.branch), Some(base.version))?
+        }
+        None => BranchState::new(),
+    };
+    let source_version = db.storage().version();
+    let source_state = collect_branch_state(db, source_id, None)?;
+    let target_state = collect_branch_state(db, target_id, None)?;
+
+    let mut plan = MergePlan {
+        source_id,
+        target_id,
+        merge_base,
+        source_version: Some(source_version),
+        puts: Vec::new(),
+        deletes: Vec::new(),
+        appends: BTreeMap::new(),
+        touched_spaces: BTreeSet::new(),
+        conflicts: Vec::new(),
+        expected: HashMap::new(),
+    };
 
-        if entries_to_apply.is_empty() {
+    // 3. Classify every key touched by either side
+    let mut candidates: Vec<&(String, Vec<u8>, TypeTag)> = source_state
+        .keys()
+        .chain(target_state.keys())
+        .chain(base_state.keys())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    candidates.sort();
+
+    for entry_key in candidates {
+        let (space, user_key, type_tag) = entry_key;
+        if *type_tag == TypeTag::Event && strategies.event == EventMergeStrategy::Append {
             continue;
         }
+        let base_val = base_state.get(entry_key);
+        let source_val = source_state.get(entry_key);
+        let target_val = target_state.get(entry_key);
 
-        // Re-scan source data for this space to get actual values
-        // (diff only stores string representations)
-        let mut source_values: HashMap<(Vec<u8>, TypeTag), Value> = HashMap::new();
-        for type_tag in DATA_TYPE_TAGS {
-            let entries = storage.list_by_type(&source_id, type_tag);
-            for (key, vv) in entries {
-                if key.namespace.space == *space {
-                    source_values.insert((key.user_key.clone(), type_tag), vv.value);
-                }
-            }
+        // Source unchanged since the base, or both sides already agree
+        if source_val == base_val || source_val == target_val {
+            continue;
         }
 
-        // Write to target
-        let mut batch: Vec<(Key, Value)> = Vec::new();
-        for diff_entry in &entries_to_apply {
-            // Find the matching source value
-            for type_tag in DATA_TYPE_TAGS {
-                if type_tag_to_primitive(type_tag) == diff_entry.primitive {
-                    let user_key_bytes = diff_entry.raw_key.clone();
-                    if let Some(value) = source_values.get(&(user_key_bytes.clone(), type_tag)) {
-                        let target_ns = Namespace::for_branch_space(target_id, space);
-                        let target_key = Key::new(target_ns, type_tag, user_key_bytes);
-                        batch.push((target_key, value.clone()));
-                        break;
-                    }
-                }
-            }
+        let target_key = Key::new(
+            Namespace::for_branch_space(target_id, space),
+            *type_tag,
+            user_key.clone(),
+        );
+
+        // Changed only on the source
+        if target_val == base_val {
+            plan.write(target_key, source_val.cloned(), target_val);
+            continue;
         }
 
-        let batch_len = batch.len() as u64;
-        if batch_len > 0 {
-            db.transaction(target_id, |txn| {
-                for (key, value) in &batch {
-                    txn.put(key.clone(), value.clone())?;
-                }
-                Ok(())
-            })?;
-            keys_applied += batch_len;
+        // Both sides changed the key: let the primitive's strategy reconcile it
+        match reconcile(*type_tag, &strategies, base_val, source_val, target_val)? {
+            Reconciled::Unchanged => {}
+            Reconciled::Merged(value) => plan.write(target_key, Some(value), target_val),
+            Reconciled::Conflict(resolved) => plan.conflicts.push(PlannedConflict {
+                entry: ConflictEntry {
+                    key: format_user_key(user_key),
+                    raw_key: user_key.clone(),
+                    primitive: type_tag_to_primitive(*type_tag),
+                    space: space.clone(),
+                    source_value: source_val.map(|v| user_value(*type_tag, user_key, v)),
+                    target_value: target_val.map(|v| user_value(*type_tag, user_key, v)),
+                },
+                key: target_key,
+                source: source_val.cloned(),
+                target: target_val.cloned(),
+                resolved,
+            }),
+        }
+    }
+
+    // 4. Event logs merge by appending rather than key by key
+    if strategies.event == EventMergeStrategy::Append {
+        plan.appends = events_to_append(&base_state, &source_state, &target_state);
+        plan.touched_spaces.extend(plan.appends.keys().cloned());
+    }
+
+    Ok(plan)
+}
+
+/// Apply a merge plan to the target in a single transaction.
+fn apply_merge(
+    db: &Arc<Database>,
+    source: &str,
+    target: &str,
+    plan: MergePlan,
+    conflicts: Vec<ConflictEntry>,
+    strategy: &str,
+) -> StrataResult<MergeInfo> {
+    let MergePlan {
+        source_id,
+        target_id,
+        merge_base,
+        source_version,
+        puts,
+        deletes,
+        appends,
+        touched_spaces,
+        expected,
+        ..
+    } = plan;
+
+    // Ensure target has every space we write into
+    let space_index = SpaceIndex::new(db.clone());
+    for space in &touched_spaces {
+        if space != "default" {
+            space_index.register(target_id, space)?;
         }
     }
+    let spaces_merged = touched_spaces.len() as u64;
+
+    // Apply all changes atomically
+    let keys_applied = puts.len() as u64;
+    let keys_deleted = deletes.len() as u64;
+    let events_appended = appends.values().map(|events| events.len() as u64).sum();
+    if !puts.is_empty() || !deletes.is_empty() || !appends.is_empty() {
+        db.transaction(target_id, |txn| {
+            // Reading each key puts it in the read set, so a write racing
+            // this transaction fails the commit; a write made since the
+            // plan was computed is caught here
+            for (key, planned) in &expected {
+                if txn.get(key)? != *planned {
+                    return Err(StrataError::conflict(format!(
+                        "Branch '{}' changed while merging from '{}'; retry the merge",
+                        target, source
+                    )));
+                }
+            }
+            for (key, value) in &puts {
+                txn.put(key.clone(), value.clone())?;
+            }
+            for key in &deletes {
+                txn.delete(key.clone())?;
+            }
+            for (space, events) in &appends {
+                let ns = Namespace::for_branch_space(target_id, space);
+                event::append_copies(txn, &ns, events)?;
+            }
+            Ok(())
+        })?;
+    }
+
+    // Later merges of the same branches start from this one
+    if let Some(version) = source_version {
+        BranchIndex::new(db.clone()).record_merge(target, source, version)?;
+    }
 
     // Reload vector backends for the target branch so that vectors merged
     // at the KV level become visible to in-memory search immediately.
@@ -618,8 +1843,12 @@ pub fn merge_branches(
         source,
         target,
         keys_applied,
+        keys_deleted,
+        events_appended,
         spaces_merged,
-        strategy = ?strategy,
+        conflicts = conflicts.len(),
+        merge_base = ?merge_base,
+        strategy,
         "Branches merged"
     );
 
@@ -627,92 +1856,444 @@ pub fn merge_branches(
         source: source.to_string(),
         target: target.to_string(),
         keys_applied,
+        keys_deleted,
+        events_appended,
         conflicts,
         spaces_merged,
+        merge_base,
     })
 }
 
 // =============================================================================
-// Tests
+// Commit log
 // =============================================================================
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use strata_core::types::Namespace;
-    use tempfile::TempDir;
-
-    fn setup() -> (TempDir, Arc<Database>) {
-        let temp_dir = TempDir::new().unwrap();
-        let db = Database::open(temp_dir.path()).unwrap();
-        (temp_dir, db)
+/// List the transactions committed on a branch, newest first.
+///
+/// Each commit carries the message and metadata its transaction set (see
+/// `TransactionContext::set_message`) and a per-primitive summary of the
+/// keys it wrote and deleted. `before` pages through older commits: only
+/// commits with a version below it are returned.
+///
+/// Commits are read from the WAL, so only the branch's own commits are
+/// listed (not those it inherited from its fork parent) and commits in WAL
+/// segments removed by compaction are gone. Commits that touched no user
+/// data (such as space registration) are skipped. The WAL is walked newest
+/// first and the walk stops once `limit` commits are found.
+///
+/// # Errors
+///
+/// - Branch does not exist
+/// - The database has no WAL (ephemeral or `cache` durability)
+pub fn list_commits(
+    db: &Arc<Database>,
+    branch: &str,
+    limit: Option<usize>,
+    before: Option<u64>,
+) -> StrataResult<Vec<CommitInfo>> {
+    let branch_id = resolve_and_verify(db, branch)?;
+
+    let mut commits: Vec<CommitInfo> = Vec::new();
+    if limit == Some(0) {
+        return Ok(commits);
     }
+    db.visit_branch_transactions(branch_id, |record, payload| {
+        if before.map_or(true, |before| payload.version < before) {
+            let commit = commit_info(record, payload);
+            if !commit.touched.is_empty() {
+                commits.push(commit);
+            }
+        }
+        Ok(limit.map_or(true, |limit| commits.len() < limit))
+    })?;
+    Ok(commits)
+}
 
-    fn setup_with_branch(name: &str) -> (TempDir, Arc<Database>) {
-        let (temp_dir, db) = setup();
-        let branch_index = BranchIndex::new(db.clone());
-        branch_index.create_branch(name).unwrap();
-        (temp_dir, db)
+/// Summarize a WAL transaction as a commit.
+fn commit_info(record: &WalRecord, payload: TransactionPayload) -> CommitInfo {
+    let mut touched: BTreeMap<TypeTag, TouchedKeys> = BTreeMap::new();
+    let changes = payload
+        .puts
+        .iter()
+        .map(|(key, _)| (key, true))
+        .chain(payload.deletes.iter().map(|key| (key, false)));
+    for (key, written) in changes {
+        if !DATA_TYPE_TAGS.contains(&key.type_tag) {
+            continue;
+        }
+        let entry = touched.entry(key.type_tag).or_insert_with(|| TouchedKeys {
+            primitive: type_tag_to_primitive(key.type_tag),
+            keys_written: 0,
+            keys_deleted: 0,
+            keys: Vec::new(),
+        });
+        if written {
+            entry.keys_written += 1;
+        } else {
+            entry.keys_deleted += 1;
+        }
+        if entry.keys.len() < COMMIT_KEY_SAMPLE {
+            entry.keys.push(format_user_key(&key.user_key));
+        }
     }
 
-    fn write_kv(db: &Arc<Database>, branch: &str, space: &str, key: &str, value: Value) {
-        let branch_id = resolve_branch_name(branch);
-        let ns = Namespace::for_branch_space(branch_id, space);
-        db.transaction(branch_id, |txn| {
-            txn.put(
-                Key::new(ns.clone(), TypeTag::KV, key.as_bytes().to_vec()),
-                value,
-            )?;
-            Ok(())
-        })
-        .unwrap();
+    let touched: Vec<TouchedKeys> = touched.into_values().collect();
+    CommitInfo {
+        version: payload.version,
+        txn_id: record.txn_id,
+        timestamp: record.timestamp,
+        message: payload.message,
+        metadata: payload.metadata,
+        keys_written: touched.iter().map(|t| t.keys_written).sum(),
+        keys_deleted: touched.iter().map(|t| t.keys_deleted).sum(),
+        touched,
     }
+}
 
-    fn write_state(db: &Arc<Database>, branch: &str, space: &str, key: &str, value: Value) {
-        let branch_id = resolve_branch_name(branch);
-        let ns = Namespace::for_branch_space(branch_id, space);
-        db.transaction(branch_id, |txn| {
-            txn.put(
-                Key::new(ns.clone(), TypeTag::State, key.as_bytes().to_vec()),
-                value,
-            )?;
-            Ok(())
-        })
-        .unwrap();
-    }
+// =============================================================================
+// Cherry-pick
+// =============================================================================
 
-    fn write_json(db: &Arc<Database>, branch: &str, space: &str, key: &str, value: Value) {
-        let branch_id = resolve_branch_name(branch);
-        let ns = Namespace::for_branch_space(branch_id, space);
-        db.transaction(branch_id, |txn| {
-            txn.put(
-                Key::new(ns.clone(), TypeTag::Json, key.as_bytes().to_vec()),
-                value,
-            )?;
-            Ok(())
-        })
-        .unwrap();
+/// Replay chosen commits from `source` onto `target`.
+///
+/// `versions` name commits as returned by [`list_commits`]. They are
+/// replayed oldest first, following the merge rules with each commit's
+/// parent state as the base: for every key the commit touched, the base is
+/// the source value just before the commit.
+///
+/// - **Target still at the base**: the commit's write or delete is applied
+/// - **Target already matches the commit**: nothing to do
+/// - **Target changed differently**: reconciled by the key's primitive
+///   strategy, otherwise a conflict
+///   - `LastWriterWins`: the commit wins
+///   - `Strict`: cherry-pick fails with the conflict count (no writes)
+///   - `Manual`: conflicts are returned and nothing is applied
+///
+/// With [`EventMergeStrategy::Append`], events appended by the commits are
+/// appended to the target log instead, skipping events already there.
+/// Everything is applied to the target in a single transaction.
+///
+/// The commits are looked up by walking the source's WAL records newest
+/// first, only back as far as the oldest requested version.
+///
+/// # Errors
+///
+/// - Either branch does not exist
+/// - `versions` is empty, or names a version that is not a commit on `source`
+/// - The database has no WAL (ephemeral or `cache` durability)
+/// - `Strict` strategy with conflicts
+/// - The target changed while the merge was being applied (`Conflict`)
+pub fn cherry_pick(
+    db: &Arc<Database>,
+    source: &str,
+    target: &str,
+    versions: &[u64],
+    strategy: MergeStrategy,
+    strategies: PrimitiveMergeStrategies,
+) -> StrataResult<CherryPickInfo> {
+    use strata_core::Storage;
+
+    let source_id = resolve_and_verify(db, source)?;
+    let target_id = resolve_and_verify(db, target)?;
+    if versions.is_empty() {
+        return Err(StrataError::invalid_input("No commits to cherry-pick"));
     }
 
-    fn read_kv(db: &Arc<Database>, branch: &str, space: &str, key: &str) -> Option<Value> {
-        let branch_id = resolve_branch_name(branch);
-        let _ns = Namespace::for_branch_space(branch_id, space);
-        let storage = db.storage();
-        let entries = storage.list_by_type(&branch_id, TypeTag::KV);
-        for (k, vv) in entries {
-            if k.namespace.space == space && k.user_key == key.as_bytes() {
-                return Some(vv.value);
-            }
+    // Walk the source's commits newest first, stopping once every requested
+    // commit is found or the walk is past the oldest one
+    let mut wanted: BTreeSet<u64> = versions.iter().copied().collect();
+    let oldest = *wanted.first().expect("versions is not empty");
+    let mut picked: Vec<TransactionPayload> = Vec::new();
+    db.visit_branch_transactions(source_id, |_, payload| {
+        if payload.version < oldest {
+            return Ok(false);
         }
-        None
-    }
-
+        if wanted.remove(&payload.version) {
+            picked.push(payload);
+        }
+        Ok(!wanted.is_empty())
+    })?;
+    if let Some(missing) = wanted.first() {
+        return Err(StrataError::invalid_input(format!(
+            "No commit at version {} on branch '{}'",
+            missing, source
+        )));
+    }
+    picked.sort_by_key(|payload| payload.version);
+
+    // Target values as they will be after the commits replayed so far
+    let storage = db.storage();
+    let mut writes: BTreeMap<Key, Option<Value>> = BTreeMap::new();
+    let mut original: HashMap<Key, Option<Value>> = HashMap::new();
+    let mut conflicts: Vec<ConflictEntry> = Vec::new();
+    let mut new_events: Vec<(String, Event)> = Vec::new();
+
+    for payload in &picked {
+        let changes = payload
+            .puts
+            .iter()
+            .map(|(key, value)| (key, Some(value)))
+            .chain(payload.deletes.iter().map(|key| (key, None)));
+
+        for (key, commit_val) in changes {
+            let type_tag = key.type_tag;
+            if !DATA_TYPE_TAGS.contains(&type_tag) {
+                continue;
+            }
+            let space = &key.namespace.space;
+            if type_tag == TypeTag::Event && strategies.event == EventMergeStrategy::Append {
+                if let Some(event) = commit_val.and_then(|v| event::decode_event(&key.user_key, v))
+                {
+                    new_events.push((space.clone(), event));
+                }
+                continue;
+            }
+
+            let base_val = storage
+                .get_versioned(key, payload.version.saturating_sub(1))?
+                .map(|vv| vv.value);
+            let target_key = Key::new(
+                Namespace::for_branch_space(target_id, space),
+                type_tag,
+                key.user_key.clone(),
+            );
+            let target_val = match writes.get(&target_key) {
+                Some(planned) => planned.clone(),
+                None => {
+                    let stored = storage.get(&target_key)?.map(|vv| vv.value);
+                    original.insert(target_key.clone(), stored.clone());
+                    stored
+                }
+            };
+
+            if commit_val == target_val.as_ref() {
+                continue;
+            }
+            if target_val == base_val {
+                writes.insert(target_key, commit_val.cloned());
+                continue;
+            }
+
+            match reconcile(
+                type_tag,
+                &strategies,
+                base_val.as_ref(),
+                commit_val,
+                target_val.as_ref(),
+            )? {
+                Reconciled::Unchanged => {}
+                Reconciled::Merged(value) => {
+                    writes.insert(target_key, Some(value));
+                }
+                Reconciled::Conflict(resolved) => {
+                    conflicts.push(ConflictEntry {
+                        key: format_user_key(&key.user_key),
+                        raw_key: key.user_key.clone(),
+                        primitive: type_tag_to_primitive(type_tag),
+                        space: space.clone(),
+                        source_value: commit_val.map(|v| user_value(type_tag, &key.user_key, v)),
+                        target_value: target_val
+                            .as_ref()
+                            .map(|v| user_value(type_tag, &key.user_key, v)),
+                    });
+                    writes.insert(target_key, resolved);
+                }
+            }
+        }
+    }
+
+    let commits: Vec<u64> = picked.iter().map(|payload| payload.version).collect();
+    if !conflicts.is_empty() {
+        match strategy {
+            MergeStrategy::Strict => {
+                return Err(StrataError::invalid_input(format!(
+                    "Cherry-pick conflict: {} keys differ between the picked commits of '{}' and '{}'. Use LastWriterWins strategy or inspect conflicts with the Manual strategy.",
+                    conflicts.len(),
+                    source,
+                    target
+                )));
+            }
+            MergeStrategy::Manual => {
+                info!(
+                    target: "strata::branch_ops",
+                    source,
+                    target,
+                    conflicts = conflicts.len(),
+                    "Cherry-pick has conflicts; nothing applied"
+                );
+                return Ok(CherryPickInfo {
+                    source: source.to_string(),
+                    target: target.to_string(),
+                    commits,
+                    keys_applied: 0,
+                    keys_deleted: 0,
+                    events_appended: 0,
+                    conflicts,
+                });
+            }
+            MergeStrategy::LastWriterWins => {}
+        }
+    }
+
+    let mut plan = MergePlan {
+        source_id,
+        target_id,
+        merge_base: None,
+        source_version: None,
+        puts: Vec::new(),
+        deletes: Vec::new(),
+        appends: BTreeMap::new(),
+        touched_spaces: BTreeSet::new(),
+        conflicts: Vec::new(),
+        expected: HashMap::new(),
+    };
+    for (key, value) in writes {
+        let current = original.remove(&key).flatten();
+        plan.write(key, value, current.as_ref());
+    }
+
+    // Skip events the target log already holds, as an event-append merge does
+    if !new_events.is_empty() {
+        let target_events: Vec<(String, Event)> = storage
+            .list_by_type(&target_id, TypeTag::Event)?
+            .into_iter()
+            .filter_map(|(key, vv)| {
+                event::decode_event(&key.user_key, &vv.value)
+                    .map(|event| (key.namespace.space, event))
+            })
+            .collect();
+        for (space, event) in new_events {
+            let present = target_events.iter().any(|(existing_space, existing)| {
+                *existing_space == space
+                    && existing.timestamp == event.timestamp
+                    && existing.event_type == event.event_type
+                    && existing.payload == event.payload
+            });
+            if !present {
+                plan.touched_spaces.insert(space.clone());
+                plan.appends.entry(space).or_default().push(event);
+            }
+        }
+    }
+
+    let info = apply_merge(
+        db,
+        source,
+        target,
+        plan,
+        conflicts,
+        &format!("CherryPick({:?})", strategy),
+    )?;
+    Ok(CherryPickInfo {
+        source: info.source,
+        target: info.target,
+        commits,
+        keys_applied: info.keys_applied,
+        keys_deleted: info.keys_deleted,
+        events_appended: info.events_appended,
+        conflicts: info.conflicts,
+    })
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strata_core::types::Namespace;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Arc<Database>) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path()).unwrap();
+        (temp_dir, db)
+    }
+
+    fn setup_with_branch(name: &str) -> (TempDir, Arc<Database>) {
+        let (temp_dir, db) = setup();
+        let branch_index = BranchIndex::new(db.clone());
+        branch_index.create_branch(name).unwrap();
+        (temp_dir, db)
+    }
+
+    fn write_kv(db: &Arc<Database>, branch: &str, space: &str, key: &str, value: Value) {
+        let branch_id = resolve_branch_name(branch);
+        let ns = Namespace::for_branch_space(branch_id, space);
+        db.transaction(branch_id, |txn| {
+            txn.put(
+                Key::new(ns.clone(), TypeTag::KV, key.as_bytes().to_vec()),
+                value,
+            )?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    fn write_state(db: &Arc<Database>, branch: &str, space: &str, key: &str, value: Value) {
+        let branch_id = resolve_branch_name(branch);
+        let ns = Namespace::for_branch_space(branch_id, space);
+        db.transaction(branch_id, |txn| {
+            txn.put(
+                Key::new(ns.clone(), TypeTag::State, key.as_bytes().to_vec()),
+                value,
+            )?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    fn write_json(db: &Arc<Database>, branch: &str, space: &str, key: &str, value: Value) {
+        let branch_id = resolve_branch_name(branch);
+        let ns = Namespace::for_branch_space(branch_id, space);
+        db.transaction(branch_id, |txn| {
+            txn.put(
+                Key::new(ns.clone(), TypeTag::Json, key.as_bytes().to_vec()),
+                value,
+            )?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    fn delete_kv(db: &Arc<Database>, branch: &str, space: &str, key: &str) {
+        let branch_id = resolve_branch_name(branch);
+        let ns = Namespace::for_branch_space(branch_id, space);
+        db.transaction(branch_id, |txn| {
+            txn.delete(Key::new(ns.clone(), TypeTag::KV, key.as_bytes().to_vec()))?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    /// A timestamp strictly after every write so far and before any later one.
+    fn timestamp_between_writes() -> u64 {
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let ts = strata_core::Timestamp::now().into();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        ts
+    }
+
+    fn read_kv(db: &Arc<Database>, branch: &str, space: &str, key: &str) -> Option<Value> {
+        let branch_id = resolve_branch_name(branch);
+        let _ns = Namespace::for_branch_space(branch_id, space);
+        let storage = db.storage();
+        let entries = storage.list_by_type(&branch_id, TypeTag::KV).unwrap();
+        for (k, vv) in entries {
+            if k.namespace.space == space && k.user_key == key.as_bytes() {
+                return Some(vv.value);
+            }
+        }
+        None
+    }
+
     // =========================================================================
     // Fork Tests
     // =========================================================================
 
     #[test]
-    fn test_fork_copies_all_data() {
+    fn test_fork_shares_all_data() {
         let (_temp, db) = setup_with_branch("source");
 
         // Write various data types
@@ -730,7 +2311,7 @@ mod tests {
         let info = fork_branch(&db, "source", "dest").unwrap();
         assert_eq!(info.source, "source");
         assert_eq!(info.destination, "dest");
-        assert!(info.keys_copied >= 4);
+        assert_eq!(info.keys_copied, 0);
 
         // Verify all data is present in destination
         assert_eq!(
@@ -742,7 +2323,7 @@ mod tests {
         // Verify state data
         let dest_id = resolve_branch_name("dest");
         let storage = db.storage();
-        let state_entries = storage.list_by_type(&dest_id, TypeTag::State);
+        let state_entries = storage.list_by_type(&dest_id, TypeTag::State).unwrap();
         assert!(
             state_entries
                 .iter()
@@ -751,7 +2332,7 @@ mod tests {
         );
 
         // Verify JSON data
-        let json_entries = storage.list_by_type(&dest_id, TypeTag::Json);
+        let json_entries = storage.list_by_type(&dest_id, TypeTag::Json).unwrap();
         assert!(
             json_entries
                 .iter()
@@ -855,6 +2436,156 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fork_does_not_copy_data() {
+        let (_temp, db) = setup_with_branch("source");
+        for i in 0..100 {
+            write_kv(&db, "source", "default", &format!("k{}", i), Value::Int(i));
+        }
+
+        fork_branch(&db, "source", "dest").unwrap();
+
+        let dest_id = resolve_branch_name("dest");
+        assert_eq!(db.storage().branch_entry_count(&dest_id), 0);
+        assert_eq!(
+            db.storage()
+                .list_by_type(&dest_id, TypeTag::KV)
+                .unwrap()
+                .len(),
+            100
+        );
+
+        // Writes to either side after the fork stay on that side
+        write_kv(&db, "source", "default", "k1", Value::Int(-1));
+        write_kv(&db, "source", "default", "new", Value::Int(0));
+        write_kv(&db, "dest", "default", "k2", Value::Int(-2));
+
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "dest", "default", "new"), None);
+        assert_eq!(read_kv(&db, "dest", "default", "k2"), Some(Value::Int(-2)));
+        assert_eq!(read_kv(&db, "source", "default", "k2"), Some(Value::Int(2)));
+        assert_eq!(db.storage().branch_entry_count(&dest_id), 1);
+    }
+
+    #[test]
+    fn test_fork_of_fork_sees_whole_lineage() {
+        let (_temp, db) = setup_with_branch("root");
+        write_kv(&db, "root", "default", "r", Value::Int(1));
+        fork_branch(&db, "root", "middle").unwrap();
+        write_kv(&db, "middle", "default", "m", Value::Int(2));
+        write_kv(&db, "root", "default", "late", Value::Int(3));
+
+        fork_branch(&db, "middle", "leaf").unwrap();
+
+        assert_eq!(read_kv(&db, "leaf", "default", "r"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "leaf", "default", "m"), Some(Value::Int(2)));
+        assert_eq!(read_kv(&db, "leaf", "default", "late"), None);
+    }
+
+    #[test]
+    fn test_fork_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+
+        {
+            let db = Database::open(&db_path).unwrap();
+            BranchIndex::new(db.clone())
+                .create_branch("source")
+                .unwrap();
+            write_kv(&db, "source", "default", "k1", Value::Int(1));
+            fork_branch(&db, "source", "dest").unwrap();
+            write_kv(&db, "source", "default", "k1", Value::Int(2));
+            write_kv(&db, "dest", "default", "k2", Value::Int(3));
+            db.flush().unwrap();
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "dest", "default", "k2"), Some(Value::Int(3)));
+        assert_eq!(read_kv(&db, "source", "default", "k1"), Some(Value::Int(2)));
+        assert_eq!(read_kv(&db, "source", "default", "k2"), None);
+    }
+
+    #[test]
+    fn test_delete_fork_keeps_source_data() {
+        let (_temp, db) = setup_with_branch("source");
+        write_kv(&db, "source", "default", "k1", Value::Int(1));
+        fork_branch(&db, "source", "dest").unwrap();
+        write_kv(&db, "dest", "default", "k2", Value::Int(2));
+
+        BranchIndex::new(db.clone()).delete_branch("dest").unwrap();
+
+        assert_eq!(read_kv(&db, "source", "default", "k1"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), None);
+        assert_eq!(read_kv(&db, "dest", "default", "k2"), None);
+
+        // The name can be reused for a fresh fork
+        write_kv(&db, "source", "default", "k1", Value::Int(10));
+        fork_branch(&db, "source", "dest").unwrap();
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(10)));
+        assert_eq!(read_kv(&db, "dest", "default", "k2"), None);
+    }
+
+    #[test]
+    fn test_delete_source_keeps_fork_data() {
+        let (_temp, db) = setup_with_branch("source");
+        write_kv(&db, "source", "default", "k1", Value::Int(1));
+        fork_branch(&db, "source", "dest").unwrap();
+
+        let branch_index = BranchIndex::new(db.clone());
+        branch_index.delete_branch("source").unwrap();
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(1)));
+
+        // Re-creating the source under the same name doesn't leak into the fork
+        branch_index.create_branch("source").unwrap();
+        write_kv(&db, "source", "default", "k1", Value::Int(2));
+        write_kv(&db, "source", "default", "k2", Value::Int(3));
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "dest", "default", "k2"), None);
+    }
+
+    #[test]
+    fn test_diff_and_merge_copy_on_write_fork() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "shared", Value::Int(1));
+        write_kv(&db, "main", "default", "gone", Value::Int(2));
+        fork_branch(&db, "main", "feature").unwrap();
+
+        // An untouched fork has no differences
+        let diff = diff_branches(&db, "main", "feature").unwrap();
+        assert_eq!(diff.summary.total_added, 0);
+        assert_eq!(diff.summary.total_removed, 0);
+        assert_eq!(diff.summary.total_modified, 0);
+
+        write_kv(&db, "feature", "default", "added", Value::Int(3));
+        let feature_id = resolve_branch_name("feature");
+        db.transaction(feature_id, |txn| {
+            txn.delete(Key::new(
+                Namespace::for_branch_space(feature_id, "default"),
+                TypeTag::KV,
+                b"gone".to_vec(),
+            ))
+        })
+        .unwrap();
+
+        let diff = diff_branches(&db, "main", "feature").unwrap();
+        assert_eq!(diff.summary.total_added, 1);
+        assert_eq!(diff.summary.total_removed, 1);
+
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Strict).unwrap();
+        assert_eq!(info.keys_applied, 1);
+        assert_eq!(info.keys_deleted, 1);
+        assert_eq!(
+            read_kv(&db, "main", "default", "added"),
+            Some(Value::Int(3))
+        );
+        assert_eq!(read_kv(&db, "main", "default", "gone"), None);
+        assert_eq!(
+            read_kv(&db, "main", "default", "shared"),
+            Some(Value::Int(1))
+        );
+    }
+
     // =========================================================================
     // Diff Tests
     // =========================================================================
@@ -910,16 +2641,8 @@ mod tests {
         let space_diff = &diff.spaces[0];
         assert_eq!(space_diff.modified.len(), 1);
         assert_eq!(space_diff.modified[0].key, "shared");
-        assert!(space_diff.modified[0]
-            .value_a
-            .as_ref()
-            .unwrap()
-            .contains("Int(1)"));
-        assert!(space_diff.modified[0]
-            .value_b
-            .as_ref()
-            .unwrap()
-            .contains("Int(2)"));
+        assert_eq!(space_diff.modified[0].value_a, Some(Value::Int(1)));
+        assert_eq!(space_diff.modified[0].value_b, Some(Value::Int(2)));
     }
 
     #[test]
@@ -948,58 +2671,260 @@ mod tests {
         assert_eq!(diff.summary.total_added, 1);
     }
 
-    // =========================================================================
-    // Merge Tests
-    // =========================================================================
-
     #[test]
-    fn test_merge_lww() {
-        let (_temp, db) = setup_with_branch("target");
-        let branch_index = BranchIndex::new(db.clone());
-        branch_index.create_branch("source").unwrap();
-
-        // Write conflicting data
-        write_kv(&db, "target", "default", "shared", Value::Int(1));
-        write_kv(&db, "source", "default", "shared", Value::Int(2));
-
-        // Source-only key
-        write_kv(
-            &db,
-            "source",
-            "default",
-            "new_key",
-            Value::String("new".into()),
-        );
-
-        let info = merge_branches(&db, "source", "target", MergeStrategy::LastWriterWins).unwrap();
-        assert!(info.keys_applied >= 2);
+    fn test_diff_json_values_and_patches() {
+        let (_temp, db) = setup_with_branch("a");
+        JsonStore::new(db.clone())
+            .create(
+                &resolve_branch_name("a"),
+                "default",
+                "doc",
+                serde_json::json!({"n": 1, "tag": "x"}).into(),
+            )
+            .unwrap();
+        fork_branch(&db, "a", "b").unwrap();
+        json_set(&db, "b", "doc", "n", serde_json::json!(2));
 
-        // Target should now have source's value for "shared"
+        let diff = diff_branches(&db, "a", "b").unwrap();
+        let entry = &diff.spaces[0].modified[0];
+        assert_eq!(entry.primitive, PrimitiveType::Json);
         assert_eq!(
-            read_kv(&db, "target", "default", "shared"),
-            Some(Value::Int(2))
+            entry.value_b,
+            Some(Value::Object(HashMap::from([
+                ("n".to_string(), Value::Int(2)),
+                ("tag".to_string(), Value::String("x".into())),
+            ])))
         );
-        // Target should have the new key
         assert_eq!(
-            read_kv(&db, "target", "default", "new_key"),
-            Some(Value::String("new".into()))
+            entry.patches,
+            vec![JsonPatch::set_at(
+                "n".parse().unwrap(),
+                serde_json::json!(2).into()
+            )]
         );
     }
 
     #[test]
-    fn test_merge_strict_no_conflicts() {
-        let (_temp, db) = setup_with_branch("target");
-        let branch_index = BranchIndex::new(db.clone());
-        branch_index.create_branch("source").unwrap();
+    fn test_diff_filters() {
+        let (_temp, db) = setup_with_branch("a");
+        BranchIndex::new(db.clone()).create_branch("b").unwrap();
+        let space_index = SpaceIndex::new(db.clone());
+        space_index
+            .register(resolve_branch_name("b"), "other")
+            .unwrap();
 
-        // Non-overlapping data (no conflicts)
-        write_kv(&db, "target", "default", "target_key", Value::Int(1));
-        write_kv(&db, "source", "default", "source_key", Value::Int(2));
+        write_kv(&db, "b", "default", "user:1", Value::Int(1));
+        write_kv(&db, "b", "default", "user:2", Value::Int(2));
+        write_kv(&db, "b", "default", "order:1", Value::Int(3));
+        write_kv(&db, "b", "other", "user:3", Value::Int(4));
+        crate::StateCell::new(db.clone())
+            .init(
+                &resolve_branch_name("b"),
+                "default",
+                "user:cell",
+                Value::Int(0),
+            )
+            .unwrap();
 
-        let info = merge_branches(&db, "source", "target", MergeStrategy::Strict).unwrap();
-        assert!(info.keys_applied >= 1);
+        let options = DiffOptions {
+            primitives: vec![PrimitiveType::Kv],
+            spaces: vec!["default".to_string()],
+            key_prefix: Some("user:".to_string()),
+            ..Default::default()
+        };
+        let diff = diff_branches_with_options(&db, "a", "b", &options).unwrap();
+        assert_eq!(diff.summary.total_added, 2);
+        let keys: Vec<_> = diff.spaces[0]
+            .added
+            .iter()
+            .map(|e| e.key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["user:1", "user:2"]);
+        assert!(diff.summary.spaces_only_in_b.is_empty());
+        assert_eq!(diff.next_cursor, None);
+    }
 
-        // Target should have both keys
+    #[test]
+    fn test_diff_pagination() {
+        let (_temp, db) = setup_with_branch("a");
+        BranchIndex::new(db.clone()).create_branch("b").unwrap();
+        for i in 0..5 {
+            write_kv(&db, "b", "default", &format!("k{}", i), Value::Int(i));
+        }
+
+        let mut options = DiffOptions {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let mut pages = Vec::new();
+        loop {
+            let diff = diff_branches_with_options(&db, "a", "b", &options).unwrap();
+            // The summary of a page counts that page's entries
+            assert_eq!(
+                diff.summary.total_added,
+                diff.spaces.iter().map(|sd| sd.added.len()).sum::<usize>()
+            );
+            pages.push(
+                diff.spaces
+                    .iter()
+                    .flat_map(|sd| sd.added.iter().map(|e| e.key.clone()))
+                    .collect::<Vec<_>>(),
+            );
+            match diff.next_cursor {
+                Some(cursor) => options.cursor = Some(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(pages, vec![vec!["k0", "k1"], vec!["k2", "k3"], vec!["k4"]]);
+
+        let summary = DiffOptions {
+            limit: Some(2),
+            summary_only: true,
+            ..Default::default()
+        };
+        let diff = diff_branches_with_options(&db, "a", "b", &summary).unwrap();
+        assert_eq!(diff.summary.total_added, 5);
+        assert_eq!(diff.next_cursor, None);
+    }
+
+    #[test]
+    fn test_diff_summary_only() {
+        let (_temp, db) = setup_with_branch("a");
+        BranchIndex::new(db.clone()).create_branch("b").unwrap();
+        write_kv(&db, "a", "default", "gone", Value::Int(1));
+        write_kv(&db, "a", "default", "changed", Value::Int(1));
+        write_kv(&db, "b", "default", "changed", Value::Int(2));
+        write_kv(&db, "b", "default", "new", Value::Int(3));
+
+        let options = DiffOptions {
+            summary_only: true,
+            ..Default::default()
+        };
+        let diff = diff_branches_with_options(&db, "a", "b", &options).unwrap();
+        assert!(diff.spaces.is_empty());
+        assert_eq!(diff.summary.total_added, 1);
+        assert_eq!(diff.summary.total_removed, 1);
+        assert_eq!(diff.summary.total_modified, 1);
+    }
+
+    #[test]
+    fn test_diff_between_timestamps() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+        write_kv(&db, "main", "default", "gone", Value::Int(1));
+        let t1 = timestamp_between_writes();
+
+        write_kv(&db, "main", "default", "k", Value::Int(2));
+        write_kv(&db, "main", "default", "new", Value::Int(3));
+        delete_kv(&db, "main", "default", "gone");
+
+        let options = DiffOptions {
+            as_of_a: Some(t1),
+            ..Default::default()
+        };
+        let diff = diff_branches_with_options(&db, "main", "main", &options).unwrap();
+        assert_eq!(diff.summary.total_added, 1);
+        assert_eq!(diff.summary.total_removed, 1);
+        assert_eq!(diff.summary.total_modified, 1);
+        let modified = &diff.spaces[0].modified[0];
+        assert_eq!(modified.key, "k");
+        assert_eq!(modified.value_a, Some(Value::Int(1)));
+        assert_eq!(modified.value_b, Some(Value::Int(2)));
+
+        // Same point on both sides: no changes
+        let options = DiffOptions {
+            as_of_a: Some(t1),
+            as_of_b: Some(t1),
+            ..Default::default()
+        };
+        let diff = diff_branches_with_options(&db, "main", "main", &options).unwrap();
+        assert!(diff.spaces.is_empty());
+    }
+
+    #[test]
+    fn test_diff_across_branches_at_timestamp() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+        fork_branch(&db, "main", "feature").unwrap();
+        let t1 = timestamp_between_writes();
+
+        write_kv(&db, "feature", "default", "k", Value::Int(2));
+
+        // At t1 the fork still read through to main's value
+        let options = DiffOptions {
+            as_of_b: Some(t1),
+            ..Default::default()
+        };
+        let diff = diff_branches_with_options(&db, "main", "feature", &options).unwrap();
+        assert!(diff.spaces.is_empty());
+
+        let diff = diff_branches(&db, "main", "feature").unwrap();
+        assert_eq!(diff.summary.total_modified, 1);
+    }
+
+    #[test]
+    fn test_diff_invalid_cursor() {
+        let (_temp, db) = setup_with_branch("a");
+        BranchIndex::new(db.clone()).create_branch("b").unwrap();
+        let options = DiffOptions {
+            cursor: Some("not-a-cursor".to_string()),
+            ..Default::default()
+        };
+        assert!(diff_branches_with_options(&db, "a", "b", &options).is_err());
+    }
+
+    // =========================================================================
+    // Merge Tests
+    // =========================================================================
+
+    #[test]
+    fn test_merge_lww() {
+        let (_temp, db) = setup_with_branch("target");
+        let branch_index = BranchIndex::new(db.clone());
+        branch_index.create_branch("source").unwrap();
+
+        // Write conflicting data
+        write_kv(&db, "target", "default", "shared", Value::Int(1));
+        write_kv(&db, "source", "default", "shared", Value::Int(2));
+
+        // Source-only key
+        write_kv(
+            &db,
+            "source",
+            "default",
+            "new_key",
+            Value::String("new".into()),
+        );
+
+        let info = merge_branches(&db, "source", "target", MergeStrategy::LastWriterWins).unwrap();
+        assert!(info.keys_applied >= 2);
+
+        // Target should now have source's value for "shared"
+        assert_eq!(
+            read_kv(&db, "target", "default", "shared"),
+            Some(Value::Int(2))
+        );
+        // Target should have the new key
+        assert_eq!(
+            read_kv(&db, "target", "default", "new_key"),
+            Some(Value::String("new".into()))
+        );
+    }
+
+    #[test]
+    fn test_merge_strict_no_conflicts() {
+        let (_temp, db) = setup_with_branch("target");
+        let branch_index = BranchIndex::new(db.clone());
+        branch_index.create_branch("source").unwrap();
+
+        // Non-overlapping data (no conflicts)
+        write_kv(&db, "target", "default", "target_key", Value::Int(1));
+        write_kv(&db, "source", "default", "source_key", Value::Int(2));
+
+        let info = merge_branches(&db, "source", "target", MergeStrategy::Strict).unwrap();
+        assert!(info.keys_applied >= 1);
+
+        // Target should have both keys
         assert_eq!(
             read_kv(&db, "target", "default", "target_key"),
             Some(Value::Int(1))
@@ -1128,7 +3053,7 @@ mod tests {
 
         // Verify the binary key data is in target
         let storage = db.storage();
-        let target_events = storage.list_by_type(&target_id, TypeTag::Event);
+        let target_events = storage.list_by_type(&target_id, TypeTag::Event).unwrap();
         assert!(
             target_events.iter().any(|(k, vv)| k.user_key == binary_key
                 && vv.value == Value::String("event-data".into())),
@@ -1161,6 +3086,269 @@ mod tests {
         assert_eq!(info.conflicts.len(), 1, "Should report 1 conflict");
         assert_eq!(info.conflicts[0].key, "shared");
         assert_eq!(info.conflicts[0].primitive, PrimitiveType::Kv);
+        assert_eq!(info.conflicts[0].source_value, Some(Value::Int(2)));
+        assert_eq!(info.conflicts[0].target_value, Some(Value::Int(1)));
+    }
+
+    // =========================================================================
+    // Three-Way Merge Tests
+    // =========================================================================
+
+    #[test]
+    fn test_fork_records_lineage() {
+        let (_temp, db) = setup_with_branch("source");
+        write_kv(&db, "source", "default", "k1", Value::Int(1));
+
+        let info = fork_branch(&db, "source", "dest").unwrap();
+
+        let meta = BranchIndex::new(db.clone())
+            .get_branch("dest")
+            .unwrap()
+            .unwrap()
+            .value;
+        assert_eq!(meta.parent_branch.as_deref(), Some("source"));
+        assert_eq!(meta.fork_version, Some(info.fork_version));
+    }
+
+    #[test]
+    fn test_merge_propagates_source_delete() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "doomed", Value::Int(1));
+        write_kv(&db, "main", "default", "kept", Value::Int(2));
+
+        fork_branch(&db, "main", "feature").unwrap();
+
+        let feature_id = resolve_branch_name("feature");
+        let ns = Namespace::for_branch_space(feature_id, "default");
+        db.transaction(feature_id, |txn| {
+            txn.delete(Key::new(ns.clone(), TypeTag::KV, b"doomed".to_vec()))
+        })
+        .unwrap();
+
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Strict).unwrap();
+        assert_eq!(info.keys_deleted, 1);
+        assert_eq!(info.keys_applied, 0);
+        assert!(info.conflicts.is_empty());
+        assert_eq!(
+            info.merge_base.as_ref().map(|b| b.branch.as_str()),
+            Some("main")
+        );
+
+        assert_eq!(read_kv(&db, "main", "default", "doomed"), None);
+        assert_eq!(read_kv(&db, "main", "default", "kept"), Some(Value::Int(2)));
+    }
+
+    #[test]
+    fn test_merge_one_sided_changes_do_not_conflict() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "a", Value::Int(1));
+        write_kv(&db, "main", "default", "b", Value::Int(1));
+
+        fork_branch(&db, "main", "feature").unwrap();
+
+        // Each side changes a different key
+        write_kv(&db, "main", "default", "a", Value::Int(2));
+        write_kv(&db, "feature", "default", "b", Value::Int(3));
+
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Strict).unwrap();
+        assert!(info.conflicts.is_empty());
+        assert_eq!(info.keys_applied, 1);
+
+        // Target-only change is kept, source-only change is applied
+        assert_eq!(read_kv(&db, "main", "default", "a"), Some(Value::Int(2)));
+        assert_eq!(read_kv(&db, "main", "default", "b"), Some(Value::Int(3)));
+    }
+
+    #[test]
+    fn test_merge_target_delete_is_kept() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "gone", Value::Int(1));
+
+        fork_branch(&db, "main", "feature").unwrap();
+
+        let main_id = resolve_branch_name("main");
+        let ns = Namespace::for_branch_space(main_id, "default");
+        db.transaction(main_id, |txn| {
+            txn.delete(Key::new(ns.clone(), TypeTag::KV, b"gone".to_vec()))
+        })
+        .unwrap();
+
+        // Source never touched the key, so the target's delete stands
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Strict).unwrap();
+        assert_eq!(info.keys_applied, 0);
+        assert_eq!(read_kv(&db, "main", "default", "gone"), None);
+    }
+
+    #[test]
+    fn test_merge_both_changed_conflicts() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+
+        fork_branch(&db, "main", "feature").unwrap();
+
+        write_kv(&db, "main", "default", "k", Value::Int(2));
+        write_kv(&db, "feature", "default", "k", Value::Int(3));
+
+        let result = merge_branches(&db, "feature", "main", MergeStrategy::Strict);
+        assert!(result.is_err());
+        assert_eq!(read_kv(&db, "main", "default", "k"), Some(Value::Int(2)));
+
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::LastWriterWins).unwrap();
+        assert_eq!(info.conflicts.len(), 1);
+        assert_eq!(read_kv(&db, "main", "default", "k"), Some(Value::Int(3)));
+    }
+
+    #[test]
+    fn test_merge_rejects_target_changed_after_planning() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+        fork_branch(&db, "main", "feature").unwrap();
+        write_kv(&db, "feature", "default", "k", Value::Int(2));
+
+        let plan = plan_merge(&db, "feature", "main", PrimitiveMergeStrategies::default()).unwrap();
+        assert!(plan.conflicts.is_empty());
+
+        // The target moves on before the plan is applied
+        write_kv(&db, "main", "default", "k", Value::Int(3));
+
+        let result = apply_merge(&db, "feature", "main", plan, Vec::new(), "Strict");
+        assert!(matches!(result, Err(StrataError::Conflict { .. })));
+        assert_eq!(read_kv(&db, "main", "default", "k"), Some(Value::Int(3)));
+
+        // Replanning sees the target's change as a conflict
+        let result = merge_branches(&db, "feature", "main", MergeStrategy::Strict);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_merge_both_changed_identically_is_clean() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+
+        fork_branch(&db, "main", "feature").unwrap();
+
+        write_kv(&db, "main", "default", "k", Value::Int(5));
+        write_kv(&db, "feature", "default", "k", Value::Int(5));
+
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Strict).unwrap();
+        assert!(info.conflicts.is_empty());
+        assert_eq!(info.keys_applied, 0);
+    }
+
+    #[test]
+    fn test_merge_base_ignores_parent_writes_after_fork() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+
+        fork_branch(&db, "main", "feature").unwrap();
+
+        // Parent changes after the fork must not leak into the merge base
+        write_kv(&db, "main", "default", "late", Value::Int(9));
+
+        // Merging parent into child: "late" was added on the source only
+        let info = merge_branches(&db, "main", "feature", MergeStrategy::Strict).unwrap();
+        assert_eq!(info.keys_applied, 1);
+        assert_eq!(
+            read_kv(&db, "feature", "default", "late"),
+            Some(Value::Int(9))
+        );
+    }
+
+    #[test]
+    fn test_merge_siblings_use_common_parent() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "shared", Value::Int(1));
+        write_kv(&db, "main", "default", "doomed", Value::Int(1));
+
+        fork_branch(&db, "main", "left").unwrap();
+        fork_branch(&db, "main", "right").unwrap();
+
+        write_kv(&db, "left", "default", "shared", Value::Int(2));
+        let left_id = resolve_branch_name("left");
+        let ns = Namespace::for_branch_space(left_id, "default");
+        db.transaction(left_id, |txn| {
+            txn.delete(Key::new(ns.clone(), TypeTag::KV, b"doomed".to_vec()))
+        })
+        .unwrap();
+
+        let info = merge_branches(&db, "left", "right", MergeStrategy::Strict).unwrap();
+        assert_eq!(
+            info.merge_base.as_ref().map(|b| b.branch.as_str()),
+            Some("main")
+        );
+        assert_eq!(info.keys_applied, 1);
+        assert_eq!(info.keys_deleted, 1);
+        assert_eq!(
+            read_kv(&db, "right", "default", "shared"),
+            Some(Value::Int(2))
+        );
+        assert_eq!(read_kv(&db, "right", "default", "doomed"), None);
+    }
+
+    #[test]
+    fn test_merge_unrelated_branches_has_no_base() {
+        let (_temp, db) = setup_with_branch("target");
+        BranchIndex::new(db.clone())
+            .create_branch("source")
+            .unwrap();
+        write_kv(&db, "source", "default", "k", Value::Int(1));
+
+        let info = merge_branches(&db, "source", "target", MergeStrategy::Strict).unwrap();
+        assert!(info.merge_base.is_none());
+        assert_eq!(info.keys_applied, 1);
+    }
+
+    #[test]
+    fn test_merge_again_starts_from_previous_merge() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+
+        fork_branch(&db, "main", "feature").unwrap();
+        write_kv(&db, "feature", "default", "k", Value::Int(2));
+        merge_branches(&db, "feature", "main", MergeStrategy::Strict).unwrap();
+
+        // Target edits the merged key; the source moves on elsewhere
+        write_kv(&db, "main", "default", "k", Value::Int(3));
+        write_kv(&db, "feature", "default", "other", Value::Int(1));
+
+        // The first merge already carried k = 2, so only "other" is new
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Strict).unwrap();
+        assert!(info.conflicts.is_empty());
+        assert_eq!(info.keys_applied, 1);
+        assert_eq!(
+            info.merge_base.as_ref().map(|b| b.branch.as_str()),
+            Some("feature")
+        );
+        assert_eq!(read_kv(&db, "main", "default", "k"), Some(Value::Int(3)));
+        assert_eq!(
+            read_kv(&db, "main", "default", "other"),
+            Some(Value::Int(1))
+        );
+
+        // Merging back the other way sees only the target's edit
+        let info = merge_branches(&db, "main", "feature", MergeStrategy::Strict).unwrap();
+        assert!(info.conflicts.is_empty());
+        assert_eq!(read_kv(&db, "feature", "default", "k"), Some(Value::Int(3)));
+    }
+
+    #[test]
+    fn test_merge_ignores_record_of_deleted_source() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+        fork_branch(&db, "main", "feature").unwrap();
+        write_kv(&db, "feature", "default", "k", Value::Int(2));
+        merge_branches(&db, "feature", "main", MergeStrategy::Strict).unwrap();
+
+        // A new, unrelated branch takes the merged branch's name
+        let branch_index = BranchIndex::new(db.clone());
+        branch_index.delete_branch("feature").unwrap();
+        branch_index.create_branch("feature").unwrap();
+        write_kv(&db, "feature", "default", "k", Value::Int(5));
+
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::LastWriterWins).unwrap();
+        assert!(info.merge_base.is_none());
+        assert_eq!(info.conflicts.len(), 1);
+        assert_eq!(read_kv(&db, "main", "default", "k"), Some(Value::Int(5)));
     }
 
     // =========================================================================
@@ -1518,7 +3706,7 @@ mod tests {
 
         // Fork
         let info = fork_branch(&db, "source", "dest").unwrap();
-        assert!(info.keys_copied > 0);
+        assert_eq!(info.keys_copied, 0);
 
         let dest_id = resolve_branch_name("dest");
 
@@ -1541,4 +3729,911 @@ mod tests {
             .value;
         assert_eq!(entry.embedding, vec![1.0, 0.0, 0.0]);
     }
+
+    // ========================================================================
+    // Per-primitive merge strategies
+    // ========================================================================
+
+    fn with_strategies(
+        json: JsonMergeStrategy,
+        event: EventMergeStrategy,
+        state: StateMergeStrategy,
+    ) -> PrimitiveMergeStrategies {
+        PrimitiveMergeStrategies { json, event, state }
+    }
+
+    fn json_doc(db: &Arc<Database>, branch: &str, doc: &str) -> serde_json::Value {
+        JsonStore::new(db.clone())
+            .get(
+                &resolve_branch_name(branch),
+                "default",
+                doc,
+                &strata_core::primitives::json::JsonPath::root(),
+            )
+            .unwrap()
+            .unwrap()
+            .into_inner()
+    }
+
+    fn json_set(db: &Arc<Database>, branch: &str, doc: &str, path: &str, value: serde_json::Value) {
+        JsonStore::new(db.clone())
+            .set(
+                &resolve_branch_name(branch),
+                "default",
+                doc,
+                &path.parse().unwrap(),
+                value.into(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_merge_json_path_level_combines_disjoint_changes() {
+        let (_temp, db) = setup_with_branch("main");
+        JsonStore::new(db.clone())
+            .create(
+                &resolve_branch_name("main"),
+                "default",
+                "doc",
+                serde_json::json!({"a": 1, "b": 1, "c": {"x": 1}}).into(),
+            )
+            .unwrap();
+
+        fork_branch(&db, "main", "feature").unwrap();
+        json_set(&db, "feature", "doc", "a", serde_json::json!(2));
+        json_set(&db, "feature", "doc", "c.y", serde_json::json!(3));
+        json_set(&db, "main", "doc", "b", serde_json::json!(2));
+
+        // Document-level merge sees a conflict
+        assert!(merge_branches(&db, "feature", "main", MergeStrategy::Strict).is_err());
+
+        let strategies = with_strategies(
+            JsonMergeStrategy::Path,
+            EventMergeStrategy::ByKey,
+            StateMergeStrategy::Value,
+        );
+        let info = merge_branches_with_strategies(
+            &db,
+            "feature",
+            "main",
+            MergeStrategy::Strict,
+            strategies,
+        )
+        .unwrap();
+        assert!(info.conflicts.is_empty());
+        assert_eq!(info.keys_applied, 1);
+        assert_eq!(
+            json_doc(&db, "main", "doc"),
+            serde_json::json!({"a": 2, "b": 2, "c": {"x": 1, "y": 3}})
+        );
+    }
+
+    #[test]
+    fn test_merge_json_overlapping_paths_conflict() {
+        let (_temp, db) = setup_with_branch("main");
+        JsonStore::new(db.clone())
+            .create(
+                &resolve_branch_name("main"),
+                "default",
+                "doc",
+                serde_json::json!({"user": {"name": "a"}, "n": 1}).into(),
+            )
+            .unwrap();
+
+        fork_branch(&db, "main", "feature").unwrap();
+        json_set(
+            &db,
+            "feature",
+            "doc",
+            "user.name",
+            serde_json::json!("source"),
+        );
+        json_set(
+            &db,
+            "main",
+            "doc",
+            "user",
+            serde_json::json!({"name": "target"}),
+        );
+        json_set(&db, "main", "doc", "n", serde_json::json!(2));
+
+        let strategies = with_strategies(
+            JsonMergeStrategy::Path,
+            EventMergeStrategy::ByKey,
+            StateMergeStrategy::Value,
+        );
+        let result = merge_branches_with_strategies(
+            &db,
+            "feature",
+            "main",
+            MergeStrategy::Strict,
+            strategies,
+        );
+        assert!(result.is_err());
+
+        // Source wins at the overlapping path; the target's other change survives
+        let info = merge_branches_with_strategies(
+            &db,
+            "feature",
+            "main",
+            MergeStrategy::LastWriterWins,
+            strategies,
+        )
+        .unwrap();
+        assert_eq!(info.conflicts.len(), 1);
+        assert_eq!(info.conflicts[0].primitive, PrimitiveType::Json);
+        assert_eq!(
+            json_doc(&db, "main", "doc"),
+            serde_json::json!({"user": {"name": "source"}, "n": 2})
+        );
+    }
+
+    #[test]
+    fn test_merge_events_append_rechains_source_events() {
+        use crate::EventLog;
+
+        let (_temp, db) = setup_with_branch("main");
+        let log = EventLog::new(db.clone());
+        let main_id = resolve_branch_name("main");
+        let feature_id = resolve_branch_name("feature");
+        let payload = |n: i64| Value::Object(HashMap::from([("n".to_string(), Value::Int(n))]));
+
+        log.append(&main_id, "default", "base", payload(0)).unwrap();
+        fork_branch(&db, "main", "feature").unwrap();
+        log.append(&feature_id, "default", "feature", payload(1))
+            .unwrap();
+        log.append(&feature_id, "default", "feature", payload(2))
+            .unwrap();
+        log.append(&main_id, "default", "main", payload(3)).unwrap();
+
+        // Both logs wrote sequence 1, so a key-by-key merge conflicts
+        assert!(merge_branches(&db, "feature", "main", MergeStrategy::Strict).is_err());
+
+        let strategies = with_strategies(
+            JsonMergeStrategy::Document,
+            EventMergeStrategy::Append,
+            StateMergeStrategy::Value,
+        );
+        let info = merge_branches_with_strategies(
+            &db,
+            "feature",
+            "main",
+            MergeStrategy::Strict,
+            strategies,
+        )
+        .unwrap();
+        assert!(info.conflicts.is_empty());
+        assert_eq!(info.events_appended, 2);
+        assert_eq!(log.len(&main_id, "default").unwrap(), 4);
+
+        let events: Vec<Event> = (0..4)
+            .map(|seq| log.get(&main_id, "default", seq).unwrap().unwrap().value)
+            .collect();
+        assert_eq!(events[2].payload, payload(1));
+        assert_eq!(events[3].payload, payload(2));
+        assert_eq!(events[3].sequence, 3);
+        for pair in events.windows(2) {
+            assert_eq!(pair[1].prev_hash, pair[0].hash);
+        }
+        assert_eq!(
+            log.get_by_type(&main_id, "default", "feature", None, None)
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Already-merged events are not appended again
+        let info = merge_branches_with_strategies(
+            &db,
+            "feature",
+            "main",
+            MergeStrategy::Strict,
+            strategies,
+        )
+        .unwrap();
+        assert_eq!(info.events_appended, 0);
+        assert_eq!(log.len(&main_id, "default").unwrap(), 4);
+    }
+
+    #[test]
+    fn test_merge_state_counter_version() {
+        use crate::StateCell;
+
+        let (_temp, db) = setup_with_branch("main");
+        let cells = StateCell::new(db.clone());
+        let main_id = resolve_branch_name("main");
+        let feature_id = resolve_branch_name("feature");
+        cells.init(&main_id, "default", "c", Value::Int(0)).unwrap();
+        cells
+            .init(&main_id, "default", "same", Value::Int(0))
+            .unwrap();
+        fork_branch(&db, "main", "feature").unwrap();
+
+        cells
+            .set(&feature_id, "default", "c", Value::Int(5))
+            .unwrap();
+        cells.set(&main_id, "default", "c", Value::Int(7)).unwrap();
+        cells.set(&main_id, "default", "c", Value::Int(8)).unwrap();
+        // Same value written on both sides at different times
+        cells
+            .set(&feature_id, "default", "same", Value::Int(1))
+            .unwrap();
+        cells
+            .set(&main_id, "default", "same", Value::Int(1))
+            .unwrap();
+
+        let strategies = with_strategies(
+            JsonMergeStrategy::Document,
+            EventMergeStrategy::ByKey,
+            StateMergeStrategy::CounterVersion,
+        );
+        let result = merge_branches_with_strategies(
+            &db,
+            "feature",
+            "main",
+            MergeStrategy::Strict,
+            strategies,
+        );
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("1 keys differ"), "{}", err);
+
+        let info = merge_branches_with_strategies(
+            &db,
+            "feature",
+            "main",
+            MergeStrategy::LastWriterWins,
+            strategies,
+        )
+        .unwrap();
+        assert_eq!(info.conflicts.len(), 1);
+        assert_eq!(info.conflicts[0].key, "c");
+
+        let merged = cells
+            .get_versioned(&main_id, "default", "c")
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.value, Value::Int(5));
+        // Counter moves past both sides (main reached 3)
+        assert_eq!(merged.version, Version::counter(4));
+    }
+
+    #[test]
+    fn test_merge_state_counter_version_one_sided_change() {
+        use crate::StateCell;
+
+        let (_temp, db) = setup_with_branch("main");
+        let cells = StateCell::new(db.clone());
+        let main_id = resolve_branch_name("main");
+        cells.init(&main_id, "default", "c", Value::Int(0)).unwrap();
+        fork_branch(&db, "main", "feature").unwrap();
+        cells
+            .set(
+                &resolve_branch_name("feature"),
+                "default",
+                "c",
+                Value::Int(1),
+            )
+            .unwrap();
+
+        let strategies = with_strategies(
+            JsonMergeStrategy::Document,
+            EventMergeStrategy::ByKey,
+            StateMergeStrategy::CounterVersion,
+        );
+        let info = merge_branches_with_strategies(
+            &db,
+            "feature",
+            "main",
+            MergeStrategy::Strict,
+            strategies,
+        )
+        .unwrap();
+        assert!(info.conflicts.is_empty());
+        assert_eq!(
+            cells.get(&main_id, "default", "c").unwrap(),
+            Some(Value::Int(1))
+        );
+    }
+
+    // ========================================================================
+    // Conflict resolution
+    // ========================================================================
+
+    /// Fork `feature` from `main` and change keys `a`, `b` and `c` on both sides.
+    fn setup_conflicting_branches() -> (TempDir, Arc<Database>) {
+        let (temp, db) = setup_with_branch("main");
+        for key in ["a", "b", "c"] {
+            write_kv(&db, "main", "default", key, Value::Int(0));
+        }
+        fork_branch(&db, "main", "feature").unwrap();
+        for key in ["a", "b", "c"] {
+            write_kv(&db, "main", "default", key, Value::Int(1));
+            write_kv(&db, "feature", "default", key, Value::Int(2));
+        }
+        write_kv(&db, "feature", "default", "new", Value::Int(3));
+        (temp, db)
+    }
+
+    fn resolve(info: &MergeInfo, key: &str, resolution: ConflictResolution) -> MergeResolution {
+        let conflict = info.conflicts.iter().find(|c| c.key == key).unwrap();
+        MergeResolution::new(conflict, resolution)
+    }
+
+    #[test]
+    fn test_merge_manual_reports_conflicts_without_applying() {
+        let (_temp, db) = setup_conflicting_branches();
+
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Manual).unwrap();
+        assert_eq!(info.conflicts.len(), 3);
+        assert_eq!(info.keys_applied, 0);
+        assert_eq!(info.conflicts[0].raw_key, b"a".to_vec());
+        assert_eq!(info.conflicts[0].source_value, Some(Value::Int(2)));
+        assert_eq!(info.conflicts[0].target_value, Some(Value::Int(1)));
+
+        // Nothing was applied, not even the non-conflicting key
+        assert_eq!(read_kv(&db, "main", "default", "a"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "main", "default", "new"), None);
+    }
+
+    #[test]
+    fn test_merge_manual_without_conflicts_applies() {
+        let (_temp, db) = setup_with_branch("main");
+        fork_branch(&db, "main", "feature").unwrap();
+        write_kv(&db, "feature", "default", "k", Value::Int(1));
+
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Manual).unwrap();
+        assert!(info.conflicts.is_empty());
+        assert_eq!(info.keys_applied, 1);
+        assert_eq!(read_kv(&db, "main", "default", "k"), Some(Value::Int(1)));
+    }
+
+    #[test]
+    fn test_resolve_merge_applies_resolutions() {
+        let (_temp, db) = setup_conflicting_branches();
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Manual).unwrap();
+
+        let resolutions = vec![
+            resolve(&info, "a", ConflictResolution::TakeSource),
+            resolve(&info, "b", ConflictResolution::TakeTarget),
+            resolve(&info, "c", ConflictResolution::Custom(Value::Int(42))),
+        ];
+        let resolved = resolve_merge(
+            &db,
+            "feature",
+            "main",
+            PrimitiveMergeStrategies::default(),
+            &resolutions,
+        )
+        .unwrap();
+        assert_eq!(resolved.conflicts.len(), 3);
+        assert_eq!(resolved.keys_applied, 3);
+
+        assert_eq!(read_kv(&db, "main", "default", "a"), Some(Value::Int(2)));
+        assert_eq!(read_kv(&db, "main", "default", "b"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "main", "default", "c"), Some(Value::Int(42)));
+        assert_eq!(read_kv(&db, "main", "default", "new"), Some(Value::Int(3)));
+    }
+
+    #[test]
+    fn test_resolve_merge_requires_every_conflict() {
+        let (_temp, db) = setup_conflicting_branches();
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Manual).unwrap();
+
+        let partial = vec![resolve(&info, "a", ConflictResolution::TakeSource)];
+        let err = resolve_merge(
+            &db,
+            "feature",
+            "main",
+            PrimitiveMergeStrategies::default(),
+            &partial,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("2 of 3"), "{}", err);
+        assert_eq!(read_kv(&db, "main", "default", "a"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "main", "default", "new"), None);
+    }
+
+    #[test]
+    fn test_resolve_merge_rejects_stale_resolution() {
+        let (_temp, db) = setup_conflicting_branches();
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Manual).unwrap();
+        let mut resolutions: Vec<_> = info
+            .conflicts
+            .iter()
+            .map(|c| MergeResolution::new(c, ConflictResolution::TakeSource))
+            .collect();
+
+        // Target catches up with the source on "a": no longer a conflict
+        write_kv(&db, "main", "default", "a", Value::Int(2));
+        assert!(resolve_merge(
+            &db,
+            "feature",
+            "main",
+            PrimitiveMergeStrategies::default(),
+            &resolutions
+        )
+        .is_err());
+
+        resolutions.remove(0);
+        resolve_merge(
+            &db,
+            "feature",
+            "main",
+            PrimitiveMergeStrategies::default(),
+            &resolutions,
+        )
+        .unwrap();
+        assert_eq!(read_kv(&db, "main", "default", "b"), Some(Value::Int(2)));
+    }
+
+    #[test]
+    fn test_resolve_merge_custom_json_and_state() {
+        use crate::StateCell;
+
+        let (_temp, db) = setup_with_branch("main");
+        let main_id = resolve_branch_name("main");
+        let feature_id = resolve_branch_name("feature");
+        let cells = StateCell::new(db.clone());
+        cells
+            .init(&main_id, "default", "cell", Value::Int(0))
+            .unwrap();
+        JsonStore::new(db.clone())
+            .create(
+                &main_id,
+                "default",
+                "doc",
+                serde_json::json!({"n": 0}).into(),
+            )
+            .unwrap();
+        fork_branch(&db, "main", "feature").unwrap();
+
+        cells
+            .set(&main_id, "default", "cell", Value::Int(1))
+            .unwrap();
+        cells
+            .set(&feature_id, "default", "cell", Value::Int(2))
+            .unwrap();
+        json_set(&db, "main", "doc", "n", serde_json::json!(1));
+        json_set(&db, "feature", "doc", "n", serde_json::json!(2));
+
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Manual).unwrap();
+        assert_eq!(info.conflicts.len(), 2);
+        let doc = info.conflicts.iter().find(|c| c.key == "doc").unwrap();
+        assert_eq!(
+            doc.source_value,
+            Some(Value::Object(HashMap::from([(
+                "n".to_string(),
+                Value::Int(2)
+            )])))
+        );
+        let cell = info.conflicts.iter().find(|c| c.key == "cell").unwrap();
+        assert_eq!(cell.target_value, Some(Value::Int(1)));
+
+        let custom_doc = Value::Object(HashMap::from([("n".to_string(), Value::Int(3))]));
+        let resolutions = vec![
+            MergeResolution::new(doc, ConflictResolution::Custom(custom_doc)),
+            MergeResolution::new(cell, ConflictResolution::Custom(Value::Int(3))),
+        ];
+        resolve_merge(
+            &db,
+            "feature",
+            "main",
+            PrimitiveMergeStrategies::default(),
+            &resolutions,
+        )
+        .unwrap();
+
+        assert_eq!(json_doc(&db, "main", "doc"), serde_json::json!({"n": 3}));
+        let merged = cells
+            .get_versioned(&main_id, "default", "cell")
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.value, Value::Int(3));
+        assert_eq!(merged.version, Version::counter(3));
+    }
+
+    #[test]
+    fn test_resolve_merge_rejects_custom_event() {
+        use crate::EventLog;
+
+        let (_temp, db) = setup_with_branch("main");
+        let log = EventLog::new(db.clone());
+        fork_branch(&db, "main", "feature").unwrap();
+        let payload = Value::Object(HashMap::new());
+        log.append(
+            &resolve_branch_name("main"),
+            "default",
+            "m",
+            payload.clone(),
+        )
+        .unwrap();
+        log.append(&resolve_branch_name("feature"), "default", "f", payload)
+            .unwrap();
+
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::Manual).unwrap();
+        let event = info
+            .conflicts
+            .iter()
+            .find(|c| c.raw_key == 0u64.to_be_bytes().to_vec())
+            .unwrap();
+        let resolutions: Vec<_> = info
+            .conflicts
+            .iter()
+            .map(|c| {
+                let resolution = if c == event {
+                    ConflictResolution::Custom(Value::Null)
+                } else {
+                    ConflictResolution::TakeSource
+                };
+                MergeResolution::new(c, resolution)
+            })
+            .collect();
+        let err = resolve_merge(
+            &db,
+            "feature",
+            "main",
+            PrimitiveMergeStrategies::default(),
+            &resolutions,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not supported"), "{}", err);
+    }
+
+    // ========================================================================
+    // Revert and historical fork
+    // ========================================================================
+
+    #[test]
+    fn test_revert_branch_restores_all_primitives() {
+        use crate::{EventLog, StateCell};
+
+        let (_temp, db) = setup_with_branch("main");
+        let id = resolve_branch_name("main");
+        let cells = StateCell::new(db.clone());
+        let log = EventLog::new(db.clone());
+
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+        write_kv(&db, "main", "default", "doomed", Value::Int(1));
+        cells.init(&id, "default", "cell", Value::Int(1)).unwrap();
+        JsonStore::new(db.clone())
+            .create(&id, "default", "doc", serde_json::json!({"n": 1}).into())
+            .unwrap();
+        log.append(&id, "default", "e", Value::Object(HashMap::new()))
+            .unwrap();
+        let t1 = timestamp_between_writes();
+
+        write_kv(&db, "main", "default", "k", Value::Int(2));
+        write_kv(&db, "main", "default", "new", Value::Int(3));
+        delete_kv(&db, "main", "default", "doomed");
+        cells.set(&id, "default", "cell", Value::Int(2)).unwrap();
+        json_set(&db, "main", "doc", "n", serde_json::json!(2));
+        log.append(&id, "default", "e", Value::Object(HashMap::new()))
+            .unwrap();
+        let t2 = timestamp_between_writes();
+
+        let info = revert_branch(&db, "main", t1).unwrap();
+        assert_eq!(info.as_of, t1);
+        assert!(info.keys_deleted >= 2, "new key and event are deleted");
+
+        assert_eq!(read_kv(&db, "main", "default", "k"), Some(Value::Int(1)));
+        assert_eq!(
+            read_kv(&db, "main", "default", "doomed"),
+            Some(Value::Int(1))
+        );
+        assert_eq!(read_kv(&db, "main", "default", "new"), None);
+        assert_eq!(json_doc(&db, "main", "doc"), serde_json::json!({"n": 1}));
+        assert_eq!(log.len(&id, "default").unwrap(), 1);
+
+        // State cells move forward in version while taking the old value
+        let cell = cells
+            .get_versioned(&id, "default", "cell")
+            .unwrap()
+            .unwrap();
+        assert_eq!(cell.value, Value::Int(1));
+        assert_eq!(cell.version, Version::counter(3));
+
+        // History stays intact
+        let key = Key::new(
+            Namespace::for_branch_space(id, "default"),
+            TypeTag::KV,
+            b"k".to_vec(),
+        );
+        assert_eq!(
+            db.storage()
+                .get_at_timestamp(&key, t2)
+                .unwrap()
+                .unwrap()
+                .value,
+            Value::Int(2)
+        );
+    }
+
+    #[test]
+    fn test_revert_branch_restores_graphs_and_vectors() {
+        use crate::graph::GraphStore;
+        use crate::primitives::vector::store::VectorStore;
+        use crate::primitives::vector::{DistanceMetric, VectorConfig};
+
+        let (_temp, db) = setup_with_branch("main");
+        let id = resolve_branch_name("main");
+        let graphs = GraphStore::new(db.clone());
+        let vectors = VectorStore::new(db.clone());
+
+        graphs.create_graph(id, "g", None).unwrap();
+        graphs.add_node(id, "g", "a", Default::default()).unwrap();
+        vectors
+            .create_collection(
+                id,
+                "default",
+                "docs",
+                VectorConfig::new(3, DistanceMetric::Cosine).unwrap(),
+            )
+            .unwrap();
+        vectors
+            .insert(id, "default", "docs", "v1", &[1.0, 0.0, 0.0], None)
+            .unwrap();
+        let t1 = timestamp_between_writes();
+
+        graphs.add_node(id, "g", "b", Default::default()).unwrap();
+        vectors
+            .insert(id, "default", "docs", "v2", &[0.0, 1.0, 0.0], None)
+            .unwrap();
+
+        revert_branch(&db, "main", t1).unwrap();
+
+        assert_eq!(graphs.list_nodes(id, "g").unwrap(), vec!["a".to_string()]);
+        let results = vectors
+            .search(id, "default", "docs", &[0.0, 1.0, 0.0], 5, None)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].key, "v1");
+    }
+
+    #[test]
+    fn test_revert_branch_restores_deleted_vector() {
+        use crate::primitives::vector::store::VectorStore;
+        use crate::primitives::vector::{DistanceMetric, VectorConfig};
+
+        let (_temp, db) = setup_with_branch("main");
+        let id = resolve_branch_name("main");
+        let vectors = VectorStore::new(db.clone());
+
+        vectors
+            .create_collection(
+                id,
+                "default",
+                "docs",
+                VectorConfig::new(3, DistanceMetric::Cosine).unwrap(),
+            )
+            .unwrap();
+        vectors
+            .insert(id, "default", "docs", "v1", &[1.0, 0.0, 0.0], None)
+            .unwrap();
+        vectors
+            .insert(id, "default", "docs", "v2", &[0.0, 1.0, 0.0], None)
+            .unwrap();
+        let t1 = timestamp_between_writes();
+
+        // The backend drops the embedding along with the vector
+        assert!(vectors.delete(id, "default", "docs", "v2").unwrap());
+
+        revert_branch(&db, "main", t1).unwrap();
+
+        let restored = vectors.get(id, "default", "docs", "v2").unwrap().unwrap();
+        assert_eq!(restored.value.embedding, vec![0.0, 1.0, 0.0]);
+        let results = vectors
+            .search(id, "default", "docs", &[0.0, 1.0, 0.0], 5, None)
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].key, "v2");
+    }
+
+    #[test]
+    fn test_revert_branch_not_found() {
+        let (_temp, db) = setup();
+        assert!(revert_branch(&db, "missing", 0).is_err());
+    }
+
+    #[test]
+    fn test_fork_branch_at_timestamp() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+        let t1 = timestamp_between_writes();
+        write_kv(&db, "main", "default", "k", Value::Int(2));
+        write_kv(&db, "main", "default", "later", Value::Int(3));
+
+        let info = fork_branch_at(&db, "main", "past", t1).unwrap();
+        assert!(info.fork_version < db.storage().version());
+
+        assert_eq!(read_kv(&db, "past", "default", "k"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "past", "default", "later"), None);
+        assert_eq!(read_kv(&db, "main", "default", "k"), Some(Value::Int(2)));
+
+        // The historical point is the merge base: main's later writes merge
+        // cleanly into the fork
+        let merged = merge_branches(&db, "main", "past", MergeStrategy::Strict).unwrap();
+        assert_eq!(merged.keys_applied, 2);
+        assert_eq!(
+            read_kv(&db, "past", "default", "later"),
+            Some(Value::Int(3))
+        );
+    }
+
+    // =========================================================================
+    // Commit log and cherry-pick tests
+    // =========================================================================
+
+    /// Version of the newest commit on a branch.
+    fn last_commit(db: &Arc<Database>, branch: &str) -> u64 {
+        list_commits(db, branch, Some(1), None).unwrap()[0].version
+    }
+
+    #[test]
+    fn test_list_commits() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "a", Value::Int(1));
+        write_kv(&db, "main", "default", "b", Value::Int(2));
+        delete_kv(&db, "main", "default", "a");
+
+        let commits = list_commits(&db, "main", None, None).unwrap();
+        assert_eq!(commits.len(), 3);
+        assert!(commits[0].version > commits[1].version);
+        assert!(commits[1].version > commits[2].version);
+        assert!(commits[0].timestamp >= commits[2].timestamp);
+        assert_eq!((commits[0].keys_written, commits[0].keys_deleted), (0, 1));
+        assert_eq!((commits[2].keys_written, commits[2].keys_deleted), (1, 0));
+
+        assert_eq!(list_commits(&db, "main", Some(2), None).unwrap().len(), 2);
+        assert!(list_commits(&db, "missing", None, None).is_err());
+    }
+
+    #[test]
+    fn test_list_commits_messages_and_touched_keys() {
+        let (_temp, db) = setup_with_branch("main");
+        let branch_id = resolve_branch_name("main");
+        let ns = Namespace::for_branch_space(branch_id, "default");
+        db.transaction(branch_id, |txn| {
+            txn.set_message("seed config");
+            txn.set_metadata("agent", "planner");
+            txn.put(Key::new_kv(ns.clone(), "a"), Value::Int(1))?;
+            txn.put(Key::new_kv(ns.clone(), "b"), Value::Int(2))?;
+            txn.put(
+                Key::new(ns.clone(), TypeTag::State, b"cell".to_vec()),
+                Value::Int(3),
+            )?;
+            Ok(())
+        })
+        .unwrap();
+        delete_kv(&db, "main", "default", "a");
+
+        let commits = list_commits(&db, "main", None, None).unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].message, None);
+        assert_eq!(commits[0].touched.len(), 1);
+        assert_eq!(commits[0].touched[0].keys_deleted, 1);
+
+        let seed = &commits[1];
+        assert_eq!(seed.message.as_deref(), Some("seed config"));
+        assert_eq!(seed.metadata["agent"], "planner");
+        assert_eq!(seed.keys_written, 3);
+        let primitives: Vec<_> = seed.touched.iter().map(|t| t.primitive).collect();
+        assert_eq!(primitives, vec![PrimitiveType::Kv, PrimitiveType::State]);
+        let mut kv_keys = seed.touched[0].keys.clone();
+        kv_keys.sort();
+        assert_eq!(kv_keys, vec!["a", "b"]);
+
+        // Page past the newest commit
+        let older = list_commits(&db, "main", None, Some(commits[0].version)).unwrap();
+        assert_eq!(older, vec![seed.clone()]);
+    }
+
+    #[test]
+    fn test_list_commits_requires_wal() {
+        let db = Database::cache().unwrap();
+        BranchIndex::new(db.clone()).create_branch("main").unwrap();
+        assert!(list_commits(&db, "main", None, None).is_err());
+    }
+
+    #[test]
+    fn test_cherry_pick_applies_selected_commits() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "shared", Value::Int(0));
+        fork_branch(&db, "main", "experiment").unwrap();
+
+        write_kv(&db, "experiment", "default", "a", Value::Int(1));
+        let first = last_commit(&db, "experiment");
+        write_kv(&db, "experiment", "default", "b", Value::Int(2));
+        write_kv(&db, "experiment", "tools", "c", Value::Int(3));
+        let third = last_commit(&db, "experiment");
+        delete_kv(&db, "experiment", "default", "shared");
+        let fourth = last_commit(&db, "experiment");
+
+        let info = cherry_pick(
+            &db,
+            "experiment",
+            "main",
+            &[fourth, first, third],
+            MergeStrategy::Strict,
+            PrimitiveMergeStrategies::default(),
+        )
+        .unwrap();
+        assert_eq!(info.commits, vec![first, third, fourth]);
+        assert_eq!(info.keys_applied, 2);
+        assert_eq!(info.keys_deleted, 1);
+        assert!(info.conflicts.is_empty());
+
+        assert_eq!(read_kv(&db, "main", "default", "a"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "main", "default", "b"), None);
+        assert_eq!(read_kv(&db, "main", "tools", "c"), Some(Value::Int(3)));
+        assert_eq!(read_kv(&db, "main", "default", "shared"), None);
+
+        // Picking the same commit again is a no-op
+        let again = cherry_pick(
+            &db,
+            "experiment",
+            "main",
+            &[first],
+            MergeStrategy::Strict,
+            PrimitiveMergeStrategies::default(),
+        )
+        .unwrap();
+        assert_eq!(again.keys_applied, 0);
+    }
+
+    #[test]
+    fn test_cherry_pick_conflicts() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+        fork_branch(&db, "main", "experiment").unwrap();
+        write_kv(&db, "experiment", "default", "k", Value::Int(2));
+        let commit = last_commit(&db, "experiment");
+        write_kv(&db, "main", "default", "k", Value::Int(5));
+
+        let pick = |strategy| {
+            cherry_pick(
+                &db,
+                "experiment",
+                "main",
+                &[commit],
+                strategy,
+                PrimitiveMergeStrategies::default(),
+            )
+        };
+
+        assert!(pick(MergeStrategy::Strict).is_err());
+
+        let manual = pick(MergeStrategy::Manual).unwrap();
+        assert_eq!(manual.conflicts.len(), 1);
+        assert_eq!(manual.conflicts[0].source_value, Some(Value::Int(2)));
+        assert_eq!(manual.conflicts[0].target_value, Some(Value::Int(5)));
+        assert_eq!(manual.keys_applied, 0);
+        assert_eq!(read_kv(&db, "main", "default", "k"), Some(Value::Int(5)));
+
+        let lww = pick(MergeStrategy::LastWriterWins).unwrap();
+        assert_eq!(lww.conflicts.len(), 1);
+        assert_eq!(read_kv(&db, "main", "default", "k"), Some(Value::Int(2)));
+    }
+
+    #[test]
+    fn test_cherry_pick_unknown_commit() {
+        let (_temp, db) = setup_with_branch("main");
+        let branch_index = BranchIndex::new(db.clone());
+        branch_index.create_branch("experiment").unwrap();
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+        let main_commit = last_commit(&db, "main");
+
+        let pick = |versions: &[u64]| {
+            cherry_pick(
+                &db,
+                "experiment",
+                "main",
+                versions,
+                MergeStrategy::Strict,
+                PrimitiveMergeStrategies::default(),
+            )
+        };
+        assert!(pick(&[]).is_err());
+        // A commit on another branch is not a commit on the source
+        assert!(pick(&[main_commit]).is_err());
+    }
 }
@@ -27,6 +27,7 @@ use strata_core::StrataResult;
 use strata_durability::branch_bundle::{
     BranchBundleReader, BranchBundleWriter, BranchlogPayload, BundleBranchInfo, ExportOptions,
 };
+use strata_durability::codec::{IdentityCodec, StorageCodec};
 
 // =============================================================================
 // Public result types
@@ -118,7 +119,7 @@ pub fn export_branch_with_options(
     let payloads = scan_branch_data(db, core_branch_id, branch_id)?;
 
     // 4. Write bundle
-    let writer = BranchBundleWriter::new(options);
+    let writer = BranchBundleWriter::new(options).with_codec(db.codec().clone_box());
     let export_info = writer
         .write(&bundle_branch_info, &payloads, path)
         .map_err(|e| StrataError::storage(format!("Failed to write bundle: {}", e)))?;
@@ -153,7 +154,7 @@ fn scan_branch_data(
 
     let mut all_keys: Vec<Key> = Vec::new();
     for type_tag in type_tags {
-        let entries = storage.list_by_type(&core_branch_id, type_tag);
+        let entries = storage.list_by_type(&core_branch_id, type_tag)?;
         all_keys.extend(entries.into_iter().map(|(k, _)| k));
     }
 
@@ -209,7 +210,7 @@ fn scan_branch_data(
 /// - I/O errors reading the archive
 pub fn import_branch(db: &Arc<Database>, path: &Path) -> StrataResult<ImportInfo> {
     // 1. Read and validate bundle
-    let contents = BranchBundleReader::read_all(path)
+    let contents = BranchBundleReader::read_all_with_codec(path, db.codec())
         .map_err(|e| StrataError::storage(format!("Failed to read bundle: {}", e)))?;
 
     let branch_id_str = &contents.branch_info.name;
@@ -277,9 +278,20 @@ pub fn import_branch(db: &Arc<Database>, path: &Path) -> StrataResult<ImportInfo
 
 /// Validate a bundle without importing it
 ///
-/// Checks the archive structure, checksums, and format version.
+/// Checks the archive structure, checksums, and format version. Encrypted
+/// bundles fail validation; use [`validate_bundle_for`] with the database
+/// they were exported from.
 pub fn validate_bundle(path: &Path) -> StrataResult<BundleInfo> {
-    let verify = BranchBundleReader::validate(path)
+    validate_bundle_with_codec(path, &IdentityCodec)
+}
+
+/// Validate a bundle using the codec (encryption key) of `db`
+pub fn validate_bundle_for(db: &Arc<Database>, path: &Path) -> StrataResult<BundleInfo> {
+    validate_bundle_with_codec(path, db.codec())
+}
+
+fn validate_bundle_with_codec(path: &Path, codec: &dyn StorageCodec) -> StrataResult<BundleInfo> {
+    let verify = BranchBundleReader::validate_with_codec(path, codec)
         .map_err(|e| StrataError::storage(format!("Bundle validation failed: {}", e)))?;
 
     Ok(BundleInfo {
@@ -447,6 +459,56 @@ mod tests {
         assert!(import_info.transactions_applied > 0);
     }
 
+    #[test]
+    fn test_export_forked_branch_includes_inherited_data() {
+        use strata_core::traits::Storage;
+
+        let (temp_dir, db) = setup_with_branch("parent");
+        let parent_id = crate::primitives::branch::resolve_branch_name("parent");
+        let parent_ns = Namespace::for_branch(parent_id);
+
+        db.transaction(parent_id, |txn| {
+            txn.put(
+                Key::new(parent_ns.clone(), TypeTag::KV, b"inherited".to_vec()),
+                strata_core::value::Value::Int(1),
+            )?;
+            Ok(())
+        })
+        .unwrap();
+        crate::branch_ops::fork_branch(&db, "parent", "child").unwrap();
+
+        let child_id = crate::primitives::branch::resolve_branch_name("child");
+        db.transaction(child_id, |txn| {
+            txn.put(
+                Key::new(
+                    Namespace::for_branch(child_id),
+                    TypeTag::KV,
+                    b"own".to_vec(),
+                ),
+                strata_core::value::Value::Int(2),
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        let bundle_path = temp_dir.path().join("child.branchbundle.tar.zst");
+        export_branch(&db, "child", &bundle_path).unwrap();
+
+        let import_dir = TempDir::new().unwrap();
+        let import_db = Database::open(import_dir.path()).unwrap();
+        import_branch(&import_db, &bundle_path).unwrap();
+
+        let ns = Namespace::for_branch(child_id);
+        for (key, value) in [(&b"inherited"[..], 1), (&b"own"[..], 2)] {
+            let stored = import_db
+                .storage()
+                .get(&Key::new(ns.clone(), TypeTag::KV, key.to_vec()))
+                .unwrap()
+                .unwrap();
+            assert_eq!(stored.value, strata_core::value::Value::Int(value));
+        }
+    }
+
     #[test]
     fn test_import_duplicate_branch_fails() {
         let (temp_dir, db) = setup_with_branch("dup-branch");
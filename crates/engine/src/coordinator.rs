@@ -202,6 +202,11 @@ impl TransactionCoordinator {
         self.manager.current_version()
     }
 
+    /// Highest transaction ID allocated so far
+    pub fn last_txn_id(&self) -> u64 {
+        self.manager.last_txn_id()
+    }
+
     /// Get next transaction ID (for internal use)
     pub fn next_txn_id(&self) -> u64 {
         self.manager.next_txn_id()
@@ -332,6 +337,8 @@ mod tests {
             final_version: 100,
             max_txn_id: 6,
             from_checkpoint: false,
+            checkpoint_version: 0,
+            checkpoint_parents: Vec::new(),
         };
 
         let result = RecoveryResult {
@@ -722,6 +729,8 @@ mod tests {
             final_version: 500,
             max_txn_id: 15,
             from_checkpoint: false,
+            checkpoint_version: 0,
+            checkpoint_parents: Vec::new(),
         };
 
         let result = RecoveryResult {
@@ -0,0 +1,764 @@
+//! Online backup and point-in-time restore
+//!
+//! `Database::backup` copies a live database into a new directory while
+//! writes continue: the MANIFEST, the snapshots of the latest checkpoint,
+//! `strata.toml`, and every WAL segment recovery still needs. The active
+//! segment is copied up to the length it had when the backup started, so
+//! the copy ends at a commit boundary.
+//!
+//! `Database::restore` turns a backup into a database directory, optionally
+//! stopping at a target timestamp. WAL records committed after the target
+//! are dropped from the restored copy, so the restored database opens in
+//! the exact state it had at that moment.
+//!
+//! Restore can also read WAL segments from a WAL archive (see
+//! `wal_archive_dir` in `strata.toml`). A backup plus the segments archived
+//! after it can be restored to any point up to the newest archived commit.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use strata_concurrency::{RecoveryCoordinator, RecoveryStats};
+use strata_core::{StrataError, StrataResult};
+use strata_durability::codec::StorageCodec;
+use strata_durability::format::segment_meta::SegmentMeta;
+use strata_durability::format::WalSegment;
+use strata_durability::wal::WalReader;
+use strata_durability::{list_snapshots, ArchiveError, LocalWalArchive, ManifestManager};
+use tracing::info;
+
+use super::checkpoint::checkpoint_snapshot;
+use super::config::{StrataConfig, CONFIG_FILE_NAME};
+use super::encryption::{check_or_init_manifest, resolve_codec, write_synced};
+use super::{Database, PersistenceMode};
+
+/// Summary of a completed `Database::backup`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupInfo {
+    /// Directory the backup was written to
+    pub path: PathBuf,
+    /// Highest commit version included in the backup
+    pub version: u64,
+    /// Number of snapshots copied
+    pub snapshots: usize,
+    /// Number of WAL segments copied
+    pub wal_segments: usize,
+    /// Total bytes copied
+    pub bytes: u64,
+}
+
+/// Summary of a completed `Database::restore`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreInfo {
+    /// Directory the database was restored into
+    pub path: PathBuf,
+    /// Timestamp the restore stopped at (microseconds since epoch), if any
+    pub target_timestamp: Option<u64>,
+    /// Highest commit version in the restored database
+    pub version: u64,
+    /// Number of WAL transactions replayed on top of the checkpoint
+    pub txns_replayed: usize,
+    /// Number of WAL transactions after the target that were dropped
+    pub txns_dropped: usize,
+}
+
+impl Database {
+    /// Copy this database to `path` without stopping writes.
+    ///
+    /// The backup holds the latest checkpoint and the WAL written since, so
+    /// it can be opened directly or passed to [`Database::restore`] to
+    /// recover any point between that checkpoint and the backup. Commits
+    /// that finish after the backup starts are not included.
+    ///
+    /// Checkpoints and WAL compaction wait while the backup runs; commits do
+    /// not. `path` must not exist or must be an empty directory.
+    ///
+    /// # Errors
+    ///
+    /// Fails for databases without a write-ahead log (ephemeral databases
+    /// and `cache` durability).
+    pub fn backup<P: AsRef<Path>>(&self, path: P) -> StrataResult<BackupInfo> {
+        if self.persistence_mode == PersistenceMode::Ephemeral
+            || !self.durability_mode.requires_wal()
+        {
+            return Err(StrataError::invalid_input(
+                "Backup requires a database with a write-ahead log",
+            ));
+        }
+        let dest = path.as_ref();
+        prepare_destination(dest)?;
+
+        // Keep checkpoints and compaction from replacing snapshots or
+        // removing segments while they are copied
+        let _run = self.checkpoint_state.run_lock.lock();
+
+        // Everything committed at or below this version is in a segment no
+        // later than `last_segment`, within its first `last_segment_len` bytes
+        let (version, last_segment, last_segment_len) = {
+            let mut wal = self
+                .wal_writer
+                .as_ref()
+                .expect("disk databases have a WAL writer")
+                .lock();
+            wal.flush().map_err(StrataError::from)?;
+            (
+                self.coordinator.current_version(),
+                wal.current_segment(),
+                wal.current_segment_size(),
+            )
+        };
+
+        let mut bytes = 0u64;
+
+        // The MANIFEST records the first segment the checkpoint does not cover
+        let manifest_path = self.data_dir.join("MANIFEST");
+        let first_segment = if ManifestManager::exists(&manifest_path) {
+            let manifest = ManifestManager::load(manifest_path.clone())
+                .map_err(|e| StrataError::storage(format!("failed to load MANIFEST: {}", e)))?;
+            bytes += copy_synced(&manifest_path, &dest.join("MANIFEST"), None)?;
+            match manifest.manifest().snapshot_id {
+                Some(_) => manifest.manifest().active_wal_segment,
+                None => 0,
+            }
+        } else {
+            0
+        };
+
+        let config_path = self.data_dir.join(CONFIG_FILE_NAME);
+        if config_path.exists() {
+            bytes += copy_synced(&config_path, &dest.join(CONFIG_FILE_NAME), None)?;
+        }
+
+        let snapshots_dir = self.data_dir.join("snapshots");
+        let mut snapshots = 0usize;
+        if snapshots_dir.exists() {
+            std::fs::create_dir_all(dest.join("snapshots")).map_err(StrataError::from)?;
+            for (_, snap_path) in list_snapshots(&snapshots_dir).map_err(StrataError::from)? {
+                let target = dest.join("snapshots").join(file_name(&snap_path)?);
+                bytes += copy_synced(&snap_path, &target, None)?;
+                snapshots += 1;
+            }
+        }
+
+        let wal_dir = self.data_dir.join("wal");
+        let dest_wal = dest.join("wal");
+        std::fs::create_dir_all(&dest_wal).map_err(StrataError::from)?;
+        let reader = WalReader::new(self.codec.clone_box());
+        let segments = reader
+            .list_segments(&wal_dir)
+            .map_err(|e| StrataError::storage(format!("WAL read failed: {}", e)))?;
+        let mut wal_segments = 0usize;
+        for seg in segments
+            .into_iter()
+            .filter(|seg| (first_segment..=last_segment).contains(seg))
+        {
+            let seg_path = WalSegment::segment_path(&wal_dir, seg);
+            let limit = (seg == last_segment).then_some(last_segment_len);
+            bytes += copy_synced(&seg_path, &WalSegment::segment_path(&dest_wal, seg), limit)?;
+            // The active segment has no sidecar yet
+            let meta_path = SegmentMeta::meta_path(&wal_dir, seg);
+            if seg != last_segment && meta_path.exists() {
+                bytes += copy_synced(&meta_path, &SegmentMeta::meta_path(&dest_wal, seg), None)?;
+            }
+            wal_segments += 1;
+        }
+        sync_dir(&dest_wal)?;
+        sync_dir(dest)?;
+
+        let info = BackupInfo {
+            path: dest.to_path_buf(),
+            version,
+            snapshots,
+            wal_segments,
+            bytes,
+        };
+        info!(
+            target: "strata::db",
+            path = ?info.path,
+            version = info.version,
+            snapshots = info.snapshots,
+            wal_segments = info.wal_segments,
+            bytes = info.bytes,
+            "Backup created"
+        );
+        Ok(info)
+    }
+
+    /// Restore the backup in `backup` into a new database directory `path`.
+    ///
+    /// With `to` (microseconds since epoch), WAL replay stops before the
+    /// first commit made after that moment and the later commits are left
+    /// out of the restored WAL, so the database opens in its state at `to`.
+    /// Without it, everything in the backup is restored.
+    ///
+    /// With `wal_archive`, WAL segments archived after the backup was taken
+    /// are replayed too, so `to` can be any point up to the newest archived
+    /// commit. Archived segments replace the backup's copies of the same
+    /// segment and are checked against their archived CRC first.
+    ///
+    /// `key` is the backup's encryption key; when `None` it is resolved the
+    /// same way as for `open`, using the backup's `strata.toml`. The backup
+    /// and the archive are not modified.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `path` is not empty, if the backup cannot be decoded with
+    /// the key, if an archived segment is corrupt, if a WAL segment is
+    /// missing, or if `to` precedes the backup's checkpoint (those commits
+    /// cannot be undone). Nothing is left in `path` on failure.
+    pub fn restore<P: AsRef<Path>, Q: AsRef<Path>>(
+        backup: P,
+        path: Q,
+        to: Option<u64>,
+        wal_archive: Option<&Path>,
+        key: Option<&[u8]>,
+    ) -> StrataResult<RestoreInfo> {
+        let backup = backup.as_ref();
+        let dest = path.as_ref();
+        let wal_dir = backup.join("wal");
+        if !wal_dir.exists() {
+            return Err(StrataError::invalid_input(format!(
+                "no backup found at '{}'",
+                backup.display()
+            )));
+        }
+
+        let config_path = backup.join(CONFIG_FILE_NAME);
+        let cfg = if config_path.exists() {
+            StrataConfig::from_file(&config_path)?
+        } else {
+            StrataConfig::default()
+        };
+        let codec = resolve_codec(backup, &cfg, key)?;
+        check_or_init_manifest(backup, codec.as_ref())?;
+
+        let reader = WalReader::new(codec.clone_box());
+        let sources = wal_sources(&reader, &wal_dir, wal_archive)?;
+
+        let existed = dest.exists();
+        prepare_destination(dest)?;
+        let result = restore_into(backup, dest, &reader, codec.as_ref(), &sources, to);
+        if result.is_err() {
+            // Leave the destination as it was found
+            let _ = std::fs::remove_dir_all(dest);
+            if existed {
+                let _ = std::fs::create_dir_all(dest);
+            }
+        }
+        let (stats, txns_dropped) = result?;
+
+        let info = RestoreInfo {
+            path: dest.to_path_buf(),
+            target_timestamp: to,
+            version: stats.final_version,
+            txns_replayed: stats.txns_replayed,
+            txns_dropped,
+        };
+        info!(
+            target: "strata::db",
+            path = ?info.path,
+            target_timestamp = ?info.target_timestamp,
+            version = info.version,
+            txns_replayed = info.txns_replayed,
+            txns_dropped = info.txns_dropped,
+            "Database restored"
+        );
+        Ok(info)
+    }
+}
+
+/// Where a restored WAL segment is copied from.
+enum SegmentSource {
+    /// The backup's copy, with its `.meta` sidecar if it has one
+    Backup {
+        path: PathBuf,
+        meta: Option<PathBuf>,
+    },
+    /// A verified archived copy
+    Archive { path: PathBuf, meta: SegmentMeta },
+}
+
+/// Segments to restore, by number: the backup's, overridden and extended by
+/// the archive's.
+///
+/// # Errors
+///
+/// Fails if an archived segment does not verify, or if the segments are not
+/// contiguous from the backup's first segment on.
+fn wal_sources(
+    reader: &WalReader,
+    wal_dir: &Path,
+    wal_archive: Option<&Path>,
+) -> StrataResult<BTreeMap<u64, SegmentSource>> {
+    let mut sources = BTreeMap::new();
+    for seg in reader
+        .list_segments(wal_dir)
+        .map_err(|e| StrataError::storage(format!("WAL read failed: {}", e)))?
+    {
+        let meta = SegmentMeta::meta_path(wal_dir, seg);
+        sources.insert(
+            seg,
+            SegmentSource::Backup {
+                path: WalSegment::segment_path(wal_dir, seg),
+                meta: meta.exists().then_some(meta),
+            },
+        );
+    }
+
+    // The backup's newest segment was copied while still being written
+    let backup_last = sources.keys().next_back().copied();
+
+    if let Some(dir) = wal_archive {
+        let archive_error = |e: ArchiveError| StrataError::storage(e.to_string());
+        let archive = LocalWalArchive::open(dir).map_err(archive_error)?;
+        // Archived segments older than the backup are covered by its checkpoint
+        let first = sources.keys().next().copied().unwrap_or(0);
+        for entry in archive.entries().map_err(archive_error)? {
+            if entry.segment_number < first {
+                continue;
+            }
+            archive.verify_segment(&entry).map_err(archive_error)?;
+            sources.insert(
+                entry.segment_number,
+                SegmentSource::Archive {
+                    path: archive.segment_path(entry.segment_number),
+                    meta: entry.meta,
+                },
+            );
+        }
+    }
+
+    let mut expected = sources.keys().next().copied();
+    for &seg in sources.keys() {
+        if Some(seg) != expected {
+            return Err(StrataError::invalid_input(format!(
+                "WAL segment {} is missing from the backup and the WAL archive",
+                expected.unwrap_or(seg)
+            )));
+        }
+        expected = Some(seg + 1);
+    }
+
+    // Commits made to that segment after the backup are only in the archive
+    if let Some(seg) = backup_last {
+        let replaced = matches!(sources.get(&seg), Some(SegmentSource::Archive { .. }));
+        if !replaced && sources.keys().next_back() != Some(&seg) {
+            return Err(StrataError::invalid_input(format!(
+                "WAL segment {} in the backup is incomplete and is not in the WAL archive",
+                seg
+            )));
+        }
+    }
+    Ok(sources)
+}
+
+/// Copy the backup and `sources` into `dest`, validate them by recovering
+/// to `to`, then drop the WAL records after `to`.
+///
+/// Returns the recovery stats and the number of dropped transactions.
+fn restore_into(
+    backup: &Path,
+    dest: &Path,
+    reader: &WalReader,
+    codec: &dyn StorageCodec,
+    sources: &BTreeMap<u64, SegmentSource>,
+    to: Option<u64>,
+) -> StrataResult<(RecoveryStats, usize)> {
+    for name in ["MANIFEST", CONFIG_FILE_NAME] {
+        if backup.join(name).exists() {
+            copy_synced(&backup.join(name), &dest.join(name), None)?;
+        }
+    }
+    let snapshots_dir = backup.join("snapshots");
+    if snapshots_dir.exists() {
+        std::fs::create_dir_all(dest.join("snapshots")).map_err(StrataError::from)?;
+        for (_, snap_path) in list_snapshots(&snapshots_dir).map_err(StrataError::from)? {
+            let target = dest.join("snapshots").join(file_name(&snap_path)?);
+            copy_synced(&snap_path, &target, None)?;
+        }
+    }
+
+    let dest_wal = dest.join("wal");
+    std::fs::create_dir_all(&dest_wal).map_err(StrataError::from)?;
+    for (&seg, source) in sources {
+        match source {
+            SegmentSource::Backup { path, meta } => {
+                copy_synced(path, &WalSegment::segment_path(&dest_wal, seg), None)?;
+                if let Some(meta) = meta {
+                    copy_synced(meta, &SegmentMeta::meta_path(&dest_wal, seg), None)?;
+                }
+            }
+            SegmentSource::Archive { path, meta } => {
+                copy_synced(path, &WalSegment::segment_path(&dest_wal, seg), None)?;
+                meta.write_to_file(&dest_wal)
+                    .map_err(|e| StrataError::storage(format!("failed to write .meta: {}", e)))?;
+            }
+        }
+    }
+
+    // Recover the copy: this validates the target against the checkpoint
+    // and decodes every record up to it
+    let mut recovery = RecoveryCoordinator::new(dest_wal.clone()).with_codec(codec.clone_box());
+    if let Some((_, snapshot)) = checkpoint_snapshot(dest)? {
+        recovery = recovery.with_snapshot_path(snapshot);
+    }
+    if let Some(target) = to {
+        recovery = recovery.with_target_timestamp(target);
+    }
+    let result = recovery.recover()?;
+
+    // Cut the segment holding the first record after the target and
+    // remove the segments after it
+    let mut txns_dropped = 0usize;
+    if let Some(target) = to {
+        let mut cut = false;
+        for &seg in sources.keys() {
+            let (records, _, _, _) = reader
+                .read_segment(&dest_wal, seg)
+                .map_err(|e| StrataError::storage(format!("WAL read failed: {}", e)))?;
+            let keep = if cut {
+                0
+            } else {
+                records
+                    .iter()
+                    .position(|r| r.timestamp > target)
+                    .unwrap_or(records.len())
+            };
+            if keep == records.len() {
+                continue;
+            }
+            txns_dropped += records.len() - keep;
+            let seg_path = WalSegment::segment_path(&dest_wal, seg);
+            if cut {
+                std::fs::remove_file(&seg_path).map_err(StrataError::from)?;
+            } else {
+                let segment = WalSegment::open_read(&dest_wal, seg).map_err(StrataError::from)?;
+                let raw = std::fs::read(&seg_path).map_err(StrataError::from)?;
+                let mut bytes = raw[..segment.header_size()].to_vec();
+                for record in &records[..keep] {
+                    bytes.extend_from_slice(&record.to_encoded_bytes(codec));
+                }
+                write_synced(&seg_path, &bytes)?;
+                cut = true;
+            }
+            let meta_path = SegmentMeta::meta_path(&dest_wal, seg);
+            if meta_path.exists() {
+                std::fs::remove_file(&meta_path).map_err(StrataError::from)?;
+            }
+        }
+    }
+    sync_dir(&dest_wal)?;
+    sync_dir(dest)?;
+
+    Ok((result.stats, txns_dropped))
+}
+
+/// Create `dest`, failing if it exists and is not an empty directory.
+fn prepare_destination(dest: &Path) -> StrataResult<()> {
+    if dest.exists() {
+        let mut entries = std::fs::read_dir(dest).map_err(|e| {
+            StrataError::invalid_input(format!(
+                "cannot use '{}' as destination: {}",
+                dest.display(),
+                e
+            ))
+        })?;
+        if entries.next().is_some() {
+            return Err(StrataError::invalid_input(format!(
+                "destination '{}' is not empty",
+                dest.display()
+            )));
+        }
+    }
+    std::fs::create_dir_all(dest).map_err(StrataError::from)
+}
+
+/// Copy `src` to `dst` (at most `limit` bytes) and fsync; returns bytes copied.
+fn copy_synced(src: &Path, dst: &Path, limit: Option<u64>) -> StrataResult<u64> {
+    use std::io::Read;
+    let file = std::fs::File::open(src).map_err(StrataError::from)?;
+    let mut out = std::fs::File::create(dst).map_err(StrataError::from)?;
+    let copied = match limit {
+        Some(limit) => std::io::copy(&mut file.take(limit), &mut out),
+        None => std::io::copy(&mut { file }, &mut out),
+    }
+    .map_err(StrataError::from)?;
+    out.sync_all().map_err(StrataError::from)?;
+    Ok(copied)
+}
+
+/// Fsync a directory so newly created entries survive a crash.
+fn sync_dir(dir: &Path) -> StrataResult<()> {
+    #[cfg(unix)]
+    std::fs::File::open(dir)
+        .and_then(|d| d.sync_all())
+        .map_err(StrataError::from)?;
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+fn file_name(path: &Path) -> StrataResult<&std::ffi::OsStr> {
+    path.file_name()
+        .ok_or_else(|| StrataError::internal(format!("invalid path '{}'", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use strata_core::types::{BranchId, Key, Namespace};
+    use strata_core::value::Value;
+    use strata_core::Storage;
+    use strata_durability::now_micros;
+    use tempfile::TempDir;
+
+    fn test_ns(branch_id: BranchId) -> Namespace {
+        Namespace::new(
+            "tenant".to_string(),
+            "app".to_string(),
+            "agent".to_string(),
+            branch_id,
+            "default".to_string(),
+        )
+    }
+
+    fn put(db: &Database, ns: &Namespace, key: &str, value: i64) {
+        db.transaction(ns.branch_id, |txn| {
+            txn.put(Key::new_kv(ns.clone(), key), Value::Int(value))?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    fn get(db: &Database, ns: &Namespace, key: &str) -> Option<Value> {
+        db.storage()
+            .get(&Key::new_kv(ns.clone(), key))
+            .unwrap()
+            .map(|v| v.value)
+    }
+
+    /// Sleep so the commits before and after the returned time differ.
+    fn mark() -> u64 {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let ts = now_micros();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        ts
+    }
+
+    #[test]
+    fn test_backup_opens_with_same_state() {
+        let temp_dir = TempDir::new().unwrap();
+        let ns = test_ns(BranchId::new());
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+        put(&db, &ns, "a", 1);
+        db.checkpoint().unwrap();
+        put(&db, &ns, "b", 2);
+
+        let info = db.backup(temp_dir.path().join("backup")).unwrap();
+        assert_eq!(info.snapshots, 1);
+        assert!(info.wal_segments >= 1);
+
+        // Writes after the backup are not in it
+        put(&db, &ns, "c", 3);
+
+        let copy = Database::open(temp_dir.path().join("backup")).unwrap();
+        assert_eq!(get(&copy, &ns, "a"), Some(Value::Int(1)));
+        assert_eq!(get(&copy, &ns, "b"), Some(Value::Int(2)));
+        assert_eq!(get(&copy, &ns, "c"), None);
+    }
+
+    #[test]
+    fn test_backup_during_concurrent_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let ns = test_ns(BranchId::new());
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+
+        let writer = {
+            let db = Arc::clone(&db);
+            let ns = ns.clone();
+            std::thread::spawn(move || {
+                for i in 0..200 {
+                    put(&db, &ns, &format!("k{}", i), i);
+                }
+            })
+        };
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let info = db.backup(temp_dir.path().join("backup")).unwrap();
+        writer.join().unwrap();
+
+        // The copy holds exactly the commits up to the backup's version
+        let copy = Database::open(temp_dir.path().join("backup")).unwrap();
+        assert_eq!(copy.current_version(), info.version);
+        let present = (0..200)
+            .filter(|i| get(&copy, &ns, &format!("k{}", i)).is_some())
+            .count();
+        assert!((0..200)
+            .take(present)
+            .all(|i| get(&copy, &ns, &format!("k{}", i)) == Some(Value::Int(i))));
+    }
+
+    #[test]
+    fn test_backup_rejects_non_empty_destination() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+        let dest = temp_dir.path().join("backup");
+        std::fs::create_dir_all(&dest).unwrap();
+        std::fs::write(dest.join("file"), b"x").unwrap();
+
+        assert!(matches!(
+            db.backup(&dest),
+            Err(StrataError::InvalidInput { .. })
+        ));
+        assert!(Database::cache()
+            .unwrap()
+            .backup(temp_dir.path().join("other"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_restore_to_timestamp() {
+        let temp_dir = TempDir::new().unwrap();
+        let ns = test_ns(BranchId::new());
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+        put(&db, &ns, "k", 1);
+        db.checkpoint().unwrap();
+        put(&db, &ns, "k", 2);
+        let target = mark();
+        put(&db, &ns, "k", 3);
+        put(&db, &ns, "other", 4);
+        db.backup(temp_dir.path().join("backup")).unwrap();
+
+        let restored = temp_dir.path().join("restored");
+        let info = Database::restore(
+            temp_dir.path().join("backup"),
+            &restored,
+            Some(target),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(info.txns_replayed, 1);
+        assert_eq!(info.txns_dropped, 2);
+
+        let db = Database::open(&restored).unwrap();
+        assert_eq!(get(&db, &ns, "k"), Some(Value::Int(2)));
+        assert_eq!(get(&db, &ns, "other"), None);
+        assert_eq!(db.current_version(), info.version);
+
+        // New commits continue from the restored state
+        put(&db, &ns, "k", 5);
+        drop(db);
+        let db = Database::open(&restored).unwrap();
+        assert_eq!(get(&db, &ns, "k"), Some(Value::Int(5)));
+        assert_eq!(get(&db, &ns, "other"), None);
+    }
+
+    #[test]
+    fn test_restore_before_checkpoint_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let ns = test_ns(BranchId::new());
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+        put(&db, &ns, "k", 1);
+        let target = mark();
+        put(&db, &ns, "k", 2);
+        db.checkpoint().unwrap();
+        db.backup(temp_dir.path().join("backup")).unwrap();
+
+        let restored = temp_dir.path().join("restored");
+        let result = Database::restore(
+            temp_dir.path().join("backup"),
+            &restored,
+            Some(target),
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(StrataError::InvalidInput { .. })));
+        assert!(!restored.exists());
+    }
+
+    /// Database whose compactions archive into `<db>/archive`, with a
+    /// backup taken after its first checkpoint.
+    fn archived_db(temp_dir: &TempDir, ns: &Namespace) -> (PathBuf, u64) {
+        let db_path = temp_dir.path().join("db");
+        std::fs::create_dir_all(&db_path).unwrap();
+        std::fs::write(
+            db_path.join(CONFIG_FILE_NAME),
+            "wal_archive_dir = \"archive\"\n",
+        )
+        .unwrap();
+
+        {
+            let db = Database::open(&db_path).unwrap();
+            put(&db, ns, "k", 1);
+            db.checkpoint().unwrap();
+            db.backup(temp_dir.path().join("backup")).unwrap();
+            put(&db, ns, "k", 2);
+        }
+        let target = mark();
+        {
+            let db = Database::open(&db_path).unwrap();
+            put(&db, ns, "k", 3);
+        }
+
+        // Start a second segment, as rotation would, so the first can be compacted
+        let wal_dir = db_path.canonicalize().unwrap().join("wal");
+        WalSegment::create(&wal_dir, 2, [0u8; 16]).unwrap();
+
+        let db = Database::open(&db_path).unwrap();
+        put(&db, ns, "other", 4);
+        db.checkpoint().unwrap();
+        db.compact().unwrap();
+        assert!(!WalSegment::segment_path(&wal_dir, 1).exists());
+        assert_eq!(db.durability_counters().unwrap().wal_segments_archived, 1);
+
+        (db_path.join("archive"), target)
+    }
+
+    #[test]
+    fn test_restore_past_backup_from_wal_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let ns = test_ns(BranchId::new());
+        let (archive, target) = archived_db(&temp_dir, &ns);
+
+        let restored = temp_dir.path().join("restored");
+        let info = Database::restore(
+            temp_dir.path().join("backup"),
+            &restored,
+            Some(target),
+            Some(&archive),
+            None,
+        )
+        .unwrap();
+        assert_eq!(info.txns_replayed, 1);
+        assert_eq!(info.txns_dropped, 1);
+
+        let db = Database::open(&restored).unwrap();
+        assert_eq!(get(&db, &ns, "k"), Some(Value::Int(2)));
+        assert_eq!(get(&db, &ns, "other"), None);
+    }
+
+    #[test]
+    fn test_restore_rejects_corrupt_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let ns = test_ns(BranchId::new());
+        let (archive, target) = archived_db(&temp_dir, &ns);
+
+        let segment = WalSegment::segment_path(&archive, 1);
+        let mut data = std::fs::read(&segment).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 0xff;
+        std::fs::write(&segment, data).unwrap();
+
+        let restored = temp_dir.path().join("restored");
+        let result = Database::restore(
+            temp_dir.path().join("backup"),
+            &restored,
+            Some(target),
+            Some(&archive),
+            None,
+        );
+        assert!(matches!(result, Err(StrataError::Storage { .. })));
+        assert!(!restored.exists());
+    }
+}
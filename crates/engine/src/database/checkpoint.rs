@@ -0,0 +1,231 @@
+//! Automatic checkpointing
+//!
+//! With a `[checkpoint]` policy in `strata.toml`, a `strata-checkpoint`
+//! thread watches how much WAL has been written and how long it has been
+//! since the last checkpoint. When a threshold is reached it submits a
+//! checkpoint, followed by WAL compaction, to the background scheduler, so
+//! long-running hosts keep a bounded WAL and a bounded restart replay.
+//!
+//! The thread holds only a weak reference to the database and exits once
+//! the database is dropped or shut down.
+//!
+//! Checkpoints are incremental: after a full snapshot, each checkpoint only
+//! writes the versions committed since the previous one and chains back to
+//! the full snapshot. Once the chain holds `max_deltas` incremental
+//! snapshots, the next checkpoint consolidates it into a new full snapshot.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex as ParkingMutex;
+use strata_concurrency::RecoveryStats;
+use strata_core::{StrataError, StrataResult};
+use strata_durability::format::snapshot_path;
+use strata_durability::{ManifestManager, WalArchiver, WalCounters};
+use tracing::warn;
+
+use super::Database;
+use crate::background::TaskPriority;
+
+/// How often the checkpoint thread re-evaluates the policy.
+const POLICY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Snapshots that make up the latest checkpoint.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct CheckpointChain {
+    /// Highest commit version covered by the chain
+    pub(crate) version: u64,
+    /// Snapshot IDs, full snapshot first (empty before the first checkpoint)
+    pub(crate) snapshots: Vec<u64>,
+}
+
+/// Checkpoint bookkeeping shared by manual and automatic checkpoints.
+#[derive(Default)]
+pub(crate) struct CheckpointState {
+    /// Serializes checkpoint and compaction runs
+    pub(crate) run_lock: ParkingMutex<()>,
+    /// Set while an automatic checkpoint is queued or running
+    pending: AtomicBool,
+    /// Set by `Database::shutdown` to stop the policy thread
+    stopped: AtomicBool,
+    /// Policy thread handle (None unless a policy was configured at open)
+    handle: ParkingMutex<Option<JoinHandle<()>>>,
+    /// Chain the next incremental checkpoint builds on
+    pub(crate) chain: ParkingMutex<CheckpointChain>,
+    /// Archiver set with `Database::set_wal_archiver`
+    pub(crate) archiver: ParkingMutex<Option<Arc<dyn WalArchiver>>>,
+    checkpoints: AtomicU64,
+    compactions: AtomicU64,
+    wal_segments_removed: AtomicU64,
+    wal_bytes_reclaimed: AtomicU64,
+    wal_segments_archived: AtomicU64,
+}
+
+impl CheckpointState {
+    /// Resume the chain of the checkpoint recovery started from.
+    pub(crate) fn restore_chain(&self, stats: &RecoveryStats, snapshot_id: Option<u64>) {
+        if let (true, Some(id)) = (stats.from_checkpoint, snapshot_id) {
+            let mut snapshots = stats.checkpoint_parents.clone();
+            snapshots.push(id);
+            *self.chain.lock() = CheckpointChain {
+                version: stats.checkpoint_version,
+                snapshots,
+            };
+        }
+    }
+
+    /// Count a completed checkpoint.
+    pub(crate) fn record_checkpoint(&self) {
+        self.checkpoints.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a completed compaction.
+    pub(crate) fn record_compaction(
+        &self,
+        segments_removed: u64,
+        bytes_reclaimed: u64,
+        segments_archived: u64,
+    ) {
+        self.compactions.fetch_add(1, Ordering::Relaxed);
+        self.wal_segments_archived
+            .fetch_add(segments_archived, Ordering::Relaxed);
+        self.wal_segments_removed
+            .fetch_add(segments_removed, Ordering::Relaxed);
+        self.wal_bytes_reclaimed
+            .fetch_add(bytes_reclaimed, Ordering::Relaxed);
+    }
+
+    /// Copy the checkpoint and compaction counters into `counters`.
+    pub(crate) fn fill_counters(&self, counters: &mut WalCounters) {
+        counters.checkpoints = self.checkpoints.load(Ordering::Relaxed);
+        counters.compactions = self.compactions.load(Ordering::Relaxed);
+        counters.wal_segments_removed = self.wal_segments_removed.load(Ordering::Relaxed);
+        counters.wal_bytes_reclaimed = self.wal_bytes_reclaimed.load(Ordering::Relaxed);
+        counters.wal_segments_archived = self.wal_segments_archived.load(Ordering::Relaxed);
+    }
+
+    /// Stop the policy thread and wait for it to exit.
+    ///
+    /// Does not wait when called from the policy thread itself (the thread
+    /// can hold the last strong reference and run the database's drop).
+    pub(crate) fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        let handle = self.handle.lock().take();
+        if let Some(handle) = handle {
+            if handle.thread().id() != std::thread::current().id() {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+/// ID and path of the snapshot referenced by the MANIFEST in `data_dir`, if any.
+pub(crate) fn checkpoint_snapshot(data_dir: &Path) -> StrataResult<Option<(u64, PathBuf)>> {
+    let manifest_path = data_dir.join("MANIFEST");
+    if !ManifestManager::exists(&manifest_path) {
+        return Ok(None);
+    }
+    let manifest = ManifestManager::load(manifest_path)
+        .map_err(|e| StrataError::storage(format!("failed to load MANIFEST: {}", e)))?;
+    Ok(manifest
+        .manifest()
+        .snapshot_id
+        .map(|id| (id, snapshot_path(&data_dir.join("snapshots"), id))))
+}
+
+/// Start the policy thread if `db` has a checkpoint policy.
+pub(crate) fn start_policy_thread(db: &Arc<Database>) -> StrataResult<()> {
+    let enabled = db
+        .config
+        .read()
+        .checkpoint
+        .as_ref()
+        .is_some_and(|policy| policy.is_enabled());
+    if !enabled || !db.durability_mode.requires_wal() {
+        return Ok(());
+    }
+
+    let weak = Arc::downgrade(db);
+    let handle = std::thread::Builder::new()
+        .name("strata-checkpoint".to_string())
+        .spawn(move || policy_loop(weak))
+        .map_err(|e| StrataError::internal(format!("failed to spawn checkpoint thread: {}", e)))?;
+    *db.checkpoint_state.handle.lock() = Some(handle);
+    Ok(())
+}
+
+fn policy_loop(weak: Weak<Database>) {
+    let mut last_bytes = 0u64;
+    let mut last_time = Instant::now();
+
+    loop {
+        std::thread::sleep(POLICY_POLL_INTERVAL);
+        let db = match weak.upgrade() {
+            Some(db) => db,
+            None => return,
+        };
+        let state = &db.checkpoint_state;
+        if state.stopped.load(Ordering::SeqCst) {
+            return;
+        }
+        if state.pending.load(Ordering::SeqCst) {
+            continue;
+        }
+
+        // The policy is re-read every tick so config changes take effect
+        let policy = match db.config.read().checkpoint.clone() {
+            Some(policy) => policy,
+            None => continue,
+        };
+        let bytes = db
+            .durability_counters()
+            .map(|c| c.bytes_written)
+            .unwrap_or(0);
+        let written = bytes.saturating_sub(last_bytes);
+        let size_due = policy
+            .wal_size_mb
+            .is_some_and(|mb| written >= mb.saturating_mul(1024 * 1024));
+        let time_due = policy
+            .interval_secs
+            .is_some_and(|secs| written > 0 && last_time.elapsed() >= Duration::from_secs(secs));
+        if !size_due && !time_due {
+            continue;
+        }
+
+        last_bytes = bytes;
+        last_time = Instant::now();
+        state.pending.store(true, Ordering::SeqCst);
+        let task_db = Weak::clone(&weak);
+        let submitted = db.scheduler.submit(TaskPriority::Low, move || {
+            if let Some(db) = task_db.upgrade() {
+                db.run_auto_checkpoint(policy.compact);
+                db.checkpoint_state.pending.store(false, Ordering::SeqCst);
+            }
+        });
+        if submitted.is_err() {
+            // Queue full or shutting down; retry on a later tick
+            state.pending.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+impl Database {
+    /// Run one automatic checkpoint and, if requested, WAL compaction.
+    ///
+    /// Failures are logged; the policy thread tries again at the next
+    /// threshold.
+    fn run_auto_checkpoint(&self, compact: bool) {
+        if let Err(e) = self.checkpoint() {
+            warn!(target: "strata::db", error = %e, "Automatic checkpoint failed");
+            return;
+        }
+        if compact {
+            if let Err(e) = self.compact() {
+                warn!(target: "strata::db", error = %e, "Automatic WAL compaction failed");
+            }
+        }
+    }
+}
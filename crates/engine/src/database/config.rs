@@ -7,6 +7,7 @@
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use strata_core::{StrataError, StrataResult};
+use strata_durability::codec::{Lz4Codec, StorageCodec};
 use strata_durability::wal::DurabilityMode;
 
 // ============================================================================
@@ -48,6 +49,71 @@ fn default_timeout_ms() -> u64 {
     5000
 }
 
+/// Automatic checkpoint policy.
+///
+/// A checkpoint runs when either threshold is reached and, unless `compact`
+/// is false, is followed by WAL compaction. With neither threshold set,
+/// checkpoints only run on request. `max_deltas` also applies to manual
+/// checkpoints. Persisted in `strata.toml` under the `[checkpoint]` section.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CheckpointConfig {
+    /// Checkpoint once this many megabytes of WAL were written since the last one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wal_size_mb: Option<u64>,
+    /// Checkpoint this many seconds after the last one, if anything was written
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_secs: Option<u64>,
+    /// Compact the WAL after each automatic checkpoint (default: true)
+    #[serde(default = "default_true")]
+    pub compact: bool,
+    /// Incremental checkpoints chained to a full one before the next
+    /// checkpoint is full again (default: 8, 0 = always full)
+    #[serde(default = "default_max_deltas")]
+    pub max_deltas: u32,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_deltas() -> u32 {
+    8
+}
+
+impl Default for CheckpointConfig {
+    fn default() -> Self {
+        Self {
+            wal_size_mb: None,
+            interval_secs: None,
+            compact: true,
+            max_deltas: default_max_deltas(),
+        }
+    }
+}
+
+impl CheckpointConfig {
+    /// Whether any automatic trigger is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.wal_size_mb.is_some() || self.interval_secs.is_some()
+    }
+
+    /// Reject zero thresholds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `wal_size_mb` or `interval_secs` is 0.
+    pub fn validate(&self) -> StrataResult<()> {
+        if self.wal_size_mb == Some(0) || self.interval_secs == Some(0) {
+            return Err(StrataError::invalid_input(
+                "Invalid [checkpoint] in strata.toml: wal_size_mb and interval_secs must be \
+                 greater than 0."
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Database configuration loaded from `strata.toml`.
 ///
 /// # Example
@@ -85,12 +151,37 @@ pub struct StrataConfig {
     /// Default: 0.4 (Anserini/Pyserini BEIR standard).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bm25_b: Option<f32>,
+    /// Path to the encryption key file (32 raw bytes or 64 hex characters).
+    /// When set, WAL records, snapshots and branch bundles are encrypted
+    /// with AES-256-GCM. The key itself is never written to `strata.toml`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption_key_file: Option<String>,
+    /// Compression for WAL records and snapshots: `"none"` or `"lz4"`.
+    /// Applied before encryption. Fixed at creation; recorded in the MANIFEST.
+    #[serde(default = "default_compression_str")]
+    pub compression: String,
+    /// Optional automatic checkpoint and WAL compaction policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<CheckpointConfig>,
+    /// Directory that WAL segments are archived to before compaction
+    /// removes them. Relative paths are resolved against the data directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wal_archive_dir: Option<String>,
+    /// Memory budget for stored values, in MiB. Beyond it, old versions and
+    /// cold keys are paged out to disk. Unset keeps everything in memory.
+    /// Applied when the database is opened.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_budget_mb: Option<u64>,
 }
 
 fn default_durability_str() -> String {
     "standard".to_string()
 }
 
+fn default_compression_str() -> String {
+    "none".to_string()
+}
+
 impl Default for StrataConfig {
     fn default() -> Self {
         Self {
@@ -100,6 +191,11 @@ impl Default for StrataConfig {
             embed_batch_size: None,
             bm25_k1: None,
             bm25_b: None,
+            encryption_key_file: None,
+            compression: default_compression_str(),
+            checkpoint: None,
+            wal_archive_dir: None,
+            memory_budget_mb: None,
         }
     }
 }
@@ -133,6 +229,28 @@ impl StrataConfig {
         }
     }
 
+    /// Build the compression codec, if compression is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not `"none"` or `"lz4"`.
+    pub fn compression_codec(&self) -> StrataResult<Option<Box<dyn StorageCodec>>> {
+        match self.compression.as_str() {
+            "none" => Ok(None),
+            "lz4" => Ok(Some(Box::new(Lz4Codec))),
+            other => Err(StrataError::invalid_input(format!(
+                "Invalid compression '{}' in strata.toml. Expected \"none\" or \"lz4\".",
+                other
+            ))),
+        }
+    }
+
+    /// Memory budget in bytes, if one is configured.
+    pub fn memory_budget(&self) -> Option<usize> {
+        self.memory_budget_mb
+            .map(|mb| usize::try_from(mb.saturating_mul(1 << 20)).unwrap_or(usize::MAX))
+    }
+
     /// Returns the default config file content with comments.
     pub fn default_toml() -> &'static str {
         r#"# Strata database configuration
@@ -156,6 +274,35 @@ auto_embed = false
 # bm25_k1 = 0.9
 # bm25_b = 0.4
 
+# Encryption at rest (AES-256-GCM). Path to a file holding a 32-byte key as
+# raw bytes or 64 hex characters. The STRATA_ENCRYPTION_KEY (hex) and
+# STRATA_ENCRYPTION_KEY_FILE environment variables take precedence.
+# encryption_key_file = "/path/to/strata.key"
+
+# Compression for WAL records and snapshots: "none" (default) or "lz4".
+# Applied before encryption. Fixed when the database is created: reopening
+# with a different setting fails with a codec mismatch.
+compression = "none"
+
+# Automatic checkpointing. A checkpoint writes the full state to a snapshot
+# so restarts only replay the WAL written after it; WAL segments it covers
+# are then removed. Triggers when either threshold is reached.
+# [checkpoint]
+# wal_size_mb = 64        # after this much WAL since the last checkpoint
+# interval_secs = 600     # after this long, if anything was written
+# compact = true          # remove covered WAL segments (default: true)
+# max_deltas = 8          # incremental checkpoints between full ones (0 = always full)
+
+# WAL archiving. Segments are copied here before compaction removes them, so
+# a backup plus the archive can be restored to any later point in time.
+# Relative paths are resolved against the data directory.
+# wal_archive_dir = "/path/to/wal-archive"
+
+# Memory budget for stored values, in MiB. When values and old versions
+# exceed it, old versions and cold keys are paged out to the spill/ directory
+# and read back on demand. Keys always stay in memory. Unset = no limit.
+# memory_budget_mb = 1024
+
 # Model configuration for query expansion and re-ranking.
 # Uncomment and configure to enable intelligent search features.
 # [model]
@@ -186,8 +333,17 @@ auto_embed = false
                 e
             ))
         })?;
-        // Validate the durability value eagerly
+        // Validate the durability, compression and checkpoint values eagerly
         config.durability_mode()?;
+        config.compression_codec()?;
+        if let Some(checkpoint) = &config.checkpoint {
+            checkpoint.validate()?;
+        }
+        if config.memory_budget_mb == Some(0) {
+            return Err(StrataError::invalid_input(
+                "Invalid memory_budget_mb in strata.toml: must be greater than 0.".to_string(),
+            ));
+        }
         Ok(config)
     }
 
@@ -255,6 +411,58 @@ mod tests {
         assert!(config.durability_mode().is_err());
     }
 
+    #[test]
+    fn parse_compression() {
+        let config: StrataConfig = toml::from_str("compression = \"lz4\"").unwrap();
+        let codec = config.compression_codec().unwrap().unwrap();
+        assert_eq!(codec.codec_id(), "lz4");
+
+        let config: StrataConfig = toml::from_str("durability = \"always\"").unwrap();
+        assert!(config.compression_codec().unwrap().is_none());
+
+        let config: StrataConfig = toml::from_str("compression = \"zip\"").unwrap();
+        assert!(config.compression_codec().is_err());
+    }
+
+    #[test]
+    fn parse_checkpoint_policy() {
+        let config: StrataConfig = toml::from_str(
+            r#"
+[checkpoint]
+wal_size_mb = 32
+"#,
+        )
+        .unwrap();
+        let checkpoint = config.checkpoint.unwrap();
+        assert_eq!(checkpoint.wal_size_mb, Some(32));
+        assert_eq!(checkpoint.interval_secs, None);
+        assert!(checkpoint.compact);
+        assert_eq!(checkpoint.max_deltas, 8);
+        assert!(checkpoint.is_enabled());
+        assert!(checkpoint.validate().is_ok());
+
+        let config: StrataConfig = toml::from_str("[checkpoint]\ninterval_secs = 0").unwrap();
+        assert!(config.checkpoint.unwrap().validate().is_err());
+
+        assert!(!CheckpointConfig::default().is_enabled());
+        assert!(StrataConfig::default().checkpoint.is_none());
+    }
+
+    #[test]
+    fn parse_memory_budget() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+
+        std::fs::write(&path, "memory_budget_mb = 256\n").unwrap();
+        let config = StrataConfig::from_file(&path).unwrap();
+        assert_eq!(config.memory_budget(), Some(256 << 20));
+
+        std::fs::write(&path, "memory_budget_mb = 0\n").unwrap();
+        assert!(StrataConfig::from_file(&path).is_err());
+
+        assert_eq!(StrataConfig::default().memory_budget(), None);
+    }
+
     #[test]
     fn default_toml_parses_correctly() {
         let config: StrataConfig = toml::from_str(StrataConfig::default_toml()).unwrap();
@@ -315,6 +523,11 @@ mod tests {
             embed_batch_size: None,
             bm25_k1: None,
             bm25_b: None,
+            encryption_key_file: None,
+            compression: "none".to_string(),
+            checkpoint: None,
+            wal_archive_dir: None,
+            memory_budget_mb: None,
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -338,6 +551,11 @@ mod tests {
             embed_batch_size: None,
             bm25_k1: None,
             bm25_b: None,
+            encryption_key_file: None,
+            compression: "none".to_string(),
+            checkpoint: None,
+            wal_archive_dir: None,
+            memory_budget_mb: None,
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -393,6 +611,11 @@ model = "qwen3:1.7b"
             embed_batch_size: None,
             bm25_k1: None,
             bm25_b: None,
+            encryption_key_file: None,
+            compression: "none".to_string(),
+            checkpoint: None,
+            wal_archive_dir: None,
+            memory_budget_mb: None,
         };
 
         config.write_to_file(&path).unwrap();
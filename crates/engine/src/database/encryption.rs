@@ -0,0 +1,755 @@
+//! Encryption and compression at rest
+//!
+//! Resolves the database storage codec from the `compression` setting and
+//! the encryption key, verifies it against the MANIFEST on open, and
+//! implements the offline `rekey` operation.
+//!
+//! With both enabled the codec is a chain, compression first
+//! (`lz4+aes-gcm-256`).
+//!
+//! ## Key sources
+//!
+//! In order of precedence:
+//!
+//! 1. A key passed explicitly at open (`Database::open_with_key`); the
+//!    executor's `OpenOptions` key and key file are passed this way
+//! 2. `STRATA_ENCRYPTION_KEY` — the key as 64 hex characters
+//! 3. `STRATA_ENCRYPTION_KEY_FILE` — path to a key file
+//! 4. `encryption_key_file` in `strata.toml`
+//!
+//! Key files hold 32 raw bytes or 64 hex characters. Without a key the
+//! database uses the identity codec (no encryption).
+//!
+//! ## Key verification
+//!
+//! An encrypted or compressed database always has a MANIFEST recording the
+//! codec id and a key check (a fixed plaintext encoded with the codec).
+//! Opening with a different codec chain fails with a `CodecMismatch`, and
+//! opening with the wrong key fails with a `DecodeError`, before any WAL
+//! record is read.
+
+use std::path::{Path, PathBuf};
+
+use strata_core::{StrataError, StrataResult};
+use strata_durability::codec::{
+    get_codec, make_key_check, verify_key_check, ChainedCodec, AES_GCM_CODEC_ID,
+    CODEC_CHAIN_SEPARATOR,
+};
+use strata_durability::format::WalSegment;
+use strata_durability::wal::WalReader;
+use strata_durability::{
+    list_snapshots, AesGcmCodec, CodecError, DiskSnapshotReader, DiskSnapshotWriter, IdentityCodec,
+    ManifestManager, SnapshotSection, StorageCodec,
+};
+use tracing::{info, warn};
+
+use super::config::StrataConfig;
+use super::Database;
+
+/// Environment variable holding the encryption key (64 hex characters).
+pub const ENCRYPTION_KEY_ENV: &str = "STRATA_ENCRYPTION_KEY";
+
+/// Environment variable holding the path to the encryption key file.
+pub const ENCRYPTION_KEY_FILE_ENV: &str = "STRATA_ENCRYPTION_KEY_FILE";
+
+/// Summary of a completed `Database::rekey`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RekeyInfo {
+    /// Codec the database was written with before the rekey
+    pub old_codec: String,
+    /// Codec the database is written with after the rekey
+    pub new_codec: String,
+    /// Number of WAL segments rewritten
+    pub wal_segments: usize,
+    /// Number of WAL records re-encoded
+    pub wal_records: usize,
+    /// Number of snapshots rewritten
+    pub snapshots: usize,
+}
+
+/// Build a codec from key material; `None` means no encryption.
+pub(crate) fn codec_from_key(key: Option<&[u8]>) -> StrataResult<Box<dyn StorageCodec>> {
+    match key {
+        Some(material) => AesGcmCodec::from_key_material(material)
+            .map(|codec| Box::new(codec) as Box<dyn StorageCodec>)
+            .map_err(|e| StrataError::invalid_input(e.to_string())),
+        None => Ok(Box::new(IdentityCodec)),
+    }
+}
+
+/// Build the storage codec: optional compression, then optional encryption.
+fn build_codec(
+    compression: Option<Box<dyn StorageCodec>>,
+    key: Option<&[u8]>,
+) -> StrataResult<Box<dyn StorageCodec>> {
+    let encryption = match key {
+        Some(_) => Some(codec_from_key(key)?),
+        None => None,
+    };
+    Ok(match (compression, encryption) {
+        (Some(c), Some(e)) => Box::new(ChainedCodec::new(vec![c, e])),
+        (Some(c), None) => c,
+        (None, Some(e)) => e,
+        (None, None) => Box::new(IdentityCodec),
+    })
+}
+
+/// The compression stage recorded in the MANIFEST, if any.
+///
+/// Rekeying changes only the encryption stage, so the compression stage is
+/// carried over from the recorded codec chain.
+fn recorded_compression(data_dir: &Path) -> StrataResult<Option<Box<dyn StorageCodec>>> {
+    let manifest_path = data_dir.join("MANIFEST");
+    if !ManifestManager::exists(&manifest_path) {
+        return Ok(None);
+    }
+    let manifest = ManifestManager::load(manifest_path)
+        .map_err(|e| StrataError::storage(format!("failed to load MANIFEST: {}", e)))?;
+    let first = manifest
+        .manifest()
+        .codec_id
+        .split(CODEC_CHAIN_SEPARATOR)
+        .next()
+        .unwrap_or("identity")
+        .to_string();
+    if first == "identity" || first == AES_GCM_CODEC_ID {
+        return Ok(None);
+    }
+    get_codec(&first)
+        .map(Some)
+        .map_err(|e| StrataError::storage(format!("cannot rekey: {}", e)))
+}
+
+/// The environment variable supplying the encryption key, if one is set.
+///
+/// Environment keys take precedence over `strata.toml`, so while one is set
+/// a key change recorded in the config has no effect.
+pub fn env_key_var() -> Option<&'static str> {
+    [ENCRYPTION_KEY_ENV, ENCRYPTION_KEY_FILE_ENV]
+        .into_iter()
+        .find(|var| std::env::var(var).is_ok_and(|value| !value.is_empty()))
+}
+
+/// Resolve the configured encryption key for a database, if any.
+///
+/// Checks the environment first, then `encryption_key_file` in `cfg`
+/// (relative paths are resolved against `data_dir`). Does not consider
+/// explicitly passed keys.
+pub fn configured_key(data_dir: &Path, cfg: &StrataConfig) -> StrataResult<Option<Vec<u8>>> {
+    if let Ok(hex) = std::env::var(ENCRYPTION_KEY_ENV) {
+        if !hex.is_empty() {
+            return Ok(Some(hex.into_bytes()));
+        }
+    }
+
+    let key_file = std::env::var(ENCRYPTION_KEY_FILE_ENV)
+        .ok()
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| cfg.encryption_key_file.as_ref().map(|p| data_dir.join(p)));
+
+    key_file.map(|path| read_key_file(&path)).transpose()
+}
+
+/// Read an encryption key file (32 raw bytes or 64 hex characters).
+pub fn read_key_file(path: &Path) -> StrataResult<Vec<u8>> {
+    std::fs::read(path).map_err(|e| {
+        StrataError::invalid_input(format!(
+            "cannot read encryption key file '{}': {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Resolve the codec for opening a database.
+///
+/// Compression comes from `cfg`. An explicit key wins over environment and
+/// config key sources.
+pub(crate) fn resolve_codec(
+    data_dir: &Path,
+    cfg: &StrataConfig,
+    explicit_key: Option<&[u8]>,
+) -> StrataResult<Box<dyn StorageCodec>> {
+    let compression = cfg.compression_codec()?;
+    match explicit_key {
+        Some(key) => build_codec(compression, Some(key)),
+        None => build_codec(compression, configured_key(data_dir, cfg)?.as_deref()),
+    }
+}
+
+/// Convert a codec error raised while opening into a `StrataError`.
+fn open_error(e: CodecError) -> StrataError {
+    let hint = match e {
+        CodecError::CodecMismatch { .. } => {
+            " (check `compression` in strata.toml and supply the encryption key via \
+             OpenOptions, STRATA_ENCRYPTION_KEY, STRATA_ENCRYPTION_KEY_FILE or \
+             encryption_key_file in strata.toml)"
+        }
+        CodecError::DecodeError { .. } => " (wrong encryption key?)",
+        _ => "",
+    };
+    StrataError::storage(format!("cannot open database: {}{}", e, hint))
+}
+
+/// Whether the WAL directory holds any record bytes.
+fn wal_has_records(wal_dir: &Path) -> StrataResult<bool> {
+    if !wal_dir.exists() {
+        return Ok(false);
+    }
+    let reader = WalReader::new(Box::new(IdentityCodec));
+    let segments = reader
+        .list_segments(wal_dir)
+        .map_err(|e| StrataError::storage(format!("WAL read failed: {}", e)))?;
+    for seg in segments {
+        let segment = WalSegment::open_read(wal_dir, seg).map_err(StrataError::from)?;
+        if segment.size() > segment.header_size() as u64 {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Verify `codec` against the MANIFEST in `data_dir`.
+///
+/// - MANIFEST present: the codec id must match and the key check must decode.
+/// - No MANIFEST, identity codec: nothing to check (legacy layout).
+/// - No MANIFEST, other codec: existing WAL records are plaintext, so this
+///   is a `CodecMismatch`; a fresh database gets a MANIFEST recording the
+///   codec and key check.
+pub(crate) fn check_or_init_manifest(
+    data_dir: &Path,
+    codec: &dyn StorageCodec,
+) -> StrataResult<()> {
+    let manifest_path = data_dir.join("MANIFEST");
+
+    if ManifestManager::exists(&manifest_path) {
+        let manifest = ManifestManager::load(manifest_path)
+            .map_err(|e| StrataError::storage(format!("failed to load MANIFEST: {}", e)))?;
+        let m = manifest.manifest();
+        if m.codec_id != codec.codec_id() {
+            return Err(open_error(CodecError::CodecMismatch {
+                expected: m.codec_id.clone(),
+                actual: codec.codec_id().to_string(),
+            }));
+        }
+        return verify_key_check(codec, &m.key_check).map_err(open_error);
+    }
+
+    if codec.codec_id() == "identity" {
+        return Ok(());
+    }
+
+    if wal_has_records(&data_dir.join("wal"))? {
+        return Err(open_error(CodecError::CodecMismatch {
+            expected: "identity".to_string(),
+            actual: codec.codec_id().to_string(),
+        }));
+    }
+
+    create_manifest(data_dir, codec)
+}
+
+/// Create a MANIFEST recording `codec` and its key check.
+fn create_manifest(data_dir: &Path, codec: &dyn StorageCodec) -> StrataResult<()> {
+    let mut manifest = ManifestManager::create(
+        data_dir.join("MANIFEST"),
+        [0u8; 16],
+        codec.codec_id().to_string(),
+    )
+    .map_err(|e| StrataError::storage(format!("failed to create MANIFEST: {}", e)))?;
+    manifest
+        .set_codec(codec.codec_id().to_string(), make_key_check(codec))
+        .map_err(|e| StrataError::storage(format!("failed to persist MANIFEST: {}", e)))?;
+    Ok(())
+}
+
+/// Whether two codecs encode identically (same codec and key).
+pub(crate) fn same_codec(a: &dyn StorageCodec, b: &dyn StorageCodec) -> bool {
+    a.codec_id() == b.codec_id() && verify_key_check(b, &make_key_check(a)).is_ok()
+}
+
+impl Database {
+    /// Rewrite a closed database under a new encryption key.
+    ///
+    /// Re-encodes every WAL record and snapshot from `old_key` to `new_key`
+    /// and updates the MANIFEST. `None` means unencrypted on either side, so
+    /// this also encrypts a plaintext database or decrypts an encrypted one.
+    /// The compression stage recorded in the MANIFEST is kept.
+    ///
+    /// The database must not be open: the rekey takes the same exclusive
+    /// lock as `open`. `strata.toml` is left unchanged.
+    ///
+    /// Files are rewritten one at a time (write to a temporary file, then
+    /// rename). An interrupted rekey can leave files under both keys, so
+    /// take a backup first.
+    ///
+    /// # Errors
+    ///
+    /// Fails without modifying anything if `old_key` does not match the
+    /// database, or if a WAL record or snapshot cannot be decoded.
+    pub fn rekey<P: AsRef<Path>>(
+        path: P,
+        old_key: Option<&[u8]>,
+        new_key: Option<&[u8]>,
+    ) -> StrataResult<RekeyInfo> {
+        let data_dir = path.as_ref();
+        let wal_dir = data_dir.join("wal");
+        let manifest_path = data_dir.join("MANIFEST");
+        if !wal_dir.exists() && !ManifestManager::exists(&manifest_path) {
+            return Err(StrataError::invalid_input(format!(
+                "no database found at '{}'",
+                data_dir.display()
+            )));
+        }
+
+        // Same exclusive lock as open(): refuse to rekey a live database
+        let lock_file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(data_dir.join(".lock"))
+            .map_err(|e| StrataError::storage(format!("failed to open lock file: {}", e)))?;
+        fs2::FileExt::try_lock_exclusive(&lock_file).map_err(|_| {
+            StrataError::storage(format!(
+                "database at '{}' is in use; close it before rekeying",
+                data_dir.display()
+            ))
+        })?;
+
+        let compression = recorded_compression(data_dir)?;
+        let old = build_codec(compression.as_ref().map(|c| c.clone_box()), old_key)?;
+        let new = build_codec(compression, new_key)?;
+        check_or_init_manifest(data_dir, old.as_ref())?;
+
+        // Decode everything first so a bad record aborts before any write
+        let reader = WalReader::new(old.clone_box());
+        let segments = if wal_dir.exists() {
+            reader
+                .list_segments(&wal_dir)
+                .map_err(|e| StrataError::storage(format!("WAL read failed: {}", e)))?
+        } else {
+            Vec::new()
+        };
+        let mut wal_rewrites = Vec::with_capacity(segments.len());
+        let mut wal_records = 0usize;
+        for seg in &segments {
+            let (records, valid_end, _, skipped) = reader
+                .read_segment(&wal_dir, *seg)
+                .map_err(|e| StrataError::storage(format!("cannot rekey WAL: {}", e)))?;
+            let segment = WalSegment::open_read(&wal_dir, *seg).map_err(StrataError::from)?;
+            if valid_end < segment.size() || skipped > 0 {
+                warn!(
+                    target: "strata::db",
+                    segment = seg,
+                    skipped,
+                    "Dropping unreadable WAL bytes during rekey"
+                );
+            }
+            let raw = std::fs::read(segment.path()).map_err(StrataError::from)?;
+            let mut bytes = raw[..segment.header_size()].to_vec();
+            for record in &records {
+                bytes.extend_from_slice(&record.to_encoded_bytes(new.as_ref()));
+            }
+            wal_records += records.len();
+            wal_rewrites.push((segment.path().to_path_buf(), bytes));
+        }
+
+        let snapshots_dir = data_dir.join("snapshots");
+        let snapshots = if snapshots_dir.exists() {
+            list_snapshots(&snapshots_dir).map_err(StrataError::from)?
+        } else {
+            Vec::new()
+        };
+        let snapshot_reader = DiskSnapshotReader::new(old.clone_box());
+        let mut loaded_snapshots = Vec::with_capacity(snapshots.len());
+        for (_, snap_path) in &snapshots {
+            let loaded = snapshot_reader.load(snap_path).map_err(|e| {
+                StrataError::storage(format!(
+                    "cannot rekey snapshot '{}': {}",
+                    snap_path.display(),
+                    e
+                ))
+            })?;
+            loaded_snapshots.push(loaded);
+        }
+
+        // Rewrite WAL segments
+        for (seg_path, bytes) in &wal_rewrites {
+            let tmp = seg_path.with_extension("seg.rekey");
+            write_synced(&tmp, bytes)?;
+            std::fs::rename(&tmp, seg_path).map_err(StrataError::from)?;
+        }
+
+        // Rewrite snapshots (the writer renames over the existing file)
+        for loaded in &loaded_snapshots {
+            let writer = DiskSnapshotWriter::new(
+                snapshots_dir.clone(),
+                new.clone_box(),
+                loaded.database_uuid(),
+            )
+            .map_err(StrataError::from)?;
+            let sections = loaded
+                .sections
+                .iter()
+                .map(|s| SnapshotSection::new(s.primitive_type, s.data.clone()))
+                .collect();
+            writer
+                .create_snapshot(loaded.snapshot_id(), loaded.watermark_txn(), sections)
+                .map_err(StrataError::from)?;
+        }
+
+        // Record the new codec last
+        if ManifestManager::exists(&manifest_path) {
+            let mut manifest = ManifestManager::load(manifest_path)
+                .map_err(|e| StrataError::storage(format!("failed to load MANIFEST: {}", e)))?;
+            manifest
+                .set_codec(new.codec_id().to_string(), make_key_check(new.as_ref()))
+                .map_err(|e| StrataError::storage(format!("failed to persist MANIFEST: {}", e)))?;
+        } else if new.codec_id() != "identity" {
+            create_manifest(data_dir, new.as_ref())?;
+        }
+
+        let info = RekeyInfo {
+            old_codec: old.codec_id().to_string(),
+            new_codec: new.codec_id().to_string(),
+            wal_segments: wal_rewrites.len(),
+            wal_records,
+            snapshots: loaded_snapshots.len(),
+        };
+        info!(
+            target: "strata::db",
+            old_codec = %info.old_codec,
+            new_codec = %info.new_codec,
+            wal_segments = info.wal_segments,
+            wal_records = info.wal_records,
+            snapshots = info.snapshots,
+            "Database rekeyed"
+        );
+
+        drop(lock_file);
+        Ok(info)
+    }
+
+    /// The storage codec WAL records, snapshots and bundles are encoded with.
+    pub(crate) fn codec(&self) -> &dyn StorageCodec {
+        self.codec.as_ref()
+    }
+}
+
+/// Write `bytes` to `path` and fsync.
+pub(super) fn write_synced(path: &Path, bytes: &[u8]) -> StrataResult<()> {
+    use std::io::Write;
+    let mut file = std::fs::File::create(path).map_err(StrataError::from)?;
+    file.write_all(bytes).map_err(StrataError::from)?;
+    file.sync_all().map_err(StrataError::from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use std::sync::Arc;
+    use strata_core::types::{BranchId, Key, Namespace};
+    use strata_core::value::Value;
+    use strata_core::Storage;
+    use strata_durability::codec::AES_GCM_CODEC_ID;
+    use tempfile::TempDir;
+
+    const KEY_A: &[u8] = b"000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const KEY_B: &[u8] = b"ffeeddccbbaa99887766554433221100ffeeddccbbaa99887766554433221100";
+
+    fn test_ns(branch_id: BranchId) -> Namespace {
+        Namespace::new(
+            "tenant".to_string(),
+            "app".to_string(),
+            "agent".to_string(),
+            branch_id,
+            "default".to_string(),
+        )
+    }
+
+    fn open(path: &Path, key: Option<&[u8]>) -> StrataResult<Arc<Database>> {
+        Database::open_with_key(path, StrataConfig::default(), key)
+    }
+
+    fn open_compressed(path: &Path, key: Option<&[u8]>) -> StrataResult<Arc<Database>> {
+        let cfg = StrataConfig {
+            compression: "lz4".to_string(),
+            ..StrataConfig::default()
+        };
+        Database::open_with_key(path, cfg, key)
+    }
+
+    fn open_err(path: &Path, key: Option<&[u8]>) -> String {
+        match open(path, key) {
+            Ok(_) => panic!("open should fail"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    fn recorded_codec(path: &Path) -> String {
+        ManifestManager::load(path.join("MANIFEST"))
+            .unwrap()
+            .manifest()
+            .codec_id
+            .clone()
+    }
+
+    fn write_value(db: &Database, ns: &Namespace, key: &str, value: &[u8]) {
+        let branch_id = ns.branch_id;
+        db.transaction(branch_id, |txn| {
+            txn.put(Key::new_kv(ns.clone(), key), Value::Bytes(value.to_vec()))?;
+            Ok(())
+        })
+        .unwrap();
+        db.flush().unwrap();
+    }
+
+    fn read_value(db: &Database, ns: &Namespace, key: &str) -> Option<Vec<u8>> {
+        db.storage()
+            .get(&Key::new_kv(ns.clone(), key))
+            .unwrap()
+            .map(|v| match v.value {
+                Value::Bytes(bytes) => bytes,
+                other => panic!("unexpected value {:?}", other),
+            })
+    }
+
+    fn wal_contains(path: &Path, needle: &[u8]) -> bool {
+        std::fs::read_dir(path.join("wal"))
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter_map(|e| std::fs::read(e.path()).ok())
+            .any(|bytes| bytes.windows(needle.len()).any(|w| w == needle))
+    }
+
+    #[test]
+    fn test_encrypted_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let ns = test_ns(BranchId::new());
+
+        {
+            let db = open(&path, Some(KEY_A)).unwrap();
+            write_value(&db, &ns, "k", b"top-secret-value");
+        }
+
+        assert!(!wal_contains(&path, b"top-secret-value"));
+
+        let db = open(&path, Some(KEY_A)).unwrap();
+        assert_eq!(read_value(&db, &ns, "k").unwrap(), b"top-secret-value");
+    }
+
+    #[test]
+    fn test_wrong_key_fails_with_decode_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let ns = test_ns(BranchId::new());
+
+        {
+            let db = open(&path, Some(KEY_A)).unwrap();
+            write_value(&db, &ns, "k", b"v");
+        }
+
+        let err = open_err(&path, Some(KEY_B));
+        assert!(err.contains("Decode error"), "{}", err);
+        assert!(err.contains("wrong encryption key"), "{}", err);
+    }
+
+    #[test]
+    fn test_missing_key_fails_with_codec_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let ns = test_ns(BranchId::new());
+
+        {
+            let db = open(&path, Some(KEY_A)).unwrap();
+            write_value(&db, &ns, "k", b"v");
+        }
+
+        let err = open_err(&path, None);
+        assert!(err.contains("Codec mismatch"), "{}", err);
+    }
+
+    #[test]
+    fn test_plaintext_database_rejects_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let ns = test_ns(BranchId::new());
+
+        {
+            let db = open(&path, None).unwrap();
+            write_value(&db, &ns, "k", b"v");
+        }
+
+        let err = open_err(&path, Some(KEY_A));
+        assert!(err.contains("Codec mismatch"), "{}", err);
+
+        // The failed open must not have touched the plaintext database
+        let db = open(&path, None).unwrap();
+        assert_eq!(read_value(&db, &ns, "k").unwrap(), b"v");
+    }
+
+    #[test]
+    fn test_invalid_key_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let err = open_err(&temp_dir.path().join("db"), Some(b"short"));
+        assert!(err.contains("Invalid encryption key"), "{}", err);
+    }
+
+    #[test]
+    fn test_checkpoint_and_compact_encrypted() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let ns = test_ns(BranchId::new());
+
+        {
+            let db = open(&path, Some(KEY_A)).unwrap();
+            write_value(&db, &ns, "before", b"1");
+            db.checkpoint().unwrap();
+            db.compact().unwrap();
+            write_value(&db, &ns, "after", b"2");
+        }
+
+        let db = open(&path, Some(KEY_A)).unwrap();
+        assert_eq!(read_value(&db, &ns, "before").unwrap(), b"1");
+        assert_eq!(read_value(&db, &ns, "after").unwrap(), b"2");
+    }
+
+    #[test]
+    fn test_rekey_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let ns = test_ns(BranchId::new());
+
+        {
+            let db = open(&path, None).unwrap();
+            write_value(&db, &ns, "k", b"rekeyed-value");
+            db.checkpoint().unwrap();
+            write_value(&db, &ns, "k2", b"wal-only");
+        }
+
+        // Plaintext -> key A
+        let info = Database::rekey(&path, None, Some(KEY_A)).unwrap();
+        assert_eq!(info.old_codec, "identity");
+        assert_eq!(info.new_codec, AES_GCM_CODEC_ID);
+        assert!(info.wal_records >= 2);
+        assert_eq!(info.snapshots, 1);
+        assert!(!wal_contains(&path, b"rekeyed-value"));
+
+        // Key A -> key B
+        Database::rekey(&path, Some(KEY_A), Some(KEY_B)).unwrap();
+        assert!(open(&path, Some(KEY_A)).is_err());
+
+        let db = open(&path, Some(KEY_B)).unwrap();
+        assert_eq!(read_value(&db, &ns, "k").unwrap(), b"rekeyed-value");
+        assert_eq!(read_value(&db, &ns, "k2").unwrap(), b"wal-only");
+        drop(db);
+
+        // Key B -> plaintext
+        Database::rekey(&path, Some(KEY_B), None).unwrap();
+        let db = open(&path, None).unwrap();
+        assert_eq!(read_value(&db, &ns, "k").unwrap(), b"rekeyed-value");
+    }
+
+    #[test]
+    fn test_rekey_wrong_old_key_changes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let ns = test_ns(BranchId::new());
+
+        {
+            let db = open(&path, Some(KEY_A)).unwrap();
+            write_value(&db, &ns, "k", b"v");
+        }
+
+        assert!(Database::rekey(&path, Some(KEY_B), None).is_err());
+
+        let db = open(&path, Some(KEY_A)).unwrap();
+        assert_eq!(read_value(&db, &ns, "k").unwrap(), b"v");
+    }
+
+    #[test]
+    fn test_rekey_refuses_open_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+
+        let _db = open(&path, None).unwrap();
+        let err = Database::rekey(&path, None, Some(KEY_A))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("in use"), "{}", err);
+    }
+
+    #[test]
+    fn test_compressed_reopen_and_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let ns = test_ns(BranchId::new());
+        let text = b"compressible-text ".repeat(64);
+
+        {
+            let db = open_compressed(&path, None).unwrap();
+            write_value(&db, &ns, "before", &text);
+            db.checkpoint().unwrap();
+            write_value(&db, &ns, "after", &text);
+        }
+
+        assert_eq!(recorded_codec(&path), "lz4");
+        assert!(!wal_contains(&path, &text));
+
+        let db = open_compressed(&path, None).unwrap();
+        assert_eq!(read_value(&db, &ns, "before").unwrap(), text);
+        assert_eq!(read_value(&db, &ns, "after").unwrap(), text);
+    }
+
+    #[test]
+    fn test_codec_chain_mismatch_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let ns = test_ns(BranchId::new());
+
+        {
+            let db = open_compressed(&path, Some(KEY_A)).unwrap();
+            write_value(&db, &ns, "k", b"v");
+        }
+        assert_eq!(recorded_codec(&path), "lz4+aes-gcm-256");
+
+        // Encryption without compression
+        let err = open_err(&path, Some(KEY_A));
+        assert!(err.contains("Codec mismatch"), "{}", err);
+
+        // Compression without encryption
+        let err = match open_compressed(&path, None) {
+            Ok(_) => panic!("open should fail"),
+            Err(e) => e.to_string(),
+        };
+        assert!(err.contains("Codec mismatch"), "{}", err);
+
+        let db = open_compressed(&path, Some(KEY_A)).unwrap();
+        assert_eq!(read_value(&db, &ns, "k").unwrap(), b"v");
+    }
+
+    #[test]
+    fn test_rekey_keeps_compression() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let ns = test_ns(BranchId::new());
+
+        {
+            let db = open_compressed(&path, None).unwrap();
+            write_value(&db, &ns, "k", b"v");
+        }
+
+        let info = Database::rekey(&path, None, Some(KEY_A)).unwrap();
+        assert_eq!(info.old_codec, "lz4");
+        assert_eq!(info.new_codec, "lz4+aes-gcm-256");
+        assert_eq!(recorded_codec(&path), "lz4+aes-gcm-256");
+
+        let db = open_compressed(&path, Some(KEY_A)).unwrap();
+        assert_eq!(read_value(&db, &ns, "k").unwrap(), b"v");
+    }
+}
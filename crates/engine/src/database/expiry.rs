@@ -0,0 +1,346 @@
+//! TTL expiry
+//!
+//! Values written with a TTL disappear from reads on their own: storage
+//! hides them once the TTL has run out at the read's timestamp. This module
+//! removes them for good. A `strata-ttl` thread watches the earliest expiry
+//! in the storage TTL index and, once it has passed, submits a reap to the
+//! background scheduler. The reap deletes the expired keys in ordinary
+//! transactions, so every expiration is a tombstone in the key's history
+//! and in the WAL.
+//!
+//! The thread starts with the first write that carries a TTL (or at open,
+//! if recovered data has TTLs), holds only a weak reference to the
+//! database, and exits once the database is dropped or shut down.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use parking_lot::Mutex as ParkingMutex;
+use strata_core::types::{BranchId, Key};
+use strata_core::{StrataResult, Timestamp};
+use tracing::warn;
+
+use super::transactions::RetryConfig;
+use super::Database;
+use crate::background::TaskPriority;
+use crate::primitives::branch::is_frozen;
+
+/// How often the expiry thread checks for expired keys.
+const EXPIRY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// State of the background expiry thread.
+#[derive(Default)]
+pub(crate) struct ExpiryState {
+    /// Set once the thread has been spawned
+    started: AtomicBool,
+    /// Set while a reap is queued or running
+    pending: AtomicBool,
+    /// Set by `Database::shutdown` to stop the thread
+    stopped: AtomicBool,
+    /// Thread handle (None until the first TTL write)
+    handle: ParkingMutex<Option<JoinHandle<()>>>,
+}
+
+impl ExpiryState {
+    /// Stop the expiry thread and wait for it to exit.
+    ///
+    /// Does not wait when called from the expiry thread itself (the thread
+    /// can hold the last strong reference and run the database's drop).
+    pub(crate) fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        let handle = self.handle.lock().take();
+        if let Some(handle) = handle {
+            if handle.thread().id() != std::thread::current().id() {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+fn expiry_loop(weak: Weak<Database>) {
+    loop {
+        std::thread::sleep(EXPIRY_POLL_INTERVAL);
+        let db = match weak.upgrade() {
+            Some(db) => db,
+            None => return,
+        };
+        let state = &db.expiry_state;
+        if state.stopped.load(Ordering::SeqCst) {
+            return;
+        }
+        if state.pending.load(Ordering::SeqCst) {
+            continue;
+        }
+        if !db
+            .storage
+            .next_expiry()
+            .is_some_and(|expiry| expiry <= Timestamp::now())
+        {
+            continue;
+        }
+
+        state.pending.store(true, Ordering::SeqCst);
+        let task_db = Weak::clone(&weak);
+        let submitted = db.scheduler.submit(TaskPriority::Low, move || {
+            if let Some(db) = task_db.upgrade() {
+                if let Err(e) = db.reap_expired() {
+                    warn!(target: "strata::db", error = %e, "Expired key cleanup failed");
+                }
+                db.expiry_state.pending.store(false, Ordering::SeqCst);
+            }
+        });
+        if submitted.is_err() {
+            // Queue full or shutting down; retry on a later tick
+            state.pending.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+impl Database {
+    /// Start the background thread that deletes expired keys, if it is not
+    /// running yet.
+    ///
+    /// KV and State writes with a TTL call this themselves; call it after
+    /// committing a transaction that used
+    /// [`TransactionContext::put_with_ttl`](strata_concurrency::TransactionContext::put_with_ttl)
+    /// directly. Until the thread runs, expired keys are still hidden from
+    /// reads but stay in storage.
+    pub fn start_expiry_reaper(self: &Arc<Self>) {
+        let state = &self.expiry_state;
+        if state.stopped.load(Ordering::SeqCst) || state.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let weak = Arc::downgrade(self);
+        match std::thread::Builder::new()
+            .name("strata-ttl".to_string())
+            .spawn(move || expiry_loop(weak))
+        {
+            Ok(handle) => *state.handle.lock() = Some(handle),
+            Err(e) => {
+                warn!(target: "strata::db", error = %e, "Failed to spawn TTL expiry thread");
+                state.started.store(false, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Delete every key whose TTL has run out.
+    ///
+    /// Each branch's expired keys are deleted in one transaction, which
+    /// leaves a tombstone in their history. A key rewritten since it
+    /// expired is left alone, and so are the keys of frozen branches, which
+    /// accept no writes: they are reaped once the branch is unfrozen. The
+    /// background expiry thread calls this; calling it directly reaps
+    /// without waiting for the next poll.
+    ///
+    /// Returns the number of keys deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error that stopped a branch's keys from being
+    /// deleted; those keys are kept for the next reap.
+    pub fn reap_expired(&self) -> StrataResult<usize> {
+        let now = Timestamp::now();
+        let mut by_branch: HashMap<BranchId, Vec<Key>> = HashMap::new();
+        for key in self.storage.take_expired(now) {
+            by_branch
+                .entry(key.namespace.branch_id)
+                .or_default()
+                .push(key);
+        }
+
+        let mut reaped = 0;
+        let mut first_error = None;
+        for (branch_id, keys) in by_branch {
+            if is_frozen(self, &branch_id) {
+                self.storage.reindex_expired(keys, now);
+                continue;
+            }
+            let result = self.transaction_with_retry(branch_id, RetryConfig::default(), |txn| {
+                let mut deleted = 0;
+                for key in &keys {
+                    if self.storage.expired_version(key, now)?.is_none() {
+                        continue;
+                    }
+                    // Reading the key puts it in the read set, so a rewrite
+                    // that commits first makes this reap retry instead of
+                    // deleting the new value
+                    if txn.get(key)?.is_some() {
+                        continue;
+                    }
+                    txn.delete(key.clone())?;
+                    deleted += 1;
+                }
+                Ok(deleted)
+            });
+            match result {
+                Ok(deleted) => reaped += deleted,
+                Err(e) => {
+                    self.storage.reindex_expired(keys, now);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(reaped),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::kv::KVStore;
+    use strata_core::types::Namespace;
+    use strata_core::value::Value;
+    use tempfile::TempDir;
+
+    const TTL: Duration = Duration::from_millis(50);
+
+    #[test]
+    fn test_expired_keys_are_hidden_then_reaped() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path()).unwrap();
+        let kv = KVStore::new(db.clone());
+        let branch_id = BranchId::new();
+
+        kv.put_with_ttl(&branch_id, "default", "lease", Value::Int(1), Some(TTL))
+            .unwrap();
+        kv.put(&branch_id, "default", "keep", Value::Int(2))
+            .unwrap();
+        assert_eq!(
+            kv.get(&branch_id, "default", "lease").unwrap(),
+            Some(Value::Int(1))
+        );
+
+        // A transaction that started before the expiry keeps seeing the key
+        let mut txn = db.begin_transaction(branch_id);
+        std::thread::sleep(TTL);
+        let key = Key::new_kv(Namespace::for_branch_space(branch_id, "default"), "lease");
+        assert_eq!(txn.get(&key).unwrap(), Some(Value::Int(1)));
+        db.end_transaction(txn);
+
+        assert_eq!(kv.get(&branch_id, "default", "lease").unwrap(), None);
+        assert_eq!(kv.list(&branch_id, "default", None).unwrap(), vec!["keep"]);
+
+        // Reaping may already have happened in the background
+        db.reap_expired().unwrap();
+        assert!(db.storage.next_expiry().is_none());
+        let history = kv.getv(&branch_id, "default", "lease").unwrap().unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.versions()[0].value, Value::Null);
+        assert_eq!(history.versions()[1].value, Value::Int(1));
+        assert_eq!(db.reap_expired().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_rewritten_key_is_not_reaped() {
+        let db = Database::cache().unwrap();
+        let kv = KVStore::new(db.clone());
+        let branch_id = BranchId::new();
+
+        kv.put_with_ttl(&branch_id, "default", "k", Value::Int(1), Some(TTL))
+            .unwrap();
+        kv.put(&branch_id, "default", "k", Value::Int(2)).unwrap();
+        std::thread::sleep(TTL);
+
+        db.reap_expired().unwrap();
+        assert_eq!(
+            kv.get(&branch_id, "default", "k").unwrap(),
+            Some(Value::Int(2))
+        );
+    }
+
+    #[test]
+    fn test_frozen_branch_is_not_reaped() {
+        use crate::primitives::branch::{resolve_branch_name, BranchIndex, BranchStatus};
+
+        let db = Database::cache().unwrap();
+        let kv = KVStore::new(db.clone());
+        let branches = BranchIndex::new(db.clone());
+        branches.create_branch("release").unwrap();
+        let branch_id = resolve_branch_name("release");
+
+        kv.put_with_ttl(&branch_id, "default", "k", Value::Int(1), Some(TTL))
+            .unwrap();
+        branches
+            .set_status("release", BranchStatus::Frozen)
+            .unwrap();
+        std::thread::sleep(TTL);
+
+        assert_eq!(db.reap_expired().unwrap(), 0);
+        let history = kv.getv(&branch_id, "default", "k").unwrap().unwrap();
+        assert_eq!(history.len(), 1);
+
+        // Unfreezing lets the key be reaped
+        branches
+            .set_status("release", BranchStatus::Active)
+            .unwrap();
+        db.reap_expired().unwrap();
+        let history = kv.getv(&branch_id, "default", "k").unwrap().unwrap();
+        assert_eq!(history.versions()[0].value, Value::Null);
+    }
+
+    #[test]
+    fn test_background_reaper_deletes_expired_keys() {
+        let db = Database::cache().unwrap();
+        let kv = KVStore::new(db.clone());
+        let branch_id = BranchId::new();
+
+        kv.batch_put_with_ttl(
+            &branch_id,
+            "default",
+            vec![
+                ("a".to_string(), Value::Int(1), Some(TTL)),
+                ("b".to_string(), Value::Int(2), None),
+            ],
+        )
+        .unwrap();
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while db.storage.next_expiry().is_some() && std::time::Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        let history = kv.getv(&branch_id, "default", "a").unwrap().unwrap();
+        assert_eq!(history.versions()[0].value, Value::Null);
+        assert_eq!(
+            kv.get(&branch_id, "default", "b").unwrap(),
+            Some(Value::Int(2))
+        );
+    }
+
+    #[test]
+    fn test_ttl_survives_recovery() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        {
+            let db = Database::open(&path).unwrap();
+            KVStore::new(db.clone())
+                .put_with_ttl(
+                    &branch_id,
+                    "default",
+                    "k",
+                    Value::Int(1),
+                    Some(Duration::from_secs(3600)),
+                )
+                .unwrap();
+            db.shutdown().unwrap();
+        }
+
+        let db = Database::open(&path).unwrap();
+        assert!(db.storage.next_expiry().is_some());
+        assert!(db.expiry_state.started.load(Ordering::SeqCst));
+        assert_eq!(
+            KVStore::new(db.clone())
+                .get(&branch_id, "default", "k")
+                .unwrap(),
+            Some(Value::Int(1))
+        );
+    }
+}
@@ -0,0 +1,1083 @@
+//! Offline integrity check and repair
+//!
+//! `Database::fsck` walks a closed database directory and checks everything
+//! recovery depends on:
+//!
+//! - the MANIFEST loads and matches the codec
+//! - every WAL segment reads to its end, with no gaps between segments and
+//!   `.meta` sidecars that agree with the records
+//! - every snapshot passes its CRC checks and the checkpoint chain links up
+//! - the MANIFEST watermark matches its snapshot, and the WAL still holds
+//!   everything the checkpoint does not cover
+//! - each event log's hash chain is intact
+//! - the vector and search caches only hold files for live data
+//!
+//! Problems are reported as [`FsckFinding`]s. With `repair`, fsck fixes what
+//! it safely can: it truncates a torn tail off the newest WAL segment,
+//! rewrites stale sidecars, and removes orphaned or broken cache files so the
+//! derived indexes are rebuilt on the next open. Lost commits, broken
+//! snapshots and broken event chains are reported but never modified.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use strata_concurrency::{CheckpointPayload, RecoveryCoordinator};
+use strata_core::types::TypeTag;
+use strata_core::{StrataError, StrataResult};
+use strata_durability::codec::StorageCodec;
+use strata_durability::disk_snapshot::SnapshotReader;
+use strata_durability::format::segment_meta::SegmentMeta;
+use strata_durability::format::{Manifest, WalSegment};
+use strata_durability::wal::{ReadStopReason, WalReader, WalReaderError};
+use strata_durability::{list_snapshots, primitive_tags, ManifestManager};
+use strata_storage::ShardedStore;
+use tracing::info;
+
+use super::checkpoint::checkpoint_snapshot;
+use super::config::{StrataConfig, CONFIG_FILE_NAME};
+use super::encryption::{check_or_init_manifest, resolve_codec};
+use super::Database;
+use crate::primitives::event::verify_chain;
+use crate::search::manifest::load_manifest;
+use crate::search::segment::SealedSegment;
+
+/// How serious an fsck finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsckSeverity {
+    /// Data is lost or the database cannot be recovered as-is
+    Error,
+    /// Recovery works around it, or only a rebuildable cache is affected
+    Warning,
+}
+
+impl FsckSeverity {
+    /// Lowercase name used in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FsckSeverity::Error => "error",
+            FsckSeverity::Warning => "warning",
+        }
+    }
+}
+
+/// Part of the database an fsck finding is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsckComponent {
+    /// The MANIFEST file and the state it records
+    Manifest,
+    /// WAL segments
+    Wal,
+    /// WAL segment `.meta` sidecars
+    SegmentMeta,
+    /// Checkpoint snapshots
+    Snapshot,
+    /// Rebuilding state from the checkpoint and WAL
+    Recovery,
+    /// Event log hash chains
+    EventChain,
+    /// Vector `.vec` and graph cache files
+    VectorCache,
+    /// Search index segments
+    SearchCache,
+}
+
+impl FsckComponent {
+    /// Lowercase name used in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FsckComponent::Manifest => "manifest",
+            FsckComponent::Wal => "wal",
+            FsckComponent::SegmentMeta => "segment_meta",
+            FsckComponent::Snapshot => "snapshot",
+            FsckComponent::Recovery => "recovery",
+            FsckComponent::EventChain => "event_chain",
+            FsckComponent::VectorCache => "vector_cache",
+            FsckComponent::SearchCache => "search_cache",
+        }
+    }
+}
+
+/// One problem found by `Database::fsck`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsckFinding {
+    /// How serious the problem is
+    pub severity: FsckSeverity,
+    /// What the problem is about
+    pub component: FsckComponent,
+    /// File or directory concerned, if any
+    pub path: Option<PathBuf>,
+    /// Description of the problem
+    pub message: String,
+    /// Whether fsck repaired it
+    pub repaired: bool,
+}
+
+/// Result of `Database::fsck`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsckReport {
+    /// Database directory that was checked
+    pub path: PathBuf,
+    /// Number of WAL segments read
+    pub wal_segments: usize,
+    /// Number of WAL records read
+    pub wal_records: usize,
+    /// Number of snapshots read
+    pub snapshots: usize,
+    /// Number of branches in the recovered state
+    pub branches: usize,
+    /// Number of event logs whose chain was verified
+    pub event_logs: usize,
+    /// Number of events verified
+    pub events: u64,
+    /// Problems found, in the order they were found
+    pub findings: Vec<FsckFinding>,
+}
+
+impl FsckReport {
+    /// Whether no problems were found.
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Whether an error was found that was not repaired.
+    pub fn has_errors(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|f| f.severity == FsckSeverity::Error && !f.repaired)
+    }
+
+    /// Number of findings that were repaired.
+    pub fn repaired(&self) -> usize {
+        self.findings.iter().filter(|f| f.repaired).count()
+    }
+}
+
+impl Database {
+    /// Check the integrity of a closed database and optionally repair it.
+    ///
+    /// Reads every WAL segment and snapshot in `path`, checks them against
+    /// the MANIFEST, rebuilds the database state, and verifies each event
+    /// log's hash chain and the vector and search caches. See [`FsckReport`]
+    /// for what is reported.
+    ///
+    /// Without `repair` nothing is modified. With `repair`, torn WAL tails
+    /// are truncated, stale `.meta` sidecars rewritten, and orphaned or
+    /// broken cache files removed; each repaired finding is marked as such.
+    ///
+    /// `key` is the encryption key; when `None` it is resolved the same way
+    /// as for `open`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if there is no database at `path`, if it is open, or if it
+    /// cannot be decoded with the key. Integrity problems are findings in
+    /// the report, not errors.
+    pub fn fsck<P: AsRef<Path>>(
+        path: P,
+        repair: bool,
+        key: Option<&[u8]>,
+    ) -> StrataResult<FsckReport> {
+        let data_dir = path.as_ref();
+        let manifest_path = data_dir.join("MANIFEST");
+        if !data_dir.join("wal").exists() && !ManifestManager::exists(&manifest_path) {
+            return Err(StrataError::invalid_input(format!(
+                "no database found at '{}'",
+                data_dir.display()
+            )));
+        }
+
+        // Same exclusive lock as open(): a live database is still changing
+        let lock_file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(data_dir.join(".lock"))
+            .map_err(|e| StrataError::storage(format!("failed to open lock file: {}", e)))?;
+        fs2::FileExt::try_lock_exclusive(&lock_file).map_err(|_| {
+            StrataError::storage(format!(
+                "database at '{}' is in use; close it before running fsck",
+                data_dir.display()
+            ))
+        })?;
+
+        let config_path = data_dir.join(CONFIG_FILE_NAME);
+        let cfg = if config_path.exists() {
+            StrataConfig::from_file(&config_path)?
+        } else {
+            StrataConfig::default()
+        };
+        let codec = resolve_codec(data_dir, &cfg, key)?;
+
+        let mut fsck = Fsck {
+            data_dir,
+            codec,
+            repair,
+            report: FsckReport {
+                path: data_dir.to_path_buf(),
+                wal_segments: 0,
+                wal_records: 0,
+                snapshots: 0,
+                branches: 0,
+                event_logs: 0,
+                events: 0,
+                findings: Vec::new(),
+            },
+        };
+        let manifest = fsck.check_manifest()?;
+        let wal_segments = fsck.check_wal()?;
+        fsck.check_snapshots(manifest.as_ref(), &wal_segments);
+        if let Some(storage) = fsck.rebuild(manifest.is_some()) {
+            fsck.report.branches = storage.branch_ids().len();
+            fsck.check_event_chains(&storage);
+            fsck.check_vector_cache(&storage);
+        }
+        fsck.check_search_cache();
+
+        let report = fsck.report;
+        info!(
+            target: "strata::db",
+            path = ?report.path,
+            findings = report.findings.len(),
+            repaired = report.repaired(),
+            "Database checked"
+        );
+        Ok(report)
+    }
+}
+
+/// State of one fsck run.
+struct Fsck<'a> {
+    data_dir: &'a Path,
+    codec: Box<dyn StorageCodec>,
+    repair: bool,
+    report: FsckReport,
+}
+
+impl Fsck<'_> {
+    /// Record a finding that fsck cannot repair.
+    fn found(
+        &mut self,
+        severity: FsckSeverity,
+        component: FsckComponent,
+        path: Option<PathBuf>,
+        message: String,
+    ) {
+        self.report.findings.push(FsckFinding {
+            severity,
+            component,
+            path,
+            message,
+            repaired: false,
+        });
+    }
+
+    /// Record a finding, applying `fix` when repairing.
+    fn found_fixable(
+        &mut self,
+        severity: FsckSeverity,
+        component: FsckComponent,
+        path: PathBuf,
+        mut message: String,
+        fix: impl FnOnce() -> std::io::Result<()>,
+    ) {
+        let mut repaired = false;
+        if self.repair {
+            match fix() {
+                Ok(()) => repaired = true,
+                Err(e) => message = format!("{} (repair failed: {})", message, e),
+            }
+        }
+        self.report.findings.push(FsckFinding {
+            severity,
+            component,
+            path: Some(path),
+            message,
+            repaired,
+        });
+    }
+
+    /// Load the MANIFEST and verify the codec against it.
+    ///
+    /// A wrong key is an error rather than a finding: nothing else can be
+    /// checked without it.
+    fn check_manifest(&mut self) -> StrataResult<Option<Manifest>> {
+        let path = self.data_dir.join("MANIFEST");
+        if !ManifestManager::exists(&path) {
+            return Ok(None);
+        }
+        match ManifestManager::load(path.clone()) {
+            Ok(manifest) => {
+                check_or_init_manifest(self.data_dir, self.codec.as_ref())?;
+                Ok(Some(manifest.manifest().clone()))
+            }
+            Err(e) => {
+                self.found(
+                    FsckSeverity::Error,
+                    FsckComponent::Manifest,
+                    Some(path),
+                    format!("MANIFEST is unreadable: {}", e),
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// Read every WAL segment and its sidecar. Returns the segment numbers.
+    fn check_wal(&mut self) -> StrataResult<Vec<u64>> {
+        let wal_dir = self.data_dir.join("wal");
+        if !wal_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let reader = WalReader::new(self.codec.clone_box());
+        let segments = reader
+            .list_segments(&wal_dir)
+            .map_err(|e| StrataError::storage(format!("WAL read failed: {}", e)))?;
+        self.report.wal_segments = segments.len();
+
+        for pair in segments.windows(2) {
+            if pair[1] != pair[0] + 1 {
+                self.found(
+                    FsckSeverity::Error,
+                    FsckComponent::Wal,
+                    Some(wal_dir.clone()),
+                    format!(
+                        "WAL segments {} to {} are missing",
+                        pair[0] + 1,
+                        pair[1] - 1
+                    ),
+                );
+            }
+        }
+
+        for (idx, &seg) in segments.iter().enumerate() {
+            let path = WalSegment::segment_path(&wal_dir, seg);
+            let size = match WalSegment::open_read(&wal_dir, seg) {
+                Ok(segment) => segment.size(),
+                Err(e) => {
+                    self.found(
+                        FsckSeverity::Error,
+                        FsckComponent::Wal,
+                        Some(path),
+                        format!("segment header is unreadable: {}", e),
+                    );
+                    continue;
+                }
+            };
+            let (records, valid_end, stop, skipped) = match reader.read_segment(&wal_dir, seg) {
+                Ok(read) => read,
+                Err(WalReaderError::Codec(e)) => {
+                    return Err(StrataError::corruption(format!("WAL decode failed: {}", e)))
+                }
+                Err(e) => {
+                    self.found(
+                        FsckSeverity::Error,
+                        FsckComponent::Wal,
+                        Some(path),
+                        format!("segment is unreadable: {}", e),
+                    );
+                    continue;
+                }
+            };
+            self.report.wal_records += records.len();
+
+            if skipped > 0 {
+                self.found(
+                    FsckSeverity::Error,
+                    FsckComponent::Wal,
+                    Some(path.clone()),
+                    format!("{} corrupted records were skipped", skipped),
+                );
+            }
+            let last = idx + 1 == segments.len();
+            match stop {
+                ReadStopReason::EndOfData => {}
+                ReadStopReason::PartialRecord | ReadStopReason::ChecksumMismatch { .. } if last => {
+                    // A write cut short by a crash; recovery ignores it
+                    let torn = path.clone();
+                    self.found_fixable(
+                        FsckSeverity::Warning,
+                        FsckComponent::Wal,
+                        path.clone(),
+                        format!(
+                            "torn tail: {} bytes after offset {} do not form a record",
+                            size - valid_end,
+                            valid_end
+                        ),
+                        || truncate(&torn, valid_end),
+                    );
+                }
+                ReadStopReason::PartialRecord | ReadStopReason::ChecksumMismatch { .. } => {
+                    self.found(
+                        FsckSeverity::Error,
+                        FsckComponent::Wal,
+                        Some(path.clone()),
+                        format!(
+                            "{} bytes after offset {} are corrupt; the commits in them are lost",
+                            size - valid_end,
+                            valid_end
+                        ),
+                    );
+                }
+                ReadStopReason::ParseError { offset, detail } => {
+                    self.found(
+                        FsckSeverity::Error,
+                        FsckComponent::Wal,
+                        Some(path.clone()),
+                        format!("record at offset {} cannot be parsed: {}", offset, detail),
+                    );
+                }
+            }
+
+            let mut expected = SegmentMeta::new_empty(seg);
+            for record in &records {
+                expected.track_record(record.txn_id, record.timestamp);
+            }
+            let meta_path = SegmentMeta::meta_path(&wal_dir, seg);
+            let problem = match SegmentMeta::read_from_file(&wal_dir, seg) {
+                Ok(None) => None,
+                Ok(Some(meta)) if meta == expected => None,
+                // Compaction trusts the sidecar's txn range when deleting
+                Ok(Some(_)) => Some((
+                    FsckSeverity::Error,
+                    "sidecar does not match the segment's records".to_string(),
+                )),
+                // Compaction falls back to scanning the segment
+                Err(e) => Some((
+                    FsckSeverity::Warning,
+                    format!("sidecar is unreadable: {}", e),
+                )),
+            };
+            if let Some((severity, message)) = problem {
+                let dir = wal_dir.clone();
+                self.found_fixable(
+                    severity,
+                    FsckComponent::SegmentMeta,
+                    meta_path.clone(),
+                    message,
+                    || {
+                        if expected.is_empty() {
+                            std::fs::remove_file(&meta_path)
+                        } else {
+                            expected.write_to_file(&dir).map_err(|e| {
+                                std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
+                            })
+                        }
+                    },
+                );
+            }
+        }
+        Ok(segments)
+    }
+
+    /// Read every snapshot and check the checkpoint against the MANIFEST and WAL.
+    fn check_snapshots(&mut self, manifest: Option<&Manifest>, wal_segments: &[u64]) {
+        let dir = self.data_dir.join("snapshots");
+        let listed = match list_snapshots(&dir) {
+            Ok(listed) => listed,
+            Err(e) => {
+                self.found(
+                    FsckSeverity::Error,
+                    FsckComponent::Snapshot,
+                    Some(dir),
+                    format!("snapshot directory is unreadable: {}", e),
+                );
+                return;
+            }
+        };
+        self.report.snapshots = listed.len();
+
+        let reader = SnapshotReader::new(self.codec.clone_box());
+        let mut checkpoints: BTreeMap<u64, (u64, CheckpointPayload)> = BTreeMap::new();
+        for (id, path) in listed {
+            let snapshot = match reader.load(&path) {
+                Ok(snapshot) => snapshot,
+                Err(e) => {
+                    self.found(
+                        FsckSeverity::Error,
+                        FsckComponent::Snapshot,
+                        Some(path),
+                        format!("snapshot is unreadable: {}", e),
+                    );
+                    continue;
+                }
+            };
+            if snapshot.snapshot_id() != id {
+                self.found(
+                    FsckSeverity::Error,
+                    FsckComponent::Snapshot,
+                    Some(path.clone()),
+                    format!("file holds snapshot {}", snapshot.snapshot_id()),
+                );
+            }
+            let payload = match snapshot.find_section(primitive_tags::STORAGE) {
+                Some(section) => CheckpointPayload::from_bytes(&section.data),
+                None => continue,
+            };
+            match payload {
+                Ok(payload) => {
+                    checkpoints.insert(id, (snapshot.watermark_txn(), payload));
+                }
+                Err(e) => self.found(
+                    FsckSeverity::Error,
+                    FsckComponent::Snapshot,
+                    Some(path),
+                    format!("storage section cannot be decoded: {}", e),
+                ),
+            }
+        }
+
+        let manifest_path = self.data_dir.join("MANIFEST");
+        let first_segment = wal_segments.first().copied();
+        let Some(manifest) = manifest else {
+            if !checkpoints.is_empty() {
+                self.found(
+                    FsckSeverity::Warning,
+                    FsckComponent::Manifest,
+                    None,
+                    "snapshots exist but no MANIFEST references them".to_string(),
+                );
+            }
+            if first_segment.is_some_and(|first| first > 1) {
+                self.found(
+                    FsckSeverity::Error,
+                    FsckComponent::Wal,
+                    Some(self.data_dir.join("wal")),
+                    "WAL segments before the first one were removed but no checkpoint covers them"
+                        .to_string(),
+                );
+            }
+            return;
+        };
+
+        let Some(snapshot_id) = manifest.snapshot_id else {
+            if manifest.snapshot_watermark.is_some() {
+                self.found(
+                    FsckSeverity::Error,
+                    FsckComponent::Manifest,
+                    Some(manifest_path),
+                    "MANIFEST records a watermark but no snapshot".to_string(),
+                );
+            }
+            if first_segment.is_some_and(|first| first > 1) {
+                self.found(
+                    FsckSeverity::Error,
+                    FsckComponent::Wal,
+                    Some(self.data_dir.join("wal")),
+                    "WAL segments before the first one were removed but no checkpoint covers them"
+                        .to_string(),
+                );
+            }
+            return;
+        };
+
+        match checkpoints.get(&snapshot_id) {
+            Some((watermark, payload)) => {
+                if manifest.snapshot_watermark != Some(*watermark) {
+                    self.found(
+                        FsckSeverity::Error,
+                        FsckComponent::Manifest,
+                        Some(manifest_path.clone()),
+                        format!(
+                            "MANIFEST watermark {:?} does not match snapshot {} watermark {}",
+                            manifest.snapshot_watermark, snapshot_id, watermark
+                        ),
+                    );
+                }
+                // Each link must start exactly where the previous one ended
+                let mut previous = 0;
+                for id in payload.parents.iter().chain(std::iter::once(&snapshot_id)) {
+                    let Some((_, link)) = checkpoints.get(id) else {
+                        self.found(
+                            FsckSeverity::Error,
+                            FsckComponent::Snapshot,
+                            None,
+                            format!(
+                                "snapshot {} builds on snapshot {}, which is missing or unreadable",
+                                snapshot_id, id
+                            ),
+                        );
+                        break;
+                    };
+                    if link.base_version != previous || link.version < previous {
+                        self.found(
+                            FsckSeverity::Error,
+                            FsckComponent::Snapshot,
+                            None,
+                            format!(
+                                "checkpoint chain is broken at snapshot {}: expected base version {}, found {}",
+                                id, previous, link.base_version
+                            ),
+                        );
+                        break;
+                    }
+                    previous = link.version;
+                }
+            }
+            None => self.found(
+                FsckSeverity::Error,
+                FsckComponent::Manifest,
+                Some(manifest_path),
+                format!(
+                    "MANIFEST references snapshot {}, which is missing or unreadable",
+                    snapshot_id
+                ),
+            ),
+        }
+
+        // Compaction only removes segments before the checkpoint's segment
+        if let Some(first) = first_segment {
+            if first > manifest.active_wal_segment.max(1) {
+                self.found(
+                    FsckSeverity::Error,
+                    FsckComponent::Wal,
+                    Some(self.data_dir.join("wal")),
+                    format!(
+                        "WAL segments {} to {} hold commits after the checkpoint and are missing",
+                        manifest.active_wal_segment,
+                        first - 1
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Rebuild the database state the way open() does.
+    fn rebuild(&mut self, has_manifest: bool) -> Option<ShardedStore> {
+        let mut recovery =
+            RecoveryCoordinator::new(self.data_dir.join("wal")).with_codec(self.codec.clone_box());
+        if has_manifest {
+            if let Ok(Some((_, path))) = checkpoint_snapshot(self.data_dir) {
+                recovery = recovery.with_snapshot_path(path);
+            }
+        }
+        match recovery.recover() {
+            Ok(result) => Some(result.storage),
+            Err(e) => {
+                self.found(
+                    FsckSeverity::Error,
+                    FsckComponent::Recovery,
+                    None,
+                    format!("database state cannot be rebuilt: {}", e),
+                );
+                None
+            }
+        }
+    }
+
+    /// Verify the hash chain of every event log.
+    fn check_event_chains(&mut self, storage: &ShardedStore) {
+        for branch_id in storage.branch_ids() {
+            let events = match storage.list_by_type(&branch_id, TypeTag::Event) {
+                Ok(events) => events,
+                Err(e) => {
+                    self.found(
+                        FsckSeverity::Error,
+                        FsckComponent::EventChain,
+                        None,
+                        format!("branch {}: cannot read events: {}", branch_id, e),
+                    );
+                    continue;
+                }
+            };
+            let mut logs: BTreeMap<String, Vec<_>> = BTreeMap::new();
+            for entry in events {
+                logs.entry(entry.0.namespace.space.clone())
+                    .or_default()
+                    .push(entry);
+            }
+            for (space, entries) in logs {
+                let verification = verify_chain(&entries);
+                self.report.event_logs += 1;
+                self.report.events += verification.length;
+                if !verification.is_valid {
+                    self.found(
+                        FsckSeverity::Error,
+                        FsckComponent::EventChain,
+                        None,
+                        format!(
+                            "branch {} space '{}': event {}: {}",
+                            branch_id,
+                            space,
+                            verification.first_invalid.unwrap_or(0),
+                            verification.error.unwrap_or_default()
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Look for vector cache files whose branch or collection is gone.
+    fn check_vector_cache(&mut self, storage: &ShardedStore) {
+        let dir = self.data_dir.join("vectors");
+        if !dir.exists() {
+            return;
+        }
+        let mut collections: HashMap<String, HashSet<String>> = HashMap::new();
+        for branch_id in storage.branch_ids() {
+            // Without the full set of collections every cache file would
+            // look orphaned, so give up on the check instead
+            let configs = match storage.list_by_type(&branch_id, TypeTag::VectorConfig) {
+                Ok(configs) => configs,
+                Err(e) => {
+                    self.found(
+                        FsckSeverity::Error,
+                        FsckComponent::VectorCache,
+                        None,
+                        format!(
+                            "branch {}: cannot read vector collections: {}",
+                            branch_id, e
+                        ),
+                    );
+                    return;
+                }
+            };
+            let names = configs
+                .into_iter()
+                .filter_map(|(key, _)| key.user_key_string())
+                .collect();
+            let branch_hex = format!("{:032x}", u128::from_be_bytes(*branch_id.as_bytes()));
+            collections.insert(branch_hex, names);
+        }
+
+        for branch_dir in read_dir_sorted(&dir) {
+            let name = file_name_string(&branch_dir);
+            let Some(names) = collections.get(&name) else {
+                self.found_orphan(FsckComponent::VectorCache, branch_dir, "branch");
+                continue;
+            };
+            for path in read_dir_sorted(&branch_dir) {
+                let file = file_name_string(&path);
+                let collection = file
+                    .strip_suffix(".vec")
+                    .or_else(|| file.strip_suffix("_graphs"));
+                match collection {
+                    Some(c) if names.contains(c) => {
+                        if path.is_dir() {
+                            self.check_graph_dir(&path);
+                        }
+                    }
+                    Some(_) => self.found_orphan(FsckComponent::VectorCache, path, "collection"),
+                    None => self.found_orphan(FsckComponent::VectorCache, path, "file"),
+                }
+            }
+        }
+    }
+
+    /// Look for sealed graph segments the graph manifest does not list.
+    fn check_graph_dir(&mut self, dir: &Path) {
+        // Manifest: heap count (u64) then 24-byte entries starting with the segment id
+        let listed: HashSet<u64> = match std::fs::read(dir.join("segments.manifest")) {
+            Ok(data) if data.len() >= 8 && (data.len() - 8) % 24 == 0 => data[8..]
+                .chunks(24)
+                .map(|entry| u64::from_le_bytes(entry[..8].try_into().unwrap()))
+                .collect(),
+            _ => HashSet::new(),
+        };
+        for path in read_dir_sorted(dir) {
+            let file = file_name_string(&path);
+            if file == "segments.manifest" {
+                continue;
+            }
+            let id = file
+                .strip_prefix("seg_")
+                .and_then(|f| f.strip_suffix(".hgr"))
+                .and_then(|id| id.parse::<u64>().ok());
+            if !id.is_some_and(|id| listed.contains(&id)) {
+                self.found_orphan(FsckComponent::VectorCache, path, "graph segment");
+            }
+        }
+    }
+
+    /// Check the search index segments against the search manifest.
+    fn check_search_cache(&mut self) {
+        let dir = self.data_dir.join("search");
+        if !dir.exists() {
+            return;
+        }
+        let manifest_path = dir.join("search.manifest");
+        let listed: HashSet<u64> = if manifest_path.exists() {
+            match load_manifest(&manifest_path) {
+                Ok(data) => data.segments.iter().map(|s| s.segment_id).collect(),
+                Err(e) => {
+                    let message = format!("search manifest is unreadable: {}", e);
+                    self.found_fixable(
+                        FsckSeverity::Warning,
+                        FsckComponent::SearchCache,
+                        dir.clone(),
+                        message,
+                        || std::fs::remove_dir_all(&dir),
+                    );
+                    return;
+                }
+            }
+        } else {
+            HashSet::new()
+        };
+
+        let mut broken = Vec::new();
+        for &id in &listed {
+            let path = dir.join(format!("seg_{}.sidx", id));
+            if let Err(e) = SealedSegment::from_mmap(&path) {
+                broken.push(format!("segment {}: {}", id, e));
+            }
+        }
+        if !broken.is_empty() {
+            broken.sort();
+            // The index is rebuilt from the primitives when the manifest is gone
+            let message = format!("search index is unusable ({})", broken.join("; "));
+            self.found_fixable(
+                FsckSeverity::Warning,
+                FsckComponent::SearchCache,
+                dir.clone(),
+                message,
+                || std::fs::remove_dir_all(&dir),
+            );
+            return;
+        }
+
+        for path in read_dir_sorted(&dir) {
+            let file = file_name_string(&path);
+            if file == "search.manifest" {
+                continue;
+            }
+            let id = file
+                .strip_prefix("seg_")
+                .and_then(|f| f.strip_suffix(".sidx"))
+                .and_then(|id| id.parse::<u64>().ok());
+            if !id.is_some_and(|id| listed.contains(&id)) {
+                self.found_orphan(FsckComponent::SearchCache, path, "segment");
+            }
+        }
+    }
+
+    /// Record an orphaned cache entry, removing it when repairing.
+    fn found_orphan(&mut self, component: FsckComponent, path: PathBuf, what: &str) {
+        let message = format!("orphaned {}: nothing references it", what);
+        let target = path.clone();
+        self.found_fixable(FsckSeverity::Warning, component, path, message, || {
+            if target.is_dir() {
+                std::fs::remove_dir_all(&target)
+            } else {
+                std::fs::remove_file(&target)
+            }
+        });
+    }
+}
+
+/// Truncate the file at `path` to `len` bytes and sync it.
+fn truncate(path: &Path, len: u64) -> std::io::Result<()> {
+    let file = std::fs::OpenOptions::new().write(true).open(path)?;
+    file.set_len(len)?;
+    file.sync_all()
+}
+
+/// Entries of `dir` in name order (empty if it cannot be read).
+fn read_dir_sorted(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries.filter_map(|e| e.ok().map(|e| e.path())).collect(),
+        Err(_) => Vec::new(),
+    };
+    paths.sort();
+    paths
+}
+
+fn file_name_string(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::event::{Event, EventLog};
+    use std::collections::HashMap;
+    use strata_core::types::{BranchId, Key, Namespace};
+    use strata_core::value::Value;
+    use strata_core::Storage;
+    use tempfile::TempDir;
+
+    fn payload(n: i64) -> Value {
+        let mut map = HashMap::new();
+        map.insert("n".to_string(), Value::Int(n));
+        map.insert("label".to_string(), Value::String(format!("event {}", n)));
+        map.insert("ok".to_string(), Value::Bool(true));
+        Value::Object(map)
+    }
+
+    /// A closed database with KV data, an event log and a checkpoint.
+    fn populated_db(path: &Path) -> BranchId {
+        let branch_id = BranchId::new();
+        let ns = Namespace::for_branch(branch_id);
+        let db = Database::open(path).unwrap();
+        let log = EventLog::new(db.clone());
+        for i in 0..5 {
+            log.append(&branch_id, "default", "tick", payload(i))
+                .unwrap();
+        }
+        db.transaction(branch_id, |txn| {
+            txn.put(Key::new_kv(ns.clone(), "a"), Value::Int(1))?;
+            Ok(())
+        })
+        .unwrap();
+        db.checkpoint().unwrap();
+        for i in 5..8 {
+            log.append(&branch_id, "default", "tick", payload(i))
+                .unwrap();
+        }
+        branch_id
+    }
+
+    fn last_segment(path: &Path) -> PathBuf {
+        let wal_dir = path.join("wal");
+        let segments = WalReader::new(Box::new(strata_durability::codec::IdentityCodec))
+            .list_segments(&wal_dir)
+            .unwrap();
+        WalSegment::segment_path(&wal_dir, *segments.last().unwrap())
+    }
+
+    #[test]
+    fn test_fsck_clean_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        populated_db(&path);
+
+        let report = Database::fsck(&path, false, None).unwrap();
+        assert!(report.is_clean(), "{:?}", report.findings);
+        assert_eq!(report.snapshots, 1);
+        assert!(report.wal_records >= 9);
+        assert_eq!(report.event_logs, 1);
+        assert_eq!(report.events, 8);
+    }
+
+    #[test]
+    fn test_fsck_refuses_open_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let _db = Database::open(&path).unwrap();
+        assert!(Database::fsck(&path, false, None).is_err());
+        assert!(Database::fsck(temp_dir.path().join("missing"), false, None).is_err());
+    }
+
+    #[test]
+    fn test_fsck_repairs_torn_tail() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let branch_id = populated_db(&path);
+
+        let segment = last_segment(&path);
+        let len = std::fs::metadata(&segment).unwrap().len();
+        let mut bytes = std::fs::read(&segment).unwrap();
+        bytes.extend_from_slice(&[0x40, 0, 0, 0, 1, 2, 3]);
+        std::fs::write(&segment, &bytes).unwrap();
+
+        let report = Database::fsck(&path, false, None).unwrap();
+        assert_eq!(report.findings.len(), 1, "{:?}", report.findings);
+        let finding = &report.findings[0];
+        assert_eq!(finding.component, FsckComponent::Wal);
+        assert_eq!(finding.severity, FsckSeverity::Warning);
+        assert!(!finding.repaired);
+        assert_eq!(std::fs::metadata(&segment).unwrap().len(), len + 7);
+
+        let report = Database::fsck(&path, true, None).unwrap();
+        assert_eq!(report.repaired(), 1);
+        assert_eq!(std::fs::metadata(&segment).unwrap().len(), len);
+        assert!(Database::fsck(&path, false, None).unwrap().is_clean());
+
+        let db = Database::open(&path).unwrap();
+        let key = Key::new_kv(Namespace::for_branch(branch_id), "a");
+        assert_eq!(
+            db.storage().get(&key).unwrap().unwrap().value,
+            Value::Int(1)
+        );
+    }
+
+    #[test]
+    fn test_fsck_reports_corrupt_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        populated_db(&path);
+
+        let (_, snapshot) = list_snapshots(&path.join("snapshots")).unwrap()[0].clone();
+        let mut bytes = std::fs::read(&snapshot).unwrap();
+        let mid = bytes.len() / 2;
+        bytes[mid] ^= 0xff;
+        std::fs::write(&snapshot, &bytes).unwrap();
+
+        let report = Database::fsck(&path, true, None).unwrap();
+        assert!(report.has_errors());
+        assert!(report
+            .findings
+            .iter()
+            .any(|f| f.component == FsckComponent::Snapshot && !f.repaired));
+        // Left for the operator: fsck never rewrites snapshots
+        assert_eq!(std::fs::read(&snapshot).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_fsck_detects_broken_event_chain() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let branch_id = populated_db(&path);
+
+        {
+            // Rewrite event 3 with a different payload but its original hashes
+            let db = Database::open(&path).unwrap();
+            let ns = Namespace::for_branch(branch_id);
+            let key = Key::new_event(ns, 3);
+            let stored = db.storage().get(&key).unwrap().unwrap().value;
+            let Value::String(json) = stored else {
+                panic!("event is not stored as a string")
+            };
+            let mut event: Event = serde_json::from_str(&json).unwrap();
+            event.payload = payload(42);
+            let tampered = Value::String(serde_json::to_string(&event).unwrap());
+            db.transaction(branch_id, |txn| {
+                txn.put(key.clone(), tampered.clone())?;
+                Ok(())
+            })
+            .unwrap();
+        }
+
+        let report = Database::fsck(&path, false, None).unwrap();
+        assert_eq!(report.findings.len(), 1, "{:?}", report.findings);
+        let finding = &report.findings[0];
+        assert_eq!(finding.component, FsckComponent::EventChain);
+        assert_eq!(finding.severity, FsckSeverity::Error);
+        assert!(finding.message.contains("event 3"));
+    }
+
+    #[test]
+    fn test_fsck_removes_orphaned_caches() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        populated_db(&path);
+
+        let orphan_branch = path.join("vectors").join(format!("{:032x}", 7u128));
+        std::fs::create_dir_all(&orphan_branch).unwrap();
+        std::fs::write(orphan_branch.join("gone.vec"), b"stale").unwrap();
+        std::fs::create_dir_all(path.join("search")).unwrap();
+        let orphan_segment = path.join("search").join("seg_999.sidx");
+        std::fs::write(&orphan_segment, b"stale").unwrap();
+
+        let report = Database::fsck(&path, false, None).unwrap();
+        let orphans: Vec<_> = report
+            .findings
+            .iter()
+            .map(|f| (f.component, f.severity))
+            .collect();
+        assert!(orphans.contains(&(FsckComponent::VectorCache, FsckSeverity::Warning)));
+        assert!(orphans.contains(&(FsckComponent::SearchCache, FsckSeverity::Warning)));
+        assert!(!report.has_errors());
+        assert!(orphan_branch.exists());
+
+        let report = Database::fsck(&path, true, None).unwrap();
+        assert_eq!(report.repaired(), report.findings.len());
+        assert!(!orphan_branch.exists());
+        assert!(!orphan_segment.exists());
+        assert!(Database::fsck(&path, false, None).unwrap().is_clean());
+        assert!(Database::open(&path).is_ok());
+    }
+}
@@ -19,16 +19,31 @@
 //!
 //! Per spec Section 4: Implicit transactions wrap legacy-style operations.
 
+mod backup;
+mod checkpoint;
 pub mod config;
+mod encryption;
+mod expiry;
+mod fsck;
 mod registry;
 mod transactions;
+mod wal_dump;
 
-pub use config::{ModelConfig, StrataConfig, SHADOW_EVENT, SHADOW_JSON, SHADOW_KV, SHADOW_STATE};
+pub use backup::{BackupInfo, RestoreInfo};
+pub use config::{
+    CheckpointConfig, ModelConfig, StrataConfig, SHADOW_EVENT, SHADOW_JSON, SHADOW_KV, SHADOW_STATE,
+};
+pub use encryption::{
+    configured_key, env_key_var, read_key_file, RekeyInfo, ENCRYPTION_KEY_ENV, ENCRYPTION_KEY_FILE_ENV,
+};
+pub use fsck::{FsckComponent, FsckFinding, FsckReport, FsckSeverity};
 pub use registry::OPEN_DATABASES;
 pub use transactions::RetryConfig;
+pub use wal_dump::{WalDumpFilter, WalDumpRecord, WalDumpWrite};
 
 use crate::background::BackgroundScheduler;
 use crate::coordinator::TransactionCoordinator;
+use crate::transaction::active::ActiveTransaction;
 use crate::transaction::TransactionPool;
 use dashmap::DashMap;
 use parking_lot::Mutex as ParkingMutex;
@@ -36,21 +51,26 @@ use std::any::{Any, TypeId};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use strata_concurrency::{RecoveryCoordinator, TransactionContext};
+use strata_concurrency::{
+    CheckpointPayload, IsolationLevel, RecoveryCoordinator, TransactionContext, TransactionPayload,
+};
 use strata_core::types::TypeTag;
 use strata_core::types::{BranchId, Key};
 use strata_core::StrataError;
 use strata_core::{StrataResult, VersionedValue};
-use strata_durability::codec::IdentityCodec;
-use strata_durability::wal::{DurabilityMode, WalConfig, WalWriter};
+use strata_durability::codec::{IdentityCodec, StorageCodec};
+use strata_durability::format::WalRecord;
+use strata_durability::wal::{
+    DurabilityMode, GroupCommit, WalConfig, WalReader, WalReaderError, WalWriter,
+};
 use strata_durability::{
-    BranchSnapshotEntry, EventSnapshotEntry, JsonSnapshotEntry, KvSnapshotEntry, StateSnapshotEntry,
+    list_snapshots, CheckpointCoordinator, CheckpointData, CheckpointError, CompactionError,
+    LocalWalArchive, ManifestError, ManifestManager, WalArchiver, WalOnlyCompactor,
 };
 use strata_durability::{
-    CheckpointCoordinator, CheckpointData, CheckpointError, CompactionError, ManifestError,
-    ManifestManager, WalOnlyCompactor,
+    BranchSnapshotEntry, EventSnapshotEntry, JsonSnapshotEntry, KvSnapshotEntry, StateSnapshotEntry,
 };
-use strata_storage::ShardedStore;
+use strata_storage::{ShardedStore, SpillConfig, SpillStats};
 use tracing::{info, warn};
 
 // ============================================================================
@@ -141,6 +161,10 @@ pub struct Database {
     /// Using parking_lot::Mutex to avoid lock poisoning on panic
     wal_writer: Option<Arc<ParkingMutex<WalWriter>>>,
 
+    /// Batches the per-commit fsyncs of concurrent committers
+    /// (`Always` durability only; the WAL writer defers its fsync to it)
+    group_commit: Option<GroupCommit>,
+
     /// Persistence mode (ephemeral vs disk-backed)
     persistence_mode: PersistenceMode,
 
@@ -170,6 +194,10 @@ pub struct Database {
     /// Unified configuration (mirrors strata.toml).
     config: parking_lot::RwLock<StrataConfig>,
 
+    /// Storage codec for WAL records, snapshots and branch bundles
+    /// (identity unless an encryption key is configured).
+    codec: Box<dyn StorageCodec>,
+
     /// Shutdown signal for the background WAL flush thread (Standard mode only)
     flush_shutdown: Arc<AtomicBool>,
 
@@ -182,6 +210,12 @@ pub struct Database {
     /// Background task scheduler for deferred work (embedding, GC, etc.)
     scheduler: BackgroundScheduler,
 
+    /// Checkpoint counters, run lock and the automatic checkpoint thread
+    checkpoint_state: checkpoint::CheckpointState,
+
+    /// Background thread that deletes keys whose TTL has run out
+    expiry_state: expiry::ExpiryState,
+
     /// Exclusive lock file preventing concurrent process access to the same database.
     ///
     /// Held for the lifetime of the Database. Dropped automatically when the
@@ -261,6 +295,23 @@ impl Database {
     /// let db = Database::open_with_config("/path/to/data", config)?;
     /// ```
     pub fn open_with_config<P: AsRef<Path>>(path: P, cfg: StrataConfig) -> StrataResult<Arc<Self>> {
+        Self::open_with_key(path, cfg, None)
+    }
+
+    /// Open database with an explicit configuration and encryption key.
+    ///
+    /// `key` (32 raw bytes or 64 hex characters) takes precedence over the
+    /// `STRATA_ENCRYPTION_KEY` / `STRATA_ENCRYPTION_KEY_FILE` environment
+    /// variables and `encryption_key_file` in the config. With no key from
+    /// any source the database is unencrypted.
+    ///
+    /// Opening an encrypted database without its key fails with a codec
+    /// mismatch; opening it with the wrong key fails with a decode error.
+    pub fn open_with_key<P: AsRef<Path>>(
+        path: P,
+        cfg: StrataConfig,
+        key: Option<&[u8]>,
+    ) -> StrataResult<Arc<Self>> {
         let data_dir = path.as_ref().to_path_buf();
         std::fs::create_dir_all(&data_dir).map_err(StrataError::from)?;
 
@@ -283,7 +334,8 @@ impl Database {
         let config_path = data_dir.join(config::CONFIG_FILE_NAME);
         cfg.write_to_file(&config_path)?;
 
-        let db = Self::open_with_mode_and_config(path, mode, cfg)?;
+        let codec = encryption::resolve_codec(&data_dir, &cfg, key)?;
+        let db = Self::open_with_mode_and_config(path, mode, cfg, codec)?;
         Ok(db)
     }
 
@@ -303,7 +355,7 @@ impl Database {
             durability: dur_str.to_string(),
             ..StrataConfig::default()
         };
-        Self::open_with_mode_and_config(path, durability_mode, cfg)
+        Self::open_with_mode_and_config(path, durability_mode, cfg, Box::new(IdentityCodec))
     }
 
     /// Open database with specific durability mode and full config.
@@ -318,6 +370,7 @@ impl Database {
     /// * `path` - Directory path for the database
     /// * `durability_mode` - Durability mode for WAL operations
     /// * `cfg` - Full configuration to store in the Database
+    /// * `codec` - Storage codec, verified against the MANIFEST
     ///
     /// # Returns
     ///
@@ -332,6 +385,7 @@ impl Database {
         path: P,
         durability_mode: DurabilityMode,
         cfg: StrataConfig,
+        codec: Box<dyn StorageCodec>,
     ) -> StrataResult<Arc<Self>> {
         // Create directory first so we can canonicalize the path
         let data_dir = path.as_ref().to_path_buf();
@@ -348,6 +402,16 @@ impl Database {
         // Check registry for existing instance
         if let Some(weak) = registry.get(&canonical_path) {
             if let Some(db) = weak.upgrade() {
+                // Never hand out an open encrypted database to a caller
+                // without its key
+                if !encryption::same_codec(db.codec(), codec.as_ref()) {
+                    return Err(StrataError::storage(format!(
+                        "cannot open database: codec or key differs from the open instance \
+                         (database uses '{}', caller supplied '{}')",
+                        db.codec().codec_id(),
+                        codec.codec_id()
+                    )));
+                }
                 info!(target: "strata::db", path = ?canonical_path, "Returning existing database instance");
                 return Ok(db);
             }
@@ -371,15 +435,31 @@ impl Database {
                 canonical_path.display()
             ))
         })?;
+        // Verify the codec (encryption key) before reading any data
+        encryption::check_or_init_manifest(&canonical_path, codec.as_ref())?;
+
         // Create WAL directory
         let wal_dir = data_dir.join("wal");
         std::fs::create_dir_all(&wal_dir).map_err(StrataError::from)?;
 
-        // Use RecoveryCoordinator for proper transaction-aware recovery
-        // This reads all WalRecords from the segmented WAL directory
-        let recovery = RecoveryCoordinator::new(wal_dir.clone());
+        // Use RecoveryCoordinator for proper transaction-aware recovery:
+        // load the latest checkpoint, then replay the WalRecords after it
+        let mut recovery = RecoveryCoordinator::new(wal_dir.clone()).with_codec(codec.clone_box());
+        let snapshot = checkpoint::checkpoint_snapshot(&canonical_path)?;
+        if let Some((_, path)) = &snapshot {
+            recovery = recovery.with_snapshot_path(path.clone());
+        }
+        if let Some(memory_budget) = cfg.memory_budget() {
+            // Spilled versions are scratch data; recovery rebuilds them
+            let dir = data_dir.join("spill");
+            std::fs::create_dir_all(&dir).map_err(StrataError::from)?;
+            recovery = recovery.with_spill(SpillConfig { dir, memory_budget });
+        }
         let result = match recovery.recover() {
             Ok(result) => result,
+            // Undecodable records mean the WAL is intact but unreadable with
+            // this codec; starting empty would silently hide the data
+            Err(e @ StrataError::Corruption { .. }) => return Err(e),
             Err(e) => {
                 warn!(
                     target: "strata::db",
@@ -392,6 +472,7 @@ impl Database {
 
         info!(
             target: "strata::db",
+            from_checkpoint = result.stats.from_checkpoint,
             txns_replayed = result.stats.txns_replayed,
             writes_applied = result.stats.writes_applied,
             deletes_applied = result.stats.deletes_applied,
@@ -404,12 +485,14 @@ impl Database {
             wal_dir,
             [0u8; 16], // database UUID placeholder
             durability_mode,
-            WalConfig::default(),
-            Box::new(IdentityCodec),
+            WalConfig::default().with_group_commit(durability_mode == DurabilityMode::Always),
+            codec.clone_box(),
         )?;
 
         // Create coordinator from recovery result (preserves version continuity)
         let coordinator = TransactionCoordinator::from_recovery(&result);
+        let checkpoint_state = checkpoint::CheckpointState::default();
+        checkpoint_state.restore_chain(&result.stats, snapshot.map(|(id, _)| id));
 
         let wal_arc = Arc::new(ParkingMutex::new(wal_writer));
         let flush_shutdown = Arc::new(AtomicBool::new(false));
@@ -444,15 +527,19 @@ impl Database {
             data_dir: canonical_path.clone(),
             storage: Arc::new(result.storage),
             wal_writer: Some(wal_arc),
+            group_commit: (durability_mode == DurabilityMode::Always).then(GroupCommit::new),
             persistence_mode: PersistenceMode::Disk,
             coordinator,
             durability_mode,
             accepting_transactions: AtomicBool::new(true),
             extensions: DashMap::new(),
             config: parking_lot::RwLock::new(cfg),
+            codec,
             flush_shutdown,
             flush_handle: ParkingMutex::new(flush_handle),
             scheduler: BackgroundScheduler::new(2, 4096),
+            checkpoint_state,
+            expiry_state: expiry::ExpiryState::default(),
             _lock_file: Some(lock_file),
         });
 
@@ -462,6 +549,12 @@ impl Database {
         // Release lock before running primitive recovery (may be slow)
         drop(registry);
 
+        // Restore copy-on-write links between forked branches so that
+        // primitive recovery sees the data they inherit.
+        let branch_index = crate::primitives::branch::BranchIndex::new(db.clone());
+        branch_index.relink_forked_branches()?;
+        branch_index.load_write_guards()?;
+
         // Run primitive recovery (e.g., VectorStore, Search Index)
         // This must happen AFTER KV recovery completes, as primitives may
         // depend on config data stored in KV.
@@ -476,6 +569,13 @@ impl Database {
             index.enable();
         }
 
+        checkpoint::start_policy_thread(&db)?;
+
+        // Recovered values with a TTL still need deleting once they expire
+        if db.storage.next_expiry().is_some() {
+            db.start_expiry_reaper();
+        }
+
         Ok(db)
     }
 
@@ -529,15 +629,19 @@ impl Database {
             data_dir: PathBuf::new(), // Empty path for ephemeral
             storage: Arc::new(storage),
             wal_writer: None, // No WAL for ephemeral
+            group_commit: None,
             persistence_mode: PersistenceMode::Ephemeral,
             coordinator,
             durability_mode: DurabilityMode::Cache, // Irrelevant but set for consistency
             accepting_transactions: AtomicBool::new(true),
             extensions: DashMap::new(),
             config: parking_lot::RwLock::new(StrataConfig::default()),
+            codec: Box::new(IdentityCodec),
             flush_shutdown: Arc::new(AtomicBool::new(false)),
             flush_handle: ParkingMutex::new(None),
             scheduler: BackgroundScheduler::new(2, 4096),
+            checkpoint_state: checkpoint::CheckpointState::default(),
+            expiry_state: expiry::ExpiryState::default(),
             _lock_file: None, // No lock for ephemeral databases
         });
 
@@ -584,23 +688,27 @@ impl Database {
 
     /// Get value at or before the given timestamp directly from storage.
     ///
-    /// This is a non-transactional read for time-travel queries.
+    /// This is a non-transactional read for time-travel queries. It fails
+    /// with `HistoryTrimmed` if retention has pruned the history it needs.
     pub(crate) fn get_at_timestamp(
         &self,
         key: &Key,
         max_timestamp: u64,
     ) -> StrataResult<Option<VersionedValue>> {
+        crate::retention::check_retained(self, &key.namespace.branch_id, max_timestamp)?;
         self.storage.get_at_timestamp(key, max_timestamp)
     }
 
     /// Scan keys matching a prefix at or before the given timestamp.
     ///
-    /// This is a non-transactional read for time-travel queries.
+    /// This is a non-transactional read for time-travel queries. It fails
+    /// with `HistoryTrimmed` if retention has pruned the history it needs.
     pub(crate) fn scan_prefix_at_timestamp(
         &self,
         prefix: &Key,
         max_timestamp: u64,
     ) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        crate::retention::check_retained(self, &prefix.namespace.branch_id, max_timestamp)?;
         self.storage.scan_prefix_at_timestamp(prefix, max_timestamp)
     }
 
@@ -624,12 +732,24 @@ impl Database {
         &self.data_dir
     }
 
-    /// Get current WAL counters snapshot.
+    /// Get current WAL counters snapshot, including checkpoint and
+    /// compaction counts.
     ///
     /// Returns `None` for ephemeral databases (no WAL).
     /// Briefly locks the WAL mutex to read counter values.
     pub fn durability_counters(&self) -> Option<strata_durability::WalCounters> {
-        self.wal_writer.as_ref().map(|w| w.lock().counters())
+        self.wal_writer.as_ref().map(|w| {
+            let mut counters = w.lock().counters();
+            self.checkpoint_state.fill_counters(&mut counters);
+            counters
+        })
+    }
+
+    /// Get spill tier counters, if `memory_budget_mb` is configured.
+    ///
+    /// Returns `None` for databases that keep everything in memory.
+    pub fn spill_stats(&self) -> Option<SpillStats> {
+        self.storage.spill_stats()
     }
 
     /// Check if the database is currently open and accepting transactions
@@ -779,7 +899,7 @@ impl Database {
     ///
     /// Removes old versions from version chains across all entries in the branch.
     /// Returns the number of pruned versions.
-    pub fn gc_versions_before(&self, branch_id: BranchId, min_version: u64) -> usize {
+    pub fn gc_versions_before(&self, branch_id: BranchId, min_version: u64) -> StrataResult<usize> {
         self.storage.gc_branch(branch_id, min_version)
     }
 
@@ -822,15 +942,82 @@ impl Database {
         }
     }
 
+    /// Visit the committed transactions of a branch recorded in the WAL,
+    /// newest first.
+    ///
+    /// The WAL is flushed first so buffered commits are included. Segments
+    /// are read newest first, and records of other branches are skipped
+    /// before their payload is decoded. The walk stops as soon as `visit`
+    /// returns `false`, so a caller after recent commits only reads the
+    /// newest segments. Only transactions still in the WAL are visited:
+    /// segments removed by compaction after a checkpoint are gone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for databases without a WAL (ephemeral databases
+    /// and `cache` durability), or the first error returned by `visit`.
+    pub(crate) fn visit_branch_transactions<F>(
+        &self,
+        branch_id: BranchId,
+        mut visit: F,
+    ) -> StrataResult<()>
+    where
+        F: FnMut(&WalRecord, TransactionPayload) -> StrataResult<bool>,
+    {
+        if self.wal_writer.is_none() {
+            return Err(StrataError::invalid_input(
+                "Commit history requires a database with a write-ahead log",
+            ));
+        }
+        self.flush()?;
+
+        let wal_dir = self.data_dir.join("wal");
+        let reader = WalReader::new(self.codec.clone_box());
+        let read_failed =
+            |e: WalReaderError| StrataError::storage(format!("WAL read failed: {}", e));
+        let segments = reader.list_segments(&wal_dir).map_err(read_failed)?;
+        let branch_bytes = *branch_id.as_bytes();
+
+        for segment in segments.into_iter().rev() {
+            let (records, _, _, _) = reader
+                .read_segment(&wal_dir, segment)
+                .map_err(read_failed)?;
+            for record in records.iter().rev() {
+                if record.branch_id != branch_bytes {
+                    continue;
+                }
+                let payload = TransactionPayload::from_bytes(&record.writeset).map_err(|e| {
+                    StrataError::storage(format!(
+                        "Failed to decode transaction payload for txn {}: {}",
+                        record.txn_id, e
+                    ))
+                })?;
+                if !visit(record, payload)? {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
     // ========================================================================
     // Checkpoint & Compaction
     // ========================================================================
 
     /// Create a snapshot checkpoint of the current database state.
     ///
-    /// Checkpoints serialize all primitive state to a crash-safe snapshot file
-    /// and update the MANIFEST watermark. After a checkpoint, WAL compaction
-    /// can safely remove segments covered by the snapshot.
+    /// Checkpoints write the storage state (every version, so history and
+    /// time-travel reads survive) to a crash-safe snapshot file and update
+    /// the MANIFEST watermark. Recovery loads the snapshot and only replays
+    /// WAL records committed after it, and WAL compaction can then remove
+    /// the segments the snapshot covers.
+    ///
+    /// After a full snapshot, checkpoints are incremental: they only write
+    /// the versions committed since the previous checkpoint and reference
+    /// the snapshots they build on. Once `max_deltas` incremental snapshots
+    /// have been chained (see [`CheckpointConfig`]), the next checkpoint
+    /// writes a full snapshot again. Snapshots no longer part of the chain
+    /// are deleted.
     ///
     /// For ephemeral (cache) databases, this is a no-op.
     ///
@@ -839,14 +1026,54 @@ impl Database {
         if self.persistence_mode == PersistenceMode::Ephemeral {
             return Ok(());
         }
+        let _run = self.checkpoint_state.run_lock.lock();
+        let max_deltas = self
+            .config
+            .read()
+            .checkpoint
+            .as_ref()
+            .map_or(CheckpointConfig::default().max_deltas, |c| c.max_deltas);
+        let mut chain = self.checkpoint_state.chain.lock().clone();
+        let incremental =
+            !chain.snapshots.is_empty() && chain.snapshots.len() <= max_deltas as usize;
+
+        // Pin the checkpoint version under the WAL lock: commits allocate
+        // their version, append to the WAL and apply to storage while
+        // holding it, so every commit at or below the pinned version is in
+        // storage and in a segment no later than the current one.
+        let (version, max_txn_id, checkpoint_segment) = {
+            let mut wal = self.wal_writer.as_ref().map(|w| w.lock());
+            let segment = match wal.as_mut() {
+                Some(wal) => {
+                    wal.flush().map_err(StrataError::from)?;
+                    wal.current_segment()
+                }
+                None => 0,
+            };
+            (
+                self.coordinator.current_version(),
+                self.coordinator.last_txn_id(),
+                segment,
+            )
+        };
 
-        // Flush WAL first to ensure all buffered writes are on disk
-        self.flush()?;
-
-        let watermark_txn = self.coordinator.current_version();
+        // Capture without the lock: later commits only add versions above
+        // the pinned one, which the capture leaves out.
+        let base_version = if incremental { chain.version } else { 0 };
+        let mut state =
+            CheckpointPayload::capture_since(&self.storage, base_version, version, max_txn_id)?;
+        if incremental {
+            state.parents = chain.snapshots.clone();
+        }
+        let watermark_txn = state.max_txn_id;
 
-        // Collect data from storage
-        let data = self.collect_checkpoint_data();
+        // Per-primitive sections are only written with full snapshots
+        let data = if incremental {
+            CheckpointData::new()
+        } else {
+            self.collect_checkpoint_data()?
+        }
+        .with_storage(state.to_bytes());
 
         // Create snapshots directory
         let snapshots_dir = self.data_dir.join("snapshots");
@@ -869,14 +1096,14 @@ impl Database {
         // Create CheckpointCoordinator
         let mut coordinator = if let Some(wm) = existing_watermark {
             CheckpointCoordinator::with_watermark(
-                snapshots_dir,
-                Box::new(IdentityCodec),
+                snapshots_dir.clone(),
+                self.codec.clone_box(),
                 [0u8; 16],
                 wm,
             )
             .map_err(|e| StrataError::internal(format!("checkpoint coordinator: {}", e)))?
         } else {
-            CheckpointCoordinator::new(snapshots_dir, Box::new(IdentityCodec), [0u8; 16])
+            CheckpointCoordinator::new(snapshots_dir.clone(), self.codec.clone_box(), [0u8; 16])
                 .map_err(|e| StrataError::internal(format!("checkpoint coordinator: {}", e)))?
         };
 
@@ -887,27 +1114,77 @@ impl Database {
                 StrataError::internal(format!("checkpoint failed: {}", e))
             })?;
 
-        // Update MANIFEST with snapshot watermark
+        // Update MANIFEST with the snapshot watermark and the first WAL
+        // segment that may hold commits the snapshot does not cover
+        manifest.manifest_mut().active_wal_segment = checkpoint_segment;
         manifest
             .set_snapshot_watermark(info.snapshot_id, info.watermark_txn)
             .map_err(|e: ManifestError| {
                 StrataError::internal(format!("manifest update failed: {}", e))
             })?;
 
+        if !incremental {
+            chain.snapshots.clear();
+        }
+        chain.snapshots.push(info.snapshot_id);
+        chain.version = state.version;
+        *self.checkpoint_state.chain.lock() = chain.clone();
+
+        // Older snapshots outside the chain are no longer needed
+        for (snapshot_id, path) in list_snapshots(&snapshots_dir).map_err(StrataError::from)? {
+            if snapshot_id < info.snapshot_id && !chain.snapshots.contains(&snapshot_id) {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    warn!(
+                        target: "strata::db",
+                        snapshot_id,
+                        error = %e,
+                        "Failed to remove old snapshot"
+                    );
+                }
+            }
+        }
+
+        self.checkpoint_state.record_checkpoint();
         info!(
             target: "strata::db",
             snapshot_id = info.snapshot_id,
             watermark_txn = info.watermark_txn,
+            version = state.version,
+            incremental,
             "Checkpoint created"
         );
 
         Ok(())
     }
 
+    /// Archive WAL segments with `archiver` before compaction removes them.
+    ///
+    /// Takes precedence over `wal_archive_dir` in `strata.toml`. Pass `None`
+    /// to go back to the configured archive directory, if any.
+    pub fn set_wal_archiver(&self, archiver: Option<Arc<dyn WalArchiver>>) {
+        *self.checkpoint_state.archiver.lock() = archiver;
+    }
+
+    /// The archiver compaction hands segments to, if any.
+    fn wal_archiver(&self) -> StrataResult<Option<Arc<dyn WalArchiver>>> {
+        if let Some(archiver) = self.checkpoint_state.archiver.lock().clone() {
+            return Ok(Some(archiver));
+        }
+        let Some(dir) = self.config.read().wal_archive_dir.clone() else {
+            return Ok(None);
+        };
+        let archive = LocalWalArchive::new(self.data_dir.join(&dir)).map_err(|e| {
+            StrataError::storage(format!("failed to open WAL archive '{}': {}", dir, e))
+        })?;
+        Ok(Some(Arc::new(archive)))
+    }
+
     /// Compact WAL segments that are no longer needed for recovery.
     ///
-    /// Removes closed WAL segments whose max transaction ID is at or below the
-    /// latest snapshot watermark. The active segment is never removed.
+    /// Removes closed WAL segments that the latest checkpoint covers. The
+    /// segment that was active when the checkpoint was taken, and every
+    /// later one, is kept. With a WAL archiver configured, each segment is
+    /// archived first; segments that fail to archive are kept.
     ///
     /// A checkpoint must exist before compaction can run. For ephemeral (cache)
     /// databases, this is a no-op.
@@ -917,6 +1194,7 @@ impl Database {
         if self.persistence_mode == PersistenceMode::Ephemeral {
             return Ok(());
         }
+        let _run = self.checkpoint_state.run_lock.lock();
 
         let wal_dir = self.data_dir.join("wal");
 
@@ -925,7 +1203,11 @@ impl Database {
         let manifest_arc = Arc::new(parking_lot::Mutex::new(manifest));
 
         // Create compactor and run
-        let compactor = WalOnlyCompactor::new(wal_dir, manifest_arc);
+        let mut compactor =
+            WalOnlyCompactor::new(wal_dir, manifest_arc).with_codec(self.codec.clone_box());
+        if let Some(archiver) = self.wal_archiver()? {
+            compactor = compactor.with_archiver(archiver);
+        }
         let compact_info = compactor.compact().map_err(|e: CompactionError| match e {
             CompactionError::NoSnapshot => StrataError::invalid_input(
                 "No checkpoint exists yet. Run checkpoint() before compact().".to_string(),
@@ -933,9 +1215,15 @@ impl Database {
             other => StrataError::internal(format!("compaction failed: {}", other)),
         })?;
 
+        self.checkpoint_state.record_compaction(
+            compact_info.wal_segments_removed as u64,
+            compact_info.reclaimed_bytes,
+            compact_info.wal_segments_archived as u64,
+        );
         info!(
             target: "strata::db",
             segments_removed = compact_info.wal_segments_removed,
+            segments_archived = compact_info.wal_segments_archived,
             bytes_reclaimed = compact_info.reclaimed_bytes,
             "WAL compaction completed"
         );
@@ -944,7 +1232,7 @@ impl Database {
     }
 
     /// Collect all primitive data from storage for checkpointing.
-    fn collect_checkpoint_data(&self) -> CheckpointData {
+    fn collect_checkpoint_data(&self) -> StrataResult<CheckpointData> {
         let mut kv_entries = Vec::new();
         let mut event_entries = Vec::new();
         let mut state_entries = Vec::new();
@@ -955,7 +1243,7 @@ impl Database {
 
         for branch_id in self.storage.branch_ids() {
             // KV entries
-            for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::KV) {
+            for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::KV)? {
                 let value_bytes = serde_json::to_vec(&vv.value).unwrap_or_default();
                 kv_entries.push(KvSnapshotEntry {
                     key: key.user_key_string().unwrap_or_default(),
@@ -966,7 +1254,7 @@ impl Database {
             }
 
             // Event entries
-            for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::Event) {
+            for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::Event)? {
                 // Skip metadata keys
                 if key.user_key == b"__meta__" || key.user_key.starts_with(b"__tidx__") {
                     continue;
@@ -985,7 +1273,7 @@ impl Database {
             }
 
             // State entries
-            for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::State) {
+            for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::State)? {
                 let value_bytes = serde_json::to_vec(&vv.value).unwrap_or_default();
                 state_entries.push(StateSnapshotEntry {
                     name: key.user_key_string().unwrap_or_default(),
@@ -996,7 +1284,7 @@ impl Database {
             }
 
             // Branch entries
-            for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::Branch) {
+            for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::Branch)? {
                 // Skip index keys
                 if key.user_key.starts_with(b"__idx_") {
                     continue;
@@ -1016,7 +1304,7 @@ impl Database {
             }
 
             // JSON entries
-            for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::Json) {
+            for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::Json)? {
                 let content = serde_json::to_vec(&vv.value).unwrap_or_default();
                 json_entries.push(JsonSnapshotEntry {
                     doc_id: key.user_key_string().unwrap_or_default(),
@@ -1043,36 +1331,40 @@ impl Database {
         if !json_entries.is_empty() {
             data = data.with_json(json_entries);
         }
-        data
+        Ok(data)
     }
 
     /// Load an existing MANIFEST or create a new one.
     ///
-    /// Also updates the active WAL segment from the current WAL writer.
+    /// A new MANIFEST starts with the current WAL segment as its active
+    /// segment. An existing one keeps the segment recorded by the last
+    /// checkpoint, which bounds what compaction may remove.
     fn load_or_create_manifest(&self) -> StrataResult<ManifestManager> {
         let manifest_path = self.data_dir.join("MANIFEST");
 
-        let mut manifest = if ManifestManager::exists(&manifest_path) {
+        let manifest = if ManifestManager::exists(&manifest_path) {
             ManifestManager::load(manifest_path).map_err(|e: ManifestError| {
                 StrataError::internal(format!("failed to load MANIFEST: {}", e))
             })?
         } else {
-            ManifestManager::create(manifest_path, [0u8; 16], "identity".to_string()).map_err(
-                |e: ManifestError| {
-                    StrataError::internal(format!("failed to create MANIFEST: {}", e))
-                },
-            )?
-        };
-
-        // Update active WAL segment from the writer
-        if let Some(ref wal) = self.wal_writer {
-            let wal = wal.lock();
-            let current_seg = wal.current_segment();
-            manifest.manifest_mut().active_wal_segment = current_seg;
+            let mut manifest = ManifestManager::create(
+                manifest_path,
+                [0u8; 16],
+                self.codec.codec_id().to_string(),
+            )
+            .map_err(|e: ManifestError| {
+                StrataError::internal(format!("failed to create MANIFEST: {}", e))
+            })?;
+            manifest.manifest_mut().key_check =
+                strata_durability::codec::make_key_check(self.codec.as_ref());
+            if let Some(ref wal) = self.wal_writer {
+                manifest.manifest_mut().active_wal_segment = wal.lock().current_segment();
+            }
             manifest.persist().map_err(|e: ManifestError| {
                 StrataError::internal(format!("failed to persist MANIFEST: {}", e))
             })?;
-        }
+            manifest
+        };
 
         Ok(manifest)
     }
@@ -1088,6 +1380,21 @@ impl Database {
                 "Database is shutting down".to_string(),
             ));
         }
+        self.check_wal_healthy()
+    }
+
+    /// Fail once a group-commit sync has failed.
+    ///
+    /// Commits are visible before their sync completes, so a failed sync
+    /// leaves visible data that may not be on disk. The database then
+    /// stops accepting transactions until it is reopened, which recovers
+    /// from whatever the WAL actually holds.
+    fn check_wal_healthy(&self) -> StrataResult<()> {
+        if self.group_commit.as_ref().is_some_and(|g| g.is_poisoned()) {
+            return Err(StrataError::storage(
+                "Database stopped after a failed WAL sync; reopen it to recover".to_string(),
+            ));
+        }
         Ok(())
     }
 
@@ -1125,6 +1432,12 @@ impl Database {
     /// - Validates and commits on success
     /// - Aborts on error
     ///
+    /// Calling `db.transaction` again from inside the closure, on the same
+    /// thread and branch, nests: the inner closure runs as a savepoint on
+    /// the outer transaction (as `txn.transaction(...)` does). If it fails,
+    /// only its own work is undone; if it succeeds, its work commits or
+    /// rolls back with the outer transaction.
+    ///
     /// # Arguments
     /// * `branch_id` - BranchId for namespace isolation
     /// * `f` - Closure that performs transaction operations
@@ -1145,14 +1458,64 @@ impl Database {
     where
         F: FnOnce(&mut TransactionContext) -> StrataResult<T>,
     {
+        let db_id = self as *const Database as usize;
+        if let Some(outer) = ActiveTransaction::find(db_id, branch_id) {
+            // SAFETY: the pointer was registered by an enclosing call of this
+            // method on this thread, whose context outlives the registration.
+            // That call is blocked in its closure until we return, and every
+            // reference handed to closures is derived from the same pointer.
+            return unsafe { (*outer).transaction(f) };
+        }
+
         self.check_accepting()?;
         let mut txn = self.begin_transaction(branch_id);
-        let result = f(&mut txn);
+        let txn_ptr: *mut TransactionContext = &mut txn;
+        let result = {
+            let _active = ActiveTransaction::register(db_id, branch_id, txn_ptr);
+            // SAFETY: `txn_ptr` points at `txn`, which is not otherwise
+            // touched until the registration is dropped.
+            f(unsafe { &mut *txn_ptr })
+        };
         let outcome = self.run_single_attempt(&mut txn, result, self.durability_mode);
         self.end_transaction(txn);
         outcome.map(|(value, _)| value)
     }
 
+    /// Execute a transaction at the given isolation level
+    ///
+    /// Like `transaction()`, but with `IsolationLevel::Serializable` every
+    /// `scan_prefix` made by the closure is re-checked at commit, and the
+    /// transaction aborts if a concurrent commit inserted a key under a
+    /// scanned prefix. Use it for invariants that span several keys, such as
+    /// a budget checked by summing a range.
+    ///
+    /// # Example
+    /// ```text
+    /// db.transaction_with_isolation(branch_id, IsolationLevel::Serializable, |txn| {
+    ///     let spent: i64 = sum(txn.scan_prefix(&alloc_prefix)?);
+    ///     if spent + amount > budget { return Err(...); }
+    ///     txn.put(new_alloc_key, Value::Int(amount))
+    /// })?;
+    /// ```
+    pub fn transaction_with_isolation<F, T>(
+        &self,
+        branch_id: BranchId,
+        isolation: IsolationLevel,
+        f: F,
+    ) -> StrataResult<T>
+    where
+        F: FnOnce(&mut TransactionContext) -> StrataResult<T>,
+    {
+        self.transaction(branch_id, |txn| {
+            // A nested call runs on the outer transaction: raise its level,
+            // never lower it
+            if isolation == IsolationLevel::Serializable {
+                txn.set_isolation(isolation);
+            }
+            f(txn)
+        })
+    }
+
     /// Execute a transaction and return both the result and commit version
     ///
     /// Like `transaction()` but also returns the commit version assigned to all writes.
@@ -1340,6 +1703,18 @@ impl Database {
     /// - WAL writing (when WAL reference is provided)
     /// - Storage application
     /// - Fsync (WAL::append handles fsync based on its DurabilityMode)
+    ///
+    /// In `Always` mode the fsync is left to group commit: the commit is
+    /// appended and applied under the WAL lock, and once the lock is
+    /// released the committer waits for a sync covering its record, which
+    /// it shares with whoever else committed meanwhile. The commit is only
+    /// acknowledged after that sync; concurrent readers may see its writes
+    /// slightly earlier, but any commit that depends on them is appended
+    /// later and so cannot become durable without them. Publishing only
+    /// after the sync would mean holding the WAL lock across it, which
+    /// serializes committers again, so this is a deliberate trade-off: if
+    /// the sync fails, the commit reports an error although its writes are
+    /// visible, and the database stops (see `docs/architecture/durability-modes.md`).
     fn commit_internal(
         &self,
         txn: &mut TransactionContext,
@@ -1353,9 +1728,36 @@ impl Database {
         } else {
             None
         };
+        if wal_guard.is_some() {
+            self.check_wal_healthy()?;
+        }
         let wal_ref = wal_guard.as_deref_mut();
 
-        self.coordinator.commit(txn, self.storage.as_ref(), wal_ref)
+        let version = self
+            .coordinator
+            .commit(txn, self.storage.as_ref(), wal_ref)?;
+
+        if let (Some(group), Some(wal), Some(guard)) =
+            (&self.group_commit, &self.wal_writer, wal_guard)
+        {
+            let seq = guard.append_seq();
+            drop(guard);
+            // The commit is already visible, so a failed sync is fatal: the
+            // group stays poisoned and no further transaction is accepted.
+            if let Err(e) = group.wait_durable(wal, seq) {
+                tracing::error!(
+                    target: "strata::db",
+                    version,
+                    error = %e,
+                    "WAL sync failed; database stops accepting transactions"
+                );
+                return Err(StrataError::storage(format!(
+                    "WAL sync failed; database stopped, version {} may not be durable: {}",
+                    version, e
+                )));
+            }
+        }
+        Ok(version)
     }
 
     // ========================================================================
@@ -1447,7 +1849,11 @@ impl Database {
         // Stop accepting new transactions
         self.accepting_transactions.store(false, Ordering::SeqCst);
 
-        // Drain background tasks (embeddings etc.) before final WAL flush
+        // Stop scheduling automatic checkpoints and expired key cleanup
+        self.checkpoint_state.stop();
+        self.expiry_state.stop();
+
+        // Drain background tasks (embeddings, checkpoints etc.) before final WAL flush
         self.scheduler.drain();
 
         // Signal the background flush thread to stop
@@ -1486,6 +1892,10 @@ impl Drop for Database {
         // Shut down the background task scheduler
         self.scheduler.shutdown();
 
+        // Stop the automatic checkpoint and expiry threads
+        self.checkpoint_state.stop();
+        self.expiry_state.stop();
+
         // Stop the background flush thread
         self.flush_shutdown.store(true, Ordering::SeqCst);
         if let Some(handle) = self.flush_handle.lock().take() {
@@ -1546,6 +1956,9 @@ mod tests {
             version,
             puts,
             deletes,
+            message: None,
+            metadata: Default::default(),
+            ttls: Vec::new(),
         };
         let record = WalRecord::new(
             txn_id,
@@ -1768,6 +2181,108 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_always_mode_commit_is_synced_before_return() {
+        let temp_dir = TempDir::new().unwrap();
+        let db =
+            Database::open_with_mode(temp_dir.path().join("db"), DurabilityMode::Always).unwrap();
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+
+        for i in 0..3 {
+            db.transaction(branch_id, |txn| {
+                txn.put(Key::new_kv(ns.clone(), format!("k{}", i)), Value::Int(i))?;
+                Ok(())
+            })
+            .unwrap();
+            // Sequential commits each get their own sync
+            let counters = db.durability_counters().unwrap();
+            assert_eq!(counters.sync_calls, counters.wal_appends);
+        }
+    }
+
+    #[test]
+    fn test_always_mode_group_commit_concurrent() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let threads = 8;
+        let per_thread = 25;
+
+        {
+            let db = Database::open_with_mode(&db_path, DurabilityMode::Always).unwrap();
+            let handles: Vec<_> = (0..threads)
+                .map(|t| {
+                    let db = Arc::clone(&db);
+                    std::thread::spawn(move || {
+                        let ns = create_test_namespace(branch_id);
+                        for i in 0..per_thread {
+                            db.transaction(branch_id, |txn| {
+                                let key = Key::new_kv(ns.clone(), format!("t{}_{}", t, i));
+                                txn.put(key, Value::Int(i))?;
+                                Ok(())
+                            })
+                            .unwrap();
+                        }
+                    })
+                })
+                .collect();
+            for handle in handles {
+                handle.join().unwrap();
+            }
+
+            let counters = db.durability_counters().unwrap();
+            assert!(counters.wal_appends >= (threads * per_thread) as u64);
+            assert!(counters.sync_calls <= counters.wal_appends);
+        }
+
+        let db = Database::open_with_mode(&db_path, DurabilityMode::Always).unwrap();
+        let ns = create_test_namespace(branch_id);
+        for t in 0..threads {
+            for i in 0..per_thread {
+                let key = Key::new_kv(ns.clone(), format!("t{}_{}", t, i));
+                let stored = db.storage().get(&key).unwrap().unwrap();
+                assert_eq!(stored.value, Value::Int(i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_always_mode_failed_sync_stops_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let a = Key::new_kv(ns.clone(), "a");
+        let b = Key::new_kv(ns, "b");
+
+        {
+            let db = Database::open_with_mode(&db_path, DurabilityMode::Always).unwrap();
+            db.transaction(branch_id, |txn| txn.put(a.clone(), Value::Int(1)))
+                .unwrap();
+
+            db.group_commit.as_ref().unwrap().fail_next_sync("injected");
+            let err = db
+                .transaction(branch_id, |txn| txn.put(b.clone(), Value::Int(2)))
+                .unwrap_err();
+            assert!(err.to_string().contains("WAL sync failed"), "{err}");
+            // Writes are published before the sync, so they stay visible
+            assert_eq!(db.storage().get(&b).unwrap().unwrap().value, Value::Int(2));
+
+            let err = db
+                .transaction(branch_id, |txn| txn.put(a.clone(), Value::Int(3)))
+                .unwrap_err();
+            assert!(err.to_string().contains("reopen"), "{err}");
+            assert_eq!(db.storage().get(&a).unwrap().unwrap().value, Value::Int(1));
+        }
+
+        // Reopening recovers from what the WAL holds
+        let db = Database::open_with_mode(&db_path, DurabilityMode::Always).unwrap();
+        assert_eq!(db.storage().get(&a).unwrap().unwrap().value, Value::Int(1));
+        db.transaction(branch_id, |txn| txn.put(a.clone(), Value::Int(4)))
+            .unwrap();
+    }
+
     #[test]
     fn test_flush() {
         let temp_dir = TempDir::new().unwrap();
@@ -1886,6 +2401,115 @@ mod tests {
         assert!(db.storage().get(&key).unwrap().is_none());
     }
 
+    #[test]
+    fn test_serializable_transaction_detects_phantom() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let prefix = Key::new_kv(ns.clone(), "alloc:");
+
+        // T1 scans the range, then T2 inserts into it and commits first
+        let mut t1 = db.begin_transaction(branch_id);
+        t1.set_isolation(IsolationLevel::Serializable);
+        assert!(t1.scan_prefix(&prefix).unwrap().is_empty());
+        t1.put(Key::new_kv(ns.clone(), "alloc:a"), Value::Int(60))
+            .unwrap();
+
+        db.transaction_with_isolation(branch_id, IsolationLevel::Serializable, |txn| {
+            assert!(txn.scan_prefix(&prefix)?.is_empty());
+            txn.put(Key::new_kv(ns.clone(), "alloc:b"), Value::Int(60))
+        })
+        .unwrap();
+
+        let err = db.commit_transaction(&mut t1).unwrap_err();
+        db.end_transaction(t1);
+        assert!(matches!(err, StrataError::TransactionAborted { .. }));
+        assert!(err.to_string().contains("\"alloc:b\""), "{}", err);
+        assert!(db
+            .storage()
+            .get(&Key::new_kv(ns, "alloc:a"))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_nested_transaction_acts_as_savepoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let kept = Key::new_kv(ns.clone(), "kept");
+        let undone = Key::new_kv(ns, "undone");
+
+        db.transaction(branch_id, |txn| {
+            txn.put(kept.clone(), Value::Int(1))?;
+            let step: StrataResult<()> = txn.transaction(|txn| {
+                txn.put(undone.clone(), Value::Int(2))?;
+                Err(StrataError::invalid_input("tool failed".to_string()))
+            });
+            assert!(step.is_err());
+            Ok(())
+        })
+        .unwrap();
+
+        assert!(db.storage().get(&kept).unwrap().is_some());
+        assert!(db.storage().get(&undone).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_nested_db_transaction_rolls_back_with_outer() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let outer = Key::new_kv(ns.clone(), "outer");
+        let inner = Key::new_kv(ns, "inner");
+
+        let result: StrataResult<()> = db.transaction(branch_id, |txn| {
+            txn.put(outer.clone(), Value::Int(1))?;
+            db.transaction(branch_id, |nested| {
+                // The nested call sees the outer transaction's writes
+                assert_eq!(nested.get(&outer)?, Some(Value::Int(1)));
+                nested.put(inner.clone(), Value::Int(2))
+            })?;
+            assert_eq!(txn.get(&inner)?, Some(Value::Int(2)));
+            Err(StrataError::invalid_input("outer failed".to_string()))
+        });
+        assert!(result.is_err());
+
+        assert!(db.storage().get(&outer).unwrap().is_none());
+        assert!(db.storage().get(&inner).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_failed_nested_db_transaction_undoes_only_its_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let kept = Key::new_kv(ns.clone(), "kept");
+        let undone = Key::new_kv(ns, "undone");
+
+        db.transaction(branch_id, |txn| {
+            txn.put(kept.clone(), Value::Int(1))?;
+            let attempt: StrataResult<()> = db.transaction(branch_id, |nested| {
+                nested.put(undone.clone(), Value::Int(2))?;
+                Err(StrataError::invalid_input("step failed".to_string()))
+            });
+            assert!(attempt.is_err());
+            Ok(())
+        })
+        .unwrap();
+
+        assert!(db.storage().get(&kept).unwrap().is_some());
+        assert!(db.storage().get(&undone).unwrap().is_none());
+    }
+
     #[test]
     fn test_begin_and_commit_manual() {
         let temp_dir = TempDir::new().unwrap();
@@ -2109,6 +2733,56 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_open_with_memory_budget_spills_and_recovers() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("budget");
+        std::fs::create_dir_all(&db_path).unwrap();
+        std::fs::write(db_path.join("strata.toml"), "memory_budget_mb = 1\n").unwrap();
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = |i: usize| Key::new_kv(ns.clone(), format!("key:{:03}", i));
+        let value =
+            |round: usize, i: usize| Value::String(format!("{}:{}:{}", round, i, "x".repeat(4096)));
+
+        let check = |db: &Database| {
+            for i in 0..100 {
+                let history = db.get_history(&key(i), None, None).unwrap();
+                assert_eq!(history.len(), 3);
+                for (round, vv) in history.iter().rev().enumerate() {
+                    assert_eq!(vv.value, value(round, i));
+                }
+                let latest = db.storage().get(&key(i)).unwrap().unwrap();
+                assert_eq!(latest.value, value(2, i));
+            }
+        };
+
+        {
+            let db = Database::open(&db_path).unwrap();
+            for round in 0..3 {
+                for i in 0..100 {
+                    db.transaction(branch_id, |txn| {
+                        txn.put(key(i), value(round, i))?;
+                        Ok(())
+                    })
+                    .unwrap();
+                }
+            }
+            let stats = db.spill_stats().unwrap();
+            assert!(stats.evictions > 0);
+            assert!(stats.spilled_bytes > 0);
+            assert!(stats.resident_bytes <= 1 << 20);
+            check(&db);
+        }
+
+        // Recovery replays into a store with the same budget
+        let db = Database::open(&db_path).unwrap();
+        assert!(db.spill_stats().unwrap().evictions > 0);
+        check(&db);
+        assert!(Database::cache().unwrap().spill_stats().is_none());
+    }
+
     // ========================================================================
     // Checkpoint & Compaction Tests
     // ========================================================================
@@ -2189,4 +2863,260 @@ mod tests {
         // Now compact should succeed
         assert!(db.compact().is_ok());
     }
+
+    #[test]
+    fn test_checkpoint_compact_and_recover() {
+        use strata_durability::format::WalSegment;
+
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let a = Key::new_kv(ns.clone(), "a");
+        let b = Key::new_kv(ns, "b");
+        let put = |db: &Database, key: &Key, v: i64| {
+            db.transaction(branch_id, |txn| {
+                txn.put(key.clone(), Value::Int(v))?;
+                Ok(())
+            })
+            .unwrap();
+        };
+
+        {
+            let db = Database::open(&db_path).unwrap();
+            put(&db, &a, 1);
+            put(&db, &a, 2);
+        }
+
+        // Start a second segment, as rotation would, so the first can be compacted
+        let wal_dir = db_path.canonicalize().unwrap().join("wal");
+        WalSegment::create(&wal_dir, 2, [0u8; 16]).unwrap();
+
+        {
+            let db = Database::open(&db_path).unwrap();
+            put(&db, &b, 3);
+            db.checkpoint().unwrap();
+            db.compact().unwrap();
+            assert!(!WalSegment::segment_path(&wal_dir, 1).exists());
+
+            let counters = db.durability_counters().unwrap();
+            assert_eq!(counters.checkpoints, 1);
+            assert_eq!(counters.compactions, 1);
+            assert_eq!(counters.wal_segments_removed, 1);
+            assert!(counters.wal_bytes_reclaimed > 0);
+
+            // Written after the checkpoint: recovered from the WAL
+            put(&db, &a, 4);
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        let latest = db.storage().get(&a).unwrap().unwrap();
+        assert_eq!(latest.value, Value::Int(4));
+        assert_eq!(db.storage().get(&b).unwrap().unwrap().value, Value::Int(3));
+        // History written before the checkpoint survives compaction
+        let first = db
+            .storage()
+            .get_versioned(&a, latest.version.as_u64() - 3)
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.value, Value::Int(1));
+
+        // New commits continue after the recovered version
+        put(&db, &b, 5);
+        assert!(db.storage().get(&b).unwrap().unwrap().version > latest.version);
+    }
+
+    #[test]
+    fn test_checkpoint_during_commits_recovers_all_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+
+        {
+            let db = Database::open(&db_path).unwrap();
+            let writer = {
+                let db = db.clone();
+                let ns = ns.clone();
+                std::thread::spawn(move || {
+                    for i in 0..200 {
+                        db.transaction(branch_id, |txn| {
+                            txn.put(Key::new_kv(ns.clone(), &format!("k{}", i)), Value::Int(i))
+                        })
+                        .unwrap();
+                    }
+                })
+            };
+            while !writer.is_finished() {
+                db.checkpoint().unwrap();
+            }
+            writer.join().unwrap();
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        for i in 0..200 {
+            let key = Key::new_kv(ns.clone(), &format!("k{}", i));
+            assert_eq!(
+                db.storage().get(&key).unwrap().unwrap().value,
+                Value::Int(i)
+            );
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_removes_older_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let cfg = StrataConfig {
+            checkpoint: Some(CheckpointConfig {
+                max_deltas: 0,
+                ..CheckpointConfig::default()
+            }),
+            ..StrataConfig::default()
+        };
+        let db = Database::open_with_config(&db_path, cfg).unwrap();
+
+        db.checkpoint().unwrap();
+        db.checkpoint().unwrap();
+
+        let snapshots_dir = db_path.canonicalize().unwrap().join("snapshots");
+        let ids: Vec<u64> = list_snapshots(&snapshots_dir)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[test]
+    fn test_incremental_checkpoints_chain_and_consolidate() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let cfg = StrataConfig {
+            checkpoint: Some(CheckpointConfig {
+                max_deltas: 2,
+                ..CheckpointConfig::default()
+            }),
+            ..StrataConfig::default()
+        };
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let put = |db: &Database, name: &str, value: Value| {
+            db.transaction(branch_id, |txn| {
+                txn.put(Key::new_kv(ns.clone(), name), value.clone())?;
+                Ok(())
+            })
+            .unwrap();
+        };
+        let snapshot_ids = |dir: &Path| -> Vec<u64> {
+            list_snapshots(&dir.join("snapshots"))
+                .unwrap()
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect()
+        };
+
+        {
+            let db = Database::open_with_config(&db_path, cfg.clone()).unwrap();
+            let blob = "x".repeat(64 * 1024);
+            for i in 0..8 {
+                put(&db, &format!("big{}", i), Value::String(blob.clone()));
+            }
+            db.checkpoint().unwrap();
+            put(&db, "small", Value::Int(1));
+            db.checkpoint().unwrap();
+
+            // The delta only holds the one new commit
+            let dir = db.data_dir().to_path_buf();
+            assert_eq!(snapshot_ids(&dir), vec![1, 2]);
+            let size = |id| {
+                std::fs::metadata(strata_durability::format::snapshot_path(
+                    &dir.join("snapshots"),
+                    id,
+                ))
+                .unwrap()
+                .len()
+            };
+            assert!(size(2) * 10 < size(1));
+            put(&db, "small", Value::Int(2));
+        }
+
+        // Recovery loads the full snapshot plus the delta, then the WAL
+        let db = Database::open_with_config(&db_path, cfg).unwrap();
+        let get = |name: &str| {
+            db.storage()
+                .get(&Key::new_kv(ns.clone(), name))
+                .unwrap()
+                .map(|v| v.value)
+        };
+        assert_eq!(get("small"), Some(Value::Int(2)));
+        assert!(get("big7").is_some());
+
+        // The reopened database keeps extending the chain
+        db.checkpoint().unwrap();
+        let dir = db.data_dir().to_path_buf();
+        assert_eq!(snapshot_ids(&dir), vec![1, 2, 3]);
+
+        // A full chain is consolidated into a new full snapshot
+        put(&db, "small", Value::Int(3));
+        db.checkpoint().unwrap();
+        assert_eq!(snapshot_ids(&dir), vec![4]);
+        drop(db);
+
+        let db = Database::open(&db_path).unwrap();
+        assert_eq!(
+            db.storage()
+                .get(&Key::new_kv(ns.clone(), "small"))
+                .unwrap()
+                .unwrap()
+                .value,
+            Value::Int(3)
+        );
+    }
+
+    #[test]
+    fn test_automatic_checkpoint_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let cfg = StrataConfig {
+            checkpoint: Some(CheckpointConfig {
+                wal_size_mb: Some(1),
+                ..CheckpointConfig::default()
+            }),
+            ..StrataConfig::default()
+        };
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+
+        {
+            let db = Database::open_with_config(&db_path, cfg).unwrap();
+            let blob = "x".repeat(64 * 1024);
+            for i in 0..24 {
+                db.transaction(branch_id, |txn| {
+                    txn.put(
+                        Key::new_kv(ns.clone(), format!("k{}", i)),
+                        Value::String(blob.clone()),
+                    )?;
+                    Ok(())
+                })
+                .unwrap();
+            }
+
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+            while db.durability_counters().unwrap().compactions == 0 {
+                assert!(
+                    std::time::Instant::now() < deadline,
+                    "automatic checkpoint did not run"
+                );
+                std::thread::sleep(std::time::Duration::from_millis(20));
+            }
+            assert!(db.durability_counters().unwrap().checkpoints >= 1);
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        for i in 0..24 {
+            let key = Key::new_kv(ns.clone(), format!("k{}", i));
+            assert!(db.storage().get(&key).unwrap().is_some());
+        }
+    }
 }
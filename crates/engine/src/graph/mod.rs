@@ -14,10 +14,12 @@ pub mod types;
 
 use std::sync::Arc;
 
+use strata_concurrency::TransactionContext;
 use strata_core::types::BranchId;
 use strata_core::{StrataError, StrataResult, Value};
 
 use crate::database::Database;
+use crate::primitives::extensions::GraphStoreExt;
 use types::*;
 
 /// Graph store providing CRUD operations on nodes and edges.
@@ -46,16 +48,7 @@ impl GraphStore {
         graph: &str,
         meta: Option<GraphMeta>,
     ) -> StrataResult<()> {
-        keys::validate_graph_name(graph)?;
-        let meta = meta.unwrap_or_default();
-        let meta_json = serde_json::to_string(&meta)
-            .map_err(|e| StrataError::serialization(e.to_string()))?;
-        let user_key = keys::meta_key(graph);
-        let storage_key = keys::storage_key(branch_id, &user_key);
-
-        self.db.transaction(branch_id, |txn| {
-            txn.put(storage_key.clone(), Value::String(meta_json.clone()))
-        })
+        self.db.transaction(branch_id, |txn| txn.graph_create(graph, meta))
     }
 
     /// Get graph metadata, or None if graph doesn't exist.
@@ -64,23 +57,7 @@ impl GraphStore {
         branch_id: BranchId,
         graph: &str,
     ) -> StrataResult<Option<GraphMeta>> {
-        let user_key = keys::meta_key(graph);
-        let storage_key = keys::storage_key(branch_id, &user_key);
-
-        self.db.transaction(branch_id, |txn| {
-            let val = txn.get(&storage_key)?;
-            match val {
-                Some(Value::String(s)) => {
-                    let meta: GraphMeta = serde_json::from_str(&s)
-                        .map_err(|e| StrataError::serialization(e.to_string()))?;
-                    Ok(Some(meta))
-                }
-                Some(_) => Err(StrataError::serialization(
-                    "Graph meta is not a string".to_string(),
-                )),
-                None => Ok(None),
-            }
-        })
+        self.db.transaction(branch_id, |txn| txn.graph_get_meta(graph))
     }
 
     /// List all graph names on a branch.
@@ -108,46 +85,7 @@ impl GraphStore {
 
     /// Delete a graph and all its data (nodes, edges, meta, ref index entries).
     pub fn delete_graph(&self, branch_id: BranchId, graph: &str) -> StrataResult<()> {
-        let prefix = keys::graph_prefix(graph);
-        let prefix_key = keys::storage_key(branch_id, &prefix);
-        let node_prefix = keys::all_nodes_prefix(graph);
-
-        self.db.transaction(branch_id, |txn| {
-            let results = txn.scan_prefix(&prefix_key)?;
-
-            // First pass: collect ref index keys to delete from nodes
-            let mut ref_keys_to_delete = Vec::new();
-            for (key, val) in &results {
-                if let Some(user_key) = key.user_key_string() {
-                    if user_key.starts_with(&node_prefix) {
-                        if let Value::String(json) = val {
-                            if let Ok(data) = serde_json::from_str::<NodeData>(json) {
-                                if let Some(uri) = data.entity_ref {
-                                    if let Some(node_id) =
-                                        keys::parse_node_key(graph, &user_key)
-                                    {
-                                        let rk = keys::ref_index_key(&uri, graph, &node_id);
-                                        ref_keys_to_delete
-                                            .push(keys::storage_key(branch_id, &rk));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-
-            // Delete ref index entries
-            for rk in ref_keys_to_delete {
-                txn.delete(rk)?;
-            }
-
-            // Delete all graph keys (nodes, edges, meta)
-            for (key, _) in results {
-                txn.delete(key)?;
-            }
-            Ok(())
-        })
+        self.db.transaction(branch_id, |txn| txn.graph_delete(graph))
     }
 
     // =========================================================================
@@ -162,42 +100,7 @@ impl GraphStore {
         node_id: &str,
         data: NodeData,
     ) -> StrataResult<()> {
-        keys::validate_graph_name(graph)?;
-        keys::validate_node_id(node_id)?;
-
-        let node_json = serde_json::to_string(&data)
-            .map_err(|e| StrataError::serialization(e.to_string()))?;
-        let user_key = keys::node_key(graph, node_id);
-        let storage_key = keys::storage_key(branch_id, &user_key);
-
-        // Build ref index key if entity_ref is present
-        let ref_key = data.entity_ref.as_ref().map(|uri| {
-            let rk = keys::ref_index_key(uri, graph, node_id);
-            keys::storage_key(branch_id, &rk)
-        });
-
-        self.db.transaction(branch_id, |txn| {
-            // If updating, clean up old ref index entry
-            let old_val = txn.get(&storage_key)?;
-            if let Some(Value::String(old_json)) = old_val {
-                if let Ok(old_data) = serde_json::from_str::<NodeData>(&old_json) {
-                    if let Some(old_uri) = old_data.entity_ref {
-                        // Remove old ref index entry
-                        let old_rk = keys::ref_index_key(&old_uri, graph, node_id);
-                        let old_sk = keys::storage_key(branch_id, &old_rk);
-                        txn.delete(old_sk)?;
-                    }
-                }
-            }
-
-            txn.put(storage_key.clone(), Value::String(node_json.clone()))?;
-
-            // Write ref index
-            if let Some(rk) = ref_key.clone() {
-                txn.put(rk, Value::String(String::new()))?;
-            }
-            Ok(())
-        })
+        self.db.transaction(branch_id, |txn| txn.graph_add_node(graph, node_id, data))
     }
 
     /// Get node data, or None if node doesn't exist.
@@ -207,23 +110,7 @@ impl GraphStore {
         graph: &str,
         node_id: &str,
     ) -> StrataResult<Option<NodeData>> {
-        let user_key = keys::node_key(graph, node_id);
-        let storage_key = keys::storage_key(branch_id, &user_key);
-
-        self.db.transaction(branch_id, |txn| {
-            let val = txn.get(&storage_key)?;
-            match val {
-                Some(Value::String(s)) => {
-                    let data: NodeData = serde_json::from_str(&s)
-                        .map_err(|e| StrataError::serialization(e.to_string()))?;
-                    Ok(Some(data))
-                }
-                Some(_) => Err(StrataError::serialization(
-                    "Node data is not a string".to_string(),
-                )),
-                None => Ok(None),
-            }
-        })
+        self.db.transaction(branch_id, |txn| txn.graph_get_node(graph, node_id))
     }
 
     /// List all node IDs in a graph.
@@ -252,69 +139,7 @@ impl GraphStore {
         graph: &str,
         node_id: &str,
     ) -> StrataResult<()> {
-        let node_user_key = keys::node_key(graph, node_id);
-        let node_storage_key = keys::storage_key(branch_id, &node_user_key);
-
-        // Prefixes for scanning incident edges
-        let fwd_prefix = keys::forward_edges_prefix(graph, node_id);
-        let fwd_prefix_key = keys::storage_key(branch_id, &fwd_prefix);
-        let rev_prefix = keys::reverse_edges_prefix(graph, node_id);
-        let rev_prefix_key = keys::storage_key(branch_id, &rev_prefix);
-
-        self.db.transaction(branch_id, |txn| {
-            // Read node to get entity_ref for ref index cleanup
-            let node_val = txn.get(&node_storage_key)?;
-            if node_val.is_none() {
-                return Ok(());
-            }
-
-            // Clean up ref index
-            if let Some(Value::String(json)) = &node_val {
-                if let Ok(data) = serde_json::from_str::<NodeData>(json) {
-                    if let Some(uri) = data.entity_ref {
-                        let rk = keys::ref_index_key(&uri, graph, node_id);
-                        let sk = keys::storage_key(branch_id, &rk);
-                        txn.delete(sk)?;
-                    }
-                }
-            }
-
-            // Delete outgoing edges (forward + their reverse counterparts)
-            let fwd_edges = txn.scan_prefix(&fwd_prefix_key)?;
-            for (key, _) in fwd_edges {
-                if let Some(user_key) = key.user_key_string() {
-                    if let Some((src, edge_type, dst)) =
-                        keys::parse_forward_edge_key(graph, &user_key)
-                    {
-                        // Delete the reverse counterpart
-                        let rev_key = keys::reverse_edge_key(graph, &dst, &edge_type, &src);
-                        let rev_sk = keys::storage_key(branch_id, &rev_key);
-                        txn.delete(rev_sk)?;
-                    }
-                }
-                txn.delete(key)?;
-            }
-
-            // Delete incoming edges (reverse + their forward counterparts)
-            let rev_edges = txn.scan_prefix(&rev_prefix_key)?;
-            for (key, _) in rev_edges {
-                if let Some(user_key) = key.user_key_string() {
-                    if let Some((dst, edge_type, src)) =
-                        keys::parse_reverse_edge_key(graph, &user_key)
-                    {
-                        // Delete the forward counterpart
-                        let fwd_key = keys::forward_edge_key(graph, &src, &edge_type, &dst);
-                        let fwd_sk = keys::storage_key(branch_id, &fwd_key);
-                        txn.delete(fwd_sk)?;
-                    }
-                }
-                txn.delete(key)?;
-            }
-
-            // Delete the node itself
-            txn.delete(node_storage_key.clone())?;
-            Ok(())
-        })
+        self.db.transaction(branch_id, |txn| txn.graph_remove_node(graph, node_id))
     }
 
     // =========================================================================
@@ -332,23 +157,8 @@ impl GraphStore {
         edge_type: &str,
         data: EdgeData,
     ) -> StrataResult<()> {
-        keys::validate_graph_name(graph)?;
-        keys::validate_node_id(src)?;
-        keys::validate_node_id(dst)?;
-        keys::validate_edge_type(edge_type)?;
-
-        let edge_json = serde_json::to_string(&data)
-            .map_err(|e| StrataError::serialization(e.to_string()))?;
-
-        let fwd = keys::forward_edge_key(graph, src, edge_type, dst);
-        let rev = keys::reverse_edge_key(graph, dst, edge_type, src);
-        let fwd_sk = keys::storage_key(branch_id, &fwd);
-        let rev_sk = keys::storage_key(branch_id, &rev);
-
         self.db.transaction(branch_id, |txn| {
-            txn.put(fwd_sk.clone(), Value::String(edge_json.clone()))?;
-            txn.put(rev_sk.clone(), Value::String(edge_json.clone()))?;
-            Ok(())
+            txn.graph_add_edge(graph, src, dst, edge_type, data)
         })
     }
 
@@ -389,15 +199,8 @@ impl GraphStore {
         dst: &str,
         edge_type: &str,
     ) -> StrataResult<()> {
-        let fwd = keys::forward_edge_key(graph, src, edge_type, dst);
-        let rev = keys::reverse_edge_key(graph, dst, edge_type, src);
-        let fwd_sk = keys::storage_key(branch_id, &fwd);
-        let rev_sk = keys::storage_key(branch_id, &rev);
-
         self.db.transaction(branch_id, |txn| {
-            txn.delete(fwd_sk.clone())?;
-            txn.delete(rev_sk.clone())?;
-            Ok(())
+            txn.graph_remove_edge(graph, src, dst, edge_type)
         })
     }
 
@@ -578,6 +381,232 @@ impl GraphStore {
     }
 }
 
+// =============================================================================
+// GraphStoreExt Implementation
+// =============================================================================
+
+impl GraphStoreExt for TransactionContext {
+    fn graph_create(&mut self, graph: &str, meta: Option<GraphMeta>) -> StrataResult<()> {
+        keys::validate_graph_name(graph)?;
+        let meta = meta.unwrap_or_default();
+        let meta_json = serde_json::to_string(&meta)
+            .map_err(|e| StrataError::serialization(e.to_string()))?;
+        let user_key = keys::meta_key(graph);
+        let storage_key = keys::storage_key(self.branch_id, &user_key);
+
+        self.put(storage_key, Value::String(meta_json))
+    }
+
+    fn graph_get_meta(&mut self, graph: &str) -> StrataResult<Option<GraphMeta>> {
+        let user_key = keys::meta_key(graph);
+        let storage_key = keys::storage_key(self.branch_id, &user_key);
+
+        match self.get(&storage_key)? {
+            Some(Value::String(s)) => {
+                let meta: GraphMeta = serde_json::from_str(&s)
+                    .map_err(|e| StrataError::serialization(e.to_string()))?;
+                Ok(Some(meta))
+            }
+            Some(_) => Err(StrataError::serialization(
+                "Graph meta is not a string".to_string(),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn graph_delete(&mut self, graph: &str) -> StrataResult<()> {
+        let branch_id = self.branch_id;
+        let prefix = keys::graph_prefix(graph);
+        let prefix_key = keys::storage_key(branch_id, &prefix);
+        let node_prefix = keys::all_nodes_prefix(graph);
+
+        let results = self.scan_prefix(&prefix_key)?;
+
+        // First pass: collect ref index keys to delete from nodes
+        let mut ref_keys_to_delete = Vec::new();
+        for (key, val) in &results {
+            if let Some(user_key) = key.user_key_string() {
+                if user_key.starts_with(&node_prefix) {
+                    if let Value::String(json) = val {
+                        if let Ok(data) = serde_json::from_str::<NodeData>(json) {
+                            if let Some(uri) = data.entity_ref {
+                                if let Some(node_id) = keys::parse_node_key(graph, &user_key) {
+                                    let rk = keys::ref_index_key(&uri, graph, &node_id);
+                                    ref_keys_to_delete.push(keys::storage_key(branch_id, &rk));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Delete ref index entries
+        for rk in ref_keys_to_delete {
+            self.delete(rk)?;
+        }
+
+        // Delete all graph keys (nodes, edges, meta)
+        for (key, _) in results {
+            self.delete(key)?;
+        }
+        Ok(())
+    }
+
+    fn graph_add_node(&mut self, graph: &str, node_id: &str, data: NodeData) -> StrataResult<()> {
+        keys::validate_graph_name(graph)?;
+        keys::validate_node_id(node_id)?;
+
+        let branch_id = self.branch_id;
+        let node_json = serde_json::to_string(&data)
+            .map_err(|e| StrataError::serialization(e.to_string()))?;
+        let user_key = keys::node_key(graph, node_id);
+        let storage_key = keys::storage_key(branch_id, &user_key);
+
+        // If updating, clean up old ref index entry
+        let old_val = self.get(&storage_key)?;
+        if let Some(Value::String(old_json)) = old_val {
+            if let Ok(old_data) = serde_json::from_str::<NodeData>(&old_json) {
+                if let Some(old_uri) = old_data.entity_ref {
+                    // Remove old ref index entry
+                    let old_rk = keys::ref_index_key(&old_uri, graph, node_id);
+                    let old_sk = keys::storage_key(branch_id, &old_rk);
+                    self.delete(old_sk)?;
+                }
+            }
+        }
+
+        self.put(storage_key, Value::String(node_json))?;
+
+        // Write ref index
+        if let Some(uri) = data.entity_ref.as_ref() {
+            let rk = keys::ref_index_key(uri, graph, node_id);
+            self.put(keys::storage_key(branch_id, &rk), Value::String(String::new()))?;
+        }
+        Ok(())
+    }
+
+    fn graph_get_node(&mut self, graph: &str, node_id: &str) -> StrataResult<Option<NodeData>> {
+        let user_key = keys::node_key(graph, node_id);
+        let storage_key = keys::storage_key(self.branch_id, &user_key);
+
+        match self.get(&storage_key)? {
+            Some(Value::String(s)) => {
+                let data: NodeData = serde_json::from_str(&s)
+                    .map_err(|e| StrataError::serialization(e.to_string()))?;
+                Ok(Some(data))
+            }
+            Some(_) => Err(StrataError::serialization(
+                "Node data is not a string".to_string(),
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn graph_remove_node(&mut self, graph: &str, node_id: &str) -> StrataResult<()> {
+        let branch_id = self.branch_id;
+        let node_user_key = keys::node_key(graph, node_id);
+        let node_storage_key = keys::storage_key(branch_id, &node_user_key);
+
+        // Prefixes for scanning incident edges
+        let fwd_prefix = keys::forward_edges_prefix(graph, node_id);
+        let fwd_prefix_key = keys::storage_key(branch_id, &fwd_prefix);
+        let rev_prefix = keys::reverse_edges_prefix(graph, node_id);
+        let rev_prefix_key = keys::storage_key(branch_id, &rev_prefix);
+
+        // Read node to get entity_ref for ref index cleanup
+        let node_val = self.get(&node_storage_key)?;
+        if node_val.is_none() {
+            return Ok(());
+        }
+
+        // Clean up ref index
+        if let Some(Value::String(json)) = &node_val {
+            if let Ok(data) = serde_json::from_str::<NodeData>(json) {
+                if let Some(uri) = data.entity_ref {
+                    let rk = keys::ref_index_key(&uri, graph, node_id);
+                    let sk = keys::storage_key(branch_id, &rk);
+                    self.delete(sk)?;
+                }
+            }
+        }
+
+        // Delete outgoing edges (forward + their reverse counterparts)
+        let fwd_edges = self.scan_prefix(&fwd_prefix_key)?;
+        for (key, _) in fwd_edges {
+            if let Some(user_key) = key.user_key_string() {
+                if let Some((src, edge_type, dst)) = keys::parse_forward_edge_key(graph, &user_key)
+                {
+                    // Delete the reverse counterpart
+                    let rev_key = keys::reverse_edge_key(graph, &dst, &edge_type, &src);
+                    let rev_sk = keys::storage_key(branch_id, &rev_key);
+                    self.delete(rev_sk)?;
+                }
+            }
+            self.delete(key)?;
+        }
+
+        // Delete incoming edges (reverse + their forward counterparts)
+        let rev_edges = self.scan_prefix(&rev_prefix_key)?;
+        for (key, _) in rev_edges {
+            if let Some(user_key) = key.user_key_string() {
+                if let Some((dst, edge_type, src)) = keys::parse_reverse_edge_key(graph, &user_key)
+                {
+                    // Delete the forward counterpart
+                    let fwd_key = keys::forward_edge_key(graph, &src, &edge_type, &dst);
+                    let fwd_sk = keys::storage_key(branch_id, &fwd_key);
+                    self.delete(fwd_sk)?;
+                }
+            }
+            self.delete(key)?;
+        }
+
+        // Delete the node itself
+        self.delete(node_storage_key)
+    }
+
+    fn graph_add_edge(
+        &mut self,
+        graph: &str,
+        src: &str,
+        dst: &str,
+        edge_type: &str,
+        data: EdgeData,
+    ) -> StrataResult<()> {
+        keys::validate_graph_name(graph)?;
+        keys::validate_node_id(src)?;
+        keys::validate_node_id(dst)?;
+        keys::validate_edge_type(edge_type)?;
+
+        let edge_json = serde_json::to_string(&data)
+            .map_err(|e| StrataError::serialization(e.to_string()))?;
+
+        let fwd = keys::forward_edge_key(graph, src, edge_type, dst);
+        let rev = keys::reverse_edge_key(graph, dst, edge_type, src);
+        let fwd_sk = keys::storage_key(self.branch_id, &fwd);
+        let rev_sk = keys::storage_key(self.branch_id, &rev);
+
+        self.put(fwd_sk, Value::String(edge_json.clone()))?;
+        self.put(rev_sk, Value::String(edge_json))
+    }
+
+    fn graph_remove_edge(
+        &mut self,
+        graph: &str,
+        src: &str,
+        dst: &str,
+        edge_type: &str,
+    ) -> StrataResult<()> {
+        let fwd = keys::forward_edge_key(graph, src, edge_type, dst);
+        let rev = keys::reverse_edge_key(graph, dst, edge_type, src);
+        let fwd_sk = keys::storage_key(self.branch_id, &fwd);
+        let rev_sk = keys::storage_key(self.branch_id, &rev);
+
+        self.delete(fwd_sk)?;
+        self.delete(rev_sk)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1388,4 +1417,36 @@ mod tests {
             .unwrap();
         assert!(refs.is_empty());
     }
+
+    // =========================================================================
+    // Transactions
+    // =========================================================================
+
+    #[test]
+    fn graph_ops_in_failed_transaction_leave_no_trace() {
+        let (db, gs) = setup();
+        let branch = default_branch();
+        gs.create_graph(branch, "tg", None).unwrap();
+
+        let result: StrataResult<()> = db.transaction(branch, |txn| {
+            txn.graph_add_node("tg", "a", NodeData::default())?;
+            txn.graph_add_node("tg", "b", NodeData::default())?;
+            txn.graph_add_edge("tg", "a", "b", "LINKS", EdgeData::default())?;
+            // Read-your-writes inside the transaction
+            assert!(txn.graph_get_node("tg", "a")?.is_some());
+            Err(StrataError::invalid_input("abort".to_string()))
+        });
+        assert!(result.is_err());
+        assert!(gs.get_node(branch, "tg", "a").unwrap().is_none());
+        assert!(gs.get_edge(branch, "tg", "a", "b", "LINKS").unwrap().is_none());
+
+        db.transaction(branch, |txn| {
+            txn.graph_add_node("tg", "a", NodeData::default())?;
+            txn.graph_add_node("tg", "b", NodeData::default())?;
+            txn.graph_add_edge("tg", "a", "b", "LINKS", EdgeData::default())
+        })
+        .unwrap();
+        assert!(gs.get_edge(branch, "tg", "a", "b", "LINKS").unwrap().is_some());
+        assert_eq!(gs.list_nodes(branch, "tg").unwrap().len(), 2);
+    }
 }
@@ -5,7 +5,7 @@
 //! - Branch lifecycle: begin_branch, end_branch, fork_branch (Epic 5)
 //! - Transaction coordination
 //! - Recovery integration
-//! - Background tasks (snapshots, TTL cleanup)
+//! - Background tasks (checkpoints, TTL cleanup)
 //!
 //! The engine is the only component that knows about:
 //! - Branch management
@@ -33,16 +33,24 @@ pub mod transaction_ops; // TransactionOps Trait Definition
 
 pub use background::{BackgroundScheduler, BackpressureError, SchedulerStats, TaskPriority};
 pub use coordinator::{TransactionCoordinator, TransactionMetrics};
-pub use database::{Database, ModelConfig, RetryConfig, StrataConfig};
+pub use database::{
+    BackupInfo, CheckpointConfig, Database, FsckComponent, FsckFinding, FsckReport, FsckSeverity,
+    ModelConfig, RekeyInfo, RestoreInfo, RetryConfig, StrataConfig, WalDumpFilter, WalDumpRecord,
+    WalDumpWrite,
+};
 pub use instrumentation::PerfTrace;
 pub use recovery::{
     diff_views, recover_all_participants, register_recovery_participant, BranchDiff, BranchError,
     DiffEntry, ReadOnlyView, RecoveryFn, RecoveryParticipant, ReplayBranchIndex, ReplayError,
 };
 pub use strata_durability::wal::DurabilityMode;
-pub use strata_durability::WalCounters;
+pub use strata_durability::{
+    ArchiveEntry, ArchiveError, ArchivePruneInfo, ArchiveVerifyReport, LocalWalArchive,
+    SegmentMeta, WalArchiver, WalCounters,
+};
+pub use strata_storage::SpillStats;
 // Note: Use strata_core::PrimitiveType for DiffEntry.primitive field
-pub use strata_concurrency::TransactionContext;
+pub use strata_concurrency::{IsolationLevel, TransactionContext};
 pub use transaction::{Transaction, TransactionPool, MAX_POOL_SIZE};
 pub use transaction_ops::TransactionOps;
 
@@ -50,6 +58,7 @@ pub mod branch_ops;
 pub mod bundle;
 pub mod graph;
 pub mod primitives;
+pub mod retention;
 pub mod search;
 
 // Re-export search types at crate root for convenience
@@ -88,6 +97,8 @@ pub use primitives::{
     EventLogExt,
     FilterCondition,
     FilterOp,
+    ForkAncestor,
+    GraphStoreExt,
     HnswBackend,
     HnswConfig,
     IndexBackendFactory,
@@ -103,7 +114,9 @@ pub use primitives::{
     // Extension traits
     KVStoreExt,
     KvHandle,
+    MergeRecord,
     MetadataFilter,
+    PendingVectorOp,
     PostingEntry,
     PostingList,
     Scorer,
@@ -149,9 +162,18 @@ pub use bundle::{BundleInfo, ExportInfo, ImportInfo};
 
 // Re-export branch_ops types at crate root
 pub use branch_ops::{
-    BranchDiffEntry, BranchDiffResult, ConflictEntry, DiffSummary, ForkInfo, MergeInfo,
-    MergeStrategy, SpaceDiff,
+    BranchDiffEntry, BranchDiffResult, CherryPickInfo, CommitInfo, ConflictEntry,
+    ConflictResolution, DiffOptions, DiffSummary, EventMergeStrategy, ForkInfo, JsonMergeStrategy,
+    MergeBase, MergeInfo, MergeResolution, MergeStrategy, PrimitiveMergeStrategies, RevertInfo,
+    SpaceDiff, StateMergeStrategy, TouchedKeys,
+};
+
+// Re-export retention types at crate root
+pub use retention::{
+    BranchRetention, PrimitiveRetention, PrunableVersion, RetentionApplyInfo, RetentionHorizon,
+    RetentionPreview, RetentionStats,
 };
+pub use strata_durability::retention::RetentionPolicy;
 
 #[cfg(feature = "perf-trace")]
 pub use instrumentation::{PerfBreakdown, PerfStats};
@@ -6,10 +6,13 @@
 //! ## MVP Methods
 //!
 //! - `create_branch(name)` - Create a new branch
+//! - `create_forked_branch(name, parent, version)` - Create a copy-on-write fork that records its fork point
 //! - `get_branch(name)` - Get branch metadata
 //! - `exists(name)` - Check if branch exists
 //! - `list_branches()` - List all branch names
 //! - `delete_branch(name)` - Delete branch and ALL its data (cascading)
+//! - `set_status(name, status)` - Protect, freeze or reactivate a branch
+//! - `set_retention(name, policy)` - Set how much version history a branch keeps
 //!
 //! ## Key Design
 //!
@@ -18,13 +21,17 @@
 //! - BranchIndex uses a global namespace (not branch-scoped) since it manages branches themselves.
 
 use crate::database::Database;
+use crate::retention::{RetentionHorizon, RetentionHorizons};
+use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use strata_core::contract::{Timestamp, Version, Versioned};
 use strata_core::types::{BranchId, Key, Namespace, TypeTag};
 use strata_core::value::Value;
 use strata_core::StrataError;
 use strata_core::StrataResult;
+use strata_durability::retention::RetentionPolicy;
 use tracing::info;
 use uuid::Uuid;
 
@@ -69,13 +76,17 @@ fn global_namespace() -> Namespace {
 
 /// Branch lifecycle status.
 ///
-/// All branches are Active. Additional statuses will be added when
-/// lifecycle transitions are implemented.
+/// Besides `Active`, a branch can be write-protected: `Protected` branches
+/// only change through merges, `Frozen` branches do not change at all.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum BranchStatus {
     /// Branch is currently active
     #[default]
     Active,
+    /// Branch only accepts writes that arrive via merge
+    Protected,
+    /// Branch rejects all writes
+    Frozen,
 }
 
 impl BranchStatus {
@@ -83,10 +94,54 @@ impl BranchStatus {
     pub fn as_str(&self) -> &'static str {
         match self {
             BranchStatus::Active => "Active",
+            BranchStatus::Protected => "Protected",
+            BranchStatus::Frozen => "Frozen",
+        }
+    }
+
+    /// Whether a write to a branch in this status is allowed.
+    ///
+    /// `via_merge` is true for writes made by merging another branch in.
+    pub fn allows_write(&self, via_merge: bool) -> bool {
+        match self {
+            BranchStatus::Active => true,
+            BranchStatus::Protected => via_merge,
+            BranchStatus::Frozen => false,
         }
     }
 }
 
+/// Statuses of write-protected branches, keyed by branch name.
+///
+/// Kept as a database extension so write guards don't need to read branch
+/// metadata on every write. Branches that are absent are `Active`.
+#[derive(Default)]
+struct BranchWriteGuards {
+    statuses: DashMap<String, BranchStatus>,
+}
+
+impl BranchWriteGuards {
+    fn set(&self, branch: &str, status: BranchStatus) {
+        if status == BranchStatus::Active {
+            self.statuses.remove(branch);
+        } else {
+            self.statuses.insert(branch.to_string(), status);
+        }
+    }
+}
+
+/// Whether the branch stored under `branch_id` is frozen.
+///
+/// Write guards are keyed by name; this serves maintenance that only knows
+/// the storage id, such as the TTL reaper.
+pub(crate) fn is_frozen(db: &Database, branch_id: &BranchId) -> bool {
+    db.extension::<BranchWriteGuards>().is_ok_and(|guards| {
+        guards.statuses.iter().any(|entry| {
+            *entry.value() == BranchStatus::Frozen && resolve_branch_name(entry.key()) == *branch_id
+        })
+    })
+}
+
 // ========== BranchMetadata Struct ==========
 
 /// Metadata about a branch
@@ -96,11 +151,40 @@ pub struct BranchMetadata {
     pub name: String,
     /// Unique branch identifier (UUID) for internal use and namespacing
     pub branch_id: String,
-    /// Parent branch name if forked (post-MVP)
+    /// Parent branch name if forked
     pub parent_branch: Option<String>,
+    /// Storage version of the parent at which this branch was forked.
+    ///
+    /// Together with `parent_branch` this identifies the fork point, which
+    /// is used as the common ancestor for three-way merges.
+    #[serde(default)]
+    pub fork_version: Option<u64>,
+    /// Branches whose data this branch reads through, nearest first.
+    ///
+    /// Forks are copy-on-write: the parent's data up to `fork_version` is
+    /// shared, not copied. The parent's own ancestry is captured too, so
+    /// the list stays valid if an ancestor is later deleted. Empty for
+    /// branches that hold all of their data themselves.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ancestry: Vec<ForkAncestor>,
+    /// The latest merge of each branch into this one, by source branch
+    /// name.
+    ///
+    /// A completed merge moves the merge base forward, so changes already
+    /// merged are not compared (or conflict) again.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub merged_from: BTreeMap<String, MergeRecord>,
 
     /// Current status
     pub status: BranchStatus,
+    /// Retention policy for the branch's version history; `None` keeps all
+    /// versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention: Option<RetentionPolicy>,
+    /// How far back the branch's history is still complete, once retention
+    /// has pruned versions from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_horizon: Option<RetentionHorizon>,
     /// Creation timestamp (microseconds since epoch)
     pub created_at: u64,
     /// Last update timestamp (microseconds since epoch)
@@ -118,6 +202,27 @@ fn default_version() -> u64 {
     1
 }
 
+/// A branch that a copy-on-write fork reads through
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ForkAncestor {
+    /// Ancestor branch name
+    pub branch: String,
+    /// Newest storage version of the ancestor visible to the fork
+    pub version: u64,
+}
+
+/// A merge into a branch, recorded on the target
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MergeRecord {
+    /// `branch_id` of the source branch that was merged.
+    ///
+    /// Identifies the source across a delete and re-create under the same
+    /// name; the record only applies while the ids match.
+    pub source_id: String,
+    /// Storage version the source was merged at
+    pub version: u64,
+}
+
 impl BranchMetadata {
     /// Create new branch metadata with Active status
     pub fn new(name: &str) -> Self {
@@ -127,7 +232,12 @@ impl BranchMetadata {
             name: name.to_string(),
             branch_id: branch_id.to_string(),
             parent_branch: None,
+            fork_version: None,
+            ancestry: Vec::new(),
+            merged_from: BTreeMap::new(),
             status: BranchStatus::Active,
+            retention: None,
+            retention_horizon: None,
             created_at: now,
             updated_at: now,
             completed_at: None,
@@ -232,8 +342,245 @@ impl BranchIndex {
     /// ## Errors
     /// - `InvalidInput` if branch already exists
     pub fn create_branch(&self, branch_id: &str) -> StrataResult<Versioned<BranchMetadata>> {
+        self.create_with_metadata(BranchMetadata::new(branch_id))
+    }
+
+    /// Create a new branch as a copy-on-write fork of `parent`
+    ///
+    /// Like `create_branch`, but stores `parent` and the parent's storage
+    /// `fork_version` in the metadata so merges can find a common ancestor,
+    /// and links the new branch to the parent's data as of `fork_version`.
+    /// No data is copied; reads of keys the new branch hasn't written fall
+    /// back to the parent (and the parent's ancestors).
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if branch already exists
+    pub fn create_forked_branch(
+        &self,
+        branch_id: &str,
+        parent: &str,
+        fork_version: u64,
+    ) -> StrataResult<Versioned<BranchMetadata>> {
+        let parent_ancestry = self
+            .get_branch(parent)?
+            .map(|m| m.value.ancestry)
+            .unwrap_or_default();
+
+        let mut branch_meta = BranchMetadata::new(branch_id);
+        branch_meta.parent_branch = Some(parent.to_string());
+        branch_meta.fork_version = Some(fork_version);
+        branch_meta.ancestry.push(ForkAncestor {
+            branch: parent.to_string(),
+            version: fork_version,
+        });
+        branch_meta
+            .ancestry
+            .extend(parent_ancestry.into_iter().map(|a| ForkAncestor {
+                version: a.version.min(fork_version),
+                ..a
+            }));
+
+        let created = self.create_with_metadata(branch_meta)?;
+        self.link_ancestry(&created.value);
+        Ok(created)
+    }
+
+    /// Point the branch's storage at its ancestors' version chains.
+    fn link_ancestry(&self, branch_meta: &BranchMetadata) {
+        let ancestry = branch_meta
+            .ancestry
+            .iter()
+            .map(|a| strata_storage::BranchAncestor {
+                branch_id: resolve_branch_name(&a.branch),
+                version: a.version,
+            })
+            .collect();
+        self.db
+            .storage()
+            .set_branch_ancestry(resolve_branch_name(&branch_meta.name), ancestry);
+    }
+
+    /// Restore copy-on-write links for every forked branch.
+    ///
+    /// Lineage lives in branch metadata; storage links are in-memory only,
+    /// so they are re-established after recovery before anything reads
+    /// branch data.
+    pub(crate) fn relink_forked_branches(&self) -> StrataResult<()> {
+        for name in self.list_branches()? {
+            if let Some(meta) = self.get_branch(&name)? {
+                if !meta.value.ancestry.is_empty() {
+                    self.link_ancestry(&meta.value);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Change a branch's status, e.g. to protect or freeze it.
+    ///
+    /// The status is part of the branch metadata, so it survives restart.
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if the branch doesn't exist
+    pub fn set_status(
+        &self,
+        branch_id: &str,
+        status: BranchStatus,
+    ) -> StrataResult<Versioned<BranchMetadata>> {
+        let updated = self.update_metadata(branch_id, |meta| meta.status = status)?;
+
+        self.write_guards()?.set(branch_id, status);
+        info!(target: "strata::branch", %branch_id, status = status.as_str(), "Branch status changed");
+        Ok(updated.into_versioned())
+    }
+
+    /// Set the retention policy for a branch's version history.
+    ///
+    /// The policy is stored with the branch and enforced when retention is
+    /// applied (see [`crate::retention::apply_retention`]). `KeepAll`
+    /// removes any policy.
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if the branch doesn't exist or the policy is invalid
+    pub fn set_retention(
+        &self,
+        branch_id: &str,
+        policy: RetentionPolicy,
+    ) -> StrataResult<Versioned<BranchMetadata>> {
+        policy
+            .validate()
+            .map_err(|e| StrataError::invalid_input(format!("Invalid retention policy: {}", e)))?;
+        let summary = policy.summary();
+        let retention = (policy != RetentionPolicy::KeepAll).then_some(policy);
+        let updated = self.update_metadata(branch_id, |meta| meta.retention = retention)?;
+
+        info!(target: "strata::branch", %branch_id, policy = %summary, "Branch retention policy set");
+        Ok(updated.into_versioned())
+    }
+
+    /// Move a branch's retention horizon forward after pruning.
+    ///
+    /// The horizon never moves back: the stored one is kept where it is
+    /// further ahead. Returns the resulting horizon.
+    pub(crate) fn advance_retention_horizon(
+        &self,
+        branch_id: &str,
+        horizon: RetentionHorizon,
+    ) -> StrataResult<RetentionHorizon> {
+        let updated = self.update_metadata(branch_id, |meta| {
+            meta.retention_horizon = Some(match meta.retention_horizon {
+                Some(stored) => stored.max(horizon),
+                None => horizon,
+            });
+        })?;
+        let horizon = updated.retention_horizon.unwrap_or(horizon);
+        self.retention_horizons()?
+            .set(resolve_branch_name(branch_id), horizon);
+        Ok(horizon)
+    }
+
+    /// Record that `source` was merged into `branch_id` as of `version`.
+    ///
+    /// The recorded version never moves back while the source is the same
+    /// branch; a source re-created under the same name replaces the record.
+    pub(crate) fn record_merge(
+        &self,
+        branch_id: &str,
+        source: &str,
+        version: u64,
+    ) -> StrataResult<()> {
+        let source_id = self
+            .get_branch(source)?
+            .ok_or_else(|| StrataError::invalid_input(format!("Branch '{}' not found", source)))?
+            .value
+            .branch_id;
+        self.update_metadata(branch_id, |meta| {
+            let record = MergeRecord { source_id, version };
+            match meta.merged_from.get_mut(source) {
+                Some(merged) if merged.source_id == record.source_id => {
+                    merged.version = merged.version.max(version);
+                }
+                _ => {
+                    meta.merged_from.insert(source.to_string(), record);
+                }
+            }
+        })?;
+        Ok(())
+    }
+
+    /// Apply `update` to a branch's stored metadata and bump its version.
+    fn update_metadata(
+        &self,
+        branch_id: &str,
+        update: impl FnOnce(&mut BranchMetadata),
+    ) -> StrataResult<BranchMetadata> {
         self.db.transaction(global_branch_id(), |txn| {
             let key = self.key_for(branch_id);
+            let mut meta: BranchMetadata = match txn.get(&key)? {
+                Some(v) => {
+                    from_stored_value(&v).map_err(|e| StrataError::serialization(e.to_string()))?
+                }
+                None => {
+                    return Err(StrataError::invalid_input(format!(
+                        "Branch '{}' not found",
+                        branch_id
+                    )))
+                }
+            };
+            update(&mut meta);
+            meta.updated_at = BranchMetadata::now();
+            meta.version += 1;
+            txn.put(key, to_stored_value(&meta)?)?;
+            Ok(meta)
+        })
+    }
+
+    /// Current status of a branch as seen by write guards.
+    ///
+    /// Served from memory, so it is cheap enough to check on every write.
+    /// Unknown branches are reported as `Active`.
+    pub fn write_status(&self, branch_id: &str) -> BranchStatus {
+        self.write_guards()
+            .ok()
+            .and_then(|guards| guards.statuses.get(branch_id).map(|s| *s))
+            .unwrap_or_default()
+    }
+
+    fn write_guards(&self) -> StrataResult<Arc<BranchWriteGuards>> {
+        self.db.extension::<BranchWriteGuards>()
+    }
+
+    fn retention_horizons(&self) -> StrataResult<Arc<RetentionHorizons>> {
+        self.db.extension::<RetentionHorizons>()
+    }
+
+    /// Load the statuses of write-protected branches and the retention
+    /// horizons of pruned branches into memory.
+    ///
+    /// Called once after recovery; `set_status` and retention keep them
+    /// current afterwards.
+    pub(crate) fn load_write_guards(&self) -> StrataResult<()> {
+        let guards = self.write_guards()?;
+        let horizons = self.retention_horizons()?;
+        for name in self.list_branches()? {
+            if let Some(meta) = self.get_branch(&name)? {
+                guards.set(&name, meta.value.status);
+                if let Some(horizon) = meta.value.retention_horizon {
+                    horizons.set(resolve_branch_name(&name), horizon);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Store metadata for a new branch, failing if the name is taken.
+    fn create_with_metadata(
+        &self,
+        branch_meta: BranchMetadata,
+    ) -> StrataResult<Versioned<BranchMetadata>> {
+        let branch_id = branch_meta.name.clone();
+        self.db.transaction(global_branch_id(), |txn| {
+            let key = self.key_for(&branch_id);
 
             // Check if branch already exists
             if txn.get(&key)?.is_some() {
@@ -243,11 +590,10 @@ impl BranchIndex {
                 )));
             }
 
-            let branch_meta = BranchMetadata::new(branch_id);
             txn.put(key, to_stored_value(&branch_meta)?)?;
 
             info!(target: "strata::branch", %branch_id, "Branch created");
-            Ok(branch_meta.into_versioned())
+            Ok(branch_meta.clone().into_versioned())
         })
     }
 
@@ -321,9 +667,16 @@ impl BranchIndex {
 
         let meta_key = self.key_for(branch_id);
 
+        // Detach a copy-on-write fork from its ancestors first, so only the
+        // data it wrote itself is deleted; the ancestors' data is untouched.
+        let ancestry = self.db.storage().branch_ancestry(&executor_branch_id);
+        self.db
+            .storage()
+            .set_branch_ancestry(executor_branch_id, Vec::new());
+
         // Single atomic transaction for all delete operations (#974).
         // Deletes branch data from all namespaces + metadata entry.
-        self.db.transaction(global_branch_id(), |txn| {
+        let result = self.db.transaction(global_branch_id(), |txn| {
             // Delete data from the executor's namespace
             Self::delete_namespace_data(txn, executor_branch_id)?;
 
@@ -339,7 +692,17 @@ impl BranchIndex {
 
             info!(target: "strata::branch", %branch_id, "Branch deleted");
             Ok(())
-        })
+        });
+
+        if result.is_err() {
+            self.db
+                .storage()
+                .set_branch_ancestry(executor_branch_id, ancestry);
+        } else {
+            self.write_guards()?.set(branch_id, BranchStatus::Active);
+            self.retention_horizons()?.remove(&executor_branch_id);
+        }
+        result
     }
 
     /// Delete all branch-scoped data within an existing transaction context.
@@ -432,6 +795,61 @@ mod tests {
         assert_eq!(result.unwrap().value.name, "test-run");
     }
 
+    #[test]
+    fn test_create_forked_branch_records_fork_point() {
+        let (_temp, _db, ri) = setup();
+
+        ri.create_branch("parent").unwrap();
+        ri.create_forked_branch("child", "parent", 42).unwrap();
+
+        let child = ri.get_branch("child").unwrap().unwrap().value;
+        assert_eq!(child.parent_branch.as_deref(), Some("parent"));
+        assert_eq!(child.fork_version, Some(42));
+
+        let parent = ri.get_branch("parent").unwrap().unwrap().value;
+        assert_eq!(parent.parent_branch, None);
+        assert_eq!(parent.fork_version, None);
+
+        assert!(ri.create_forked_branch("child", "parent", 43).is_err());
+    }
+
+    #[test]
+    fn test_create_forked_branch_captures_ancestry() {
+        let (_temp, db, ri) = setup();
+
+        ri.create_branch("root").unwrap();
+        ri.create_forked_branch("middle", "root", 10).unwrap();
+        ri.create_forked_branch("leaf", "middle", 20).unwrap();
+
+        let leaf = ri.get_branch("leaf").unwrap().unwrap().value;
+        assert_eq!(
+            leaf.ancestry,
+            vec![
+                ForkAncestor {
+                    branch: "middle".to_string(),
+                    version: 20
+                },
+                ForkAncestor {
+                    branch: "root".to_string(),
+                    version: 10
+                },
+            ]
+        );
+
+        // Storage reads through the same ancestry
+        let storage_ancestry = db.storage().branch_ancestry(&resolve_branch_name("leaf"));
+        assert_eq!(storage_ancestry.len(), 2);
+        assert_eq!(storage_ancestry[0].branch_id, resolve_branch_name("middle"));
+        assert_eq!(storage_ancestry[1].version, 10);
+
+        // Deleting the fork detaches it
+        ri.delete_branch("leaf").unwrap();
+        assert!(db
+            .storage()
+            .branch_ancestry(&resolve_branch_name("leaf"))
+            .is_empty());
+    }
+
     #[test]
     fn test_get_branch_not_found() {
         let (_temp, _db, ri) = setup();
@@ -492,5 +910,77 @@ mod tests {
     #[test]
     fn test_branch_status_as_str() {
         assert_eq!(BranchStatus::Active.as_str(), "Active");
+        assert_eq!(BranchStatus::Protected.as_str(), "Protected");
+        assert_eq!(BranchStatus::Frozen.as_str(), "Frozen");
+    }
+
+    #[test]
+    fn test_branch_status_allows_write() {
+        assert!(BranchStatus::Active.allows_write(false));
+        assert!(BranchStatus::Active.allows_write(true));
+        assert!(!BranchStatus::Protected.allows_write(false));
+        assert!(BranchStatus::Protected.allows_write(true));
+        assert!(!BranchStatus::Frozen.allows_write(false));
+        assert!(!BranchStatus::Frozen.allows_write(true));
+    }
+
+    #[test]
+    fn test_set_status() {
+        let (_temp, _db, ri) = setup();
+
+        ri.create_branch("main").unwrap();
+        assert_eq!(ri.write_status("main"), BranchStatus::Active);
+
+        let updated = ri.set_status("main", BranchStatus::Protected).unwrap();
+        assert_eq!(updated.value.status, BranchStatus::Protected);
+        assert_eq!(updated.value.version, 2);
+        assert_eq!(ri.write_status("main"), BranchStatus::Protected);
+        assert_eq!(
+            ri.get_branch("main").unwrap().unwrap().value.status,
+            BranchStatus::Protected
+        );
+
+        ri.set_status("main", BranchStatus::Active).unwrap();
+        assert_eq!(ri.write_status("main"), BranchStatus::Active);
+
+        assert!(ri.set_status("missing", BranchStatus::Frozen).is_err());
+        assert_eq!(ri.write_status("missing"), BranchStatus::Active);
+    }
+
+    #[test]
+    fn test_delete_branch_clears_status() {
+        let (_temp, _db, ri) = setup();
+
+        ri.create_branch("main").unwrap();
+        ri.set_status("main", BranchStatus::Frozen).unwrap();
+        ri.delete_branch("main").unwrap();
+        ri.create_branch("main").unwrap();
+
+        assert_eq!(ri.write_status("main"), BranchStatus::Active);
+    }
+
+    #[test]
+    fn test_status_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+
+        {
+            let db = Database::open(&db_path).unwrap();
+            let ri = BranchIndex::new(db.clone());
+            ri.create_branch("main").unwrap();
+            ri.create_branch("release").unwrap();
+            ri.set_status("main", BranchStatus::Protected).unwrap();
+            ri.set_status("release", BranchStatus::Frozen).unwrap();
+            db.flush().unwrap();
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        let ri = BranchIndex::new(db);
+        assert_eq!(ri.write_status("main"), BranchStatus::Protected);
+        assert_eq!(ri.write_status("release"), BranchStatus::Frozen);
+        assert_eq!(
+            ri.get_branch("release").unwrap().unwrap().value.status,
+            BranchStatus::Frozen
+        );
     }
 }
@@ -8,4 +8,7 @@ mod handle;
 mod index;
 
 pub use handle::{BranchHandle, EventHandle, JsonHandle, KvHandle, StateHandle};
-pub use index::{resolve_branch_name, BranchIndex, BranchMetadata, BranchStatus};
+pub(crate) use index::is_frozen;
+pub use index::{
+    resolve_branch_name, BranchIndex, BranchMetadata, BranchStatus, ForkAncestor, MergeRecord,
+};
@@ -28,6 +28,11 @@
 //! Uses SHA-256 for deterministic cross-platform hashing. Hash version 1 computes:
 //! SHA256(sequence || event_type_len || event_type || timestamp || payload_len || payload || prev_hash)
 //!
+//! Version 2 hashes the same fields but serializes the payload with object
+//! keys sorted, so a hash can be recomputed from a stored event. Version 1
+//! payloads were hashed in `HashMap` iteration order; only their links can be
+//! verified.
+//!
 //! ## Key Design
 //!
 //! - TypeTag: Event (0x02)
@@ -42,16 +47,18 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use strata_concurrency::TransactionContext;
 use strata_core::contract::{Timestamp, Version, Versioned};
+use strata_core::primitives::ChainVerification;
 use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
 use strata_core::StrataError;
 use strata_core::StrataResult;
+use strata_core::VersionedValue;
 
 // Re-export Event from core
 pub use strata_core::primitives::Event;
 
-/// Hash version constants
-pub(crate) const HASH_VERSION_SHA256: u8 = 1; // SHA-256
+/// Hash version constants (1 = SHA-256 in payload iteration order)
+pub(crate) const HASH_VERSION_CANONICAL: u8 = 2; // SHA-256, payload keys sorted
 
 /// Per-stream metadata for O(1) access to stream statistics
 ///
@@ -97,7 +104,8 @@ pub(crate) struct EventLogMeta {
     pub next_sequence: u64,
     /// Hash of the last event (head of chain)
     pub head_hash: [u8; 32],
-    /// Hash algorithm version (0 = legacy DefaultHasher, 1 = SHA-256)
+    /// Hash algorithm version (0 = legacy DefaultHasher, 1 = SHA-256,
+    /// 2 = SHA-256 with sorted payload keys)
     #[serde(default)]
     pub hash_version: u8,
     /// Per-stream metadata for O(1) stream queries
@@ -110,7 +118,7 @@ impl Default for EventLogMeta {
         Self {
             next_sequence: 0,
             head_hash: [0u8; 32],
-            hash_version: HASH_VERSION_SHA256, // New logs use SHA-256
+            hash_version: HASH_VERSION_CANONICAL, // New logs use canonical SHA-256
             streams: HashMap::new(),
         }
     }
@@ -142,8 +150,11 @@ pub fn compute_event_hash(
     // Timestamp (8 bytes, little-endian)
     hasher.update(timestamp.to_le_bytes());
 
-    // Payload as canonical JSON with length prefix
-    let payload_bytes = serde_json::to_vec(payload).unwrap_or_default();
+    // Payload as canonical JSON with length prefix. Going through
+    // serde_json::Value sorts object keys (its map is a BTreeMap).
+    let payload_bytes = serde_json::to_value(payload)
+        .and_then(|v| serde_json::to_vec(&v))
+        .unwrap_or_default();
     hasher.update((payload_bytes.len() as u32).to_le_bytes());
     hasher.update(&payload_bytes);
 
@@ -823,6 +834,189 @@ impl EventLogExt for TransactionContext {
     }
 }
 
+// ========== Branch Merge Support ==========
+
+/// Decode an event stored under an event key.
+///
+/// Returns `None` for the log metadata key and per-type index keys, which
+/// share the Event type tag but don't hold events.
+pub(crate) fn decode_event(user_key: &[u8], value: &Value) -> Option<Event> {
+    if user_key.len() != 8 || user_key == b"__meta__" {
+        return None;
+    }
+    from_stored_value(value).ok()
+}
+
+/// Append copies of existing events to the log in `ns` within a transaction.
+///
+/// Each event keeps its type, payload and timestamp but is assigned the
+/// log's next sequence and chained onto its current head hash, so the
+/// target log stays contiguous and verifiable. Returns the new sequences.
+pub(crate) fn append_copies(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+    events: &[Event],
+) -> StrataResult<Vec<u64>> {
+    let meta_key = Key::new_event_meta(ns.clone());
+    let mut meta: EventLogMeta = match txn.get(&meta_key)? {
+        Some(v) => from_stored_value(&v).unwrap_or_else(|_| EventLogMeta::default()),
+        None => EventLogMeta::default(),
+    };
+
+    let mut sequences = Vec::with_capacity(events.len());
+    for source in events {
+        let sequence = meta.next_sequence;
+        let hash = compute_event_hash(
+            sequence,
+            &source.event_type,
+            &source.payload,
+            source.timestamp,
+            &meta.head_hash,
+        );
+
+        let event = Event {
+            sequence,
+            event_type: source.event_type.clone(),
+            payload: source.payload.clone(),
+            timestamp: source.timestamp,
+            prev_hash: meta.head_hash,
+            hash,
+        };
+        txn.put(
+            Key::new_event(ns.clone(), sequence),
+            to_stored_value(&event)?,
+        )?;
+        txn.put(
+            Key::new_event_type_idx(ns.clone(), &event.event_type, sequence),
+            Value::Null,
+        )?;
+
+        match meta.streams.get_mut(&event.event_type) {
+            Some(stream_meta) => stream_meta.update(sequence, event.timestamp),
+            None => {
+                meta.streams.insert(
+                    event.event_type.clone(),
+                    StreamMeta::new(sequence, event.timestamp),
+                );
+            }
+        }
+
+        meta.next_sequence = sequence + 1;
+        meta.head_hash = hash;
+        sequences.push(sequence);
+    }
+
+    txn.put(meta_key, to_stored_value(&meta)?)?;
+    Ok(sequences)
+}
+
+// ========== Chain Verification ==========
+
+/// Verify the hash chain of one event log from its stored entries.
+///
+/// `entries` are the Event-tagged entries of a single namespace, as listed
+/// from storage. Checks that sequences run from 0 without gaps, that each
+/// event links to the hash of the one before it, and that the log metadata
+/// agrees with the last event. Event hashes are recomputed only for logs
+/// with hash version 2 or later.
+pub(crate) fn verify_chain(entries: &[(Key, VersionedValue)]) -> ChainVerification {
+    let mut meta: Option<EventLogMeta> = None;
+    let mut events = Vec::new();
+    for (key, versioned) in entries {
+        if key.user_key == b"__meta__" {
+            match from_stored_value(&versioned.value) {
+                Ok(m) => meta = Some(m),
+                Err(e) => {
+                    return ChainVerification::invalid(
+                        0,
+                        0,
+                        format!("log metadata is unreadable: {}", e),
+                    )
+                }
+            }
+        } else if key.user_key.len() == 8 {
+            let sequence = u64::from_be_bytes(key.user_key[..].try_into().unwrap());
+            match from_stored_value::<Event>(&versioned.value) {
+                Ok(event) if event.sequence == sequence => events.push(event),
+                Ok(event) => {
+                    return ChainVerification::invalid(
+                        0,
+                        sequence,
+                        format!(
+                            "event stored as sequence {} claims sequence {}",
+                            sequence, event.sequence
+                        ),
+                    )
+                }
+                Err(e) => {
+                    return ChainVerification::invalid(
+                        0,
+                        sequence,
+                        format!("event is unreadable: {}", e),
+                    )
+                }
+            }
+        }
+    }
+    events.sort_by_key(|e| e.sequence);
+
+    let length = events.len() as u64;
+    let recompute = meta
+        .as_ref()
+        .is_some_and(|m| m.hash_version >= HASH_VERSION_CANONICAL);
+    let mut prev_hash = [0u8; 32];
+    for (expected, event) in (0u64..).zip(&events) {
+        if event.sequence != expected {
+            return ChainVerification::invalid(
+                length,
+                expected,
+                format!("event {} is missing", expected),
+            );
+        }
+        if event.prev_hash != prev_hash {
+            return ChainVerification::invalid(
+                length,
+                expected,
+                "previous hash does not match the preceding event",
+            );
+        }
+        if recompute
+            && compute_event_hash(
+                event.sequence,
+                &event.event_type,
+                &event.payload,
+                event.timestamp,
+                &event.prev_hash,
+            ) != event.hash
+        {
+            return ChainVerification::invalid(
+                length,
+                expected,
+                "hash does not match the event contents",
+            );
+        }
+        prev_hash = event.hash;
+    }
+
+    match meta {
+        Some(meta) if meta.next_sequence != length => ChainVerification::invalid(
+            length,
+            length.min(meta.next_sequence),
+            format!(
+                "log metadata expects {} events, found {}",
+                meta.next_sequence, length
+            ),
+        ),
+        Some(meta) if length > 0 && meta.head_hash != prev_hash => ChainVerification::invalid(
+            length,
+            length - 1,
+            "log head hash does not match the last event",
+        ),
+        None if length > 0 => ChainVerification::invalid(length, 0, "log metadata is missing"),
+        _ => ChainVerification::valid(length),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -873,7 +1067,7 @@ mod tests {
         let meta = EventLogMeta::default();
         assert_eq!(meta.next_sequence, 0);
         assert_eq!(meta.head_hash, [0u8; 32]);
-        assert_eq!(meta.hash_version, HASH_VERSION_SHA256);
+        assert_eq!(meta.hash_version, HASH_VERSION_CANONICAL);
         assert!(meta.streams.is_empty());
     }
 
@@ -1184,6 +1378,32 @@ mod tests {
         assert!(non_zero_after_8 > 0, "SHA-256 should use all 32 bytes");
     }
 
+    #[test]
+    fn test_sha256_hash_ignores_payload_key_order() {
+        // HashMap iteration order differs between map instances; the hash
+        // must be recomputable from a stored (re-deserialized) payload
+        let keys: Vec<String> = (0..16).map(|i| format!("k{}", i)).collect();
+        let forward = Value::Object(keys.iter().map(|k| (k.clone(), Value::Null)).collect());
+        let reverse = Value::Object(
+            keys.iter()
+                .rev()
+                .map(|k| (k.clone(), Value::Null))
+                .collect(),
+        );
+        let stored: Value =
+            serde_json::from_str(&serde_json::to_string(&forward).unwrap()).unwrap();
+
+        let hash = compute_event_hash(1, "test", &forward, 1234567890, &[0u8; 32]);
+        assert_eq!(
+            hash,
+            compute_event_hash(1, "test", &reverse, 1234567890, &[0u8; 32])
+        );
+        assert_eq!(
+            hash,
+            compute_event_hash(1, "test", &stored, 1234567890, &[0u8; 32])
+        );
+    }
+
     // ========== Query by Type Tests ==========
 
     #[test]
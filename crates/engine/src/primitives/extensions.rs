@@ -40,6 +40,8 @@ use strata_core::contract::Version;
 use strata_core::primitives::json::{JsonPath, JsonValue};
 use strata_core::{StrataResult, Value};
 
+use crate::graph::types::{EdgeData, GraphMeta, NodeData};
+
 // Forward declarations - traits are defined here, implementations
 // are added in their respective primitive modules.
 
@@ -126,6 +128,48 @@ pub trait VectorStoreExt {
     ) -> StrataResult<Version>;
 }
 
+/// Graph operations within a transaction
+///
+/// Operate on the transaction's branch. Implemented in `graph/mod.rs`
+pub trait GraphStoreExt {
+    /// Create (or overwrite the metadata of) a graph
+    fn graph_create(&mut self, graph: &str, meta: Option<GraphMeta>) -> StrataResult<()>;
+
+    /// Read graph metadata
+    fn graph_get_meta(&mut self, graph: &str) -> StrataResult<Option<GraphMeta>>;
+
+    /// Delete a graph with all its nodes and edges
+    fn graph_delete(&mut self, graph: &str) -> StrataResult<()>;
+
+    /// Add or update a node
+    fn graph_add_node(&mut self, graph: &str, node_id: &str, data: NodeData) -> StrataResult<()>;
+
+    /// Read a node
+    fn graph_get_node(&mut self, graph: &str, node_id: &str) -> StrataResult<Option<NodeData>>;
+
+    /// Remove a node and its incident edges
+    fn graph_remove_node(&mut self, graph: &str, node_id: &str) -> StrataResult<()>;
+
+    /// Add or update an edge
+    fn graph_add_edge(
+        &mut self,
+        graph: &str,
+        src: &str,
+        dst: &str,
+        edge_type: &str,
+        data: EdgeData,
+    ) -> StrataResult<()>;
+
+    /// Remove an edge
+    fn graph_remove_edge(
+        &mut self,
+        graph: &str,
+        src: &str,
+        dst: &str,
+        edge_type: &str,
+    ) -> StrataResult<()>;
+}
+
 // Note: BranchIndex does not have an extension trait because run operations
 // are typically done outside of cross-primitive transactions. Run lifecycle
 // operations (create, complete, fail) are usually standalone operations
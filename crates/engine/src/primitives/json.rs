@@ -868,6 +868,72 @@ impl JsonStoreExt for TransactionContext {
     }
 }
 
+// =============================================================================
+// Value Conversion
+// =============================================================================
+//
+// Used by branch operations to report documents as plain Values and to
+// accept Values as replacement documents.
+
+/// Convert a JSON value into a [`Value`]
+///
+/// Integers that fit in `i64` become `Value::Int`; other numbers become
+/// `Value::Float`.
+pub(crate) fn json_to_value(json: &serde_json::Value) -> Value {
+    match json {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Bool(*b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Int(i),
+            None => Value::Float(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        serde_json::Value::String(s) => Value::String(s.clone()),
+        serde_json::Value::Array(items) => Value::Array(items.iter().map(json_to_value).collect()),
+        serde_json::Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), json_to_value(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// Convert a [`Value`] into a JSON document value
+///
+/// Fails for bytes and non-finite floats, which have no JSON representation,
+/// and for documents exceeding the JSON limits.
+pub(crate) fn value_to_json(value: &Value) -> StrataResult<JsonValue> {
+    fn convert(value: &Value) -> StrataResult<serde_json::Value> {
+        Ok(match value {
+            Value::Null => serde_json::Value::Null,
+            Value::Bool(b) => serde_json::Value::Bool(*b),
+            Value::Int(i) => serde_json::Value::from(*i),
+            Value::Float(f) => serde_json::Number::from_f64(*f)
+                .map(serde_json::Value::Number)
+                .ok_or_else(|| {
+                    StrataError::invalid_input(format!("{} is not a valid JSON number", f))
+                })?,
+            Value::String(s) => serde_json::Value::String(s.clone()),
+            Value::Bytes(_) => {
+                return Err(StrataError::invalid_input(
+                    "bytes cannot be stored in a JSON document",
+                ))
+            }
+            Value::Array(items) => {
+                serde_json::Value::Array(items.iter().map(convert).collect::<StrataResult<_>>()?)
+            }
+            Value::Object(map) => serde_json::Value::Object(
+                map.iter()
+                    .map(|(k, v)| Ok((k.clone(), convert(v)?)))
+                    .collect::<StrataResult<_>>()?,
+            ),
+        })
+    }
+
+    let json = JsonValue::from_value(convert(value)?);
+    json.validate().map_err(limit_error_to_error)?;
+    Ok(json)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -25,6 +25,7 @@
 use crate::database::Database;
 use crate::primitives::extensions::KVStoreExt;
 use std::sync::Arc;
+use std::time::Duration;
 use strata_concurrency::TransactionContext;
 use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
@@ -137,6 +138,29 @@ impl KVStore {
         space: &str,
         key: &str,
         value: Value,
+    ) -> StrataResult<Version> {
+        self.put_with_ttl(branch_id, space, key, value, None)
+    }
+
+    /// Put a value that expires `ttl` after the write commits
+    ///
+    /// Once the TTL has run out, reads no longer see the key and a
+    /// background task deletes it, leaving a tombstone in its history.
+    /// `ttl: None` is the same as [`put`](Self::put).
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let ttl = Some(Duration::from_secs(30));
+    /// kv.put_with_ttl(&branch_id, "default", "lock:job-7", Value::Bool(true), ttl)?;
+    /// ```
+    pub fn put_with_ttl(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        key: &str,
+        value: Value,
+        ttl: Option<Duration>,
     ) -> StrataResult<Version> {
         // Extract text for indexing before the value is consumed by the transaction
         let text_for_index = match &value {
@@ -147,8 +171,11 @@ impl KVStore {
 
         let ((), commit_version) = self.db.transaction_with_version(*branch_id, |txn| {
             let storage_key = self.key_for(branch_id, space, key);
-            txn.put(storage_key, value)
+            txn.put_with_ttl(storage_key, value, ttl)
         })?;
+        if ttl.is_some() {
+            self.db.start_expiry_reaper();
+        }
 
         // Update inverted index for BM25 search (zero overhead when disabled)
         if let Some(text) = text_for_index {
@@ -229,6 +256,24 @@ impl KVStore {
         branch_id: &BranchId,
         space: &str,
         entries: Vec<(String, Value)>,
+    ) -> StrataResult<Vec<Result<Version, String>>> {
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| (key, value, None))
+            .collect();
+        self.batch_put_with_ttl(branch_id, space, entries)
+    }
+
+    /// Put multiple key-value pairs, each with an optional TTL, in a single
+    /// transaction.
+    ///
+    /// Same as [`batch_put`](Self::batch_put); entries with a TTL expire
+    /// that long after the batch commits.
+    pub fn batch_put_with_ttl(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        entries: Vec<(String, Value, Option<Duration>)>,
     ) -> StrataResult<Vec<Result<Version, String>>> {
         if entries.is_empty() {
             return Ok(Vec::new());
@@ -237,7 +282,7 @@ impl KVStore {
         // Extract text for indexing BEFORE the values are consumed by the transaction
         let texts: Vec<Option<String>> = entries
             .iter()
-            .map(|(_, value)| match value {
+            .map(|(_, value, _)| match value {
                 Value::String(s) => Some(s.clone()),
                 Value::Null | Value::Bool(_) | Value::Bytes(_) => None,
                 other => serde_json::to_string(other).ok(),
@@ -245,19 +290,22 @@ impl KVStore {
             .collect();
 
         let ((), commit_version) = self.db.transaction_with_version(*branch_id, |txn| {
-            for (key, value) in &entries {
+            for (key, value, ttl) in &entries {
                 let storage_key = self.key_for(branch_id, space, key);
-                txn.put(storage_key, value.clone())?;
+                txn.put_with_ttl(storage_key, value.clone(), *ttl)?;
             }
             Ok(())
         })?;
+        if entries.iter().any(|(_, _, ttl)| ttl.is_some()) {
+            self.db.start_expiry_reaper();
+        }
 
         let version = Version::Txn(commit_version);
 
         // Post-commit: update inverted index for all items
         let index = self.db.extension::<crate::search::InvertedIndex>()?;
         let index_enabled = index.is_enabled();
-        for (i, (key, _)) in entries.iter().enumerate() {
+        for (i, (key, _, _)) in entries.iter().enumerate() {
             if index_enabled {
                 if let Some(ref text) = texts[i] {
                     let entity_ref = crate::search::EntityRef::Kv {
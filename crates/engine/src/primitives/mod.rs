@@ -50,7 +50,7 @@ pub mod vector;
 
 // Re-exports - primitives are exported as they're implemented
 pub use branch::{BranchHandle, EventHandle, JsonHandle, KvHandle, StateHandle};
-pub use branch::{BranchIndex, BranchMetadata, BranchStatus};
+pub use branch::{BranchIndex, BranchMetadata, BranchStatus, ForkAncestor, MergeRecord};
 pub use event::{Event, EventLog};
 pub use json::{JsonDoc, JsonStore};
 pub use kv::KVStore;
@@ -59,10 +59,10 @@ pub use state::{State, StateCell};
 pub use vector::{
     register_vector_recovery, validate_collection_name, validate_vector_key, BruteForceBackend,
     CollectionId, CollectionInfo, CollectionRecord, DistanceMetric, FilterCondition, FilterOp,
-    HnswBackend, HnswConfig, IndexBackendFactory, JsonScalar, MetadataFilter, StorageDtype,
-    VectorBackendState, VectorConfig, VectorConfigSerde, VectorEntry, VectorError, VectorHeap,
-    VectorId, VectorIndexBackend, VectorMatch, VectorMatchWithSource, VectorRecord, VectorResult,
-    VectorStore,
+    HnswBackend, HnswConfig, IndexBackendFactory, JsonScalar, MetadataFilter, PendingVectorOp,
+    StorageDtype, VectorBackendState, VectorConfig, VectorConfigSerde, VectorEntry, VectorError,
+    VectorHeap, VectorId, VectorIndexBackend, VectorMatch, VectorMatchWithSource, VectorRecord,
+    VectorResult, VectorStore,
 };
 
 // Re-export search types for convenience (from search module)
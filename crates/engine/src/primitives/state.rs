@@ -19,6 +19,7 @@ use crate::database::{Database, RetryConfig};
 use crate::primitives::extensions::StateCellExt;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Duration;
 use strata_concurrency::TransactionContext;
 use strata_core::contract::{Version, Versioned};
 use strata_core::types::{BranchId, Key, Namespace};
@@ -29,14 +30,14 @@ use strata_core::{StrataResult, VersionedHistory};
 pub use strata_core::primitives::State;
 
 /// Serialize a struct to Value::String for storage
-fn to_stored_value<T: Serialize>(v: &T) -> StrataResult<Value> {
+pub(crate) fn to_stored_value<T: Serialize>(v: &T) -> StrataResult<Value> {
     serde_json::to_string(v)
         .map(Value::String)
         .map_err(|e| strata_core::StrataError::serialization(e.to_string()))
 }
 
 /// Deserialize from Value::String storage
-fn from_stored_value<T: for<'de> Deserialize<'de>>(
+pub(crate) fn from_stored_value<T: for<'de> Deserialize<'de>>(
     v: &Value,
 ) -> std::result::Result<T, serde_json::Error> {
     match v {
@@ -108,7 +109,23 @@ impl StateCell {
         name: &str,
         value: Value,
     ) -> StrataResult<Version> {
-        self.db.transaction(*branch_id, |txn| {
+        self.init_with_ttl(branch_id, space, name, value, None)
+    }
+
+    /// Initialize a cell that expires `ttl` after it is created
+    ///
+    /// Same as [`init`](Self::init); if the cell already exists it is left
+    /// untouched, TTL included. Once the TTL has run out the cell reads as
+    /// missing and can be initialized again, which makes this a lease.
+    pub fn init_with_ttl(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        name: &str,
+        value: Value,
+        ttl: Option<Duration>,
+    ) -> StrataResult<Version> {
+        let version = self.db.transaction(*branch_id, |txn| {
             let key = self.key_for(branch_id, space, name);
 
             // Idempotent: if cell already exists, return existing version
@@ -119,9 +136,13 @@ impl StateCell {
 
             // Create new state
             let state = State::new(value);
-            txn.put(key, to_stored_value(&state)?)?;
+            txn.put_with_ttl(key, to_stored_value(&state)?, ttl)?;
             Ok(state.version)
-        })
+        })?;
+        if ttl.is_some() {
+            self.db.start_expiry_reaper();
+        }
+        Ok(version)
     }
 
     /// Read current state value.
@@ -266,6 +287,21 @@ impl StateCell {
         space: &str,
         name: &str,
         value: Value,
+    ) -> StrataResult<Version> {
+        self.set_with_ttl(branch_id, space, name, value, None)
+    }
+
+    /// Unconditional set of a cell that expires `ttl` after the write
+    ///
+    /// Same as [`set`](Self::set). The TTL belongs to this write: a later
+    /// set or CAS without a TTL makes the cell permanent again.
+    pub fn set_with_ttl(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        name: &str,
+        value: Value,
+        ttl: Option<Duration>,
     ) -> StrataResult<Version> {
         let retry_config = RetryConfig::default()
             .with_max_retries(50)
@@ -292,9 +328,12 @@ impl StateCell {
                     updated_at: State::now(),
                 };
 
-                txn.put(key, to_stored_value(&new_state)?)?;
+                txn.put_with_ttl(key, to_stored_value(&new_state)?, ttl)?;
                 Ok(new_state.version)
             })?;
+        if ttl.is_some() {
+            self.db.start_expiry_reaper();
+        }
 
         // Update inverted index (zero overhead when disabled)
         let index = self.db.extension::<crate::search::InvertedIndex>()?;
@@ -640,6 +679,32 @@ mod tests {
         assert_eq!(v1, v2, "Idempotent init should return same version");
     }
 
+    #[test]
+    fn test_init_with_ttl_acts_as_lease() {
+        let (_temp, _db, sc) = setup();
+        let branch_id = BranchId::new();
+        let ttl = Some(Duration::from_millis(50));
+
+        sc.init_with_ttl(&branch_id, "default", "lock", Value::Int(1), ttl)
+            .unwrap();
+        // Held: a second init leaves the holder in place
+        sc.init_with_ttl(&branch_id, "default", "lock", Value::Int(2), ttl)
+            .unwrap();
+        assert_eq!(
+            sc.get(&branch_id, "default", "lock").unwrap(),
+            Some(Value::Int(1))
+        );
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(sc.get(&branch_id, "default", "lock").unwrap(), None);
+        sc.init_with_ttl(&branch_id, "default", "lock", Value::Int(2), None)
+            .unwrap();
+        assert_eq!(
+            sc.get(&branch_id, "default", "lock").unwrap(),
+            Some(Value::Int(2))
+        );
+    }
+
     #[test]
     fn test_read_nonexistent() {
         let (_temp, _db, sc) = setup();
@@ -133,6 +133,14 @@ pub trait VectorIndexBackend: Send + Sync {
     /// Get a vector by ID (for metadata lookups after search)
     fn get(&self, id: VectorId) -> Option<&[f32]>;
 
+    /// Get the embedding of a deleted vector, if the backend still holds it
+    ///
+    /// Used by branch revert to restore vectors deleted since the point it
+    /// reverts to. Default: `None`.
+    fn get_deleted(&self, _id: VectorId) -> Option<&[f32]> {
+        None
+    }
+
     /// Check if a vector exists
     fn contains(&self, id: VectorId) -> bool;
 
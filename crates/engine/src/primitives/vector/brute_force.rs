@@ -128,6 +128,10 @@ impl VectorIndexBackend for BruteForceBackend {
         self.heap.get(id)
     }
 
+    fn get_deleted(&self, id: VectorId) -> Option<&[f32]> {
+        self.heap.get_deleted(id)
+    }
+
     fn contains(&self, id: VectorId) -> bool {
         self.heap.contains(id)
     }
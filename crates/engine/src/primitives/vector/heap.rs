@@ -81,6 +81,13 @@ pub struct VectorHeap {
     /// This must be persisted in snapshots for correct recovery.
     free_slots: Vec<usize>,
 
+    /// Embeddings of deleted vectors, by their former VectorId
+    ///
+    /// Kept so a branch revert can restore vectors deleted since the point
+    /// it reverts to. Not persisted: a heap rebuilt on reopen or reload
+    /// starts without them.
+    deleted: BTreeMap<VectorId, Vec<f32>>,
+
     /// Next VectorId to allocate (monotonically increasing)
     ///
     /// This value is NEVER decremented, even after deletions.
@@ -110,6 +117,7 @@ impl VectorHeap {
             data: VectorData::InMemory(Vec::new()),
             id_to_offset: BTreeMap::new(),
             free_slots: Vec::new(),
+            deleted: BTreeMap::new(),
             next_id: AtomicU64::new(1),
             version: AtomicU64::new(0),
         }
@@ -131,6 +139,7 @@ impl VectorHeap {
             data: VectorData::InMemory(data),
             id_to_offset,
             free_slots,
+            deleted: BTreeMap::new(),
             next_id: AtomicU64::new(next_id),
             version: AtomicU64::new(0),
         }
@@ -150,6 +159,7 @@ impl VectorHeap {
             data: VectorData::Mmap(mmap_data),
             id_to_offset,
             free_slots,
+            deleted: BTreeMap::new(),
             next_id: AtomicU64::new(next_id),
             version: AtomicU64::new(0),
         })
@@ -242,6 +252,7 @@ impl VectorHeap {
             });
         }
 
+        self.deleted.remove(&id);
         match &mut self.data {
             VectorData::InMemory(vec) => {
                 if let Some(&offset) = self.id_to_offset.get(&id) {
@@ -335,8 +346,13 @@ impl VectorHeap {
     /// The storage slot is added to free_slots for reuse.
     /// The VectorId is NEVER reused (Invariant S4).
     ///
-    /// Security note: Data is zeroed to prevent information leakage.
+    /// Security note: Data is zeroed to prevent information leakage. A copy
+    /// of the embedding stays readable through `get_deleted()` only.
     pub fn delete(&mut self, id: VectorId) -> bool {
+        if let Some(embedding) = self.get(id) {
+            let embedding = embedding.to_vec();
+            self.deleted.insert(id, embedding);
+        }
         match &mut self.data {
             VectorData::InMemory(v) => {
                 if let Some(offset) = self.id_to_offset.remove(&id) {
@@ -394,6 +410,7 @@ impl VectorHeap {
         self.data = VectorData::InMemory(Vec::new());
         self.id_to_offset.clear();
         self.free_slots.clear();
+        self.deleted.clear();
         // Note: next_id is NOT reset — IDs are never reused
         self.version.fetch_add(1, Ordering::Release);
     }
@@ -443,6 +460,14 @@ impl VectorHeap {
         }
     }
 
+    /// Get the embedding a deleted vector had when it was deleted
+    ///
+    /// Returns None if the vector is live, was never deleted, or was deleted
+    /// before this heap was built.
+    pub fn get_deleted(&self, id: VectorId) -> Option<&[f32]> {
+        self.deleted.get(&id).map(|embedding| embedding.as_slice())
+    }
+
     /// Check if a vector exists
     pub fn contains(&self, id: VectorId) -> bool {
         self.id_to_offset.contains_key(&id)
@@ -918,6 +943,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_deleted_embedding_kept_until_reinserted() {
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        let mut heap = VectorHeap::new(config);
+
+        let id = heap.insert(&[1.0, 0.0, 0.0]).unwrap();
+        assert!(heap.get_deleted(id).is_none());
+
+        heap.delete(id);
+        assert!(heap.get(id).is_none());
+        assert_eq!(heap.get_deleted(id), Some(&[1.0, 0.0, 0.0][..]));
+
+        heap.upsert(id, &[0.0, 1.0, 0.0]).unwrap();
+        assert!(heap.get_deleted(id).is_none());
+        assert_eq!(heap.get(id), Some(&[0.0, 1.0, 0.0][..]));
+    }
+
     // ====================================================================
     // mmap integration tests
     // ====================================================================
@@ -1523,6 +1523,10 @@ impl VectorIndexBackend for HnswBackend {
         self.heap.get(id)
     }
 
+    fn get_deleted(&self, id: VectorId) -> Option<&[f32]> {
+        self.heap.get_deleted(id)
+    }
+
     fn contains(&self, id: VectorId) -> bool {
         self.heap.contains(id)
     }
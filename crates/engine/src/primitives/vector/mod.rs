@@ -48,7 +48,7 @@ pub use hnsw::{HnswBackend, HnswConfig};
 pub use recovery::register_vector_recovery;
 pub use segmented::{SegmentedHnswBackend, SegmentedHnswConfig};
 pub use snapshot::{CollectionSnapshotHeader, VECTOR_SNAPSHOT_VERSION};
-pub use store::{RecoveryStats, VectorBackendState, VectorStore};
+pub use store::{PendingVectorOp, RecoveryStats, VectorBackendState, VectorStore};
 pub use types::{
     CollectionId, CollectionInfo, CollectionRecord, DistanceMetric, StorageDtype, VectorConfig,
     VectorConfigSerde, VectorEntry, VectorId, VectorMatch, VectorMatchWithSource, VectorRecord,
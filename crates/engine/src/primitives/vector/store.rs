@@ -84,6 +84,33 @@ impl Default for VectorBackendState {
     }
 }
 
+/// A vector index change staged by a transaction
+///
+/// Returned by [`VectorStore::stage_insert`] and [`VectorStore::stage_delete`].
+/// The KV side of the change commits with the transaction; the in-memory
+/// index is only updated when the op is passed to
+/// [`VectorStore::apply_committed`] after a successful commit.
+#[derive(Debug, Clone)]
+pub struct PendingVectorOp {
+    collection_id: CollectionId,
+    kv_key: Key,
+    vector_id: VectorId,
+    change: PendingVectorChange,
+}
+
+#[derive(Debug, Clone)]
+enum PendingVectorChange {
+    /// Insert or replace the embedding of the record written by the op
+    Upsert {
+        embedding: Vec<f32>,
+        version: u64,
+        created_at: u64,
+        updated_at: u64,
+    },
+    /// Remove the embedding from the index
+    Delete,
+}
+
 /// Vector storage and search primitive
 ///
 /// Manages collections of vectors with similarity search capabilities.
@@ -700,6 +727,201 @@ impl VectorStore {
         Ok(true)
     }
 
+    // ========================================================================
+    // Transactional Operations
+    // ========================================================================
+
+    /// Stage a vector upsert in a caller-owned transaction
+    ///
+    /// Writes the vector's KV record into `txn` but leaves the in-memory
+    /// index alone. The record and the collection config are read through
+    /// `txn`, so a concurrent write to the same key or a concurrent
+    /// collection delete fails the transaction's validation. Once `txn`
+    /// commits, pass the returned op to [`apply_committed`](Self::apply_committed);
+    /// if it aborts, drop the op.
+    ///
+    /// # Errors
+    /// Same as [`insert`](Self::insert).
+    pub fn stage_insert(
+        &self,
+        txn: &mut TransactionContext,
+        space: &str,
+        collection: &str,
+        key: &str,
+        embedding: &[f32],
+        metadata: Option<JsonValue>,
+    ) -> VectorResult<(Version, PendingVectorOp)> {
+        validate_vector_key(key)?;
+        if embedding.iter().any(|v| v.is_nan() || v.is_infinite()) {
+            return Err(VectorError::InvalidEmbedding {
+                reason: "embedding contains NaN or Infinity values".to_string(),
+            });
+        }
+
+        let branch_id = txn.branch_id;
+        self.ensure_collection_loaded(branch_id, space, collection)?;
+        let collection_id = CollectionId::new(branch_id, collection);
+        let ns = self.namespace_for(branch_id, space);
+
+        let config_key = Key::new_vector_config(ns.clone(), collection);
+        let config = match txn
+            .get(&config_key)
+            .map_err(|e| VectorError::Storage(e.to_string()))?
+        {
+            Some(Value::Bytes(bytes)) => {
+                VectorConfig::try_from(CollectionRecord::from_bytes(&bytes)?.config)?
+            }
+            Some(_) => {
+                return Err(VectorError::Serialization(
+                    "Expected Bytes value for collection record".to_string(),
+                ))
+            }
+            None => {
+                return Err(VectorError::CollectionNotFound {
+                    name: collection.to_string(),
+                })
+            }
+        };
+        if embedding.len() != config.dimension {
+            return Err(VectorError::DimensionMismatch {
+                expected: config.dimension,
+                got: embedding.len(),
+            });
+        }
+
+        let kv_key = Key::new_vector(ns, collection, key);
+        let record = match Self::get_vector_record_in(txn, &kv_key)? {
+            Some(mut existing) => {
+                // Update existing: keep the same VectorId
+                existing.update_lite(metadata);
+                existing
+            }
+            None => {
+                // New vector: IDs are never reused, so one allocated by a
+                // transaction that later aborts is simply skipped
+                let state = self.state()?;
+                let mut backends = state.backends.write();
+                let backend = backends.get_mut(&collection_id).ok_or_else(|| {
+                    VectorError::CollectionNotFound {
+                        name: collection.to_string(),
+                    }
+                })?;
+                VectorRecord::new_lite(backend.allocate_id(), metadata)
+            }
+        };
+
+        txn.put(kv_key.clone(), Value::Bytes(record.to_bytes()?))
+            .map_err(|e| VectorError::Storage(e.to_string()))?;
+
+        let op = PendingVectorOp {
+            collection_id,
+            kv_key,
+            vector_id: VectorId(record.vector_id),
+            change: PendingVectorChange::Upsert {
+                embedding: embedding.to_vec(),
+                version: record.version,
+                created_at: record.created_at,
+                updated_at: record.updated_at,
+            },
+        };
+        Ok((Version::counter(record.version), op))
+    }
+
+    /// Stage a vector delete in a caller-owned transaction
+    ///
+    /// Deletes the vector's KV record in `txn`. Returns `None` if the vector
+    /// does not exist (as seen by `txn`); otherwise the returned op must be
+    /// passed to [`apply_committed`](Self::apply_committed) once `txn` commits.
+    pub fn stage_delete(
+        &self,
+        txn: &mut TransactionContext,
+        space: &str,
+        collection: &str,
+        key: &str,
+    ) -> VectorResult<Option<PendingVectorOp>> {
+        let branch_id = txn.branch_id;
+        self.ensure_collection_loaded(branch_id, space, collection)?;
+
+        let kv_key = Key::new_vector(self.namespace_for(branch_id, space), collection, key);
+        let Some(record) = Self::get_vector_record_in(txn, &kv_key)? else {
+            return Ok(None);
+        };
+        txn.delete(kv_key.clone())
+            .map_err(|e| VectorError::Storage(e.to_string()))?;
+
+        Ok(Some(PendingVectorOp {
+            collection_id: CollectionId::new(branch_id, collection),
+            kv_key,
+            vector_id: VectorId(record.vector_id),
+            change: PendingVectorChange::Delete,
+        }))
+    }
+
+    /// Apply the index changes of a committed transaction
+    ///
+    /// Ops are applied in the order they were staged. An op whose record has
+    /// been overwritten by a later commit is skipped, since the index already
+    /// holds (or will receive) that commit's change; ops on a collection that
+    /// has been deleted are skipped too.
+    pub fn apply_committed(&self, ops: Vec<PendingVectorOp>) -> VectorResult<()> {
+        if ops.is_empty() {
+            return Ok(());
+        }
+
+        // Hold the write lock across the record check and the index update,
+        // like `insert` does across its commit, so the two cannot interleave
+        let state = self.state()?;
+        let mut backends = state.backends.write();
+        for op in ops {
+            let Some(backend) = backends.get_mut(&op.collection_id) else {
+                continue;
+            };
+            let current = self.get_vector_record_by_key(&op.kv_key)?;
+            match op.change {
+                PendingVectorChange::Upsert {
+                    embedding,
+                    version,
+                    created_at,
+                    updated_at,
+                } => {
+                    let is_current = current.is_some_and(|r| {
+                        r.vector_id == op.vector_id.as_u64()
+                            && r.version == version
+                            && r.updated_at == updated_at
+                    });
+                    if is_current {
+                        backend.insert_with_timestamp(op.vector_id, &embedding, created_at)?;
+                    }
+                }
+                PendingVectorChange::Delete => {
+                    let reinserted =
+                        matches!(&current, Some(r) if r.vector_id == op.vector_id.as_u64());
+                    if !reinserted {
+                        backend.delete_with_timestamp(op.vector_id, now_micros())?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a vector record through a transaction (records the read)
+    fn get_vector_record_in(
+        txn: &mut TransactionContext,
+        key: &Key,
+    ) -> VectorResult<Option<VectorRecord>> {
+        match txn
+            .get(key)
+            .map_err(|e| VectorError::Storage(e.to_string()))?
+        {
+            Some(Value::Bytes(bytes)) => Ok(Some(VectorRecord::from_bytes(&bytes)?)),
+            Some(_) => Err(VectorError::Serialization(
+                "Expected Bytes value for vector record".to_string(),
+            )),
+            None => Ok(None),
+        }
+    }
+
     /// Batch insert multiple vectors (upsert semantics)
     ///
     /// Acquires the write lock once, validates all entries, commits all KV writes,
@@ -2135,8 +2357,12 @@ impl VectorStore {
                                 }
                             }
                         } else {
-                            // Target-originated: try old target backend first, then source
-                            match old_backend.as_ref().and_then(|b| b.get(old_vid)) {
+                            // Target-originated: try old target backend first (including
+                            // vectors it deleted, which a revert brings back), then source
+                            let old_embedding = old_backend
+                                .as_ref()
+                                .and_then(|b| b.get(old_vid).or_else(|| b.get_deleted(old_vid)));
+                            match old_embedding {
                                 Some(emb) => emb.to_vec(),
                                 None => {
                                     if let Some(src_bid) = source_branch_id {
@@ -2445,13 +2671,11 @@ fn now_micros() -> u64 {
 //
 // Extension trait implementation for cross-primitive transactions.
 //
-// LIMITATION: VectorStore operations in transactions have limited support
-// because embeddings are stored in in-memory backends (VectorHeap/HNSW)
-// which are not accessible through TransactionContext. Full vector operations
-// require access to the VectorBackendState which is Database-scoped.
-//
-// Future enhancement: Could add pending_vector_ops to TransactionContext
-// and apply them at commit time, but this requires significant infrastructure.
+// LIMITATION: embeddings live in in-memory backends (VectorHeap/HNSW) which
+// are not accessible through TransactionContext, so these methods return
+// errors. Transactional writes go through `VectorStore::stage_insert` /
+// `stage_delete`, which buffer the index change until the caller applies it
+// with `VectorStore::apply_committed` after commit.
 
 impl VectorStoreExt for TransactionContext {
     fn vector_get(
@@ -3110,6 +3334,157 @@ mod tests {
     // WAL Replay Tests
     // ========================================
 
+    #[test]
+    fn test_staged_upsert_reaches_index_after_commit() {
+        let (_temp, db, store) = setup();
+        let branch_id = BranchId::new();
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+
+        let mut txn = db.begin_transaction(branch_id);
+        let (_, op) = store
+            .stage_insert(&mut txn, "default", "test", "a", &[1.0, 0.0, 0.0], None)
+            .unwrap();
+        assert!(store
+            .get(branch_id, "default", "test", "a")
+            .unwrap()
+            .is_none());
+
+        db.commit_transaction(&mut txn).unwrap();
+        db.end_transaction(txn);
+        store.apply_committed(vec![op]).unwrap();
+
+        let entry = store
+            .get(branch_id, "default", "test", "a")
+            .unwrap()
+            .unwrap()
+            .value;
+        assert_eq!(entry.embedding, vec![1.0, 0.0, 0.0]);
+        let results = store
+            .search(branch_id, "default", "test", &[1.0, 0.0, 0.0], 1, None)
+            .unwrap();
+        assert_eq!(results[0].key, "a");
+    }
+
+    #[test]
+    fn test_staged_ops_are_dropped_on_abort() {
+        let (_temp, db, store) = setup();
+        let branch_id = BranchId::new();
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+        store
+            .insert(branch_id, "default", "test", "keep", &[0.0, 1.0, 0.0], None)
+            .unwrap();
+
+        let mut txn = db.begin_transaction(branch_id);
+        store
+            .stage_insert(&mut txn, "default", "test", "a", &[1.0, 0.0, 0.0], None)
+            .unwrap();
+        assert!(store
+            .stage_delete(&mut txn, "default", "test", "keep")
+            .unwrap()
+            .is_some());
+        db.end_transaction(txn);
+
+        assert!(store
+            .get(branch_id, "default", "test", "a")
+            .unwrap()
+            .is_none());
+        let results = store
+            .search(branch_id, "default", "test", &[0.0, 1.0, 0.0], 10, None)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].key, "keep");
+    }
+
+    #[test]
+    fn test_staged_upsert_conflicts_with_concurrent_insert() {
+        let (_temp, db, store) = setup();
+        let branch_id = BranchId::new();
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+
+        let mut txn = db.begin_transaction(branch_id);
+        store
+            .stage_insert(&mut txn, "default", "test", "a", &[1.0, 0.0, 0.0], None)
+            .unwrap();
+        store
+            .insert(branch_id, "default", "test", "a", &[0.0, 1.0, 0.0], None)
+            .unwrap();
+
+        let err = db.commit_transaction(&mut txn).unwrap_err();
+        assert!(
+            matches!(err, strata_core::StrataError::TransactionAborted { .. }),
+            "{:?}",
+            err
+        );
+        db.end_transaction(txn);
+        let entry = store
+            .get(branch_id, "default", "test", "a")
+            .unwrap()
+            .unwrap()
+            .value;
+        assert_eq!(entry.embedding, vec![0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_staged_delete_then_reinsert() {
+        let (_temp, db, store) = setup();
+        let branch_id = BranchId::new();
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+        store
+            .insert(branch_id, "default", "test", "a", &[1.0, 0.0, 0.0], None)
+            .unwrap();
+        store
+            .insert(branch_id, "default", "test", "b", &[0.0, 1.0, 0.0], None)
+            .unwrap();
+
+        let mut txn = db.begin_transaction(branch_id);
+        let mut ops = Vec::new();
+        ops.extend(
+            store
+                .stage_delete(&mut txn, "default", "test", "a")
+                .unwrap(),
+        );
+        ops.extend(
+            store
+                .stage_delete(&mut txn, "default", "test", "b")
+                .unwrap(),
+        );
+        let (_, op) = store
+            .stage_insert(&mut txn, "default", "test", "b", &[0.0, 0.0, 1.0], None)
+            .unwrap();
+        ops.push(op);
+        db.commit_transaction(&mut txn).unwrap();
+        db.end_transaction(txn);
+        store.apply_committed(ops).unwrap();
+
+        assert!(store
+            .get(branch_id, "default", "test", "a")
+            .unwrap()
+            .is_none());
+        let results = store
+            .search(branch_id, "default", "test", &[1.0, 0.0, 0.0], 10, None)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].key, "b");
+        let entry = store
+            .get(branch_id, "default", "test", "b")
+            .unwrap()
+            .unwrap()
+            .value;
+        assert_eq!(entry.embedding, vec![0.0, 0.0, 1.0]);
+    }
+
     #[test]
     fn test_replay_create_collection() {
         let (_temp, _db, store) = setup();
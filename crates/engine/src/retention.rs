@@ -0,0 +1,681 @@
+//! Retention: pruning branch version history by policy
+//!
+//! Engine-level module that enforces the [`RetentionPolicy`] stored with
+//! each branch (see [`BranchIndex::set_retention`]), following the pattern
+//! established by `branch_ops.rs`.
+//!
+//! ## Operations
+//!
+//! - `get_branch_retention` / `set_branch_retention` — Read or set a
+//!   branch's policy
+//! - `retention_stats` — Keys, versions and bytes a branch holds, per primitive
+//! - `preview_retention` — Exactly which versions applying the policy would prune
+//! - `apply_retention` — Prune those versions
+//!
+//! ## Horizon
+//!
+//! Pruning never removes the latest version of a key, so current reads are
+//! unaffected. A time-travel read from before a pruned version's successor
+//! was written would however see an older value, or none. After pruning,
+//! the branch records a [`RetentionHorizon`]: the point from which its
+//! history is still complete. Time-travel reads, and forks, reverts and
+//! diffs as of a timestamp, fail with `HistoryTrimmed` before the horizon.
+//!
+//! Versions that a copy-on-write fork of the branch reads through are never
+//! pruned. Pruning happens in memory: versions that recovery replays from
+//! the WAL come back until retention is applied again, and the persisted
+//! horizon keeps rejecting reads before it in the meantime.
+
+use crate::branch_ops::render_user_key;
+use crate::database::Database;
+use crate::primitives::branch::resolve_branch_name;
+use crate::{BranchIndex, BranchMetadata};
+use dashmap::DashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use strata_core::contract::{EntityRef, Timestamp, Version};
+use strata_core::types::{BranchId, Key, TypeTag};
+use strata_core::PrimitiveType;
+use strata_core::StrataError;
+use strata_core::StrataResult;
+use strata_durability::retention::RetentionPolicy;
+use tracing::info;
+
+/// Primitives whose history retention prunes, in report order
+const RETAINED_PRIMITIVES: [PrimitiveType; 5] = [
+    PrimitiveType::Kv,
+    PrimitiveType::Event,
+    PrimitiveType::State,
+    PrimitiveType::Json,
+    PrimitiveType::Vector,
+];
+
+// =============================================================================
+// Public result types
+// =============================================================================
+
+/// The point from which a pruned branch's history is complete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionHorizon {
+    /// Oldest storage version whose state of the branch is still readable
+    pub version: u64,
+    /// Commit time of `version` (microseconds since epoch); reads as of an
+    /// earlier time fail
+    pub timestamp: u64,
+}
+
+impl RetentionHorizon {
+    /// The horizon covering both `self` and `other`.
+    pub fn max(self, other: RetentionHorizon) -> RetentionHorizon {
+        RetentionHorizon {
+            version: self.version.max(other.version),
+            timestamp: self.timestamp.max(other.timestamp),
+        }
+    }
+}
+
+/// A branch's retention policy and horizon.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BranchRetention {
+    /// Branch name
+    pub branch: String,
+    /// Stored policy (`KeepAll` if none was set)
+    pub policy: RetentionPolicy,
+    /// Horizon left by past pruning, if any
+    pub horizon: Option<RetentionHorizon>,
+}
+
+impl BranchRetention {
+    fn from_metadata(meta: BranchMetadata) -> Self {
+        BranchRetention {
+            branch: meta.name,
+            policy: meta.retention.unwrap_or_default(),
+            horizon: meta.retention_horizon,
+        }
+    }
+}
+
+/// Keys, versions and bytes of one primitive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrimitiveRetention {
+    /// Primitive the counts are for
+    pub primitive: PrimitiveType,
+    /// Number of keys
+    pub keys: u64,
+    /// Number of versions, tombstones included
+    pub versions: u64,
+    /// Approximate bytes the versions hold in memory
+    pub bytes: u64,
+}
+
+impl PrimitiveRetention {
+    fn empty(primitive: PrimitiveType) -> Self {
+        PrimitiveRetention {
+            primitive,
+            keys: 0,
+            versions: 0,
+            bytes: 0,
+        }
+    }
+}
+
+/// Version history a branch holds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetentionStats {
+    /// Branch name
+    pub branch: String,
+    /// Stored policy
+    pub policy: RetentionPolicy,
+    /// Horizon left by past pruning, if any
+    pub horizon: Option<RetentionHorizon>,
+    /// History held per primitive. Keys a fork inherits count toward the
+    /// branch that wrote them.
+    pub primitives: Vec<PrimitiveRetention>,
+}
+
+impl RetentionStats {
+    /// Versions held across all primitives
+    pub fn versions(&self) -> u64 {
+        self.primitives.iter().map(|p| p.versions).sum()
+    }
+
+    /// Approximate bytes held across all primitives
+    pub fn bytes(&self) -> u64 {
+        self.primitives.iter().map(|p| p.bytes).sum()
+    }
+}
+
+/// A version that applying the retention policy would prune.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrunableVersion {
+    /// Primitive the key belongs to
+    pub primitive: PrimitiveType,
+    /// Space the key belongs to
+    pub space: String,
+    /// User key, readable (event sequences as numbers)
+    pub key: String,
+    /// Storage version
+    pub version: u64,
+    /// Commit time (microseconds since epoch)
+    pub timestamp: u64,
+    /// Approximate bytes the version holds in memory
+    pub bytes: u64,
+    /// Whether the version is a deletion marker
+    pub tombstone: bool,
+}
+
+/// What applying a branch's retention policy would prune.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetentionPreview {
+    /// Branch name
+    pub branch: String,
+    /// Stored policy
+    pub policy: RetentionPolicy,
+    /// Prunable history per primitive; `keys` counts keys that would lose
+    /// versions
+    pub primitives: Vec<PrimitiveRetention>,
+    /// Every prunable version, ordered by primitive, space and key, newest
+    /// version first
+    pub versions: Vec<PrunableVersion>,
+    /// Horizon the branch would have after applying the policy
+    pub horizon: Option<RetentionHorizon>,
+}
+
+/// Result of applying a branch's retention policy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionApplyInfo {
+    /// Branch name
+    pub branch: String,
+    /// Number of versions pruned
+    pub versions_pruned: u64,
+    /// Approximate bytes freed
+    pub bytes_freed: u64,
+    /// Horizon after pruning, if the branch has ever been pruned
+    pub horizon: Option<RetentionHorizon>,
+}
+
+// =============================================================================
+// Horizon checks
+// =============================================================================
+
+/// Retention horizons of pruned branches.
+///
+/// Kept as a database extension so time-travel reads don't need to read
+/// branch metadata. Branches that are absent have complete history.
+#[derive(Default)]
+pub(crate) struct RetentionHorizons {
+    horizons: DashMap<BranchId, RetentionHorizon>,
+}
+
+impl RetentionHorizons {
+    pub(crate) fn set(&self, branch_id: BranchId, horizon: RetentionHorizon) {
+        self.horizons.insert(branch_id, horizon);
+    }
+
+    pub(crate) fn remove(&self, branch_id: &BranchId) {
+        self.horizons.remove(branch_id);
+    }
+}
+
+/// Check that retention has kept the history a read of `branch_id` as of
+/// `as_of` (microseconds since epoch) needs.
+///
+/// A copy-on-write fork also reads through its ancestors, so their horizons
+/// apply too.
+///
+/// # Errors
+///
+/// `HistoryTrimmed` if `as_of` is before the horizon. Its versions are the
+/// newest version still visible at `as_of` and the horizon's version.
+pub(crate) fn check_retained(db: &Database, branch_id: &BranchId, as_of: u64) -> StrataResult<()> {
+    let horizons = db.extension::<RetentionHorizons>()?;
+    if horizons.horizons.is_empty() {
+        return Ok(());
+    }
+    let ancestry = db.storage().branch_ancestry(branch_id);
+    let lineage = std::iter::once(*branch_id).chain(ancestry.iter().map(|a| a.branch_id));
+    for id in lineage {
+        let Some(horizon) = horizons.horizons.get(&id).map(|h| *h) else {
+            continue;
+        };
+        if as_of < horizon.timestamp {
+            let requested = db.storage().version_at_timestamp(branch_id, as_of)?;
+            return Err(StrataError::history_trimmed(
+                EntityRef::branch(id),
+                Version::txn(requested),
+                Version::txn(horizon.version),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Policy
+// =============================================================================
+
+/// Get a branch's retention policy and horizon.
+///
+/// # Errors
+///
+/// - Branch does not exist
+pub fn get_branch_retention(db: &Arc<Database>, branch: &str) -> StrataResult<BranchRetention> {
+    Ok(BranchRetention::from_metadata(branch_metadata(db, branch)?))
+}
+
+/// Set a branch's retention policy.
+///
+/// The policy takes effect the next time retention is applied.
+///
+/// # Errors
+///
+/// - Branch does not exist
+/// - Policy is invalid (`KeepLast(0)`, `KeepFor` of zero)
+pub fn set_branch_retention(
+    db: &Arc<Database>,
+    branch: &str,
+    policy: RetentionPolicy,
+) -> StrataResult<BranchRetention> {
+    let meta = BranchIndex::new(db.clone()).set_retention(branch, policy)?;
+    Ok(BranchRetention::from_metadata(meta.value))
+}
+
+// =============================================================================
+// Stats, preview and apply
+// =============================================================================
+
+/// Report the keys, versions and bytes a branch holds, per primitive.
+///
+/// # Errors
+///
+/// - Branch does not exist
+pub fn retention_stats(db: &Arc<Database>, branch: &str) -> StrataResult<RetentionStats> {
+    let meta = branch_metadata(db, branch)?;
+    let policy = meta.retention.unwrap_or_default();
+    let plan = RetentionPlan::build(db, &resolve_branch_name(branch), &policy)?;
+    Ok(RetentionStats {
+        branch: meta.name,
+        policy,
+        horizon: meta.retention_horizon,
+        primitives: plan.held,
+    })
+}
+
+/// Report exactly which versions applying a branch's policy would prune.
+///
+/// # Errors
+///
+/// - Branch does not exist
+pub fn preview_retention(db: &Arc<Database>, branch: &str) -> StrataResult<RetentionPreview> {
+    let meta = branch_metadata(db, branch)?;
+    let policy = meta.retention.unwrap_or_default();
+    let plan = RetentionPlan::build(db, &resolve_branch_name(branch), &policy)?;
+    let horizon = match (meta.retention_horizon, plan.horizon) {
+        (Some(stored), Some(planned)) => Some(stored.max(planned)),
+        (stored, planned) => stored.or(planned),
+    };
+    Ok(RetentionPreview {
+        branch: meta.name,
+        policy,
+        primitives: plan.prunable,
+        versions: plan.versions,
+        horizon,
+    })
+}
+
+/// Prune the versions a branch's retention policy no longer keeps.
+///
+/// The horizon is recorded before anything is pruned, so time-travel reads
+/// racing with the pruning are already rejected. With no policy (or
+/// `KeepAll`) nothing is pruned.
+///
+/// # Errors
+///
+/// - Branch does not exist
+pub fn apply_retention(db: &Arc<Database>, branch: &str) -> StrataResult<RetentionApplyInfo> {
+    let meta = branch_metadata(db, branch)?;
+    let policy = meta.retention.unwrap_or_default();
+    let branch_id = resolve_branch_name(branch);
+    let plan = RetentionPlan::build(db, &branch_id, &policy)?;
+
+    let horizon = match plan.horizon {
+        Some(horizon) => {
+            Some(BranchIndex::new(db.clone()).advance_retention_horizon(branch, horizon)?)
+        }
+        None => meta.retention_horizon,
+    };
+    let (versions_pruned, bytes_freed) = db.storage().prune_versions(&branch_id, &plan.prune)?;
+
+    info!(
+        target: "strata::retention",
+        branch,
+        policy = %policy.summary(),
+        versions_pruned,
+        bytes_freed,
+        "Retention applied"
+    );
+
+    Ok(RetentionApplyInfo {
+        branch: meta.name,
+        versions_pruned: versions_pruned as u64,
+        bytes_freed: bytes_freed as u64,
+        horizon,
+    })
+}
+
+// =============================================================================
+// Planning
+// =============================================================================
+
+/// What a policy keeps and prunes of a branch's own history.
+struct RetentionPlan {
+    /// History held, per primitive in `RETAINED_PRIMITIVES` order
+    held: Vec<PrimitiveRetention>,
+    /// Prunable history, per primitive in `RETAINED_PRIMITIVES` order
+    prunable: Vec<PrimitiveRetention>,
+    /// Every prunable version
+    versions: Vec<PrunableVersion>,
+    /// Prunable versions by key
+    prune: Vec<(Key, Vec<u64>)>,
+    /// Horizon after pruning, if anything is pruned
+    horizon: Option<RetentionHorizon>,
+}
+
+impl RetentionPlan {
+    /// Walk every version of the branch's own keys, newest first, and ask
+    /// the policy about each. The latest version of a key and versions
+    /// copy-on-write forks read through are always kept.
+    fn build(db: &Database, branch_id: &BranchId, policy: &RetentionPolicy) -> StrataResult<Self> {
+        let now = Timestamp::now().as_micros();
+        let storage = db.storage();
+        let pins = storage.fork_pins(branch_id);
+
+        let mut plan = RetentionPlan {
+            held: RETAINED_PRIMITIVES.map(PrimitiveRetention::empty).to_vec(),
+            prunable: RETAINED_PRIMITIVES.map(PrimitiveRetention::empty).to_vec(),
+            versions: Vec::new(),
+            prune: Vec::new(),
+            horizon: None,
+        };
+        storage.visit_branch_versions(branch_id, |key, versions| {
+            let Some(slot) = retained_slot(key.type_tag) else {
+                return;
+            };
+            let primitive = RETAINED_PRIMITIVES[slot];
+            let held = &mut plan.held[slot];
+            held.keys += 1;
+            held.versions += versions.len() as u64;
+            held.bytes += versions
+                .iter()
+                .map(|sv| sv.approx_size() as u64)
+                .sum::<u64>();
+
+            let pinned: Vec<usize> = pins
+                .iter()
+                .filter_map(|&pin| versions.iter().position(|sv| sv.version().as_u64() <= pin))
+                .collect();
+            let mut pruned = Vec::new();
+            for (index, sv) in versions.iter().enumerate().skip(1) {
+                let version = sv.version().as_u64();
+                let timestamp = u64::from(sv.timestamp());
+                if pinned.contains(&index)
+                    || policy.should_retain(version, timestamp, index + 1, now, primitive)
+                {
+                    continue;
+                }
+                if pruned.is_empty() {
+                    // Reads are exact again from the newest pruned version's
+                    // successor on
+                    let successor = versions[index - 1];
+                    let horizon = RetentionHorizon {
+                        version: successor.version().as_u64(),
+                        timestamp: u64::from(successor.timestamp()),
+                    };
+                    plan.horizon = Some(plan.horizon.map_or(horizon, |h| h.max(horizon)));
+                }
+                let bytes = sv.approx_size() as u64;
+                plan.prunable[slot].versions += 1;
+                plan.prunable[slot].bytes += bytes;
+                plan.versions.push(PrunableVersion {
+                    primitive,
+                    space: key.namespace.space.clone(),
+                    key: render_user_key(key),
+                    version,
+                    timestamp,
+                    bytes,
+                    tombstone: sv.is_tombstone(),
+                });
+                pruned.push(version);
+            }
+            if !pruned.is_empty() {
+                plan.prunable[slot].keys += 1;
+                plan.prune.push((key.clone(), pruned));
+            }
+        })?;
+
+        plan.versions.sort_by(|a, b| {
+            (primitive_slot(a.primitive), &a.space, &a.key, b.version).cmp(&(
+                primitive_slot(b.primitive),
+                &b.space,
+                &b.key,
+                a.version,
+            ))
+        });
+        Ok(plan)
+    }
+}
+
+/// Position in `RETAINED_PRIMITIVES` of the primitive a type tag stores,
+/// or `None` for metadata tags retention leaves alone.
+fn retained_slot(tag: TypeTag) -> Option<usize> {
+    let primitive = match tag {
+        TypeTag::KV => PrimitiveType::Kv,
+        TypeTag::Event => PrimitiveType::Event,
+        TypeTag::State => PrimitiveType::State,
+        TypeTag::Json => PrimitiveType::Json,
+        TypeTag::Vector => PrimitiveType::Vector,
+        _ => return None,
+    };
+    Some(primitive_slot(primitive))
+}
+
+fn primitive_slot(primitive: PrimitiveType) -> usize {
+    RETAINED_PRIMITIVES
+        .iter()
+        .position(|p| *p == primitive)
+        .unwrap_or(RETAINED_PRIMITIVES.len())
+}
+
+/// Read a branch's metadata, failing if it doesn't exist.
+fn branch_metadata(db: &Arc<Database>, branch: &str) -> StrataResult<BranchMetadata> {
+    BranchIndex::new(db.clone())
+        .get_branch(branch)?
+        .map(|meta| meta.value)
+        .ok_or_else(|| StrataError::invalid_input(format!("Branch '{}' not found", branch)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{KVStore, StateCell};
+    use std::time::Duration;
+    use strata_core::types::Namespace;
+    use strata_core::value::Value;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Arc<Database>, BranchId) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+        BranchIndex::new(db.clone()).create_branch("main").unwrap();
+        (temp_dir, db, resolve_branch_name("main"))
+    }
+
+    fn kv_key(branch_id: BranchId, key: &str) -> Key {
+        Key::new_kv(Namespace::for_branch_space(branch_id, "default"), key)
+    }
+
+    /// Versions of a key, newest first
+    fn history(db: &Database, key: &Key) -> Vec<(u64, u64)> {
+        db.get_history(key, None, None)
+            .unwrap()
+            .iter()
+            .map(|vv| (vv.version.as_u64(), vv.timestamp.as_micros()))
+            .collect()
+    }
+
+    #[test]
+    fn test_set_and_get_retention() {
+        let (_temp, db, _) = setup();
+
+        let initial = get_branch_retention(&db, "main").unwrap();
+        assert_eq!(initial.policy, RetentionPolicy::KeepAll);
+        assert_eq!(initial.horizon, None);
+
+        let set = set_branch_retention(&db, "main", RetentionPolicy::keep_last(3)).unwrap();
+        assert_eq!(set.policy, RetentionPolicy::KeepLast(3));
+        assert_eq!(
+            get_branch_retention(&db, "main").unwrap().policy,
+            RetentionPolicy::KeepLast(3)
+        );
+
+        assert!(set_branch_retention(&db, "main", RetentionPolicy::KeepLast(0)).is_err());
+        assert!(set_branch_retention(&db, "missing", RetentionPolicy::keep_last(1)).is_err());
+        assert!(get_branch_retention(&db, "missing").is_err());
+    }
+
+    #[test]
+    fn test_preview_stats_and_apply_keep_last() {
+        let (_temp, db, branch_id) = setup();
+        let kv = KVStore::new(db.clone());
+        for i in 0..4 {
+            kv.put(&branch_id, "default", "a", Value::Int(i)).unwrap();
+        }
+        kv.put(&branch_id, "default", "b", Value::Int(0)).unwrap();
+        let a = kv_key(branch_id, "a");
+        let before = history(&db, &a);
+
+        // Without a policy nothing is prunable
+        assert!(preview_retention(&db, "main").unwrap().versions.is_empty());
+        let stats = retention_stats(&db, "main").unwrap();
+        assert_eq!(stats.primitives[0].primitive, PrimitiveType::Kv);
+        assert_eq!(stats.primitives[0].keys, 2);
+        assert_eq!(stats.primitives[0].versions, 5);
+        assert!(stats.bytes() > 0);
+
+        set_branch_retention(&db, "main", RetentionPolicy::keep_last(2)).unwrap();
+        let preview = preview_retention(&db, "main").unwrap();
+        let pruned: Vec<u64> = preview.versions.iter().map(|v| v.version).collect();
+        assert_eq!(pruned, vec![before[2].0, before[3].0]);
+        assert!(preview.versions.iter().all(|v| v.key == "a"));
+        assert_eq!(preview.primitives[0].keys, 1);
+        assert_eq!(preview.primitives[0].versions, 2);
+        let expected_horizon = RetentionHorizon {
+            version: before[1].0,
+            timestamp: before[1].1,
+        };
+        assert_eq!(preview.horizon, Some(expected_horizon));
+
+        let applied = apply_retention(&db, "main").unwrap();
+        assert_eq!(applied.versions_pruned, 2);
+        assert_eq!(applied.bytes_freed, preview.primitives[0].bytes);
+        assert_eq!(applied.horizon, Some(expected_horizon));
+        assert_eq!(history(&db, &a), before[..2].to_vec());
+        assert_eq!(retention_stats(&db, "main").unwrap().versions(), 3);
+        assert!(preview_retention(&db, "main").unwrap().versions.is_empty());
+
+        // Reads from the horizon on still work; earlier ones are rejected
+        assert_eq!(
+            kv.get_at(&branch_id, "default", "a", expected_horizon.timestamp)
+                .unwrap(),
+            Some(Value::Int(2))
+        );
+        let err = kv
+            .get_at(&branch_id, "default", "a", expected_horizon.timestamp - 1)
+            .unwrap_err();
+        assert!(matches!(err, StrataError::HistoryTrimmed { .. }), "{err}");
+        assert!(kv
+            .list_at(&branch_id, "default", None, before[3].1)
+            .is_err());
+    }
+
+    #[test]
+    fn test_composite_policy_overrides_per_primitive() {
+        let (_temp, db, branch_id) = setup();
+        let kv = KVStore::new(db.clone());
+        let state = StateCell::new(db.clone());
+        for i in 0..3 {
+            kv.put(&branch_id, "default", "k", Value::Int(i)).unwrap();
+            state
+                .set(&branch_id, "default", "s", Value::Int(i))
+                .unwrap();
+        }
+
+        let policy = RetentionPolicy::composite(RetentionPolicy::keep_all())
+            .with_override(PrimitiveType::Kv, RetentionPolicy::keep_last(1))
+            .build();
+        set_branch_retention(&db, "main", policy).unwrap();
+        let preview = preview_retention(&db, "main").unwrap();
+        assert_eq!(preview.versions.len(), 2);
+        assert!(preview
+            .versions
+            .iter()
+            .all(|v| v.primitive == PrimitiveType::Kv));
+
+        let recent = RetentionPolicy::keep_for(Duration::from_secs(3600));
+        set_branch_retention(&db, "main", recent).unwrap();
+        assert!(preview_retention(&db, "main").unwrap().versions.is_empty());
+    }
+
+    #[test]
+    fn test_apply_keeps_versions_forks_read() {
+        let (_temp, db, branch_id) = setup();
+        let kv = KVStore::new(db.clone());
+        kv.put(&branch_id, "default", "k", Value::Int(1)).unwrap();
+        kv.put(&branch_id, "default", "k", Value::Int(2)).unwrap();
+        crate::branch_ops::fork_branch(&db, "main", "child").unwrap();
+        kv.put(&branch_id, "default", "k", Value::Int(3)).unwrap();
+
+        set_branch_retention(&db, "main", RetentionPolicy::keep_last(1)).unwrap();
+        let applied = apply_retention(&db, "main").unwrap();
+        assert_eq!(applied.versions_pruned, 1);
+        assert_eq!(
+            kv.get(&resolve_branch_name("child"), "default", "k")
+                .unwrap(),
+            Some(Value::Int(2))
+        );
+        assert_eq!(history(&db, &kv_key(branch_id, "k")).len(), 2);
+    }
+
+    #[test]
+    fn test_horizon_survives_reopen_and_delete_clears_it() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let branch_id = resolve_branch_name("main");
+        let first_ts = {
+            let db = Database::open(&path).unwrap();
+            BranchIndex::new(db.clone()).create_branch("main").unwrap();
+            let kv = KVStore::new(db.clone());
+            kv.put(&branch_id, "default", "k", Value::Int(1)).unwrap();
+            kv.put(&branch_id, "default", "k", Value::Int(2)).unwrap();
+            let first_ts = history(&db, &kv_key(branch_id, "k"))[1].1;
+            set_branch_retention(&db, "main", RetentionPolicy::keep_last(1)).unwrap();
+            apply_retention(&db, "main").unwrap();
+            db.flush().unwrap();
+            first_ts
+        };
+
+        let db = Database::open(&path).unwrap();
+        let kv = KVStore::new(db.clone());
+        assert!(get_branch_retention(&db, "main").unwrap().horizon.is_some());
+        assert!(matches!(
+            kv.get_at(&branch_id, "default", "k", first_ts),
+            Err(StrataError::HistoryTrimmed { .. })
+        ));
+
+        let branches = BranchIndex::new(db.clone());
+        branches.delete_branch("main").unwrap();
+        branches.create_branch("main").unwrap();
+        assert!(kv.get_at(&branch_id, "default", "k", first_ts).is_ok());
+    }
+}
@@ -91,7 +91,7 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
         branches_scanned += 1;
 
         // --- KV entries ---
-        for (key, vv) in db.storage().list_by_type(&branch_id, TypeTag::KV) {
+        for (key, vv) in db.storage().list_by_type(&branch_id, TypeTag::KV)? {
             let text = match &vv.value {
                 Value::String(s) => s.clone(),
                 Value::Null | Value::Bool(_) | Value::Bytes(_) => continue,
@@ -115,7 +115,7 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
         }
 
         // --- State entries ---
-        for (key, vv) in db.storage().list_by_type(&branch_id, TypeTag::State) {
+        for (key, vv) in db.storage().list_by_type(&branch_id, TypeTag::State)? {
             let text = match &vv.value {
                 Value::String(s) => s.clone(),
                 Value::Null | Value::Bool(_) | Value::Bytes(_) => continue,
@@ -136,7 +136,7 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
         }
 
         // --- Event entries ---
-        for (key, vv) in db.storage().list_by_type(&branch_id, TypeTag::Event) {
+        for (key, vv) in db.storage().list_by_type(&branch_id, TypeTag::Event)? {
             // Skip metadata keys (same as checkpoint logic)
             if key.user_key == b"__meta__" || key.user_key.starts_with(b"__tidx__") {
                 continue;
@@ -0,0 +1,67 @@
+//! Thread-local registry of closure transactions in progress
+//!
+//! `Database::transaction` registers its context here while the closure
+//! runs, so a `db.transaction` call made from inside that closure, on the
+//! same thread, database and branch, can join it as a savepoint instead of
+//! starting an independent transaction.
+
+use std::cell::RefCell;
+use strata_concurrency::TransactionContext;
+use strata_core::types::BranchId;
+
+struct ActiveEntry {
+    db: usize,
+    branch_id: BranchId,
+    txn: *mut TransactionContext,
+}
+
+thread_local! {
+    /// Contexts of the closure transactions running on this thread, innermost last
+    static ACTIVE_TXNS: RefCell<Vec<ActiveEntry>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Registration of a running closure transaction
+///
+/// Removed from the registry when dropped, including on panic.
+pub(crate) struct ActiveTransaction {
+    _not_send: std::marker::PhantomData<*mut TransactionContext>,
+}
+
+impl ActiveTransaction {
+    /// Register `txn` as the running transaction of `db` on `branch_id`
+    ///
+    /// The pointer must stay valid, and only be dereferenced through
+    /// [`ActiveTransaction::find`], until the returned guard is dropped.
+    pub(crate) fn register(
+        db: usize,
+        branch_id: BranchId,
+        txn: *mut TransactionContext,
+    ) -> ActiveTransaction {
+        ACTIVE_TXNS.with(|active| {
+            active.borrow_mut().push(ActiveEntry { db, branch_id, txn });
+        });
+        ActiveTransaction {
+            _not_send: std::marker::PhantomData,
+        }
+    }
+
+    /// Innermost running transaction of `db` on `branch_id`, if any
+    pub(crate) fn find(db: usize, branch_id: BranchId) -> Option<*mut TransactionContext> {
+        ACTIVE_TXNS.with(|active| {
+            active
+                .borrow()
+                .iter()
+                .rev()
+                .find(|entry| entry.db == db && entry.branch_id == branch_id)
+                .map(|entry| entry.txn)
+        })
+    }
+}
+
+impl Drop for ActiveTransaction {
+    fn drop(&mut self) {
+        ACTIVE_TXNS.with(|active| {
+            active.borrow_mut().pop();
+        });
+    }
+}
@@ -17,7 +17,7 @@
 //! - State cell CAS (compare-and-swap) support
 //! - JSON document operations via TransactionContext
 
-use crate::primitives::event::{EventLogMeta, HASH_VERSION_SHA256};
+use crate::primitives::event::{EventLogMeta, HASH_VERSION_CANONICAL};
 use crate::transaction_ops::TransactionOps;
 use strata_concurrency::{JsonStoreExt, TransactionContext};
 use strata_core::types::{BranchId, Key, Namespace, TypeTag};
@@ -281,7 +281,7 @@ impl<'a> TransactionOps for Transaction<'a> {
         let meta = EventLogMeta {
             next_sequence: sequence + 1,
             head_hash: event.hash,
-            hash_version: HASH_VERSION_SHA256,
+            hash_version: HASH_VERSION_CANONICAL,
             streams: Default::default(),
         };
         let meta_json = serde_json::to_string(&meta).map_err(|e| StrataError::Serialization {
@@ -3,6 +3,7 @@
 //! This module provides:
 //! - Thread-local transaction pooling (zero allocations after warmup)
 //! - Pool management utilities
+//! - Registry of closure transactions running on each thread
 //! - Transaction wrapper implementing TransactionOps
 //!
 //! # Architecture
@@ -17,6 +18,7 @@
 //! The Transaction type wraps TransactionContext and implements the
 //! TransactionOps trait for unified primitive access within transactions.
 
+pub(crate) mod active;
 pub mod context;
 pub mod pool;
 
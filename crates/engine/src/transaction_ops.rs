@@ -49,7 +49,8 @@ use strata_core::{
 ///
 /// ## Not Supported in Transactions
 ///
-/// - **Vector**: requires in-memory index backends; use `VectorStore` directly
+/// - **Vector**: requires in-memory index backends; use `VectorStore` directly,
+///   or `VectorStore::stage_insert` / `stage_delete` to write within a transaction
 /// - **Branch**: metadata/status operations; use `BranchIndex` directly
 ///
 /// These return `Err(StrataError::InvalidInput)` with guidance on the
@@ -1,6 +1,6 @@
 //! Branch lifecycle operations.
 //!
-//! Branch operations: create, get, list, exists, delete.
+//! Branch operations: create, get, list, exists, delete, set status.
 
 use super::Strata;
 use crate::types::*;
@@ -53,7 +53,7 @@ impl Strata {
     /// List all branches.
     ///
     /// # Arguments
-    /// - `state`: Optional status filter (ignored in MVP).
+    /// - `state`: Optional status filter.
     /// - `limit`: Optional maximum number of branches to return.
     /// - `offset`: Optional offset (ignored in MVP).
     pub fn branch_list(
@@ -103,4 +103,23 @@ impl Strata {
             }),
         }
     }
+
+    /// Set a branch's status.
+    ///
+    /// `Protected` branches only accept writes that arrive via merge,
+    /// `Frozen` branches reject all writes, and `Active` lifts either.
+    ///
+    /// # Returns
+    /// Tuple of (BranchInfo, version).
+    pub fn branch_set_status(&self, name: &str, status: BranchStatus) -> Result<(BranchInfo, u64)> {
+        match self.executor.execute(Command::BranchSetStatus {
+            branch: BranchId::from(name),
+            status,
+        })? {
+            Output::BranchWithVersion { info, version } => Ok((info, version)),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchSetStatus".into(),
+            }),
+        }
+    }
 }
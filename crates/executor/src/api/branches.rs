@@ -27,11 +27,32 @@
 //! // Merge branches
 //! use strata_engine::MergeStrategy;
 //! db.branches().merge("experiment-2", "main", MergeStrategy::LastWriterWins)?;
+//!
+//! // Keep the last 10 versions of every key on a branch
+//! use strata_engine::RetentionPolicy;
+//! db.branches().set_retention("main", RetentionPolicy::keep_last(10))?;
+//! db.branches().apply_retention("main")?;
+//!
+//! // Cherry-pick the latest commit of a branch
+//! let latest = db.branches().log("experiment-2", Some(1), None)?;
+//! db.branches().cherry_pick(
+//!     "experiment-2",
+//!     "main",
+//!     vec![latest[0].version],
+//!     MergeStrategy::Strict,
+//!     Default::default(),
+//! )?;
 //! ```
 
-use crate::types::BranchId;
+use crate::types::{BranchId, BranchStatus};
 use crate::{Command, Error, Executor, Output, Result};
-use strata_engine::branch_ops::{BranchDiffResult, ForkInfo, MergeInfo, MergeStrategy};
+use strata_engine::branch_ops::{
+    BranchDiffResult, CherryPickInfo, CommitInfo, DiffOptions, ForkInfo, MergeInfo,
+    MergeResolution, MergeStrategy, PrimitiveMergeStrategies, RevertInfo,
+};
+use strata_engine::{
+    BranchRetention, RetentionApplyInfo, RetentionPolicy, RetentionPreview, RetentionStats,
+};
 
 /// Handle for branch management operations.
 ///
@@ -105,6 +126,91 @@ impl<'a> Branches<'a> {
         }
     }
 
+    /// Set a branch's status.
+    ///
+    /// Use `BranchStatus::Protected` to only allow merges into the branch,
+    /// `BranchStatus::Frozen` to reject all writes, and
+    /// `BranchStatus::Active` to lift either.
+    pub fn set_status(&self, name: &str, status: BranchStatus) -> Result<()> {
+        match self.executor.execute(Command::BranchSetStatus {
+            branch: BranchId::from(name),
+            status,
+        })? {
+            Output::BranchWithVersion { .. } => Ok(()),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchSetStatus".into(),
+            }),
+        }
+    }
+
+    /// Set how much version history a branch keeps.
+    ///
+    /// The policy is stored with the branch and takes effect when
+    /// [`apply_retention`](Self::apply_retention) runs.
+    pub fn set_retention(&self, name: &str, policy: RetentionPolicy) -> Result<BranchRetention> {
+        match self.executor.execute(Command::BranchSetRetention {
+            branch: BranchId::from(name),
+            policy,
+        })? {
+            Output::BranchRetention(info) => Ok(info),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchSetRetention".into(),
+            }),
+        }
+    }
+
+    /// Get a branch's retention policy and horizon.
+    pub fn retention(&self, name: &str) -> Result<BranchRetention> {
+        match self.executor.execute(Command::BranchGetRetention {
+            branch: BranchId::from(name),
+        })? {
+            Output::BranchRetention(info) => Ok(info),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchGetRetention".into(),
+            }),
+        }
+    }
+
+    /// Keys, versions and bytes a branch holds, per primitive.
+    pub fn retention_stats(&self, name: &str) -> Result<RetentionStats> {
+        match self.executor.execute(Command::RetentionStats {
+            branch: Some(BranchId::from(name)),
+        })? {
+            Output::RetentionStats(stats) => Ok(stats),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for RetentionStats".into(),
+            }),
+        }
+    }
+
+    /// List exactly which versions applying the branch's retention policy
+    /// would prune.
+    pub fn preview_retention(&self, name: &str) -> Result<RetentionPreview> {
+        match self.executor.execute(Command::RetentionPreview {
+            branch: Some(BranchId::from(name)),
+        })? {
+            Output::RetentionPreview(preview) => Ok(preview),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for RetentionPreview".into(),
+            }),
+        }
+    }
+
+    /// Prune the versions the branch's retention policy no longer keeps.
+    ///
+    /// Time-travel reads from before the resulting horizon fail with
+    /// `Error::HistoryTrimmed` afterwards.
+    pub fn apply_retention(&self, name: &str) -> Result<RetentionApplyInfo> {
+        match self.executor.execute(Command::RetentionApply {
+            branch: Some(BranchId::from(name)),
+        })? {
+            Output::RetentionResult(info) => Ok(info),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for RetentionApply".into(),
+            }),
+        }
+    }
+
     /// Fork a branch, creating a copy with all its data.
     pub fn fork(&self, source: &str, destination: &str) -> Result<ForkInfo> {
         match self.executor.execute(Command::BranchFork {
@@ -118,11 +224,78 @@ impl<'a> Branches<'a> {
         }
     }
 
+    /// Fork a branch as it was at a past timestamp (microseconds since epoch).
+    pub fn fork_at(&self, source: &str, destination: &str, as_of: u64) -> Result<ForkInfo> {
+        match self.executor.execute(Command::BranchForkAt {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            as_of,
+        })? {
+            Output::BranchForked(info) => Ok(info),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchForkAt".into(),
+            }),
+        }
+    }
+
+    /// Restore a branch's state as of a past timestamp (microseconds since
+    /// epoch).
+    ///
+    /// The old state is written back as new versions, so the reverted-away
+    /// history stays readable with time-travel reads.
+    pub fn revert(&self, branch: &str, as_of: u64) -> Result<RevertInfo> {
+        match self.executor.execute(Command::BranchRevert {
+            branch: branch.to_string(),
+            as_of,
+        })? {
+            Output::BranchReverted(info) => Ok(info),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchRevert".into(),
+            }),
+        }
+    }
+
     /// Compare two branches and return their differences.
     pub fn diff(&self, branch_a: &str, branch_b: &str) -> Result<BranchDiffResult> {
+        self.diff_with_options(branch_a, branch_b, DiffOptions::default())
+    }
+
+    /// Compare a branch with itself between two points in time.
+    ///
+    /// Timestamps are microseconds since epoch; `None` means the current
+    /// state. Use [`diff_with_options`](Self::diff_with_options) with
+    /// `as_of_a`/`as_of_b` to compare two different branches in the past.
+    pub fn diff_at(
+        &self,
+        branch: &str,
+        as_of_a: Option<u64>,
+        as_of_b: Option<u64>,
+    ) -> Result<BranchDiffResult> {
+        self.diff_with_options(
+            branch,
+            branch,
+            DiffOptions {
+                as_of_a,
+                as_of_b,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Compare two branches, filtered by primitive, space and key prefix.
+    ///
+    /// Set `options.limit` to page through large diffs: pass each result's
+    /// `next_cursor` back as `options.cursor` until it is `None`.
+    pub fn diff_with_options(
+        &self,
+        branch_a: &str,
+        branch_b: &str,
+        options: DiffOptions,
+    ) -> Result<BranchDiffResult> {
         match self.executor.execute(Command::BranchDiff {
             branch_a: branch_a.to_string(),
             branch_b: branch_b.to_string(),
+            options,
         })? {
             Output::BranchDiff(result) => Ok(result),
             _ => Err(Error::Internal {
@@ -133,10 +306,28 @@ impl<'a> Branches<'a> {
 
     /// Merge data from source branch into target branch.
     pub fn merge(&self, source: &str, target: &str, strategy: MergeStrategy) -> Result<MergeInfo> {
+        self.merge_with_strategies(
+            source,
+            target,
+            strategy,
+            PrimitiveMergeStrategies::default(),
+        )
+    }
+
+    /// Merge with per-primitive semantics (JSON path merge, event append,
+    /// counter-aware state cells).
+    pub fn merge_with_strategies(
+        &self,
+        source: &str,
+        target: &str,
+        strategy: MergeStrategy,
+        primitives: PrimitiveMergeStrategies,
+    ) -> Result<MergeInfo> {
         match self.executor.execute(Command::BranchMerge {
             source: source.to_string(),
             target: target.to_string(),
             strategy,
+            primitives,
         })? {
             Output::BranchMerged(info) => Ok(info),
             _ => Err(Error::Internal {
@@ -144,4 +335,80 @@ impl<'a> Branches<'a> {
             }),
         }
     }
+
+    /// Complete a merge by resolving every conflict reported by a
+    /// [`MergeStrategy::Manual`] merge.
+    ///
+    /// Build each resolution with [`MergeResolution::new`] from the
+    /// conflicts in the returned [`MergeInfo`]. Fails without writing
+    /// anything if a conflict is left unresolved or a resolution no longer
+    /// matches a conflict.
+    pub fn resolve_merge(
+        &self,
+        source: &str,
+        target: &str,
+        primitives: PrimitiveMergeStrategies,
+        resolutions: Vec<MergeResolution>,
+    ) -> Result<MergeInfo> {
+        match self.executor.execute(Command::BranchMergeResolve {
+            source: source.to_string(),
+            target: target.to_string(),
+            primitives,
+            resolutions,
+        })? {
+            Output::BranchMerged(info) => Ok(info),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchMergeResolve".into(),
+            }),
+        }
+    }
+
+    /// List the transactions committed on a branch, newest first.
+    ///
+    /// Pass the version of the last commit seen as `before` to page through
+    /// older commits. Commits are read from the write-ahead log, so this
+    /// fails for databases without one (cache databases).
+    pub fn log(
+        &self,
+        branch: &str,
+        limit: Option<u64>,
+        before: Option<u64>,
+    ) -> Result<Vec<CommitInfo>> {
+        match self.executor.execute(Command::BranchLog {
+            branch: branch.to_string(),
+            limit,
+            before,
+        })? {
+            Output::BranchLog(commits) => Ok(commits),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchLog".into(),
+            }),
+        }
+    }
+
+    /// Replay chosen commits from `source` onto `target`.
+    ///
+    /// `commits` are commit versions from [`log`](Self::log). Conflicts
+    /// follow the merge rules and are handled by `strategy`.
+    pub fn cherry_pick(
+        &self,
+        source: &str,
+        target: &str,
+        commits: Vec<u64>,
+        strategy: MergeStrategy,
+        primitives: PrimitiveMergeStrategies,
+    ) -> Result<CherryPickInfo> {
+        match self.executor.execute(Command::BranchCherryPick {
+            source: source.to_string(),
+            target: target.to_string(),
+            commits,
+            strategy,
+            primitives,
+        })? {
+            Output::BranchCherryPicked(info) => Ok(info),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchCherryPick".into(),
+            }),
+        }
+    }
 }
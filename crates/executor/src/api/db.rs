@@ -1,4 +1,4 @@
-//! Database operations: ping, info, flush, compact, configuration.
+//! Database operations: ping, info, flush, compact, backup, configuration.
 
 use super::Strata;
 use crate::output::EmbedStatusInfo;
@@ -8,7 +8,7 @@ use strata_engine::StrataConfig;
 
 impl Strata {
     // =========================================================================
-    // Database Operations (4)
+    // Database Operations (5)
     // =========================================================================
 
     /// Ping the database.
@@ -51,6 +51,20 @@ impl Strata {
         }
     }
 
+    /// Copy the database to `path` while writes continue.
+    ///
+    /// The backup can be opened directly or passed to [`Strata::restore`].
+    pub fn backup(&self, path: &str) -> Result<BackupResult> {
+        match self.executor.execute(Command::Backup {
+            path: path.to_string(),
+        })? {
+            Output::BackupCreated(result) => Ok(result),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for Backup".into(),
+            }),
+        }
+    }
+
     // =========================================================================
     // Bundle Operations (3)
     // =========================================================================
@@ -1,6 +1,8 @@
 //! Key-value store operations.
 
-use super::Strata;
+use std::time::Duration;
+
+use super::{ttl_millis, Strata};
 use crate::{Command, Error, Output, Result, Value};
 
 impl Strata {
@@ -34,6 +36,39 @@ impl Strata {
             space: self.space_id(),
             key: key.to_string(),
             value: value.into(),
+            ttl_ms: None,
+            expires_at: None,
+        })? {
+            Output::Version(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvPut".into(),
+            }),
+        }
+    }
+
+    /// Put a value that expires `ttl` after the write.
+    ///
+    /// Once expired, reads no longer see the key and it is deleted in the
+    /// background, leaving a tombstone in its history.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.kv_put_with_ttl("lock:job-7", "worker-2", Duration::from_secs(30))?;
+    /// ```
+    pub fn kv_put_with_ttl(
+        &self,
+        key: &str,
+        value: impl Into<Value>,
+        ttl: Duration,
+    ) -> Result<u64> {
+        match self.executor.execute(Command::KvPut {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            key: key.to_string(),
+            value: value.into(),
+            ttl_ms: Some(ttl_millis(ttl)),
+            expires_at: None,
         })? {
             Output::Version(v) => Ok(v),
             _ => Err(Error::Internal {
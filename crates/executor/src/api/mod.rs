@@ -48,18 +48,23 @@ mod vector;
 
 pub use branches::Branches;
 pub use strata_engine::branch_ops::{
-    BranchDiffEntry, BranchDiffResult, ConflictEntry, DiffSummary, ForkInfo, MergeInfo,
-    MergeStrategy, SpaceDiff,
+    BranchDiffEntry, BranchDiffResult, CherryPickInfo, CommitInfo, ConflictEntry,
+    ConflictResolution, DiffOptions, DiffSummary, EventMergeStrategy, ForkInfo, JsonMergeStrategy,
+    MergeInfo, MergeResolution, MergeStrategy, PrimitiveMergeStrategies, RevertInfo, SpaceDiff,
+    StateMergeStrategy, TouchedKeys,
 };
 
 use std::path::Path;
 use std::sync::Arc;
 
-use strata_engine::{Database, ModelConfig};
+use strata_engine::{
+    Database, FsckReport, ModelConfig, RekeyInfo, RestoreInfo, WalDumpFilter, WalDumpRecord,
+};
 use strata_security::{AccessMode, OpenOptions};
 
 use std::sync::Once;
 
+use crate::bridge::to_core_branch_id;
 use crate::types::BranchId;
 use crate::{Command, Error, Executor, Output, Result, Session};
 
@@ -73,6 +78,12 @@ fn ensure_vector_recovery() {
     });
 }
 
+/// A TTL as the `ttl_ms` of a write command, rounded up to whole milliseconds.
+fn ttl_millis(ttl: std::time::Duration) -> u64 {
+    let millis = ttl.as_millis() + u128::from(ttl.subsec_nanos() % 1_000_000 != 0);
+    u64::try_from(millis).unwrap_or(u64::MAX)
+}
+
 /// High-level typed wrapper for database operations.
 ///
 /// `Strata` provides a convenient Rust API that wraps the executor's
@@ -181,9 +192,23 @@ impl Strata {
             opts.embed_batch_size
                 .unwrap_or(cfg.embed_batch_size.unwrap_or(512)),
         );
-
-        let db = Database::open_with_config(&data_dir, cfg).map_err(|e| Error::Internal {
-            reason: format!("Failed to open database: {}", e),
+        // Keys given in the options are passed explicitly, so they win over
+        // the environment as well as strata.toml
+        let key = match (&opts.encryption_key, &opts.encryption_key_file) {
+            (Some(key), _) => Some(key.as_bytes().to_vec()),
+            (None, Some(key_file)) => Some(
+                strata_engine::database::read_key_file(&data_dir.join(key_file)).map_err(|e| {
+                    Error::Internal {
+                        reason: format!("Failed to open database: {}", e),
+                    }
+                })?,
+            ),
+            (None, None) => None,
+        };
+        let db = Database::open_with_key(&data_dir, cfg, key.as_deref()).map_err(|e| {
+            Error::Internal {
+                reason: format!("Failed to open database: {}", e),
+            }
         })?;
 
         let access_mode = opts.access_mode;
@@ -230,6 +255,197 @@ impl Strata {
         })
     }
 
+    /// Rewrite a closed database under a new encryption key (offline).
+    ///
+    /// The old key is read from `old_key_file`, or from `encryption_key_file`
+    /// in `strata.toml` when `None`. Passing `None` for `new_key_file`
+    /// decrypts the database. On success `strata.toml` is updated to point at
+    /// the new key file.
+    ///
+    /// Fails without touching the database while `STRATA_ENCRYPTION_KEY` or
+    /// `STRATA_ENCRYPTION_KEY_FILE` is set: the environment key takes
+    /// precedence over `strata.toml`, so the database would not reopen with
+    /// the new key.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let info = Strata::rekey("/data/myapp", None, Some(Path::new("/keys/new.key")))?;
+    /// println!("rewrote {} WAL segments", info.wal_segments);
+    /// ```
+    pub fn rekey<P: AsRef<Path>>(
+        path: P,
+        old_key_file: Option<&Path>,
+        new_key_file: Option<&Path>,
+    ) -> Result<RekeyInfo> {
+        if let Some(var) = strata_engine::database::env_key_var() {
+            return Err(Error::InvalidInput {
+                reason: format!(
+                    "{} is set and overrides strata.toml; unset it and pass the old key file",
+                    var
+                ),
+            });
+        }
+        let data_dir = path.as_ref();
+        let config_path = data_dir.join(strata_engine::database::config::CONFIG_FILE_NAME);
+        let mut cfg = if config_path.exists() {
+            strata_engine::database::config::StrataConfig::from_file(&config_path).map_err(|e| {
+                Error::Internal {
+                    reason: format!("Failed to read config: {}", e),
+                }
+            })?
+        } else {
+            strata_engine::database::config::StrataConfig::default()
+        };
+
+        let read_key = |path: &Path| {
+            std::fs::read(path).map_err(|e| Error::Internal {
+                reason: format!("Failed to read key file '{}': {}", path.display(), e),
+            })
+        };
+        let old_key = match old_key_file {
+            Some(path) => Some(read_key(path)?),
+            None => strata_engine::database::configured_key(data_dir, &cfg).map_err(|e| {
+                Error::Internal {
+                    reason: format!("Failed to resolve encryption key: {}", e),
+                }
+            })?,
+        };
+        let new_key = new_key_file.map(read_key).transpose()?;
+
+        let info =
+            Database::rekey(data_dir, old_key.as_deref(), new_key.as_deref()).map_err(|e| {
+                Error::Internal {
+                    reason: format!("Failed to rekey database: {}", e),
+                }
+            })?;
+
+        // Relative key paths in strata.toml resolve against the data dir, so
+        // record an absolute path.
+        cfg.encryption_key_file = new_key_file.map(|path| {
+            path.canonicalize()
+                .unwrap_or_else(|_| path.to_path_buf())
+                .to_string_lossy()
+                .into_owned()
+        });
+        if config_path.exists() || cfg.encryption_key_file.is_some() {
+            cfg.write_to_file(&config_path)
+                .map_err(|e| Error::Internal {
+                    reason: format!("Failed to update config: {}", e),
+                })?;
+        }
+
+        Ok(info)
+    }
+
+    /// Restore a backup made with [`Strata::backup`] into `path` (offline).
+    ///
+    /// With `to` (microseconds since epoch) the database is restored to its
+    /// state at that moment; commits after it are left out. With
+    /// `wal_archive`, WAL segments archived after the backup are replayed
+    /// as well. `path` must not exist or be empty. The backup's key is read
+    /// from `key_file`, or from the configured key sources (environment,
+    /// then the backup's `strata.toml`) when `None`.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let info = Strata::restore("/data/restored", "/backups/monday", Some(ts), None, None)?;
+    /// let db = Strata::open("/data/restored")?;
+    /// ```
+    pub fn restore<P: AsRef<Path>, Q: AsRef<Path>>(
+        path: P,
+        backup: Q,
+        to: Option<u64>,
+        wal_archive: Option<&Path>,
+        key_file: Option<&Path>,
+    ) -> Result<RestoreInfo> {
+        let key = key_file
+            .map(|path| {
+                std::fs::read(path).map_err(|e| Error::Internal {
+                    reason: format!("Failed to read key file '{}': {}", path.display(), e),
+                })
+            })
+            .transpose()?;
+
+        Database::restore(backup, path, to, wal_archive, key.as_deref()).map_err(|e| {
+            Error::Internal {
+                reason: format!("Failed to restore database: {}", e),
+            }
+        })
+    }
+
+    /// Check the integrity of a closed database (offline).
+    ///
+    /// Validates every WAL segment and snapshot, the MANIFEST watermark,
+    /// each event log's hash chain and the vector and search caches. With
+    /// `repair`, torn WAL tails are truncated and orphaned or broken cache
+    /// files are removed so they are rebuilt on the next open. The key is
+    /// read from `key_file`, or from the configured key sources when `None`.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let report = Strata::fsck("/data/strata", false, None)?;
+    /// for finding in &report.findings {
+    ///     println!("{}: {}", finding.component.as_str(), finding.message);
+    /// }
+    /// ```
+    pub fn fsck<P: AsRef<Path>>(
+        path: P,
+        repair: bool,
+        key_file: Option<&Path>,
+    ) -> Result<FsckReport> {
+        let key = key_file
+            .map(|path| {
+                std::fs::read(path).map_err(|e| Error::Internal {
+                    reason: format!("Failed to read key file '{}': {}", path.display(), e),
+                })
+            })
+            .transpose()?;
+
+        Database::fsck(path, repair, key.as_deref()).map_err(|e| Error::Internal {
+            reason: format!("Failed to check database: {}", e),
+        })
+    }
+
+    /// Decode the WAL of a database without opening it (offline).
+    ///
+    /// Returns each WAL record matching `filter` with its decoded writes,
+    /// without running recovery. `branch` restricts the dump to one branch
+    /// by name, replacing `filter.branch_id`. The key is read from
+    /// `key_file`, or from the configured key sources when `None`.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let records = Strata::wal_dump("/data/strata", Some("main"), WalDumpFilter::default(), None)?;
+    /// for record in &records {
+    ///     println!("txn {}: {} writes", record.txn_id, record.writes.len());
+    /// }
+    /// ```
+    pub fn wal_dump<P: AsRef<Path>>(
+        path: P,
+        branch: Option<&str>,
+        mut filter: WalDumpFilter,
+        key_file: Option<&Path>,
+    ) -> Result<Vec<WalDumpRecord>> {
+        if let Some(branch) = branch {
+            filter.branch_id = Some(to_core_branch_id(&BranchId::from(branch))?);
+        }
+        let key = key_file
+            .map(|path| {
+                std::fs::read(path).map_err(|e| Error::Internal {
+                    reason: format!("Failed to read key file '{}': {}", path.display(), e),
+                })
+            })
+            .transpose()?;
+
+        Database::wal_dump(path, &filter, key.as_deref()).map_err(|e| Error::Internal {
+            reason: format!("Failed to dump WAL: {}", e),
+        })
+    }
+
     /// Create a new independent handle to the same database.
     ///
     /// Each handle has its own branch context (starting on "default") and can
@@ -623,6 +839,59 @@ mod tests {
         assert!(!info.version.is_empty());
     }
 
+    #[test]
+    fn test_rekey_then_reopen() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("db");
+        let key_file = temp.path().join("new.key");
+        std::fs::write(&key_file, "ab".repeat(32)).unwrap();
+
+        {
+            let db = Strata::open(&path).unwrap();
+            db.kv_put("k", "v").unwrap();
+        }
+
+        Strata::rekey(&path, None, Some(&key_file)).unwrap();
+
+        // strata.toml now names the new key, so a plain open finds it
+        let db = Strata::open(&path).unwrap();
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::String("v".into())));
+        drop(db);
+
+        // Decrypting reads the old key back from strata.toml
+        Strata::rekey(&path, None, None).unwrap();
+        let db = Strata::open(&path).unwrap();
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::String("v".into())));
+    }
+
+    #[test]
+    fn test_open_with_key_file_option() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("db");
+        let key_file = temp.path().join("db.key");
+        let wrong_key_file = temp.path().join("wrong.key");
+        std::fs::write(&key_file, "ab".repeat(32)).unwrap();
+        std::fs::write(&wrong_key_file, "cd".repeat(32)).unwrap();
+        let with_key_file = |file: &Path| {
+            Strata::open_with(
+                &path,
+                OpenOptions::new().encryption_key_file(file.to_str().unwrap()),
+            )
+        };
+
+        {
+            let db = with_key_file(&key_file).unwrap();
+            db.kv_put("k", "v").unwrap();
+        }
+
+        // The option key is used in place of strata.toml's (none here)
+        assert!(Strata::open(&path).is_err());
+        assert!(with_key_file(&wrong_key_file).is_err());
+        assert!(with_key_file(&temp.path().join("missing.key")).is_err());
+        let db = with_key_file(&key_file).unwrap();
+        assert_eq!(db.kv_get("k").unwrap(), Some(Value::String("v".into())));
+    }
+
     #[test]
     fn test_kv_put_get() {
         let db = create_strata();
@@ -947,7 +1216,7 @@ mod tests {
         let info = db.fork_branch("forked").unwrap();
         assert_eq!(info.source, "default");
         assert_eq!(info.destination, "forked");
-        assert!(info.keys_copied >= 2);
+        assert_eq!(info.keys_copied, 0);
     }
 
     #[test]
@@ -1110,9 +1379,17 @@ mod tests {
         let db = create_strata();
         db.graph_create("ng").unwrap();
 
-        db.graph_add_node("ng", "n1", None, Some(Value::Object(
-            [("name".to_string(), Value::String("Alice".into()))].into_iter().collect()
-        ))).unwrap();
+        db.graph_add_node(
+            "ng",
+            "n1",
+            None,
+            Some(Value::Object(
+                [("name".to_string(), Value::String("Alice".into()))]
+                    .into_iter()
+                    .collect(),
+            )),
+        )
+        .unwrap();
 
         let node = db.graph_get_node("ng", "n1").unwrap();
         assert!(node.is_some());
@@ -1129,7 +1406,8 @@ mod tests {
         let db = create_strata();
         db.graph_create("eg").unwrap();
 
-        db.graph_add_edge("eg", "A", "B", "KNOWS", None, None).unwrap();
+        db.graph_add_edge("eg", "A", "B", "KNOWS", None, None)
+            .unwrap();
 
         let neighbors = db.graph_neighbors("eg", "A", "outgoing", None).unwrap();
         assert_eq!(neighbors.len(), 1);
@@ -1192,9 +1470,12 @@ mod tests {
             "patient-4821",
             Some("json://main/patient-4821"),
             Some(Value::Object(
-                [("department".to_string(), Value::String("endocrinology".into()))]
-                    .into_iter()
-                    .collect(),
+                [(
+                    "department".to_string(),
+                    Value::String("endocrinology".into()),
+                )]
+                .into_iter()
+                .collect(),
             )),
         )
         .unwrap();
@@ -1203,9 +1484,12 @@ mod tests {
             "ICD:E11.9",
             None,
             Some(Value::Object(
-                [("description".to_string(), Value::String("Type 2 Diabetes".into()))]
-                    .into_iter()
-                    .collect(),
+                [(
+                    "description".to_string(),
+                    Value::String("Type 2 Diabetes".into()),
+                )]
+                .into_iter()
+                .collect(),
             )),
         )
         .unwrap();
@@ -1223,29 +1507,62 @@ mod tests {
             "ICD:N18.3",
             None,
             Some(Value::Object(
-                [("description".to_string(), Value::String("CKD Stage 3".into()))]
-                    .into_iter()
-                    .collect(),
+                [(
+                    "description".to_string(),
+                    Value::String("CKD Stage 3".into()),
+                )]
+                .into_iter()
+                .collect(),
             )),
         )
         .unwrap();
 
         // Add edges
         db.graph_add_edge(
-            "patient_care", "patient-4821", "ICD:E11.9", "DIAGNOSED_WITH", None, None,
-        ).unwrap();
+            "patient_care",
+            "patient-4821",
+            "ICD:E11.9",
+            "DIAGNOSED_WITH",
+            None,
+            None,
+        )
+        .unwrap();
         db.graph_add_edge(
-            "patient_care", "patient-4821", "lab:HbA1c", "HAS_LAB_RESULT", None, None,
-        ).unwrap();
+            "patient_care",
+            "patient-4821",
+            "lab:HbA1c",
+            "HAS_LAB_RESULT",
+            None,
+            None,
+        )
+        .unwrap();
         db.graph_add_edge(
-            "patient_care", "lab:HbA1c", "ICD:E11.9", "SUPPORTS", Some(0.95), None,
-        ).unwrap();
+            "patient_care",
+            "lab:HbA1c",
+            "ICD:E11.9",
+            "SUPPORTS",
+            Some(0.95),
+            None,
+        )
+        .unwrap();
         db.graph_add_edge(
-            "patient_care", "ICD:E11.9", "med:metformin", "TREATED_BY", None, None,
-        ).unwrap();
+            "patient_care",
+            "ICD:E11.9",
+            "med:metformin",
+            "TREATED_BY",
+            None,
+            None,
+        )
+        .unwrap();
         db.graph_add_edge(
-            "patient_care", "med:metformin", "ICD:N18.3", "CONTRAINDICATES", Some(0.8), None,
-        ).unwrap();
+            "patient_care",
+            "med:metformin",
+            "ICD:N18.3",
+            "CONTRAINDICATES",
+            Some(0.8),
+            None,
+        )
+        .unwrap();
 
         // Query: what supports the diabetes diagnosis?
         let supporters = db
@@ -1321,7 +1638,8 @@ mod tests {
     #[test]
     fn test_graph_create_with_cascade_policy() {
         let db = create_strata();
-        db.graph_create_with_policy("cascade_g", Some("cascade")).unwrap();
+        db.graph_create_with_policy("cascade_g", Some("cascade"))
+            .unwrap();
 
         let meta = db.graph_get_meta("cascade_g").unwrap();
         assert!(meta.is_some());
@@ -1,6 +1,8 @@
 //! State cell operations.
 
-use super::Strata;
+use std::time::Duration;
+
+use super::{ttl_millis, Strata};
 use crate::{Command, Error, Output, Result, Value};
 
 impl Strata {
@@ -15,6 +17,30 @@ impl Strata {
             space: self.space_id(),
             cell: cell.to_string(),
             value: value.into(),
+            ttl_ms: None,
+            expires_at: None,
+        })? {
+            Output::Version(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for StateSet".into(),
+            }),
+        }
+    }
+
+    /// Set a state cell value that expires `ttl` after the write.
+    pub fn state_set_with_ttl(
+        &self,
+        cell: &str,
+        value: impl Into<Value>,
+        ttl: Duration,
+    ) -> Result<u64> {
+        match self.executor.execute(Command::StateSet {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            cell: cell.to_string(),
+            value: value.into(),
+            ttl_ms: Some(ttl_millis(ttl)),
+            expires_at: None,
         })? {
             Output::Version(v) => Ok(v),
             _ => Err(Error::Internal {
@@ -85,6 +111,33 @@ impl Strata {
             space: self.space_id(),
             cell: cell.to_string(),
             value: value.into(),
+            ttl_ms: None,
+            expires_at: None,
+        })? {
+            Output::Version(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for StateInit".into(),
+            }),
+        }
+    }
+
+    /// Initialize a state cell that expires `ttl` after it is created.
+    ///
+    /// While the cell exists, further inits leave it alone; once it has
+    /// expired it can be initialized again, so the cell works as a lease.
+    pub fn state_init_with_ttl(
+        &self,
+        cell: &str,
+        value: impl Into<Value>,
+        ttl: Duration,
+    ) -> Result<u64> {
+        match self.executor.execute(Command::StateInit {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            cell: cell.to_string(),
+            value: value.into(),
+            ttl_ms: Some(ttl_millis(ttl)),
+            expires_at: None,
         })? {
             Output::Version(v) => Ok(v),
             _ => Err(Error::Internal {
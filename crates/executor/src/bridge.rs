@@ -9,13 +9,14 @@
 //! - Type conversion helpers: Value ↔ JsonValue, DistanceMetric, etc.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use strata_core::limits::Limits;
 use strata_core::primitives::json::{JsonPath, JsonValue};
-use strata_core::{StrataError, StrataResult, Value};
+use strata_core::{StrataError, StrataResult, Timestamp, Value};
 use strata_engine::{
-    BranchIndex as PrimitiveBranchIndex, Database, EventLog as PrimitiveEventLog,
-    GraphStore, JsonStore as PrimitiveJsonStore, KVStore as PrimitiveKVStore,
+    BranchIndex as PrimitiveBranchIndex, Database, EventLog as PrimitiveEventLog, GraphStore,
+    JsonStore as PrimitiveJsonStore, KVStore as PrimitiveKVStore,
     SpaceIndex as PrimitiveSpaceIndex, StateCell as PrimitiveStateCell,
     VectorStore as PrimitiveVectorStore,
 };
@@ -144,6 +145,32 @@ pub fn validate_value(value: &Value, limits: &Limits) -> StrataResult<()> {
     limits.validate_value(value).map_err(limit_error_to_strata)
 }
 
+/// Resolve the `ttl_ms` / `expires_at` fields of a write into a TTL.
+///
+/// `expires_at` is in microseconds since epoch. At most one may be set; a
+/// zero TTL or an expiry that has already passed is rejected rather than
+/// writing a value nobody can read.
+pub fn resolve_ttl(ttl_ms: Option<u64>, expires_at: Option<u64>) -> StrataResult<Option<Duration>> {
+    match (ttl_ms, expires_at) {
+        (None, None) => Ok(None),
+        (Some(_), Some(_)) => Err(StrataError::invalid_input(
+            "Only one of ttl_ms and expires_at may be set",
+        )),
+        (Some(0), None) => Err(StrataError::invalid_input("ttl_ms must be positive")),
+        (Some(ms), None) => Ok(Some(Duration::from_millis(ms))),
+        (None, Some(at)) => {
+            let now = Timestamp::now().as_micros();
+            if at <= now {
+                return Err(StrataError::invalid_input(format!(
+                    "expires_at {} is not in the future",
+                    at
+                )));
+            }
+            Ok(Some(Duration::from_micros(at - now)))
+        }
+    }
+}
+
 /// Validate a vector against dimension limits.
 pub fn validate_vector(vec: &[f32], limits: &Limits) -> StrataResult<()> {
     limits.validate_vector(vec).map_err(limit_error_to_strata)
@@ -335,6 +362,18 @@ pub fn from_engine_metric(metric: strata_engine::DistanceMetric) -> crate::types
     }
 }
 
+// =============================================================================
+// Isolation Level Conversion
+// =============================================================================
+
+/// Convert executor TxnIsolation to engine IsolationLevel.
+pub fn to_engine_isolation(isolation: crate::types::TxnIsolation) -> strata_engine::IsolationLevel {
+    match isolation {
+        crate::types::TxnIsolation::Snapshot => strata_engine::IsolationLevel::Snapshot,
+        crate::types::TxnIsolation::Serializable => strata_engine::IsolationLevel::Serializable,
+    }
+}
+
 // =============================================================================
 // SearchFilter Conversion
 // =============================================================================
@@ -406,6 +445,17 @@ pub fn from_engine_branch_status(
 ) -> crate::types::BranchStatus {
     match status {
         strata_engine::BranchStatus::Active => crate::types::BranchStatus::Active,
+        strata_engine::BranchStatus::Protected => crate::types::BranchStatus::Protected,
+        strata_engine::BranchStatus::Frozen => crate::types::BranchStatus::Frozen,
+    }
+}
+
+/// Convert executor BranchStatus to engine BranchStatus.
+pub fn to_engine_branch_status(status: crate::types::BranchStatus) -> strata_engine::BranchStatus {
+    match status {
+        crate::types::BranchStatus::Active => strata_engine::BranchStatus::Active,
+        crate::types::BranchStatus::Protected => strata_engine::BranchStatus::Protected,
+        crate::types::BranchStatus::Frozen => strata_engine::BranchStatus::Frozen,
     }
 }
 
@@ -485,6 +535,22 @@ mod tests {
         assert!(validate_key(&long_key).is_err());
     }
 
+    #[test]
+    fn test_resolve_ttl() {
+        assert_eq!(resolve_ttl(None, None).unwrap(), None);
+        assert_eq!(
+            resolve_ttl(Some(1500), None).unwrap(),
+            Some(Duration::from_millis(1500))
+        );
+        let in_a_minute = Timestamp::now().as_micros() + 60_000_000;
+        let ttl = resolve_ttl(None, Some(in_a_minute)).unwrap().unwrap();
+        assert!(ttl > Duration::from_secs(59) && ttl <= Duration::from_secs(60));
+
+        assert!(resolve_ttl(Some(0), None).is_err());
+        assert!(resolve_ttl(Some(10), Some(in_a_minute)).is_err());
+        assert!(resolve_ttl(None, Some(1)).is_err());
+    }
+
     #[test]
     fn test_value_json_roundtrip() {
         let value = Value::Int(42);
@@ -11,7 +11,9 @@
 
 use serde::{Deserialize, Serialize};
 use strata_core::Value;
-use strata_engine::MergeStrategy;
+use strata_engine::{
+    DiffOptions, MergeResolution, MergeStrategy, PrimitiveMergeStrategies, RetentionPolicy,
+};
 
 use crate::types::*;
 
@@ -80,6 +82,13 @@ pub enum Command {
         key: String,
         /// Value to store.
         value: Value,
+        /// Expire the key this many milliseconds after the write.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ttl_ms: Option<u64>,
+        /// Expire the key at this time (microseconds since epoch).
+        /// Exclusive with `ttl_ms`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expires_at: Option<u64>,
     },
 
     /// Get a value by key.
@@ -369,6 +378,13 @@ pub enum Command {
         cell: String,
         /// Value to store.
         value: Value,
+        /// Expire the cell this many milliseconds after the write.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ttl_ms: Option<u64>,
+        /// Expire the cell at this time (microseconds since epoch).
+        /// Exclusive with `ttl_ms`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expires_at: Option<u64>,
     },
 
     /// Read a state cell value.
@@ -433,6 +449,13 @@ pub enum Command {
         cell: String,
         /// Initial value.
         value: Value,
+        /// Expire the cell this many milliseconds after the write.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ttl_ms: Option<u64>,
+        /// Expire the cell at this time (microseconds since epoch).
+        /// Exclusive with `ttl_ms`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expires_at: Option<u64>,
     },
 
     /// Delete a state cell.
@@ -653,6 +676,37 @@ pub enum Command {
         branch: BranchId,
     },
 
+    /// Set a branch's status to protect, freeze or reactivate it.
+    ///
+    /// Protected branches only accept writes that arrive via merge; frozen
+    /// branches reject all writes. The status survives restart.
+    /// Returns: `Output::BranchWithVersion`
+    BranchSetStatus {
+        /// Branch to update.
+        branch: BranchId,
+        /// New status.
+        status: BranchStatus,
+    },
+
+    /// Set how much version history a branch keeps.
+    ///
+    /// The policy is stored with the branch and enforced by
+    /// `RetentionApply`; `keep_all` removes it.
+    /// Returns: `Output::BranchRetention`
+    BranchSetRetention {
+        /// Branch to update.
+        branch: BranchId,
+        /// Retention policy to store.
+        policy: RetentionPolicy,
+    },
+
+    /// Get a branch's retention policy and horizon.
+    /// Returns: `Output::BranchRetention`
+    BranchGetRetention {
+        /// Branch to query.
+        branch: BranchId,
+    },
+
     /// Fork a branch, creating a complete copy of all its data.
     /// Returns: `Output::BranchForked`
     BranchFork {
@@ -662,6 +716,27 @@ pub enum Command {
         destination: String,
     },
 
+    /// Fork a branch as it was at a past timestamp.
+    /// Returns: `Output::BranchForked`
+    BranchForkAt {
+        /// Source branch name.
+        source: String,
+        /// Destination branch name.
+        destination: String,
+        /// Timestamp to fork from (microseconds since epoch).
+        as_of: u64,
+    },
+
+    /// Restore a branch's state as of a past timestamp, written as new
+    /// versions across all primitives in one transaction.
+    /// Returns: `Output::BranchReverted`
+    BranchRevert {
+        /// Branch to revert.
+        branch: String,
+        /// Timestamp to revert to (microseconds since epoch).
+        as_of: u64,
+    },
+
     /// Compare two branches and return structured differences.
     /// Returns: `Output::BranchDiff`
     BranchDiff {
@@ -669,6 +744,9 @@ pub enum Command {
         branch_a: String,
         /// Second branch to compare.
         branch_b: String,
+        /// Filters, pagination and summary-only mode (defaults to the full diff).
+        #[serde(default)]
+        options: DiffOptions,
     },
 
     /// Merge data from source branch into target branch.
@@ -680,9 +758,58 @@ pub enum Command {
         target: String,
         /// Conflict resolution strategy.
         strategy: MergeStrategy,
+        /// Per-primitive merge semantics (defaults compare whole values).
+        #[serde(default)]
+        primitives: PrimitiveMergeStrategies,
     },
 
-    // ==================== Transaction (5) ====================
+    /// Complete a merge by applying a resolution for every conflict reported
+    /// by a `Manual` merge. Non-conflicting changes are applied alongside.
+    /// Returns: `Output::BranchMerged`
+    BranchMergeResolve {
+        /// Source branch name.
+        source: String,
+        /// Target branch name.
+        target: String,
+        /// Per-primitive merge semantics used to compute the conflicts.
+        #[serde(default)]
+        primitives: PrimitiveMergeStrategies,
+        /// One resolution per conflicting key.
+        resolutions: Vec<MergeResolution>,
+    },
+
+    /// List the transactions committed on a branch, newest first, with their
+    /// messages, metadata and touched keys per primitive.
+    /// Returns: `Output::BranchLog`
+    BranchLog {
+        /// Branch name.
+        branch: String,
+        /// Maximum number of commits to return.
+        #[serde(default)]
+        limit: Option<u64>,
+        /// Only return commits with a version below this one (for paging).
+        #[serde(default)]
+        before: Option<u64>,
+    },
+
+    /// Replay chosen commits from one branch onto another, following the
+    /// merge conflict rules.
+    /// Returns: `Output::BranchCherryPicked`
+    BranchCherryPick {
+        /// Source branch name.
+        source: String,
+        /// Target branch name.
+        target: String,
+        /// Commit versions to replay (from `BranchLog`).
+        commits: Vec<u64>,
+        /// Conflict resolution strategy.
+        strategy: MergeStrategy,
+        /// Per-primitive merge semantics (defaults compare whole values).
+        #[serde(default)]
+        primitives: PrimitiveMergeStrategies,
+    },
+
+    // ==================== Transaction (8) ====================
     /// Begin a new transaction.
     /// Returns: `Output::TxnBegun`
     TxnBegin {
@@ -701,6 +828,27 @@ pub enum Command {
     /// Returns: `Output::Unit`
     TxnRollback,
 
+    /// Mark a savepoint in the current transaction.
+    /// Returns: `Output::Unit`
+    TxnSavepoint {
+        /// Savepoint name; reusing a name shadows the earlier savepoint.
+        name: String,
+    },
+
+    /// Undo everything done since a savepoint, keeping the savepoint.
+    /// Returns: `Output::Unit`
+    TxnRollbackTo {
+        /// Savepoint name.
+        name: String,
+    },
+
+    /// Release a savepoint (and any created after it), keeping its writes.
+    /// Returns: `Output::Unit`
+    TxnRelease {
+        /// Savepoint name.
+        name: String,
+    },
+
     /// Get current transaction info.
     /// Returns: `Output::MaybeTxnInfo`
     TxnInfo,
@@ -710,9 +858,8 @@ pub enum Command {
     TxnIsActive,
 
     // ==================== Retention (3) ====================
-    // Note: Branch-level retention is handled via BranchSetRetention/BranchGetRetention
-    // These are database-wide retention operations
-    /// Apply retention policy (trigger garbage collection).
+    // Note: Policies are set via BranchSetRetention/BranchGetRetention
+    /// Prune the versions the branch's retention policy no longer keeps.
     /// Returns: `Output::RetentionResult`
     RetentionApply {
         /// Target branch (defaults to "default").
@@ -720,7 +867,7 @@ pub enum Command {
         branch: Option<BranchId>,
     },
 
-    /// Get retention statistics.
+    /// Get the keys, versions and bytes a branch holds, per primitive.
     /// Returns: `Output::RetentionStats`
     RetentionStats {
         /// Target branch (defaults to "default").
@@ -728,7 +875,7 @@ pub enum Command {
         branch: Option<BranchId>,
     },
 
-    /// Preview what would be deleted by retention policy.
+    /// List exactly which versions `RetentionApply` would prune.
     /// Returns: `Output::RetentionPreview`
     RetentionPreview {
         /// Target branch (defaults to "default").
@@ -736,7 +883,7 @@ pub enum Command {
         branch: Option<BranchId>,
     },
 
-    // ==================== Database (4) ====================
+    // ==================== Database (5) ====================
     /// Ping the database to check connectivity
     Ping,
 
@@ -749,6 +896,13 @@ pub enum Command {
     /// Trigger compaction
     Compact,
 
+    /// Copy the database to a new directory while writes continue.
+    /// Returns: `Output::BackupCreated`
+    Backup {
+        /// Destination directory (must not exist or be empty).
+        path: String,
+    },
+
     /// Get the available time range for a branch.
     /// Returns: `Output::TimeRange`
     TimeRange {
@@ -1164,14 +1318,23 @@ impl Command {
                 | Command::VectorBatchUpsert { .. }
                 | Command::BranchCreate { .. }
                 | Command::BranchDelete { .. }
+                | Command::BranchSetStatus { .. }
+                | Command::BranchSetRetention { .. }
                 | Command::BranchFork { .. }
+                | Command::BranchForkAt { .. }
+                | Command::BranchRevert { .. }
                 | Command::BranchMerge { .. }
+                | Command::BranchMergeResolve { .. }
+                | Command::BranchCherryPick { .. }
                 | Command::ConfigSetAutoEmbed { .. }
                 | Command::SpaceCreate { .. }
                 | Command::SpaceDelete { .. }
                 | Command::TxnBegin { .. }
                 | Command::TxnCommit
                 | Command::TxnRollback
+                | Command::TxnSavepoint { .. }
+                | Command::TxnRollbackTo { .. }
+                | Command::TxnRelease { .. }
                 | Command::RetentionApply { .. }
                 | Command::Flush
                 | Command::Compact
@@ -1188,6 +1351,64 @@ impl Command {
         )
     }
 
+    /// Returns the branch whose data this command writes, if any.
+    ///
+    /// Used with [`is_merge`](Self::is_merge) to enforce per-branch write
+    /// protection. Optional branches must already be filled in by
+    /// [`resolve_defaults`](Self::resolve_defaults). Retention commands count
+    /// as writes since they rewrite the branch's history. Commands that
+    /// create a new branch (fork, import) or only change other branch
+    /// metadata return `None`.
+    pub fn write_branch(&self) -> Option<&str> {
+        match self {
+            Command::KvPut { branch, .. }
+            | Command::KvBatchPut { branch, .. }
+            | Command::KvDelete { branch, .. }
+            | Command::JsonSet { branch, .. }
+            | Command::JsonBatchSet { branch, .. }
+            | Command::JsonDelete { branch, .. }
+            | Command::EventAppend { branch, .. }
+            | Command::EventBatchAppend { branch, .. }
+            | Command::StateSet { branch, .. }
+            | Command::StateBatchSet { branch, .. }
+            | Command::StateCas { branch, .. }
+            | Command::StateInit { branch, .. }
+            | Command::StateDelete { branch, .. }
+            | Command::VectorUpsert { branch, .. }
+            | Command::VectorDelete { branch, .. }
+            | Command::VectorCreateCollection { branch, .. }
+            | Command::VectorDeleteCollection { branch, .. }
+            | Command::VectorBatchUpsert { branch, .. }
+            | Command::SpaceCreate { branch, .. }
+            | Command::SpaceDelete { branch, .. }
+            | Command::GraphCreate { branch, .. }
+            | Command::GraphDelete { branch, .. }
+            | Command::GraphAddNode { branch, .. }
+            | Command::GraphRemoveNode { branch, .. }
+            | Command::GraphAddEdge { branch, .. }
+            | Command::GraphRemoveEdge { branch, .. }
+            | Command::RetentionApply { branch } => branch.as_ref().map(|b| b.as_str()),
+            Command::BranchDelete { branch } | Command::BranchSetRetention { branch, .. } => {
+                Some(branch.as_str())
+            }
+            Command::BranchRevert { branch, .. } => Some(branch),
+            Command::BranchMerge { target, .. }
+            | Command::BranchMergeResolve { target, .. }
+            | Command::BranchCherryPick { target, .. } => Some(target),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this command writes to its target branch by merging.
+    ///
+    /// Protected branches accept these writes and reject all others.
+    pub fn is_merge(&self) -> bool {
+        matches!(
+            self,
+            Command::BranchMerge { .. } | Command::BranchMergeResolve { .. }
+        )
+    }
+
     /// Returns the variant name as a static string.
     ///
     /// The exhaustive match ensures the compiler flags any new `Command`
@@ -1233,12 +1454,23 @@ impl Command {
             Command::BranchList { .. } => "BranchList",
             Command::BranchExists { .. } => "BranchExists",
             Command::BranchDelete { .. } => "BranchDelete",
+            Command::BranchSetStatus { .. } => "BranchSetStatus",
+            Command::BranchSetRetention { .. } => "BranchSetRetention",
+            Command::BranchGetRetention { .. } => "BranchGetRetention",
             Command::BranchFork { .. } => "BranchFork",
+            Command::BranchForkAt { .. } => "BranchForkAt",
+            Command::BranchRevert { .. } => "BranchRevert",
             Command::BranchDiff { .. } => "BranchDiff",
             Command::BranchMerge { .. } => "BranchMerge",
+            Command::BranchMergeResolve { .. } => "BranchMergeResolve",
+            Command::BranchLog { .. } => "BranchLog",
+            Command::BranchCherryPick { .. } => "BranchCherryPick",
             Command::TxnBegin { .. } => "TxnBegin",
             Command::TxnCommit => "TxnCommit",
             Command::TxnRollback => "TxnRollback",
+            Command::TxnSavepoint { .. } => "TxnSavepoint",
+            Command::TxnRollbackTo { .. } => "TxnRollbackTo",
+            Command::TxnRelease { .. } => "TxnRelease",
             Command::TxnInfo => "TxnInfo",
             Command::TxnIsActive => "TxnIsActive",
             Command::RetentionApply { .. } => "RetentionApply",
@@ -1248,6 +1480,7 @@ impl Command {
             Command::Info => "Info",
             Command::Flush => "Flush",
             Command::Compact => "Compact",
+            Command::Backup { .. } => "Backup",
             Command::TimeRange { .. } => "TimeRange",
             Command::BranchExport { .. } => "BranchExport",
             Command::BranchImport { .. } => "BranchImport",
@@ -1393,17 +1626,29 @@ impl Command {
             | Command::BranchList { .. }
             | Command::BranchExists { .. }
             | Command::BranchDelete { .. }
+            | Command::BranchSetStatus { .. }
+            | Command::BranchSetRetention { .. }
+            | Command::BranchGetRetention { .. }
             | Command::BranchFork { .. }
+            | Command::BranchForkAt { .. }
+            | Command::BranchRevert { .. }
             | Command::BranchDiff { .. }
             | Command::BranchMerge { .. }
+            | Command::BranchMergeResolve { .. }
+            | Command::BranchLog { .. }
+            | Command::BranchCherryPick { .. }
             | Command::TxnCommit
             | Command::TxnRollback
+            | Command::TxnSavepoint { .. }
+            | Command::TxnRollbackTo { .. }
+            | Command::TxnRelease { .. }
             | Command::TxnInfo
             | Command::TxnIsActive
             | Command::Ping
             | Command::Info
             | Command::Flush
             | Command::Compact
+            | Command::Backup { .. }
             | Command::EmbedStatus
             | Command::ConfigGet
             | Command::ConfigSetAutoEmbed { .. }
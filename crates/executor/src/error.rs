@@ -211,6 +211,17 @@ pub enum Error {
         command: String,
     },
 
+    /// Write command rejected because the target branch is protected or frozen
+    #[error("access denied: {command} rejected — branch '{branch}' is {status:?}")]
+    BranchWriteDenied {
+        /// Name of the rejected command.
+        command: String,
+        /// The branch the command would have written to.
+        branch: String,
+        /// The branch's status.
+        status: crate::types::BranchStatus,
+    },
+
     // ==================== Transaction Errors ====================
     /// No active transaction
     #[error("no active transaction")]
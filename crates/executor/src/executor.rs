@@ -136,6 +136,39 @@ impl Executor {
         self.access_mode
     }
 
+    /// Reject writes to protected or frozen branches.
+    ///
+    /// Protected branches only accept merges; frozen branches accept no
+    /// writes. Must be called after `resolve_defaults()`.
+    pub(crate) fn check_branch_writable(&self, cmd: &Command) -> Result<()> {
+        match cmd.write_branch() {
+            Some(branch) => self.check_write_allowed(cmd.name(), branch, cmd.is_merge()),
+            None => Ok(()),
+        }
+    }
+
+    /// Reject a write by `command` to `branch` if the branch's status forbids it.
+    ///
+    /// Used directly by sessions, whose in-transaction writes go to the
+    /// transaction's branch whatever the command names.
+    pub(crate) fn check_write_allowed(
+        &self,
+        command: &str,
+        branch: &str,
+        via_merge: bool,
+    ) -> Result<()> {
+        let status = self.primitives.branch.write_status(branch);
+        if status.allows_write(via_merge) {
+            return Ok(());
+        }
+        warn!(target: "strata::command", %command, %branch, status = status.as_str(), "Write rejected on protected branch");
+        Err(Error::BranchWriteDenied {
+            command: command.to_string(),
+            branch: branch.to_string(),
+            status: crate::bridge::from_engine_branch_status(status),
+        })
+    }
+
     /// Auto-register a space on first write to a non-default space.
     ///
     /// This is idempotent: calling it on an already-registered space just
@@ -163,6 +196,7 @@ impl Executor {
         }
 
         cmd.resolve_defaults();
+        self.check_branch_writable(&cmd)?;
 
         let cmd_name = cmd.name();
         let start = Instant::now();
@@ -196,6 +230,16 @@ impl Executor {
                 convert_result(self.primitives.db.compact())?;
                 Ok(Output::Unit)
             }
+            Command::Backup { path } => {
+                let info = convert_result(self.primitives.db.backup(&path))?;
+                Ok(Output::BackupCreated(crate::types::BackupResult {
+                    path: info.path.to_string_lossy().to_string(),
+                    version: info.version,
+                    snapshots: info.snapshots as u64,
+                    wal_segments: info.wal_segments as u64,
+                    bytes: info.bytes,
+                }))
+            }
             Command::EmbedStatus => {
                 let info = crate::handlers::embed_hook::embed_status(&self.primitives);
                 Ok(Output::EmbedStatus(info))
@@ -223,13 +267,23 @@ impl Executor {
                 space,
                 key,
                 value,
+                ttl_ms,
+                expires_at,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
                 })?;
                 let space = space.unwrap_or_else(|| "default".to_string());
                 self.ensure_space_registered(&branch, &space)?;
-                crate::handlers::kv::kv_put(&self.primitives, branch, space, key, value)
+                crate::handlers::kv::kv_put(
+                    &self.primitives,
+                    branch,
+                    space,
+                    key,
+                    value,
+                    ttl_ms,
+                    expires_at,
+                )
             }
             Command::KvBatchPut {
                 branch,
@@ -522,13 +576,23 @@ impl Executor {
                 space,
                 cell,
                 value,
+                ttl_ms,
+                expires_at,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
                 })?;
                 let space = space.unwrap_or_else(|| "default".to_string());
                 self.ensure_space_registered(&branch, &space)?;
-                crate::handlers::state::state_set(&self.primitives, branch, space, cell, value)
+                crate::handlers::state::state_set(
+                    &self.primitives,
+                    branch,
+                    space,
+                    cell,
+                    value,
+                    ttl_ms,
+                    expires_at,
+                )
             }
             Command::StateGet {
                 branch,
@@ -586,13 +650,23 @@ impl Executor {
                 space,
                 cell,
                 value,
+                ttl_ms,
+                expires_at,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
                 })?;
                 let space = space.unwrap_or_else(|| "default".to_string());
                 self.ensure_space_registered(&branch, &space)?;
-                crate::handlers::state::state_init(&self.primitives, branch, space, cell, value)
+                crate::handlers::state::state_init(
+                    &self.primitives,
+                    branch,
+                    space,
+                    cell,
+                    value,
+                    ttl_ms,
+                    expires_at,
+                )
             }
             Command::StateDelete {
                 branch,
@@ -841,23 +915,90 @@ impl Executor {
             Command::BranchDelete { branch } => {
                 crate::handlers::branch::branch_delete(&self.primitives, branch)
             }
+            Command::BranchSetStatus { branch, status } => {
+                crate::handlers::branch::branch_set_status(&self.primitives, branch, status)
+            }
+            Command::BranchSetRetention { branch, policy } => {
+                crate::handlers::retention::branch_set_retention(&self.primitives, branch, policy)
+            }
+            Command::BranchGetRetention { branch } => {
+                crate::handlers::retention::branch_get_retention(&self.primitives, branch)
+            }
             Command::BranchFork {
                 source,
                 destination,
             } => crate::handlers::branch::branch_fork(&self.primitives, source, destination),
-            Command::BranchDiff { branch_a, branch_b } => {
-                crate::handlers::branch::branch_diff(&self.primitives, branch_a, branch_b)
+            Command::BranchForkAt {
+                source,
+                destination,
+                as_of,
+            } => crate::handlers::branch::branch_fork_at(
+                &self.primitives,
+                source,
+                destination,
+                as_of,
+            ),
+            Command::BranchRevert { branch, as_of } => {
+                crate::handlers::branch::branch_revert(&self.primitives, branch, as_of)
+            }
+            Command::BranchDiff {
+                branch_a,
+                branch_b,
+                options,
+            } => {
+                crate::handlers::branch::branch_diff(&self.primitives, branch_a, branch_b, options)
             }
             Command::BranchMerge {
                 source,
                 target,
                 strategy,
-            } => crate::handlers::branch::branch_merge(&self.primitives, source, target, strategy),
+                primitives,
+            } => crate::handlers::branch::branch_merge(
+                &self.primitives,
+                source,
+                target,
+                strategy,
+                primitives,
+            ),
+            Command::BranchMergeResolve {
+                source,
+                target,
+                primitives,
+                resolutions,
+            } => crate::handlers::branch::branch_merge_resolve(
+                &self.primitives,
+                source,
+                target,
+                primitives,
+                resolutions,
+            ),
+            Command::BranchLog {
+                branch,
+                limit,
+                before,
+            } => crate::handlers::branch::branch_log(&self.primitives, branch, limit, before),
+            Command::BranchCherryPick {
+                source,
+                target,
+                commits,
+                strategy,
+                primitives,
+            } => crate::handlers::branch::branch_cherry_pick(
+                &self.primitives,
+                source,
+                target,
+                commits,
+                strategy,
+                primitives,
+            ),
 
             // Transaction commands - handled by Session, not Executor
             Command::TxnBegin { .. }
             | Command::TxnCommit
             | Command::TxnRollback
+            | Command::TxnSavepoint { .. }
+            | Command::TxnRollbackTo { .. }
+            | Command::TxnRelease { .. }
             | Command::TxnInfo
             | Command::TxnIsActive => Err(Error::Internal {
                 reason: "Transaction commands not yet implemented".to_string(),
@@ -865,21 +1006,13 @@ impl Executor {
 
             // Retention commands
             Command::RetentionApply { branch } => {
-                let branch = branch.ok_or(Error::InvalidInput {
-                    reason: "Branch must be specified or resolved to default".into(),
-                })?;
-                let branch_id = crate::bridge::to_core_branch_id(&branch)?;
-                // Use the current version as the safe GC boundary:
-                // all versions older than the current version are prunable
-                // since they have been superseded by newer commits.
-                let current = self.primitives.db.current_version();
-                let _pruned = self.primitives.db.gc_versions_before(branch_id, current);
-                Ok(Output::Unit)
+                crate::handlers::retention::retention_apply(&self.primitives, branch)
+            }
+            Command::RetentionStats { branch } => {
+                crate::handlers::retention::retention_stats(&self.primitives, branch)
             }
-            Command::RetentionStats { .. } | Command::RetentionPreview { .. } => {
-                Err(Error::Internal {
-                    reason: "Retention commands not yet implemented".to_string(),
-                })
+            Command::RetentionPreview { branch } => {
+                crate::handlers::retention::retention_preview(&self.primitives, branch)
             }
 
             // Bundle commands
@@ -890,7 +1023,7 @@ impl Executor {
                 crate::handlers::branch::branch_import(&self.primitives, path)
             }
             Command::BranchBundleValidate { path } => {
-                crate::handlers::branch::branch_bundle_validate(path)
+                crate::handlers::branch::branch_bundle_validate(&self.primitives, path)
             }
 
             // Embedding commands
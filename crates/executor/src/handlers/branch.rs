@@ -23,7 +23,7 @@ fn metadata_to_branch_info(m: &BranchMetadata) -> BranchInfo {
         status: from_engine_branch_status(m.status),
         created_at: m.created_at,
         updated_at: m.updated_at,
-        parent_id: None,
+        parent_id: m.parent_branch.clone().map(BranchId::from),
     }
 }
 
@@ -121,17 +121,19 @@ pub fn branch_get(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
 /// Handle BranchList command.
 pub fn branch_list(
     p: &Arc<Primitives>,
-    _state: Option<crate::types::BranchStatus>,
+    state: Option<crate::types::BranchStatus>,
     limit: Option<u64>,
     _offset: Option<u64>,
 ) -> Result<Output> {
-    // MVP: ignore status filter, list all branches
     let ids = convert_result(p.branch.list_branches())?;
 
     let mut all = Vec::new();
     for id in ids {
         if let Some(versioned) = convert_result(p.branch.get_branch(&id))? {
-            all.push(versioned_to_branch_info(versioned));
+            let info = versioned_to_branch_info(versioned);
+            if state.map_or(true, |s| info.info.status == s) {
+                all.push(info);
+            }
         }
     }
 
@@ -179,8 +181,24 @@ pub fn branch_delete(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
     Ok(Output::Unit)
 }
 
+/// Handle BranchSetStatus command.
+pub fn branch_set_status(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    status: crate::types::BranchStatus,
+) -> Result<Output> {
+    let versioned = convert_result(p.branch.set_status(
+        branch.as_str(),
+        crate::bridge::to_engine_branch_status(status),
+    ))?;
+    Ok(Output::BranchWithVersion {
+        info: metadata_to_branch_info(&versioned.value),
+        version: extract_version(&versioned.version),
+    })
+}
+
 // =============================================================================
-// Branch Operations (fork, diff, merge)
+// Branch Operations (fork, revert, diff, merge, cherry-pick)
 // =============================================================================
 
 /// Handle BranchFork command.
@@ -194,14 +212,43 @@ pub fn branch_fork(p: &Arc<Primitives>, source: String, destination: String) ->
     Ok(Output::BranchForked(info))
 }
 
-/// Handle BranchDiff command.
-pub fn branch_diff(p: &Arc<Primitives>, branch_a: String, branch_b: String) -> Result<Output> {
-    let result =
-        strata_engine::branch_ops::diff_branches(&p.db, &branch_a, &branch_b).map_err(|e| {
-            Error::Internal {
-                reason: e.to_string(),
-            }
+/// Handle BranchForkAt command.
+pub fn branch_fork_at(
+    p: &Arc<Primitives>,
+    source: String,
+    destination: String,
+    as_of: u64,
+) -> Result<Output> {
+    let info = strata_engine::branch_ops::fork_branch_at(&p.db, &source, &destination, as_of)
+        .map_err(|e| Error::Internal {
+            reason: e.to_string(),
         })?;
+    Ok(Output::BranchForked(info))
+}
+
+/// Handle BranchRevert command.
+pub fn branch_revert(p: &Arc<Primitives>, branch: String, as_of: u64) -> Result<Output> {
+    let info = strata_engine::branch_ops::revert_branch(&p.db, &branch, as_of).map_err(|e| {
+        Error::Internal {
+            reason: e.to_string(),
+        }
+    })?;
+    Ok(Output::BranchReverted(info))
+}
+
+/// Handle BranchDiff command.
+pub fn branch_diff(
+    p: &Arc<Primitives>,
+    branch_a: String,
+    branch_b: String,
+    options: strata_engine::DiffOptions,
+) -> Result<Output> {
+    let result = strata_engine::branch_ops::diff_branches_with_options(
+        &p.db, &branch_a, &branch_b, &options,
+    )
+    .map_err(|e| Error::Internal {
+        reason: e.to_string(),
+    })?;
     Ok(Output::BranchDiff(result))
 }
 
@@ -211,14 +258,66 @@ pub fn branch_merge(
     source: String,
     target: String,
     strategy: strata_engine::MergeStrategy,
+    primitives: strata_engine::PrimitiveMergeStrategies,
 ) -> Result<Output> {
-    let info = strata_engine::branch_ops::merge_branches(&p.db, &source, &target, strategy)
-        .map_err(|e| Error::Internal {
-            reason: e.to_string(),
-        })?;
+    let info = strata_engine::branch_ops::merge_branches_with_strategies(
+        &p.db, &source, &target, strategy, primitives,
+    )
+    .map_err(|e| Error::Internal {
+        reason: e.to_string(),
+    })?;
     Ok(Output::BranchMerged(info))
 }
 
+/// Handle BranchMergeResolve command.
+pub fn branch_merge_resolve(
+    p: &Arc<Primitives>,
+    source: String,
+    target: String,
+    primitives: strata_engine::PrimitiveMergeStrategies,
+    resolutions: Vec<strata_engine::MergeResolution>,
+) -> Result<Output> {
+    let info =
+        strata_engine::branch_ops::resolve_merge(&p.db, &source, &target, primitives, &resolutions)
+            .map_err(|e| Error::Internal {
+                reason: e.to_string(),
+            })?;
+    Ok(Output::BranchMerged(info))
+}
+
+/// Handle BranchLog command.
+pub fn branch_log(
+    p: &Arc<Primitives>,
+    branch: String,
+    limit: Option<u64>,
+    before: Option<u64>,
+) -> Result<Output> {
+    let commits =
+        strata_engine::branch_ops::list_commits(&p.db, &branch, limit.map(|l| l as usize), before)
+            .map_err(|e| Error::Internal {
+                reason: e.to_string(),
+            })?;
+    Ok(Output::BranchLog(commits))
+}
+
+/// Handle BranchCherryPick command.
+pub fn branch_cherry_pick(
+    p: &Arc<Primitives>,
+    source: String,
+    target: String,
+    commits: Vec<u64>,
+    strategy: strata_engine::MergeStrategy,
+    primitives: strata_engine::PrimitiveMergeStrategies,
+) -> Result<Output> {
+    let info = strata_engine::branch_ops::cherry_pick(
+        &p.db, &source, &target, &commits, strategy, primitives,
+    )
+    .map_err(|e| Error::Internal {
+        reason: e.to_string(),
+    })?;
+    Ok(Output::BranchCherryPicked(info))
+}
+
 // =============================================================================
 // Bundle Handlers
 // =============================================================================
@@ -256,10 +355,12 @@ pub fn branch_import(p: &Arc<Primitives>, path: String) -> Result<Output> {
 }
 
 /// Handle BranchBundleValidate command.
-pub fn branch_bundle_validate(path: String) -> Result<Output> {
+pub fn branch_bundle_validate(p: &Arc<Primitives>, path: String) -> Result<Output> {
     let validate_path = std::path::Path::new(&path);
-    let info = strata_engine::bundle::validate_bundle(validate_path).map_err(|e| Error::Io {
-        reason: format!("Validation failed: {}", e),
+    let info = strata_engine::bundle::validate_bundle_for(&p.db, validate_path).map_err(|e| {
+        Error::Io {
+            reason: format!("Validation failed: {}", e),
+        }
     })?;
 
     Ok(Output::BundleValidated(
@@ -290,8 +391,13 @@ mod tests {
         let m = BranchMetadata {
             name: "test-branch".to_string(),
             branch_id: "some-uuid".to_string(),
-            parent_branch: None,
+            parent_branch: Some("main".to_string()),
+            fork_version: Some(7),
+            ancestry: Vec::new(),
+            merged_from: Default::default(),
             status: strata_engine::BranchStatus::Active,
+            retention: None,
+            retention_horizon: None,
             created_at: 1000000,
             updated_at: 2000000,
             completed_at: None,
@@ -301,5 +407,6 @@ mod tests {
         let info = metadata_to_branch_info(&m);
         assert_eq!(info.id.as_str(), "test-branch");
         assert_eq!(info.status, crate::types::BranchStatus::Active);
+        assert_eq!(info.parent_id, Some(BranchId::from("main")));
     }
 }
@@ -3,9 +3,11 @@
 use std::sync::Arc;
 
 use strata_core::Value;
+use strata_engine::extensions::GraphStoreExt;
 use strata_engine::graph::types::{
     BfsOptions, CascadePolicy, Direction, EdgeData, GraphMeta, NodeData,
 };
+use strata_engine::TransactionContext;
 
 use crate::bridge::{to_core_branch_id, Primitives};
 use crate::convert::convert_result;
@@ -42,6 +44,49 @@ fn parse_cascade_policy(s: Option<&str>) -> Result<CascadePolicy> {
     }
 }
 
+/// Build node data from command fields.
+fn node_data(entity_ref: Option<String>, properties: Option<Value>) -> Result<NodeData> {
+    let props = match properties {
+        Some(v) => {
+            let json = crate::bridge::value_to_serde_json_public(v)?;
+            Some(json)
+        }
+        None => None,
+    };
+    Ok(NodeData {
+        entity_ref,
+        properties: props,
+    })
+}
+
+/// Build edge data from command fields.
+fn edge_data(weight: Option<f64>, properties: Option<Value>) -> Result<EdgeData> {
+    let props = match properties {
+        Some(v) => {
+            let json = crate::bridge::value_to_serde_json_public(v)?;
+            Some(json)
+        }
+        None => None,
+    };
+    Ok(EdgeData {
+        weight: weight.unwrap_or(1.0),
+        properties: props,
+    })
+}
+
+/// Convert optional graph metadata or node data to `Output::Maybe`.
+fn maybe_output<T: serde::Serialize>(data: Option<T>) -> Result<Output> {
+    match data {
+        Some(d) => {
+            let json = serde_json::to_value(&d).map_err(|e| Error::Serialization {
+                reason: e.to_string(),
+            })?;
+            Ok(Output::Maybe(Some(serde_json_to_value(json)?)))
+        }
+        None => Ok(Output::Maybe(None)),
+    }
+}
+
 /// Handle GraphCreate command.
 pub fn graph_create(
     p: &Arc<Primitives>,
@@ -73,22 +118,10 @@ pub fn graph_list(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
 }
 
 /// Handle GraphGetMeta command.
-pub fn graph_get_meta(
-    p: &Arc<Primitives>,
-    branch: BranchId,
-    graph: String,
-) -> Result<Output> {
+pub fn graph_get_meta(p: &Arc<Primitives>, branch: BranchId, graph: String) -> Result<Output> {
     let core_branch = to_core_branch_id(&branch)?;
     let meta = convert_result(p.graph.get_graph_meta(core_branch, &graph))?;
-    match meta {
-        Some(m) => {
-            let json = serde_json::to_value(&m).map_err(|e| Error::Serialization {
-                reason: e.to_string(),
-            })?;
-            Ok(Output::Maybe(Some(serde_json_to_value(json)?)))
-        }
-        None => Ok(Output::Maybe(None)),
-    }
+    maybe_output(meta)
 }
 
 /// Handle GraphAddNode command.
@@ -101,17 +134,7 @@ pub fn graph_add_node(
     properties: Option<Value>,
 ) -> Result<Output> {
     let core_branch = to_core_branch_id(&branch)?;
-    let props = match properties {
-        Some(v) => {
-            let json = crate::bridge::value_to_serde_json_public(v)?;
-            Some(json)
-        }
-        None => None,
-    };
-    let data = NodeData {
-        entity_ref,
-        properties: props,
-    };
+    let data = node_data(entity_ref, properties)?;
     convert_result(p.graph.add_node(core_branch, &graph, &node_id, data))?;
     Ok(Output::Unit)
 }
@@ -125,15 +148,7 @@ pub fn graph_get_node(
 ) -> Result<Output> {
     let core_branch = to_core_branch_id(&branch)?;
     let node = convert_result(p.graph.get_node(core_branch, &graph, &node_id))?;
-    match node {
-        Some(data) => {
-            let json = serde_json::to_value(&data).map_err(|e| Error::Serialization {
-                reason: e.to_string(),
-            })?;
-            Ok(Output::Maybe(Some(serde_json_to_value(json)?)))
-        }
-        None => Ok(Output::Maybe(None)),
-    }
+    maybe_output(node)
 }
 
 /// Handle GraphRemoveNode command.
@@ -149,11 +164,7 @@ pub fn graph_remove_node(
 }
 
 /// Handle GraphListNodes command.
-pub fn graph_list_nodes(
-    p: &Arc<Primitives>,
-    branch: BranchId,
-    graph: String,
-) -> Result<Output> {
+pub fn graph_list_nodes(p: &Arc<Primitives>, branch: BranchId, graph: String) -> Result<Output> {
     let core_branch = to_core_branch_id(&branch)?;
     let nodes = convert_result(p.graph.list_nodes(core_branch, &graph))?;
     Ok(Output::Keys(nodes))
@@ -172,18 +183,11 @@ pub fn graph_add_edge(
     properties: Option<Value>,
 ) -> Result<Output> {
     let core_branch = to_core_branch_id(&branch)?;
-    let props = match properties {
-        Some(v) => {
-            let json = crate::bridge::value_to_serde_json_public(v)?;
-            Some(json)
-        }
-        None => None,
-    };
-    let data = EdgeData {
-        weight: weight.unwrap_or(1.0),
-        properties: props,
-    };
-    convert_result(p.graph.add_edge(core_branch, &graph, &src, &dst, &edge_type, data))?;
+    let data = edge_data(weight, properties)?;
+    convert_result(
+        p.graph
+            .add_edge(core_branch, &graph, &src, &dst, &edge_type, data),
+    )?;
     Ok(Output::Unit)
 }
 
@@ -197,7 +201,10 @@ pub fn graph_remove_edge(
     edge_type: String,
 ) -> Result<Output> {
     let core_branch = to_core_branch_id(&branch)?;
-    convert_result(p.graph.remove_edge(core_branch, &graph, &src, &dst, &edge_type))?;
+    convert_result(
+        p.graph
+            .remove_edge(core_branch, &graph, &src, &dst, &edge_type),
+    )?;
     Ok(Output::Unit)
 }
 
@@ -262,6 +269,97 @@ pub fn graph_bfs(
     }))
 }
 
+// =============================================================================
+// In-Transaction Handlers
+// =============================================================================
+//
+// Used by `Session` while a transaction is open: the graph is read and
+// written through the transaction's context on its branch.
+
+/// Handle GraphCreate inside a transaction.
+pub fn graph_create_in_txn(
+    ctx: &mut TransactionContext,
+    graph: String,
+    cascade_policy: Option<String>,
+) -> Result<Output> {
+    let policy = parse_cascade_policy(cascade_policy.as_deref())?;
+    let meta = GraphMeta {
+        cascade_policy: policy,
+    };
+    convert_result(ctx.graph_create(&graph, Some(meta)))?;
+    Ok(Output::Unit)
+}
+
+/// Handle GraphDelete inside a transaction.
+pub fn graph_delete_in_txn(ctx: &mut TransactionContext, graph: String) -> Result<Output> {
+    convert_result(ctx.graph_delete(&graph))?;
+    Ok(Output::Unit)
+}
+
+/// Handle GraphGetMeta inside a transaction.
+pub fn graph_get_meta_in_txn(ctx: &mut TransactionContext, graph: String) -> Result<Output> {
+    maybe_output(convert_result(ctx.graph_get_meta(&graph))?)
+}
+
+/// Handle GraphAddNode inside a transaction.
+pub fn graph_add_node_in_txn(
+    ctx: &mut TransactionContext,
+    graph: String,
+    node_id: String,
+    entity_ref: Option<String>,
+    properties: Option<Value>,
+) -> Result<Output> {
+    let data = node_data(entity_ref, properties)?;
+    convert_result(ctx.graph_add_node(&graph, &node_id, data))?;
+    Ok(Output::Unit)
+}
+
+/// Handle GraphGetNode inside a transaction.
+pub fn graph_get_node_in_txn(
+    ctx: &mut TransactionContext,
+    graph: String,
+    node_id: String,
+) -> Result<Output> {
+    maybe_output(convert_result(ctx.graph_get_node(&graph, &node_id))?)
+}
+
+/// Handle GraphRemoveNode inside a transaction.
+pub fn graph_remove_node_in_txn(
+    ctx: &mut TransactionContext,
+    graph: String,
+    node_id: String,
+) -> Result<Output> {
+    convert_result(ctx.graph_remove_node(&graph, &node_id))?;
+    Ok(Output::Unit)
+}
+
+/// Handle GraphAddEdge inside a transaction.
+pub fn graph_add_edge_in_txn(
+    ctx: &mut TransactionContext,
+    graph: String,
+    src: String,
+    dst: String,
+    edge_type: String,
+    weight: Option<f64>,
+    properties: Option<Value>,
+) -> Result<Output> {
+    let data = edge_data(weight, properties)?;
+    convert_result(ctx.graph_add_edge(&graph, &src, &dst, &edge_type, data))?;
+    Ok(Output::Unit)
+}
+
+/// Handle GraphRemoveEdge inside a transaction.
+pub fn graph_remove_edge_in_txn(
+    ctx: &mut TransactionContext,
+    graph: String,
+    src: String,
+    dst: String,
+    edge_type: String,
+) -> Result<Output> {
+    convert_result(ctx.graph_remove_edge(&graph, &src, &dst, &edge_type))?;
+    Ok(Output::Unit)
+}
+
 /// Convert serde_json::Value to strata_core::Value.
 fn serde_json_to_value(json: serde_json::Value) -> Result<Value> {
     crate::bridge::serde_json_to_value_public(json).map_err(|e| Error::Serialization {
@@ -4,12 +4,13 @@
 //! directly to engine primitives via `bridge::Primitives`.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use strata_core::Value;
 
 use crate::bridge::{
-    extract_version, to_core_branch_id, to_versioned_value, validate_key, validate_value,
-    Primitives,
+    extract_version, resolve_ttl, to_core_branch_id, to_versioned_value, validate_key,
+    validate_value, Primitives,
 };
 use crate::convert::convert_result;
 use crate::types::BranchId;
@@ -64,16 +65,19 @@ pub fn kv_put(
     space: String,
     key: String,
     value: Value,
+    ttl_ms: Option<u64>,
+    expires_at: Option<u64>,
 ) -> Result<Output> {
     require_branch_exists(p, &branch)?;
     let branch_id = to_core_branch_id(&branch)?;
     convert_result(validate_key(&key))?;
     convert_result(validate_value(&value, &p.limits))?;
+    let ttl = convert_result(resolve_ttl(ttl_ms, expires_at))?;
 
     // Extract text before the value is consumed by put()
     let text = super::embed_hook::extract_text(&value);
 
-    let version = convert_result(p.kv.put(&branch_id, &space, &key, value))?;
+    let version = convert_result(p.kv.put_with_ttl(&branch_id, &space, &key, value, ttl))?;
 
     // Best-effort auto-embed after successful write
     if let Some(ref text) = text {
@@ -200,7 +204,7 @@ pub fn kv_batch_put(
     ];
 
     // Pre-validate entries, collect valid ones with their original indices
-    let mut valid_entries: Vec<(usize, String, Value)> = Vec::with_capacity(n);
+    let mut valid_entries: Vec<(usize, String, Value, Option<Duration>)> = Vec::with_capacity(n);
     for (i, entry) in entries.into_iter().enumerate() {
         if let Err(e) = validate_key(&entry.key) {
             results[i].error = Some(e.to_string());
@@ -210,7 +214,14 @@ pub fn kv_batch_put(
             results[i].error = Some(e.to_string());
             continue;
         }
-        valid_entries.push((i, entry.key, entry.value));
+        let ttl = match resolve_ttl(entry.ttl_ms, entry.expires_at) {
+            Ok(ttl) => ttl,
+            Err(e) => {
+                results[i].error = Some(e.to_string());
+                continue;
+            }
+        };
+        valid_entries.push((i, entry.key, entry.value, ttl));
     }
 
     if valid_entries.is_empty() {
@@ -220,19 +231,20 @@ pub fn kv_batch_put(
     // Extract text for embed hooks BEFORE values are consumed
     let embed_data: Vec<(usize, String, Option<String>)> = valid_entries
         .iter()
-        .map(|(idx, key, value)| {
+        .map(|(idx, key, value, _)| {
             let text = super::embed_hook::extract_text(value);
             (*idx, key.clone(), text)
         })
         .collect();
 
-    // Build engine entries (key, value) pairs
-    let engine_entries: Vec<(String, Value)> = valid_entries
+    // Build engine entries (key, value, ttl)
+    let engine_entries: Vec<(String, Value, Option<Duration>)> = valid_entries
         .into_iter()
-        .map(|(_, key, value)| (key, value))
+        .map(|(_, key, value, ttl)| (key, value, ttl))
         .collect();
 
-    let engine_results = convert_result(p.kv.batch_put(&branch_id, &space, engine_entries))?;
+    let engine_results =
+        convert_result(p.kv.batch_put_with_ttl(&branch_id, &space, engine_entries))?;
 
     // Merge engine results back into the results vec
     for (j, (orig_idx, _, _)) in embed_data.iter().enumerate() {
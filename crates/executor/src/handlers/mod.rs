@@ -11,7 +11,7 @@
 //! | `vector` | 19 | VectorStore |
 //! | `branch` | 24 | BranchIndex |
 //! | `transaction` | 5 | TransactionControl |
-//! | `retention` | 3 | RetentionSubstrate |
+//! | `retention` | 5 | Branch retention policies |
 //! | `database` | 4 | Database-level |
 
 pub mod branch;
@@ -25,6 +25,7 @@ pub mod graph;
 pub mod json;
 pub mod kv;
 pub mod models;
+pub mod retention;
 pub mod search;
 pub mod space;
 pub mod state;
@@ -33,7 +34,4 @@ pub mod vector;
 // Transaction commands are deferred because the Executor is stateless by design.
 // Transactions require session state management which would need additional design work.
 //
-// Retention commands (RetentionApply, RetentionStats, RetentionPreview) are deferred
-// as they require additional infrastructure for garbage collection statistics.
-//
-// Database commands (Ping, Info, Flush, Compact) are implemented directly in executor.rs.
+// Database commands (Ping, Info, Flush, Compact, Backup) are implemented directly in executor.rs.
@@ -0,0 +1,61 @@
+//! Retention command handlers
+//!
+//! This module implements handlers for branch retention policies and the
+//! Retention commands by dispatching to `strata_engine::retention`.
+
+use std::sync::Arc;
+
+use strata_engine::{retention, RetentionPolicy};
+
+use crate::bridge::Primitives;
+use crate::convert::convert_result;
+use crate::types::BranchId;
+use crate::{Error, Output, Result};
+
+/// Resolve the optional branch of a Retention command.
+fn require_branch(branch: Option<BranchId>) -> Result<BranchId> {
+    branch.ok_or(Error::InvalidInput {
+        reason: "Branch must be specified or resolved to default".into(),
+    })
+}
+
+/// Handle BranchSetRetention command.
+pub fn branch_set_retention(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    policy: RetentionPolicy,
+) -> Result<Output> {
+    let info = convert_result(retention::set_branch_retention(
+        &p.db,
+        branch.as_str(),
+        policy,
+    ))?;
+    Ok(Output::BranchRetention(info))
+}
+
+/// Handle BranchGetRetention command.
+pub fn branch_get_retention(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
+    let info = convert_result(retention::get_branch_retention(&p.db, branch.as_str()))?;
+    Ok(Output::BranchRetention(info))
+}
+
+/// Handle RetentionApply command.
+pub fn retention_apply(p: &Arc<Primitives>, branch: Option<BranchId>) -> Result<Output> {
+    let branch = require_branch(branch)?;
+    let info = convert_result(retention::apply_retention(&p.db, branch.as_str()))?;
+    Ok(Output::RetentionResult(info))
+}
+
+/// Handle RetentionStats command.
+pub fn retention_stats(p: &Arc<Primitives>, branch: Option<BranchId>) -> Result<Output> {
+    let branch = require_branch(branch)?;
+    let stats = convert_result(retention::retention_stats(&p.db, branch.as_str()))?;
+    Ok(Output::RetentionStats(stats))
+}
+
+/// Handle RetentionPreview command.
+pub fn retention_preview(p: &Arc<Primitives>, branch: Option<BranchId>) -> Result<Output> {
+    let branch = require_branch(branch)?;
+    let preview = convert_result(retention::preview_retention(&p.db, branch.as_str()))?;
+    Ok(Output::RetentionPreview(preview))
+}
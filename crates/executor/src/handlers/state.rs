@@ -64,16 +64,19 @@ pub fn state_set(
     space: String,
     cell: String,
     value: Value,
+    ttl_ms: Option<u64>,
+    expires_at: Option<u64>,
 ) -> Result<Output> {
     require_branch_exists(p, &branch)?;
     let branch_id = bridge::to_core_branch_id(&branch)?;
     convert_result(bridge::validate_key(&cell))?;
     convert_result(validate_value(&value, &p.limits))?;
+    let ttl = convert_result(bridge::resolve_ttl(ttl_ms, expires_at))?;
 
     // Extract text before value is consumed
     let text = super::embed_hook::extract_text(&value);
 
-    let version = convert_result(p.state.set(&branch_id, &space, &cell, value))?;
+    let version = convert_result(p.state.set_with_ttl(&branch_id, &space, &cell, value, ttl))?;
 
     // Best-effort auto-embed after successful write
     if let Some(ref text) = text {
@@ -206,16 +209,19 @@ pub fn state_init(
     space: String,
     cell: String,
     value: Value,
+    ttl_ms: Option<u64>,
+    expires_at: Option<u64>,
 ) -> Result<Output> {
     require_branch_exists(p, &branch)?;
     let branch_id = bridge::to_core_branch_id(&branch)?;
     convert_result(bridge::validate_key(&cell))?;
     convert_result(validate_value(&value, &p.limits))?;
+    let ttl = convert_result(bridge::resolve_ttl(ttl_ms, expires_at))?;
 
     // Extract text before value is consumed
     let text = super::embed_hook::extract_text(&value);
 
-    let version = convert_result(p.state.init(&branch_id, &space, &cell, value))?;
+    let version = convert_result(p.state.init_with_ttl(&branch_id, &space, &cell, value, ttl))?;
 
     // Best-effort auto-embed after successful write
     if let Some(ref text) = text {
@@ -5,6 +5,7 @@
 use std::sync::Arc;
 
 use strata_core::Value;
+use strata_engine::{PendingVectorOp, TransactionContext};
 
 use crate::bridge::{
     extract_version, from_engine_metric, is_internal_collection, serde_json_to_value_public,
@@ -408,6 +409,106 @@ pub fn time_range(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
     }
 }
 
+// =============================================================================
+// In-Transaction Handlers
+// =============================================================================
+//
+// Used by `Session` while a transaction is open. The vector records are
+// written through the transaction; the matching index changes are pushed to
+// `pending` and applied with `VectorStore::apply_committed` after commit.
+
+/// Handle VectorUpsert inside a transaction.
+#[allow(clippy::too_many_arguments)]
+pub fn vector_upsert_in_txn(
+    p: &Arc<Primitives>,
+    ctx: &mut TransactionContext,
+    pending: &mut Vec<PendingVectorOp>,
+    space: String,
+    collection: String,
+    key: String,
+    vector: Vec<f32>,
+    metadata: Option<Value>,
+) -> Result<Output> {
+    convert_result(validate_key(&key))?;
+    convert_result(validate_not_internal_collection(&collection))?;
+    convert_result(validate_vector(&vector, &p.limits))?;
+
+    let json_metadata = metadata
+        .map(value_to_serde_json_public)
+        .transpose()
+        .map_err(crate::Error::from)?;
+    let branch_id = ctx.branch_id;
+    let (version, op) = convert_vector_result(
+        p.vector
+            .stage_insert(ctx, &space, &collection, &key, &vector, json_metadata),
+        branch_id,
+    )?;
+    pending.push(op);
+    Ok(Output::Version(extract_version(&version)))
+}
+
+/// Handle VectorBatchUpsert inside a transaction.
+pub fn vector_batch_upsert_in_txn(
+    p: &Arc<Primitives>,
+    ctx: &mut TransactionContext,
+    pending: &mut Vec<PendingVectorOp>,
+    space: String,
+    collection: String,
+    entries: Vec<crate::types::BatchVectorEntry>,
+) -> Result<Output> {
+    convert_result(validate_not_internal_collection(&collection))?;
+    for entry in &entries {
+        convert_result(validate_key(&entry.key))?;
+        convert_result(validate_vector(&entry.vector, &p.limits))?;
+    }
+
+    let branch_id = ctx.branch_id;
+    let mut version_nums = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let json_metadata = entry
+            .metadata
+            .map(value_to_serde_json_public)
+            .transpose()
+            .map_err(crate::Error::from)?;
+        let (version, op) = convert_vector_result(
+            p.vector.stage_insert(
+                ctx,
+                &space,
+                &collection,
+                &entry.key,
+                &entry.vector,
+                json_metadata,
+            ),
+            branch_id,
+        )?;
+        pending.push(op);
+        version_nums.push(extract_version(&version));
+    }
+    Ok(Output::Versions(version_nums))
+}
+
+/// Handle VectorDelete inside a transaction.
+pub fn vector_delete_in_txn(
+    p: &Arc<Primitives>,
+    ctx: &mut TransactionContext,
+    pending: &mut Vec<PendingVectorOp>,
+    space: String,
+    collection: String,
+    key: String,
+) -> Result<Output> {
+    convert_result(validate_key(&key))?;
+    convert_result(validate_not_internal_collection(&collection))?;
+
+    let branch_id = ctx.branch_id;
+    let op = convert_vector_result(
+        p.vector.stage_delete(ctx, &space, &collection, &key),
+        branch_id,
+    )?;
+    let existed = op.is_some();
+    pending.extend(op);
+    Ok(Output::Bool(existed))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
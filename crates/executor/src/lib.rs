@@ -72,8 +72,10 @@ mod tests;
 
 // Core types
 pub use api::{
-    BranchDiffEntry, BranchDiffResult, Branches, ConflictEntry, DiffSummary, ForkInfo, MergeInfo,
-    MergeStrategy, SpaceDiff, Strata,
+    BranchDiffEntry, BranchDiffResult, Branches, CherryPickInfo, CommitInfo, ConflictEntry,
+    ConflictResolution, DiffOptions, DiffSummary, EventMergeStrategy, ForkInfo, JsonMergeStrategy,
+    MergeInfo, MergeResolution, MergeStrategy, PrimitiveMergeStrategies, RevertInfo, SpaceDiff,
+    StateMergeStrategy, Strata, TouchedKeys,
 };
 pub use command::Command;
 pub use error::Error;
@@ -85,6 +87,10 @@ pub use types::*;
 // Re-export Value from strata_core so users don't need to import it
 pub use strata_core::Value;
 
+// Re-export PrimitiveType so diff filters and merge conflicts can be
+// inspected without depending on strata-core directly
+pub use strata_core::PrimitiveType;
+
 // Re-export security types so users don't need strata-security directly
 pub use strata_security::{AccessMode, OpenOptions};
 
@@ -92,11 +98,35 @@ pub use strata_security::{AccessMode, OpenOptions};
 pub use strata_engine::WalCounters;
 
 // Re-export configuration types so users don't need strata-engine directly
-pub use strata_engine::{ModelConfig, StrataConfig};
+pub use strata_engine::{CheckpointConfig, ModelConfig, StrataConfig};
 
 // Re-export Database and DurabilityMode so users can open/create databases
 // and create sessions without depending on strata-engine directly
 pub use strata_engine::{Database, DurabilityMode};
 
+// Re-export the rekey and restore summaries (return types of Strata::rekey
+// and Strata::restore)
+pub use strata_engine::{RekeyInfo, RestoreInfo};
+
+// Re-export the integrity check report (return type of Strata::fsck)
+pub use strata_engine::{FsckComponent, FsckFinding, FsckReport, FsckSeverity};
+
+// Re-export the WAL dump types (filter and return type of Strata::wal_dump)
+pub use strata_engine::{WalDumpFilter, WalDumpRecord, WalDumpWrite};
+
+// Re-export retention types (policy and return types of the Branches
+// retention methods)
+pub use strata_engine::{
+    BranchRetention, PrimitiveRetention, PrunableVersion, RetentionApplyInfo, RetentionHorizon,
+    RetentionPolicy, RetentionPreview, RetentionStats,
+};
+
+// Re-export WAL archive types (see `wal_archive_dir` in strata.toml and
+// Database::set_wal_archiver)
+pub use strata_engine::{
+    ArchiveEntry, ArchiveError, ArchivePruneInfo, ArchiveVerifyReport, LocalWalArchive,
+    SegmentMeta, WalArchiver,
+};
+
 /// Result type for executor operations
 pub type Result<T> = std::result::Result<T, Error>;
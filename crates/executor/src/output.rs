@@ -6,8 +6,13 @@
 
 use serde::{Deserialize, Serialize};
 use strata_core::Value;
-use strata_engine::branch_ops::{BranchDiffResult, ForkInfo, MergeInfo};
-use strata_engine::{StrataConfig, WalCounters};
+use strata_engine::branch_ops::{
+    BranchDiffResult, CherryPickInfo, CommitInfo, ForkInfo, MergeInfo, RevertInfo,
+};
+use strata_engine::{
+    BranchRetention, RetentionApplyInfo, RetentionPreview, RetentionStats, StrataConfig,
+    WalCounters,
+};
 
 use crate::types::*;
 
@@ -114,6 +119,18 @@ pub enum Output {
     /// Branch merge result
     BranchMerged(MergeInfo),
 
+    /// Branch revert result
+    BranchReverted(RevertInfo),
+
+    /// Commits on a branch, newest first
+    BranchLog(Vec<CommitInfo>),
+
+    /// Cherry-pick result
+    BranchCherryPicked(CherryPickInfo),
+
+    /// Branch retention policy and horizon
+    BranchRetention(BranchRetention),
+
     /// Database configuration snapshot
     Config(StrataConfig),
 
@@ -136,6 +153,16 @@ pub enum Output {
     /// Transaction aborted
     TxnAborted,
 
+    // ==================== Retention-specific ====================
+    /// Result of applying a branch's retention policy
+    RetentionResult(RetentionApplyInfo),
+
+    /// Version history a branch holds, per primitive
+    RetentionStats(RetentionStats),
+
+    /// Versions applying a branch's retention policy would prune
+    RetentionPreview(RetentionPreview),
+
     // ==================== Database-specific ====================
     /// Database info
     DatabaseInfo(DatabaseInfo),
@@ -146,6 +173,9 @@ pub enum Output {
         version: String,
     },
 
+    /// Backup result
+    BackupCreated(BackupResult),
+
     // ==================== Intelligence ====================
     /// Search results across primitives
     SearchResults(Vec<SearchResultHit>),
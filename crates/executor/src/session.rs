@@ -25,12 +25,12 @@
 use std::sync::Arc;
 
 use strata_core::types::{Key, Namespace, TypeTag};
-use strata_engine::{Database, Transaction, TransactionContext, TransactionOps};
+use strata_engine::{Database, PendingVectorOp, Transaction, TransactionContext, TransactionOps};
 use strata_security::AccessMode;
 
 use crate::bridge::{
-    extract_version, json_to_value, parse_path, to_core_branch_id, to_versioned_value,
-    value_to_json,
+    extract_version, json_to_value, parse_path, resolve_ttl, to_core_branch_id,
+    to_engine_isolation, to_versioned_value, value_to_json,
 };
 use crate::convert::convert_result;
 use crate::types::BranchId;
@@ -40,15 +40,27 @@ use crate::{Command, Error, Executor, Output, Result};
 /// open transaction with read-your-writes semantics.
 ///
 /// When no transaction is active, commands delegate to the inner `Executor`.
-/// When a transaction is active, data commands (KV, Event, State, JSON)
-/// route through the engine's `Transaction<'a>` / `TransactionOps` trait,
-/// while non-transactional commands (Branch, Vector, DB) still delegate to
-/// the `Executor`.
+/// When a transaction is active, data commands (KV, Event, State, JSON,
+/// vector upserts/deletes and graph mutations) route through the engine's
+/// `TransactionContext`, while non-transactional commands (Branch, vector
+/// collections, DB) still delegate to the `Executor`.
+///
+/// Vector writes in a transaction update the vector index only once the
+/// transaction commits; until then searches see the committed vectors.
+///
+/// Savepoints (`TxnSavepoint`, `TxnRollbackTo`, `TxnRelease`) undo part of
+/// an open transaction, including its pending vector index changes.
 pub struct Session {
     executor: Executor,
     db: Arc<Database>,
     txn_ctx: Option<TransactionContext>,
     txn_branch_id: Option<strata_core::types::BranchId>,
+    /// Name of the open transaction's branch, for write-status checks
+    txn_branch: Option<BranchId>,
+    /// Vector index changes to apply when the open transaction commits
+    pending_vectors: Vec<PendingVectorOp>,
+    /// Open savepoints with the number of pending vector ops when each was set
+    savepoints: Vec<(String, usize)>,
 }
 
 impl Session {
@@ -59,6 +71,9 @@ impl Session {
             db,
             txn_ctx: None,
             txn_branch_id: None,
+            txn_branch: None,
+            pending_vectors: Vec::new(),
+            savepoints: Vec::new(),
         }
     }
 
@@ -69,6 +84,9 @@ impl Session {
             db,
             txn_ctx: None,
             txn_branch_id: None,
+            txn_branch: None,
+            pending_vectors: Vec::new(),
+            savepoints: Vec::new(),
         }
     }
 
@@ -92,19 +110,19 @@ impl Session {
             Command::TxnBegin { .. } => self.handle_begin(&cmd),
             Command::TxnCommit => self.handle_commit(),
             Command::TxnRollback => self.handle_abort(),
+            Command::TxnSavepoint { name } => self.handle_savepoint(name),
+            Command::TxnRollbackTo { name } => self.handle_rollback_to(name),
+            Command::TxnRelease { name } => self.handle_release(name),
             Command::TxnInfo => self.handle_txn_info(),
             Command::TxnIsActive => Ok(Output::Bool(self.in_transaction())),
 
-            // Vector write commands are not supported inside a transaction
-            // because the engine's vector store is not transactional.
-            Command::VectorUpsert { .. }
-            | Command::VectorDelete { .. }
-            | Command::VectorCreateCollection { .. }
-            | Command::VectorDeleteCollection { .. }
+            // Collection create/delete rebuild in-memory index backends and
+            // are not supported inside a transaction.
+            Command::VectorCreateCollection { .. } | Command::VectorDeleteCollection { .. }
                 if self.txn_ctx.is_some() =>
             {
                 Err(Error::InvalidInput {
-                    reason: "Vector write operations are not supported inside a transaction"
+                    reason: "Vector collection operations are not supported inside a transaction"
                         .to_string(),
                 })
             }
@@ -128,11 +146,13 @@ impl Session {
             | Command::BranchList { .. }
             | Command::BranchExists { .. }
             | Command::BranchDelete { .. }
-            // Vector commands: writes delegate to executor outside txn,
-            // reads are always safe to delegate.
-            | Command::VectorUpsert { .. }
+            | Command::BranchSetStatus { .. }
+            | Command::BranchSetRetention { .. }
+            | Command::BranchGetRetention { .. }
+            // Vector reads and collection commands: reads are always safe to
+            // delegate and see committed vectors. Vector upserts and deletes
+            // are data commands (below).
             | Command::VectorGet { .. }
-            | Command::VectorDelete { .. }
             | Command::VectorSearch { .. }
             | Command::VectorCreateCollection { .. }
             | Command::VectorDeleteCollection { .. }
@@ -141,6 +161,7 @@ impl Session {
             | Command::Info
             | Command::Flush
             | Command::Compact
+            | Command::Backup { .. }
             | Command::EmbedStatus
             | Command::RetentionApply { .. }
             | Command::RetentionStats { .. }
@@ -178,7 +199,13 @@ impl Session {
 
             // Data commands: route through txn if active, else delegate
             _ => {
-                if self.txn_ctx.is_some() {
+                if let Some(branch) = &self.txn_branch {
+                    // In-transaction writes go to the transaction's branch,
+                    // not the one named by the command.
+                    if cmd.write_branch().is_some() {
+                        self.executor
+                            .check_write_allowed(cmd.name(), branch.as_str(), false)?;
+                    }
                     self.execute_in_txn(cmd)
                 } else {
                     self.executor.execute(cmd)
@@ -201,15 +228,37 @@ impl Session {
             return Err(Error::TransactionAlreadyActive);
         }
 
-        let branch = match cmd {
-            Command::TxnBegin { branch, .. } => branch.clone().unwrap_or_else(BranchId::default),
+        let (branch, options) = match cmd {
+            Command::TxnBegin { branch, options } => (
+                branch.clone().unwrap_or_else(BranchId::default),
+                options.clone().unwrap_or_default(),
+            ),
             _ => unreachable!(),
         };
 
         let core_branch_id = to_core_branch_id(&branch)?;
-        let ctx = self.db.begin_transaction(core_branch_id);
+        if self
+            .executor
+            .primitives()
+            .branch
+            .write_status(branch.as_str())
+            == strata_engine::BranchStatus::Frozen
+        {
+            return Err(Error::BranchWriteDenied {
+                command: cmd.name().to_string(),
+                branch: branch.to_string(),
+                status: crate::types::BranchStatus::Frozen,
+            });
+        }
+        self.pending_vectors.clear();
+        self.savepoints.clear();
+        let mut ctx = self.db.begin_transaction(core_branch_id);
+        ctx.set_isolation(to_engine_isolation(options.isolation));
+        ctx.commit_message = options.message;
+        ctx.commit_metadata = options.metadata;
         self.txn_ctx = Some(ctx);
         self.txn_branch_id = Some(core_branch_id);
+        self.txn_branch = Some(branch);
 
         Ok(Output::TxnBegun)
     }
@@ -217,10 +266,45 @@ impl Session {
     fn handle_commit(&mut self) -> Result<Output> {
         let mut ctx = self.txn_ctx.take().ok_or(Error::TransactionNotActive)?;
         self.txn_branch_id = None;
-
+        let branch = self
+            .txn_branch
+            .take()
+            .expect("txn_branch set when txn_ctx is Some");
+
+        let pending_vectors = std::mem::take(&mut self.pending_vectors);
+        self.savepoints.clear();
+
+        // The branch may have been frozen or protected since the writes were
+        // buffered; a transaction with writes then aborts instead of committing.
+        let has_writes = ctx.has_pending_operations()
+            || !ctx.json_writes().is_empty()
+            || !pending_vectors.is_empty();
+        if has_writes {
+            if let Err(e) = self
+                .executor
+                .check_write_allowed("TxnCommit", branch.as_str(), false)
+            {
+                self.db.end_transaction(ctx);
+                return Err(e);
+            }
+        }
+        let has_ttls = ctx.ttls().next().is_some();
         match self.db.commit_transaction(&mut ctx) {
             Ok(version) => {
                 self.db.end_transaction(ctx);
+                if has_ttls {
+                    self.db.start_expiry_reaper();
+                }
+                self.executor
+                    .primitives()
+                    .vector
+                    .apply_committed(pending_vectors)
+                    .map_err(|e| Error::Internal {
+                        reason: format!(
+                            "Committed at version {} but the vector index update failed: {}",
+                            version, e
+                        ),
+                    })?;
                 Ok(Output::TxnCommitted { version })
             }
             Err(e) => {
@@ -253,10 +337,40 @@ impl Session {
     fn handle_abort(&mut self) -> Result<Output> {
         let ctx = self.txn_ctx.take().ok_or(Error::TransactionNotActive)?;
         self.txn_branch_id = None;
+        self.txn_branch = None;
+        self.pending_vectors.clear();
+        self.savepoints.clear();
         self.db.end_transaction(ctx);
         Ok(Output::TxnAborted)
     }
 
+    fn handle_savepoint(&mut self, name: &str) -> Result<Output> {
+        let ctx = self.txn_ctx.as_mut().ok_or(Error::TransactionNotActive)?;
+        ctx.savepoint(name).map_err(Error::from)?;
+        self.savepoints
+            .push((name.to_string(), self.pending_vectors.len()));
+        Ok(Output::Unit)
+    }
+
+    fn handle_rollback_to(&mut self, name: &str) -> Result<Output> {
+        let ctx = self.txn_ctx.as_mut().ok_or(Error::TransactionNotActive)?;
+        ctx.rollback_to_savepoint(name).map_err(Error::from)?;
+        if let Some(index) = self.savepoints.iter().rposition(|(n, _)| n == name) {
+            self.pending_vectors.truncate(self.savepoints[index].1);
+            self.savepoints.truncate(index + 1);
+        }
+        Ok(Output::Unit)
+    }
+
+    fn handle_release(&mut self, name: &str) -> Result<Output> {
+        let ctx = self.txn_ctx.as_mut().ok_or(Error::TransactionNotActive)?;
+        ctx.release_savepoint(name).map_err(Error::from)?;
+        if let Some(index) = self.savepoints.iter().rposition(|(n, _)| n == name) {
+            self.savepoints.truncate(index);
+        }
+        Ok(Output::Unit)
+    }
+
     fn handle_txn_info(&self) -> Result<Output> {
         if let Some(ctx) = &self.txn_ctx {
             Ok(Output::TxnInfo(Some(crate::types::TransactionInfo {
@@ -300,7 +414,10 @@ impl Session {
             | Command::JsonGet { space, .. }
             | Command::JsonGetv { space, .. }
             | Command::JsonDelete { space, .. }
-            | Command::JsonList { space, .. } => {
+            | Command::JsonList { space, .. }
+            | Command::VectorUpsert { space, .. }
+            | Command::VectorBatchUpsert { space, .. }
+            | Command::VectorDelete { space, .. } => {
                 space.clone().unwrap_or_else(|| "default".to_string())
             }
             _ => "default".to_string(),
@@ -309,7 +426,14 @@ impl Session {
 
         // Temporarily take the context to create a Transaction
         let mut ctx = self.txn_ctx.take().unwrap();
-        let result = Self::dispatch_in_txn(&self.executor, &mut ctx, ns, cmd);
+        let result = Self::dispatch_in_txn(
+            &self.executor,
+            &mut ctx,
+            &mut self.pending_vectors,
+            ns,
+            space,
+            cmd,
+        );
         self.txn_ctx = Some(ctx);
 
         result
@@ -318,7 +442,9 @@ impl Session {
     fn dispatch_in_txn(
         executor: &Executor,
         ctx: &mut TransactionContext,
+        pending_vectors: &mut Vec<PendingVectorOp>,
         ns: Namespace,
+        space: String,
         cmd: Command,
     ) -> Result<Output> {
         // Read commands use ctx.get() / ctx.scan_prefix() directly so they
@@ -419,9 +545,20 @@ impl Session {
             }
 
             // === Write commands — use Transaction ===
-            Command::KvPut { key, value, .. } => {
+            Command::KvPut {
+                key,
+                value,
+                ttl_ms,
+                expires_at,
+                ..
+            } => {
+                let ttl = convert_result(resolve_ttl(ttl_ms, expires_at))?;
+                let full_key = Key::new_kv(ns.clone(), &key);
                 let mut txn = Transaction::new(ctx, ns);
                 let version = txn.kv_put(&key, value).map_err(Error::from)?;
+                if let Some(ttl) = ttl {
+                    ctx.set_ttl(&full_key, ttl).map_err(Error::from)?;
+                }
                 Ok(Output::Version(extract_version(&version)))
             }
             Command::KvDelete { key, .. } => {
@@ -468,9 +605,20 @@ impl Session {
             }
 
             // === State writes — use Transaction ===
-            Command::StateInit { cell, value, .. } => {
+            Command::StateInit {
+                cell,
+                value,
+                ttl_ms,
+                expires_at,
+                ..
+            } => {
+                let ttl = convert_result(resolve_ttl(ttl_ms, expires_at))?;
+                let full_key = Key::new_state(ns.clone(), &cell);
                 let mut txn = Transaction::new(ctx, ns);
                 let version = txn.state_init(&cell, value).map_err(Error::from)?;
+                if let Some(ttl) = ttl {
+                    ctx.set_ttl(&full_key, ttl).map_err(Error::from)?;
+                }
                 Ok(Output::Version(extract_version(&version)))
             }
             Command::StateCas {
@@ -487,7 +635,14 @@ impl Session {
                 let version = txn.state_cas(&cell, expected, value).map_err(Error::from)?;
                 Ok(Output::MaybeVersion(Some(extract_version(&version))))
             }
-            Command::StateSet { cell, value, .. } => {
+            Command::StateSet {
+                cell,
+                value,
+                ttl_ms,
+                expires_at,
+                ..
+            } => {
+                let ttl = convert_result(resolve_ttl(ttl_ms, expires_at))?;
                 // Construct key using the space-aware namespace (not StateCellExt
                 // which hardcodes the "default" space).
                 let full_key = Key::new_state(ns, &cell);
@@ -507,7 +662,8 @@ impl Session {
                     .map_err(|e| Error::Serialization {
                         reason: e.to_string(),
                     })?;
-                ctx.put(full_key, serialized).map_err(Error::from)?;
+                ctx.put_with_ttl(full_key, serialized, ttl)
+                    .map_err(Error::from)?;
                 Ok(Output::Version(extract_version(&new_version)))
             }
 
@@ -529,8 +685,95 @@ impl Session {
                 Ok(Output::Uint(if deleted { 1 } else { 0 }))
             }
 
+            // === Vector writes — staged, index updated after commit ===
+            Command::VectorUpsert {
+                collection,
+                key,
+                vector,
+                metadata,
+                ..
+            } => crate::handlers::vector::vector_upsert_in_txn(
+                executor.primitives(),
+                ctx,
+                pending_vectors,
+                space,
+                collection,
+                key,
+                vector,
+                metadata,
+            ),
+            Command::VectorBatchUpsert {
+                collection,
+                entries,
+                ..
+            } => crate::handlers::vector::vector_batch_upsert_in_txn(
+                executor.primitives(),
+                ctx,
+                pending_vectors,
+                space,
+                collection,
+                entries,
+            ),
+            Command::VectorDelete {
+                collection, key, ..
+            } => crate::handlers::vector::vector_delete_in_txn(
+                executor.primitives(),
+                ctx,
+                pending_vectors,
+                space,
+                collection,
+                key,
+            ),
+
+            // === Graph operations — via ctx on the transaction's branch ===
+            Command::GraphCreate {
+                graph,
+                cascade_policy,
+                ..
+            } => crate::handlers::graph::graph_create_in_txn(ctx, graph, cascade_policy),
+            Command::GraphDelete { graph, .. } => {
+                crate::handlers::graph::graph_delete_in_txn(ctx, graph)
+            }
+            Command::GraphGetMeta { graph, .. } => {
+                crate::handlers::graph::graph_get_meta_in_txn(ctx, graph)
+            }
+            Command::GraphAddNode {
+                graph,
+                node_id,
+                entity_ref,
+                properties,
+                ..
+            } => crate::handlers::graph::graph_add_node_in_txn(
+                ctx, graph, node_id, entity_ref, properties,
+            ),
+            Command::GraphGetNode { graph, node_id, .. } => {
+                crate::handlers::graph::graph_get_node_in_txn(ctx, graph, node_id)
+            }
+            Command::GraphRemoveNode { graph, node_id, .. } => {
+                crate::handlers::graph::graph_remove_node_in_txn(ctx, graph, node_id)
+            }
+            Command::GraphAddEdge {
+                graph,
+                src,
+                dst,
+                edge_type,
+                weight,
+                properties,
+                ..
+            } => crate::handlers::graph::graph_add_edge_in_txn(
+                ctx, graph, src, dst, edge_type, weight, properties,
+            ),
+            Command::GraphRemoveEdge {
+                graph,
+                src,
+                dst,
+                edge_type,
+                ..
+            } => crate::handlers::graph::graph_remove_edge_in_txn(ctx, graph, src, dst, edge_type),
+
             // Commands not directly mapped to TransactionOps — delegate to executor.
             // This includes batch operations, history, CAS, scan, incr, etc.
+            // Graph listing and traversal also read the committed store.
             other => executor.execute(other),
         }
     }
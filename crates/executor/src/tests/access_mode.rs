@@ -2,11 +2,11 @@
 
 use std::sync::Arc;
 
-use strata_engine::Database;
+use strata_engine::{Database, MergeStrategy};
 use strata_security::AccessMode;
 
-use crate::types::DistanceMetric;
-use crate::{Command, Error, Executor, Session, Strata, Value};
+use crate::types::{BranchId, BranchStatus, DistanceMetric};
+use crate::{Command, Error, Executor, Output, Session, Strata, Value};
 
 // =============================================================================
 // Helpers
@@ -34,6 +34,8 @@ fn test_read_only_blocks_kv_put() {
         space: None,
         key: "k".into(),
         value: Value::Int(1),
+        ttl_ms: None,
+        expires_at: None,
     });
 
     match result {
@@ -72,6 +74,8 @@ fn test_read_only_blocks_all_writes() {
             space: None,
             key: "k".into(),
             value: Value::Int(1),
+            ttl_ms: None,
+            expires_at: None,
         },
         Command::KvDelete {
             branch: None,
@@ -102,6 +106,8 @@ fn test_read_only_blocks_all_writes() {
             space: None,
             cell: "c".into(),
             value: Value::Int(1),
+            ttl_ms: None,
+            expires_at: None,
         },
         Command::StateCas {
             branch: None,
@@ -115,6 +121,8 @@ fn test_read_only_blocks_all_writes() {
             space: None,
             cell: "c".into(),
             value: Value::Int(1),
+            ttl_ms: None,
+            expires_at: None,
         },
         Command::StateDelete {
             branch: None,
@@ -154,14 +162,45 @@ fn test_read_only_blocks_all_writes() {
         Command::BranchDelete {
             branch: crate::types::BranchId::from("x"),
         },
+        Command::BranchSetStatus {
+            branch: crate::types::BranchId::from("x"),
+            status: crate::types::BranchStatus::Frozen,
+        },
+        Command::BranchSetRetention {
+            branch: crate::types::BranchId::from("x"),
+            policy: strata_engine::RetentionPolicy::keep_last(1),
+        },
         Command::BranchFork {
             source: "default".into(),
             destination: "fork".into(),
         },
+        Command::BranchForkAt {
+            source: "default".into(),
+            destination: "fork".into(),
+            as_of: 0,
+        },
+        Command::BranchRevert {
+            branch: "default".into(),
+            as_of: 0,
+        },
         Command::BranchMerge {
             source: "a".into(),
             target: "b".into(),
             strategy: strata_engine::MergeStrategy::LastWriterWins,
+            primitives: Default::default(),
+        },
+        Command::BranchMergeResolve {
+            source: "a".into(),
+            target: "b".into(),
+            primitives: Default::default(),
+            resolutions: vec![],
+        },
+        Command::BranchCherryPick {
+            source: "a".into(),
+            target: "b".into(),
+            commits: vec![1],
+            strategy: strata_engine::MergeStrategy::Strict,
+            primitives: Default::default(),
         },
         Command::ConfigSetAutoEmbed { enabled: true },
         Command::TxnBegin {
@@ -170,6 +209,9 @@ fn test_read_only_blocks_all_writes() {
         },
         Command::TxnCommit,
         Command::TxnRollback,
+        Command::TxnSavepoint { name: "sp".into() },
+        Command::TxnRollbackTo { name: "sp".into() },
+        Command::TxnRelease { name: "sp".into() },
         Command::RetentionApply { branch: None },
         Command::Flush,
         Command::Compact,
@@ -283,6 +325,9 @@ fn test_read_only_allows_all_reads() {
         Command::BranchExists {
             branch: crate::types::BranchId::default(),
         },
+        Command::BranchGetRetention {
+            branch: crate::types::BranchId::default(),
+        },
         Command::Ping,
         Command::Info,
         Command::TxnInfo,
@@ -303,6 +348,12 @@ fn test_read_only_allows_all_reads() {
         Command::BranchDiff {
             branch_a: "default".into(),
             branch_b: "default".into(),
+            options: Default::default(),
+        },
+        Command::BranchLog {
+            branch: "default".into(),
+            limit: None,
+            before: None,
         },
         Command::ConfigGet,
         Command::AutoEmbedStatus,
@@ -390,6 +441,8 @@ fn test_is_write_classification() {
             space: None,
             key: "".into(),
             value: Value::Null,
+            ttl_ms: None,
+            expires_at: None,
         },
         Command::KvDelete {
             branch: None,
@@ -420,6 +473,8 @@ fn test_is_write_classification() {
             space: None,
             cell: "".into(),
             value: Value::Null,
+            ttl_ms: None,
+            expires_at: None,
         },
         Command::StateCas {
             branch: None,
@@ -433,6 +488,8 @@ fn test_is_write_classification() {
             space: None,
             cell: "".into(),
             value: Value::Null,
+            ttl_ms: None,
+            expires_at: None,
         },
         Command::StateDelete {
             branch: None,
@@ -472,12 +529,23 @@ fn test_is_write_classification() {
         Command::BranchDelete {
             branch: crate::types::BranchId::default(),
         },
+        Command::BranchSetStatus {
+            branch: crate::types::BranchId::default(),
+            status: crate::types::BranchStatus::Protected,
+        },
+        Command::BranchSetRetention {
+            branch: crate::types::BranchId::default(),
+            policy: strata_engine::RetentionPolicy::keep_last(1),
+        },
         Command::TxnBegin {
             branch: None,
             options: None,
         },
         Command::TxnCommit,
         Command::TxnRollback,
+        Command::TxnSavepoint { name: "sp".into() },
+        Command::TxnRollbackTo { name: "sp".into() },
+        Command::TxnRelease { name: "sp".into() },
         Command::RetentionApply { branch: None },
         Command::Flush,
         Command::Compact,
@@ -490,10 +558,33 @@ fn test_is_write_classification() {
             source: "".into(),
             destination: "".into(),
         },
+        Command::BranchForkAt {
+            source: "".into(),
+            destination: "".into(),
+            as_of: 0,
+        },
+        Command::BranchRevert {
+            branch: "".into(),
+            as_of: 0,
+        },
         Command::BranchMerge {
             source: "".into(),
             target: "".into(),
             strategy: strata_engine::MergeStrategy::LastWriterWins,
+            primitives: Default::default(),
+        },
+        Command::BranchMergeResolve {
+            source: "a".into(),
+            target: "b".into(),
+            primitives: Default::default(),
+            resolutions: vec![],
+        },
+        Command::BranchCherryPick {
+            source: "a".into(),
+            target: "b".into(),
+            commits: vec![1],
+            strategy: strata_engine::MergeStrategy::Strict,
+            primitives: Default::default(),
         },
         Command::ConfigSetAutoEmbed { enabled: false },
     ];
@@ -617,6 +708,9 @@ fn test_is_write_classification() {
         Command::BranchExists {
             branch: crate::types::BranchId::default(),
         },
+        Command::BranchGetRetention {
+            branch: crate::types::BranchId::default(),
+        },
         Command::Ping,
         Command::Info,
         Command::TxnInfo,
@@ -640,6 +734,12 @@ fn test_is_write_classification() {
         Command::BranchDiff {
             branch_a: "".into(),
             branch_b: "".into(),
+            options: Default::default(),
+        },
+        Command::BranchLog {
+            branch: "".into(),
+            limit: None,
+            before: None,
         },
         Command::ConfigGet,
         Command::AutoEmbedStatus,
@@ -664,3 +764,212 @@ fn test_open_with_defaults_is_read_write() {
     let result = strata.kv_put("k", "v");
     assert!(result.is_ok());
 }
+
+// =============================================================================
+// Branch protection
+// =============================================================================
+
+fn kv_put_on(branch: &str) -> Command {
+    Command::KvPut {
+        branch: Some(BranchId::from(branch)),
+        space: None,
+        key: "k".into(),
+        value: Value::Int(1),
+        ttl_ms: None,
+        expires_at: None,
+    }
+}
+
+#[test]
+fn test_protected_branch_rejects_direct_writes() {
+    let strata = Strata::cache().unwrap();
+    strata.branches().create("main").unwrap();
+    strata.branches().fork("main", "feature").unwrap();
+    strata
+        .branch_set_status("main", BranchStatus::Protected)
+        .unwrap();
+
+    match strata.executor().execute(kv_put_on("main")) {
+        Err(Error::BranchWriteDenied {
+            command,
+            branch,
+            status,
+        }) => {
+            assert_eq!(command, "KvPut");
+            assert_eq!(branch, "main");
+            assert_eq!(status, BranchStatus::Protected);
+        }
+        other => panic!("expected BranchWriteDenied, got {:?}", other),
+    }
+
+    // Other branches are unaffected, and merges into main still land
+    strata.executor().execute(kv_put_on("feature")).unwrap();
+    strata
+        .branches()
+        .merge("feature", "main", MergeStrategy::Strict)
+        .unwrap();
+
+    let info = strata.branch_get("main").unwrap().unwrap();
+    assert_eq!(info.info.status, BranchStatus::Protected);
+}
+
+#[test]
+fn test_frozen_branch_rejects_all_writes() {
+    let strata = Strata::cache().unwrap();
+    strata.branches().create("main").unwrap();
+    strata.branches().fork("main", "feature").unwrap();
+    strata.executor().execute(kv_put_on("feature")).unwrap();
+    strata
+        .branch_set_status("main", BranchStatus::Frozen)
+        .unwrap();
+
+    assert!(matches!(
+        strata.executor().execute(kv_put_on("main")),
+        Err(Error::BranchWriteDenied { .. })
+    ));
+    assert!(matches!(
+        strata
+            .branches()
+            .merge("feature", "main", MergeStrategy::Strict),
+        Err(Error::BranchWriteDenied { .. })
+    ));
+    assert!(matches!(
+        strata.branches().delete("main"),
+        Err(Error::BranchWriteDenied { .. })
+    ));
+    assert!(matches!(
+        strata.executor().execute(Command::BranchSetRetention {
+            branch: BranchId::from("main"),
+            policy: strata_engine::RetentionPolicy::keep_last(1),
+        }),
+        Err(Error::BranchWriteDenied { .. })
+    ));
+    assert!(matches!(
+        strata.executor().execute(Command::RetentionApply {
+            branch: Some(BranchId::from("main")),
+        }),
+        Err(Error::BranchWriteDenied { .. })
+    ));
+
+    // Reactivating lifts the guard
+    strata
+        .branch_set_status("main", BranchStatus::Active)
+        .unwrap();
+    strata.executor().execute(kv_put_on("main")).unwrap();
+}
+
+fn kv_put_unscoped() -> Command {
+    Command::KvPut {
+        branch: None,
+        space: None,
+        key: "k".into(),
+        value: Value::Int(1),
+        ttl_ms: None,
+        expires_at: None,
+    }
+}
+
+fn txn_begin_on(branch: &str) -> Command {
+    Command::TxnBegin {
+        branch: Some(BranchId::from(branch)),
+        options: None,
+    }
+}
+
+#[test]
+fn test_frozen_branch_rejects_writes_in_transaction() {
+    let strata = Strata::cache().unwrap();
+    strata.branches().create("main").unwrap();
+
+    let mut session = Session::new(strata.database());
+    session.execute(txn_begin_on("main")).unwrap();
+    session.execute(kv_put_unscoped()).unwrap();
+    strata
+        .branch_set_status("main", BranchStatus::Frozen)
+        .unwrap();
+
+    // The write names no branch but lands on the transaction's branch
+    assert!(matches!(
+        session.execute(kv_put_unscoped()),
+        Err(Error::BranchWriteDenied { .. })
+    ));
+    match session.execute(Command::TxnCommit) {
+        Err(Error::BranchWriteDenied {
+            command, branch, ..
+        }) => {
+            assert_eq!(command, "TxnCommit");
+            assert_eq!(branch, "main");
+        }
+        other => panic!("expected BranchWriteDenied, got {:?}", other),
+    }
+    assert!(!session.in_transaction());
+    let read = strata.executor().execute(Command::KvGet {
+        branch: Some(BranchId::from("main")),
+        space: None,
+        key: "k".into(),
+        as_of: None,
+    });
+    assert!(matches!(read, Ok(Output::MaybeVersioned(None))));
+}
+
+#[test]
+fn test_frozen_branch_rejects_txn_begin() {
+    let strata = Strata::cache().unwrap();
+    strata.branches().create("main").unwrap();
+    strata
+        .branch_set_status("main", BranchStatus::Frozen)
+        .unwrap();
+
+    let mut session = Session::new(strata.database());
+    assert!(matches!(
+        session.execute(txn_begin_on("main")),
+        Err(Error::BranchWriteDenied { .. })
+    ));
+    assert!(!session.in_transaction());
+}
+
+#[test]
+fn test_protected_branch_rejects_unscoped_writes_in_transaction() {
+    let strata = Strata::cache().unwrap();
+    strata.branches().create("main").unwrap();
+    strata
+        .branch_set_status("main", BranchStatus::Protected)
+        .unwrap();
+
+    let mut session = Session::new(strata.database());
+    session.execute(txn_begin_on("main")).unwrap();
+    assert!(matches!(
+        session.execute(kv_put_unscoped()),
+        Err(Error::BranchWriteDenied { .. })
+    ));
+}
+
+#[test]
+fn test_frozen_default_branch_allows_writes_in_transaction_on_other_branch() {
+    let strata = Strata::cache().unwrap();
+    strata.branches().create("main").unwrap();
+    strata
+        .branch_set_status("default", BranchStatus::Frozen)
+        .unwrap();
+
+    let mut session = Session::new(strata.database());
+    session.execute(txn_begin_on("main")).unwrap();
+    session.execute(kv_put_unscoped()).unwrap();
+    session.execute(Command::TxnCommit).unwrap();
+}
+
+#[test]
+fn test_branch_list_filters_by_status() {
+    let strata = Strata::cache().unwrap();
+    strata.branches().create("main").unwrap();
+    strata.branches().create("scratch").unwrap();
+    strata
+        .branch_set_status("main", BranchStatus::Frozen)
+        .unwrap();
+
+    let frozen = strata
+        .branch_list(Some(BranchStatus::Frozen), None, None)
+        .unwrap();
+    assert_eq!(frozen.len(), 1);
+    assert_eq!(frozen[0].info.id.as_str(), "main");
+}
@@ -93,6 +93,8 @@ fn test_kv_write_read_determinism() {
             space: None,
             key: "test-key".to_string(),
             value: Value::String("test-value".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -129,6 +131,8 @@ fn test_state_write_read_determinism() {
             space: None,
             cell: "counter".to_string(),
             value: Value::Int(42),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -197,6 +201,8 @@ fn test_sequential_writes_determinism() {
             space: None,
             key: format!("key-{}", i),
             value: Value::Int(i),
+            ttl_ms: None,
+            expires_at: None,
         });
 
         match result {
@@ -239,6 +245,8 @@ fn test_kv_list_determinism() {
                 space: None,
                 key: format!("user:{}", i),
                 value: Value::Int(i),
+                ttl_ms: None,
+                expires_at: None,
             })
             .unwrap();
     }
@@ -59,18 +59,24 @@ fn test_execute_many_preserves_order() {
             space: None,
             key: "key1".to_string(),
             value: Value::Int(1),
+            ttl_ms: None,
+            expires_at: None,
         },
         Command::KvPut {
             branch: Some(BranchId::from("default")),
             space: None,
             key: "key2".to_string(),
             value: Value::Int(2),
+            ttl_ms: None,
+            expires_at: None,
         },
         Command::KvPut {
             branch: Some(BranchId::from("default")),
             space: None,
             key: "key3".to_string(),
             value: Value::Int(3),
+            ttl_ms: None,
+            expires_at: None,
         },
     ]);
 
@@ -159,6 +165,8 @@ fn test_execute_many_mixed_operations() {
             space: None,
             key: "counter".to_string(),
             value: Value::Int(10),
+            ttl_ms: None,
+            expires_at: None,
         },
         // Get it
         Command::KvGet {
@@ -236,6 +244,8 @@ fn test_execute_many_large_batch() {
             space: None,
             key: format!("key_{}", i),
             value: Value::Int(i),
+            ttl_ms: None,
+            expires_at: None,
         })
         .collect();
 
@@ -43,6 +43,8 @@ fn test_kv_put_get_parity() {
         space: None,
         key: "key2".to_string(),
         value: Value::String("executor".into()),
+        ttl_ms: None,
+        expires_at: None,
     });
 
     // Both should succeed with a Version output
@@ -287,6 +289,8 @@ fn test_state_set_get_parity() {
         space: None,
         cell: "cell1".to_string(),
         value: Value::Int(100),
+        ttl_ms: None,
+        expires_at: None,
     });
 
     let counter1 = match result {
@@ -535,6 +539,8 @@ fn test_branch_isolation_parity() {
             space: None,
             key: "shared-key".to_string(),
             value: Value::String("from-a".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -545,6 +551,8 @@ fn test_branch_isolation_parity() {
             space: None,
             key: "shared-key".to_string(),
             value: Value::String("from-b".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
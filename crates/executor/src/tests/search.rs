@@ -52,6 +52,8 @@ fn test_search_returns_empty_for_kv_primitive() {
             space: None,
             key: "greeting".to_string(),
             value: Value::String("hello world".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -61,6 +63,8 @@ fn test_search_returns_empty_for_kv_primitive() {
             space: None,
             key: "farewell".to_string(),
             value: Value::String("goodbye world".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -99,6 +103,8 @@ fn test_search_with_primitive_filter() {
             space: None,
             key: "test_key".to_string(),
             value: Value::String("searchable data".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -62,6 +62,13 @@ fn test_command_compact() {
     test_command_round_trip(Command::Compact);
 }
 
+#[test]
+fn test_command_backup() {
+    test_command_round_trip(Command::Backup {
+        path: "/backups/monday".to_string(),
+    });
+}
+
 // =============================================================================
 // KV Command Tests (4 MVP)
 // =============================================================================
@@ -73,7 +80,34 @@ fn test_command_kv_put() {
         space: None,
         key: "test-key".to_string(),
         value: Value::String("test-value".to_string()),
+        ttl_ms: None,
+        expires_at: None,
+    });
+}
+
+#[test]
+fn test_command_kv_put_with_ttl() {
+    test_command_round_trip(Command::KvPut {
+        branch: None,
+        space: None,
+        key: "lease".to_string(),
+        value: Value::Int(1),
+        ttl_ms: Some(30_000),
+        expires_at: None,
     });
+
+    // Unset TTL fields are omitted, so older clients see the same JSON
+    let json = serde_json::to_string(&Command::KvPut {
+        branch: None,
+        space: None,
+        key: "k".to_string(),
+        value: Value::Int(1),
+        ttl_ms: None,
+        expires_at: None,
+    })
+    .unwrap();
+    assert!(!json.contains("ttl_ms"));
+    assert!(!json.contains("expires_at"));
 }
 
 #[test]
@@ -192,6 +226,8 @@ fn test_command_state_set() {
         space: None,
         cell: "counter".to_string(),
         value: Value::Int(42),
+        ttl_ms: None,
+        expires_at: None,
     });
 }
 
@@ -267,6 +303,32 @@ fn test_command_branch_create() {
     });
 }
 
+#[test]
+fn test_command_branch_set_status() {
+    test_command_round_trip(Command::BranchSetStatus {
+        branch: BranchId::from("main"),
+        status: BranchStatus::Frozen,
+    });
+}
+
+#[test]
+fn test_command_branch_set_retention() {
+    test_command_round_trip(Command::BranchSetRetention {
+        branch: BranchId::from("main"),
+        policy: strata_engine::RetentionPolicy::composite(
+            strata_engine::RetentionPolicy::keep_last(10),
+        )
+        .with_override(
+            strata_core::PrimitiveType::Event,
+            strata_engine::RetentionPolicy::keep_for(std::time::Duration::from_secs(3600)),
+        )
+        .build(),
+    });
+    test_command_round_trip(Command::BranchGetRetention {
+        branch: BranchId::from("main"),
+    });
+}
+
 #[test]
 fn test_command_branch_list() {
     test_command_round_trip(Command::BranchList {
@@ -280,11 +342,27 @@ fn test_command_branch_list() {
 // Transaction Command Tests
 // =============================================================================
 
+#[test]
+fn test_command_txn_begin_with_message() {
+    test_command_round_trip(Command::TxnBegin {
+        branch: None,
+        options: Some(TxnOptions {
+            read_only: false,
+            isolation: TxnIsolation::Serializable,
+            message: Some("apply tool call".to_string()),
+            metadata: [("tool".to_string(), "search".to_string())].into(),
+        }),
+    });
+}
+
 #[test]
 fn test_command_txn_begin() {
     test_command_round_trip(Command::TxnBegin {
         branch: None,
-        options: Some(TxnOptions { read_only: true }),
+        options: Some(TxnOptions {
+            read_only: true,
+            ..Default::default()
+        }),
     });
 }
 
@@ -298,6 +376,19 @@ fn test_command_txn_rollback() {
     test_command_round_trip(Command::TxnRollback);
 }
 
+#[test]
+fn test_command_txn_savepoints() {
+    test_command_round_trip(Command::TxnSavepoint {
+        name: "tool-call".to_string(),
+    });
+    test_command_round_trip(Command::TxnRollbackTo {
+        name: "tool-call".to_string(),
+    });
+    test_command_round_trip(Command::TxnRelease {
+        name: "tool-call".to_string(),
+    });
+}
+
 // =============================================================================
 // Output Tests
 // =============================================================================
@@ -474,6 +565,8 @@ fn test_command_with_complex_value() {
         space: None,
         key: "complex".to_string(),
         value: complex_value,
+        ttl_ms: None,
+        expires_at: None,
     });
 }
 
@@ -484,6 +577,8 @@ fn test_command_with_bytes_value() {
         space: None,
         key: "binary".to_string(),
         value: Value::Bytes(vec![0, 1, 2, 255, 254, 253]),
+        ttl_ms: None,
+        expires_at: None,
     });
 }
 
@@ -500,6 +595,8 @@ fn test_command_with_branch_none_round_trip() {
         space: None,
         key: "test".to_string(),
         value: Value::Int(42),
+        ttl_ms: None,
+        expires_at: None,
     };
     let json = serde_json::to_string(&cmd).unwrap();
     assert!(
@@ -592,11 +689,67 @@ fn test_command_branch_fork() {
     });
 }
 
+#[test]
+fn test_command_branch_fork_at() {
+    test_command_round_trip(Command::BranchForkAt {
+        source: "main".to_string(),
+        destination: "snapshot".to_string(),
+        as_of: 1_700_000_000_000_000,
+    });
+}
+
+#[test]
+fn test_command_branch_revert() {
+    test_command_round_trip(Command::BranchRevert {
+        branch: "main".to_string(),
+        as_of: 1_700_000_000_000_000,
+    });
+}
+
+#[test]
+fn test_command_branch_log() {
+    test_command_round_trip(Command::BranchLog {
+        branch: "main".to_string(),
+        limit: Some(10),
+        before: Some(40),
+    });
+}
+
+#[test]
+fn test_command_branch_cherry_pick() {
+    test_command_round_trip(Command::BranchCherryPick {
+        source: "experiment".to_string(),
+        target: "main".to_string(),
+        commits: vec![12, 15],
+        strategy: strata_engine::MergeStrategy::LastWriterWins,
+        primitives: Default::default(),
+    });
+}
+
 #[test]
 fn test_command_branch_diff() {
     test_command_round_trip(Command::BranchDiff {
         branch_a: "main".to_string(),
         branch_b: "experiment".to_string(),
+        options: Default::default(),
+    });
+}
+
+#[test]
+fn test_command_branch_diff_with_options() {
+    test_command_round_trip(Command::BranchDiff {
+        branch_a: "main".to_string(),
+        branch_b: "experiment".to_string(),
+        options: strata_engine::DiffOptions {
+            primitives: vec![strata_core::PrimitiveType::Json],
+            spaces: vec!["default".to_string()],
+            key_prefix: Some("user:".to_string()),
+            limit: Some(100),
+            cursor: Some("0011".to_string()),
+            summary_only: false,
+            as_of_a: Some(1_700_000_000_000_000),
+            as_of_b: None,
+        },
     });
 }
 
@@ -606,6 +759,7 @@ fn test_command_branch_merge_lww() {
         source: "experiment".to_string(),
         target: "main".to_string(),
         strategy: MergeStrategy::LastWriterWins,
+        primitives: Default::default(),
     });
 }
 
@@ -615,6 +769,46 @@ fn test_command_branch_merge_strict() {
         source: "experiment".to_string(),
         target: "main".to_string(),
         strategy: MergeStrategy::Strict,
+        primitives: Default::default(),
+    });
+}
+
+#[test]
+fn test_command_branch_merge_per_primitive() {
+    test_command_round_trip(Command::BranchMerge {
+        source: "experiment".to_string(),
+        target: "main".to_string(),
+        strategy: MergeStrategy::Strict,
+        primitives: strata_engine::PrimitiveMergeStrategies {
+            json: strata_engine::JsonMergeStrategy::Path,
+            event: strata_engine::EventMergeStrategy::Append,
+            state: strata_engine::StateMergeStrategy::CounterVersion,
+        },
+    });
+}
+
+#[test]
+fn test_command_branch_merge_resolve() {
+    use strata_engine::{ConflictResolution, MergeResolution};
+
+    test_command_round_trip(Command::BranchMergeResolve {
+        source: "experiment".to_string(),
+        target: "main".to_string(),
+        primitives: Default::default(),
+        resolutions: vec![
+            MergeResolution {
+                space: "default".to_string(),
+                primitive: strata_core::PrimitiveType::Kv,
+                raw_key: b"a".to_vec(),
+                resolution: ConflictResolution::TakeSource,
+            },
+            MergeResolution {
+                space: "default".to_string(),
+                primitive: strata_core::PrimitiveType::Json,
+                raw_key: b"doc".to_vec(),
+                resolution: ConflictResolution::Custom(Value::Int(1)),
+            },
+        ],
     });
 }
 
@@ -650,6 +844,85 @@ fn test_output_branch_forked() {
         destination: "experiment".to_string(),
         keys_copied: 42,
         spaces_copied: 2,
+        fork_version: 17,
+    }));
+}
+
+#[test]
+fn test_output_branch_reverted() {
+    test_output_round_trip(Output::BranchReverted(
+        strata_engine::branch_ops::RevertInfo {
+            branch: "main".to_string(),
+            as_of: 1_700_000_000_000_000,
+            keys_restored: 3,
+            keys_deleted: 1,
+        },
+    ));
+}
+
+#[test]
+fn test_output_branch_log() {
+    test_output_round_trip(Output::BranchLog(vec![
+        strata_engine::branch_ops::CommitInfo {
+            version: 12,
+            txn_id: 7,
+            timestamp: 1_700_000_000_000_000,
+            message: Some("apply tool call".to_string()),
+            metadata: [("agent".to_string(), "planner".to_string())].into(),
+            keys_written: 2,
+            keys_deleted: 1,
+            touched: vec![strata_engine::branch_ops::TouchedKeys {
+                primitive: strata_core::PrimitiveType::Kv,
+                keys_written: 2,
+                keys_deleted: 1,
+                keys: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            }],
+        },
+    ]));
+}
+
+#[test]
+fn test_output_branch_cherry_picked() {
+    test_output_round_trip(Output::BranchCherryPicked(
+        strata_engine::branch_ops::CherryPickInfo {
+            source: "experiment".to_string(),
+            target: "main".to_string(),
+            commits: vec![12, 15],
+            keys_applied: 3,
+            keys_deleted: 0,
+            events_appended: 0,
+            conflicts: vec![],
+        },
+    ));
+}
+
+#[test]
+fn test_output_retention_preview() {
+    use strata_engine::{
+        PrimitiveRetention, PrunableVersion, RetentionHorizon, RetentionPolicy, RetentionPreview,
+    };
+    test_output_round_trip(Output::RetentionPreview(RetentionPreview {
+        branch: "main".to_string(),
+        policy: RetentionPolicy::keep_last(1),
+        primitives: vec![PrimitiveRetention {
+            primitive: strata_core::PrimitiveType::Kv,
+            keys: 1,
+            versions: 1,
+            bytes: 64,
+        }],
+        versions: vec![PrunableVersion {
+            primitive: strata_core::PrimitiveType::Kv,
+            space: "default".to_string(),
+            key: "k".to_string(),
+            version: 3,
+            timestamp: 1_000,
+            bytes: 64,
+            tombstone: false,
+        }],
+        horizon: Some(RetentionHorizon {
+            version: 4,
+            timestamp: 2_000,
+        }),
     }));
 }
 
@@ -667,7 +940,8 @@ fn test_output_branch_diff() {
                 primitive: strata_core::PrimitiveType::Kv,
                 space: "default".to_string(),
                 value_a: None,
-                value_b: Some("hello".to_string()),
+                value_b: Some(Value::String("hello".to_string())),
+                patches: vec![],
             }],
             removed: vec![],
             modified: vec![],
@@ -679,6 +953,7 @@ fn test_output_branch_diff() {
             spaces_only_in_a: vec![],
             spaces_only_in_b: vec![],
         },
+        next_cursor: Some("0011".to_string()),
     }));
 }
 
@@ -688,8 +963,14 @@ fn test_output_branch_merged() {
         source: "experiment".to_string(),
         target: "main".to_string(),
         keys_applied: 5,
+        keys_deleted: 1,
+        events_appended: 2,
         conflicts: vec![],
         spaces_merged: 1,
+        merge_base: Some(strata_engine::branch_ops::MergeBase {
+            branch: "main".to_string(),
+            version: 17,
+        }),
     }));
 }
 
@@ -702,6 +983,11 @@ fn test_output_config() {
         embed_batch_size: None,
         bm25_k1: None,
         bm25_b: None,
+        encryption_key_file: None,
+        compression: "none".to_string(),
+        checkpoint: None,
+        wal_archive_dir: None,
+        memory_budget_mb: None,
     }));
 }
 
@@ -712,6 +998,11 @@ fn test_output_durability_counters() {
         sync_calls: 10,
         bytes_written: 4096,
         sync_nanos: 500_000,
+        checkpoints: 2,
+        compactions: 1,
+        wal_segments_removed: 3,
+        wal_bytes_reclaimed: 1 << 20,
+        wal_segments_archived: 2,
     }));
     // Also test default (cache databases)
     test_output_round_trip(Output::DurabilityCounters(
@@ -1,7 +1,7 @@
 //! Session tests: verify transactional session lifecycle and routing.
 
 use crate::Value;
-use crate::{Command, Error, Output, Session};
+use crate::{Command, Error, Output, Session, TxnIsolation, TxnOptions};
 use strata_engine::Database;
 
 /// Create a test session with a cache in-memory database.
@@ -34,6 +34,55 @@ fn test_begin_commit_lifecycle() {
     assert!(!session.in_transaction());
 }
 
+#[test]
+fn test_commit_message_recorded_in_branch_log() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let mut session = Session::new(Database::open(temp.path()).unwrap());
+    session
+        .execute(Command::BranchCreate {
+            branch_id: Some("default".into()),
+            metadata: None,
+        })
+        .unwrap();
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: Some(TxnOptions {
+                message: Some("apply tool call".into()),
+                metadata: [("tool".to_string(), "search".to_string())].into(),
+                ..Default::default()
+            }),
+        })
+        .unwrap();
+    session
+        .execute(Command::KvPut {
+            branch: None,
+            space: None,
+            key: "k".to_string(),
+            value: Value::Int(1),
+            ttl_ms: None,
+            expires_at: None,
+        })
+        .unwrap();
+    session.execute(Command::TxnCommit).unwrap();
+
+    let commits = match session
+        .execute(Command::BranchLog {
+            branch: "default".into(),
+            limit: Some(1),
+            before: None,
+        })
+        .unwrap()
+    {
+        Output::BranchLog(commits) => commits,
+        other => panic!("Expected BranchLog, got {:?}", other),
+    };
+    assert_eq!(commits[0].message.as_deref(), Some("apply tool call"));
+    assert_eq!(commits[0].metadata["tool"], "search");
+    assert_eq!(commits[0].touched[0].keys, vec!["k"]);
+}
+
 #[test]
 fn test_begin_abort_lifecycle() {
     let mut session = create_test_session();
@@ -167,6 +216,8 @@ fn test_ryw_kv_put_get_inside_txn() {
             space: None,
             key: "ryw_key".to_string(),
             value: Value::String("written_in_txn".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -215,6 +266,8 @@ fn test_ryw_kv_get_inside_txn() {
             space: None,
             key: "exists_key".to_string(),
             value: Value::Int(42),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -266,6 +319,8 @@ fn test_drop_with_active_txn_does_not_panic() {
             space: None,
             key: "abandoned".to_string(),
             value: Value::Bool(true),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -319,6 +374,8 @@ fn test_data_commands_without_txn_delegate() {
         space: None,
         key: "no_txn_key".to_string(),
         value: Value::Int(99),
+        ttl_ms: None,
+        expires_at: None,
     });
     assert!(result.is_ok(), "KvPut should succeed without txn");
 
@@ -380,6 +437,8 @@ fn test_multiple_txn_cycles() {
                 space: None,
                 key: format!("cycle_{}", i),
                 value: Value::Int(i as i64),
+                ttl_ms: None,
+                expires_at: None,
             })
             .unwrap();
 
@@ -438,8 +497,458 @@ fn test_state_init_in_txn() {
         space: None,
         cell: "counter".to_string(),
         value: Value::Int(0),
+        ttl_ms: None,
+        expires_at: None,
     });
     assert!(result.is_ok(), "StateInit should succeed in txn");
 
     session.execute(Command::TxnCommit).unwrap();
 }
+
+// =============================================================================
+// TTL In Transaction
+// =============================================================================
+
+#[test]
+fn test_kv_put_with_ttl_in_txn() {
+    let mut session = create_test_session();
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+
+    // Setting both forms of TTL is rejected
+    let result = session.execute(Command::KvPut {
+        branch: None,
+        space: None,
+        key: "lease".to_string(),
+        value: Value::Int(1),
+        ttl_ms: Some(50),
+        expires_at: Some(u64::MAX),
+    });
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+    session
+        .execute(Command::KvPut {
+            branch: None,
+            space: None,
+            key: "lease".to_string(),
+            value: Value::Int(1),
+            ttl_ms: Some(50),
+            expires_at: None,
+        })
+        .unwrap();
+    session.execute(Command::TxnCommit).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(60));
+    let result = session
+        .execute(Command::KvGet {
+            branch: None,
+            space: None,
+            key: "lease".to_string(),
+            as_of: None,
+        })
+        .unwrap();
+    assert!(matches!(result, Output::MaybeVersioned(None)));
+}
+
+// =============================================================================
+// Vector and Graph Operations In Transaction
+// =============================================================================
+
+fn create_collection(session: &mut Session) {
+    session
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "emb".to_string(),
+            dimension: 3,
+            metric: crate::types::DistanceMetric::Cosine,
+        })
+        .unwrap();
+}
+
+fn upsert(key: &str, vector: Vec<f32>) -> Command {
+    Command::VectorUpsert {
+        branch: None,
+        space: None,
+        collection: "emb".to_string(),
+        key: key.to_string(),
+        vector,
+        metadata: None,
+    }
+}
+
+fn search_keys(session: &mut Session) -> Vec<String> {
+    match session
+        .execute(Command::VectorSearch {
+            branch: None,
+            space: None,
+            collection: "emb".to_string(),
+            query: vec![1.0, 0.0, 0.0],
+            k: 10,
+            filter: None,
+            metric: None,
+            as_of: None,
+        })
+        .unwrap()
+    {
+        Output::VectorMatches(matches) => matches.into_iter().map(|m| m.key).collect(),
+        other => panic!("Expected VectorMatches, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_vector_upsert_rolled_back() {
+    let mut session = create_test_session();
+    create_collection(&mut session);
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+    session.execute(upsert("v1", vec![1.0, 0.0, 0.0])).unwrap();
+    // The index only changes on commit
+    assert!(search_keys(&mut session).is_empty());
+    session.execute(Command::TxnRollback).unwrap();
+
+    assert!(search_keys(&mut session).is_empty());
+    let result = session
+        .execute(Command::VectorGet {
+            branch: None,
+            space: None,
+            collection: "emb".to_string(),
+            key: "v1".to_string(),
+            as_of: None,
+        })
+        .unwrap();
+    assert!(matches!(result, Output::VectorData(None)));
+}
+
+#[test]
+fn test_document_embedding_and_edge_commit_together() {
+    let mut session = create_test_session();
+    create_collection(&mut session);
+    session.execute(upsert("old", vec![0.0, 1.0, 0.0])).unwrap();
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+    session
+        .execute(Command::KvPut {
+            branch: None,
+            space: None,
+            key: "doc1".to_string(),
+            value: Value::String("hello".into()),
+            ttl_ms: None,
+            expires_at: None,
+        })
+        .unwrap();
+    session
+        .execute(upsert("doc1", vec![1.0, 0.0, 0.0]))
+        .unwrap();
+    let deleted = session
+        .execute(Command::VectorDelete {
+            branch: None,
+            space: None,
+            collection: "emb".to_string(),
+            key: "old".to_string(),
+        })
+        .unwrap();
+    assert_eq!(deleted, Output::Bool(true));
+    session
+        .execute(Command::GraphCreate {
+            branch: None,
+            graph: "g".to_string(),
+            cascade_policy: None,
+        })
+        .unwrap();
+    for node in ["doc1", "topic"] {
+        session
+            .execute(Command::GraphAddNode {
+                branch: None,
+                graph: "g".to_string(),
+                node_id: node.to_string(),
+                entity_ref: None,
+                properties: None,
+            })
+            .unwrap();
+    }
+    session
+        .execute(Command::GraphAddEdge {
+            branch: None,
+            graph: "g".to_string(),
+            src: "doc1".to_string(),
+            dst: "topic".to_string(),
+            edge_type: "ABOUT".to_string(),
+            weight: None,
+            properties: None,
+        })
+        .unwrap();
+    // Graph reads see the transaction's own writes
+    let node = session
+        .execute(Command::GraphGetNode {
+            branch: None,
+            graph: "g".to_string(),
+            node_id: "doc1".to_string(),
+        })
+        .unwrap();
+    assert!(matches!(node, Output::Maybe(Some(_))));
+    session.execute(Command::TxnCommit).unwrap();
+
+    assert_eq!(search_keys(&mut session), vec!["doc1".to_string()]);
+    match session
+        .execute(Command::GraphNeighbors {
+            branch: None,
+            graph: "g".to_string(),
+            node_id: "doc1".to_string(),
+            direction: None,
+            edge_type: None,
+        })
+        .unwrap()
+    {
+        Output::GraphNeighbors(hits) => {
+            assert_eq!(hits.len(), 1);
+            assert_eq!(hits[0].node_id, "topic");
+        }
+        other => panic!("Expected GraphNeighbors, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_concurrent_vector_upserts_conflict() {
+    let db = Database::cache().unwrap();
+    let mut first = Session::new(db.clone());
+    let mut second = Session::new(db);
+    create_collection(&mut first);
+
+    for session in [&mut first, &mut second] {
+        session
+            .execute(Command::TxnBegin {
+                branch: None,
+                options: None,
+            })
+            .unwrap();
+    }
+    first.execute(upsert("v", vec![1.0, 0.0, 0.0])).unwrap();
+    second.execute(upsert("v", vec![0.0, 1.0, 0.0])).unwrap();
+    first.execute(Command::TxnCommit).unwrap();
+
+    let result = second.execute(Command::TxnCommit);
+    assert!(matches!(result, Err(Error::TransactionConflict { .. })));
+    assert_eq!(search_keys(&mut second), vec!["v".to_string()]);
+}
+
+#[test]
+fn test_vector_collection_ops_rejected_in_txn() {
+    let mut session = create_test_session();
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+
+    let result = session.execute(Command::VectorCreateCollection {
+        branch: None,
+        space: None,
+        collection: "emb".to_string(),
+        dimension: 3,
+        metric: crate::types::DistanceMetric::Cosine,
+    });
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+}
+
+// =============================================================================
+// Serializable Isolation
+// =============================================================================
+
+/// Begin a transaction that lists `alloc:*` and adds one more allocation.
+fn allocate(session: &mut Session, isolation: TxnIsolation, key: &str) {
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: Some(TxnOptions {
+                isolation,
+                ..Default::default()
+            }),
+        })
+        .unwrap();
+    session
+        .execute(Command::KvList {
+            branch: None,
+            space: None,
+            prefix: Some("alloc:".to_string()),
+            cursor: None,
+            limit: None,
+            as_of: None,
+        })
+        .unwrap();
+    session
+        .execute(Command::KvPut {
+            branch: None,
+            space: None,
+            key: key.to_string(),
+            value: Value::Int(60),
+            ttl_ms: None,
+            expires_at: None,
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_snapshot_isolation_allows_phantom_inserts() {
+    let db = Database::cache().unwrap();
+    let mut first = Session::new(db.clone());
+    let mut second = Session::new(db);
+
+    allocate(&mut first, TxnIsolation::Snapshot, "alloc:a");
+    allocate(&mut second, TxnIsolation::Snapshot, "alloc:b");
+    first.execute(Command::TxnCommit).unwrap();
+    assert!(second.execute(Command::TxnCommit).is_ok());
+}
+
+#[test]
+fn test_serializable_conflict_names_phantom_key() {
+    let db = Database::cache().unwrap();
+    let mut first = Session::new(db.clone());
+    let mut second = Session::new(db);
+
+    allocate(&mut first, TxnIsolation::Serializable, "alloc:a");
+    allocate(&mut second, TxnIsolation::Serializable, "alloc:b");
+    first.execute(Command::TxnCommit).unwrap();
+
+    match second.execute(Command::TxnCommit) {
+        Err(Error::TransactionConflict { reason }) => {
+            assert!(reason.contains("\"alloc:a\""), "{}", reason);
+        }
+        other => panic!("Expected TransactionConflict, got {:?}", other),
+    }
+    assert!(!second.in_transaction());
+}
+
+// =============================================================================
+// Savepoints
+// =============================================================================
+
+fn kv_put(key: &str, value: i64) -> Command {
+    Command::KvPut {
+        branch: None,
+        space: None,
+        key: key.to_string(),
+        value: Value::Int(value),
+        ttl_ms: None,
+        expires_at: None,
+    }
+}
+
+fn kv_exists(session: &mut Session, key: &str) -> bool {
+    match session
+        .execute(Command::KvGet {
+            branch: None,
+            space: None,
+            key: key.to_string(),
+            as_of: None,
+        })
+        .unwrap()
+    {
+        Output::MaybeVersioned(v) => v.is_some(),
+        Output::Maybe(v) => v.is_some(),
+        other => panic!("Expected Maybe output, got {:?}", other),
+    }
+}
+
+fn event_append(event_type: &str) -> Command {
+    Command::EventAppend {
+        branch: None,
+        space: None,
+        event_type: event_type.to_string(),
+        payload: Value::Object(std::collections::HashMap::new()),
+    }
+}
+
+#[test]
+fn test_rollback_to_savepoint_undoes_only_the_step() {
+    let mut session = create_test_session();
+    create_collection(&mut session);
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+
+    session.execute(kv_put("before", 1)).unwrap();
+    session.execute(event_append("plan")).unwrap();
+    session
+        .execute(Command::TxnSavepoint {
+            name: "tool".into(),
+        })
+        .unwrap();
+
+    session.execute(kv_put("during", 2)).unwrap();
+    let failed_step = session.execute(event_append("tool_call")).unwrap();
+    session.execute(upsert("v", vec![1.0, 0.0, 0.0])).unwrap();
+
+    let result = session.execute(Command::TxnRollbackTo {
+        name: "tool".into(),
+    });
+    assert!(matches!(result, Ok(Output::Unit)));
+    assert!(kv_exists(&mut session, "before"));
+    assert!(!kv_exists(&mut session, "during"));
+
+    // The event sequence continues from the savepoint
+    let retried_step = session.execute(event_append("tool_retry")).unwrap();
+    assert_eq!(retried_step, failed_step);
+
+    session.execute(Command::TxnCommit).unwrap();
+    assert!(kv_exists(&mut session, "before"));
+    assert!(!kv_exists(&mut session, "during"));
+    assert!(matches!(
+        session.execute(Command::EventLen {
+            branch: None,
+            space: None,
+        }),
+        Ok(Output::Uint(2))
+    ));
+    assert!(search_keys(&mut session).is_empty());
+}
+
+#[test]
+fn test_release_savepoint_keeps_writes() {
+    let mut session = create_test_session();
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+    session
+        .execute(Command::TxnSavepoint { name: "sp".into() })
+        .unwrap();
+    session.execute(kv_put("k", 1)).unwrap();
+    session
+        .execute(Command::TxnRelease { name: "sp".into() })
+        .unwrap();
+
+    let result = session.execute(Command::TxnRollbackTo { name: "sp".into() });
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+    session.execute(Command::TxnCommit).unwrap();
+    assert!(kv_exists(&mut session, "k"));
+}
+
+#[test]
+fn test_savepoint_requires_transaction() {
+    let mut session = create_test_session();
+    let result = session.execute(Command::TxnSavepoint { name: "sp".into() });
+    assert!(matches!(result, Err(Error::TransactionNotActive)));
+}
@@ -350,6 +350,8 @@ fn test_space_auto_register_on_write() {
             space: Some("auto-space".to_string()),
             key: "key1".to_string(),
             value: Value::Int(42),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -476,6 +478,8 @@ fn test_command_without_space_uses_default() {
         space: None,
         key: "compat-key".to_string(),
         value: Value::String("compat-value".into()),
+        ttl_ms: None,
+        expires_at: None,
     });
     assert!(matches!(result, Ok(Output::Version(_))));
 
@@ -505,6 +509,8 @@ fn test_explicit_space_in_command() {
             space: Some("explicit".to_string()),
             key: "key1".to_string(),
             value: Value::Int(42),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -4,6 +4,7 @@
 //! All types are serializable for cross-language use.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use strata_core::Value;
 
 // =============================================================================
@@ -61,6 +62,10 @@ impl std::fmt::Display for BranchId {
 pub enum BranchStatus {
     /// Branch is active and accepting reads/writes.
     Active,
+    /// Branch only accepts writes that arrive via merge.
+    Protected,
+    /// Branch rejects all writes.
+    Frozen,
 }
 
 /// Branch information
@@ -228,6 +233,13 @@ pub struct BatchKvEntry {
     pub key: String,
     /// Value to store.
     pub value: Value,
+    /// Expire the key this many milliseconds after the batch commits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_ms: Option<u64>,
+    /// Expire the key at this time (microseconds since epoch).
+    /// Exclusive with `ttl_ms`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
 }
 
 /// Entry for batch event append operations.
@@ -274,11 +286,33 @@ pub struct BatchItemResult {
 // Transaction Types
 // =============================================================================
 
+/// Transaction isolation level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxnIsolation {
+    /// Snapshot isolation (default). Inserts into a scanned range by a
+    /// concurrent transaction are not detected.
+    #[default]
+    Snapshot,
+    /// Snapshot isolation plus phantom detection: the transaction aborts if
+    /// a concurrent commit inserted a key under a prefix it listed.
+    Serializable,
+}
+
 /// Transaction options
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct TxnOptions {
     /// If true, the transaction only permits reads.
     pub read_only: bool,
+    /// Isolation level of the transaction.
+    #[serde(default)]
+    pub isolation: TxnIsolation,
+    /// Message recorded with the commit (shown by `BranchLog`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Metadata recorded with the commit (shown by `BranchLog`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
 }
 
 /// Transaction information
@@ -321,6 +355,21 @@ pub struct DatabaseInfo {
     pub total_keys: u64,
 }
 
+/// Information about a completed database backup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupResult {
+    /// Directory the backup was written to.
+    pub path: String,
+    /// Highest commit version included in the backup.
+    pub version: u64,
+    /// Number of snapshots copied.
+    pub snapshots: u64,
+    /// Number of WAL segments copied.
+    pub wal_segments: u64,
+    /// Total bytes copied.
+    pub bytes: u64,
+}
+
 // =============================================================================
 // Bundle Types
 // =============================================================================
@@ -29,7 +29,7 @@ pub enum AccessMode {
 ///     .access_mode(AccessMode::ReadOnly)
 ///     .durability("always");
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct OpenOptions {
     /// The access mode for the database.
     pub access_mode: AccessMode,
@@ -50,6 +50,16 @@ pub struct OpenOptions {
     /// Override embedding batch size for auto-embed.
     /// `None` means "use the config file value, or 512 if unset".
     pub embed_batch_size: Option<usize>,
+    /// Encryption key as 64 hex characters. Takes precedence over the
+    /// `STRATA_ENCRYPTION_KEY` environment variable and key files.
+    /// Never written to `strata.toml`.
+    pub encryption_key: Option<String>,
+    /// Path to an encryption key file (32 raw bytes or 64 hex characters).
+    /// Takes precedence over the `STRATA_ENCRYPTION_KEY` and
+    /// `STRATA_ENCRYPTION_KEY_FILE` environment variables and
+    /// `encryption_key_file` in `strata.toml`, but not `encryption_key`.
+    /// Relative paths are resolved against the data directory.
+    pub encryption_key_file: Option<String>,
 }
 
 impl OpenOptions {
@@ -100,6 +110,39 @@ impl OpenOptions {
         self.embed_batch_size = Some(size);
         self
     }
+
+    /// Set the encryption key (64 hex characters).
+    pub fn encryption_key(mut self, hex_key: &str) -> Self {
+        self.encryption_key = Some(hex_key.to_string());
+        self
+    }
+
+    /// Set the path to the encryption key file.
+    pub fn encryption_key_file(mut self, path: &str) -> Self {
+        self.encryption_key_file = Some(path.to_string());
+        self
+    }
+}
+
+impl std::fmt::Debug for OpenOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpenOptions")
+            .field("access_mode", &self.access_mode)
+            .field("auto_embed", &self.auto_embed)
+            .field("durability", &self.durability)
+            .field("model_endpoint", &self.model_endpoint)
+            .field("model_name", &self.model_name)
+            .field("model_api_key", &self.model_api_key)
+            .field("model_timeout_ms", &self.model_timeout_ms)
+            .field("embed_batch_size", &self.embed_batch_size)
+            // Never print key material
+            .field(
+                "encryption_key",
+                &self.encryption_key.as_ref().map(|_| "<redacted>"),
+            )
+            .field("encryption_key_file", &self.encryption_key_file)
+            .finish()
+    }
 }
 
 impl Default for OpenOptions {
@@ -113,6 +156,8 @@ impl Default for OpenOptions {
             model_api_key: None,
             model_timeout_ms: None,
             embed_batch_size: None,
+            encryption_key: None,
+            encryption_key_file: None,
         }
     }
 }
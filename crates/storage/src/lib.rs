@@ -1,11 +1,14 @@
 //! Storage layer for Strata
 //!
-//! This crate provides pure in-memory data structures. No file I/O.
+//! This crate provides in-memory data structures. The only file I/O is the
+//! optional spill tier, which pages cold versions to scratch files when a
+//! memory budget is set.
 //!
 //! - ShardedStore: DashMap + HashMap with MVCC version chains
 //! - Lock-free reads via DashMap
 //! - Per-BranchId sharding (no cross-branch contention)
 //! - FxHashMap for O(1) lookups
+//! - SpillTier: larger-than-memory stores under a memory budget
 //!
 //! Persistence and durability are handled by the `strata-durability` crate.
 
@@ -16,6 +19,7 @@ pub mod index;
 pub mod primitive_ext;
 pub mod registry;
 pub mod sharded;
+pub mod spill;
 pub mod stored_value;
 pub mod ttl;
 
@@ -25,5 +29,6 @@ pub use primitive_ext::{
     PrimitiveExtError, PrimitiveStorageExt,
 };
 pub use registry::PrimitiveRegistry;
-pub use sharded::{Shard, ShardedSnapshot, ShardedStore};
+pub use sharded::{BranchAncestor, Shard, ShardedSnapshot, ShardedStore};
+pub use spill::{SpillConfig, SpillStats};
 pub use ttl::TTLIndex;
@@ -31,15 +31,19 @@
 //! 4. Performance: Avoiding enum matching on every comparison
 
 use dashmap::DashMap;
-use rustc_hash::FxHashMap;
-use std::collections::BTreeSet;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::borrow::Cow;
 use std::collections::VecDeque;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use strata_core::types::{BranchId, Key};
 use strata_core::{Timestamp, Version, VersionedValue};
 
+use crate::spill::{SpillConfig, SpillRef, SpillStats, SpillTier};
 use crate::stored_value::StoredValue;
+use crate::ttl::TTLIndex;
 
 /// Per-branch shard containing branch's data
 ///
@@ -52,12 +56,23 @@ use crate::stored_value::StoredValue;
 ///
 /// Uses VecDeque for O(1) push_front instead of SmallVec's O(n) insert(0, ...).
 /// This is critical for workloads that repeatedly update the same key (like CAS).
-#[derive(Debug, Clone)]
+///
+/// # Spilled Versions
+///
+/// In a store with a memory budget, the oldest versions (or all of them)
+/// may live in a spill run instead of `versions`. The spilled tail is always
+/// older than every resident version. The accessors on this type see only
+/// the resident versions; `ShardedStore` reads page the tail in.
+#[derive(Debug)]
 pub struct VersionChain {
     /// Versions stored newest-first for efficient MVCC reads
     /// VecDeque provides O(1) push_front for new versions
     /// Uses StoredValue to include TTL information
     versions: VecDeque<StoredValue>,
+    /// Older versions paged out to the spill tier, newest first
+    spilled: Option<SpillRef>,
+    /// Spill epoch of the last point read or write (recency for eviction)
+    touched: AtomicU64,
 }
 
 impl VersionChain {
@@ -65,7 +80,65 @@ impl VersionChain {
     pub fn new(value: StoredValue) -> Self {
         let mut versions = VecDeque::with_capacity(4);
         versions.push_front(value);
-        Self { versions }
+        Self::from_versions(versions)
+    }
+
+    fn from_versions(versions: VecDeque<StoredValue>) -> Self {
+        Self {
+            versions,
+            spilled: None,
+            touched: AtomicU64::new(0),
+        }
+    }
+
+    /// Newest version (resident, then spilled) matching `pred`
+    fn find(
+        &self,
+        spill: Option<&SpillTier>,
+        pred: impl Fn(&StoredValue) -> bool,
+    ) -> StrataResult<Option<Cow<'_, StoredValue>>> {
+        if let Some(sv) = self.versions.iter().find(|sv| pred(sv)) {
+            return Ok(Some(Cow::Borrowed(sv)));
+        }
+        let (Some(spilled), Some(spill)) = (&self.spilled, spill) else {
+            return Ok(None);
+        };
+        Ok(spill.load(spilled)?.into_iter().find(pred).map(Cow::Owned))
+    }
+
+    /// Run `f` on this chain with its spilled tail paged in
+    fn with_loaded<R>(
+        &self,
+        spill: Option<&SpillTier>,
+        f: impl FnOnce(&VersionChain) -> R,
+    ) -> StrataResult<R> {
+        match (&self.spilled, spill) {
+            (Some(spilled), Some(spill)) => {
+                let mut versions = self.versions.clone();
+                versions.extend(spill.load(spilled)?);
+                Ok(f(&VersionChain::from_versions(versions)))
+            }
+            _ => Ok(f(self)),
+        }
+    }
+
+    /// Move the spilled tail back into memory and release its run record
+    ///
+    /// If the tail cannot be read the chain is left unchanged.
+    fn page_in(&mut self, spill: &SpillTier) -> StrataResult<()> {
+        if let Some(spilled) = &self.spilled {
+            let tail = spill.load(spilled)?;
+            spill.add_resident(tail.iter().map(StoredValue::approx_size).sum());
+            self.versions.extend(tail);
+            spill.release(spilled);
+            self.spilled = None;
+        }
+        Ok(())
+    }
+
+    /// Approximate bytes held by the resident versions
+    fn resident_size(&self) -> usize {
+        self.versions.iter().map(StoredValue::approx_size).sum()
     }
 
     /// Add a new version (must be newer than existing versions)
@@ -134,6 +207,59 @@ impl VersionChain {
         pruned
     }
 
+    /// Remove versions older than min_version, except those still visible
+    /// at a pinned version.
+    ///
+    /// A pin is the fork version of a copy-on-write child: the newest version
+    /// <= each pin must survive so the child can keep reading it. Keeps at
+    /// least the latest version. Returns the number of pruned versions.
+    pub fn gc_pinned(&mut self, min_version: u64, pinned: &[u64]) -> usize {
+        if pinned.is_empty() {
+            return self.gc(min_version);
+        }
+        let keep: Vec<usize> = pinned
+            .iter()
+            .filter_map(|&pin| {
+                self.versions
+                    .iter()
+                    .position(|sv| sv.version().as_u64() <= pin)
+            })
+            .collect();
+
+        let before = self.versions.len();
+        let mut index = 0;
+        self.versions.retain(|sv| {
+            let i = index;
+            index += 1;
+            i == 0 || sv.version().as_u64() >= min_version || keep.contains(&i)
+        });
+        before - self.versions.len()
+    }
+
+    /// Remove the given versions, except the latest and those still visible
+    /// at a pinned version (see [`gc_pinned`](Self::gc_pinned)).
+    ///
+    /// Returns the number of pruned versions.
+    pub fn prune_versions(&mut self, versions: &[u64], pinned: &[u64]) -> usize {
+        let keep: Vec<usize> = pinned
+            .iter()
+            .filter_map(|&pin| {
+                self.versions
+                    .iter()
+                    .position(|sv| sv.version().as_u64() <= pin)
+            })
+            .collect();
+
+        let before = self.versions.len();
+        let mut index = 0;
+        self.versions.retain(|sv| {
+            let i = index;
+            index += 1;
+            i == 0 || keep.contains(&i) || !versions.contains(&sv.version().as_u64())
+        });
+        before - self.versions.len()
+    }
+
     /// Number of versions stored
     pub fn version_count(&self) -> usize {
         self.versions.len()
@@ -174,7 +300,7 @@ impl VersionChain {
 
     /// Check if the version chain is empty
     pub fn is_empty(&self) -> bool {
-        self.versions.is_empty()
+        self.versions.is_empty() && self.spilled.is_none()
     }
 }
 
@@ -188,6 +314,8 @@ pub struct Shard {
     pub(crate) data: FxHashMap<Key, VersionChain>,
     /// Sorted index of all keys for O(log n + k) prefix scans
     pub(crate) ordered_keys: BTreeSet<Key>,
+    /// Branches this shard reads through, nearest first (empty unless forked)
+    pub(crate) ancestry: Vec<BranchAncestor>,
 }
 
 impl Shard {
@@ -196,6 +324,7 @@ impl Shard {
         Self {
             data: FxHashMap::default(),
             ordered_keys: BTreeSet::new(),
+            ancestry: Vec::new(),
         }
     }
 
@@ -204,6 +333,40 @@ impl Shard {
         Self {
             data: FxHashMap::with_capacity_and_hasher(capacity, Default::default()),
             ordered_keys: BTreeSet::new(),
+            ancestry: Vec::new(),
+        }
+    }
+
+    /// Visit every key selected by `filter` in key order, with its chain.
+    ///
+    /// Stops at the first error `f` returns.
+    fn for_each_chain(
+        &self,
+        filter: &KeyFilter<'_>,
+        branch_id: BranchId,
+        mut f: impl FnMut(&Key, &VersionChain) -> StrataResult<()>,
+    ) -> StrataResult<()> {
+        let mut visit = |k: &Key| match self.data.get(k) {
+            Some(chain) => f(k, chain),
+            None => Ok(()),
+        };
+        match filter {
+            KeyFilter::All => self.ordered_keys.iter().try_for_each(&mut visit),
+            KeyFilter::Type(type_tag) => self
+                .ordered_keys
+                .iter()
+                .filter(|k| k.type_tag == *type_tag)
+                .try_for_each(&mut visit),
+            KeyFilter::Prefix(prefix) if prefix.namespace.branch_id == branch_id => {
+                self.keys_with_prefix(prefix).try_for_each(&mut visit)
+            }
+            KeyFilter::Prefix(prefix) => {
+                let prefix = with_branch(prefix, branch_id);
+                self.ordered_keys
+                    .range::<Key, _>(&prefix..)
+                    .take_while(|k| k.starts_with(&prefix))
+                    .try_for_each(&mut visit)
+            }
         }
     }
 
@@ -233,6 +396,145 @@ impl Default for Shard {
     }
 }
 
+// ============================================================================
+// Copy-on-write Lineage
+// ============================================================================
+
+/// A branch whose version chains a copy-on-write fork reads through
+///
+/// A forked branch does not copy its parent's data. Its shard records an
+/// ancestry instead: the parent, then the parent's own ancestors, each with
+/// the newest version of that branch the fork may see. A read that finds
+/// nothing in the fork's own chains falls back to each ancestor in turn.
+///
+/// The ancestry is captured whole at fork time, so it does not depend on
+/// the parent's shard staying linked (e.g. if the parent is deleted and a
+/// branch with the same name is created later).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BranchAncestor {
+    /// Branch whose version chains are shared
+    pub branch_id: BranchId,
+    /// Newest version of that branch visible through this link
+    pub version: u64,
+}
+
+/// Which keys of a shard a scan visits
+enum KeyFilter<'a> {
+    /// Every key in the shard
+    All,
+    /// Keys of one primitive type
+    Type(strata_core::types::TypeTag),
+    /// Keys starting with a prefix (its branch is swapped per ancestor)
+    Prefix(&'a Key),
+}
+
+/// Which versions an eviction pass moves to the spill tier
+#[derive(Debug, Clone, Copy)]
+enum Eviction {
+    /// Every version but the newest of each key
+    OldVersions,
+    /// Whole chains not read or written since this spill epoch
+    ColdBefore(u64),
+    /// Whole chains
+    Any,
+}
+
+/// Window of versions a read may observe in one shard
+///
+/// `min_version` hides what a branch held before it was forked (its own
+/// data starts after the fork point); `max_version` and `max_timestamp`
+/// express the snapshot or point-in-time being read.
+#[derive(Debug, Clone, Copy)]
+struct ReadBound {
+    min_version: u64,
+    max_version: u64,
+    max_timestamp: u64,
+}
+
+impl ReadBound {
+    /// Latest committed state
+    const LATEST: ReadBound = ReadBound {
+        min_version: 0,
+        max_version: u64::MAX,
+        max_timestamp: u64::MAX,
+    };
+
+    fn at_version(max_version: u64) -> Self {
+        ReadBound {
+            max_version,
+            ..Self::LATEST
+        }
+    }
+
+    fn at_timestamp(max_timestamp: u64) -> Self {
+        ReadBound {
+            max_timestamp,
+            ..Self::LATEST
+        }
+    }
+
+    /// Bound for a branch's own chains, given the branch's ancestry
+    fn own(self, ancestry: &[BranchAncestor]) -> Self {
+        ReadBound {
+            min_version: ancestry.first().map_or(0, |a| a.version.saturating_add(1)),
+            ..self
+        }
+    }
+
+    /// Bound for the `index`th ancestor's chains
+    fn inherited(self, ancestry: &[BranchAncestor], index: usize) -> Self {
+        ReadBound {
+            min_version: ancestry
+                .get(index + 1)
+                .map_or(0, |a| a.version.saturating_add(1)),
+            max_version: self.max_version.min(ancestry[index].version),
+            max_timestamp: self.max_timestamp,
+        }
+    }
+
+    /// Newest version in the chain visible within this window
+    ///
+    /// Pages in the chain's spilled tail only if no resident version is
+    /// within the upper bounds.
+    fn select<'a>(
+        &self,
+        chain: &'a VersionChain,
+        spill: Option<&SpillTier>,
+    ) -> StrataResult<Option<Cow<'a, StoredValue>>> {
+        Ok(chain
+            .find(spill, |sv| {
+                sv.version().as_u64() <= self.max_version
+                    && u64::from(sv.timestamp()) <= self.max_timestamp
+            })?
+            .filter(|sv| sv.version().as_u64() >= self.min_version))
+    }
+}
+
+/// The same key in another branch's namespace
+fn with_branch(key: &Key, branch_id: BranchId) -> Key {
+    let mut namespace = key.namespace.clone();
+    namespace.branch_id = branch_id;
+    Key::new(namespace, key.type_tag, key.user_key.clone())
+}
+
+/// Keep values that are neither tombstones nor expired
+fn is_live(sv: &StoredValue) -> bool {
+    !sv.is_expired() && !sv.is_tombstone()
+}
+
+/// Keep values that are neither tombstones nor expired at `now`
+fn live_at(now: Timestamp) -> impl Fn(&StoredValue) -> bool + Copy {
+    move |sv| !sv.is_expired_at(now) && !sv.is_tombstone()
+}
+
+/// The time a read bounded by `max_timestamp` evaluates expiry at
+///
+/// Reads of the past see entries as they were then; reads bounded in the
+/// future only know the present, so they never hide less than a latest read.
+fn expiry_clock(max_timestamp: u64) -> Timestamp {
+    Timestamp::from_micros(max_timestamp.min(Timestamp::now().as_micros()))
+}
+
 /// Sharded storage - DashMap by BranchId, HashMap within
 ///
 /// # Design
@@ -248,6 +550,13 @@ impl Default for Shard {
 /// - put(): Only locks the target branch's shard
 /// - Different branches never contend
 ///
+/// # Memory Budget
+///
+/// A store created with [`with_spill`](Self::with_spill) keeps the values
+/// of its version chains under a memory budget: writes that push it over
+/// the budget page old versions and cold keys out to disk (see
+/// [`crate::spill`]). Reads return exactly what an in-memory store would.
+///
 /// # Example
 ///
 /// ```text
@@ -262,6 +571,10 @@ pub struct ShardedStore {
     shards: DashMap<BranchId, Shard>,
     /// Global version for snapshots
     version: AtomicU64,
+    /// Disk tier for versions evicted under the memory budget
+    spill: Option<SpillTier>,
+    /// Expiry times of values written with a TTL
+    ttl_index: Mutex<TTLIndex>,
 }
 
 impl ShardedStore {
@@ -270,6 +583,8 @@ impl ShardedStore {
         Self {
             shards: DashMap::new(),
             version: AtomicU64::new(0),
+            spill: None,
+            ttl_index: Mutex::new(TTLIndex::new()),
         }
     }
 
@@ -278,9 +593,28 @@ impl ShardedStore {
         Self {
             shards: DashMap::with_capacity(num_branches),
             version: AtomicU64::new(0),
+            spill: None,
+            ttl_index: Mutex::new(TTLIndex::new()),
         }
     }
 
+    /// Create a store that spills to disk beyond a memory budget
+    ///
+    /// Fails if the spill directory cannot be created or cleared.
+    pub fn with_spill(config: SpillConfig) -> std::io::Result<Self> {
+        Ok(Self {
+            shards: DashMap::new(),
+            version: AtomicU64::new(0),
+            spill: Some(SpillTier::open(config)?),
+            ttl_index: Mutex::new(TTLIndex::new()),
+        })
+    }
+
+    /// Spill tier counters, or `None` if the store has no memory budget
+    pub fn spill_stats(&self) -> Option<SpillStats> {
+        self.spill.as_ref().map(SpillTier::stats)
+    }
+
     /// Get current version
     #[inline]
     pub fn version(&self) -> u64 {
@@ -348,15 +682,38 @@ impl ShardedStore {
     #[inline]
     pub fn put(&self, key: Key, value: StoredValue) {
         let branch_id = key.namespace.branch_id;
-        let mut shard = self.shards.entry(branch_id).or_default();
+        {
+            let mut shard = self.shards.entry(branch_id).or_default();
+            self.push_version(&mut shard, key, value);
+        }
+        self.maybe_evict();
+    }
+
+    /// Add a version to a key's chain in a locked shard
+    #[inline]
+    fn push_version(&self, shard: &mut Shard, key: Key, value: StoredValue) {
+        let spill = self.spill.as_ref();
+        if let Some(spill) = spill {
+            spill.add_resident(value.approx_size());
+        }
+        if let Some(expiry) = value.expiry_timestamp() {
+            self.ttl_index.lock().unwrap().insert(expiry, key.clone());
+        }
 
         if let Some(chain) = shard.data.get_mut(&key) {
             // Add new version to existing chain
             chain.push(value);
+            if let Some(spill) = spill {
+                chain.touched.store(spill.epoch(), Ordering::Relaxed);
+            }
         } else {
             // Create new chain — also add to BTreeSet index
+            let chain = VersionChain::new(value);
+            if let Some(spill) = spill {
+                chain.touched.store(spill.epoch(), Ordering::Relaxed);
+            }
             shard.ordered_keys.insert(key.clone());
-            shard.data.insert(key, VersionChain::new(value));
+            shard.data.insert(key, chain);
         }
     }
 
@@ -373,10 +730,9 @@ impl ShardedStore {
     /// # Returns
     /// The previous value if it existed and wasn't already deleted
     #[inline]
-    pub fn delete(&self, key: &Key) -> Option<VersionedValue> {
+    pub fn delete(&self, key: &Key) -> StrataResult<Option<VersionedValue>> {
         let delete_version = self.next_version();
-        // delete_with_version always succeeds, unwrap is safe
-        self.delete_with_version(key, delete_version).unwrap()
+        self.delete_with_version(key, delete_version)
     }
 
     /// Delete a key with a specific version (for batched deletes)
@@ -391,21 +747,13 @@ impl ShardedStore {
     ) -> StrataResult<Option<VersionedValue>> {
         use strata_core::Version;
 
-        let branch_id = key.namespace.branch_id;
-
         // Get the previous value before adding tombstone
-        let previous = self.shards.get(&branch_id).and_then(|shard| {
-            shard.data.get(key).and_then(|chain| {
-                chain.latest().and_then(|sv| {
-                    // Don't return tombstones as "previous value"
-                    if sv.is_tombstone() {
-                        None
-                    } else {
-                        Some(sv.versioned().clone())
-                    }
-                })
-            })
-        });
+        // (don't return tombstones as "previous value")
+        let previous = self
+            .resolve(key, ReadBound::LATEST, |sv| {
+                (!sv.is_tombstone()).then(|| sv.versioned().clone())
+            })?
+            .flatten();
 
         // Add tombstone to version chain
         let tombstone = StoredValue::tombstone(Version::txn(version));
@@ -419,17 +767,10 @@ impl ShardedStore {
     /// Lock-free check via DashMap read guard.
     /// Returns false for deleted keys (tombstones).
     #[inline]
-    pub fn contains(&self, key: &Key) -> bool {
-        let branch_id = key.namespace.branch_id;
-        self.shards
-            .get(&branch_id)
-            .map(|shard| {
-                shard
-                    .data
-                    .get(key)
-                    .is_some_and(|chain| chain.latest().is_some_and(|sv| !sv.is_tombstone()))
-            })
-            .unwrap_or(false)
+    pub fn contains(&self, key: &Key) -> StrataResult<bool> {
+        Ok(self
+            .resolve(key, ReadBound::LATEST, |sv| !sv.is_tombstone())?
+            .unwrap_or(false))
     }
 
     /// Apply a batch of writes and deletes atomically
@@ -457,10 +798,41 @@ impl ShardedStore {
         deletes: &[Key],
         version: u64,
     ) -> strata_core::StrataResult<()> {
-        use std::sync::atomic::Ordering;
-
         // Capture timestamp once for entire batch
-        let timestamp = Timestamp::now();
+        self.apply_batch_at(writes, deletes, version, Timestamp::now())
+    }
+
+    /// Apply a batch of writes and deletes with an explicit commit timestamp
+    ///
+    /// Same as [`apply_batch`](Self::apply_batch), but every write and
+    /// tombstone carries `timestamp`. Used by WAL recovery so that replayed
+    /// entries keep their original commit time and time-travel reads stay
+    /// valid across restarts.
+    #[allow(clippy::type_complexity)]
+    pub fn apply_batch_at(
+        &self,
+        writes: &[(Key, strata_core::value::Value)],
+        deletes: &[Key],
+        version: u64,
+        timestamp: Timestamp,
+    ) -> strata_core::StrataResult<()> {
+        self.apply_batch_with_ttls(writes, deletes, &[], version, timestamp)
+    }
+
+    /// Apply a batch whose writes may carry a TTL
+    ///
+    /// Same as [`apply_batch_at`](Self::apply_batch_at); writes whose key is
+    /// listed in `ttls` expire that long after `timestamp`.
+    #[allow(clippy::type_complexity)]
+    pub fn apply_batch_with_ttls(
+        &self,
+        writes: &[(Key, strata_core::value::Value)],
+        deletes: &[Key],
+        ttls: &[(Key, Duration)],
+        version: u64,
+        timestamp: Timestamp,
+    ) -> strata_core::StrataResult<()> {
+        use std::sync::atomic::Ordering;
 
         // Group writes and deletes by branch_id to apply atomically per branch.
         // This ensures concurrent readers never see partial transaction state
@@ -469,8 +841,9 @@ impl ShardedStore {
             FxHashMap::default();
 
         for (key, value) in writes {
+            let ttl = ttls.iter().find(|(k, _)| k == key).map(|(_, ttl)| *ttl);
             let stored =
-                StoredValue::with_timestamp(value.clone(), Version::txn(version), timestamp, None);
+                StoredValue::with_timestamp(value.clone(), Version::txn(version), timestamp, ttl);
             branch_ops
                 .entry(key.namespace.branch_id)
                 .or_insert_with(|| (Vec::new(), Vec::new()))
@@ -491,22 +864,12 @@ impl ShardedStore {
             let mut shard = self.shards.entry(branch_id).or_default();
 
             for (key, stored) in branch_writes {
-                if let Some(chain) = shard.data.get_mut(&key) {
-                    chain.push(stored);
-                } else {
-                    shard.ordered_keys.insert(key.clone());
-                    shard.data.insert(key, VersionChain::new(stored));
-                }
+                self.push_version(&mut shard, key, stored);
             }
 
             for key in branch_deletes {
-                let tombstone = StoredValue::tombstone(Version::txn(version));
-                if let Some(chain) = shard.data.get_mut(&key) {
-                    chain.push(tombstone);
-                } else {
-                    shard.ordered_keys.insert(key.clone());
-                    shard.data.insert(key, VersionChain::new(tombstone));
-                }
+                let tombstone = StoredValue::tombstone_at(Version::txn(version), timestamp);
+                self.push_version(&mut shard, key, tombstone);
             }
         }
 
@@ -514,6 +877,7 @@ impl ShardedStore {
         // This ensures subsequent snapshots can see the committed data
         self.version.fetch_max(version, Ordering::AcqRel);
 
+        self.maybe_evict();
         Ok(())
     }
 
@@ -532,18 +896,11 @@ impl ShardedStore {
         key: &Key,
         max_timestamp: u64,
     ) -> strata_core::StrataResult<Option<VersionedValue>> {
-        let branch_id = key.namespace.branch_id;
-        Ok(self.shards.get(&branch_id).and_then(|shard| {
-            shard.data.get(key).and_then(|chain| {
-                chain.get_at_timestamp(max_timestamp).and_then(|sv| {
-                    if !sv.is_expired() && !sv.is_tombstone() {
-                        Some(sv.versioned().clone())
-                    } else {
-                        None
-                    }
-                })
-            })
-        }))
+        Ok(self
+            .resolve(key, ReadBound::at_timestamp(max_timestamp), |sv| {
+                live_at(expiry_clock(max_timestamp))(sv).then(|| sv.versioned().clone())
+            })?
+            .flatten())
     }
 
     /// Scan keys matching a prefix, returning values at or before the given timestamp.
@@ -552,27 +909,100 @@ impl ShardedStore {
         prefix: &Key,
         max_timestamp: u64,
     ) -> strata_core::StrataResult<Vec<(Key, VersionedValue)>> {
+        self.collect_visible(
+            &prefix.namespace.branch_id,
+            KeyFilter::Prefix(prefix),
+            ReadBound::at_timestamp(max_timestamp),
+            live_at(expiry_clock(max_timestamp)),
+        )
+    }
+
+    /// Scan keys matching a prefix in key order, resuming after a key
+    ///
+    /// Returns at most `limit` live entries that start with `prefix` and
+    /// sort after `after`, as of `max_timestamp` (the latest state if
+    /// `None`). Unlike the other scans it seeks to `after` and stops once
+    /// `limit` entries are found, so a large prefix can be read in pages.
+    pub fn scan_prefix_after(
+        &self,
+        prefix: &Key,
+        after: Option<&Key>,
+        max_timestamp: Option<u64>,
+        limit: usize,
+    ) -> StrataResult<Vec<(Key, VersionedValue)>> {
         let branch_id = prefix.namespace.branch_id;
-        Ok(self
-            .shards
-            .get(&branch_id)
-            .map(|shard| {
-                shard
-                    .keys_with_prefix(prefix)
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.get_at_timestamp(max_timestamp).and_then(|sv| {
-                                if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default())
+        let bound = max_timestamp.map_or(ReadBound::LATEST, ReadBound::at_timestamp);
+        let include = live_at(expiry_clock(bound.max_timestamp));
+        let ancestry = match self.shards.get(&branch_id) {
+            Some(shard) if limit > 0 => shard.ancestry.clone(),
+            _ => return Ok(Vec::new()),
+        };
+        let levels: Vec<(BranchId, ReadBound)> = std::iter::once((branch_id, bound.own(&ancestry)))
+            .chain(
+                ancestry
+                    .iter()
+                    .enumerate()
+                    .map(|(index, a)| (a.branch_id, bound.inherited(&ancestry, index))),
+            )
+            .collect();
+
+        let mut results = Vec::new();
+        let mut resume = after.cloned();
+        while results.len() < limit {
+            // Each level reports at most `limit` keys past `resume`, deleted
+            // ones included since they hide the same key in an ancestor.
+            // Keys up to the smallest cut-off are then decided exactly.
+            let mut decided: BTreeMap<Key, Option<VersionedValue>> = BTreeMap::new();
+            let mut horizon: Option<Key> = None;
+            for (level_branch, level_bound) in &levels {
+                let Some(shard) = self.shards.get(level_branch) else {
+                    continue;
+                };
+                let level_prefix = with_branch(prefix, *level_branch);
+                let start = match &resume {
+                    Some(key) if key >= prefix => Bound::Excluded(with_branch(key, *level_branch)),
+                    _ => Bound::Included(level_prefix.clone()),
+                };
+                let mut seen = 0usize;
+                for k in shard
+                    .ordered_keys
+                    .range::<Key, _>((start, Bound::Unbounded))
+                    .take_while(|k| k.starts_with(&level_prefix))
+                {
+                    let Some(chain) = shard.data.get(k) else {
+                        continue;
+                    };
+                    let Some(sv) = level_bound.select(chain, self.spill.as_ref())? else {
+                        continue;
+                    };
+                    let key = with_branch(k, branch_id);
+                    seen += 1;
+                    if seen == limit && horizon.as_ref().map_or(true, |h| key < *h) {
+                        horizon = Some(key.clone());
+                    }
+                    decided
+                        .entry(key)
+                        .or_insert_with(|| include(&sv).then(|| sv.versioned().clone()));
+                    if seen == limit {
+                        break;
+                    }
+                }
+            }
+
+            for (key, value) in decided {
+                if horizon.as_ref().is_some_and(|h| key > *h) || results.len() == limit {
+                    break;
+                }
+                if let Some(value) = value {
+                    results.push((key, value));
+                }
+            }
+            match horizon {
+                Some(key) => resume = Some(key),
+                None => break,
+            }
+        }
+        Ok(results)
     }
 
     /// Get the available time range for a branch.
@@ -586,38 +1016,230 @@ impl ShardedStore {
     /// predates timestamp tracking, so it is not meaningful for time-range
     /// queries.
     pub fn time_range(&self, branch_id: BranchId) -> strata_core::StrataResult<Option<(u64, u64)>> {
-        Ok(self.shards.get(&branch_id).and_then(|shard| {
-            let mut min_ts = u64::MAX;
-            let mut max_ts = 0u64;
-            for chain in shard.data.values() {
-                if let Some(sv) = chain.latest() {
-                    let ts: u64 = sv.timestamp().into();
-                    if ts > 0 && !sv.is_tombstone() {
-                        min_ts = min_ts.min(ts);
-                        max_ts = max_ts.max(ts);
+        let mut min_ts = u64::MAX;
+        let mut max_ts = 0u64;
+        let mut observe = |sv: &StoredValue| {
+            let ts: u64 = sv.timestamp().into();
+            if ts > 0 && !sv.is_tombstone() {
+                min_ts = min_ts.min(ts);
+                max_ts = max_ts.max(ts);
+            }
+        };
+
+        let forked = match self.shards.get(&branch_id) {
+            Some(shard) if shard.ancestry.is_empty() => {
+                for chain in shard.data.values() {
+                    if let Some(sv) = ReadBound::LATEST.select(chain, self.spill.as_ref())? {
+                        observe(&sv);
                     }
                 }
+                false
             }
-            if max_ts == 0 {
-                None
-            } else {
-                Some((min_ts, max_ts))
-            }
-        }))
+            Some(_) => true,
+            None => return Ok(None),
+        };
+        if forked {
+            // Inherited entries count too: resolve every visible key.
+            self.visit_visible(&branch_id, KeyFilter::All, ReadBound::LATEST, |_, sv| {
+                observe(sv)
+            })?;
+        }
+
+        if max_ts == 0 {
+            Ok(None)
+        } else {
+            Ok(Some((min_ts, max_ts)))
+        }
     }
 
     /// Garbage-collect old versions from all entries for a given branch.
     ///
     /// Calls `VersionChain::gc(min_version)` on each entry in the branch's shard.
-    /// Returns the total number of pruned versions.
-    pub fn gc_branch(&self, branch_id: BranchId, min_version: u64) -> usize {
+    /// Versions that copy-on-write forks of this branch still read through
+    /// (the newest version at each fork point) are kept.
+    /// Returns the total number of pruned versions, or the first error
+    /// reading a spilled chain tail.
+    pub fn gc_branch(&self, branch_id: BranchId, min_version: u64) -> StrataResult<usize> {
+        let pinned = self.fork_pins(&branch_id);
+
         let mut pruned = 0;
+        let mut result = Ok(());
         if let Some(mut shard) = self.shards.get_mut(&branch_id) {
             for chain in shard.data.values_mut() {
-                pruned += chain.gc(min_version);
+                match &self.spill {
+                    Some(spill) => {
+                        // Spilled versions are pruned in memory; whatever
+                        // survives is evicted again by the next pass.
+                        result = chain.page_in(spill);
+                        if result.is_err() {
+                            break;
+                        }
+                        let before = chain.resident_size();
+                        pruned += chain.gc_pinned(min_version, &pinned);
+                        spill.sub_resident(before - chain.resident_size());
+                    }
+                    None => pruned += chain.gc_pinned(min_version, &pinned),
+                }
             }
         }
-        pruned
+        self.maybe_evict();
+        result.map(|()| pruned)
+    }
+
+    /// Fork versions at which copy-on-write forks read through a branch
+    ///
+    /// The newest version of each key at or before a pin is still visible
+    /// to a fork, so pruning keeps it.
+    pub fn fork_pins(&self, branch_id: &BranchId) -> Vec<u64> {
+        self.shards
+            .iter()
+            .flat_map(|entry| {
+                entry
+                    .value()
+                    .ancestry
+                    .iter()
+                    .filter(|a| a.branch_id == *branch_id)
+                    .map(|a| a.version)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Visit every stored version of a branch's own keys
+    ///
+    /// `f` gets each key with its versions newest first, tombstones and
+    /// spilled versions included. Keys a copy-on-write fork inherits are
+    /// not visited: their versions belong to the ancestor.
+    pub fn visit_branch_versions(
+        &self,
+        branch_id: &BranchId,
+        mut f: impl FnMut(&Key, &[&StoredValue]),
+    ) -> StrataResult<()> {
+        let Some(shard) = self.shards.get(branch_id) else {
+            return Ok(());
+        };
+        for (key, chain) in shard.data.iter() {
+            chain.with_loaded(self.spill.as_ref(), |chain| {
+                f(key, &chain.history(None, None));
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Remove specific versions of a branch's keys
+    ///
+    /// Calls `VersionChain::prune_versions` for each listed key, so the
+    /// latest version of a key and versions copy-on-write forks still read
+    /// through are kept even if listed.
+    /// Returns the number of pruned versions and the approximate bytes they
+    /// held, or the first error reading a spilled chain tail.
+    pub fn prune_versions(
+        &self,
+        branch_id: &BranchId,
+        prune: &[(Key, Vec<u64>)],
+    ) -> StrataResult<(usize, usize)> {
+        let pinned = self.fork_pins(branch_id);
+
+        let (mut pruned, mut freed) = (0, 0);
+        let mut result = Ok(());
+        if let Some(mut shard) = self.shards.get_mut(branch_id) {
+            for (key, versions) in prune {
+                let Some(chain) = shard.data.get_mut(key) else {
+                    continue;
+                };
+                if let Some(spill) = &self.spill {
+                    result = chain.page_in(spill);
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                let before = chain.resident_size();
+                pruned += chain.prune_versions(versions, &pinned);
+                let removed = before - chain.resident_size();
+                if let Some(spill) = &self.spill {
+                    spill.sub_resident(removed);
+                }
+                freed += removed;
+            }
+        }
+        self.maybe_evict();
+        result.map(|()| (pruned, freed))
+    }
+
+    /// Every stored version in `(after, up_to]`, across all branches
+    ///
+    /// Includes shadowed versions and tombstones, oldest first within each
+    /// key, so replaying the entries in version order rebuilds the same
+    /// version chains. Used to write restorable checkpoints: `after = 0`
+    /// captures everything, a previous checkpoint's version captures only
+    /// what changed since.
+    pub fn versions_between(
+        &self,
+        after: u64,
+        up_to: u64,
+    ) -> StrataResult<Vec<(Key, StoredValue)>> {
+        let mut out = Vec::new();
+        for shard in self.shards.iter() {
+            for (key, chain) in shard.data.iter() {
+                chain.with_loaded(self.spill.as_ref(), |chain| {
+                    for sv in chain.versions.iter().rev() {
+                        let version = sv.version().as_u64();
+                        if version > after && version <= up_to {
+                            out.push((key.clone(), sv.clone()));
+                        }
+                    }
+                })?;
+            }
+        }
+        Ok(out)
+    }
+
+    // ========================================================================
+    // Expiry
+    // ========================================================================
+
+    /// Take the keys whose TTL ran out at or before `now` out of the index
+    ///
+    /// The index is a hint: a key may have been overwritten or deleted since
+    /// it was indexed, so check [`expired_version`](Self::expired_version)
+    /// before acting on it.
+    pub fn take_expired(&self, now: Timestamp) -> Vec<Key> {
+        let mut index = self.ttl_index.lock().unwrap();
+        let expired = index.find_expired(now);
+        index.remove_expired(now);
+        expired
+    }
+
+    /// Put keys back in the expiry index, e.g. after a failed cleanup
+    pub fn reindex_expired(&self, keys: Vec<Key>, expiry: Timestamp) {
+        let mut index = self.ttl_index.lock().unwrap();
+        for key in keys {
+            index.insert(expiry, key);
+        }
+    }
+
+    /// Earliest expiry time of any indexed value, if any
+    pub fn next_expiry(&self) -> Option<Timestamp> {
+        self.ttl_index.lock().unwrap().next_expiry()
+    }
+
+    /// Version of `key`'s latest value on its own branch if that value had
+    /// expired at `now`
+    ///
+    /// Returns `None` when the key is missing, deleted, or still live.
+    pub fn expired_version(&self, key: &Key, now: Timestamp) -> StrataResult<Option<u64>> {
+        let Some(shard) = self.shards.get(&key.namespace.branch_id) else {
+            return Ok(None);
+        };
+        let Some(chain) = shard.data.get(key) else {
+            return Ok(None);
+        };
+        chain.with_loaded(self.spill.as_ref(), |chain| {
+            chain
+                .latest()
+                .filter(|sv| !sv.is_tombstone() && sv.is_expired_at(now))
+                .map(|sv| sv.version().as_u64())
+        })
     }
 
     // ========================================================================
@@ -636,27 +1258,10 @@ impl ShardedStore {
     /// # Returns
     ///
     /// Vector of (Key, VersionedValue) pairs, sorted by key
-    pub fn list_branch(&self, branch_id: &BranchId) -> Vec<(Key, VersionedValue)> {
-        self.shards
-            .get(branch_id)
-            .map(|shard| {
-                shard
-                    .ordered_keys
-                    .iter()
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.latest().and_then(|sv| {
-                                if !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default()
+    pub fn list_branch(&self, branch_id: &BranchId) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        self.collect_visible(branch_id, KeyFilter::All, ReadBound::LATEST, |sv| {
+            !sv.is_tombstone()
+        })
     }
 
     /// List entries matching a key prefix
@@ -674,29 +1279,14 @@ impl ShardedStore {
     /// # Returns
     ///
     /// Vector of (Key, VersionedValue) pairs matching prefix, sorted by key
-    pub fn list_by_prefix(&self, prefix: &Key) -> Vec<(Key, VersionedValue)> {
-        let branch_id = prefix.namespace.branch_id;
-
-        self.shards
-            .get(&branch_id)
-            .map(|shard| {
-                shard
-                    .keys_with_prefix(prefix)
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.latest().and_then(|sv| {
-                                if !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default()
-    }
+    pub fn list_by_prefix(&self, prefix: &Key) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        self.collect_visible(
+            &prefix.namespace.branch_id,
+            KeyFilter::Prefix(prefix),
+            ReadBound::LATEST,
+            |sv| !sv.is_tombstone(),
+        )
+    }
 
     /// List entries of a specific type for a branch
     ///
@@ -714,28 +1304,80 @@ impl ShardedStore {
         &self,
         branch_id: &BranchId,
         type_tag: strata_core::types::TypeTag,
-    ) -> Vec<(Key, VersionedValue)> {
-        self.shards
-            .get(branch_id)
-            .map(|shard| {
-                shard
-                    .ordered_keys
-                    .iter()
-                    .filter(|k| k.type_tag == type_tag)
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.latest().and_then(|sv| {
-                                if !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default()
+    ) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        self.collect_visible(
+            branch_id,
+            KeyFilter::Type(type_tag),
+            ReadBound::LATEST,
+            |sv| !sv.is_tombstone(),
+        )
+    }
+
+    /// List entries of a specific type for a branch as of a past version
+    ///
+    /// Walks each key's version chain to the newest version <= `max_version`,
+    /// filtering out tombstones and expired values. Used to reconstruct the
+    /// state of a branch at a fork point (e.g. as a three-way merge base).
+    ///
+    /// # Returns
+    ///
+    /// Vector of (Key, VersionedValue) pairs of the specified type, sorted
+    pub fn list_by_type_at_version(
+        &self,
+        branch_id: &BranchId,
+        type_tag: strata_core::types::TypeTag,
+        max_version: u64,
+    ) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        self.collect_visible(
+            branch_id,
+            KeyFilter::Type(type_tag),
+            ReadBound::at_version(max_version),
+            is_live,
+        )
+    }
+
+    /// List entries of a specific type for a branch as of a past timestamp
+    ///
+    /// Like [`list_by_type_at_version`](Self::list_by_type_at_version), but
+    /// bounded by commit timestamp (microseconds since epoch). Used to diff
+    /// a branch against its own or another branch's past state.
+    ///
+    /// # Returns
+    ///
+    /// Vector of (Key, VersionedValue) pairs of the specified type, sorted
+    pub fn list_by_type_at_timestamp(
+        &self,
+        branch_id: &BranchId,
+        type_tag: strata_core::types::TypeTag,
+        max_timestamp: u64,
+    ) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        self.collect_visible(
+            branch_id,
+            KeyFilter::Type(type_tag),
+            ReadBound::at_timestamp(max_timestamp),
+            live_at(expiry_clock(max_timestamp)),
+        )
+    }
+
+    /// Newest commit version visible on a branch at or before a timestamp
+    ///
+    /// Resolves every key of the branch (including inherited keys and
+    /// deletions) as of `max_timestamp` and returns the highest version
+    /// among them: the storage version whose view of the branch matches its
+    /// state at that time. Returns 0 if the branch had no data yet.
+    pub fn version_at_timestamp(
+        &self,
+        branch_id: &BranchId,
+        max_timestamp: u64,
+    ) -> StrataResult<u64> {
+        let mut max_version = 0u64;
+        self.visit_visible(
+            branch_id,
+            KeyFilter::All,
+            ReadBound::at_timestamp(max_timestamp),
+            |_, sv| max_version = max_version.max(sv.version().as_u64()),
+        )?;
+        Ok(max_version)
     }
 
     /// Count entries of a specific type for a branch (excludes tombstones)
@@ -743,24 +1385,19 @@ impl ShardedStore {
         &self,
         branch_id: &BranchId,
         type_tag: strata_core::types::TypeTag,
-    ) -> usize {
-        self.shards
-            .get(branch_id)
-            .map(|shard| {
-                shard
-                    .ordered_keys
-                    .iter()
-                    .filter(|k| {
-                        k.type_tag == type_tag
-                            && shard
-                                .data
-                                .get(*k)
-                                .and_then(|chain| chain.latest())
-                                .is_some_and(|sv| !sv.is_tombstone())
-                    })
-                    .count()
-            })
-            .unwrap_or(0)
+    ) -> StrataResult<usize> {
+        let mut count = 0;
+        self.visit_visible(
+            branch_id,
+            KeyFilter::Type(type_tag),
+            ReadBound::LATEST,
+            |_, sv| {
+                if !sv.is_tombstone() {
+                    count += 1;
+                }
+            },
+        )?;
+        Ok(count)
     }
 
     /// Iterate over all branches
@@ -775,7 +1412,300 @@ impl ShardedStore {
     /// Removes the entire shard for the given branch.
     /// Returns true if the branch existed and was removed.
     pub fn clear_branch(&self, branch_id: &BranchId) -> bool {
-        self.shards.remove(branch_id).is_some()
+        let Some((_, shard)) = self.shards.remove(branch_id) else {
+            return false;
+        };
+        if let Some(spill) = &self.spill {
+            for chain in shard.data.values() {
+                spill.sub_resident(chain.resident_size());
+                if let Some(spilled) = &chain.spilled {
+                    spill.release(spilled);
+                }
+            }
+        }
+        true
+    }
+
+    // ========================================================================
+    // Spill Tier
+    // ========================================================================
+
+    /// Evict versions to the spill tier if the store is over its budget
+    ///
+    /// Called after writes with no shard guard held. Only one eviction runs
+    /// at a time; writers that find one in progress carry on. Eviction stops
+    /// at 3/4 of the budget so a store hovering at its budget does not evict
+    /// on every write. If a run cannot be written, the versions stay in
+    /// memory and the next write tries again.
+    fn maybe_evict(&self) {
+        let Some(spill) = &self.spill else {
+            return;
+        };
+        if !spill.over_budget() {
+            return;
+        }
+        let Some(cold_before) = spill.begin_eviction() else {
+            return;
+        };
+
+        let target = spill.budget() / 4 * 3;
+        let passes = [
+            Eviction::OldVersions,
+            Eviction::ColdBefore(cold_before),
+            Eviction::Any,
+        ];
+        'passes: for pass in passes {
+            for branch_id in self.branch_ids() {
+                if spill.resident() <= target {
+                    break 'passes;
+                }
+                let Some(mut shard) = self.shards.get_mut(&branch_id) else {
+                    continue;
+                };
+                if Self::evict_shard(spill, &mut shard, pass, target).is_err() {
+                    break 'passes;
+                }
+            }
+        }
+        spill.end_eviction();
+    }
+
+    /// Move one shard's `pass` victims into a new spill run
+    ///
+    /// Each victim's existing spilled tail is merged into its new record, so
+    /// a chain always has at most one record on disk.
+    fn evict_shard(
+        spill: &SpillTier,
+        shard: &mut Shard,
+        pass: Eviction,
+        target: usize,
+    ) -> std::io::Result<()> {
+        let excess = spill.resident().saturating_sub(target);
+        let mut freed = 0;
+        let mut victims: Vec<(&mut VersionChain, usize)> = Vec::new();
+        let mut tails: Vec<Vec<StoredValue>> = Vec::new();
+
+        for chain in shard.data.values_mut() {
+            if freed >= excess {
+                break;
+            }
+            let keep = match pass {
+                Eviction::OldVersions => 1,
+                Eviction::ColdBefore(epoch) if chain.touched.load(Ordering::Relaxed) >= epoch => {
+                    continue
+                }
+                Eviction::ColdBefore(_) | Eviction::Any => 0,
+            };
+            if chain.versions.len() <= keep {
+                continue;
+            }
+            // A tail that cannot be read stays where it is; reads of the
+            // chain report the error.
+            let old_tail = match &chain.spilled {
+                Some(spilled) => match spill.load(spilled) {
+                    Ok(old_tail) => old_tail,
+                    Err(_) => continue,
+                },
+                None => Vec::new(),
+            };
+            let mut tail: Vec<StoredValue> = chain.versions.split_off(keep).into();
+            let moved = tail.len();
+            freed += tail.iter().map(StoredValue::approx_size).sum::<usize>();
+            tail.extend(old_tail);
+            victims.push((chain, moved));
+            tails.push(tail);
+        }
+        if victims.is_empty() {
+            return Ok(());
+        }
+
+        match spill.write_run(&tails) {
+            Ok(refs) => {
+                for ((chain, _), spilled) in victims.into_iter().zip(refs) {
+                    if let Some(old) = chain.spilled.replace(spilled) {
+                        spill.release(&old);
+                    }
+                }
+                spill.sub_resident(freed);
+                Ok(())
+            }
+            Err(e) => {
+                for ((chain, moved), mut tail) in victims.into_iter().zip(tails) {
+                    chain.versions.extend(tail.drain(..moved));
+                }
+                Err(e)
+            }
+        }
+    }
+
+    // ========================================================================
+    // Copy-on-write Lineage
+    // ========================================================================
+
+    /// Ancestry a branch reads through, nearest first (empty unless forked)
+    pub fn branch_ancestry(&self, branch_id: &BranchId) -> Vec<BranchAncestor> {
+        self.shards
+            .get(branch_id)
+            .map(|shard| shard.ancestry.clone())
+            .unwrap_or_default()
+    }
+
+    /// Replace a branch's ancestry
+    ///
+    /// Linking a branch to its ancestors makes it a copy-on-write fork: no
+    /// data is copied, and anything the branch itself held at or before the
+    /// nearest ancestor's version is hidden. An empty ancestry detaches the
+    /// branch from its ancestors.
+    pub fn set_branch_ancestry(&self, branch_id: BranchId, ancestry: Vec<BranchAncestor>) {
+        if ancestry.is_empty() {
+            if let Some(mut shard) = self.shards.get_mut(&branch_id) {
+                shard.ancestry = ancestry;
+            }
+        } else {
+            self.shards.entry(branch_id).or_default().ancestry = ancestry;
+        }
+    }
+
+    /// Resolve a key through its branch's ancestry and map the visible version
+    ///
+    /// Returns the first version visible within `bound`, looking in the
+    /// key's own branch and then in each ancestor. Tombstones are visible
+    /// too, so a delete in a fork hides the inherited value. Only one shard
+    /// guard is held at a time.
+    fn resolve<R>(
+        &self,
+        key: &Key,
+        bound: ReadBound,
+        f: impl FnOnce(&StoredValue) -> R,
+    ) -> StrataResult<Option<R>> {
+        let ancestry = {
+            let Some(shard) = self.shards.get(&key.namespace.branch_id) else {
+                return Ok(None);
+            };
+            let own = bound.own(&shard.ancestry);
+            if let Some(chain) = shard.data.get(key) {
+                if let Some(sv) = self.select_point(chain, own)? {
+                    return Ok(Some(f(&sv)));
+                }
+            }
+            if shard.ancestry.is_empty() {
+                return Ok(None);
+            }
+            shard.ancestry.clone()
+        };
+
+        for (index, ancestor) in ancestry.iter().enumerate() {
+            let Some(shard) = self.shards.get(&ancestor.branch_id) else {
+                continue;
+            };
+            let inherited = bound.inherited(&ancestry, index);
+            let key = with_branch(key, ancestor.branch_id);
+            if let Some(chain) = shard.data.get(&key) {
+                if let Some(sv) = self.select_point(chain, inherited)? {
+                    return Ok(Some(f(&sv)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Select the version a point read sees, marking the chain as recently
+    /// used so eviction keeps it resident
+    fn select_point<'a>(
+        &self,
+        chain: &'a VersionChain,
+        bound: ReadBound,
+    ) -> StrataResult<Option<Cow<'a, StoredValue>>> {
+        let spill = self.spill.as_ref();
+        if let Some(spill) = spill {
+            chain.touched.store(spill.epoch(), Ordering::Relaxed);
+        }
+        bound.select(chain, spill)
+    }
+
+    /// Visit the visible version of every key selected by `filter`
+    ///
+    /// Each key is visited once, with the version that decides it (possibly
+    /// a tombstone or an expired value). Keys are reported in `branch_id`'s
+    /// namespace even when inherited. Without an ancestry keys are visited
+    /// in key order; with one, the branch's own keys come first. Stops at
+    /// the first spilled chain tail that cannot be read.
+    fn visit_visible(
+        &self,
+        branch_id: &BranchId,
+        filter: KeyFilter<'_>,
+        bound: ReadBound,
+        mut f: impl FnMut(&Key, &StoredValue),
+    ) -> StrataResult<()> {
+        let ancestry = {
+            let Some(shard) = self.shards.get(branch_id) else {
+                return Ok(());
+            };
+            let own = bound.own(&shard.ancestry);
+            if shard.ancestry.is_empty() {
+                return shard.for_each_chain(&filter, *branch_id, |k, chain| {
+                    if let Some(sv) = own.select(chain, self.spill.as_ref())? {
+                        f(k, &sv);
+                    }
+                    Ok(())
+                });
+            }
+            shard.ancestry.clone()
+        };
+
+        // Keys decided by a nearer branch, including those it deleted
+        let mut decided: FxHashSet<Key> = FxHashSet::default();
+        let levels = std::iter::once((*branch_id, bound.own(&ancestry))).chain(
+            ancestry
+                .iter()
+                .enumerate()
+                .map(|(index, a)| (a.branch_id, bound.inherited(&ancestry, index))),
+        );
+
+        for (level_branch, level_bound) in levels {
+            let Some(shard) = self.shards.get(&level_branch) else {
+                continue;
+            };
+            shard.for_each_chain(&filter, level_branch, |k, chain| {
+                let Some(sv) = level_bound.select(chain, self.spill.as_ref())? else {
+                    return Ok(());
+                };
+                let key = if level_branch == *branch_id {
+                    k.clone()
+                } else {
+                    with_branch(k, *branch_id)
+                };
+                if !decided.contains(&key) {
+                    f(&key, &sv);
+                    decided.insert(key);
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Collect visible entries selected by `filter` that pass `include`,
+    /// sorted by key
+    fn collect_visible(
+        &self,
+        branch_id: &BranchId,
+        filter: KeyFilter<'_>,
+        bound: ReadBound,
+        include: impl Fn(&StoredValue) -> bool,
+    ) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        let mut results = Vec::new();
+        let mut sorted = true;
+        self.visit_visible(branch_id, filter, bound, |k, sv| {
+            if include(sv) {
+                sorted = sorted && results.last().map_or(true, |(last, _)| last < k);
+                results.push((k.clone(), sv.versioned().clone()));
+            }
+        })?;
+        if !sorted {
+            results.sort_by(|(a, _), (b, _)| a.cmp(b));
+        }
+        Ok(results)
     }
 
     // ========================================================================
@@ -815,6 +1745,7 @@ impl ShardedStore {
     pub fn snapshot(self: &Arc<Self>) -> ShardedSnapshot {
         ShardedSnapshot {
             version: self.version.load(Ordering::Acquire),
+            timestamp: Timestamp::now(),
             store: Arc::clone(self),
         }
     }
@@ -867,11 +1798,12 @@ impl std::fmt::Debug for ShardedStore {
 /// Lightweight snapshot view for MVCC reads
 ///
 /// ShardedSnapshot is a thin wrapper that captures:
-/// - The version number at snapshot time
+/// - The version number and time at snapshot time
 /// - An Arc reference to the underlying store
 ///
-/// All reads delegate to `store.get_versioned(key, version)`, which walks
-/// the version chain to find the correct value. This provides:
+/// All reads walk the store's version chains up to the snapshot version,
+/// and treat values as expired if their TTL had run out by the snapshot
+/// time, so repeated reads agree even as TTLs elapse. This provides:
 /// - O(1) snapshot creation (just capture version + Arc clone)
 /// - O(1) snapshot cloning (derive Clone)
 /// - No unbounded memory growth (no per-snapshot cache)
@@ -892,6 +1824,8 @@ impl std::fmt::Debug for ShardedStore {
 pub struct ShardedSnapshot {
     /// Version captured at snapshot time
     version: u64,
+    /// Time the snapshot was taken; TTL expiry is evaluated at this time
+    timestamp: Timestamp,
     /// Reference to the underlying store
     store: Arc<ShardedStore>,
 }
@@ -905,16 +1839,24 @@ impl ShardedSnapshot {
         self.version
     }
 
+    /// Time the snapshot was taken
+    ///
+    /// Values written with a TTL are hidden if they had expired by then.
+    #[inline]
+    pub fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+
     // NOTE: `get()` is provided by the SnapshotView trait implementation,
     // which includes proper MVCC version filtering and TTL expiration checks.
     // Use `SnapshotView::get()` directly instead of an inherent method.
 
     /// Check if a key exists at or before the snapshot version
     #[inline]
-    pub fn contains(&self, key: &Key) -> bool {
+    pub fn contains(&self, key: &Key) -> StrataResult<bool> {
         // Use the SnapshotView trait method for proper version filtering
         use strata_core::traits::SnapshotView;
-        SnapshotView::get(self, key).ok().flatten().is_some()
+        Ok(SnapshotView::get(self, key)?.is_some())
     }
 
     /// List all entries for a branch at snapshot version
@@ -922,28 +1864,13 @@ impl ShardedSnapshot {
     /// Returns entries as they existed at the snapshot version,
     /// filtering out expired values and tombstones.
     /// Results are sorted by key (BTreeSet iteration order).
-    pub fn list_branch(&self, branch_id: &BranchId) -> Vec<(Key, VersionedValue)> {
-        self.store
-            .shards
-            .get(branch_id)
-            .map(|shard| {
-                shard
-                    .ordered_keys
-                    .iter()
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.get_at_version(self.version).and_then(|sv| {
-                                if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default()
+    pub fn list_branch(&self, branch_id: &BranchId) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        self.store.collect_visible(
+            branch_id,
+            KeyFilter::All,
+            ReadBound::at_version(self.version),
+            live_at(self.timestamp),
+        )
     }
 
     /// List entries matching a prefix at snapshot version
@@ -951,28 +1878,13 @@ impl ShardedSnapshot {
     /// Returns entries as they existed at the snapshot version,
     /// filtering out expired values and tombstones.
     /// Uses BTreeSet range scan for O(log n + k) performance.
-    pub fn list_by_prefix(&self, prefix: &Key) -> Vec<(Key, VersionedValue)> {
-        let branch_id = prefix.namespace.branch_id;
-        self.store
-            .shards
-            .get(&branch_id)
-            .map(|shard| {
-                shard
-                    .keys_with_prefix(prefix)
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.get_at_version(self.version).and_then(|sv| {
-                                if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default()
+    pub fn list_by_prefix(&self, prefix: &Key) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        self.store.collect_visible(
+            &prefix.namespace.branch_id,
+            KeyFilter::Prefix(prefix),
+            ReadBound::at_version(self.version),
+            live_at(self.timestamp),
+        )
     }
 
     /// List entries of a specific type at snapshot version
@@ -984,74 +1896,52 @@ impl ShardedSnapshot {
         &self,
         branch_id: &BranchId,
         type_tag: strata_core::types::TypeTag,
-    ) -> Vec<(Key, VersionedValue)> {
-        self.store
-            .shards
-            .get(branch_id)
-            .map(|shard| {
-                shard
-                    .ordered_keys
-                    .iter()
-                    .filter(|k| k.type_tag == type_tag)
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.get_at_version(self.version).and_then(|sv| {
-                                if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default()
+    ) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        self.store.collect_visible(
+            branch_id,
+            KeyFilter::Type(type_tag),
+            ReadBound::at_version(self.version),
+            live_at(self.timestamp),
+        )
     }
 
     /// Get count of entries for a branch at snapshot version
     ///
     /// Counts only entries that existed at the snapshot version
     /// (excludes tombstones and expired values).
-    pub fn branch_entry_count(&self, branch_id: &BranchId) -> usize {
-        self.store
-            .shards
-            .get(branch_id)
-            .map(|shard| {
-                shard
-                    .data
-                    .iter()
-                    .filter(|(_, chain)| {
-                        chain
-                            .get_at_version(self.version)
-                            .is_some_and(|sv| !sv.is_expired() && !sv.is_tombstone())
-                    })
-                    .count()
-            })
-            .unwrap_or(0)
+    pub fn branch_entry_count(&self, branch_id: &BranchId) -> StrataResult<usize> {
+        let mut count = 0;
+        self.store.visit_visible(
+            branch_id,
+            KeyFilter::All,
+            ReadBound::at_version(self.version),
+            |_, sv| {
+                if live_at(self.timestamp)(sv) {
+                    count += 1;
+                }
+            },
+        )?;
+        Ok(count)
     }
 
     /// Get total entries across all branches at snapshot version
     ///
     /// Counts only entries that existed at the snapshot version
     /// (excludes tombstones and expired values).
-    pub fn total_entries(&self) -> usize {
-        self.store
-            .shards
-            .iter()
-            .map(|entry| {
-                entry
-                    .value()
-                    .data
-                    .iter()
-                    .filter(|(_, chain)| {
-                        chain
-                            .get_at_version(self.version)
-                            .is_some_and(|sv| !sv.is_expired() && !sv.is_tombstone())
-                    })
-                    .count()
-            })
-            .sum()
+    pub fn total_entries(&self) -> StrataResult<usize> {
+        let bound = ReadBound::at_version(self.version);
+        let mut count = 0;
+        for entry in self.store.shards.iter() {
+            for chain in entry.value().data.values() {
+                if bound
+                    .select(chain, self.store.spill.as_ref())?
+                    .is_some_and(|sv| live_at(self.timestamp)(&sv))
+                {
+                    count += 1;
+                }
+            }
+        }
+        Ok(count)
     }
 
     /// Get number of branches (shards)
@@ -1084,43 +1974,31 @@ impl Storage for ShardedStore {
     ///
     /// Returns None if key doesn't exist, is expired, or is a tombstone.
     fn get(&self, key: &Key) -> StrataResult<Option<VersionedValue>> {
-        let branch_id = key.namespace.branch_id;
-        Ok(self.shards.get(&branch_id).and_then(|shard| {
-            shard.data.get(key).and_then(|chain| {
-                chain.latest().and_then(|sv| {
-                    // Filter out expired values and tombstones
-                    if !sv.is_expired() && !sv.is_tombstone() {
-                        Some(sv.versioned().clone())
-                    } else {
-                        None
-                    }
-                })
-            })
-        }))
+        // Filter out expired values and tombstones
+        Ok(self
+            .resolve(key, ReadBound::LATEST, |sv| {
+                is_live(sv).then(|| sv.versioned().clone())
+            })?
+            .flatten())
     }
 
     /// Get value at or before specified version (for snapshot isolation)
     ///
     /// Returns the value if version <= max_version, not expired, and not a tombstone.
     fn get_versioned(&self, key: &Key, max_version: u64) -> StrataResult<Option<VersionedValue>> {
-        let branch_id = key.namespace.branch_id;
-        Ok(self.shards.get(&branch_id).and_then(|shard| {
-            shard.data.get(key).and_then(|chain| {
-                chain.get_at_version(max_version).and_then(|sv| {
-                    // Filter out expired values and tombstones
-                    if !sv.is_expired() && !sv.is_tombstone() {
-                        Some(sv.versioned().clone())
-                    } else {
-                        None
-                    }
-                })
-            })
-        }))
+        // Filter out expired values and tombstones
+        Ok(self
+            .resolve(key, ReadBound::at_version(max_version), |sv| {
+                is_live(sv).then(|| sv.versioned().clone())
+            })?
+            .flatten())
     }
 
     /// Get version history for a key
     ///
     /// Returns historical versions newest first, filtered by limit and before_version.
+    /// Values whose TTL has run out are included: they were real versions,
+    /// and the tombstone written when they were reaped follows them.
     fn get_history(
         &self,
         key: &Key,
@@ -1130,20 +2008,62 @@ impl Storage for ShardedStore {
         let branch_id = key.namespace.branch_id;
 
         // Get the shard and extract history within the same scope to avoid lifetime issues
-        let result = match self.shards.get(&branch_id) {
-            Some(shard) => match shard.data.get(key) {
-                Some(chain) => chain
-                    .history(limit, before_version)
-                    .into_iter()
-                    .filter(|sv| !sv.is_expired())
-                    .map(|sv| sv.versioned().clone())
-                    .collect(),
-                None => Vec::new(),
-            },
-            None => Vec::new(),
+        let ancestry = match self.shards.get(&branch_id) {
+            Some(shard) if shard.ancestry.is_empty() => {
+                let result = match shard.data.get(key) {
+                    Some(chain) => chain.with_loaded(self.spill.as_ref(), |chain| {
+                        chain
+                            .history(limit, before_version)
+                            .into_iter()
+                            .map(|sv| sv.versioned().clone())
+                            .collect()
+                    })?,
+                    None => Vec::new(),
+                };
+                return Ok(result);
+            }
+            Some(shard) => shard.ancestry.clone(),
+            None => return Ok(Vec::new()),
         };
 
-        Ok(result)
+        // Forked branch: the branch's own versions, then each ancestor's
+        // versions up to its fork point. The windows don't overlap and get
+        // older at each level, so the result stays newest first.
+        let before = before_version.unwrap_or(u64::MAX);
+        let levels = std::iter::once((branch_id, ReadBound::LATEST.own(&ancestry))).chain(
+            ancestry
+                .iter()
+                .enumerate()
+                .map(|(index, a)| (a.branch_id, ReadBound::LATEST.inherited(&ancestry, index))),
+        );
+
+        let mut result = Vec::new();
+        for (level_branch, bound) in levels {
+            let Some(shard) = self.shards.get(&level_branch) else {
+                continue;
+            };
+            let level_key = with_branch(key, level_branch);
+            if let Some(chain) = shard.data.get(&level_key) {
+                chain.with_loaded(self.spill.as_ref(), |chain| {
+                    result.extend(
+                        chain
+                            .versions
+                            .iter()
+                            .filter(|sv| {
+                                let v = sv.version().as_u64();
+                                v >= bound.min_version && v <= bound.max_version && v < before
+                            })
+                            .cloned(),
+                    )
+                })?;
+            }
+        }
+
+        Ok(result
+            .into_iter()
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|sv| sv.versioned().clone())
+            .collect())
     }
 
     /// Put key-value pair with optional TTL
@@ -1163,7 +2083,7 @@ impl Storage for ShardedStore {
     ///
     /// Returns the latest version's value if it existed.
     fn delete(&self, key: &Key) -> StrataResult<Option<VersionedValue>> {
-        Ok(ShardedStore::delete(self, key))
+        ShardedStore::delete(self, key)
     }
 
     /// Scan keys with given prefix at or before max_version
@@ -1175,27 +2095,12 @@ impl Storage for ShardedStore {
         prefix: &Key,
         max_version: u64,
     ) -> StrataResult<Vec<(Key, VersionedValue)>> {
-        let branch_id = prefix.namespace.branch_id;
-        Ok(self
-            .shards
-            .get(&branch_id)
-            .map(|shard| {
-                shard
-                    .keys_with_prefix(prefix)
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.get_at_version(max_version).and_then(|sv| {
-                                if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default())
+        self.collect_visible(
+            &prefix.namespace.branch_id,
+            KeyFilter::Prefix(prefix),
+            ReadBound::at_version(max_version),
+            is_live,
+        )
     }
 
     /// Scan all keys for a given branch_id at or before max_version
@@ -1206,29 +2111,13 @@ impl Storage for ShardedStore {
         branch_id: BranchId,
         max_version: u64,
     ) -> StrataResult<Vec<(Key, VersionedValue)>> {
-        Ok(self
-            .shards
-            .get(&branch_id)
-            .map(|shard| {
-                let mut results: Vec<_> = shard
-                    .data
-                    .iter()
-                    .filter_map(|(k, chain)| {
-                        chain.get_at_version(max_version).and_then(|sv| {
-                            // Filter out expired values and tombstones
-                            if !sv.is_expired() && !sv.is_tombstone() {
-                                Some((k.clone(), sv.versioned().clone()))
-                            } else {
-                                None
-                            }
-                        })
-                    })
-                    .collect();
-
-                results.sort_by(|(a, _), (b, _)| a.cmp(b));
-                results
-            })
-            .unwrap_or_default())
+        // Filter out expired values and tombstones
+        self.collect_visible(
+            &branch_id,
+            KeyFilter::All,
+            ReadBound::at_version(max_version),
+            is_live,
+        )
     }
 
     /// Get current global version
@@ -1279,11 +2168,16 @@ use strata_core::traits::SnapshotView;
 impl SnapshotView for ShardedSnapshot {
     /// Get value from snapshot with MVCC version filtering
     ///
-    /// Delegates to `store.get_versioned(key, version)` which walks the
-    /// version chain to find the correct value at the snapshot version.
+    /// Walks the version chain to find the value at the snapshot version,
+    /// hiding values that had expired at the snapshot time.
     fn get(&self, key: &Key) -> StrataResult<Option<VersionedValue>> {
-        // Delegate to Storage::get_versioned for MVCC lookup
-        Storage::get_versioned(&*self.store, key, self.version)
+        let live = live_at(self.timestamp);
+        Ok(self
+            .store
+            .resolve(key, ReadBound::at_version(self.version), |sv| {
+                live(sv).then(|| sv.versioned().clone())
+            })?
+            .flatten())
     }
 
     /// Scan keys with prefix from snapshot
@@ -1291,28 +2185,12 @@ impl SnapshotView for ShardedSnapshot {
     /// Uses BTreeSet range scan for O(log n + k) performance.
     /// Returns all matching keys at or before snapshot version.
     fn scan_prefix(&self, prefix: &Key) -> StrataResult<Vec<(Key, VersionedValue)>> {
-        let branch_id = prefix.namespace.branch_id;
-        Ok(self
-            .store
-            .shards
-            .get(&branch_id)
-            .map(|shard| {
-                shard
-                    .keys_with_prefix(prefix)
-                    .filter_map(|k| {
-                        shard.data.get(k).and_then(|chain| {
-                            chain.get_at_version(self.version).and_then(|sv| {
-                                if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default())
+        self.store.collect_visible(
+            &prefix.namespace.branch_id,
+            KeyFilter::Prefix(prefix),
+            ReadBound::at_version(self.version),
+            live_at(self.timestamp),
+        )
     }
 
     /// Get snapshot version
@@ -1418,6 +2296,16 @@ mod tests {
         StoredValue::new(value, Version::txn(version), None)
     }
 
+    /// Link `child` to `parent` (and the parent's ancestry) at `version`
+    fn fork(store: &ShardedStore, parent: BranchId, child: BranchId, version: u64) {
+        let mut ancestry = vec![BranchAncestor {
+            branch_id: parent,
+            version,
+        }];
+        ancestry.extend(store.branch_ancestry(&parent));
+        store.set_branch_ancestry(child, ancestry);
+    }
+
     #[test]
     fn test_put_and_get() {
         use strata_core::value::Value;
@@ -1458,18 +2346,60 @@ mod tests {
         assert!(store.get(&key).unwrap().is_some());
 
         // Delete
-        let deleted = store.delete(&key);
+        let deleted = store.delete(&key).unwrap();
         assert!(deleted.is_some());
         assert!(store.get(&key).unwrap().is_none());
     }
 
+    #[test]
+    fn test_ttl_expiry_index_and_snapshot_time() {
+        use strata_core::value::Value;
+
+        let store = Arc::new(ShardedStore::new());
+        let branch_id = BranchId::new();
+        let key = create_test_key(branch_id, "lease");
+        let written = Timestamp::from_micros(Timestamp::now().as_micros() - 10_000);
+        let ttls = vec![(key.clone(), Duration::from_millis(5))];
+        store
+            .apply_batch_with_ttls(&[(key.clone(), Value::Int(1))], &[], &ttls, 1, written)
+            .unwrap();
+
+        let expiry = written.saturating_add(Duration::from_millis(5));
+        assert_eq!(store.next_expiry(), Some(expiry));
+        assert!(store.get(&key).unwrap().is_none());
+        assert_eq!(
+            store.expired_version(&key, Timestamp::now()).unwrap(),
+            Some(1)
+        );
+
+        // Reads of the past, and snapshots taken back then, still see it
+        let before = written.as_micros() + 1_000;
+        assert!(store.get_at_timestamp(&key, before).unwrap().is_some());
+        let snapshot = ShardedSnapshot {
+            version: 1,
+            timestamp: Timestamp::from_micros(before),
+            store: Arc::clone(&store),
+        };
+        assert!(SnapshotView::get(&snapshot, &key).unwrap().is_some());
+
+        assert!(store
+            .take_expired(Timestamp::from_micros(before))
+            .is_empty());
+        assert_eq!(store.take_expired(Timestamp::now()), vec![key.clone()]);
+        assert_eq!(store.next_expiry(), None);
+
+        // Once deleted, the key no longer counts as expired
+        store.delete_with_version(&key, 2).unwrap();
+        assert_eq!(store.expired_version(&key, Timestamp::now()).unwrap(), None);
+    }
+
     #[test]
     fn test_delete_nonexistent() {
         let store = ShardedStore::new();
         let branch_id = BranchId::new();
         let key = create_test_key(branch_id, "nonexistent");
 
-        assert!(store.delete(&key).is_none());
+        assert!(store.delete(&key).unwrap().is_none());
     }
 
     #[test]
@@ -1481,9 +2411,9 @@ mod tests {
         let key = create_test_key(branch_id, "exists");
         let value = create_stored_value(Value::Int(42), 1);
 
-        assert!(!store.contains(&key));
+        assert!(!store.contains(&key).unwrap());
         store.put(key.clone(), value);
-        assert!(store.contains(&key));
+        assert!(store.contains(&key).unwrap());
     }
 
     #[test]
@@ -1548,6 +2478,39 @@ mod tests {
         assert!(store.get(&key3).unwrap().is_none());
     }
 
+    #[test]
+    fn test_versions_between() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        let key = create_test_key(branch_id, "k");
+
+        store
+            .apply_batch(&[(key.clone(), Value::Int(1))], &[], 1)
+            .unwrap();
+        store
+            .apply_batch(&[], std::slice::from_ref(&key), 2)
+            .unwrap();
+        store
+            .apply_batch(&[(key.clone(), Value::Int(3))], &[], 3)
+            .unwrap();
+
+        let versions = |after, up_to| -> Vec<(u64, bool)> {
+            store
+                .versions_between(after, up_to)
+                .unwrap()
+                .iter()
+                .map(|(_, sv)| (sv.version().as_u64(), sv.is_tombstone()))
+                .collect()
+        };
+        assert_eq!(versions(0, 2), vec![(1, false), (2, true)]);
+        assert_eq!(versions(1, 3), vec![(2, true), (3, false)]);
+        assert_eq!(versions(0, 3).len(), 3);
+        assert!(versions(0, 0).is_empty());
+        assert!(versions(3, 3).is_empty());
+    }
+
     #[test]
     fn test_branch_entry_count() {
         use strata_core::value::Value;
@@ -1562,190 +2525,657 @@ mod tests {
             store.put(key, create_stored_value(Value::Int(i), 1));
         }
 
-        assert_eq!(store.branch_entry_count(&branch_id), 5);
-        assert_eq!(store.total_entries(), 5);
+        assert_eq!(store.branch_entry_count(&branch_id), 5);
+        assert_eq!(store.total_entries(), 5);
+    }
+
+    #[test]
+    fn test_concurrent_writes_different_branches() {
+        use std::thread;
+        use strata_core::value::Value;
+
+        let store = Arc::new(ShardedStore::new());
+
+        // 10 threads, each with its own branch, writing 100 keys
+        let handles: Vec<_> = (0..10)
+            .map(|_| {
+                let store = Arc::clone(&store);
+                thread::spawn(move || {
+                    let branch_id = BranchId::new();
+                    for i in 0..100 {
+                        let key = create_test_key(branch_id, &format!("key{}", i));
+                        store.put(key, create_stored_value(Value::Int(i), 1));
+                    }
+                    branch_id
+                })
+            })
+            .collect();
+
+        let branch_ids: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        // Verify each branch has 100 entries
+        for branch_id in &branch_ids {
+            assert_eq!(store.branch_entry_count(branch_id), 100);
+        }
+
+        assert_eq!(store.shard_count(), 10);
+        assert_eq!(store.total_entries(), 1000);
+    }
+
+    // ========================================================================
+    // List Operations Tests
+    // ========================================================================
+
+    #[test]
+    fn test_list_branch_empty() {
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+
+        let results = store.list_branch(&branch_id).unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_list_branch() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+
+        // Insert some keys
+        for i in 0..5 {
+            let key = create_test_key(branch_id, &format!("key{}", i));
+            store.put(key, create_stored_value(Value::Int(i), 1));
+        }
+
+        let results = store.list_branch(&branch_id).unwrap();
+        assert_eq!(results.len(), 5);
+
+        // Verify sorted order
+        for i in 0..results.len() - 1 {
+            assert!(results[i].0 < results[i + 1].0);
+        }
+    }
+
+    #[test]
+    fn test_list_by_prefix() {
+        use strata_core::types::Namespace;
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        let ns = Namespace::new(
+            "tenant".to_string(),
+            "app".to_string(),
+            "agent".to_string(),
+            branch_id,
+            "default".to_string(),
+        );
+
+        // Insert keys with different prefixes
+        store.put(
+            Key::new_kv(ns.clone(), "user:alice"),
+            create_stored_value(Value::Int(1), 1),
+        );
+        store.put(
+            Key::new_kv(ns.clone(), "user:bob"),
+            create_stored_value(Value::Int(2), 1),
+        );
+        store.put(
+            Key::new_kv(ns.clone(), "config:timeout"),
+            create_stored_value(Value::Int(3), 1),
+        );
+
+        // Query with "user:" prefix
+        let prefix = Key::new_kv(ns.clone(), "user:");
+        let results = store.list_by_prefix(&prefix).unwrap();
+
+        assert_eq!(results.len(), 2);
+        // Should be alice, bob in sorted order
+        assert!(results[0].0.user_key_string().unwrap().contains("alice"));
+        assert!(results[1].0.user_key_string().unwrap().contains("bob"));
+    }
+
+    #[test]
+    fn test_list_by_prefix_empty() {
+        use strata_core::types::Namespace;
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        let ns = Namespace::new(
+            "tenant".to_string(),
+            "app".to_string(),
+            "agent".to_string(),
+            branch_id,
+            "default".to_string(),
+        );
+
+        store.put(
+            Key::new_kv(ns.clone(), "data:foo"),
+            create_stored_value(Value::Int(1), 1),
+        );
+
+        // Query with non-matching prefix
+        let prefix = Key::new_kv(ns.clone(), "user:");
+        let results = store.list_by_prefix(&prefix).unwrap();
+
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_list_by_type() {
+        use strata_core::types::{Namespace, TypeTag};
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        let ns = Namespace::new(
+            "tenant".to_string(),
+            "app".to_string(),
+            "agent".to_string(),
+            branch_id,
+            "default".to_string(),
+        );
+
+        // Insert KV entries
+        store.put(
+            Key::new_kv(ns.clone(), "kv1"),
+            create_stored_value(Value::Int(1), 1),
+        );
+        store.put(
+            Key::new_kv(ns.clone(), "kv2"),
+            create_stored_value(Value::Int(2), 1),
+        );
+
+        // Insert Event entries
+        store.put(
+            Key::new_event(ns.clone(), 1),
+            create_stored_value(Value::Int(10), 1),
+        );
+
+        // Insert State entries
+        store.put(
+            Key::new_state(ns.clone(), "state1"),
+            create_stored_value(Value::Int(20), 1),
+        );
+
+        // Query by type
+        let kv_results = store.list_by_type(&branch_id, TypeTag::KV).unwrap();
+        assert_eq!(kv_results.len(), 2);
+
+        let event_results = store.list_by_type(&branch_id, TypeTag::Event).unwrap();
+        assert_eq!(event_results.len(), 1);
+
+        let state_results = store.list_by_type(&branch_id, TypeTag::State).unwrap();
+        assert_eq!(state_results.len(), 1);
+    }
+
+    #[test]
+    fn test_list_by_type_at_version() {
+        use strata_core::types::TypeTag;
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        let k1 = create_test_key(branch_id, "k1");
+        let k2 = create_test_key(branch_id, "k2");
+
+        store.put(k1.clone(), create_stored_value(Value::Int(1), 1));
+        store.put(k2.clone(), create_stored_value(Value::Int(2), 2));
+        store.put(k1.clone(), create_stored_value(Value::Int(10), 3));
+        store.delete_with_version(&k2, 4).unwrap();
+
+        // Version 1: only k1 at its first value
+        let at_1 = store
+            .list_by_type_at_version(&branch_id, TypeTag::KV, 1)
+            .unwrap();
+        assert_eq!(at_1.len(), 1);
+        assert_eq!(at_1[0].1.value, Value::Int(1));
+
+        // Version 3: k1 updated, k2 still live
+        let at_3 = store
+            .list_by_type_at_version(&branch_id, TypeTag::KV, 3)
+            .unwrap();
+        assert_eq!(at_3.len(), 2);
+        assert_eq!(at_3[0].1.value, Value::Int(10));
+
+        // Version 4: k2 tombstoned
+        let at_4 = store
+            .list_by_type_at_version(&branch_id, TypeTag::KV, 4)
+            .unwrap();
+        assert_eq!(at_4.len(), 1);
+        assert_eq!(at_4[0].0, k1);
+    }
+
+    #[test]
+    fn test_list_by_type_at_timestamp() {
+        use strata_core::types::TypeTag;
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        let k1 = create_test_key(branch_id, "k1");
+        let k2 = create_test_key(branch_id, "k2");
+        let at = |value: i64, version: u64, ts: u64| {
+            StoredValue::with_timestamp(
+                Value::Int(value),
+                Version::txn(version),
+                Timestamp::from_micros(ts),
+                None,
+            )
+        };
+
+        store.put(k1.clone(), at(1, 1, 100));
+        store.put(k1.clone(), at(10, 2, 200));
+        store.put(k2.clone(), at(2, 3, 300));
+
+        let at_150 = store
+            .list_by_type_at_timestamp(&branch_id, TypeTag::KV, 150)
+            .unwrap();
+        assert_eq!(at_150.len(), 1);
+        assert_eq!(at_150[0].1.value, Value::Int(1));
+
+        let at_300 = store
+            .list_by_type_at_timestamp(&branch_id, TypeTag::KV, 300)
+            .unwrap();
+        assert_eq!(at_300.len(), 2);
+        assert_eq!(at_300[0].1.value, Value::Int(10));
+
+        assert!(store
+            .list_by_type_at_timestamp(&branch_id, TypeTag::KV, 50)
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(store.version_at_timestamp(&branch_id, 50).unwrap(), 0);
+        assert_eq!(store.version_at_timestamp(&branch_id, 250).unwrap(), 2);
+        assert_eq!(store.version_at_timestamp(&branch_id, 300).unwrap(), 3);
+    }
+
+    // ========================================================================
+    // Copy-on-write Fork Tests
+    // ========================================================================
+
+    #[test]
+    fn test_fork_reads_fall_back_to_parent() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let parent = BranchId::new();
+        let child = BranchId::new();
+
+        store.put(
+            create_test_key(parent, "k1"),
+            create_stored_value(Value::Int(1), 1),
+        );
+        store.put(
+            create_test_key(parent, "k2"),
+            create_stored_value(Value::Int(2), 2),
+        );
+        store.set_version(2);
+
+        fork(&store, parent, child, 2);
+        // Nothing is copied into the child
+        assert_eq!(store.branch_entry_count(&child), 0);
+
+        let child_k1 = create_test_key(child, "k1");
+        let child_k2 = create_test_key(child, "k2");
+        assert_eq!(
+            Storage::get(&store, &child_k1).unwrap().unwrap().value,
+            Value::Int(1)
+        );
+        assert!(store.contains(&child_k2).unwrap());
+
+        // Parent writes after the fork are invisible to the child
+        store.put(
+            create_test_key(parent, "k1"),
+            create_stored_value(Value::Int(10), 3),
+        );
+        store.put(
+            create_test_key(parent, "k3"),
+            create_stored_value(Value::Int(3), 4),
+        );
+        assert_eq!(
+            Storage::get(&store, &child_k1).unwrap().unwrap().value,
+            Value::Int(1)
+        );
+        assert!(Storage::get(&store, &create_test_key(child, "k3"))
+            .unwrap()
+            .is_none());
+
+        // Child writes shadow the parent without touching it
+        store.put(child_k2.clone(), create_stored_value(Value::Int(20), 5));
+        store.delete_with_version(&child_k1, 6).unwrap();
+        assert_eq!(
+            Storage::get(&store, &child_k2).unwrap().unwrap().value,
+            Value::Int(20)
+        );
+        assert!(Storage::get(&store, &child_k1).unwrap().is_none());
+        assert_eq!(
+            Storage::get(&store, &create_test_key(parent, "k2"))
+                .unwrap()
+                .unwrap()
+                .value,
+            Value::Int(2)
+        );
+
+        // Versioned reads before the child's write still see the parent
+        assert_eq!(
+            Storage::get_versioned(&store, &child_k1, 5)
+                .unwrap()
+                .unwrap()
+                .value,
+            Value::Int(1)
+        );
     }
 
     #[test]
-    fn test_concurrent_writes_different_branches() {
-        use std::thread;
+    fn test_fork_scans_include_inherited_keys() {
+        use strata_core::types::TypeTag;
         use strata_core::value::Value;
 
         let store = Arc::new(ShardedStore::new());
+        let parent = BranchId::new();
+        let child = BranchId::new();
 
-        // 10 threads, each with its own branch, writing 100 keys
-        let handles: Vec<_> = (0..10)
-            .map(|_| {
-                let store = Arc::clone(&store);
-                thread::spawn(move || {
-                    let branch_id = BranchId::new();
-                    for i in 0..100 {
-                        let key = create_test_key(branch_id, &format!("key{}", i));
-                        store.put(key, create_stored_value(Value::Int(i), 1));
-                    }
-                    branch_id
-                })
-            })
-            .collect();
-
-        let branch_ids: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
-
-        // Verify each branch has 100 entries
-        for branch_id in &branch_ids {
-            assert_eq!(store.branch_entry_count(branch_id), 100);
+        for (i, name) in ["a", "b", "c"].iter().enumerate() {
+            store.put(
+                create_test_key(parent, name),
+                create_stored_value(Value::Int(i as i64), i as u64 + 1),
+            );
         }
+        store.set_version(3);
+        fork(&store, parent, child, 3);
 
-        assert_eq!(store.shard_count(), 10);
-        assert_eq!(store.total_entries(), 1000);
-    }
+        store.put(
+            create_test_key(child, "aa"),
+            create_stored_value(Value::Int(10), 4),
+        );
+        store
+            .delete_with_version(&create_test_key(child, "b"), 5)
+            .unwrap();
 
-    // ========================================================================
-    // List Operations Tests
-    // ========================================================================
+        let expected = vec![
+            create_test_key(child, "a"),
+            create_test_key(child, "aa"),
+            create_test_key(child, "c"),
+        ];
+        let keys = |entries: Vec<(Key, VersionedValue)>| {
+            entries.into_iter().map(|(k, _)| k).collect::<Vec<_>>()
+        };
 
-    #[test]
-    fn test_list_branch_empty() {
-        let store = ShardedStore::new();
-        let branch_id = BranchId::new();
+        assert_eq!(keys(store.list_branch(&child).unwrap()), expected);
+        assert_eq!(
+            keys(store.list_by_type(&child, TypeTag::KV).unwrap()),
+            expected
+        );
+        assert_eq!(store.count_by_type(&child, TypeTag::KV).unwrap(), 3);
+        assert_eq!(
+            keys(Storage::scan_by_branch(&*store, child, u64::MAX).unwrap()),
+            expected
+        );
 
-        let results = store.list_branch(&branch_id);
-        assert!(results.is_empty());
+        let prefix = create_test_key(child, "a");
+        assert_eq!(
+            keys(Storage::scan_prefix(&*store, &prefix, u64::MAX).unwrap()),
+            expected[..2].to_vec()
+        );
+        assert_eq!(
+            keys(store.list_by_prefix(&prefix).unwrap()),
+            expected[..2].to_vec()
+        );
+
+        // A snapshot taken before the child's writes sees only inherited keys
+        let snapshot = ShardedSnapshot {
+            version: 3,
+            timestamp: Timestamp::now(),
+            store: Arc::clone(&store),
+        };
+        assert_eq!(snapshot.list_branch(&child).unwrap().len(), 3);
+        assert_eq!(snapshot.branch_entry_count(&child).unwrap(), 3);
+        assert_eq!(
+            keys(SnapshotView::scan_prefix(&snapshot, &prefix).unwrap()),
+            vec![create_test_key(child, "a")]
+        );
     }
 
     #[test]
-    fn test_list_branch() {
+    fn test_scan_prefix_after_pages_through_fork() {
         use strata_core::value::Value;
 
-        let store = ShardedStore::new();
-        let branch_id = BranchId::new();
+        let store = Arc::new(ShardedStore::new());
+        let parent = BranchId::new();
+        let child = BranchId::new();
 
-        // Insert some keys
-        for i in 0..5 {
-            let key = create_test_key(branch_id, &format!("key{}", i));
-            store.put(key, create_stored_value(Value::Int(i), 1));
+        for i in 0..10u64 {
+            store.put(
+                create_test_key(parent, &format!("k{}", i)),
+                create_stored_value(Value::Int(i as i64), i + 1),
+            );
         }
+        store.set_version(10);
+        fork(&store, parent, child, 10);
+
+        // The child deletes most inherited keys and adds one of its own
+        let mut version = 10;
+        for i in [0, 1, 2, 4, 5, 7] {
+            version += 1;
+            store
+                .delete_with_version(&create_test_key(child, &format!("k{}", i)), version)
+                .unwrap();
+        }
+        store.put(
+            create_test_key(child, "k55"),
+            create_stored_value(Value::Int(55), version + 1),
+        );
 
-        let results = store.list_branch(&branch_id);
-        assert_eq!(results.len(), 5);
+        let prefix = create_test_key(child, "k");
+        let expected = store.list_by_prefix(&prefix).unwrap();
+        assert_eq!(expected.len(), 5);
 
-        // Verify sorted order
-        for i in 0..results.len() - 1 {
-            assert!(results[i].0 < results[i + 1].0);
+        for page_size in 1..=6 {
+            let mut paged = Vec::new();
+            let mut after: Option<Key> = None;
+            loop {
+                let page = store
+                    .scan_prefix_after(&prefix, after.as_ref(), None, page_size)
+                    .unwrap();
+                assert!(page.len() <= page_size);
+                let full = page.len() == page_size;
+                after = page.last().map(|(k, _)| k.clone());
+                paged.extend(page);
+                if !full {
+                    break;
+                }
+            }
+            assert_eq!(paged, expected, "page size {}", page_size);
         }
     }
 
     #[test]
-    fn test_list_by_prefix() {
-        use strata_core::types::Namespace;
+    fn test_fork_of_fork_reads_through_ancestry() {
         use strata_core::value::Value;
 
         let store = ShardedStore::new();
-        let branch_id = BranchId::new();
-        let ns = Namespace::new(
-            "tenant".to_string(),
-            "app".to_string(),
-            "agent".to_string(),
-            branch_id,
-            "default".to_string(),
-        );
+        let root = BranchId::new();
+        let middle = BranchId::new();
+        let leaf = BranchId::new();
 
-        // Insert keys with different prefixes
         store.put(
-            Key::new_kv(ns.clone(), "user:alice"),
+            create_test_key(root, "k"),
             create_stored_value(Value::Int(1), 1),
         );
+        store.set_version(1);
+        fork(&store, root, middle, 1);
+
+        // Written to the root after the first fork: invisible downstream
         store.put(
-            Key::new_kv(ns.clone(), "user:bob"),
-            create_stored_value(Value::Int(2), 1),
+            create_test_key(root, "late"),
+            create_stored_value(Value::Int(2), 2),
         );
         store.put(
-            Key::new_kv(ns.clone(), "config:timeout"),
-            create_stored_value(Value::Int(3), 1),
+            create_test_key(middle, "m"),
+            create_stored_value(Value::Int(3), 3),
         );
+        store.set_version(3);
 
-        // Query with "user:" prefix
-        let prefix = Key::new_kv(ns.clone(), "user:");
-        let results = store.list_by_prefix(&prefix);
+        fork(&store, middle, leaf, 3);
 
-        assert_eq!(results.len(), 2);
-        // Should be alice, bob in sorted order
-        assert!(results[0].0.user_key_string().unwrap().contains("alice"));
-        assert!(results[1].0.user_key_string().unwrap().contains("bob"));
+        assert!(store.contains(&create_test_key(leaf, "k")).unwrap());
+        assert!(store.contains(&create_test_key(leaf, "m")).unwrap());
+        assert!(!store.contains(&create_test_key(leaf, "late")).unwrap());
+        assert_eq!(store.list_branch(&leaf).unwrap().len(), 2);
+
+        // The leaf keeps its view even if the middle branch is detached
+        store.set_branch_ancestry(middle, Vec::new());
+        assert!(store.contains(&create_test_key(leaf, "k")).unwrap());
     }
 
     #[test]
-    fn test_list_by_prefix_empty() {
-        use strata_core::types::Namespace;
+    fn test_fork_hides_stale_destination_data() {
         use strata_core::value::Value;
 
         let store = ShardedStore::new();
-        let branch_id = BranchId::new();
-        let ns = Namespace::new(
-            "tenant".to_string(),
-            "app".to_string(),
-            "agent".to_string(),
-            branch_id,
-            "default".to_string(),
-        );
+        let parent = BranchId::new();
+        let child = BranchId::new();
 
+        // Leftovers from an earlier branch that used the same id
         store.put(
-            Key::new_kv(ns.clone(), "data:foo"),
-            create_stored_value(Value::Int(1), 1),
+            create_test_key(child, "old"),
+            create_stored_value(Value::Int(0), 1),
         );
+        store
+            .delete_with_version(&create_test_key(child, "k"), 2)
+            .unwrap();
+        store.put(
+            create_test_key(parent, "k"),
+            create_stored_value(Value::Int(1), 3),
+        );
+        store.set_version(3);
 
-        // Query with non-matching prefix
-        let prefix = Key::new_kv(ns.clone(), "user:");
-        let results = store.list_by_prefix(&prefix);
-
-        assert!(results.is_empty());
+        fork(&store, parent, child, 3);
+        assert!(!store.contains(&create_test_key(child, "old")).unwrap());
+        assert!(store.contains(&create_test_key(child, "k")).unwrap());
     }
 
     #[test]
-    fn test_list_by_type() {
-        use strata_core::types::{Namespace, TypeTag};
+    fn test_fork_history_spans_ancestry() {
         use strata_core::value::Value;
 
         let store = ShardedStore::new();
-        let branch_id = BranchId::new();
-        let ns = Namespace::new(
-            "tenant".to_string(),
-            "app".to_string(),
-            "agent".to_string(),
-            branch_id,
-            "default".to_string(),
-        );
+        let parent = BranchId::new();
+        let child = BranchId::new();
+        let parent_key = create_test_key(parent, "k");
+        let child_key = create_test_key(child, "k");
 
-        // Insert KV entries
-        store.put(
-            Key::new_kv(ns.clone(), "kv1"),
-            create_stored_value(Value::Int(1), 1),
+        store.put(parent_key.clone(), create_stored_value(Value::Int(1), 1));
+        store.put(parent_key.clone(), create_stored_value(Value::Int(2), 2));
+        store.set_version(2);
+        fork(&store, parent, child, 2);
+        store.put(parent_key, create_stored_value(Value::Int(3), 3));
+        store.put(child_key.clone(), create_stored_value(Value::Int(4), 4));
+
+        let versions = |history: Vec<VersionedValue>| {
+            history
+                .into_iter()
+                .map(|vv| vv.version.as_u64())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            versions(Storage::get_history(&store, &child_key, None, None).unwrap()),
+            vec![4, 2, 1]
         );
-        store.put(
-            Key::new_kv(ns.clone(), "kv2"),
-            create_stored_value(Value::Int(2), 1),
+        assert_eq!(
+            versions(Storage::get_history(&store, &child_key, Some(2), Some(4)).unwrap()),
+            vec![2, 1]
         );
+    }
 
-        // Insert Event entries
-        store.put(
-            Key::new_event(ns.clone(), 1),
-            create_stored_value(Value::Int(10), 1),
-        );
+    #[test]
+    fn test_gc_keeps_versions_pinned_by_fork() {
+        use strata_core::value::Value;
 
-        // Insert State entries
-        store.put(
-            Key::new_state(ns.clone(), "state1"),
-            create_stored_value(Value::Int(20), 1),
+        let store = ShardedStore::new();
+        let parent = BranchId::new();
+        let child = BranchId::new();
+        let parent_key = create_test_key(parent, "k");
+
+        store.put(parent_key.clone(), create_stored_value(Value::Int(1), 1));
+        store.put(parent_key.clone(), create_stored_value(Value::Int(2), 2));
+        store.set_version(2);
+        fork(&store, parent, child, 2);
+        store.put(parent_key.clone(), create_stored_value(Value::Int(3), 3));
+        store.put(parent_key.clone(), create_stored_value(Value::Int(4), 4));
+
+        // Versions 1 and 3 go; 2 is what the child sees, 4 is the latest
+        assert_eq!(store.gc_branch(parent, 4).unwrap(), 2);
+        assert_eq!(
+            Storage::get(&store, &create_test_key(child, "k"))
+                .unwrap()
+                .unwrap()
+                .value,
+            Value::Int(2)
         );
+        assert_eq!(
+            Storage::get(&store, &parent_key).unwrap().unwrap().value,
+            Value::Int(4)
+        );
+    }
 
-        // Query by type
-        let kv_results = store.list_by_type(&branch_id, TypeTag::KV);
-        assert_eq!(kv_results.len(), 2);
+    #[test]
+    fn test_prune_versions_keeps_latest_and_pins() {
+        use strata_core::value::Value;
 
-        let event_results = store.list_by_type(&branch_id, TypeTag::Event);
-        assert_eq!(event_results.len(), 1);
+        let store = ShardedStore::new();
+        let parent = BranchId::new();
+        let child = BranchId::new();
+        let key = create_test_key(parent, "k");
+        let other = create_test_key(parent, "other");
 
-        let state_results = store.list_by_type(&branch_id, TypeTag::State);
-        assert_eq!(state_results.len(), 1);
+        for version in 1..=3 {
+            store.put(
+                key.clone(),
+                create_stored_value(Value::Int(version as i64), version),
+            );
+        }
+        store.set_version(3);
+        fork(&store, parent, child, 3);
+        store.put(key.clone(), create_stored_value(Value::Int(4), 4));
+        store.put(key.clone(), create_stored_value(Value::Int(5), 5));
+        store.put(other.clone(), create_stored_value(Value::Int(6), 6));
+        assert_eq!(store.fork_pins(&parent), vec![3]);
+
+        let mut seen = Vec::new();
+        store
+            .visit_branch_versions(&parent, |k, versions| {
+                if *k == key {
+                    seen = versions.iter().map(|sv| sv.version().as_u64()).collect();
+                }
+            })
+            .unwrap();
+        assert_eq!(seen, vec![5, 4, 3, 2, 1]);
+        let mut child_keys = 0;
+        store
+            .visit_branch_versions(&child, |_, _| child_keys += 1)
+            .unwrap();
+        assert_eq!(child_keys, 0);
+
+        // 3 is pinned by the fork and 6 is the latest of its key
+        let (pruned, freed) = store
+            .prune_versions(
+                &parent,
+                &[(key.clone(), vec![1, 3, 4]), (other.clone(), vec![6])],
+            )
+            .unwrap();
+        assert_eq!(pruned, 2);
+        assert!(freed > 0);
+        let versions = Storage::get_history(&store, &key, None, None)
+            .unwrap()
+            .into_iter()
+            .map(|vv| vv.version.as_u64())
+            .collect::<Vec<_>>();
+        assert_eq!(versions, vec![5, 3, 2]);
+        assert!(Storage::get(&store, &other).unwrap().is_some());
     }
 
     #[test]
@@ -1777,9 +3207,9 @@ mod tests {
             );
         }
 
-        assert_eq!(store.count_by_type(&branch_id, TypeTag::KV), 5);
-        assert_eq!(store.count_by_type(&branch_id, TypeTag::Event), 3);
-        assert_eq!(store.count_by_type(&branch_id, TypeTag::State), 0);
+        assert_eq!(store.count_by_type(&branch_id, TypeTag::KV).unwrap(), 5);
+        assert_eq!(store.count_by_type(&branch_id, TypeTag::Event).unwrap(), 3);
+        assert_eq!(store.count_by_type(&branch_id, TypeTag::State).unwrap(), 0);
     }
 
     #[test]
@@ -1868,7 +3298,7 @@ mod tests {
             );
         }
 
-        let results = store.list_branch(&branch_id);
+        let results = store.list_branch(&branch_id).unwrap();
         let result_keys: Vec<_> = results
             .iter()
             .map(|(k, _)| k.user_key_string().unwrap())
@@ -1931,7 +3361,7 @@ mod tests {
         assert_eq!(value.unwrap().value, Value::Int(42));
 
         // contains works
-        assert!(snapshot.contains(&key));
+        assert!(snapshot.contains(&key).unwrap());
     }
 
     #[test]
@@ -1953,14 +3383,14 @@ mod tests {
         assert_eq!(snapshot.version(), 1);
 
         // list_branch works - sees data at version 1
-        let results = snapshot.list_branch(&branch_id);
+        let results = snapshot.list_branch(&branch_id).unwrap();
         assert_eq!(results.len(), 5);
 
         // branch_entry_count works
-        assert_eq!(snapshot.branch_entry_count(&branch_id), 5);
+        assert_eq!(snapshot.branch_entry_count(&branch_id).unwrap(), 5);
 
         // total_entries works
-        assert_eq!(snapshot.total_entries(), 5);
+        assert_eq!(snapshot.total_entries().unwrap(), 5);
     }
 
     #[test]
@@ -3371,4 +4801,196 @@ mod tests {
         let results_none = Storage::scan_prefix(&store, &prefix_none, u64::MAX).unwrap();
         assert_eq!(results_none.len(), 0, "gamma: prefix should match 0 keys");
     }
+
+    // ========================================================================
+    // Spill Tier
+    // ========================================================================
+
+    fn spilling_store(dir: &tempfile::TempDir, memory_budget: usize) -> ShardedStore {
+        ShardedStore::with_spill(SpillConfig {
+            dir: dir.path().to_path_buf(),
+            memory_budget,
+        })
+        .unwrap()
+    }
+
+    /// 100 keys with versions 3k+1..=3k+3, each value ~200 bytes
+    fn write_versions(store: &ShardedStore, branch_id: BranchId) {
+        use strata_core::value::Value;
+
+        let mut version = 0;
+        for round in 0..3 {
+            for i in 0..100 {
+                version += 1;
+                let key = create_test_key(branch_id, &format!("key:{:03}", i));
+                let value = Value::String(format!("{}:{}", round, "x".repeat(200)));
+                let stored = StoredValue::with_timestamp(
+                    value,
+                    Version::txn(version),
+                    Timestamp::from_micros(version * 1000),
+                    None,
+                );
+                store.put(key, stored);
+            }
+        }
+        store.set_version(version);
+    }
+
+    #[test]
+    fn test_spill_keeps_reads_identical() {
+        use strata_core::value::Value;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = spilling_store(&dir, 16 * 1024);
+        let memory = ShardedStore::new();
+        let branch_id = BranchId::new();
+        write_versions(&store, branch_id);
+        write_versions(&memory, branch_id);
+
+        let stats = store.spill_stats().unwrap();
+        assert!(stats.evictions > 0);
+        assert!(stats.runs > 0);
+        assert!(stats.resident_bytes <= 16 * 1024);
+        assert!(memory.spill_stats().is_none());
+
+        for i in 0..100 {
+            let key = create_test_key(branch_id, &format!("key:{:03}", i));
+            assert_eq!(
+                Storage::get(&store, &key).unwrap(),
+                Storage::get(&memory, &key).unwrap()
+            );
+            for version in [i + 1, i + 101, 250] {
+                assert_eq!(
+                    Storage::get_versioned(&store, &key, version).unwrap(),
+                    Storage::get_versioned(&memory, &key, version).unwrap()
+                );
+            }
+            assert_eq!(
+                store.get_at_timestamp(&key, (i + 1) * 1000).unwrap(),
+                memory.get_at_timestamp(&key, (i + 1) * 1000).unwrap()
+            );
+            assert_eq!(
+                Storage::get_history(&store, &key, None, None).unwrap(),
+                Storage::get_history(&memory, &key, None, None).unwrap()
+            );
+        }
+
+        let prefix = create_test_key(branch_id, "key:");
+        for version in [50, 150, u64::MAX] {
+            assert_eq!(
+                Storage::scan_prefix(&store, &prefix, version).unwrap(),
+                Storage::scan_prefix(&memory, &prefix, version).unwrap()
+            );
+        }
+        assert_eq!(store.versions_between(0, 300).unwrap().len(), 300);
+        assert_eq!(
+            store.versions_between(100, 200).unwrap(),
+            memory.versions_between(100, 200).unwrap()
+        );
+        assert_eq!(
+            store.time_range(branch_id).unwrap(),
+            memory.time_range(branch_id).unwrap()
+        );
+
+        // Writes after eviction land in front of the spilled versions
+        let key = create_test_key(branch_id, "key:000");
+        store.put(key.clone(), create_stored_value(Value::Int(7), 301));
+        assert_eq!(
+            Storage::get(&store, &key).unwrap().unwrap().value,
+            Value::Int(7)
+        );
+        assert_eq!(
+            Storage::get_history(&store, &key, None, None)
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_spill_fork_reads_spilled_parent_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = spilling_store(&dir, 16 * 1024);
+        let memory = ShardedStore::new();
+        let parent = BranchId::new();
+        let child = BranchId::new();
+        for store in [&store, &memory] {
+            write_versions(store, parent);
+            fork(store, parent, child, 150);
+        }
+
+        for i in 0..100 {
+            let key = create_test_key(child, &format!("key:{:03}", i));
+            assert_eq!(
+                Storage::get(&store, &key).unwrap(),
+                Storage::get(&memory, &key).unwrap()
+            );
+            assert_eq!(
+                Storage::get_history(&store, &key, None, None).unwrap(),
+                Storage::get_history(&memory, &key, None, None).unwrap()
+            );
+        }
+        assert_eq!(
+            store.list_branch(&child).unwrap(),
+            memory.list_branch(&child).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_spill_gc_and_clear_branch_release_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = spilling_store(&dir, 16 * 1024);
+        let branch_id = BranchId::new();
+        write_versions(&store, branch_id);
+        assert!(store.spill_stats().unwrap().runs > 0);
+
+        assert_eq!(store.gc_branch(branch_id, 201).unwrap(), 200);
+        let key = create_test_key(branch_id, "key:042");
+        assert_eq!(
+            Storage::get_history(&store, &key, None, None)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        assert!(store.clear_branch(&branch_id));
+        let stats = store.spill_stats().unwrap();
+        assert_eq!(stats.runs, 0);
+        assert_eq!(stats.spilled_bytes, 0);
+        assert_eq!(stats.resident_bytes, 0);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_spill_corrupt_run_fails_reads() {
+        use strata_core::StrataError;
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = spilling_store(&dir, 16 * 1024);
+        let branch_id = BranchId::new();
+        write_versions(&store, branch_id);
+
+        // Damage every record, leaving the run headers intact
+        for entry in std::fs::read_dir(dir.path()).unwrap() {
+            let path = entry.unwrap().path();
+            let mut bytes = std::fs::read(&path).unwrap();
+            bytes[8..].iter_mut().for_each(|b| *b ^= 0xFF);
+            std::fs::write(&path, bytes).unwrap();
+        }
+
+        let key = create_test_key(branch_id, "key:000");
+        let corrupt = |e: StrataError| matches!(e, StrataError::Corruption { .. });
+        assert!(corrupt(
+            Storage::get_versioned(&store, &key, 1).unwrap_err()
+        ));
+        assert!(corrupt(
+            Storage::get_history(&store, &key, None, None).unwrap_err()
+        ));
+        let prefix = create_test_key(branch_id, "key:");
+        assert!(corrupt(
+            Storage::scan_prefix(&store, &prefix, 50).unwrap_err()
+        ));
+        assert!(corrupt(store.versions_between(0, 300).unwrap_err()));
+        assert!(corrupt(store.gc_branch(branch_id, 201).unwrap_err()));
+    }
 }
@@ -0,0 +1,398 @@
+//! On-disk spill tier for ShardedStore
+//!
+//! With a memory budget, `ShardedStore` keeps only part of its version
+//! chains in RAM. When the values held in memory exceed the budget, an
+//! eviction pass moves versions into immutable run files on disk:
+//!
+//! 1. Old versions: everything but the newest version of each key
+//! 2. Cold keys: whole chains not read or written since the previous pass
+//! 3. Any remaining keys
+//!
+//! The spilled part of a chain is always older than its resident part, so
+//! a read looks at the resident versions first and only reads the disk when
+//! it needs an older version. New writes go to the resident front of the
+//! chain; nothing is read back to accept a write.
+//!
+//! Keys, the ordered key index and branch ancestry stay in memory; the
+//! budget covers values only.
+//!
+//! # Run file format
+//!
+//! ```text
+//! magic("STSP", 4) + version(4)
+//! record*: len(4) + crc32(4) + MessagePack(Vec<StoredValue>, newest first)
+//! ```
+//!
+//! Each eviction pass writes one run per shard, with one record per chain
+//! it evicts from. A run is deleted once none of its records are referenced. Run
+//! files are scratch space: the WAL and snapshots remain the source of
+//! truth, and the spill directory is cleared when the store is created.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use rustc_hash::FxHashMap;
+use strata_core::{StrataError, StrataResult};
+
+use crate::stored_value::StoredValue;
+
+/// Magic bytes at the start of every run file.
+pub const SPILL_RUN_MAGIC: &[u8; 4] = b"STSP";
+
+/// Current run file format version.
+pub const SPILL_RUN_VERSION: u32 = 1;
+
+const RUN_HEADER_SIZE: u64 = 8;
+const RECORD_HEADER_SIZE: usize = 8;
+
+/// Configuration of the spill tier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpillConfig {
+    /// Directory for run files (created if missing, cleared on open)
+    pub dir: PathBuf,
+    /// Bytes of values kept in memory before versions are spilled
+    pub memory_budget: usize,
+}
+
+/// Counters describing the spill tier.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpillStats {
+    /// Configured memory budget in bytes
+    pub memory_budget: usize,
+    /// Approximate bytes of values held in memory
+    pub resident_bytes: usize,
+    /// Bytes of live records in run files
+    pub spilled_bytes: u64,
+    /// Run files on disk
+    pub runs: usize,
+    /// Eviction passes run
+    pub evictions: u64,
+    /// Spilled records read back from disk
+    pub page_ins: u64,
+}
+
+/// Location of a spilled chain tail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SpillRef {
+    run: u64,
+    offset: u64,
+    len: u32,
+}
+
+struct Run {
+    file: Mutex<File>,
+    path: PathBuf,
+    /// Records still referenced by a chain
+    live: AtomicUsize,
+}
+
+/// Run files and memory accounting shared by a store's shards.
+pub(crate) struct SpillTier {
+    dir: PathBuf,
+    budget: usize,
+    runs: RwLock<FxHashMap<u64, Arc<Run>>>,
+    next_run: AtomicU64,
+    resident: AtomicUsize,
+    spilled: AtomicU64,
+    /// Advanced by each eviction pass; chains remember when they were used
+    epoch: AtomicU64,
+    evicting: AtomicBool,
+    evictions: AtomicU64,
+    page_ins: AtomicU64,
+}
+
+impl SpillTier {
+    /// Create the tier, clearing run files left by a previous process.
+    pub(crate) fn open(config: SpillConfig) -> io::Result<Self> {
+        std::fs::create_dir_all(&config.dir)?;
+        for entry in std::fs::read_dir(&config.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "spill") {
+                std::fs::remove_file(path)?;
+            }
+        }
+        Ok(SpillTier {
+            dir: config.dir,
+            budget: config.memory_budget,
+            runs: RwLock::new(FxHashMap::default()),
+            next_run: AtomicU64::new(1),
+            resident: AtomicUsize::new(0),
+            spilled: AtomicU64::new(0),
+            epoch: AtomicU64::new(1),
+            evicting: AtomicBool::new(false),
+            evictions: AtomicU64::new(0),
+            page_ins: AtomicU64::new(0),
+        })
+    }
+
+    pub(crate) fn budget(&self) -> usize {
+        self.budget
+    }
+
+    pub(crate) fn epoch(&self) -> u64 {
+        self.epoch.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn resident(&self) -> usize {
+        self.resident.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn add_resident(&self, bytes: usize) {
+        self.resident.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn sub_resident(&self, bytes: usize) {
+        // Saturate: accounting is approximate and must never wrap
+        let _ = self
+            .resident
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |r| {
+                Some(r.saturating_sub(bytes))
+            });
+    }
+
+    pub(crate) fn over_budget(&self) -> bool {
+        self.resident() > self.budget
+    }
+
+    /// Claim the right to run an eviction pass; `None` if one is running.
+    ///
+    /// Returns the epoch before the pass: chains last used before it are cold.
+    pub(crate) fn begin_eviction(&self) -> Option<u64> {
+        if self.evicting.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+        self.evictions.fetch_add(1, Ordering::Relaxed);
+        Some(self.epoch.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub(crate) fn end_eviction(&self) {
+        self.evicting.store(false, Ordering::Release);
+    }
+
+    /// Write `tails` (each newest first) to a new run file, in order.
+    pub(crate) fn write_run(&self, tails: &[Vec<StoredValue>]) -> io::Result<Vec<SpillRef>> {
+        let run = self.next_run.fetch_add(1, Ordering::Relaxed);
+        let path = self.dir.join(format!("run-{:06}.spill", run));
+
+        let mut buf = Vec::new();
+        buf.extend_from_slice(SPILL_RUN_MAGIC);
+        buf.extend_from_slice(&SPILL_RUN_VERSION.to_le_bytes());
+        let mut refs = Vec::with_capacity(tails.len());
+        for tail in tails {
+            let payload =
+                rmp_serde::to_vec(tail).map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            refs.push(SpillRef {
+                run,
+                offset: buf.len() as u64,
+                len: payload.len() as u32,
+            });
+            buf.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            buf.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+            buf.extend_from_slice(&payload);
+        }
+
+        let mut file = File::options()
+            .create_new(true)
+            .read(true)
+            .write(true)
+            .open(&path)?;
+        file.write_all(&buf)?;
+
+        self.spilled
+            .fetch_add(buf.len() as u64 - RUN_HEADER_SIZE, Ordering::Relaxed);
+        self.runs.write().unwrap().insert(
+            run,
+            Arc::new(Run {
+                file: Mutex::new(file),
+                path,
+                live: AtomicUsize::new(refs.len()),
+            }),
+        );
+        Ok(refs)
+    }
+
+    /// Read a spilled chain tail, newest first.
+    ///
+    /// Fails with `StrataError::Corruption` if the record does not match its
+    /// checksum, and with a storage error if the run file cannot be read.
+    pub(crate) fn load(&self, spill: &SpillRef) -> StrataResult<Vec<StoredValue>> {
+        self.try_load(spill).map_err(|e| {
+            let message = format!(
+                "failed to read spilled versions from run {} at offset {}",
+                spill.run, spill.offset
+            );
+            if e.kind() == io::ErrorKind::InvalidData {
+                StrataError::corruption(format!("{}: {}", message, e))
+            } else {
+                StrataError::storage_with_source(message, e)
+            }
+        })
+    }
+
+    fn try_load(&self, spill: &SpillRef) -> io::Result<Vec<StoredValue>> {
+        let run = self
+            .runs
+            .read()
+            .unwrap()
+            .get(&spill.run)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "run file was removed"))?;
+
+        let mut buf = vec![0u8; RECORD_HEADER_SIZE + spill.len as usize];
+        {
+            let mut file = run.file.lock().unwrap();
+            file.seek(SeekFrom::Start(spill.offset))?;
+            file.read_exact(&mut buf)?;
+        }
+        self.page_ins.fetch_add(1, Ordering::Relaxed);
+
+        let len = u32::from_le_bytes(buf[0..4].try_into().unwrap());
+        let crc = u32::from_le_bytes(buf[4..8].try_into().unwrap());
+        let payload = &buf[RECORD_HEADER_SIZE..];
+        if len != spill.len || crc32fast::hash(payload) != crc {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "record checksum mismatch",
+            ));
+        }
+        rmp_serde::from_slice(payload).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Drop a reference to a spilled tail, deleting its run once unused.
+    pub(crate) fn release(&self, spill: &SpillRef) {
+        let mut runs = self.runs.write().unwrap();
+        let Some(run) = runs.get(&spill.run) else {
+            return;
+        };
+        self.spilled.fetch_sub(
+            RECORD_HEADER_SIZE as u64 + spill.len as u64,
+            Ordering::Relaxed,
+        );
+        if run.live.fetch_sub(1, Ordering::AcqRel) == 1 {
+            let run = runs.remove(&spill.run).expect("run present");
+            let _ = std::fs::remove_file(&run.path);
+        }
+    }
+
+    pub(crate) fn stats(&self) -> SpillStats {
+        SpillStats {
+            memory_budget: self.budget,
+            resident_bytes: self.resident(),
+            spilled_bytes: self.spilled.load(Ordering::Relaxed),
+            runs: self.runs.read().unwrap().len(),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            page_ins: self.page_ins.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Drop for SpillTier {
+    fn drop(&mut self) {
+        if let Ok(runs) = self.runs.get_mut() {
+            for run in runs.values() {
+                let _ = std::fs::remove_file(&run.path);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for SpillTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpillTier")
+            .field("dir", &self.dir)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strata_core::{Value, Version};
+
+    fn tier(dir: &tempfile::TempDir) -> SpillTier {
+        SpillTier::open(SpillConfig {
+            dir: dir.path().to_path_buf(),
+            memory_budget: 1024,
+        })
+        .unwrap()
+    }
+
+    fn tail(versions: std::ops::RangeInclusive<u64>) -> Vec<StoredValue> {
+        versions
+            .rev()
+            .map(|v| StoredValue::new(Value::Int(v as i64), Version::txn(v), None))
+            .collect()
+    }
+
+    #[test]
+    fn test_run_roundtrip_and_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let spill = tier(&dir);
+        let tails = vec![tail(1..=3), tail(4..=4)];
+        let refs = spill.write_run(&tails).unwrap();
+
+        assert_eq!(spill.load(&refs[0]).unwrap(), tails[0]);
+        assert_eq!(spill.load(&refs[1]).unwrap(), tails[1]);
+        assert_eq!(spill.stats().runs, 1);
+        assert_eq!(spill.stats().page_ins, 2);
+
+        spill.release(&refs[0]);
+        assert_eq!(spill.stats().runs, 1);
+        spill.release(&refs[1]);
+        assert_eq!(spill.stats().runs, 0);
+        assert_eq!(spill.stats().spilled_bytes, 0);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_open_clears_stale_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("run-000001.spill"), b"stale").unwrap();
+        std::fs::write(dir.path().join("keep.txt"), b"other").unwrap();
+
+        let spill = tier(&dir);
+        assert_eq!(spill.stats().runs, 0);
+        assert!(!dir.path().join("run-000001.spill").exists());
+        assert!(dir.path().join("keep.txt").exists());
+
+        // A new run may reuse the stale file's name
+        let tails = [tail(1..=1)];
+        let refs = spill.write_run(&tails).unwrap();
+        assert_eq!(spill.load(&refs[0]).unwrap(), tails[0]);
+    }
+
+    #[test]
+    fn test_load_detects_corrupt_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let spill = tier(&dir);
+        let refs = spill.write_run(&[tail(1..=2)]).unwrap();
+
+        let path = dir.path().join("run-000001.spill");
+        let mut bytes = std::fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        std::fs::write(&path, bytes).unwrap();
+
+        let err = spill.load(&refs[0]).unwrap_err();
+        assert!(matches!(err, StrataError::Corruption { .. }), "{err}");
+    }
+
+    #[test]
+    fn test_load_reports_unreadable_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let spill = tier(&dir);
+        let refs = spill.write_run(&[tail(1..=2)]).unwrap();
+
+        // Truncate the run so the record can no longer be read in full
+        let path = dir.path().join("run-000001.spill");
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 4]).unwrap();
+
+        let err = spill.load(&refs[0]).unwrap_err();
+        assert!(matches!(err, StrataError::Storage { .. }), "{err}");
+    }
+}
@@ -7,6 +7,7 @@
 
 use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
 use strata_core::{Timestamp, Value, Version, VersionedValue};
 
 /// A stored value with optional TTL
@@ -14,7 +15,7 @@ use strata_core::{Timestamp, Value, Version, VersionedValue};
 /// Wraps `VersionedValue` with TTL metadata for the storage layer.
 /// This separation keeps TTL as a storage concern, not part of the
 /// contract types.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StoredValue {
     /// The versioned value (value + version + timestamp)
     inner: VersionedValue,
@@ -78,6 +79,15 @@ impl StoredValue {
         }
     }
 
+    /// Create a tombstone entry with an explicit timestamp
+    pub fn tombstone_at(version: Version, timestamp: Timestamp) -> Self {
+        StoredValue {
+            inner: VersionedValue::with_timestamp(Value::Null, version, timestamp),
+            ttl: None,
+            is_tombstone: true,
+        }
+    }
+
     /// Check whether this entry is a tombstone (explicit deletion marker)
     #[inline]
     pub fn is_tombstone(&self) -> bool {
@@ -122,8 +132,15 @@ impl StoredValue {
 
     /// Check if this value has expired
     pub fn is_expired(&self) -> bool {
+        self.is_expired_at(Timestamp::now())
+    }
+
+    /// Check if this value had expired at `now`
+    ///
+    /// Snapshot and time-travel reads evaluate expiry at their own
+    /// timestamp rather than the wall clock, so they stay repeatable.
+    pub fn is_expired_at(&self, now: Timestamp) -> bool {
         if let Some(ttl) = self.ttl {
-            let now = Timestamp::now();
             if let Some(age) = now.duration_since(self.inner.timestamp) {
                 return age >= ttl;
             }
@@ -137,6 +154,31 @@ impl StoredValue {
     pub fn expiry_timestamp(&self) -> Option<Timestamp> {
         self.ttl.map(|ttl| self.inner.timestamp.saturating_add(ttl))
     }
+
+    /// Approximate bytes of memory held by this entry, including the heap
+    /// data of its value
+    ///
+    /// Used to account stores against a memory budget; it does not include
+    /// allocator overhead or collection slack.
+    pub fn approx_size(&self) -> usize {
+        std::mem::size_of::<StoredValue>() + value_heap_size(&self.inner.value)
+    }
+}
+
+fn value_heap_size(value: &Value) -> usize {
+    match value {
+        Value::String(s) => s.len(),
+        Value::Bytes(b) => b.len(),
+        Value::Array(items) => items
+            .iter()
+            .map(|v| std::mem::size_of::<Value>() + value_heap_size(v))
+            .sum(),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(k, v)| k.len() + std::mem::size_of::<Value>() + value_heap_size(v))
+            .sum(),
+        Value::Null | Value::Bool(_) | Value::Int(_) | Value::Float(_) => 0,
+    }
 }
 
 impl From<StoredValue> for VersionedValue {
@@ -225,4 +267,16 @@ mod tests {
         assert_eq!(sv.version(), Version::Sequence(10));
         assert!(sv.ttl().is_none());
     }
+
+    #[test]
+    fn test_stored_value_approx_size_counts_heap_data() {
+        let small = StoredValue::new(Value::Int(1), Version::txn(1), None);
+        let large = StoredValue::new(
+            Value::Array(vec![Value::String("x".repeat(1000))]),
+            Version::txn(1),
+            None,
+        );
+        assert_eq!(small.approx_size(), std::mem::size_of::<StoredValue>());
+        assert!(large.approx_size() > small.approx_size() + 1000);
+    }
 }
@@ -63,6 +63,11 @@ impl TTLIndex {
             .collect()
     }
 
+    /// The earliest expiry timestamp in the index, if any
+    pub fn next_expiry(&self) -> Option<Timestamp> {
+        self.index.keys().next().copied()
+    }
+
     /// Remove all entries for keys that have expired before the given timestamp
     ///
     /// This is used after cleanup to remove stale index entries.
@@ -230,5 +235,9 @@ mod tests {
         // Find expired at 350 - should get keys at 100, 200, 300
         let expired = index.find_expired(Timestamp::from_micros(350));
         assert_eq!(expired.len(), 3);
+
+        assert_eq!(index.next_expiry(), Some(Timestamp::from_micros(100)));
+        index.remove_expired(Timestamp::from_micros(350));
+        assert_eq!(index.next_expiry(), Some(Timestamp::from_micros(400)));
     }
 }
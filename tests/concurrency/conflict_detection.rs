@@ -5,12 +5,14 @@
 //! - CAS conflicts
 //! - JSON document conflicts
 //! - JSON path conflicts
+//! - Phantom conflicts (serializable isolation)
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use strata_concurrency::transaction::{CASOperation, TransactionContext};
+use strata_concurrency::transaction::{CASOperation, IsolationLevel, TransactionContext};
 use strata_concurrency::validation::{
-    validate_cas_set, validate_read_set, validate_transaction, ConflictType,
+    validate_cas_set, validate_read_set, validate_scan_predicates, validate_transaction,
+    ConflictType,
 };
 use strata_core::traits::Storage;
 use strata_core::types::{Key, Namespace};
@@ -407,3 +409,131 @@ fn large_read_set_with_one_conflict() {
     assert!(!result.is_valid());
     assert_eq!(result.conflict_count(), 1);
 }
+
+// ============================================================================
+// Phantom Conflicts (Serializable)
+// ============================================================================
+
+/// Two transactions each check a budget by summing `alloc:*` and insert a new
+/// allocation that fits on its own. Together they overspend.
+fn allocate_within_budget(
+    store: &Arc<ShardedStore>,
+    branch_id: BranchId,
+    txn_id: u64,
+    isolation: IsolationLevel,
+    name: &str,
+) -> TransactionContext {
+    let mut txn =
+        TransactionContext::with_snapshot(txn_id, branch_id, Box::new(store.create_snapshot()));
+    txn.set_isolation(isolation);
+    let allocated: i64 = txn
+        .scan_prefix(&create_test_key(branch_id, "alloc:"))
+        .unwrap()
+        .into_iter()
+        .map(|(_, v)| match v {
+            Value::Int(n) => n,
+            _ => 0,
+        })
+        .sum();
+    assert!(allocated + 60 <= 100);
+    txn.put(create_test_key(branch_id, name), Value::Int(60))
+        .unwrap();
+    txn
+}
+
+#[test]
+fn snapshot_isolation_allows_phantom_write_skew() {
+    let store = Arc::new(ShardedStore::new());
+    let branch_id = BranchId::new();
+
+    let mut t1 = allocate_within_budget(&store, branch_id, 1, IsolationLevel::Snapshot, "alloc:a");
+    let mut t2 = allocate_within_budget(&store, branch_id, 2, IsolationLevel::Snapshot, "alloc:b");
+
+    t1.commit(&*store).unwrap();
+    t1.apply_writes(&*store, 1).unwrap();
+    assert!(t2.commit(&*store).is_ok());
+}
+
+#[test]
+fn serializable_detects_phantom_insert() {
+    let store = Arc::new(ShardedStore::new());
+    let branch_id = BranchId::new();
+
+    let mut t1 = allocate_within_budget(
+        &store,
+        branch_id,
+        1,
+        IsolationLevel::Serializable,
+        "alloc:a",
+    );
+    let mut t2 = allocate_within_budget(
+        &store,
+        branch_id,
+        2,
+        IsolationLevel::Serializable,
+        "alloc:b",
+    );
+
+    t1.commit(&*store).unwrap();
+    t1.apply_writes(&*store, 1).unwrap();
+
+    let result = validate_transaction(&t2, &*store).unwrap();
+    assert_eq!(result.conflict_count(), 1);
+    match &result.conflicts[0] {
+        ConflictType::PhantomConflict { key, prefix, .. } => {
+            assert_eq!(*key, create_test_key(branch_id, "alloc:a"));
+            assert_eq!(*prefix, create_test_key(branch_id, "alloc:"));
+        }
+        other => panic!("Expected PhantomConflict, got {:?}", other),
+    }
+
+    let err = t2.commit(&*store).unwrap_err();
+    assert!(err.to_string().contains("\"alloc:a\""), "{}", err);
+}
+
+#[test]
+fn serializable_ignores_inserts_outside_scanned_prefix() {
+    let store = Arc::new(ShardedStore::new());
+    let branch_id = BranchId::new();
+
+    let mut txn = allocate_within_budget(
+        &store,
+        branch_id,
+        1,
+        IsolationLevel::Serializable,
+        "alloc:a",
+    );
+    Storage::put(
+        &*store,
+        create_test_key(branch_id, "other"),
+        Value::Int(1),
+        None,
+    )
+    .unwrap();
+
+    assert!(txn.commit(&*store).is_ok());
+}
+
+#[test]
+fn phantom_validation_accepts_keys_already_read() {
+    let store = Arc::new(ShardedStore::new());
+    let branch_id = BranchId::new();
+    let prefix = create_test_key(branch_id, "alloc:");
+    let key = create_test_key(branch_id, "alloc:a");
+
+    Storage::put(&*store, key.clone(), Value::Int(10), None).unwrap();
+    let v = Storage::get(&*store, &key)
+        .unwrap()
+        .unwrap()
+        .version
+        .as_u64();
+
+    let mut read_set = HashMap::new();
+    read_set.insert(key, v);
+    let result =
+        validate_scan_predicates(std::slice::from_ref(&prefix), &read_set, &*store).unwrap();
+    assert!(result.is_valid());
+
+    let result = validate_scan_predicates(&[prefix], &HashMap::new(), &*store).unwrap();
+    assert_eq!(result.conflict_count(), 1);
+}
@@ -40,6 +40,8 @@ fn concurrent_sessions_isolated_views() {
             space: None,
             key: "isolated_key".into(),
             value: Value::String("session_a_value".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -99,6 +101,8 @@ fn concurrent_session_increments() {
             space: None,
             key: "counter".into(),
             value: Value::Int(0),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -151,6 +155,8 @@ fn concurrent_session_increments() {
                             space: None,
                             key: "counter".into(),
                             value: Value::Int(current + 1),
+                            ttl_ms: None,
+                            expires_at: None,
                         })
                         .is_err()
                     {
@@ -221,6 +227,8 @@ fn session_drop_rolls_back_transaction() {
                 space: None,
                 key: "drop_test".into(),
                 value: Value::String("should_not_persist".into()),
+                ttl_ms: None,
+                expires_at: None,
             })
             .unwrap();
         // Session dropped here - transaction should be rolled back
@@ -262,6 +270,8 @@ fn session_drop_after_commit_preserves_data() {
                 space: None,
                 key: "commit_drop_test".into(),
                 value: Value::String("should_persist".into()),
+                ttl_ms: None,
+                expires_at: None,
             })
             .unwrap();
         session.execute(Command::TxnCommit).unwrap();
@@ -350,6 +360,8 @@ fn new_transaction_after_commit() {
             space: None,
             key: "txn1".into(),
             value: Value::Int(1),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
     session.execute(Command::TxnCommit).unwrap();
@@ -367,6 +379,8 @@ fn new_transaction_after_commit() {
             space: None,
             key: "txn2".into(),
             value: Value::Int(2),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
     session.execute(Command::TxnCommit).unwrap();
@@ -417,6 +431,8 @@ fn new_transaction_after_rollback() {
             space: None,
             key: "rolled_back".into(),
             value: Value::Int(1),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
     session.execute(Command::TxnRollback).unwrap();
@@ -434,6 +450,8 @@ fn new_transaction_after_rollback() {
             space: None,
             key: "committed".into(),
             value: Value::Int(2),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
     session.execute(Command::TxnCommit).unwrap();
@@ -481,6 +499,8 @@ fn empty_string_preserved() {
             space: None,
             key: "empty".into(),
             value: Value::String("".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -514,6 +534,8 @@ fn null_value_is_storable() {
             space: None,
             key: "null_key".into(),
             value: Value::Int(42),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -538,6 +560,8 @@ fn null_value_is_storable() {
             space: None,
             key: "null_key".into(),
             value: Value::Null,
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -592,6 +616,8 @@ fn integer_boundaries_preserved() {
                 space: None,
                 key: key.into(),
                 value: Value::Int(value),
+                ttl_ms: None,
+                expires_at: None,
             })
             .unwrap();
 
@@ -631,6 +657,8 @@ fn float_special_values() {
             space: None,
             key: "pi".into(),
             value: Value::Float(std::f64::consts::PI),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -660,6 +688,8 @@ fn float_special_values() {
             space: None,
             key: "inf".into(),
             value: Value::Float(f64::INFINITY),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -689,6 +719,8 @@ fn float_special_values() {
             space: None,
             key: "nan".into(),
             value: Value::Float(f64::NAN),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -736,6 +768,8 @@ fn large_nested_object() {
             space: None,
             key: "large_object".into(),
             value: Value::Object(outer.clone()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -774,6 +808,8 @@ fn concurrent_reads_consistent() {
             space: None,
             key: "concurrent_read".into(),
             value: Value::Int(42),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -843,6 +879,8 @@ fn concurrent_writes_different_keys() {
                             space: None,
                             key,
                             value: Value::Int((thread_id * 1000 + i) as i64),
+                            ttl_ms: None,
+                            expires_at: None,
                         })
                         .unwrap();
                 }
@@ -899,6 +937,8 @@ fn kv_put_atomic() {
             space: None,
             key: "atomic_test".into(),
             value: Value::Int(1),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -923,6 +963,8 @@ fn kv_put_atomic() {
                             space: None,
                             key: "atomic_test".into(),
                             value: Value::Int(thread_id as i64),
+                            ttl_ms: None,
+                            expires_at: None,
                         })
                         .unwrap();
                 }
@@ -998,6 +1040,8 @@ fn executor_branch_isolation() {
             space: None,
             key: "shared_key".into(),
             value: Value::String("value_in_branch_a".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -1007,6 +1051,8 @@ fn executor_branch_isolation() {
             space: None,
             key: "shared_key".into(),
             value: Value::String("value_in_branch_b".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -1105,6 +1151,8 @@ fn error_recovery() {
             space: None,
             key: "recovery_test".into(),
             value: Value::Int(123),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -45,6 +45,8 @@ fn branch_data_is_isolated() {
             space: None,
             key: "secret".into(),
             value: Value::String("branch_a_secret".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -54,6 +56,8 @@ fn branch_data_is_isolated() {
             space: None,
             cell: "state".into(),
             value: Value::Int(42),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -129,6 +133,8 @@ fn branch_delete_removes_all_data() {
             space: None,
             key: "key1".into(),
             value: Value::String("value1".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -138,6 +144,8 @@ fn branch_delete_removes_all_data() {
             space: None,
             key: "key2".into(),
             value: Value::Int(123),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -147,6 +155,8 @@ fn branch_delete_removes_all_data() {
             space: None,
             cell: "cell1".into(),
             value: Value::Bool(true),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -226,6 +236,8 @@ fn branch_delete_cleans_up_data() {
             space: None,
             key: "persistent_key".into(),
             value: Value::String("should_be_deleted".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -276,6 +288,8 @@ fn default_branch_always_works() {
             space: None,
             key: "default_key".into(),
             value: Value::String("default_value".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
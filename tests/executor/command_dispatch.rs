@@ -70,6 +70,8 @@ fn kv_put_returns_version() {
             space: None,
             key: "test_key".into(),
             value: Value::String("test_value".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -90,6 +92,8 @@ fn kv_get_returns_maybe_versioned() {
             space: None,
             key: "k".into(),
             value: Value::Int(42),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -141,6 +145,8 @@ fn kv_delete_returns_bool() {
             space: None,
             key: "k".into(),
             value: Value::Int(1),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -228,6 +234,8 @@ fn state_set_read_cycle() {
             space: None,
             cell: "status".into(),
             value: Value::String("active".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -564,6 +572,8 @@ fn commands_with_none_branch_use_default() {
             space: None,
             key: "default_test".into(),
             value: Value::String("value".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -621,6 +631,8 @@ fn different_branches_are_isolated() {
             space: None,
             key: "shared_key".into(),
             value: Value::String("branch_a_value".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -631,6 +643,8 @@ fn different_branches_are_isolated() {
             space: None,
             key: "shared_key".into(),
             value: Value::String("branch_b_value".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -17,6 +17,8 @@ fn kv_put_roundtrip() {
         space: None,
         key: "test_key".into(),
         value: Value::String("test_value".into()),
+        ttl_ms: None,
+        expires_at: None,
     };
 
     let json = serde_json::to_string(&cmd).unwrap();
@@ -32,6 +34,8 @@ fn kv_put_with_branch_roundtrip() {
         space: None,
         key: "key".into(),
         value: Value::Int(42),
+        ttl_ms: None,
+        expires_at: None,
     };
 
     let json = serde_json::to_string(&cmd).unwrap();
@@ -134,6 +138,8 @@ fn kv_put_json_format() {
         space: None,
         key: "k".into(),
         value: Value::Int(42),
+        ttl_ms: None,
+        expires_at: None,
     };
 
     let json = serde_json::to_value(&cmd).unwrap();
@@ -150,6 +156,8 @@ fn branch_field_omitted_when_none() {
         space: None,
         key: "k".into(),
         value: Value::Int(1),
+        ttl_ms: None,
+        expires_at: None,
     };
 
     let json = serde_json::to_string(&cmd).unwrap();
@@ -165,6 +173,8 @@ fn branch_field_present_when_some() {
         space: None,
         key: "k".into(),
         value: Value::Int(1),
+        ttl_ms: None,
+        expires_at: None,
     };
 
     let json = serde_json::to_string(&cmd).unwrap();
@@ -191,10 +201,14 @@ fn deserialize_kv_put_minimal() {
             space: _,
             key,
             value,
+            ttl_ms,
+            expires_at,
         } => {
             assert!(branch.is_none());
             assert_eq!(key, "k");
             assert_eq!(value, Value::Int(42));
+            assert!(ttl_ms.is_none());
+            assert!(expires_at.is_none());
         }
         _ => panic!("Expected KvPut"),
     }
@@ -213,6 +227,7 @@ fn deserialize_kv_put_with_branch() {
             space: _,
             key,
             value,
+            ..
         } => {
             assert_eq!(
                 branch.unwrap().as_str(),
@@ -360,6 +375,8 @@ fn value_types_roundtrip() {
         space: None,
         key: "k".into(),
         value: Value::String("hello".into()),
+        ttl_ms: None,
+        expires_at: None,
     };
     let json = serde_json::to_string(&cmd).unwrap();
     let parsed: Command = serde_json::from_str(&json).unwrap();
@@ -371,6 +388,8 @@ fn value_types_roundtrip() {
         space: None,
         key: "k".into(),
         value: Value::Int(-42),
+        ttl_ms: None,
+        expires_at: None,
     };
     let json = serde_json::to_string(&cmd).unwrap();
     let parsed: Command = serde_json::from_str(&json).unwrap();
@@ -382,6 +401,8 @@ fn value_types_roundtrip() {
         space: None,
         key: "k".into(),
         value: Value::Float(3.14),
+        ttl_ms: None,
+        expires_at: None,
     };
     let json = serde_json::to_string(&cmd).unwrap();
     let parsed: Command = serde_json::from_str(&json).unwrap();
@@ -393,6 +414,8 @@ fn value_types_roundtrip() {
         space: None,
         key: "k".into(),
         value: Value::Bool(true),
+        ttl_ms: None,
+        expires_at: None,
     };
     let json = serde_json::to_string(&cmd).unwrap();
     let parsed: Command = serde_json::from_str(&json).unwrap();
@@ -404,6 +427,8 @@ fn value_types_roundtrip() {
         space: None,
         key: "k".into(),
         value: Value::Null,
+        ttl_ms: None,
+        expires_at: None,
     };
     let json = serde_json::to_string(&cmd).unwrap();
     let parsed: Command = serde_json::from_str(&json).unwrap();
@@ -415,6 +440,8 @@ fn value_types_roundtrip() {
         space: None,
         key: "k".into(),
         value: Value::Array(vec![Value::Int(1), Value::Int(2)]),
+        ttl_ms: None,
+        expires_at: None,
     };
     let json = serde_json::to_string(&cmd).unwrap();
     let parsed: Command = serde_json::from_str(&json).unwrap();
@@ -430,6 +457,8 @@ fn value_types_roundtrip() {
                 .into_iter()
                 .collect(),
         ),
+        ttl_ms: None,
+        expires_at: None,
     };
     let json = serde_json::to_string(&cmd).unwrap();
     let parsed: Command = serde_json::from_str(&json).unwrap();
@@ -133,6 +133,8 @@ fn read_your_writes_kv() {
             space: None,
             key: "txn_key".into(),
             value: Value::Int(42),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -177,6 +179,8 @@ fn read_your_writes_state() {
             space: None,
             cell: "cell".into(),
             value: Value::String("value".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -261,6 +265,8 @@ fn rollback_discards_kv_writes() {
             space: None,
             key: "rollback_test".into(),
             value: Value::Int(100),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -303,6 +309,8 @@ fn rollback_discards_state_writes() {
             space: None,
             cell: "rollback_cell".into(),
             value: Value::String("uncommitted".into()),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -347,6 +355,8 @@ fn commit_makes_kv_writes_visible() {
             space: None,
             key: "commit_test".into(),
             value: Value::Int(999),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -541,6 +551,8 @@ fn multiple_kv_operations_in_transaction() {
                 space: None,
                 key: format!("key_{}", i),
                 value: Value::Int(i),
+                ttl_ms: None,
+                expires_at: None,
             })
             .unwrap();
     }
@@ -608,6 +620,8 @@ fn cross_primitive_transaction() {
             space: None,
             key: "kv_key".into(),
             value: Value::Int(1),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -618,6 +632,8 @@ fn cross_primitive_transaction() {
             space: None,
             cell: "state_cell".into(),
             value: Value::Int(2),
+            ttl_ms: None,
+            expires_at: None,
         })
         .unwrap();
 
@@ -698,6 +714,8 @@ fn session_drop_cleans_up_transaction() {
                 space: None,
                 key: "drop_test".into(),
                 value: Value::Int(1),
+                ttl_ms: None,
+                expires_at: None,
             })
             .unwrap();
 
@@ -233,7 +233,7 @@ fn list_branch_keys() {
         Storage::put(&store, key, Value::Int(i), None).unwrap();
     }
 
-    let keys = store.list_branch(&branch_id);
+    let keys = store.list_branch(&branch_id).unwrap();
     assert_eq!(keys.len(), 5);
 }
 